@@ -19,6 +19,10 @@ fn main() {
         x11_platform: { all(feature = "x11", free_unix, not(redox)) },
         wayland_platform: { all(feature = "wayland", free_unix, not(redox)) },
         orbital_platform: { redox },
+
+        // The in-memory backend, available next to the native ones. It blocks the thread while
+        // waiting for events, which the Web doesn't allow.
+        headless_platform: { all(feature = "headless", not(web_platform)) },
     }
 
     // Winit defined cfgs.
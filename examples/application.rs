@@ -432,6 +432,12 @@ impl ApplicationHandler for Application {
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                 info!("Window={window_id:?} changed scale to {scale_factor}");
             },
+            WindowEvent::SurfaceTransformChanged(transform) => {
+                info!("Window={window_id:?} changed surface transform to {transform:?}");
+            },
+            WindowEvent::SafeAreaChanged(insets) => {
+                info!("Window={window_id:?} changed safe area to {insets:?}");
+            },
             WindowEvent::ThemeChanged(theme) => {
                 info!("Theme changed to {theme:?}");
                 window.set_draw_theme(theme);
@@ -452,6 +458,12 @@ impl ApplicationHandler for Application {
                 window.modifiers = modifiers.state();
                 info!("Modifiers changed to {:?}", window.modifiers);
             },
+            WindowEvent::KeyboardLayoutChanged => {
+                info!("Keyboard layout changed to {:?}", window.window.keyboard_layout_name());
+            },
+            WindowEvent::KeyboardLayoutDirectionChanged(direction) => {
+                info!("Keyboard layout direction changed to {direction:?}");
+            },
             WindowEvent::MouseWheel { delta, .. } => match delta {
                 MouseScrollDelta::LineDelta(x, y) => {
                     info!("Mouse wheel Line Delta: ({x},{y})");
@@ -540,6 +552,9 @@ impl ApplicationHandler for Application {
             WindowEvent::DoubleTapGesture { .. } => {
                 info!("Smart zoom");
             },
+            WindowEvent::PointerLock { locked } => {
+                info!("Pointer lock {}", if locked { "engaged" } else { "released" });
+            },
             WindowEvent::TouchpadPressure { .. }
             | WindowEvent::DragLeft { .. }
             | WindowEvent::KeyboardInput { .. }
@@ -1000,7 +1015,7 @@ impl<T: Eq> Binding<T> {
     }
 
     fn is_triggered_by(&self, trigger: &T, mods: &ModifiersState) -> bool {
-        &self.trigger == trigger && &self.mods == mods
+        &self.trigger == trigger && mods.matches_exactly(self.mods)
     }
 }
 
@@ -88,8 +88,7 @@ impl ApplicationHandler for ControlFlowDemo {
                 event: KeyEvent { logical_key: key, state: ElementState::Pressed, .. },
                 ..
             } => match key.as_ref() {
-                // WARNING: Consider using `key_without_modifiers()` if available on your platform.
-                // See the `key_binding` example
+                // WARNING: Consider using `KeyEvent::key_without_modifiers` for key bindings.
                 Key::Character("1") => {
                     self.mode = Mode::Wait;
                     warn!("mode: {:?}", self.mode);
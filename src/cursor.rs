@@ -1,10 +1,15 @@
 use core::fmt;
 use std::error::Error;
 use std::hash::{Hash, Hasher};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+#[cfg(not(web_platform))]
+use std::time::{Duration, Instant};
 
 use cursor_icon::CursorIcon;
+#[cfg(web_platform)]
+use web_time::{Duration, Instant};
 
+use crate::dpi::PhysicalPosition;
 use crate::platform_impl::{PlatformCustomCursor, PlatformCustomCursorSource};
 
 /// The maximum width and height for a cursor when using [`CustomCursor::from_rgba`].
@@ -264,3 +269,216 @@ impl NoCustomCursor {
         Ok(Self)
     }
 }
+
+/// The cursor last passed to [`Window::set_cursor()`][crate::window::Window::set_cursor], as
+/// returned by [`Window::current_cursor()`][crate::window::Window::current_cursor].
+#[derive(Debug, Default)]
+pub(crate) struct CurrentCursor(Mutex<Cursor>);
+
+impl CurrentCursor {
+    pub(crate) fn set(&self, cursor: &Cursor) {
+        *self.0.lock().unwrap() = cursor.clone();
+    }
+
+    pub(crate) fn get(&self) -> Cursor {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// State of a cursor warp started with
+/// [`Window::animate_cursor_to()`][crate::window::Window::animate_cursor_to].
+///
+/// Platforms that drive the animation from their event loop sample this on every tick, and cancel
+/// it once they observe a cursor position that the animation couldn't have produced.
+#[cfg_attr(not(any(x11_platform, windows_platform, macos_platform)), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CursorAnimation {
+    from: PhysicalPosition<i32>,
+    to: PhysicalPosition<i32>,
+    start: Instant,
+    duration: Duration,
+    /// Progress of the last warp, in the `0.0..=1.0` range.
+    warped: f64,
+}
+
+#[cfg_attr(not(any(x11_platform, windows_platform, macos_platform)), allow(dead_code))]
+impl CursorAnimation {
+    /// The interval between two consecutive warps.
+    pub(crate) const FRAME_INTERVAL: Duration = Duration::from_millis(8);
+
+    /// Distance in pixels a reported position may stray from the warp path before it is
+    /// considered to be user input.
+    const TOLERANCE: f64 = 1.5;
+
+    pub(crate) fn new(
+        from: PhysicalPosition<i32>,
+        to: PhysicalPosition<i32>,
+        start: Instant,
+        duration: Duration,
+    ) -> Self {
+        Self { from, to, start, duration, warped: 0.0 }
+    }
+
+    /// Eased progress of the animation at `now`, in the `0.0..=1.0` range.
+    fn progress(&self, now: Instant) -> f64 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+
+        let t =
+            now.saturating_duration_since(self.start).as_secs_f64() / self.duration.as_secs_f64();
+        let t = t.min(1.0);
+        // Smoothstep, so the cursor accelerates away from its origin and settles on the target.
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    fn lerp(&self, progress: f64) -> (f64, f64) {
+        let x = self.from.x as f64 + (self.to.x - self.from.x) as f64 * progress;
+        let y = self.from.y as f64 + (self.to.y - self.from.y) as f64 * progress;
+        (x, y)
+    }
+
+    /// The position the cursor should be warped to at `now`.
+    pub(crate) fn sample(&self, now: Instant) -> PhysicalPosition<i32> {
+        let (x, y) = self.lerp(self.progress(now));
+        PhysicalPosition::new(x.round() as i32, y.round() as i32)
+    }
+
+    /// Sample the animation at `now` and remember that the cursor was warped there.
+    pub(crate) fn step(&mut self, now: Instant) -> PhysicalPosition<i32> {
+        self.warped = self.progress(now);
+        self.sample(now)
+    }
+
+    /// Whether the animation has reached its target at `now`.
+    pub(crate) fn is_finished(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= self.duration
+    }
+
+    /// Whether the reported cursor `position` was caused by something other than our own warps.
+    ///
+    /// Motion events for earlier warps may still be in flight when the next warp is issued, so
+    /// every position on the path travelled so far is accepted, not only the last one.
+    pub(crate) fn is_user_motion(&self, position: PhysicalPosition<f64>) -> bool {
+        let (end_x, end_y) = self.lerp(self.warped);
+        let (dx, dy) = (end_x - self.from.x as f64, end_y - self.from.y as f64);
+        let (px, py) = (position.x - self.from.x as f64, position.y - self.from.y as f64);
+
+        let length_squared = dx * dx + dy * dy;
+        let t = if length_squared == 0.0 {
+            0.0
+        } else {
+            ((px * dx + py * dy) / length_squared).clamp(0.0, 1.0)
+        };
+
+        let (off_x, off_y) = (px - dx * t, py - dy * t);
+        (off_x * off_x + off_y * off_y).sqrt() > Self::TOLERANCE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation(start: Instant) -> CursorAnimation {
+        CursorAnimation::new(
+            PhysicalPosition::new(0, 0),
+            PhysicalPosition::new(100, 200),
+            start,
+            Duration::from_millis(100),
+        )
+    }
+
+    #[test]
+    fn cursor_animation_sampling() {
+        let start = Instant::now();
+        let animation = animation(start);
+
+        assert_eq!(animation.sample(start), PhysicalPosition::new(0, 0));
+        assert_eq!(
+            animation.sample(start + Duration::from_millis(50)),
+            PhysicalPosition::new(50, 100)
+        );
+        assert_eq!(
+            animation.sample(start + Duration::from_millis(100)),
+            PhysicalPosition::new(100, 200)
+        );
+        assert_eq!(
+            animation.sample(start + Duration::from_secs(1)),
+            PhysicalPosition::new(100, 200)
+        );
+
+        // Eased, so the first quarter of the time covers less than a quarter of the distance.
+        let quarter = animation.sample(start + Duration::from_millis(25));
+        assert!(quarter.x < 25 && quarter.y < 50);
+
+        assert!(!animation.is_finished(start + Duration::from_millis(99)));
+        assert!(animation.is_finished(start + Duration::from_millis(100)));
+
+        let instant = CursorAnimation::new(
+            PhysicalPosition::new(0, 0),
+            PhysicalPosition::new(10, 10),
+            start,
+            Duration::ZERO,
+        );
+        assert_eq!(instant.sample(start), PhysicalPosition::new(10, 10));
+        assert!(instant.is_finished(start));
+    }
+
+    #[test]
+    fn cursor_animation_cancel_on_user_input() {
+        let start = Instant::now();
+        let mut animation = animation(start);
+
+        // Nothing was warped yet, only the origin is ours.
+        assert!(!animation.is_user_motion(PhysicalPosition::new(0.0, 0.0)));
+        assert!(animation.is_user_motion(PhysicalPosition::new(10.0, 20.0)));
+
+        let warped = animation.step(start + Duration::from_millis(50));
+        assert_eq!(warped, PhysicalPosition::new(50, 100));
+
+        // Both the latest warp and stale events from earlier warps are ours.
+        assert!(!animation.is_user_motion(PhysicalPosition::new(50.0, 100.0)));
+        assert!(!animation.is_user_motion(PhysicalPosition::new(10.0, 20.0)));
+        assert!(!animation.is_user_motion(PhysicalPosition::new(25.5, 50.0)));
+
+        // Positions off the path, or past the last warp, come from the user.
+        assert!(animation.is_user_motion(PhysicalPosition::new(60.0, 100.0)));
+        assert!(animation.is_user_motion(PhysicalPosition::new(75.0, 150.0)));
+        assert!(animation.is_user_motion(PhysicalPosition::new(-5.0, 0.0)));
+    }
+
+    #[test]
+    fn current_cursor_tracks_last_set() {
+        let current = CurrentCursor::default();
+        assert_eq!(current.get(), Cursor::default());
+
+        current.set(&CursorIcon::Wait.into());
+        assert_eq!(current.get(), Cursor::Icon(CursorIcon::Wait));
+
+        #[cfg(wayland_platform)]
+        {
+            let image = CursorImage::from_rgba(vec![0; 4], 1, 1, 0, 0).unwrap();
+            let custom = CustomCursor {
+                inner: PlatformCustomCursor::Wayland(OnlyCursorImage(Arc::new(image))),
+            };
+            current.set(&custom.clone().into());
+            assert_eq!(current.get(), Cursor::Custom(custom));
+        }
+
+        current.set(&CursorIcon::Pointer.into());
+        assert_eq!(current.get(), Cursor::Icon(CursorIcon::Pointer));
+    }
+
+    #[test]
+    fn extended_cursor_icons_have_css_names() {
+        for (icon, name) in [
+            (CursorIcon::Cell, "cell"),
+            (CursorIcon::VerticalText, "vertical-text"),
+            (CursorIcon::AllScroll, "all-scroll"),
+            (CursorIcon::AllResize, "all-resize"),
+        ] {
+            assert_eq!(icon.name(), name);
+        }
+    }
+}
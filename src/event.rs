@@ -34,8 +34,10 @@
 //!
 //! [`EventLoop::run_app(...)`]: crate::event_loop::EventLoop::run_app
 //! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::{Mutex, Weak};
+use std::time::Duration;
 #[cfg(not(web_platform))]
 use std::time::Instant;
 
@@ -45,14 +47,14 @@ use smol_str::SmolStr;
 #[cfg(web_platform)]
 use web_time::Instant;
 
-use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize};
 use crate::error::RequestError;
 use crate::event_loop::AsyncRequestSerial;
-use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersKeysState, ModifiersState};
 use crate::platform_impl;
 #[cfg(doc)]
 use crate::window::Window;
-use crate::window::{ActivationToken, Theme, WindowId};
+use crate::window::{ActivationToken, SurfaceTransform, Theme, WindowId};
 
 // TODO: Remove once the backends can call `ApplicationHandler` methods directly. For now backends
 // like Windows and Web require `Event` to wire user events, otherwise each backend will have to
@@ -140,13 +142,19 @@ pub enum StartCause {
 
 /// Describes an event from a [`Window`].
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowEvent {
     /// The activation token was delivered back and now could be used.
     #[cfg_attr(not(any(x11_platform, wayland_platform)), allow(rustdoc::broken_intra_doc_links))]
     /// Delivered in response to [`request_activation_token`].
     ///
     /// [`request_activation_token`]: crate::platform::startup_notify::WindowExtStartupNotify::request_activation_token
-    ActivationTokenDone { serial: AsyncRequestSerial, token: ActivationToken },
+    ActivationTokenDone {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw"))]
+        serial: AsyncRequestSerial,
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw"))]
+        token: ActivationToken,
+    },
 
     /// The size of the window's surface has changed.
     ///
@@ -156,7 +164,15 @@ pub enum WindowEvent {
     /// This event will not necessarily be emitted upon window creation, query
     /// [`Window::surface_size`] if you need to determine the surface's initial size.
     ///
+    /// Use [`Window::is_minimized`] if you want to skip rendering while the window is minimized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Not emitted when the window is minimized, instead of reporting the zero size
+    ///   of its hidden client area. Other platforms may still report a zero size.
+    ///
     /// [`Window::surface_size`]: crate::window::Window::surface_size
+    /// [`Window::is_minimized`]: crate::window::Window::is_minimized
     SurfaceResized(PhysicalSize<u32>),
 
     /// The position of the window has changed.
@@ -226,26 +242,81 @@ pub enum WindowEvent {
     /// - **Windows:** The shift key overrides NumLock. In other words, while shift is held down,
     ///   numpad keys act as if NumLock wasn't active. When this is used, the OS sends fake key
     ///   events which are not marked as `is_synthetic`.
+    /// - **macOS:** The media keys, i.e. the volume, brightness, playback and eject keys of the
+    ///   function row or the Touch Bar, are reported as [`NamedKey`]s to the key window, like
+    ///   [`NamedKey::MediaPlayPause`]. This needs no entitlement, but they are only received while
+    ///   the application is active: they aren't delivered globally, which would require an event
+    ///   tap and the Accessibility permission. The system may also handle them, e.g. the playback
+    ///   keys control the "Now Playing" media as well.
     /// - **iOS:** Unsupported.
+    ///
+    /// [`NamedKey`]: crate::keyboard::NamedKey
+    /// [`NamedKey::MediaPlayPause`]: crate::keyboard::NamedKey::MediaPlayPause
     KeyboardInput {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
         event: KeyEvent,
 
-        /// If `true`, the event was generated synthetically by winit
-        /// in one of the following circumstances:
+        /// If `true`, the event was generated synthetically by winit rather than by a key
+        /// being pressed or released, so it should not trigger actions such as firing in a game.
+        ///
+        /// Synthetic events are generated on focus transitions, right after the corresponding
+        /// [`Focused`][Self::Focused] event:
         ///
-        /// * Synthetic key press events are generated for all keys pressed when a window gains
-        ///   focus. Likewise, synthetic key release events are generated for all keys pressed when
-        ///   a window goes out of focus. ***Currently, this is only functional on X11 and
-        ///   Windows***
+        /// * When a window gains focus, a synthetic press is generated for every key that is held
+        ///   down at that time.
+        /// * When a window loses focus, a synthetic release is generated for every key that is
+        ///   still held down, so that no key appears stuck while the window is unfocused.
         ///
         /// Otherwise, this value is always `false`.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **X11:** On `FocusIn` and `FocusOut`, for the keys reported by `XQueryKeymap`.
+        /// - **Windows:** On `WM_SETFOCUS` and `WM_KILLFOCUS`, for the keys reported by
+        ///   `GetKeyboardState`. Caps Lock is sent first, then non-modifier keys before modifier
+        ///   keys on focus gain, and modifier keys before non-modifier keys on focus loss.
+        /// - **Wayland / macOS / Web / iOS / Android / Orbital:** Synthetic events are never
+        ///   generated, so this is always `false`. On Wayland, the keys held when the window gains
+        ///   focus are not reported at all.
+        ///
+        /// The events injected with `EventInjector::inject_key`, available with the `test-util`
+        /// cargo feature, are synthetic on all platforms.
         is_synthetic: bool,
     },
 
     /// The keyboard modifiers have changed.
     ModifiersChanged(Modifiers),
 
+    /// The active keyboard layout has changed.
+    ///
+    /// Query the new layout with [`Window::keyboard_layout_name`]. This is a hint to invalidate
+    /// anything derived from the layout, it can be delivered even when the name stays the same,
+    /// e.g. when switching between two layouts with the same name.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Only delivered to the window with keyboard focus.
+    /// - **macOS:** Delivered with the first keyboard event after the switch.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`Window::keyboard_layout_name`]: crate::window::Window::keyboard_layout_name
+    KeyboardLayoutChanged,
+
+    /// The direction of the text typed with the active keyboard layout has changed, e.g. when
+    /// switching from an English to a Hebrew layout.
+    ///
+    /// Delivered right after the [`KeyboardLayoutChanged`][Self::KeyboardLayoutChanged] event of
+    /// the switch, with the new [`Window::keyboard_layout_direction`]. It isn't delivered when the
+    /// direction of the new layout is unknown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`Window::keyboard_layout_direction`]: crate::window::Window::keyboard_layout_direction
+    KeyboardLayoutDirectionChanged(keyboard::TextDirection),
+
     /// An event from an input method.
     ///
     /// **Note:** You have to explicitly enable this event using [`Window::set_ime_allowed`].
@@ -257,6 +328,7 @@ pub enum WindowEvent {
 
     /// The pointer has moved on the window.
     PointerMoved {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
 
         /// (x,y) coordinates in pixels relative to the top-left corner of the window. Because the
@@ -281,10 +353,14 @@ pub enum WindowEvent {
         primary: bool,
 
         source: PointerSource,
+
+        /// The time the platform reported the move at, see [`KeyEvent::timestamp`].
+        timestamp: Option<Duration>,
     },
 
     /// The pointer has entered the window.
     PointerEntered {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
 
         /// The position of the pointer when it entered the window.
@@ -310,6 +386,7 @@ pub enum WindowEvent {
 
     /// The pointer has left the window.
     PointerLeft {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
 
         /// The position of the pointer when it left the window. The position reported can be
@@ -334,11 +411,62 @@ pub enum WindowEvent {
         kind: PointerKind,
     },
 
+    /// The pointer lock of the window was engaged or released.
+    ///
+    /// This complements [`Window::set_cursor_grab`] with [`CursorGrabMode::Locked`]: it is
+    /// emitted once the platform actually locks or releases the pointer, which may differ from
+    /// the requested state, e.g. when the compositor refuses the lock or breaks it when the
+    /// window loses focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires the compositor to support the `zwp_pointer_constraints_v1`
+    ///   protocol. The lock is only engaged once the pointer enters the window, so this event can
+    ///   lag behind the call to [`Window::set_cursor_grab`].
+    /// - **iOS / Android / Web / Orbital / Windows / X11:** Unsupported.
+    ///
+    /// [`Window::set_cursor_grab`]: crate::window::Window::set_cursor_grab
+    /// [`CursorGrabMode::Locked`]: crate::window::CursorGrabMode::Locked
+    PointerLock { locked: bool },
+
     /// A mouse wheel movement or touchpad scroll occurred.
-    MouseWheel { device_id: Option<DeviceId>, delta: MouseScrollDelta, phase: TouchPhase },
+    MouseWheel {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
+        device_id: Option<DeviceId>,
+        delta: MouseScrollDelta,
+        /// The phase of the scroll.
+        ///
+        /// Mouse wheels always report [`TouchPhase::Moved`]. A touchpad scroll starts with
+        /// [`TouchPhase::Started`] when the fingers begin moving and ends with
+        /// [`TouchPhase::Ended`] when they are lifted. During momentum scrolling, this follows
+        /// [`momentum_phase`](Self::MouseWheel::momentum_phase).
+        phase: TouchPhase,
+        /// The phase of the momentum scrolling, or `None` if the event isn't part of it.
+        ///
+        /// When the fingers are lifted from the touchpad during a fling, the system keeps
+        /// scrolling with decaying deltas to emulate inertia:
+        ///
+        /// - [`TouchPhase::Started`]: the first event of the momentum scrolling, right after the
+        ///   scroll with the fingers ended.
+        /// - [`TouchPhase::Moved`]: the following events, until the scrolling slows down.
+        /// - [`TouchPhase::Ended`]: the last event, the fling has ended.
+        /// - [`TouchPhase::Cancelled`]: the momentum scrolling was interrupted before ending by
+        ///   itself, e.g. by touching the touchpad again.
+        ///
+        /// Applications implementing their own inertial scrolling should ignore the events with a
+        /// momentum phase, and start their animation when the scroll with the fingers ends.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **macOS:** The `momentumPhase` of the scroll event.
+        /// - **iOS / Android / Windows / X11 / Wayland / Web / Orbital:** Always `None`. On
+        ///   Windows, the inertia of precision touchpads is reported like regular wheel events.
+        momentum_phase: Option<TouchPhase>,
+    },
 
     /// An mouse button press has been received.
     PointerButton {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
         state: ElementState,
 
@@ -361,15 +489,20 @@ pub enum WindowEvent {
         primary: bool,
 
         button: ButtonSource,
+
+        /// The time the platform reported the button at, see [`KeyEvent::timestamp`].
+        timestamp: Option<Duration>,
     },
 
     /// Two-finger pinch gesture, often used for magnification.
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS** and **iOS**.
+    /// - Only available on **macOS**, **iOS** and **Wayland**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
+    /// - On Wayland, requires the compositor to support the `zwp_pointer_gestures_v1` protocol.
     PinchGesture {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
         /// Positive values indicate magnification (zooming in) and  negative
         /// values indicate shrinking (zooming out).
@@ -386,6 +519,7 @@ pub enum WindowEvent {
     /// - Only available on **iOS**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
     PanGesture {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
         /// Change in pixels of pan gesture from last update.
         delta: PhysicalPosition<f32>,
@@ -410,7 +544,10 @@ pub enum WindowEvent {
     ///
     /// - Only available on **macOS 10.8** and later, and **iOS**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
-    DoubleTapGesture { device_id: Option<DeviceId> },
+    DoubleTapGesture {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
+        device_id: Option<DeviceId>,
+    },
 
     /// Two-finger rotation gesture.
     ///
@@ -419,9 +556,14 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS** and **iOS**.
+    /// - Only available on **macOS**, **iOS** and **Wayland**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
+    /// - On Wayland, the rotation is reported as part of the pinch gesture of the
+    ///   `zwp_pointer_gestures_v1` protocol, so [`PinchGesture`] events are sent alongside.
+    ///
+    /// [`PinchGesture`]: Self::PinchGesture
     RotationGesture {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
         /// change in rotation in degrees
         delta: f32,
@@ -435,6 +577,7 @@ pub enum WindowEvent {
     /// - **macOS**: Only supported on Apple forcetouch-capable macbooks.
     /// - **Android / iOS / Wayland / X11 / Windows / Orbital / Web:** Unsupported.
     TouchpadPressure {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
         device_id: Option<DeviceId>,
         /// Value between 0 and 1 representing how hard the touchpad is being
         /// pressed.
@@ -468,6 +611,31 @@ pub enum WindowEvent {
         surface_size_writer: SurfaceSizeWriter,
     },
 
+    /// The transform the surface is expected to be rendered with has changed.
+    ///
+    /// See [`Window::surface_transform`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / macOS / Android / Windows / X11 / Web / Orbital:** Unsupported.
+    ///
+    /// [`Window::surface_transform`]: crate::window::Window::surface_transform
+    SurfaceTransformChanged(SurfaceTransform),
+
+    /// The safe area of the window has changed, e.g. because the device was rotated, or the window
+    /// entered fullscreen on a display with a notch.
+    ///
+    /// Contains the new value of [`Window::safe_area`]. The area to draw important content in
+    /// usually changes along with the surface size, so this is emitted before the corresponding
+    /// [`WindowEvent::RedrawRequested`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web / Wayland / Windows / X11 / Orbital:** Unsupported.
+    ///
+    /// [`Window::safe_area`]: crate::window::Window::safe_area
+    SafeAreaChanged(PhysicalInsets<u32>),
+
     /// The system window theme has changed.
     ///
     /// Applications might wish to react to this to change the theme of the content of the window
@@ -530,6 +698,7 @@ pub enum WindowEvent {
 /// **Wayland/X11:** [`Unknown`](Self::Unknown) device types are converted to known variants by the
 /// system.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PointerKind {
     Mouse,
     /// See [`PointerSource::Touch`] for more details.
@@ -537,7 +706,7 @@ pub enum PointerKind {
     /// ## Platform-specific
     ///
     /// **macOS:** Unsupported.
-    Touch(FingerId),
+    Touch(#[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw"))] FingerId),
     Unknown,
 }
 
@@ -546,6 +715,7 @@ pub enum PointerKind {
 /// **Wayland/X11:** [`Unknown`](Self::Unknown) device types are converted to known variants by the
 /// system.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PointerSource {
     Mouse,
     /// Represents a touch event.
@@ -573,6 +743,7 @@ pub enum PointerSource {
     ///
     /// **macOS:** Unsupported.
     Touch {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw"))]
         finger_id: FingerId,
 
         /// Describes how hard the screen was pressed. May be [`None`] if the hardware does not
@@ -606,6 +777,7 @@ impl From<PointerSource> for PointerKind {
 /// **Wayland/X11:** [`Unknown`](Self::Unknown) device types are converted to known variants by the
 /// system.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ButtonSource {
     Mouse(MouseButton),
     /// See [`PointerSource::Touch`] for more details.
@@ -614,6 +786,7 @@ pub enum ButtonSource {
     ///
     /// **macOS:** Unsupported.
     Touch {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw"))]
         finger_id: FingerId,
         force: Option<Force>,
     },
@@ -652,6 +825,28 @@ impl From<MouseButton> for ButtonSource {
 /// `DeviceId` which identifies its origin. Note that devices may be virtual (representing an
 /// on-screen cursor and keyboard focus) or physical. Virtual devices typically aggregate inputs
 /// from multiple physical devices.
+///
+/// The ID of a device stays the same for the lifetime of the event loop, as long as the device
+/// stays connected, so it can be used as the key of per-device state, e.g. in a `HashMap`. Once a
+/// device has been removed, its ID may be reused for a device connected later.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use winit::dpi::PhysicalPosition;
+/// use winit::event::DeviceId;
+///
+/// let mut positions: HashMap<DeviceId, PhysicalPosition<f64>> = HashMap::new();
+/// // Construct an ID with `from_raw` to test the handling of device events.
+/// let device_id = DeviceId::from_raw(0);
+/// positions.insert(device_id, PhysicalPosition::new(10.0, 20.0));
+/// assert_eq!(positions[&device_id], PhysicalPosition::new(10.0, 20.0));
+/// ```
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Only set on device events from tablets, which is best-effort. The system does not
+///   tell apart standard mice, trackpads and keyboards, so their events have no device ID.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId(i64);
 
@@ -659,20 +854,32 @@ impl DeviceId {
     /// Convert the [`DeviceId`] into the underlying integer.
     ///
     /// This is useful if you need to pass the ID across an FFI boundary, or store it in an atomic.
-    #[allow(dead_code)]
-    pub(crate) const fn into_raw(self) -> i64 {
+    pub const fn into_raw(self) -> i64 {
         self.0
     }
 
     /// Construct a [`DeviceId`] from the underlying integer.
     ///
-    /// This should only be called with integers returned from [`DeviceId::into_raw`].
-    #[allow(dead_code)]
-    pub(crate) const fn from_raw(id: i64) -> Self {
+    /// This should only be called with integers returned from [`DeviceId::into_raw`], or to
+    /// construct the events of a device in tests.
+    pub const fn from_raw(id: i64) -> Self {
         Self(id)
     }
 }
 
+#[cfg(feature = "serde")]
+impl crate::utils::serde_raw::Raw for DeviceId {
+    type Raw = i64;
+
+    fn to_raw(&self) -> i64 {
+        self.0
+    }
+
+    fn from_raw(raw: i64) -> Self {
+        Self(raw)
+    }
+}
+
 /// Identifier of a finger in a touch event.
 ///
 /// Whenever a touch event is received it contains a `FingerId` which uniquely identifies the finger
@@ -698,6 +905,19 @@ impl FingerId {
     }
 }
 
+#[cfg(feature = "serde")]
+impl crate::utils::serde_raw::Raw for FingerId {
+    type Raw = usize;
+
+    fn to_raw(&self) -> usize {
+        self.0
+    }
+
+    fn from_raw(raw: usize) -> Self {
+        Self(raw)
+    }
+}
+
 /// Represents raw hardware events that are not associated with any particular window.
 ///
 /// Useful for interactions that diverge significantly from a conventional 2D GUI, such as 3D camera
@@ -706,7 +926,15 @@ impl FingerId {
 /// (corresponding to GUI pointers and keyboard focus) the device IDs may not match.
 ///
 /// Note that these events are delivered regardless of input focus.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** When a native event produces both device and window events, such as a button press,
+///   the device events are always delivered first.
+/// - **Others:** The order of the device and window events produced by the same native event is
+///   unspecified.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeviceEvent {
     /// Change in physical position of a pointing device.
     ///
@@ -715,6 +943,20 @@ pub enum DeviceEvent {
     ///
     /// ## Platform-specific
     ///
+    /// **macOS:** Tablets in absolute mode don't report any motion delta, so it is derived from
+    /// successive cursor positions instead, which are accelerated and stop at the edges of the
+    /// screen. Other devices that report no delta, e.g. when the cursor is warped, emit no motion.
+    /// Consecutive motion is coalesced into a single event per iteration of the event loop, unless
+    /// disabled with
+    #[cfg_attr(
+        any(macos_platform, docsrs),
+        doc = "[`EventLoopBuilderExtMacOS::with_pointer_motion_coalescing()`][crate::platform::macos::EventLoopBuilderExtMacOS::with_pointer_motion_coalescing()]."
+    )]
+    #[cfg_attr(
+        not(any(macos_platform, docsrs)),
+        doc = "`EventLoopBuilderExtMacOS::with_pointer_motion_coalescing()`."
+    )]
+    ///
     /// **Web:** Only returns raw data, not OS accelerated, if [`CursorGrabMode::Locked`] is used
     /// and browser support is available, see
     #[cfg_attr(
@@ -740,30 +982,127 @@ pub enum DeviceEvent {
         delta: MouseScrollDelta,
     },
 
+    /// A button on a pointing device was pressed or released.
+    ///
+    /// The `button` is numbered as follows:
+    ///
+    /// - `0`: [`MouseButton::Left`]
+    /// - `1`: [`MouseButton::Right`]
+    /// - `2`: [`MouseButton::Middle`]
+    /// - `3`: [`MouseButton::Back`]
+    /// - `4`: [`MouseButton::Forward`]
+    ///
+    /// Additional buttons are reported with the raw number the platform assigns to them. Use
+    /// [`MouseButton::from_device_button`] to compare them with the buttons of the window events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The core X11 button number is reported instead, where `1` is left, `2` is
+    ///   middle, `3` is right, `4` to `7` are the scroll wheel, `8` is back and `9` is forward.
+    /// - **Windows:** Only the first five buttons are reported.
     Button {
         button: ButtonId,
         state: ElementState,
     },
 
+    /// The state of a tablet stylus changed.
+    ///
+    /// Sent alongside the pointer events the stylus generates. This requires a pen tablet, no such
+    /// events are sent for an ordinary mouse.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Not sent when the `sendEvent:` override is disabled, like other device events.
+    /// - **iOS / Android / Web / Windows / X11 / Wayland / Orbital:** Unsupported.
+    TabletPoint {
+        /// The pressure applied to the tip of the stylus, from `0.0` to `1.0`.
+        pressure: f32,
+        /// The tilt of the stylus as (x, y), each from `-1.0` to `1.0`, where `(0.0, 0.0)` is
+        /// perpendicular to the tablet.
+        tilt: (f64, f64),
+    },
+
+    /// A tablet tool, such as a stylus, came into the proximity of the tablet.
+    ///
+    /// The tool is reported as in proximity as long as the tablet senses it, even while it
+    /// doesn't touch the surface. This is the time to switch e.g. between a pen and an eraser
+    /// mode.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Not sent when the `sendEvent:` override is disabled, like other device events.
+    /// - **iOS / Android / Web / Windows / X11 / Wayland / Orbital:** Unsupported.
+    TabletProximityEntered {
+        /// The kind of tool that came into proximity.
+        tool: TabletTool,
+    },
+
+    /// A tablet tool left the proximity of the tablet.
+    ///
+    /// See [`TabletProximityEntered`][Self::TabletProximityEntered].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Not sent when the `sendEvent:` override is disabled, like other device events.
+    /// - **iOS / Android / Web / Windows / X11 / Wayland / Orbital:** Unsupported.
+    TabletProximityLeft {
+        /// The kind of tool that left proximity.
+        tool: TabletTool,
+    },
+
     Key(RawKeyEvent),
 }
 
+/// The kind of tool used on a tablet, see [`DeviceEvent::TabletProximityEntered`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TabletTool {
+    /// The tip of a stylus.
+    Pen,
+    /// The eraser end of a stylus.
+    Eraser,
+    /// A puck, a mouse-like tool used on the tablet.
+    Cursor,
+    /// The tablet didn't tell the kind of tool.
+    Unknown,
+}
+
 /// Describes a keyboard input as a raw device event.
 ///
-/// Note that holding down a key may produce repeated `RawKeyEvent`s. The
-/// operating system doesn't provide information whether such an event is a
-/// repeat or the initial keypress. An application may emulate this by, for
-/// example keeping a Map/Set of pressed keys and determining whether a keypress
-/// corresponds to an already pressed key.
+/// Note that holding down a key may produce repeated `RawKeyEvent`s, check
+/// [`repeat`][Self::repeat] to filter them out.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Caps Lock is not reported, as its presses and releases can't be told apart.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RawKeyEvent {
     pub physical_key: keyboard::PhysicalKey,
     pub state: ElementState,
+    /// Whether this is a press generated by the key being held down, like [`KeyEvent::repeat`].
+    ///
+    /// Always `false` for releases.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Web:** Reported by the system. On macOS, raw key events are only sent while the
+    ///   application is active.
+    /// - **Windows / X11:** Derived from whether the key was already pressed, as the system
+    ///   doesn't report it.
+    pub repeat: bool,
 }
 
 /// Describes a keyboard input targeting a window.
+///
+/// With the `serde` cargo feature, the platform-specific parts of the event are not serialized; a
+/// deserialized event derives them like `KeyEvent::synthetic()` does.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "SerializedKeyEvent", into = "SerializedKeyEvent")
+)]
 pub struct KeyEvent {
     /// Represents the position of a key independent of the currently active layout.
     ///
@@ -788,34 +1127,52 @@ pub struct KeyEvent {
     /// ## `Fn` and `FnLock`
     ///
     /// `Fn` and `FnLock` key events are *exceedingly unlikely* to be emitted by Winit. These keys
-    /// are usually handled at the hardware or OS level, and aren't surfaced to applications. If
-    /// you somehow see this in the wild, we'd like to know :)
+    /// are usually handled at the hardware or OS level, and aren't surfaced to applications. The
+    /// exception is macOS, which reports the presses and releases of `Fn`, along with
+    /// [`ModifiersState::FN`]. If you somehow see them elsewhere in the wild, we'd like to know :)
+    ///
+    /// [`ModifiersState::FN`]: crate::keyboard::ModifiersState::FN
     pub physical_key: keyboard::PhysicalKey,
 
-    // Allowing `broken_intra_doc_links` for `logical_key`, because
-    // `key_without_modifiers` is not available on all platforms
-    #[cfg_attr(
-        not(any(windows_platform, macos_platform, x11_platform, wayland_platform)),
-        allow(rustdoc::broken_intra_doc_links)
-    )]
     /// This value is affected by all modifiers except <kbd>Ctrl</kbd>.
     ///
     /// This has two use cases:
     /// - Allows querying whether the current input is a Dead key.
-    /// - Allows handling key-bindings on platforms which don't support [`key_without_modifiers`].
+    /// - Allows handling key-bindings on platforms which don't support
+    ///   [`key_without_modifiers`][Self::key_without_modifiers].
     ///
-    /// If you use this field (or [`key_without_modifiers`] for that matter) for keyboard
-    /// shortcuts, **it is important that you provide users with a way to configure your
-    /// application's shortcuts so you don't render your application unusable for users with an
-    /// incompatible keyboard layout.**
+    /// If you use this field (or [`key_without_modifiers`][Self::key_without_modifiers] for that
+    /// matter) for keyboard shortcuts, **it is important that you provide users with a way to
+    /// configure your application's shortcuts so you don't render your application unusable for
+    /// users with an incompatible keyboard layout.**
     ///
     /// ## Platform-specific
     /// - **Web:** Dead keys might be reported as the real key instead of `Dead` depending on the
     ///   browser/OS.
-    ///
-    /// [`key_without_modifiers`]: crate::platform::modifier_supplement::KeyEventExtModifierSupplement::key_without_modifiers
     pub logical_key: keyboard::Key,
 
+    /// The key in the active layout, ignoring all modifiers.
+    ///
+    /// This ignores all modifiers including, but not limited to <kbd>Shift</kbd>, <kbd>Caps
+    /// Lock</kbd>, and <kbd>Ctrl</kbd>. In most cases this means that the unicode character is
+    /// lowercase. For example, pressing <kbd>Shift</kbd>+<kbd>1</kbd> on a US layout reports
+    /// `Key::Character("!")` as the [`logical_key`][Self::logical_key], but `Key::Character("1")`
+    /// here, so a shortcut bound to <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>1</kbd> can be matched
+    /// along with the [`ModifiersState`].
+    ///
+    /// In case `logical_key` reports `Dead`, this still reports the key as `Character` according
+    /// to the current keyboard layout, so this value cannot be `Dead` on the platforms that
+    /// support it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web / iOS / Android:** The key without modifiers isn't available, this is the same as
+    ///   [`logical_key`][Self::logical_key], which reflects <kbd>Shift</kbd> and the other
+    ///   modifiers except <kbd>Ctrl</kbd>.
+    ///
+    /// [`ModifiersState`]: keyboard::ModifiersState
+    pub key_without_modifiers: keyboard::Key,
+
     /// Contains the text produced by this keypress.
     ///
     /// In most cases this is identical to the content
@@ -837,6 +1194,28 @@ pub struct KeyEvent {
     /// See also: `text_with_all_modifiers()`
     pub text: Option<SmolStr>,
 
+    /// Whether [`text`][Self::text] is the result of composing this keypress with preceding dead
+    /// keys, e.g. `"é"` when pressing <kbd>e</kbd> after the dead key <kbd>´</kbd>.
+    ///
+    /// The dead keys themselves are reported as [`Key::Dead`], so a text field can show them as
+    /// an intermediate state, and replace it with the text of the event where this is `true`. If
+    /// the keys can't be combined, the text contains the dead key's character followed by the
+    /// character of this keypress, and this is still `true`.
+    ///
+    /// This is always `false` for key releases. Compositions handled by an input method are
+    /// reported through [`WindowEvent::Ime`] instead.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Sequences of the XKB compose table are reported, including the ones
+    ///   started with the <kbd>Compose</kbd> key.
+    /// - **Web:** Dead keys are always reported as `Key::Dead(None)`, which still starts a
+    ///   composition.
+    /// - **iOS / Android / Orbital:** Unsupported, always `false`.
+    ///
+    /// [`Key::Dead`]: keyboard::Key::Dead
+    pub composed: bool,
+
     /// Contains the location of this key on the keyboard.
     ///
     /// Certain keys on the keyboard may appear in more than once place. For example, the "Shift"
@@ -847,9 +1226,20 @@ pub struct KeyEvent {
     /// This field allows the user to differentiate between keys like this that have the same
     /// symbolic value but different locations on the keyboard.
     ///
+    /// For [`NamedKey::Enter`], the main Enter key is always reported as
+    /// [`KeyLocation::Standard`] and the numpad Enter key as [`KeyLocation::Numpad`].
+    ///
     /// See the [`KeyLocation`] type for more details.
     ///
+    /// ## Platform-specific
+    ///
+    /// - **Orbital:** Always [`KeyLocation::Standard`]; the numpad Enter key is not distinguished
+    ///   from the main one.
+    ///
     /// [`KeyLocation`]: crate::keyboard::KeyLocation
+    /// [`KeyLocation::Standard`]: crate::keyboard::KeyLocation::Standard
+    /// [`KeyLocation::Numpad`]: crate::keyboard::KeyLocation::Numpad
+    /// [`NamedKey::Enter`]: crate::keyboard::NamedKey::Enter
     pub location: keyboard::KeyLocation,
 
     /// Whether the key is being pressed or released.
@@ -890,16 +1280,303 @@ pub struct KeyEvent {
     /// ```
     pub repeat: bool,
 
+    /// The time the platform reported the event at, e.g. to measure the input latency.
+    ///
+    /// This is the time since a platform-specific reference point, so only the difference
+    /// between two timestamps is meaningful, and only for events of the same platform. It is
+    /// `None` when the platform doesn't provide one, and for synthetic events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The `timestamp` of the `NSEvent`, the time since the system started, excluding
+    ///   the time it was asleep.
+    /// - **Windows:** The `GetMessageTime` of the message, in milliseconds since the system
+    ///   started. It wraps around after 49.7 days.
+    /// - **X11:** The time of the X event, in milliseconds since an arbitrary point set by the
+    ///   server. It wraps around after 49.7 days.
+    /// - **Wayland:** The time of the Wayland event, in milliseconds since an arbitrary point set
+    ///   by the compositor. It wraps around after 49.7 days. This is `None` for the key repeats,
+    ///   as they are generated by Winit.
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always `None`.
+    pub timestamp: Option<Duration>,
+
     /// Platform-specific key event information.
     ///
-    /// On Windows, Linux and macOS, this type contains the key without modifiers and the text with
-    /// all modifiers applied.
+    /// On Windows, Linux, macOS and Redox, this type contains the text with all modifiers applied.
     ///
-    /// On Android, iOS, Redox and Web, this type is a no-op.
+    /// On Android, iOS and Web, this type is a no-op.
     pub(crate) platform_specific: platform_impl::KeyEventExtra,
 }
 
+#[cfg(feature = "test-util")]
+impl KeyEvent {
+    /// Create a synthetic key event, e.g. to be sent with [`EventInjector::inject`].
+    ///
+    /// The other parts of the event are derived from the given values: the key without modifiers
+    /// is `logical_key` and the text with all modifiers is `text`.
+    ///
+    /// [`EventInjector::inject`]: crate::event_loop::EventInjector::inject
+    pub fn synthetic(
+        physical_key: keyboard::PhysicalKey,
+        logical_key: keyboard::Key,
+        text: Option<SmolStr>,
+        location: keyboard::KeyLocation,
+        state: ElementState,
+        repeat: bool,
+    ) -> Self {
+        let platform_specific = platform_impl::KeyEventExtra::synthetic(text.as_ref());
+        Self {
+            physical_key,
+            key_without_modifiers: logical_key.clone(),
+            logical_key,
+            text,
+            composed: false,
+            location,
+            state,
+            repeat,
+            timestamp: None,
+            platform_specific,
+        }
+    }
+}
+
+impl KeyEvent {
+    /// The key to match keyboard shortcuts against, as selected by `mode`.
+    ///
+    /// Shortcuts are matched against this key along with the [`ModifiersState`], which this key
+    /// doesn't depend on: <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>Z</kbd> reports `"z"` rather than
+    /// `"Z"` or `"\x1a"`.
+    ///
+    /// - [`ShortcutMode::ByLayout`] returns the key as typed in the active layout without
+    ///   modifiers, see [`key_without_modifiers`][Self::key_without_modifiers].
+    /// - [`ShortcutMode::ByPosition`] returns the character the physical key types on a US layout,
+    ///   for the character keys of the main block, such as the letters, digits and punctuation.
+    ///   Other keys, such as [`NamedKey::Enter`], the numpad, and keys missing from US keyboards
+    ///   such as [`KeyCode::IntlBackslash`], are returned as in [`ShortcutMode::ByLayout`].
+    ///
+    /// See [`ShortcutMode`] for the trade-offs between the two.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web / iOS / Android:** The key without modifiers isn't available, [`logical_key`] is
+    ///   used instead, which reflects <kbd>Shift</kbd> and the other modifiers except
+    ///   <kbd>Ctrl</kbd>.
+    ///
+    /// [`ModifiersState`]: keyboard::ModifiersState
+    /// [`ShortcutMode`]: keyboard::ShortcutMode
+    /// [`ShortcutMode::ByLayout`]: keyboard::ShortcutMode::ByLayout
+    /// [`ShortcutMode::ByPosition`]: keyboard::ShortcutMode::ByPosition
+    /// [`NamedKey::Enter`]: keyboard::NamedKey::Enter
+    /// [`KeyCode::IntlBackslash`]: keyboard::KeyCode::IntlBackslash
+    /// [`logical_key`]: Self::logical_key
+    pub fn key_for_shortcut(&self, mode: keyboard::ShortcutMode) -> keyboard::Key {
+        if mode == keyboard::ShortcutMode::ByPosition {
+            let character = match self.physical_key {
+                keyboard::PhysicalKey::Code(code) => code.us_layout_character(),
+                keyboard::PhysicalKey::Unidentified(_) => None,
+            };
+            if let Some(character) = character {
+                return keyboard::Key::Character(SmolStr::new_static(character));
+            }
+        }
+
+        self.key_without_modifiers.clone()
+    }
+
+    /// Whether [`text`][Self::text] agrees with [`logical_key`][Self::logical_key].
+    ///
+    /// This is meant to be asserted in tests, to catch a backend reporting a key and a text that
+    /// don't match, e.g. after an <kbd>AltGr</kbd> combination. The event is consistent if either:
+    /// - It has no text.
+    /// - The text results from a composition with dead keys, see [`composed`][Self::composed].
+    /// - The logical key is a [`Key::Character`] with the same text.
+    /// - The logical key is a [`Key::Named`] whose [`NamedKey::to_text`] is the text, or which has
+    ///   no textual equivalent at all, like [`NamedKey::Delete`].
+    /// - The logical key is a [`Key::Dead`] or [`Key::Unidentified`], which don't say anything
+    ///   about the text.
+    ///
+    /// In debug builds, Winit checks this for the events it reports on Windows, macOS, X11 and
+    /// Wayland, and logs a warning for the events that aren't consistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "test-util")]
+    /// # {
+    /// use winit::event::{ElementState, KeyEvent};
+    /// use winit::keyboard::{Key, KeyCode, KeyLocation, NamedKey};
+    ///
+    /// let key = |logical_key, text: Option<&str>| {
+    ///     KeyEvent::synthetic(
+    ///         KeyCode::KeyQ.into(),
+    ///         logical_key,
+    ///         text.map(Into::into),
+    ///         KeyLocation::Standard,
+    ///         ElementState::Pressed,
+    ///         false,
+    ///     )
+    /// };
+    /// assert!(key(Key::Character("@".into()), Some("@")).text_matches_logical());
+    /// assert!(key(Key::Character("q".into()), None).text_matches_logical());
+    /// assert!(key(Key::Named(NamedKey::Enter), Some("\r")).text_matches_logical());
+    /// assert!(!key(Key::Character("q".into()), Some("@")).text_matches_logical());
+    /// # }
+    /// ```
+    ///
+    /// [`Key::Character`]: keyboard::Key::Character
+    /// [`Key::Named`]: keyboard::Key::Named
+    /// [`Key::Dead`]: keyboard::Key::Dead
+    /// [`Key::Unidentified`]: keyboard::Key::Unidentified
+    /// [`NamedKey::to_text`]: keyboard::NamedKey::to_text
+    /// [`NamedKey::Delete`]: keyboard::NamedKey::Delete
+    pub fn text_matches_logical(&self) -> bool {
+        let Some(text) = &self.text else { return true };
+        if self.composed {
+            return true;
+        }
+        match &self.logical_key {
+            keyboard::Key::Character(character) => character == text,
+            keyboard::Key::Named(named) => named.to_text().map_or(true, |named| named == text),
+            keyboard::Key::Dead(_) | keyboard::Key::Unidentified(_) => true,
+        }
+    }
+
+    /// Log the event if [`text_matches_logical`][Self::text_matches_logical] doesn't hold, in
+    /// debug builds only.
+    #[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform))]
+    #[inline]
+    pub(crate) fn debug_check_text(&self) {
+        if cfg!(debug_assertions) && !self.text_matches_logical() {
+            tracing::warn!(
+                "key event with a text that doesn't match its logical key, this is a bug: {self:?}"
+            );
+        }
+    }
+}
+
+/// The serialized form of a [`KeyEvent`], without the platform-specific parts.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SerializedKeyEvent {
+    physical_key: keyboard::PhysicalKey,
+    logical_key: keyboard::Key,
+    key_without_modifiers: keyboard::Key,
+    text: Option<SmolStr>,
+    composed: bool,
+    location: keyboard::KeyLocation,
+    state: ElementState,
+    repeat: bool,
+    timestamp: Option<Duration>,
+}
+
+#[cfg(feature = "serde")]
+impl From<KeyEvent> for SerializedKeyEvent {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            physical_key: event.physical_key,
+            logical_key: event.logical_key,
+            key_without_modifiers: event.key_without_modifiers,
+            text: event.text,
+            composed: event.composed,
+            location: event.location,
+            state: event.state,
+            repeat: event.repeat,
+            timestamp: event.timestamp,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedKeyEvent> for KeyEvent {
+    fn from(event: SerializedKeyEvent) -> Self {
+        let platform_specific = platform_impl::KeyEventExtra::synthetic(event.text.as_ref());
+        Self {
+            physical_key: event.physical_key,
+            logical_key: event.logical_key,
+            key_without_modifiers: event.key_without_modifiers,
+            text: event.text,
+            composed: event.composed,
+            location: event.location,
+            state: event.state,
+            repeat: event.repeat,
+            timestamp: event.timestamp,
+            platform_specific,
+        }
+    }
+}
+
+/// Tracks dead keys to report [`KeyEvent::composed`], for platforms that don't report it natively.
+#[cfg_attr(not(any(windows_platform, macos_platform, web_platform)), allow(dead_code))]
+#[derive(Debug, Default)]
+pub(crate) struct DeadKeyComposition {
+    pending: bool,
+}
+
+#[cfg_attr(not(any(windows_platform, macos_platform, web_platform)), allow(dead_code))]
+impl DeadKeyComposition {
+    /// Feed a key event, returns whether its text is composed with preceding dead keys.
+    pub(crate) fn feed(
+        &mut self,
+        state: ElementState,
+        key: &keyboard::Key,
+        has_text: bool,
+    ) -> bool {
+        // Modifiers may be held to type the base character, and don't end the composition.
+        if state.is_released() || key.is_modifier() {
+            return false;
+        }
+
+        if matches!(key, keyboard::Key::Dead(_)) {
+            self.pending = true;
+            return false;
+        }
+
+        std::mem::take(&mut self.pending) && has_text
+    }
+}
+
+/// Tracks pressed keys to report [`RawKeyEvent::repeat`], for platforms that don't report it
+/// natively.
+#[cfg_attr(not(any(windows_platform, x11_platform)), allow(dead_code))]
+#[derive(Debug, Default)]
+pub(crate) struct RawKeyRepeat {
+    pressed: HashSet<keyboard::PhysicalKey>,
+}
+
+#[cfg_attr(not(any(windows_platform, x11_platform)), allow(dead_code))]
+impl RawKeyRepeat {
+    /// Feed a raw key event, returns whether it is a repeat.
+    pub(crate) fn feed(
+        &mut self,
+        physical_key: keyboard::PhysicalKey,
+        state: ElementState,
+    ) -> bool {
+        match state {
+            ElementState::Pressed => !self.pressed.insert(physical_key),
+            ElementState::Released => {
+                self.pressed.remove(&physical_key);
+                false
+            },
+        }
+    }
+}
+
 /// Describes keyboard modifiers event.
+///
+/// Besides the [`state`][Self::state], the side-specific accessors such as
+/// [`lshift_state`][Self::lshift_state] report which of the left or right modifier keys are held.
+/// They return [`ModifiersKeyState::Released`] when the key is known not to be pressed, and
+/// [`ModifiersKeyState::Unknown`] when the platform doesn't report it. [`keys_state`] returns them
+/// all at once.
+///
+/// ## Platform-specific
+///
+/// - **macOS / Windows / Orbital:** The side-specific state is reported, keys are either
+///   [`ModifiersKeyState::Pressed`] or [`ModifiersKeyState::Released`].
+/// - **X11 / Wayland / Web / iOS / Android:** Unsupported, the side-specific state is always
+///   [`ModifiersKeyState::Unknown`].
+///
+/// [`keys_state`]: Self::keys_state
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Modifiers {
@@ -909,6 +1586,11 @@ pub struct Modifiers {
     //
     // The field providing a metadata, it shouldn't be used as a source of truth.
     pub(crate) pressed_mods: ModifiersKeys,
+
+    // NOTE: Modifiers keys whose state is reported by the platform, the ones not in
+    // `pressed_mods` are released.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) known_mods: ModifiersKeys,
 }
 
 impl Modifiers {
@@ -957,9 +1639,25 @@ impl Modifiers {
         self.mod_state(ModifiersKeys::RSUPER)
     }
 
+    /// The state of all the left and right modifier keys at once.
+    pub fn keys_state(&self) -> ModifiersKeysState {
+        ModifiersKeysState {
+            lshift: self.lshift_state(),
+            rshift: self.rshift_state(),
+            lcontrol: self.lcontrol_state(),
+            rcontrol: self.rcontrol_state(),
+            lalt: self.lalt_state(),
+            ralt: self.ralt_state(),
+            lsuper: self.lsuper_state(),
+            rsuper: self.rsuper_state(),
+        }
+    }
+
     fn mod_state(&self, modifier: ModifiersKeys) -> ModifiersKeyState {
         if self.pressed_mods.contains(modifier) {
             ModifiersKeyState::Pressed
+        } else if self.known_mods.contains(modifier) {
+            ModifiersKeyState::Released
         } else {
             ModifiersKeyState::Unknown
         }
@@ -968,7 +1666,7 @@ impl Modifiers {
 
 impl From<ModifiersState> for Modifiers {
     fn from(value: ModifiersState) -> Self {
-        Self { state: value, pressed_mods: Default::default() }
+        Self { state: value, pressed_mods: Default::default(), known_mods: Default::default() }
     }
 }
 
@@ -1118,6 +1816,19 @@ impl ElementState {
     pub fn is_pressed(self) -> bool {
         self == ElementState::Pressed
     }
+
+    /// True if `self == Released`.
+    pub fn is_released(self) -> bool {
+        self == ElementState::Released
+    }
+
+    /// The opposite state, `Released` for `Pressed` and vice versa.
+    pub fn toggle(self) -> ElementState {
+        match self {
+            ElementState::Pressed => ElementState::Released,
+            ElementState::Released => ElementState::Pressed,
+        }
+    }
 }
 
 /// Describes a button of a mouse controller.
@@ -1137,7 +1848,71 @@ pub enum MouseButton {
     Other(u16),
 }
 
+impl MouseButton {
+    /// Converts the number of a [`DeviceEvent::Button`] to the [`MouseButton`] of the window
+    /// events.
+    ///
+    /// The numbering is `0` for [`Left`][Self::Left], `1` for [`Right`][Self::Right], `2` for
+    /// [`Middle`][Self::Middle], `3` for [`Back`][Self::Back] and `4` for
+    /// [`Forward`][Self::Forward]. The other numbers are [`Other`][Self::Other], saturating at
+    /// [`u16::MAX`].
+    ///
+    /// This is the inverse of the conversion to `u32`, except for `Other(0)` to `Other(4)`, which
+    /// come back as the named buttons.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The device events use the core X11 numbering instead, see
+    ///   [`DeviceEvent::Button`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::event::MouseButton;
+    ///
+    /// assert_eq!(MouseButton::from_device_button(1), MouseButton::Right);
+    /// assert_eq!(MouseButton::from_device_button(7), MouseButton::Other(7));
+    /// assert_eq!(u32::from(MouseButton::Right), 1);
+    /// # }
+    /// ```
+    pub fn from_device_button(button: ButtonId) -> Self {
+        match button {
+            0 => MouseButton::Left,
+            1 => MouseButton::Right,
+            2 => MouseButton::Middle,
+            3 => MouseButton::Back,
+            4 => MouseButton::Forward,
+            n => MouseButton::Other(u16::try_from(n).unwrap_or(u16::MAX)),
+        }
+    }
+}
+
+/// The number of the button in [`DeviceEvent::Button`], see [`MouseButton::from_device_button`].
+impl From<MouseButton> for u32 {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => 0,
+            MouseButton::Right => 1,
+            MouseButton::Middle => 2,
+            MouseButton::Back => 3,
+            MouseButton::Forward => 4,
+            MouseButton::Other(n) => n.into(),
+        }
+    }
+}
+
 /// Describes a difference in the mouse scroll wheel state.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Scrolling is reported as a [`PixelDelta`][Self::PixelDelta] when the device has
+///   precise scrolling deltas (`hasPreciseScrollingDeltas`), like trackpads and the Magic Mouse,
+///   and as a [`LineDelta`][Self::LineDelta] otherwise. This holds for both
+///   [`WindowEvent::MouseWheel`] and [`DeviceEvent::MouseWheel`].
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseScrollDelta {
@@ -1198,6 +1973,23 @@ impl PartialEq for SurfaceSizeWriter {
 
 impl Eq for SurfaceSizeWriter {}
 
+/// The writer is serialized as a unit, a deserialized writer isn't connected to any window and
+/// its requests are ignored.
+#[cfg(feature = "serde")]
+impl Serialize for SurfaceSizeWriter {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SurfaceSizeWriter {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <()>::deserialize(deserializer)?;
+        Ok(Self { new_surface_size: Weak::new() })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeSet, HashSet};
@@ -1251,8 +2043,13 @@ mod tests {
                     primary: true,
                     position: (0, 0).into(),
                     source: PointerSource::Mouse,
+                    timestamp: None,
                 });
                 with_window_event(ModifiersChanged(event::Modifiers::default()));
+                with_window_event(KeyboardLayoutChanged);
+                with_window_event(KeyboardLayoutDirectionChanged(
+                    crate::keyboard::TextDirection::RightToLeft,
+                ));
                 with_window_event(PointerEntered {
                     device_id: None,
                     primary: true,
@@ -1265,10 +2062,12 @@ mod tests {
                     position: Some((0, 0).into()),
                     kind: PointerKind::Mouse,
                 });
+                with_window_event(PointerLock { locked: true });
                 with_window_event(MouseWheel {
                     device_id: None,
                     delta: event::MouseScrollDelta::LineDelta(0.0, 0.0),
                     phase: event::TouchPhase::Started,
+                    momentum_phase: None,
                 });
                 with_window_event(PointerButton {
                     device_id: None,
@@ -1276,6 +2075,7 @@ mod tests {
                     state: event::ElementState::Pressed,
                     position: (0, 0).into(),
                     button: event::MouseButton::Other(0).into(),
+                    timestamp: None,
                 });
                 with_window_event(PointerButton {
                     device_id: None,
@@ -1286,6 +2086,7 @@ mod tests {
                         finger_id: fid,
                         force: Some(event::Force::Normalized(0.0)),
                     },
+                    timestamp: None,
                 });
                 with_window_event(PinchGesture {
                     device_id: None,
@@ -1304,6 +2105,10 @@ mod tests {
                     phase: event::TouchPhase::Started,
                 });
                 with_window_event(TouchpadPressure { device_id: None, pressure: 0.0, stage: 0 });
+                with_window_event(SurfaceTransformChanged(
+                    crate::window::SurfaceTransform::Rotate90,
+                ));
+                with_window_event(SafeAreaChanged(crate::dpi::PhysicalInsets::new(0, 0, 0, 0)));
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
                 with_window_event(Occluded(true));
             }
@@ -1320,6 +2125,9 @@ mod tests {
                     delta: event::MouseScrollDelta::LineDelta(0.0, 0.0),
                 });
                 with_device_event(Button { button: 0, state: event::ElementState::Pressed });
+                with_device_event(TabletPoint { pressure: 0.0, tilt: (0.0, 0.0) });
+                with_device_event(TabletProximityEntered { tool: event::TabletTool::Pen });
+                with_device_event(TabletProximityLeft { tool: event::TabletTool::Eraser });
             }
         }};
     }
@@ -1367,4 +2175,205 @@ mod tests {
 
         let _ = event::Force::Calibrated { force: 0.0, max_possible_force: 0.0 }.clone();
     }
+
+    #[test]
+    fn dead_key_composition() {
+        use super::{DeadKeyComposition, ElementState};
+        use crate::keyboard::{Key, NamedKey};
+
+        let (pressed, released) = (ElementState::Pressed, ElementState::Released);
+        let dead = Key::Dead(Some('´'));
+        let e = Key::Character("e".into());
+        let shift = Key::Named(NamedKey::Shift);
+        let mut composition = DeadKeyComposition::default();
+
+        assert!(!composition.feed(pressed, &e, true));
+
+        // ´ e
+        assert!(!composition.feed(pressed, &dead, false));
+        assert!(!composition.feed(released, &dead, false));
+        assert!(composition.feed(pressed, &e, true));
+        assert!(!composition.feed(released, &e, false));
+        assert!(!composition.feed(pressed, &e, true));
+
+        // ´ Shift+E
+        assert!(!composition.feed(pressed, &dead, false));
+        assert!(!composition.feed(pressed, &shift, false));
+        assert!(composition.feed(pressed, &Key::Character("E".into()), true));
+
+        // ´ Escape, which produces no text and cancels the composition.
+        assert!(!composition.feed(pressed, &dead, false));
+        assert!(!composition.feed(pressed, &Key::Named(NamedKey::Escape), false));
+        assert!(!composition.feed(pressed, &e, true));
+    }
+
+    #[test]
+    fn raw_key_repeat() {
+        use super::{ElementState, RawKeyRepeat};
+        use crate::keyboard::{KeyCode, PhysicalKey};
+
+        let (pressed, released) = (ElementState::Pressed, ElementState::Released);
+        let a = PhysicalKey::Code(KeyCode::KeyA);
+        let b = PhysicalKey::Code(KeyCode::KeyB);
+        let mut repeat = RawKeyRepeat::default();
+
+        assert!(!repeat.feed(a, pressed));
+        assert!(repeat.feed(a, pressed));
+        assert!(repeat.feed(a, pressed));
+
+        // Other keys are tracked independently.
+        assert!(!repeat.feed(b, pressed));
+        assert!(repeat.feed(a, pressed));
+
+        assert!(!repeat.feed(a, released));
+        assert!(!repeat.feed(a, pressed));
+        assert!(repeat.feed(b, pressed));
+
+        // Stray releases are harmless.
+        assert!(!repeat.feed(b, released));
+        assert!(!repeat.feed(b, released));
+        assert!(!repeat.feed(b, pressed));
+    }
+
+    #[test]
+    fn element_state() {
+        use super::ElementState::{Pressed, Released};
+
+        assert!(Pressed.is_pressed());
+        assert!(!Pressed.is_released());
+        assert!(Released.is_released());
+        assert!(!Released.is_pressed());
+
+        assert_eq!(Pressed.toggle(), Released);
+        assert_eq!(Released.toggle(), Pressed);
+        assert_eq!(Pressed.toggle().toggle(), Pressed);
+    }
+
+    #[test]
+    fn mouse_button_device_numbering() {
+        use super::MouseButton;
+
+        let buttons = [
+            (MouseButton::Left, 0),
+            (MouseButton::Right, 1),
+            (MouseButton::Middle, 2),
+            (MouseButton::Back, 3),
+            (MouseButton::Forward, 4),
+            (MouseButton::Other(5), 5),
+            (MouseButton::Other(u16::MAX), u16::MAX.into()),
+        ];
+        for (button, number) in buttons {
+            assert_eq!(u32::from(button), number);
+            assert_eq!(MouseButton::from_device_button(number), button);
+        }
+
+        // The named buttons take precedence.
+        assert_eq!(
+            MouseButton::from_device_button(MouseButton::Other(3).into()),
+            MouseButton::Back
+        );
+        // Out of range numbers saturate.
+        assert_eq!(MouseButton::from_device_button(u32::MAX), MouseButton::Other(u16::MAX));
+    }
+
+    #[test]
+    fn modifiers_key_state() {
+        use super::Modifiers;
+        use crate::keyboard::{
+            ModifiersKeyState, ModifiersKeys, ModifiersKeysState, ModifiersState,
+        };
+
+        let unknown = Modifiers::from(ModifiersState::SHIFT);
+        assert_eq!(unknown.lshift_state(), ModifiersKeyState::Unknown);
+        assert_eq!(unknown.rshift_state(), ModifiersKeyState::Unknown);
+        assert_eq!(unknown.keys_state(), ModifiersKeysState::default());
+
+        let known = Modifiers {
+            state: ModifiersState::SHIFT,
+            pressed_mods: ModifiersKeys::LSHIFT,
+            known_mods: ModifiersKeys::all(),
+        };
+        assert_eq!(known.lshift_state(), ModifiersKeyState::Pressed);
+        assert_eq!(known.rshift_state(), ModifiersKeyState::Released);
+        assert_eq!(known.lalt_state(), ModifiersKeyState::Released);
+        assert_eq!(known.keys_state(), ModifiersKeysState {
+            lshift: ModifiersKeyState::Pressed,
+            rshift: ModifiersKeyState::Released,
+            lcontrol: ModifiersKeyState::Released,
+            rcontrol: ModifiersKeyState::Released,
+            lalt: ModifiersKeyState::Released,
+            ralt: ModifiersKeyState::Released,
+            lsuper: ModifiersKeyState::Released,
+            rsuper: ModifiersKeyState::Released,
+        });
+
+        // Some keys may be known while others aren't.
+        let partial = Modifiers {
+            state: ModifiersState::ALT,
+            pressed_mods: ModifiersKeys::RALT,
+            known_mods: ModifiersKeys::LALT | ModifiersKeys::RALT,
+        };
+        let keys = partial.keys_state();
+        assert_eq!(
+            (keys.lalt, keys.ralt),
+            (ModifiersKeyState::Released, ModifiersKeyState::Pressed)
+        );
+        assert_eq!(keys.lshift, ModifiersKeyState::Unknown);
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn key_for_shortcut_on_azerty() {
+        use super::{ElementState, KeyEvent};
+        use crate::keyboard::ShortcutMode::{ByLayout, ByPosition};
+        use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKeyCode, PhysicalKey};
+
+        // What a French AZERTY layout reports, without modifiers.
+        fn azerty(physical_key: PhysicalKey, logical_key: Key) -> KeyEvent {
+            let location = KeyLocation::Standard;
+            KeyEvent::synthetic(
+                physical_key,
+                logical_key,
+                None,
+                location,
+                ElementState::Pressed,
+                false,
+            )
+        }
+        let character = |code, character: &str| {
+            azerty(PhysicalKey::Code(code), Key::Character(character.into()))
+        };
+
+        let keys = [
+            // The physical key, what it types on AZERTY, and on a US layout.
+            (character(KeyCode::KeyQ, "a"), "a", "q"),
+            (character(KeyCode::KeyW, "z"), "z", "w"),
+            (character(KeyCode::KeyZ, "w"), "w", "z"),
+            (character(KeyCode::Semicolon, "m"), "m", ";"),
+            (character(KeyCode::KeyM, ","), ",", "m"),
+            (character(KeyCode::Digit1, "&"), "&", "1"),
+            (character(KeyCode::Digit2, "é"), "é", "2"),
+            (character(KeyCode::BracketLeft, "^"), "^", "["),
+        ];
+        for (event, by_layout, by_position) in keys {
+            assert_eq!(event.key_for_shortcut(ByLayout), Key::Character(by_layout.into()));
+            assert_eq!(event.key_for_shortcut(ByPosition), Key::Character(by_position.into()));
+        }
+
+        // Keys without a US character are the same in both modes.
+        let others = [
+            azerty(PhysicalKey::Code(KeyCode::Enter), Key::Named(NamedKey::Enter)),
+            azerty(PhysicalKey::Code(KeyCode::F5), Key::Named(NamedKey::F5)),
+            character(KeyCode::IntlBackslash, "<"),
+            character(KeyCode::Numpad1, "1"),
+            azerty(
+                PhysicalKey::Unidentified(NativeKeyCode::Xkb(0x1ff)),
+                Key::Character("ù".into()),
+            ),
+        ];
+        for event in others {
+            assert_eq!(event.key_for_shortcut(ByPosition), event.logical_key);
+            assert_eq!(event.key_for_shortcut(ByLayout), event.logical_key);
+        }
+    }
 }
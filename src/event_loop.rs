@@ -8,25 +8,35 @@
 //!
 //! See the root-level documentation for information on how to create and use an event loop to
 //! handle events.
-use std::fmt;
+use std::collections::{HashMap, VecDeque};
 use std::marker::PhantomData;
 #[cfg(any(x11_platform, wayland_platform))]
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{mpsc, Arc, Mutex};
 #[cfg(not(web_platform))]
 use std::time::{Duration, Instant};
+use std::{fmt, mem};
 
 use rwh_06::{DisplayHandle, HandleError, HasDisplayHandle};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 #[cfg(web_platform)]
 use web_time::{Duration, Instant};
 
 use crate::application::ApplicationHandler;
+use crate::dpi::PhysicalPosition;
 use crate::error::{EventLoopError, RequestError};
+use crate::event::{DeviceEvent, DeviceId, PointerKind, StartCause, WindowEvent};
+use crate::keyboard::{Key, KeyCode, NativeKey, NativeKeyCode, PhysicalKey};
 use crate::monitor::MonitorHandle;
+#[cfg(any(docsrs, macos_platform))]
+use crate::platform::macos::ApplicationHandlerExtMacOS;
 use crate::platform_impl;
 use crate::utils::AsAny;
-use crate::window::{CustomCursor, CustomCursorSource, Theme, Window, WindowAttributes};
+use crate::window::{
+    CommonWindowState, CustomCursor, CustomCursorSource, Theme, Window, WindowAttributes, WindowId,
+};
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
@@ -45,6 +55,7 @@ use crate::window::{CustomCursor, CustomCursorSource, Theme, Window, WindowAttri
 /// [`Window`]: crate::window::Window
 pub struct EventLoop {
     pub(crate) event_loop: platform_impl::EventLoop,
+    pub(crate) handler_options: HandlerOptions,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
@@ -57,6 +68,9 @@ pub struct EventLoop {
 #[derive(Default, PartialEq, Eq, Hash)]
 pub struct EventLoopBuilder {
     pub(crate) platform_specific: platform_impl::PlatformSpecificEventLoopAttributes,
+    pub(crate) frame_stats: bool,
+    pub(crate) pointer_move_coalescing: bool,
+    pub(crate) key_overrides: KeyOverrides,
 }
 
 static EVENT_LOOP_CREATED: AtomicBool = AtomicBool::new(false);
@@ -107,10 +121,102 @@ impl EventLoopBuilder {
         #[allow(clippy::unnecessary_mut_passed)]
         Ok(EventLoop {
             event_loop: platform_impl::EventLoop::new(&mut self.platform_specific)?,
+            handler_options: self.handler_options(),
             _marker: PhantomData,
         })
     }
 
+    /// Whether to collect frame timing statistics, available through
+    /// [`ActiveEventLoop::frame_stats()`][dyn ActiveEventLoop::frame_stats].
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn with_frame_stats(&mut self, enabled: bool) -> &mut Self {
+        self.frame_stats = enabled;
+        self
+    }
+
+    /// Whether to coalesce the [`WindowEvent::PointerMoved`] events of each pointer into a single
+    /// event per event loop iteration, carrying the latest position.
+    ///
+    /// This spares applications that only care about the latest position from processing every
+    /// event of high polling rate devices. A coalesced move is delivered before any other event of
+    /// the same window, so button presses and the like still happen at the right position, and at
+    /// the latest just before [`ApplicationHandler::about_to_wait()`]. The intermediate positions
+    /// remain available through
+    /// [`Window::pointer_move_history()`][dyn Window::pointer_move_history].
+    ///
+    /// The default is `false`, delivering every move.
+    #[inline]
+    pub fn with_pointer_move_coalescing(&mut self, enabled: bool) -> &mut Self {
+        self.pointer_move_coalescing = enabled;
+        self
+    }
+
+    /// Identify the keys by their native code, e.g. the extra keys of specialty keyboards that
+    /// Winit reports as [`PhysicalKey::Unidentified`], or keys to report differently than Winit's
+    /// mapping does.
+    ///
+    /// The `key_code_override` is called with the [`NativeKeyCode`] of the key of every
+    /// [`WindowEvent::KeyboardInput`] and [`DeviceEvent::Key`] event, and takes precedence over
+    /// Winit's mapping: when it returns a [`KeyCode`], the key is reported as
+    /// [`PhysicalKey::Code`] of it, including to [`Window::held_keys()`][dyn Window::held_keys].
+    /// Returning `None` keeps the key Winit identified, if any.
+    ///
+    /// The override runs for every key event, on the event loop thread, so it must be cheap.
+    ///
+    /// The default is `None`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Web / Orbital:** Only called for the unidentified keys, the native code of the
+    ///   others isn't available.
+    #[inline]
+    pub fn with_key_code_override(
+        &mut self,
+        key_code_override: fn(NativeKeyCode) -> Option<KeyCode>,
+    ) -> &mut Self {
+        self.key_overrides.key_code = Some(key_code_override);
+        self
+    }
+
+    /// Identify the logical keys Winit reports as [`Key::Unidentified`], e.g. the media and
+    /// vendor keys some platforms don't map.
+    ///
+    /// The `key_override` is called with the [`NativeKey`] of such keys in the
+    /// [`WindowEvent::KeyboardInput`] events, for both the [`logical_key`] and the
+    /// [`key_without_modifiers`]. When it returns a key, it is reported instead, which should be a
+    /// [`Key::Named`] or a [`Key::Character`]. Returning `None` leaves the key unidentified.
+    ///
+    /// This runs after the [`key_code_override`][Self::with_key_code_override], so the
+    /// [`physical_key`] of the event is already identified by then. The [`text`] of the event is
+    /// left as is.
+    ///
+    /// The override runs for every such event, on the event loop thread, so it must be cheap.
+    ///
+    /// The default is `None`.
+    ///
+    /// [`logical_key`]: crate::event::KeyEvent::logical_key
+    /// [`key_without_modifiers`]: crate::event::KeyEvent::key_without_modifiers
+    /// [`physical_key`]: crate::event::KeyEvent::physical_key
+    /// [`text`]: crate::event::KeyEvent::text
+    #[inline]
+    pub fn with_key_override(&mut self, key_override: fn(&NativeKey) -> Option<Key>) -> &mut Self {
+        self.key_overrides.key = Some(key_override);
+        self
+    }
+
+    /// The options implemented by wrapping the application, see [`HandlerOptions`].
+    pub(crate) fn handler_options(&self) -> HandlerOptions {
+        HandlerOptions {
+            frame_stats: self.frame_stats,
+            pointer_move_coalescing: self.pointer_move_coalescing,
+            key_overrides: self.key_overrides,
+            #[cfg(feature = "test-util")]
+            injected_events: Default::default(),
+        }
+    }
+
     #[cfg(web_platform)]
     pub(crate) fn allow_event_loop_recreation() {
         EVENT_LOOP_CREATED.store(false, Ordering::Relaxed);
@@ -190,7 +296,12 @@ impl EventLoop {
     /// To get the actual event loop, call [`build`][EventLoopBuilder::build] on that.
     #[inline]
     pub fn builder() -> EventLoopBuilder {
-        EventLoopBuilder { platform_specific: Default::default() }
+        EventLoopBuilder {
+            platform_specific: Default::default(),
+            frame_stats: false,
+            pointer_move_coalescing: false,
+            key_overrides: KeyOverrides::default(),
+        }
     }
 }
 
@@ -227,7 +338,7 @@ impl EventLoop {
     #[inline]
     #[cfg(not(all(web_platform, target_feature = "exception-handling")))]
     pub fn run_app<A: ApplicationHandler>(self, app: A) -> Result<(), EventLoopError> {
-        self.event_loop.run_app(app)
+        self.event_loop.run_app(self.handler_options.wrap(app))
     }
 
     /// Creates an [`EventLoopProxy`] that can be used to dispatch user events
@@ -236,6 +347,15 @@ impl EventLoop {
         self.event_loop.window_target().create_proxy()
     }
 
+    /// Creates an [`EventInjector`] that can be used to send synthetic window events to the
+    /// application, possibly from another thread.
+    ///
+    /// This is meant for testing, see [`EventInjector`] for details.
+    #[cfg(feature = "test-util")]
+    pub fn create_event_injector(&self) -> EventInjector {
+        self.handler_options.create_event_injector(self.create_proxy())
+    }
+
     /// Gets a persistent reference to the underlying platform display.
     ///
     /// See the [`OwnedDisplayHandle`] type for more information.
@@ -285,11 +405,12 @@ impl HasDisplayHandle for EventLoop {
 impl AsFd for EventLoop {
     /// Get the underlying [EventLoop]'s `fd` which you can register
     /// into other event loop, like [`calloop`] or [`mio`]. When doing so, the
-    /// loop must be polled with the [`pump_app_events`] API.
+    /// loop must be polled with the [`pump_app_events`] API, see [`dispatch_pending`].
     ///
     /// [`calloop`]: https://crates.io/crates/calloop
     /// [`mio`]: https://crates.io/crates/mio
     /// [`pump_app_events`]: crate::platform::pump_events::EventLoopExtPumpEvents::pump_app_events
+    /// [`dispatch_pending`]: crate::platform::pump_events::EventLoopExtPumpEvents::dispatch_pending
     fn as_fd(&self) -> BorrowedFd<'_> {
         self.event_loop.as_fd()
     }
@@ -299,11 +420,12 @@ impl AsFd for EventLoop {
 impl AsRawFd for EventLoop {
     /// Get the underlying [EventLoop]'s raw `fd` which you can register
     /// into other event loop, like [`calloop`] or [`mio`]. When doing so, the
-    /// loop must be polled with the [`pump_app_events`] API.
+    /// loop must be polled with the [`pump_app_events`] API, see [`dispatch_pending`].
     ///
     /// [`calloop`]: https://crates.io/crates/calloop
     /// [`mio`]: https://crates.io/crates/mio
     /// [`pump_app_events`]: crate::platform::pump_events::EventLoopExtPumpEvents::pump_app_events
+    /// [`dispatch_pending`]: crate::platform::pump_events::EventLoopExtPumpEvents::dispatch_pending
     fn as_raw_fd(&self) -> RawFd {
         self.event_loop.as_raw_fd()
     }
@@ -322,6 +444,9 @@ pub trait ActiveEventLoop: AsAny {
     ///
     /// - **Web:** The window is created but not inserted into the Web page automatically. Please
     ///   see the Web platform module for more information.
+    /// - **Wayland:** Blocks until the compositor sent the initial configure of the window, so its
+    ///   size and state are final once this returns. There is no timeout, a compositor that never
+    ///   configures the window blocks this forever.
     fn create_window(
         &self,
         window_attributes: WindowAttributes,
@@ -386,6 +511,12 @@ pub trait ActiveEventLoop: AsAny {
     /// - **iOS / Android / Wayland / x11 / Orbital:** Unsupported.
     fn system_theme(&self) -> Option<Theme>;
 
+    /// Returns information about the windowing system the event loop is connected to.
+    ///
+    /// This is meant for adapting behavior to the environment, for example to skip client-side
+    /// decorations when the server draws them. See [`WindowingInfo`] for the detection limits.
+    fn windowing_info(&self) -> WindowingInfo;
+
     /// Sets the [`ControlFlow`].
     fn set_control_flow(&self, control_flow: ControlFlow);
 
@@ -505,41 +636,2156 @@ impl EventLoopProxy {
     }
 }
 
-/// Control when device events are captured.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum DeviceEvents {
-    /// Report device events regardless of window focus.
-    Always,
-    /// Only capture device events while the window is focused.
-    #[default]
-    WhenFocused,
-    /// Never capture device events.
-    Never,
+/// Route events of different types from many senders into one channel, waking up the
+/// [`EventLoop`] for each of them.
+///
+/// [`EventLoopProxy`] only wakes up the event loop, so applications usually pair it with a
+/// channel carrying their own event type `T`, which they drain in
+/// [`ApplicationHandler::proxy_wake_up()`]. `ProxyRouter` does that pairing, and hands out a
+/// typed [`ProxySender<U>`] for every `U: Into<T>`, so that each subsystem can send its own
+/// event type without knowing about `T`.
+///
+/// `T` and `U` must be [`Send`], since the events are sent from other threads. The router and
+/// its senders are then [`Send`] and [`Sync`], and can be cloned freely.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::mpsc::Receiver;
+///
+/// use winit::application::ApplicationHandler;
+/// use winit::event::WindowEvent;
+/// use winit::event_loop::{ActiveEventLoop, EventLoop, ProxyRouter};
+/// use winit::window::WindowId;
+///
+/// enum UserEvent {
+///     Network(String),
+///     Timer(u32),
+/// }
+///
+/// impl From<String> for UserEvent {
+///     fn from(message: String) -> Self {
+///         Self::Network(message)
+///     }
+/// }
+///
+/// impl From<u32> for UserEvent {
+///     fn from(tick: u32) -> Self {
+///         Self::Timer(tick)
+///     }
+/// }
+///
+/// struct App {
+///     events: Receiver<UserEvent>,
+/// }
+///
+/// impl ApplicationHandler for App {
+///     fn proxy_wake_up(&mut self, _: &dyn ActiveEventLoop) {
+///         for event in self.events.try_iter() {
+///             match event {
+///                 UserEvent::Network(message) => println!("network: {message}"),
+///                 UserEvent::Timer(tick) => println!("timer: {tick}"),
+///             }
+///         }
+///     }
+///     // ...
+/// #   fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+/// #   fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+/// }
+///
+/// let event_loop = EventLoop::new().unwrap();
+/// let (router, events) = ProxyRouter::<UserEvent>::new(event_loop.create_proxy());
+///
+/// let network = router.sender::<String>();
+/// std::thread::spawn(move || network.send("connected".to_owned()));
+/// let timer = router.sender::<u32>();
+/// std::thread::spawn(move || timer.send(1));
+///
+/// event_loop.run_app(App { events }).unwrap();
+/// ```
+pub struct ProxyRouter<T> {
+    proxy: EventLoopProxy,
+    sender: mpsc::Sender<T>,
 }
 
-/// A unique identifier of the winit's async request.
+impl<T> Clone for ProxyRouter<T> {
+    fn clone(&self) -> Self {
+        Self { proxy: self.proxy.clone(), sender: self.sender.clone() }
+    }
+}
+
+impl<T> fmt::Debug for ProxyRouter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyRouter").finish_non_exhaustive()
+    }
+}
+
+impl<T: Send + 'static> ProxyRouter<T> {
+    /// Create a router waking up the event loop of `proxy`, and the [`Receiver`] its events are
+    /// delivered to.
+    ///
+    /// [`Receiver`]: mpsc::Receiver
+    pub fn new(proxy: EventLoopProxy) -> (Self, mpsc::Receiver<T>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self { proxy, sender }, receiver)
+    }
+
+    /// Create a sender for events of type `U`, which are converted to `T` when sent.
+    pub fn sender<U: Into<T>>(&self) -> ProxySender<U> {
+        let proxy = self.proxy.clone();
+        let sender = self.sender.clone();
+        ProxySender {
+            send: Arc::new(move |event: U| {
+                sender.send(event.into()).map_err(|_| RouterClosed)?;
+                proxy.wake_up();
+                Ok(())
+            }),
+        }
+    }
+
+    /// Send an event of type `T` directly and wake up the event loop.
+    pub fn send(&self, event: T) -> Result<(), RouterClosed> {
+        self.sender.send(event).map_err(|_| RouterClosed)?;
+        self.proxy.wake_up();
+        Ok(())
+    }
+}
+
+/// Send events of type `U` through a [`ProxyRouter`].
 ///
-/// This could be used to identify the async request once it's done
-/// and a specific action must be taken.
+/// Created with [`ProxyRouter::sender()`].
+pub struct ProxySender<U> {
+    send: Arc<dyn Fn(U) -> Result<(), RouterClosed> + Send + Sync>,
+}
+
+impl<U> Clone for ProxySender<U> {
+    fn clone(&self) -> Self {
+        Self { send: self.send.clone() }
+    }
+}
+
+impl<U> fmt::Debug for ProxySender<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxySender").finish_non_exhaustive()
+    }
+}
+
+impl<U> ProxySender<U> {
+    /// Convert `event` to the event type of the router, queue it and wake up the [`EventLoop`].
+    ///
+    /// Events are received in the order they were sent, across all senders of the same router.
+    /// If the event loop is no longer running, the event is queued but never handled.
+    pub fn send(&self, event: U) -> Result<(), RouterClosed> {
+        (self.send)(event)
+    }
+}
+
+/// The error returned when sending through a [`ProxyRouter`] whose [`Receiver`] was dropped.
 ///
-/// One of the handling scenarios could be to maintain a working list
-/// containing [`AsyncRequestSerial`] and some closure associated with it.
-/// Then once event is arriving the working list is being traversed and a job
-/// executed and removed from the list.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct AsyncRequestSerial {
-    serial: usize,
+/// [`Receiver`]: mpsc::Receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouterClosed;
+
+impl fmt::Display for RouterClosed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the receiving end of the proxy router was dropped")
+    }
 }
 
-impl AsyncRequestSerial {
-    // TODO(kchibisov): Remove `cfg` when the clipboard will be added.
-    #[allow(dead_code)]
-    pub(crate) fn get() -> Self {
-        static CURRENT_SERIAL: AtomicUsize = AtomicUsize::new(0);
-        // NOTE: We rely on wrap around here, while the user may just request
-        // in the loop usize::MAX times that's issue is considered on them.
-        let serial = CURRENT_SERIAL.fetch_add(1, Ordering::Relaxed);
-        Self { serial }
+impl std::error::Error for RouterClosed {}
+
+/// Inject synthetic [`WindowEvent`]s into the [`EventLoop`], possibly from a different thread.
+///
+/// The injected events are delivered to [`ApplicationHandler::window_event()`] like any other
+/// window event, right before the next [`ApplicationHandler::proxy_wake_up()`]. This allows
+/// exercising input handling deterministically, without OS-level automation.
+///
+/// This is intended for testing only: the events bypass the real hardware and the windowing
+/// system entirely, so the state winit tracks internally (e.g. focus, cursor position or
+/// modifiers) is not updated by them, and the [`WindowId`] is not validated.
+///
+/// Created with [`EventLoop::create_event_injector()`]. Only available with the `test-util` cargo
+/// feature.
+#[cfg(feature = "test-util")]
+#[derive(Clone)]
+pub struct EventInjector {
+    proxy: EventLoopProxy,
+    events: Arc<InjectedEvents>,
+}
+
+#[cfg(feature = "test-util")]
+impl fmt::Debug for EventInjector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventInjector").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl EventInjector {
+    /// Queue `event` for the window `window_id` and wake up the [`EventLoop`].
+    ///
+    /// Events are delivered in the order they were injected. If the event loop is no longer
+    /// running, the event is never delivered.
+    pub fn inject(&self, window_id: WindowId, event: WindowEvent) {
+        self.events.queue.lock().unwrap().push_back(InjectedEvent::Window(window_id, event));
+        self.proxy.wake_up();
+    }
+
+    /// Queue the key `event` for the window `window_id` and wake up the [`EventLoop`].
+    ///
+    /// The event is delivered as a [`WindowEvent::KeyboardInput`] with `is_synthetic` set to
+    /// `true`, without a device. Create it with [`KeyEvent::synthetic()`].
+    ///
+    /// The event bypasses the input method entirely, so no [`WindowEvent::Ime`] event is
+    /// generated even when IME is allowed, and the text of the event is delivered as is. Neither
+    /// are the modifiers updated: inject a [`WindowEvent::ModifiersChanged`] to simulate them.
+    ///
+    /// [`KeyEvent::synthetic()`]: crate::event::KeyEvent::synthetic
+    pub fn inject_key(&self, window_id: WindowId, event: crate::event::KeyEvent) {
+        self.inject(window_id, WindowEvent::KeyboardInput {
+            device_id: None,
+            event,
+            is_synthetic: true,
+        });
+    }
+
+    /// Queue the device `event` and wake up the [`EventLoop`].
+    ///
+    /// The event is delivered to [`ApplicationHandler::device_event()`], in order with the window
+    /// events, regardless of the [`DeviceEvents`] filter.
+    pub fn inject_device_event(&self, device_id: Option<DeviceId>, event: DeviceEvent) {
+        self.events.queue.lock().unwrap().push_back(InjectedEvent::Device(device_id, event));
+        self.proxy.wake_up();
+    }
+}
+
+#[cfg(feature = "test-util")]
+enum InjectedEvent {
+    Window(WindowId, WindowEvent),
+    Device(Option<DeviceId>, DeviceEvent),
+}
+
+/// The events queued by [`EventInjector`], shared with the event loop.
+#[cfg(feature = "test-util")]
+#[derive(Default)]
+pub(crate) struct InjectedEvents {
+    queue: Mutex<VecDeque<InjectedEvent>>,
+}
+
+/// Wraps the user's [`ApplicationHandler`] to deliver the injected events.
+#[cfg(feature = "test-util")]
+pub(crate) struct InjectingHandler<A> {
+    app: A,
+    events: Arc<InjectedEvents>,
+}
+
+#[cfg(feature = "test-util")]
+impl<A: ApplicationHandler> InjectingHandler<A> {
+    pub(crate) fn new(app: A, events: Arc<InjectedEvents>) -> Self {
+        Self { app, events }
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for InjectingHandler<A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        // Don't hold the lock while dispatching, the handler may inject more events.
+        loop {
+            let Some(event) = self.events.queue.lock().unwrap().pop_front() else {
+                break;
+            };
+            match event {
+                InjectedEvent::Window(window_id, event) => {
+                    self.app.window_event(event_loop, window_id, event)
+                },
+                InjectedEvent::Device(device_id, event) => {
+                    self.app.device_event(event_loop, device_id, event)
+                },
+            }
+        }
+
+        // The wake up may have been requested by the user as well, and spurious wake ups are
+        // allowed, so always forward it.
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    #[inline]
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) {
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[cfg(any(docsrs, macos_platform))]
+    #[inline]
+    fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+}
+
+/// An event captured by an [`EventRecorder`].
+///
+/// The `time` is relative to the first recorded event. With the `serde` cargo feature, recordings
+/// can be saved and loaded, to be replayed later with an [`EventPlayer`].
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RecordedEvent {
+    /// See [`ApplicationHandler::window_event()`].
+    Window {
+        time: Duration,
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw"))]
+        window_id: WindowId,
+        event: WindowEvent,
+    },
+    /// See [`ApplicationHandler::device_event()`].
+    Device {
+        time: Duration,
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_raw::option"))]
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    },
+}
+
+#[cfg(feature = "test-util")]
+impl RecordedEvent {
+    /// The time of the event, relative to the first recorded event.
+    pub fn time(&self) -> Duration {
+        match self {
+            Self::Window { time, .. } | Self::Device { time, .. } => *time,
+        }
+    }
+}
+
+/// Wraps an [`ApplicationHandler`] to record the window and device events delivered to it.
+///
+/// The recording can be replayed with an [`EventPlayer`], e.g. to reproduce a bug or to drive a
+/// UI test. Pass the recorder to [`EventLoop::run_app()`] by mutable reference to keep access to
+/// it once the event loop exits:
+///
+/// ```no_run
+/// # use winit::application::ApplicationHandler;
+/// # use winit::event_loop::{EventLoop, EventRecorder};
+/// # fn scope<A: ApplicationHandler>(app: A) {
+/// let event_loop = EventLoop::new().unwrap();
+/// let mut recorder = EventRecorder::new(app);
+/// event_loop.run_app(&mut recorder).unwrap();
+/// let events = recorder.into_events();
+/// # }
+/// ```
+///
+/// Replaying is not fully deterministic:
+///
+/// - Only the window and device events are recorded, the other callbacks of the handler, e.g.
+///   [`ApplicationHandler::about_to_wait()`], follow from the replaying event loop.
+/// - [`WindowId`]s, [`DeviceId`]s and [`FingerId`]s are only meaningful in the session they were
+///   recorded in. The windows have to be created in the same order, or the IDs of the recording
+///   rewritten, for the events to reach the corresponding windows.
+/// - The state winit tracks for the windows, e.g. focus, cursor position and modifiers, and the
+///   monitors and their handles are not part of the recording, see [`EventInjector`].
+/// - The [`SurfaceSizeWriter`] of a replayed [`WindowEvent::ScaleFactorChanged`] isn't connected to
+///   any window, and [`KeyEvent`]s only carry the platform-specific parts of
+///   [`KeyEvent::synthetic()`].
+///
+/// Only available with the `test-util` cargo feature.
+///
+/// [`FingerId`]: crate::event::FingerId
+/// [`SurfaceSizeWriter`]: crate::event::SurfaceSizeWriter
+/// [`KeyEvent`]: crate::event::KeyEvent
+/// [`KeyEvent::synthetic()`]: crate::event::KeyEvent::synthetic
+#[cfg(feature = "test-util")]
+#[derive(Debug)]
+pub struct EventRecorder<A> {
+    app: A,
+    start: Option<Instant>,
+    events: Vec<RecordedEvent>,
+}
+
+#[cfg(feature = "test-util")]
+impl<A> EventRecorder<A> {
+    /// Record the events delivered to `app`.
+    pub fn new(app: A) -> Self {
+        Self { app, start: None, events: Vec::new() }
+    }
+
+    /// The wrapped handler.
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// The wrapped handler.
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// The events recorded so far.
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    /// Returns the recorded events, dropping the wrapped handler.
+    pub fn into_events(self) -> Vec<RecordedEvent> {
+        self.events
+    }
+
+    fn elapsed(&mut self) -> Duration {
+        let now = Instant::now();
+        now.saturating_duration_since(*self.start.get_or_insert(now))
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for EventRecorder<A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        let time = self.elapsed();
+        self.events.push(RecordedEvent::Window { time, window_id, event: event.clone() });
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    fn device_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) {
+        let time = self.elapsed();
+        self.events.push(RecordedEvent::Device { time, device_id, event });
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[cfg(any(docsrs, macos_platform))]
+    #[inline]
+    fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+}
+
+/// Replays the events of an [`EventRecorder`] through an [`EventInjector`].
+///
+/// See [`EventRecorder`] for what is and isn't reproduced by replaying. Only available with the
+/// `test-util` cargo feature.
+///
+/// ## Platform-specific
+///
+/// - **Web:** Unsupported, as the thread can't be blocked between the events.
+#[cfg(all(feature = "test-util", not(web_platform)))]
+#[derive(Debug, Clone)]
+pub struct EventPlayer {
+    injector: EventInjector,
+    events: Vec<RecordedEvent>,
+}
+
+#[cfg(all(feature = "test-util", not(web_platform)))]
+impl EventPlayer {
+    /// Replay `events` into the event loop of `injector`.
+    pub fn new(injector: EventInjector, events: Vec<RecordedEvent>) -> Self {
+        Self { injector, events }
+    }
+
+    /// Inject the events at the cadence they were recorded at, blocking until the last one was
+    /// injected.
+    ///
+    /// This has to be called from another thread than the event loop's, which receives the
+    /// events while this is waiting for the next one. Each event is delivered on the next
+    /// iteration of the event loop after it was injected, so the cadence is only approximate.
+    pub fn play(&self) {
+        let start = Instant::now();
+        for event in &self.events {
+            let now = Instant::now();
+            let deadline = start + event.time();
+            if deadline > now {
+                std::thread::sleep(deadline - now);
+            }
+            match event.clone() {
+                RecordedEvent::Window { window_id, event, .. } => {
+                    self.injector.inject(window_id, event)
+                },
+                RecordedEvent::Device { device_id, event, .. } => {
+                    self.injector.inject_device_event(device_id, event)
+                },
+            }
+        }
+    }
+}
+
+/// Frame timing statistics collected by the event loop.
+///
+/// Each value is a rolling average over the last [`FrameStats::WINDOW`] samples. Returned by
+/// [`ActiveEventLoop::frame_stats()`][dyn ActiveEventLoop::frame_stats], and only collected when
+/// enabled with [`EventLoopBuilder::with_frame_stats()`]; otherwise all values are zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// The time between the starts of two consecutive [`WindowEvent::RedrawRequested`], across
+    /// all windows.
+    pub redraw_interval: Duration,
+    /// The time spent in [`ApplicationHandler::window_event()`] handling
+    /// [`WindowEvent::RedrawRequested`].
+    pub handler_duration: Duration,
+    /// The time the event loop spent waiting for new events, from
+    /// [`ApplicationHandler::about_to_wait()`] returning to the next
+    /// [`ApplicationHandler::new_events()`].
+    pub wait_duration: Duration,
+}
+
+impl FrameStats {
+    /// The number of samples the rolling averages are computed over.
+    pub const WINDOW: usize = 60;
+}
+
+/// The state Winit keeps for each event loop on top of the one of the backend, see
+/// [`CommonWindowState`].
+#[derive(Debug, Default)]
+pub(crate) struct CommonEventLoopState {
+    /// The windows created by the event loop, until their [`WindowEvent::Destroyed`] was
+    /// delivered.
+    windows: Mutex<HashMap<WindowId, Arc<CommonWindowState>>>,
+    /// The statistics of the event loop, present once the [`FrameStatsHandler`] recorded some.
+    frame_stats: Mutex<Option<FrameStatsRecorder>>,
+}
+
+impl CommonEventLoopState {
+    /// The state of `event_loop`, or `None` if it isn't the one of a backend.
+    pub(crate) fn of(event_loop: &dyn ActiveEventLoop) -> Option<&Self> {
+        platform_impl::common_event_loop_state(event_loop)
+    }
+
+    /// Register a newly created window, called by the backends.
+    ///
+    /// This replaces the state of a destroyed window that had the same ID.
+    pub(crate) fn register_window(&self, window: &dyn Window) {
+        if let Some(state) = platform_impl::common_window_state(window) {
+            self.windows.lock().unwrap().insert(window.id(), state.clone());
+        }
+    }
+
+    pub(crate) fn window(&self, window_id: WindowId) -> Option<Arc<CommonWindowState>> {
+        self.windows.lock().unwrap().get(&window_id).cloned()
+    }
+
+    fn forget_window(&self, window_id: WindowId) {
+        self.windows.lock().unwrap().remove(&window_id);
+    }
+}
+
+impl dyn ActiveEventLoop + '_ {
+    /// The [`FrameStats`] of the event loop.
+    ///
+    /// Only collected when enabled with [`EventLoopBuilder::with_frame_stats()`], otherwise this
+    /// returns [`FrameStats::default()`].
+    pub fn frame_stats(&self) -> FrameStats {
+        CommonEventLoopState::of(self)
+            .and_then(|state| {
+                state.frame_stats.lock().unwrap().as_ref().map(FrameStatsRecorder::stats)
+            })
+            .unwrap_or_default()
+    }
+
+    /// The tag of the window with the given ID, see [`WindowAttributes::with_user_tag()`].
+    ///
+    /// This is still available while handling the [`WindowEvent::Destroyed`] of the window, and
+    /// `None` afterwards, or for windows created without a tag.
+    pub fn window_tag(&self, window_id: WindowId) -> Option<u64> {
+        let state = CommonEventLoopState::of(self)?;
+        state.window(window_id).and_then(|window| window.user_tag)
+    }
+}
+
+/// A rolling average over the last [`FrameStats::WINDOW`] durations.
+#[derive(Debug, Default)]
+struct RollingAverage {
+    samples: VecDeque<Duration>,
+    sum: Duration,
+}
+
+impl RollingAverage {
+    fn push(&mut self, sample: Duration) {
+        if self.samples.len() == FrameStats::WINDOW {
+            self.sum -= self.samples.pop_front().unwrap();
+        }
+        self.samples.push_back(sample);
+        self.sum += sample;
+    }
+
+    fn average(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::ZERO,
+            len => self.sum / len as u32,
+        }
+    }
+}
+
+/// Accumulates the [`FrameStats`] from the timestamps reported by [`FrameStatsHandler`].
+#[derive(Debug, Default)]
+pub(crate) struct FrameStatsRecorder {
+    last_redraw: Option<Instant>,
+    wait_start: Option<Instant>,
+    redraw_interval: RollingAverage,
+    handler_duration: RollingAverage,
+    wait_duration: RollingAverage,
+}
+
+impl FrameStatsRecorder {
+    fn redraw_started(&mut self, now: Instant) {
+        if let Some(last) = self.last_redraw.replace(now) {
+            self.redraw_interval.push(now.saturating_duration_since(last));
+        }
+    }
+
+    fn redraw_finished(&mut self, start: Instant, now: Instant) {
+        self.handler_duration.push(now.saturating_duration_since(start));
+    }
+
+    fn wait_started(&mut self, now: Instant) {
+        self.wait_start = Some(now);
+    }
+
+    fn wait_finished(&mut self, now: Instant) {
+        if let Some(start) = self.wait_start.take() {
+            self.wait_duration.push(now.saturating_duration_since(start));
+        }
+    }
+
+    fn stats(&self) -> FrameStats {
+        FrameStats {
+            redraw_interval: self.redraw_interval.average(),
+            handler_duration: self.handler_duration.average(),
+            wait_duration: self.wait_duration.average(),
+        }
+    }
+}
+
+fn record_frame_stats(event_loop: &dyn ActiveEventLoop, f: impl FnOnce(&mut FrameStatsRecorder)) {
+    if let Some(state) = CommonEventLoopState::of(event_loop) {
+        f(state.frame_stats.lock().unwrap().get_or_insert_with(Default::default));
+    }
+}
+
+/// The options of the [`EventLoopBuilder`] that are implemented by wrapping the user's
+/// [`ApplicationHandler`], whichever way the event loop is run.
+#[derive(Clone)]
+pub(crate) struct HandlerOptions {
+    frame_stats: bool,
+    pointer_move_coalescing: bool,
+    key_overrides: KeyOverrides,
+    #[cfg(feature = "test-util")]
+    injected_events: Arc<InjectedEvents>,
+}
+
+impl HandlerOptions {
+    /// Wraps `app` in the handlers implementing the options.
+    pub(crate) fn wrap<A: ApplicationHandler>(&self, app: A) -> impl ApplicationHandler {
+        let app = WindowTrackingHandler::new(app, self.key_overrides);
+        let app = PointerMoveCoalescingHandler::new(app, self.pointer_move_coalescing);
+        let app = FrameStatsHandler::new(app, self.frame_stats);
+        #[cfg(feature = "test-util")]
+        let app = InjectingHandler::new(app, self.injected_events.clone());
+        app
+    }
+
+    /// Creates an [`EventInjector`] for the event loop that `proxy` wakes up.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn create_event_injector(&self, proxy: EventLoopProxy) -> EventInjector {
+        EventInjector { proxy, events: self.injected_events.clone() }
+    }
+}
+
+/// A pointer move held back by the [`PointerMoveCoalescingHandler`].
+struct PendingPointerMove {
+    window_id: WindowId,
+    device_id: Option<DeviceId>,
+    kind: PointerKind,
+    event: WindowEvent,
+    history: Vec<PhysicalPosition<f64>>,
+}
+
+/// Wraps the user's [`ApplicationHandler`] to coalesce the pointer moves, see
+/// [`EventLoopBuilder::with_pointer_move_coalescing()`].
+pub(crate) struct PointerMoveCoalescingHandler<A> {
+    app: A,
+    enabled: bool,
+    pending: Vec<PendingPointerMove>,
+}
+
+impl<A: ApplicationHandler> PointerMoveCoalescingHandler<A> {
+    pub(crate) fn new(app: A, enabled: bool) -> Self {
+        Self { app, enabled, pending: Vec::new() }
+    }
+
+    /// Deliver the pending moves of the windows matching `filter`, in the order they started.
+    fn flush(&mut self, event_loop: &dyn ActiveEventLoop, filter: impl Fn(WindowId) -> bool) {
+        let (flushed, pending) = mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|pending: &PendingPointerMove| filter(pending.window_id));
+        self.pending = pending;
+        let state = CommonEventLoopState::of(event_loop);
+        for PendingPointerMove { window_id, event, history, .. } in flushed {
+            if let Some(window) = state.and_then(|state| state.window(window_id)) {
+                *window.pointer_move_history.lock().unwrap() = history;
+            }
+            self.app.window_event(event_loop, window_id, event);
+        }
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for PointerMoveCoalescingHandler<A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        if !self.enabled {
+            return self.app.window_event(event_loop, window_id, event);
+        }
+
+        let WindowEvent::PointerMoved { device_id, position, ref source, .. } = event else {
+            self.flush(event_loop, |id| id == window_id);
+            return self.app.window_event(event_loop, window_id, event);
+        };
+
+        let kind = PointerKind::from(source.clone());
+        match self.pending.iter_mut().find(|pending| {
+            pending.window_id == window_id && pending.device_id == device_id && pending.kind == kind
+        }) {
+            Some(pending) => {
+                pending.event = event;
+                pending.history.push(position);
+            },
+            None => self.pending.push(PendingPointerMove {
+                window_id,
+                device_id,
+                kind,
+                event,
+                history: vec![position],
+            }),
+        }
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) {
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.flush(event_loop, |_| true);
+        self.app.about_to_wait(event_loop);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.flush(event_loop, |_| true);
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[cfg(any(docsrs, macos_platform))]
+    #[inline]
+    fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+}
+
+/// Wraps the user's [`ApplicationHandler`] to collect the [`FrameStats`].
+pub(crate) struct FrameStatsHandler<A> {
+    app: A,
+    enabled: bool,
+}
+
+impl<A: ApplicationHandler> FrameStatsHandler<A> {
+    pub(crate) fn new(app: A, enabled: bool) -> Self {
+        Self { app, enabled }
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for FrameStatsHandler<A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
+        if self.enabled {
+            let now = Instant::now();
+            record_frame_stats(event_loop, |recorder| recorder.wait_finished(now));
+        }
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        if !self.enabled || !matches!(event, WindowEvent::RedrawRequested) {
+            return self.app.window_event(event_loop, window_id, event);
+        }
+
+        // Don't hold the lock while dispatching, the handler may query the stats.
+        let start = Instant::now();
+        record_frame_stats(event_loop, |recorder| recorder.redraw_started(start));
+        self.app.window_event(event_loop, window_id, event);
+        let now = Instant::now();
+        record_frame_stats(event_loop, |recorder| recorder.redraw_finished(start, now));
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) {
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+        if self.enabled {
+            let now = Instant::now();
+            record_frame_stats(event_loop, |recorder| recorder.wait_started(now));
+        }
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[cfg(any(docsrs, macos_platform))]
+    #[inline]
+    fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+}
+
+/// See [`EventLoopBuilder::with_key_code_override()`].
+type KeyCodeOverride = fn(NativeKeyCode) -> Option<KeyCode>;
+
+/// Identify `physical_key` with the `key_code_override`, see
+/// [`EventLoopBuilder::with_key_code_override()`].
+fn override_key_code(physical_key: &mut PhysicalKey, key_code_override: Option<KeyCodeOverride>) {
+    let Some(key_code_override) = key_code_override else { return };
+    if let Some(code) = platform_impl::native_key_code(*physical_key).and_then(key_code_override) {
+        *physical_key = PhysicalKey::Code(code);
+    }
+}
+
+/// See [`EventLoopBuilder::with_key_override()`].
+type KeyOverride = fn(&NativeKey) -> Option<Key>;
+
+/// The key overrides set on the [`EventLoopBuilder`].
+///
+/// The overrides are compared and hashed by address, so that the builder keeps implementing
+/// `PartialEq`, `Eq` and `Hash`.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct KeyOverrides {
+    /// See [`EventLoopBuilder::with_key_code_override()`].
+    key_code: Option<KeyCodeOverride>,
+    /// See [`EventLoopBuilder::with_key_override()`].
+    key: Option<KeyOverride>,
+}
+
+impl KeyOverrides {
+    // FIXME: Use `std::ptr::fn_addr_eq` (Rust 1.85) once available in MSRV.
+    fn addresses(&self) -> (Option<usize>, Option<usize>) {
+        (self.key_code.map(|f| f as usize), self.key.map(|f| f as usize))
+    }
+}
+
+impl PartialEq for KeyOverrides {
+    fn eq(&self, other: &Self) -> bool {
+        self.addresses() == other.addresses()
+    }
+}
+
+impl Eq for KeyOverrides {}
+
+impl std::hash::Hash for KeyOverrides {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.addresses().hash(state);
+    }
+}
+
+/// Identify `key` with the `key_override`, see [`EventLoopBuilder::with_key_override()`].
+fn override_key(key: &mut Key, key_override: Option<KeyOverride>) {
+    if let (Key::Unidentified(native), Some(key_override)) = (&*key, key_override) {
+        if let Some(identified) = key_override(native) {
+            *key = identified;
+        }
+    }
+}
+
+/// Wraps the user's [`ApplicationHandler`] to deliver the notifications of the
+/// [`ResizeReceiver`][crate::window::ResizeReceiver]s, to identify the overridden keys, to track
+/// the keys held down in each window, to filter key repeats and to forget the state of the
+/// destroyed windows.
+pub(crate) struct WindowTrackingHandler<A> {
+    app: A,
+    key_overrides: KeyOverrides,
+}
+
+impl<A: ApplicationHandler> WindowTrackingHandler<A> {
+    pub(crate) fn new(app: A, key_overrides: KeyOverrides) -> Self {
+        Self { app, key_overrides }
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for WindowTrackingHandler<A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    #[inline]
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        mut event: WindowEvent,
+    ) {
+        if let WindowEvent::KeyboardInput { event, .. } = &mut event {
+            override_key_code(&mut event.physical_key, self.key_overrides.key_code);
+            override_key(&mut event.logical_key, self.key_overrides.key);
+            override_key(&mut event.key_without_modifiers, self.key_overrides.key);
+        }
+        // Only look the window up for the events it tracks, this runs for every pointer move too.
+        let tracked = matches!(
+            event,
+            WindowEvent::KeyboardInput { .. }
+                | WindowEvent::Focused(false)
+                | WindowEvent::Destroyed
+                | WindowEvent::SurfaceResized(_)
+                | WindowEvent::ScaleFactorChanged { .. }
+        );
+        if tracked {
+            let window =
+                CommonEventLoopState::of(event_loop).and_then(|state| state.window(window_id));
+            if let Some(window) = window {
+                window.notify_resize(&event);
+                window.track_held_keys(&event);
+                if !window.filter_key_repeat(&event) {
+                    return;
+                }
+            }
+        }
+        let destroyed = matches!(event, WindowEvent::Destroyed);
+        self.app.window_event(event_loop, window_id, event);
+        if destroyed {
+            // The state, including the tag, remains available to the handler of the event.
+            if let Some(state) = CommonEventLoopState::of(event_loop) {
+                state.forget_window(window_id);
+            }
+        }
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        mut event: DeviceEvent,
+    ) {
+        if let DeviceEvent::Key(event) = &mut event {
+            override_key_code(&mut event.physical_key, self.key_overrides.key_code);
+        }
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[cfg(any(docsrs, macos_platform))]
+    #[inline]
+    fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+}
+
+/// Information about the windowing system, see [`ActiveEventLoop::windowing_info`].
+///
+/// The information is gathered once per call and describes the environment at that time; a window
+/// manager may be replaced while the application is running.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WindowingInfo {
+    pub(crate) backend: WindowingBackend,
+    pub(crate) name: Option<String>,
+    pub(crate) capabilities: WindowingCapabilities,
+}
+
+impl WindowingInfo {
+    pub(crate) fn new(
+        backend: WindowingBackend,
+        name: Option<String>,
+        capabilities: WindowingCapabilities,
+    ) -> Self {
+        Self { backend, name, capabilities }
+    }
+
+    /// The backend the event loop uses.
+    pub fn backend(&self) -> WindowingBackend {
+        self.backend
+    }
+
+    /// The name of the window manager or compositor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The `_NET_WM_NAME` of the window advertised through `_NET_SUPPORTING_WM_CHECK`.
+    ///   Returns `None` when no window manager is running or it doesn't follow the EWMH
+    ///   specification, like xmonad and dwm. Some window managers append version information to
+    ///   their name.
+    /// - **Wayland:** Always returns `None`, the protocol doesn't identify the compositor.
+    /// - **macOS / iOS / Windows / Web / Android / Orbital:** Always returns `None`, there is only
+    ///   one window manager.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// What the window manager or compositor supports.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Derived from the hints in `_NET_SUPPORTED` and thus only as accurate as the
+    ///   window manager's advertisement; server-side decorations are assumed whenever a window
+    ///   manager is detected.
+    /// - **Wayland:** Derived from the advertised globals. Server-side decorations are reported
+    ///   when `zxdg_decoration_manager_v1` is available, though the compositor may still prefer
+    ///   client-side decorations for a particular window. Always-on-top is never supported.
+    pub fn capabilities(&self) -> WindowingCapabilities {
+        self.capabilities
+    }
+}
+
+/// The windowing backend of an event loop, see [`WindowingInfo::backend`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowingBackend {
+    AppKit,
+    UIKit,
+    Windows,
+    X11,
+    Wayland,
+    Web,
+    Android,
+    Orbital,
+    /// The in-memory backend of the `headless` cargo feature.
+    Headless,
+}
+
+bitflags::bitflags! {
+    /// Capabilities of the window manager or compositor, see [`WindowingInfo::capabilities`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct WindowingCapabilities: u32 {
+        /// Window decorations are drawn by the server, see [`Window::set_decorations`].
+        ///
+        /// [`Window::set_decorations`]: crate::window::Window::set_decorations
+        const SERVER_SIDE_DECORATIONS = 1 << 0;
+        /// Interactive moves and resizes can be started, see [`Window::drag_window`] and
+        /// [`Window::drag_resize_window`].
+        ///
+        /// [`Window::drag_window`]: crate::window::Window::drag_window
+        /// [`Window::drag_resize_window`]: crate::window::Window::drag_resize_window
+        const MOVE_RESIZE = 1 << 1;
+        /// Windows can be kept above others, see [`WindowLevel::AlwaysOnTop`].
+        ///
+        /// [`WindowLevel::AlwaysOnTop`]: crate::window::WindowLevel::AlwaysOnTop
+        const ALWAYS_ON_TOP = 1 << 2;
+    }
+}
+
+/// Control when device events are captured.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceEvents {
+    /// Report device events regardless of window focus.
+    Always,
+    /// Only capture device events while the window is focused.
+    #[default]
+    WhenFocused,
+    /// Never capture device events.
+    Never,
+}
+
+/// A unique identifier of the winit's async request.
+///
+/// This could be used to identify the async request once it's done
+/// and a specific action must be taken.
+///
+/// One of the handling scenarios could be to maintain a working list
+/// containing [`AsyncRequestSerial`] and some closure associated with it.
+/// Then once event is arriving the working list is being traversed and a job
+/// executed and removed from the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsyncRequestSerial {
+    serial: usize,
+}
+
+impl AsyncRequestSerial {
+    // TODO(kchibisov): Remove `cfg` when the clipboard will be added.
+    #[allow(dead_code)]
+    pub(crate) fn get() -> Self {
+        static CURRENT_SERIAL: AtomicUsize = AtomicUsize::new(0);
+        // NOTE: We rely on wrap around here, while the user may just request
+        // in the loop usize::MAX times that's issue is considered on them.
+        let serial = CURRENT_SERIAL.fetch_add(1, Ordering::Relaxed);
+        Self { serial }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::utils::serde_raw::Raw for AsyncRequestSerial {
+    type Raw = usize;
+
+    fn to_raw(&self) -> usize {
+        self.serial
+    }
+
+    fn from_raw(serial: usize) -> Self {
+        Self { serial }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+    #[cfg(feature = "test-util")]
+    use crate::event::{ButtonSource, ElementState, KeyEvent, MouseButton};
+    #[cfg(feature = "test-util")]
+    use crate::keyboard::{Key, KeyCode, KeyLocation, PhysicalKey};
+
+    #[derive(Default)]
+    struct MockProxy(AtomicUsize);
+
+    impl EventLoopProxyProvider for MockProxy {
+        fn wake_up(&self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    struct MockEventLoop(Arc<MockProxy>);
+
+    impl ActiveEventLoop for MockEventLoop {
+        fn create_proxy(&self) -> EventLoopProxy {
+            EventLoopProxy::new(self.0.clone())
+        }
+
+        fn create_window(&self, _: WindowAttributes) -> Result<Box<dyn Window>, RequestError> {
+            unimplemented!()
+        }
+
+        fn create_custom_cursor(
+            &self,
+            _: CustomCursorSource,
+        ) -> Result<CustomCursor, RequestError> {
+            unimplemented!()
+        }
+
+        fn available_monitors(&self) -> Box<dyn Iterator<Item = MonitorHandle>> {
+            Box::new(std::iter::empty())
+        }
+
+        fn primary_monitor(&self) -> Option<MonitorHandle> {
+            None
+        }
+
+        fn listen_device_events(&self, _: DeviceEvents) {}
+
+        fn system_theme(&self) -> Option<Theme> {
+            None
+        }
+
+        fn windowing_info(&self) -> WindowingInfo {
+            WindowingInfo::new(WindowingBackend::X11, None, WindowingCapabilities::empty())
+        }
+
+        fn set_control_flow(&self, _: ControlFlow) {}
+
+        fn control_flow(&self) -> ControlFlow {
+            ControlFlow::default()
+        }
+
+        fn exit(&self) {}
+
+        fn exiting(&self) -> bool {
+            false
+        }
+
+        fn owned_display_handle(&self) -> OwnedDisplayHandle {
+            unimplemented!()
+        }
+
+        fn rwh_06_handle(&self) -> &dyn HasDisplayHandle {
+            unimplemented!()
+        }
+    }
+
+    #[cfg(feature = "test-util")]
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<(WindowId, WindowEvent)>,
+        device_events: Vec<(Option<DeviceId>, DeviceEvent)>,
+        wake_ups: usize,
+    }
+
+    #[cfg(feature = "test-util")]
+    impl ApplicationHandler for Recorder {
+        fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+        fn proxy_wake_up(&mut self, _: &dyn ActiveEventLoop) {
+            self.wake_ups += 1;
+        }
+
+        fn window_event(&mut self, _: &dyn ActiveEventLoop, id: WindowId, event: WindowEvent) {
+            self.events.push((id, event));
+        }
+
+        fn device_event(
+            &mut self,
+            _: &dyn ActiveEventLoop,
+            device_id: Option<DeviceId>,
+            event: DeviceEvent,
+        ) {
+            self.device_events.push((device_id, event));
+        }
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn injected_events_reach_handler() {
+        let event_loop = MockEventLoop(Default::default());
+        let events = Arc::<InjectedEvents>::default();
+        let injector = EventInjector { proxy: event_loop.create_proxy(), events: events.clone() };
+        let mut handler = InjectingHandler::new(Recorder::default(), events);
+
+        let window_id = WindowId::from_raw(1);
+        let key = WindowEvent::KeyboardInput {
+            device_id: None,
+            event: KeyEvent::synthetic(
+                PhysicalKey::Code(KeyCode::KeyA),
+                Key::Character("a".into()),
+                Some("a".into()),
+                KeyLocation::Standard,
+                ElementState::Pressed,
+                false,
+            ),
+            is_synthetic: false,
+        };
+        let pointer = WindowEvent::PointerButton {
+            device_id: None,
+            state: ElementState::Pressed,
+            position: PhysicalPosition::new(10., 20.),
+            primary: true,
+            button: ButtonSource::Mouse(MouseButton::Left),
+            timestamp: None,
+        };
+        injector.inject(window_id, key.clone());
+        injector.inject(window_id, pointer.clone());
+        assert_eq!(event_loop.0 .0.load(Ordering::Relaxed), 2);
+
+        handler.proxy_wake_up(&event_loop);
+        assert_eq!(handler.app.events, [(window_id, key), (window_id, pointer)]);
+        assert_eq!(handler.app.wake_ups, 1);
+
+        // The queue is drained.
+        handler.proxy_wake_up(&event_loop);
+        assert_eq!(handler.app.events.len(), 2);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn injected_keys_are_synthetic() {
+        let event_loop = MockEventLoop(Default::default());
+        let events = Arc::<InjectedEvents>::default();
+        let injector = EventInjector { proxy: event_loop.create_proxy(), events: events.clone() };
+        let mut handler = InjectingHandler::new(Recorder::default(), events);
+
+        let window_id = WindowId::from_raw(1);
+        let event = KeyEvent::synthetic(
+            PhysicalKey::Code(KeyCode::KeyB),
+            Key::Character("b".into()),
+            Some("b".into()),
+            KeyLocation::Standard,
+            ElementState::Released,
+            false,
+        );
+        injector.inject_key(window_id, event.clone());
+        assert_eq!(event_loop.0 .0.load(Ordering::Relaxed), 1);
+
+        handler.proxy_wake_up(&event_loop);
+        let key = WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: true };
+        assert_eq!(handler.app.events, [(window_id, key)]);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn recorded_events_replay() {
+        let event_loop = MockEventLoop(Default::default());
+        let mut recorder = EventRecorder::new(Recorder::default());
+
+        let window_id = WindowId::from_raw(784);
+        let device_id = Some(DeviceId::from_raw(1));
+        recorder.window_event(&event_loop, window_id, WindowEvent::Focused(true));
+        recorder
+            .device_event(&event_loop, device_id, DeviceEvent::PointerMotion { delta: (1., -2.) });
+        std::thread::sleep(Duration::from_millis(20));
+        recorder.window_event(&event_loop, window_id, WindowEvent::KeyboardInput {
+            device_id,
+            event: KeyEvent::synthetic(
+                PhysicalKey::Code(KeyCode::KeyA),
+                Key::Character("a".into()),
+                Some("a".into()),
+                KeyLocation::Standard,
+                ElementState::Pressed,
+                false,
+            ),
+            is_synthetic: false,
+        });
+
+        let events = recorder.events().to_vec();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].time(), Duration::ZERO);
+        assert!(events[2].time() >= Duration::from_millis(20));
+        let original = recorder.app();
+
+        // Replay to an identical handler.
+        let events_queue = Arc::<InjectedEvents>::default();
+        let injector =
+            EventInjector { proxy: event_loop.create_proxy(), events: events_queue.clone() };
+        let mut handler = InjectingHandler::new(Recorder::default(), events_queue);
+        let start = Instant::now();
+        EventPlayer::new(injector, events).play();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        handler.proxy_wake_up(&event_loop);
+        assert_eq!(handler.app.events, original.events);
+        assert_eq!(handler.app.device_events, original.device_events);
+    }
+
+    #[test]
+    fn proxy_router_wraps_sub_senders() {
+        #[derive(Debug, PartialEq)]
+        enum UserEvent {
+            Network(String),
+            Timer(u32),
+        }
+
+        impl From<String> for UserEvent {
+            fn from(message: String) -> Self {
+                Self::Network(message)
+            }
+        }
+
+        impl From<u32> for UserEvent {
+            fn from(tick: u32) -> Self {
+                Self::Timer(tick)
+            }
+        }
+
+        let event_loop = MockEventLoop(Default::default());
+        let (router, receiver) = ProxyRouter::<UserEvent>::new(event_loop.create_proxy());
+        let network = router.sender::<String>();
+        let timer = router.sender::<u32>();
+
+        std::thread::spawn(move || network.send("connected".to_owned()).unwrap()).join().unwrap();
+        timer.send(1).unwrap();
+        router.send(UserEvent::Timer(2)).unwrap();
+        assert_eq!(event_loop.0 .0.load(Ordering::Relaxed), 3);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [
+            UserEvent::Network("connected".to_owned()),
+            UserEvent::Timer(1),
+            UserEvent::Timer(2),
+        ]);
+
+        drop(receiver);
+        assert_eq!(timer.send(3), Err(RouterClosed));
+        assert_eq!(event_loop.0 .0.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    #[cfg(headless_platform)]
+    fn resize_notifications_reach_receiver() {
+        use crate::dpi::PhysicalSize;
+        use crate::window::ResizeNotification;
+
+        #[derive(Default)]
+        struct Counter(usize);
+
+        impl ApplicationHandler for Counter {
+            fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+            fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {
+                self.0 += 1;
+            }
+        }
+
+        // A real event loop to create the windows with, it isn't run.
+        let headless = platform_impl::headless::EventLoop::new();
+        let event_loop = headless.window_target();
+        let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let other_window = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let mut handler = WindowTrackingHandler::new(Counter::default(), KeyOverrides::default());
+        let receiver = window.resize_notifier();
+        let other = other_window.resize_notifier();
+
+        let (size, other_size, smaller) =
+            (PhysicalSize::new(640, 480), PhysicalSize::new(800, 600), PhysicalSize::new(320, 240));
+        handler.window_event(event_loop, window.id(), WindowEvent::SurfaceResized(size));
+        handler.window_event(event_loop, window.id(), WindowEvent::Focused(true));
+        handler.window_event(
+            event_loop,
+            other_window.id(),
+            WindowEvent::SurfaceResized(other_size),
+        );
+
+        // The handler still receives all events.
+        assert_eq!(handler.app.0, 3);
+        assert_eq!(receiver.try_recv(), Some(ResizeNotification::SurfaceResized(size)));
+        assert_eq!(receiver.try_recv(), None);
+        assert_eq!(other.try_recv(), Some(ResizeNotification::SurfaceResized(other_size)));
+
+        // The receiver is disconnected once the window is destroyed, even though the window is
+        // still alive.
+        let receiver = std::thread::spawn(move || {
+            let notifications: Vec<_> = std::iter::from_fn(|| receiver.recv()).collect();
+            notifications
+        });
+        handler.window_event(event_loop, window.id(), WindowEvent::SurfaceResized(smaller));
+        handler.window_event(event_loop, window.id(), WindowEvent::Destroyed);
+        assert_eq!(receiver.join().unwrap(), [ResizeNotification::SurfaceResized(smaller)]);
+        assert_eq!(other.try_recv(), None);
+    }
+
+    #[test]
+    #[cfg(headless_platform)]
+    fn held_keys_follow_keyboard_input() {
+        use std::collections::HashSet;
+
+        use crate::event::{ElementState, KeyEvent};
+        use crate::keyboard::{Key, KeyCode, KeyLocation, PhysicalKey};
+        use crate::platform_impl::KeyEventExtra;
+
+        struct NoOp;
+
+        impl ApplicationHandler for NoOp {
+            fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+            fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+        }
+
+        fn input(code: KeyCode, state: ElementState) -> WindowEvent {
+            let logical_key = Key::Character("a".into());
+            WindowEvent::KeyboardInput {
+                device_id: None,
+                event: KeyEvent {
+                    physical_key: PhysicalKey::Code(code),
+                    platform_specific: KeyEventExtra::synthetic(None),
+                    key_without_modifiers: logical_key.clone(),
+                    logical_key,
+                    text: None,
+                    composed: false,
+                    location: KeyLocation::Standard,
+                    state,
+                    repeat: false,
+                    timestamp: None,
+                },
+                is_synthetic: false,
+            }
+        }
+
+        let set = |codes: &[KeyCode]| {
+            codes.iter().map(|&code| PhysicalKey::Code(code)).collect::<HashSet<_>>()
+        };
+
+        // A real event loop to create the windows with, it isn't run.
+        let headless = platform_impl::headless::EventLoop::new();
+        let event_loop = headless.window_target();
+        let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let other = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let mut handler = WindowTrackingHandler::new(NoOp, KeyOverrides::default());
+        let mut deliver = |window: &dyn Window, event| {
+            handler.window_event(event_loop, window.id(), event);
+            window.held_keys()
+        };
+
+        deliver(&*window, input(KeyCode::KeyW, ElementState::Pressed));
+        deliver(&*window, input(KeyCode::ShiftLeft, ElementState::Pressed));
+        deliver(&*window, input(KeyCode::KeyW, ElementState::Pressed));
+        deliver(&*other, input(KeyCode::KeyA, ElementState::Pressed));
+        assert_eq!(window.held_keys(), set(&[KeyCode::KeyW, KeyCode::ShiftLeft]));
+        assert_eq!(other.held_keys(), set(&[KeyCode::KeyA]));
+
+        let held = deliver(&*window, input(KeyCode::KeyW, ElementState::Released));
+        assert_eq!(held, set(&[KeyCode::ShiftLeft]));
+
+        // Losing focus forgets the keys, as their releases won't be delivered.
+        assert_eq!(deliver(&*window, WindowEvent::Focused(false)), set(&[]));
+        let held = deliver(&*window, input(KeyCode::ShiftLeft, ElementState::Released));
+        assert_eq!(held, set(&[]));
+
+        assert_eq!(deliver(&*other, WindowEvent::Destroyed), set(&[]));
+    }
+
+    #[test]
+    #[cfg(headless_platform)]
+    fn pointer_moves_coalesced_per_iteration() {
+        use crate::event::PointerSource;
+
+        #[derive(Debug, PartialEq)]
+        enum Delivered {
+            Moved(WindowId, f64, Vec<f64>),
+            Focused(WindowId),
+            AboutToWait,
+        }
+
+        struct Recorder(Vec<Box<dyn Window>>, Vec<Delivered>);
+
+        impl ApplicationHandler for Recorder {
+            fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+            fn window_event(&mut self, _: &dyn ActiveEventLoop, id: WindowId, event: WindowEvent) {
+                match event {
+                    WindowEvent::PointerMoved { position, .. } => {
+                        let window = self.0.iter().find(|window| window.id() == id).unwrap();
+                        let history = window.pointer_move_history();
+                        let history = history.iter().map(|position| position.x).collect();
+                        self.1.push(Delivered::Moved(id, position.x, history));
+                    },
+                    WindowEvent::Focused(_) => self.1.push(Delivered::Focused(id)),
+                    _ => (),
+                }
+            }
+
+            fn about_to_wait(&mut self, _: &dyn ActiveEventLoop) {
+                self.1.push(Delivered::AboutToWait);
+            }
+        }
+
+        let moved = |x| WindowEvent::PointerMoved {
+            device_id: None,
+            position: PhysicalPosition::new(x, 0.0),
+            primary: true,
+            source: PointerSource::Mouse,
+            timestamp: None,
+        };
+
+        // A real event loop to create the windows with, it isn't run.
+        let headless = platform_impl::headless::EventLoop::new();
+        let event_loop = headless.window_target();
+        let windows: Vec<_> = (0..3)
+            .map(|_| event_loop.create_window(WindowAttributes::default()).unwrap())
+            .collect();
+        let [first, second, third] = [0, 1, 2].map(|index| windows[index].id());
+        let mut handler = PointerMoveCoalescingHandler::new(
+            WindowTrackingHandler::new(Recorder(windows, Vec::new()), KeyOverrides::default()),
+            true,
+        );
+        for x in [1.0, 2.0, 3.0] {
+            handler.window_event(event_loop, first, moved(x));
+        }
+        handler.window_event(event_loop, second, moved(10.0));
+        assert!(handler.app.app.1.is_empty());
+
+        // Other events of the window deliver its pending move first.
+        handler.window_event(event_loop, first, WindowEvent::Focused(true));
+        handler.window_event(event_loop, first, moved(4.0));
+        handler.window_event(event_loop, first, moved(5.0));
+        handler.about_to_wait(event_loop);
+        assert_eq!(handler.app.app.1, [
+            Delivered::Moved(first, 3.0, vec![1.0, 2.0, 3.0]),
+            Delivered::Focused(first),
+            Delivered::Moved(second, 10.0, vec![10.0]),
+            Delivered::Moved(first, 5.0, vec![4.0, 5.0]),
+            Delivered::AboutToWait,
+        ]);
+
+        // Every move is delivered by default.
+        let windows = mem::take(&mut handler.app.app.0);
+        let mut handler = PointerMoveCoalescingHandler::new(
+            WindowTrackingHandler::new(Recorder(windows, Vec::new()), KeyOverrides::default()),
+            false,
+        );
+        handler.window_event(event_loop, third, moved(1.0));
+        handler.window_event(event_loop, third, moved(2.0));
+        assert_eq!(handler.app.app.1, [
+            Delivered::Moved(third, 1.0, vec![]),
+            Delivered::Moved(third, 2.0, vec![]),
+        ]);
+    }
+
+    #[test]
+    #[cfg(headless_platform)]
+    fn key_repeat_filter_suppresses_repeats() {
+        use crate::event::{ElementState, KeyEvent};
+        use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, PhysicalKey};
+        use crate::platform_impl::KeyEventExtra;
+        use crate::window::KeyRepeatFilter;
+
+        fn input(logical_key: Key, text: Option<&str>, repeat: bool) -> WindowEvent {
+            WindowEvent::KeyboardInput {
+                device_id: None,
+                event: KeyEvent {
+                    physical_key: PhysicalKey::Code(KeyCode::KeyA),
+                    platform_specific: KeyEventExtra::synthetic(None),
+                    key_without_modifiers: logical_key.clone(),
+                    logical_key,
+                    text: text.map(Into::into),
+                    composed: false,
+                    location: KeyLocation::Standard,
+                    state: ElementState::Pressed,
+                    repeat,
+                    timestamp: None,
+                },
+                is_synthetic: false,
+            }
+        }
+
+        #[derive(Default)]
+        struct Repeats(Vec<bool>);
+
+        impl ApplicationHandler for Repeats {
+            fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+            fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, event: WindowEvent) {
+                if let WindowEvent::KeyboardInput { event, .. } = event {
+                    self.0.push(event.repeat);
+                }
+            }
+        }
+
+        // A real event loop to create the windows with, it isn't run.
+        let headless = platform_impl::headless::EventLoop::new();
+        let event_loop = headless.window_target();
+        let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let other = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let mut handler = WindowTrackingHandler::new(Repeats::default(), KeyOverrides::default());
+        let letter = |repeat| input(Key::Character("a".into()), Some("a"), repeat);
+        let arrow = |repeat| input(Key::Named(NamedKey::ArrowUp), None, repeat);
+
+        window.set_key_repeat_filter(KeyRepeatFilter::SuppressRepeats);
+        assert_eq!(window.key_repeat_filter(), KeyRepeatFilter::SuppressRepeats);
+        for event in [letter(false), letter(true), letter(true), arrow(false), arrow(true)] {
+            handler.window_event(event_loop, window.id(), event);
+        }
+        assert_eq!(handler.app.0, [false, false]);
+
+        handler.app.0.clear();
+        window.set_key_repeat_filter(KeyRepeatFilter::SuppressRepeatsForNonText);
+        for event in [letter(false), letter(true), arrow(false), arrow(true)] {
+            handler.window_event(event_loop, window.id(), event);
+        }
+        assert_eq!(handler.app.0, [false, true, false]);
+
+        // Other windows deliver all repeats.
+        handler.app.0.clear();
+        handler.window_event(event_loop, other.id(), arrow(true));
+        assert_eq!(handler.app.0, [true]);
+        assert_eq!(other.key_repeat_filter(), KeyRepeatFilter::DeliverAll);
+
+        // The filter goes away with the window.
+        handler.app.0.clear();
+        handler.window_event(event_loop, window.id(), WindowEvent::Destroyed);
+        handler.window_event(event_loop, window.id(), arrow(true));
+        assert_eq!(handler.app.0, [true]);
+    }
+
+    #[test]
+    #[cfg(all(headless_platform, any(x11_platform, wayland_platform)))]
+    fn key_code_override_precedes_mapping() {
+        use std::collections::HashSet;
+
+        use crate::event::{ElementState, KeyEvent, RawKeyEvent};
+        use crate::keyboard::{Key, KeyCode, KeyLocation, PhysicalKey};
+        use crate::platform_impl::KeyEventExtra;
+
+        fn key_code_override(native: NativeKeyCode) -> Option<KeyCode> {
+            match native {
+                // An extra key Winit doesn't identify.
+                NativeKeyCode::Xkb(0x1a5) => Some(KeyCode::F13),
+                // The key Winit identifies as `KeyCode::CapsLock`.
+                NativeKeyCode::Xkb(58) => Some(KeyCode::Escape),
+                _ => None,
+            }
+        }
+
+        #[derive(Default)]
+        struct Recorder(Vec<PhysicalKey>);
+
+        impl ApplicationHandler for Recorder {
+            fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+            fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, event: WindowEvent) {
+                if let WindowEvent::KeyboardInput { event, .. } = event {
+                    self.0.push(event.physical_key);
+                }
+            }
+
+            fn device_event(
+                &mut self,
+                _: &dyn ActiveEventLoop,
+                _: Option<DeviceId>,
+                event: DeviceEvent,
+            ) {
+                if let DeviceEvent::Key(event) = event {
+                    self.0.push(event.physical_key);
+                }
+            }
+        }
+
+        fn input(physical_key: PhysicalKey) -> WindowEvent {
+            let logical_key = Key::Character("a".into());
+            WindowEvent::KeyboardInput {
+                device_id: None,
+                event: KeyEvent {
+                    physical_key,
+                    platform_specific: KeyEventExtra::synthetic(None),
+                    key_without_modifiers: logical_key.clone(),
+                    logical_key,
+                    text: None,
+                    composed: false,
+                    location: KeyLocation::Standard,
+                    state: ElementState::Pressed,
+                    repeat: false,
+                    timestamp: None,
+                },
+                is_synthetic: false,
+            }
+        }
+
+        // A real event loop to create the window with, it isn't run.
+        let headless = platform_impl::headless::EventLoop::new();
+        let event_loop = headless.window_target();
+        let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let mut handler = WindowTrackingHandler::new(Recorder::default(), KeyOverrides {
+            key_code: Some(key_code_override),
+            key: None,
+        });
+
+        let extra_key = PhysicalKey::Unidentified(NativeKeyCode::Xkb(0x1a5));
+        let other_key = PhysicalKey::Unidentified(NativeKeyCode::Xkb(0x1a6));
+        let caps_lock = PhysicalKey::Code(KeyCode::CapsLock);
+        let key_a = PhysicalKey::Code(KeyCode::KeyA);
+        for physical_key in [extra_key, other_key, caps_lock, key_a] {
+            handler.window_event(event_loop, window.id(), input(physical_key));
+        }
+        let raw =
+            RawKeyEvent { physical_key: caps_lock, state: ElementState::Pressed, repeat: false };
+        handler.device_event(event_loop, None, DeviceEvent::Key(raw));
+
+        let f13 = PhysicalKey::Code(KeyCode::F13);
+        let escape = PhysicalKey::Code(KeyCode::Escape);
+        assert_eq!(handler.app.0, [f13, other_key, escape, key_a, escape]);
+        // The window tracks the overridden keys.
+        assert_eq!(window.held_keys(), HashSet::from([f13, other_key, escape, key_a]));
+
+        // Without an override, the keys are delivered as Winit identified them.
+        let mut handler = WindowTrackingHandler::new(Recorder::default(), KeyOverrides::default());
+        handler.window_event(event_loop, window.id(), input(extra_key));
+        handler.window_event(event_loop, window.id(), input(caps_lock));
+        assert_eq!(handler.app.0, [extra_key, caps_lock]);
+    }
+
+    #[test]
+    #[cfg(headless_platform)]
+    fn key_override_identifies_logical_keys() {
+        use smol_str::SmolStr;
+
+        use crate::event::{ElementState, KeyEvent};
+        use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, PhysicalKey};
+        use crate::platform_impl::KeyEventExtra;
+
+        fn key_override(native: &NativeKey) -> Option<Key> {
+            match native {
+                NativeKey::Xkb(0x1008ff1b) => Some(Key::Named(NamedKey::BrowserSearch)),
+                NativeKey::Xkb(0x1008ff1c) => Some(Key::Character("§".into())),
+                _ => None,
+            }
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Delivered {
+            logical_key: Key,
+            key_without_modifiers: Key,
+            text: Option<SmolStr>,
+        }
+
+        #[derive(Default)]
+        struct Recorder(Vec<Delivered>);
+
+        impl ApplicationHandler for Recorder {
+            fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+            fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, event: WindowEvent) {
+                if let WindowEvent::KeyboardInput { event, .. } = event {
+                    self.0.push(Delivered {
+                        logical_key: event.logical_key,
+                        key_without_modifiers: event.key_without_modifiers,
+                        text: event.text,
+                    });
+                }
+            }
+        }
+
+        fn input(logical_key: Key, key_without_modifiers: Key, text: Option<&str>) -> WindowEvent {
+            WindowEvent::KeyboardInput {
+                device_id: None,
+                event: KeyEvent {
+                    physical_key: PhysicalKey::Code(KeyCode::F13),
+                    platform_specific: KeyEventExtra::synthetic(None),
+                    key_without_modifiers,
+                    logical_key,
+                    text: text.map(Into::into),
+                    composed: false,
+                    location: KeyLocation::Standard,
+                    state: ElementState::Pressed,
+                    repeat: false,
+                    timestamp: None,
+                },
+                is_synthetic: false,
+            }
+        }
+
+        // A real event loop to create the window with, it isn't run.
+        let headless = platform_impl::headless::EventLoop::new();
+        let event_loop = headless.window_target();
+        let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+        let mut handler = WindowTrackingHandler::new(Recorder::default(), KeyOverrides {
+            key_code: None,
+            key: Some(key_override),
+        });
+
+        let search_key = Key::Unidentified(NativeKey::Xkb(0x1008ff1b));
+        let section_key = Key::Unidentified(NativeKey::Xkb(0x1008ff1c));
+        let other_key = Key::Unidentified(NativeKey::Xkb(0x1008ff1d));
+        let key_a = Key::Character("a".into());
+        // Both keys of the event are identified on their own, and the text is left as is.
+        handler.window_event(
+            event_loop,
+            window.id(),
+            input(search_key.clone(), key_a.clone(), None),
+        );
+        handler.window_event(
+            event_loop,
+            window.id(),
+            input(other_key.clone(), section_key.clone(), Some("x")),
+        );
+        // Identified keys aren't passed to the override.
+        handler.window_event(
+            event_loop,
+            window.id(),
+            input(key_a.clone(), key_a.clone(), Some("a")),
+        );
+
+        assert_eq!(handler.app.0, [
+            Delivered {
+                logical_key: Key::Named(NamedKey::BrowserSearch),
+                key_without_modifiers: key_a.clone(),
+                text: None,
+            },
+            Delivered {
+                logical_key: other_key,
+                key_without_modifiers: Key::Character("§".into()),
+                text: Some("x".into()),
+            },
+            Delivered {
+                logical_key: key_a.clone(),
+                key_without_modifiers: key_a,
+                text: Some("a".into()),
+            },
+        ]);
+
+        // Without an override, the keys are delivered unidentified.
+        let mut handler = WindowTrackingHandler::new(Recorder::default(), KeyOverrides::default());
+        handler.window_event(
+            event_loop,
+            window.id(),
+            input(search_key.clone(), section_key.clone(), None),
+        );
+        assert_eq!(handler.app.0, [Delivered {
+            logical_key: search_key,
+            key_without_modifiers: section_key,
+            text: None,
+        }]);
+    }
+
+    #[test]
+    fn builders_compare_key_overrides() {
+        fn hash(builder: &EventLoopBuilder) -> u64 {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            builder.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        fn none(_: NativeKeyCode) -> Option<KeyCode> {
+            None
+        }
+
+        fn f13(_: NativeKeyCode) -> Option<KeyCode> {
+            Some(KeyCode::F13)
+        }
+
+        let mut builder = EventLoop::builder();
+        let mut other = EventLoop::builder();
+        assert!(builder == other);
+        builder.with_key_code_override(none);
+        assert!(builder != other);
+        other.with_key_code_override(none);
+        assert!(builder == other);
+        assert_eq!(hash(&builder), hash(&other));
+        other.with_key_code_override(f13);
+        assert!(builder != other);
+    }
+
+    #[test]
+    fn frame_stats_rolling_average() {
+        let ms = Duration::from_millis;
+        let start = Instant::now();
+        let mut recorder = FrameStatsRecorder::default();
+        assert_eq!(recorder.stats(), FrameStats::default());
+
+        // Frames every 16ms taking 4ms to draw, then waiting until the next frame.
+        for frame in 0..10 {
+            let now = start + ms(16 * frame);
+            recorder.wait_finished(now);
+            recorder.redraw_started(now);
+            recorder.redraw_finished(now, now + ms(4));
+            recorder.wait_started(now + ms(6));
+        }
+        assert_eq!(recorder.stats(), FrameStats {
+            redraw_interval: ms(16),
+            handler_duration: ms(4),
+            wait_duration: ms(10),
+        });
+
+        // Older samples fall out of the window, including the transition to the slower frames.
+        let start = start + ms(16 * 10);
+        for frame in 0..=FrameStats::WINDOW as u64 {
+            let now = start + ms(32 * frame);
+            recorder.wait_finished(now);
+            recorder.redraw_started(now);
+            recorder.redraw_finished(now, now + ms(8));
+            recorder.wait_started(now + ms(8));
+        }
+        assert_eq!(recorder.stats(), FrameStats {
+            redraw_interval: ms(32),
+            handler_duration: ms(8),
+            wait_duration: ms(24),
+        });
     }
 }
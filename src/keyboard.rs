@@ -74,6 +74,8 @@ use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
 pub use smol_str::SmolStr;
 
+use crate::event::{ElementState, KeyEvent};
+
 /// Contains the platform-native physical key identifier
 ///
 /// The exact values vary from platform to platform (which is part of why this is a per-platform
@@ -128,6 +130,122 @@ impl std::fmt::Debug for NativeKeyCode {
     }
 }
 
+impl NativeKeyCode {
+    /// Wrap a raw code in the variant of the current platform.
+    ///
+    /// The code is truncated to the width of the variant, i.e. to 16 bits on macOS and Windows.
+    /// Platforms without a native key code, such as the Web, get [`NativeKeyCode::Unidentified`].
+    ///
+    /// ```
+    /// use winit::keyboard::NativeKeyCode;
+    ///
+    /// let code = NativeKeyCode::from_current_platform(0x1c);
+    /// if code != NativeKeyCode::Unidentified {
+    ///     assert_eq!(code.current_platform_code(), Some(0x1c));
+    /// }
+    /// ```
+    pub fn from_current_platform(raw: u32) -> NativeKeyCode {
+        if cfg!(android_platform) {
+            NativeKeyCode::Android(raw)
+        } else if cfg!(macos_platform) {
+            NativeKeyCode::MacOS(raw as u16)
+        } else if cfg!(windows_platform) {
+            NativeKeyCode::Windows(raw as u16)
+        } else if cfg!(any(x11_platform, wayland_platform)) {
+            NativeKeyCode::Xkb(raw)
+        } else {
+            NativeKeyCode::Unidentified
+        }
+    }
+
+    /// The raw code, if this is the variant of the current platform.
+    ///
+    /// This is the inverse of [`NativeKeyCode::from_current_platform`], codes of other platforms
+    /// return `None`.
+    ///
+    /// ```
+    /// use winit::keyboard::NativeKeyCode;
+    ///
+    /// assert_eq!(NativeKeyCode::Unidentified.current_platform_code(), None);
+    /// # #[cfg(not(target_os = "windows"))]
+    /// assert_eq!(NativeKeyCode::Windows(0x1c).current_platform_code(), None);
+    /// ```
+    pub fn current_platform_code(&self) -> Option<u32> {
+        match *self {
+            NativeKeyCode::Android(raw) if cfg!(android_platform) => Some(raw),
+            NativeKeyCode::MacOS(raw) if cfg!(macos_platform) => Some(raw.into()),
+            NativeKeyCode::Windows(raw) if cfg!(windows_platform) => Some(raw.into()),
+            NativeKeyCode::Xkb(raw) if cfg!(any(x11_platform, wayland_platform)) => Some(raw),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for NativeKeyCode {
+    type Err = NativeKeyCodeParseError;
+
+    /// Parses the format emitted by the `Debug` implementation, such as `Xkb(0x0026)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::NativeKeyCode;
+    ///
+    /// let code = NativeKeyCode::Windows(0x1c);
+    /// assert_eq!(format!("{code:?}").parse(), Ok(code));
+    /// assert_eq!("Unidentified".parse(), Ok(NativeKeyCode::Unidentified));
+    /// assert!("Xkb(38)".parse::<NativeKeyCode>().is_err());
+    /// # }
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || NativeKeyCodeParseError { input: s.to_owned() };
+
+        if s == "Unidentified" {
+            return Ok(NativeKeyCode::Unidentified);
+        }
+
+        let (variant, code) =
+            s.strip_suffix(')').and_then(|s| s.split_once('(')).ok_or_else(error)?;
+        let digits = code.strip_prefix("0x").ok_or_else(error)?;
+        // `from_str_radix` also accepts a leading sign, which `Debug` never emits.
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+
+        match variant {
+            "Android" => u32::from_str_radix(digits, 16).map(NativeKeyCode::Android),
+            "MacOS" => u16::from_str_radix(digits, 16).map(NativeKeyCode::MacOS),
+            "Windows" => u16::from_str_radix(digits, 16).map(NativeKeyCode::Windows),
+            "Xkb" => u32::from_str_radix(digits, 16).map(NativeKeyCode::Xkb),
+            _ => return Err(error()),
+        }
+        .map_err(|_| error())
+    }
+}
+
+/// The error returned when parsing a [`NativeKeyCode`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeKeyCodeParseError {
+    input: String,
+}
+
+impl std::fmt::Display for NativeKeyCodeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid native key code `{}`, expected `Unidentified` or a variant with a hex code \
+             such as `Xkb(0x0026)`",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for NativeKeyCodeParseError {}
+
 /// Contains the platform-native logical key identifier
 ///
 /// Exactly what that means differs from platform to platform, but the values are to some degree
@@ -138,6 +256,10 @@ impl std::fmt::Debug for NativeKeyCode {
 /// This enum is primarily used to store raw keysym when Winit doesn't map a given native logical
 /// key identifier to a meaningful [`Key`] variant. This lets you use [`Key`], and let the user
 /// define keybinds which work in the presence of identifiers we haven't mapped for you yet.
+///
+/// Keybinds using these identifiers are not portable across platforms: a `NativeKey` saved on one
+/// platform, e.g. with the `serde` feature, is never reported on another. See
+/// [`NativeKey::normalize()`].
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NativeKey {
@@ -188,6 +310,38 @@ impl std::fmt::Debug for NativeKey {
     }
 }
 
+impl NativeKey {
+    /// Map the identifiers of other platforms to [`NativeKey::Unidentified`].
+    ///
+    /// Only the variant of the current platform is preserved, so a keybind loaded from another
+    /// platform compares equal to other unidentified keys instead of silently never matching.
+    /// This is lossy, the foreign identifier can't be recovered.
+    ///
+    /// ```
+    /// use winit::keyboard::NativeKey;
+    ///
+    /// assert_eq!(NativeKey::Unidentified.normalize(), NativeKey::Unidentified);
+    /// # #[cfg(not(target_os = "windows"))]
+    /// assert_eq!(NativeKey::Windows(0x41).normalize(), NativeKey::Unidentified);
+    /// ```
+    pub fn normalize(&self) -> NativeKey {
+        match self {
+            NativeKey::Unidentified => NativeKey::Unidentified,
+            #[cfg(android_platform)]
+            NativeKey::Android(_) => self.clone(),
+            #[cfg(macos_platform)]
+            NativeKey::MacOS(_) => self.clone(),
+            #[cfg(windows_platform)]
+            NativeKey::Windows(_) => self.clone(),
+            #[cfg(any(x11_platform, wayland_platform))]
+            NativeKey::Xkb(_) => self.clone(),
+            #[cfg(web_platform)]
+            NativeKey::Web(_) => self.clone(),
+            _ => NativeKey::Unidentified,
+        }
+    }
+}
+
 impl From<NativeKeyCode> for NativeKey {
     #[inline]
     fn from(code: NativeKeyCode) -> Self {
@@ -233,6 +387,274 @@ pub enum PhysicalKey {
     Unidentified(NativeKeyCode),
 }
 
+impl PhysicalKey {
+    /// Returns `true` if this is the key `code`, or the unidentified key `fallback`.
+    ///
+    /// This checks keybinds that are stored as a [`KeyCode`] along with the native code of the
+    /// key, in case it can't be identified, e.g. with a different platform or keyboard.
+    ///
+    /// The [`KeyCode`] always wins: when the key is identified, only `code` is compared and
+    /// `fallback` is ignored, as a [`KeyCode`] can't be translated to a native code. The `fallback`
+    /// is only compared when the key is reported as [`PhysicalKey::Unidentified`], even if the bind
+    /// was saved while it was identified. A `fallback` of [`NativeKeyCode::Unidentified`] carries
+    /// no information, and never matches.
+    ///
+    /// ```
+    /// use winit::keyboard::{KeyCode, NativeKeyCode, PhysicalKey};
+    ///
+    /// let fallback = NativeKeyCode::Xkb(0x26);
+    /// assert!(PhysicalKey::Code(KeyCode::KeyA).matches(KeyCode::KeyA, fallback));
+    /// assert!(PhysicalKey::Unidentified(fallback).matches(KeyCode::KeyA, fallback));
+    /// assert!(!PhysicalKey::Code(KeyCode::KeyB).matches(KeyCode::KeyA, fallback));
+    /// ```
+    pub fn matches(&self, code: KeyCode, fallback: NativeKeyCode) -> bool {
+        match self {
+            PhysicalKey::Code(key) => *key == code,
+            PhysicalKey::Unidentified(NativeKeyCode::Unidentified) => false,
+            PhysicalKey::Unidentified(native) => *native == fallback,
+        }
+    }
+
+    /// Returns `true` if the key was identified as a [`KeyCode`].
+    ///
+    /// Keybinds to a [`PhysicalKey::Unidentified`] key are tied to the platform and keyboard
+    /// they were made with, so settings UIs may want to reject them.
+    ///
+    /// ```
+    /// use winit::keyboard::{KeyCode, NativeKeyCode, PhysicalKey};
+    ///
+    /// assert!(PhysicalKey::Code(KeyCode::KeyA).is_known());
+    /// assert!(!PhysicalKey::Unidentified(NativeKeyCode::Xkb(0x26)).is_known());
+    /// ```
+    pub fn is_known(&self) -> bool {
+        matches!(self, PhysicalKey::Code(_))
+    }
+
+    /// Returns `true` if this is the physical key of a modifier.
+    ///
+    /// See [`KeyCode::is_modifier`] for which keys are considered modifiers. Unidentified keys are
+    /// never modifiers.
+    pub fn is_modifier(&self) -> bool {
+        match self {
+            PhysicalKey::Code(code) => code.is_modifier(),
+            PhysicalKey::Unidentified(_) => false,
+        }
+    }
+
+    /// Returns the usage of the key on the USB HID Keyboard/Keypad page (0x07).
+    ///
+    /// Unlike the native scancodes, the usage is the same on every platform and doesn't depend on
+    /// the keyboard layout, which makes it suitable for storing portable keybinds or interfacing
+    /// with HID-level tools. Only the usage ID is returned, without the page, e.g. `0x04` for
+    /// [`KeyCode::KeyA`].
+    ///
+    /// Returns `None` for unidentified keys and for keys without a usage on that page, such as
+    /// [`KeyCode::Fn`], [`KeyCode::F25`] and above, or the media keys of the Consumer page.
+    ///
+    /// ```
+    /// use winit::keyboard::{KeyCode, PhysicalKey};
+    ///
+    /// assert_eq!(PhysicalKey::Code(KeyCode::KeyA).to_hid_usage(), Some(0x04));
+    /// assert_eq!(PhysicalKey::Code(KeyCode::ControlLeft).to_hid_usage(), Some(0xe0));
+    /// assert_eq!(PhysicalKey::Code(KeyCode::Fn).to_hid_usage(), None);
+    /// ```
+    pub fn to_hid_usage(&self) -> Option<u16> {
+        let PhysicalKey::Code(code) = self else {
+            return None;
+        };
+        HID_USAGES.iter().find(|(key, _)| key == code).map(|(_, usage)| *usage)
+    }
+
+    /// Returns the key with the given usage on the USB HID Keyboard/Keypad page (0x07).
+    ///
+    /// This is the inverse of [`to_hid_usage`][Self::to_hid_usage]. The "Non-US # and ~" usage
+    /// (0x32) is also mapped to [`KeyCode::Backslash`], as it is the same key on ISO keyboards.
+    /// Usages without a corresponding [`KeyCode`] are returned as
+    /// [`PhysicalKey::Unidentified`] with [`NativeKeyCode::Unidentified`].
+    ///
+    /// ```
+    /// use winit::keyboard::{KeyCode, NativeKeyCode, PhysicalKey};
+    ///
+    /// assert_eq!(PhysicalKey::from_hid_usage(0x04), PhysicalKey::Code(KeyCode::KeyA));
+    /// assert_eq!(
+    ///     PhysicalKey::from_hid_usage(0x01),
+    ///     PhysicalKey::Unidentified(NativeKeyCode::Unidentified)
+    /// );
+    /// ```
+    pub fn from_hid_usage(usage: u16) -> PhysicalKey {
+        if usage == 0x32 {
+            return PhysicalKey::Code(KeyCode::Backslash);
+        }
+        match HID_USAGES.binary_search_by_key(&usage, |(_, usage)| *usage) {
+            Ok(index) => PhysicalKey::Code(HID_USAGES[index].0),
+            Err(_) => PhysicalKey::Unidentified(NativeKeyCode::Unidentified),
+        }
+    }
+}
+
+/// The [`KeyCode`]s with a usage on the USB HID Keyboard/Keypad page (0x07), sorted by usage.
+///
+/// The usages follow the UI Events Specification's [code value tables].
+///
+/// [code value tables]: https://w3c.github.io/uievents-code/#code-value-tables
+const HID_USAGES: [(KeyCode, u16); 155] = [
+    (KeyCode::KeyA, 0x04),
+    (KeyCode::KeyB, 0x05),
+    (KeyCode::KeyC, 0x06),
+    (KeyCode::KeyD, 0x07),
+    (KeyCode::KeyE, 0x08),
+    (KeyCode::KeyF, 0x09),
+    (KeyCode::KeyG, 0x0a),
+    (KeyCode::KeyH, 0x0b),
+    (KeyCode::KeyI, 0x0c),
+    (KeyCode::KeyJ, 0x0d),
+    (KeyCode::KeyK, 0x0e),
+    (KeyCode::KeyL, 0x0f),
+    (KeyCode::KeyM, 0x10),
+    (KeyCode::KeyN, 0x11),
+    (KeyCode::KeyO, 0x12),
+    (KeyCode::KeyP, 0x13),
+    (KeyCode::KeyQ, 0x14),
+    (KeyCode::KeyR, 0x15),
+    (KeyCode::KeyS, 0x16),
+    (KeyCode::KeyT, 0x17),
+    (KeyCode::KeyU, 0x18),
+    (KeyCode::KeyV, 0x19),
+    (KeyCode::KeyW, 0x1a),
+    (KeyCode::KeyX, 0x1b),
+    (KeyCode::KeyY, 0x1c),
+    (KeyCode::KeyZ, 0x1d),
+    (KeyCode::Digit1, 0x1e),
+    (KeyCode::Digit2, 0x1f),
+    (KeyCode::Digit3, 0x20),
+    (KeyCode::Digit4, 0x21),
+    (KeyCode::Digit5, 0x22),
+    (KeyCode::Digit6, 0x23),
+    (KeyCode::Digit7, 0x24),
+    (KeyCode::Digit8, 0x25),
+    (KeyCode::Digit9, 0x26),
+    (KeyCode::Digit0, 0x27),
+    (KeyCode::Enter, 0x28),
+    (KeyCode::Escape, 0x29),
+    (KeyCode::Backspace, 0x2a),
+    (KeyCode::Tab, 0x2b),
+    (KeyCode::Space, 0x2c),
+    (KeyCode::Minus, 0x2d),
+    (KeyCode::Equal, 0x2e),
+    (KeyCode::BracketLeft, 0x2f),
+    (KeyCode::BracketRight, 0x30),
+    (KeyCode::Backslash, 0x31),
+    (KeyCode::Semicolon, 0x33),
+    (KeyCode::Quote, 0x34),
+    (KeyCode::Backquote, 0x35),
+    (KeyCode::Comma, 0x36),
+    (KeyCode::Period, 0x37),
+    (KeyCode::Slash, 0x38),
+    (KeyCode::CapsLock, 0x39),
+    (KeyCode::F1, 0x3a),
+    (KeyCode::F2, 0x3b),
+    (KeyCode::F3, 0x3c),
+    (KeyCode::F4, 0x3d),
+    (KeyCode::F5, 0x3e),
+    (KeyCode::F6, 0x3f),
+    (KeyCode::F7, 0x40),
+    (KeyCode::F8, 0x41),
+    (KeyCode::F9, 0x42),
+    (KeyCode::F10, 0x43),
+    (KeyCode::F11, 0x44),
+    (KeyCode::F12, 0x45),
+    (KeyCode::PrintScreen, 0x46),
+    (KeyCode::ScrollLock, 0x47),
+    (KeyCode::Pause, 0x48),
+    (KeyCode::Insert, 0x49),
+    (KeyCode::Home, 0x4a),
+    (KeyCode::PageUp, 0x4b),
+    (KeyCode::Delete, 0x4c),
+    (KeyCode::End, 0x4d),
+    (KeyCode::PageDown, 0x4e),
+    (KeyCode::ArrowRight, 0x4f),
+    (KeyCode::ArrowLeft, 0x50),
+    (KeyCode::ArrowDown, 0x51),
+    (KeyCode::ArrowUp, 0x52),
+    (KeyCode::NumLock, 0x53),
+    (KeyCode::NumpadDivide, 0x54),
+    (KeyCode::NumpadMultiply, 0x55),
+    (KeyCode::NumpadSubtract, 0x56),
+    (KeyCode::NumpadAdd, 0x57),
+    (KeyCode::NumpadEnter, 0x58),
+    (KeyCode::Numpad1, 0x59),
+    (KeyCode::Numpad2, 0x5a),
+    (KeyCode::Numpad3, 0x5b),
+    (KeyCode::Numpad4, 0x5c),
+    (KeyCode::Numpad5, 0x5d),
+    (KeyCode::Numpad6, 0x5e),
+    (KeyCode::Numpad7, 0x5f),
+    (KeyCode::Numpad8, 0x60),
+    (KeyCode::Numpad9, 0x61),
+    (KeyCode::Numpad0, 0x62),
+    (KeyCode::NumpadDecimal, 0x63),
+    (KeyCode::IntlBackslash, 0x64),
+    (KeyCode::ContextMenu, 0x65),
+    (KeyCode::Power, 0x66),
+    (KeyCode::NumpadEqual, 0x67),
+    (KeyCode::F13, 0x68),
+    (KeyCode::F14, 0x69),
+    (KeyCode::F15, 0x6a),
+    (KeyCode::F16, 0x6b),
+    (KeyCode::F17, 0x6c),
+    (KeyCode::F18, 0x6d),
+    (KeyCode::F19, 0x6e),
+    (KeyCode::F20, 0x6f),
+    (KeyCode::F21, 0x70),
+    (KeyCode::F22, 0x71),
+    (KeyCode::F23, 0x72),
+    (KeyCode::F24, 0x73),
+    (KeyCode::Open, 0x74),
+    (KeyCode::Help, 0x75),
+    (KeyCode::Select, 0x77),
+    (KeyCode::Again, 0x79),
+    (KeyCode::Undo, 0x7a),
+    (KeyCode::Cut, 0x7b),
+    (KeyCode::Copy, 0x7c),
+    (KeyCode::Paste, 0x7d),
+    (KeyCode::Find, 0x7e),
+    (KeyCode::AudioVolumeMute, 0x7f),
+    (KeyCode::AudioVolumeUp, 0x80),
+    (KeyCode::AudioVolumeDown, 0x81),
+    (KeyCode::NumpadComma, 0x85),
+    (KeyCode::IntlRo, 0x87),
+    (KeyCode::KanaMode, 0x88),
+    (KeyCode::IntlYen, 0x89),
+    (KeyCode::Convert, 0x8a),
+    (KeyCode::NonConvert, 0x8b),
+    (KeyCode::Lang1, 0x90),
+    (KeyCode::Lang2, 0x91),
+    (KeyCode::Lang3, 0x92),
+    (KeyCode::Lang4, 0x93),
+    (KeyCode::Lang5, 0x94),
+    (KeyCode::Abort, 0x9b),
+    (KeyCode::Props, 0xa3),
+    (KeyCode::NumpadParenLeft, 0xb6),
+    (KeyCode::NumpadParenRight, 0xb7),
+    (KeyCode::NumpadBackspace, 0xbb),
+    (KeyCode::NumpadHash, 0xcc),
+    (KeyCode::NumpadMemoryStore, 0xd0),
+    (KeyCode::NumpadMemoryRecall, 0xd1),
+    (KeyCode::NumpadMemoryClear, 0xd2),
+    (KeyCode::NumpadMemoryAdd, 0xd3),
+    (KeyCode::NumpadMemorySubtract, 0xd4),
+    (KeyCode::NumpadClear, 0xd8),
+    (KeyCode::NumpadClearEntry, 0xd9),
+    (KeyCode::ControlLeft, 0xe0),
+    (KeyCode::ShiftLeft, 0xe1),
+    (KeyCode::AltLeft, 0xe2),
+    (KeyCode::SuperLeft, 0xe3),
+    (KeyCode::ControlRight, 0xe4),
+    (KeyCode::ShiftRight, 0xe5),
+    (KeyCode::AltRight, 0xe6),
+    (KeyCode::SuperRight, 0xe7),
+];
+
 impl From<KeyCode> for PhysicalKey {
     #[inline]
     fn from(code: KeyCode) -> Self {
@@ -739,6 +1161,332 @@ pub enum KeyCode {
     F35,
 }
 
+/// Generates the `ALL` array of a key enum along with the conversions between
+/// its variants and their names, from the list of its variants in declaration order.
+///
+/// A variant is named after itself, unless a name is given with `Variant = "Name"`.
+macro_rules! key_table {
+    ($ty:ident { $($key:ident $(= $name:literal)?),* $(,)? }) => {
+        impl $ty {
+            /// Every variant, in declaration order.
+            // Only the tests use it for some of the enums.
+            #[allow(dead_code)]
+            const ALL: [$ty; [$($ty::$key),*].len()] = [$($ty::$key),*];
+
+            /// The name of the variant in the table.
+            fn table_name(&self) -> &'static str {
+                match self {
+                    $($ty::$key => key_table!(@name $key $($name)?),)*
+                }
+            }
+
+            /// The variant with the given name in the table, if any.
+            fn from_table_name(name: &str) -> Option<Self> {
+                match name {
+                    $(key_table!(@name $key $($name)?) => Some($ty::$key),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+    (@name $key:ident) => {
+        stringify!($key)
+    };
+    (@name $key:ident $name:literal) => {
+        $name
+    };
+}
+
+key_table!(KeyCode {
+    Backquote, Backslash, BracketLeft, BracketRight, Comma, Digit0, Digit1, Digit2, Digit3,
+    Digit4, Digit5, Digit6, Digit7, Digit8, Digit9, Equal, IntlBackslash, IntlRo, IntlYen, KeyA,
+    KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM, KeyN, KeyO, KeyP,
+    KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ, Minus, Period, Quote, Semicolon,
+    Slash, AltLeft, AltRight, Backspace, CapsLock, ContextMenu, ControlLeft, ControlRight,
+    Enter, SuperLeft = "MetaLeft", SuperRight = "MetaRight", ShiftLeft, ShiftRight, Space, Tab,
+    Convert, KanaMode, Lang1, Lang2, Lang3, Lang4, Lang5, NonConvert, Delete, End, Help, Home,
+    Insert, PageDown, PageUp, ArrowDown, ArrowLeft, ArrowRight, ArrowUp, NumLock, Numpad0,
+    Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9, NumpadAdd,
+    NumpadBackspace, NumpadClear, NumpadClearEntry, NumpadComma, NumpadDecimal, NumpadDivide,
+    NumpadEnter, NumpadEqual, NumpadHash, NumpadMemoryAdd, NumpadMemoryClear,
+    NumpadMemoryRecall, NumpadMemoryStore, NumpadMemorySubtract, NumpadMultiply,
+    NumpadParenLeft, NumpadParenRight, NumpadStar, NumpadSubtract, Escape, Fn, FnLock,
+    PrintScreen, ScrollLock, Pause, BrowserBack, BrowserFavorites, BrowserForward, BrowserHome,
+    BrowserRefresh, BrowserSearch, BrowserStop, Eject, LaunchApp1, LaunchApp2, LaunchMail,
+    MediaPlayPause, MediaSelect, MediaStop, MediaTrackNext, MediaTrackPrevious, Power, Sleep,
+    AudioVolumeDown, AudioVolumeMute, AudioVolumeUp, WakeUp, Meta, Hyper, Turbo, Abort, Resume,
+    Suspend, Again, Copy, Cut, Find, Open, Paste, Props, Select, Undo, Hiragana, Katakana, F1,
+    F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21,
+    F22, F23, F24, F25, F26, F27, F28, F29, F30, F31, F32, F33, F34, F35,
+});
+
+impl KeyCode {
+    /// Returns an iterator over every [`KeyCode`] known to winit.
+    ///
+    /// Keys that winit can't identify are reported as [`PhysicalKey::Unidentified`] and are
+    /// thus not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// assert!(KeyCode::all().any(|code| code == KeyCode::KeyA));
+    /// # }
+    /// ```
+    pub fn all() -> impl Iterator<Item = KeyCode> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns `true` if this is the physical key of a modifier.
+    ///
+    /// These are the keys whose [`NamedKey`] is a modifier according to
+    /// [`NamedKey::is_modifier`]: both <kbd>Shift</kbd>, <kbd>Control</kbd>, <kbd>Alt</kbd> and
+    /// <kbd>Super</kbd> keys, the lock keys, <kbd>Fn</kbd>, <kbd>FnLock</kbd>, and the legacy
+    /// <kbd>Meta</kbd> and <kbd>Hyper</kbd> keys. Note that the logical key of the same physical
+    /// key may differ with the keyboard layout, e.g. [`AltRight`][Self::AltRight] is
+    /// [`NamedKey::AltGraph`] on many layouts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// assert!(KeyCode::ShiftLeft.is_modifier());
+    /// assert!(KeyCode::CapsLock.is_modifier());
+    /// assert!(!KeyCode::KeyA.is_modifier());
+    /// # }
+    /// ```
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::AltLeft
+                | KeyCode::AltRight
+                | KeyCode::CapsLock
+                | KeyCode::ControlLeft
+                | KeyCode::ControlRight
+                | KeyCode::Fn
+                | KeyCode::FnLock
+                | KeyCode::Meta
+                | KeyCode::NumLock
+                | KeyCode::ScrollLock
+                | KeyCode::ShiftLeft
+                | KeyCode::ShiftRight
+                | KeyCode::Hyper
+                | KeyCode::SuperLeft
+                | KeyCode::SuperRight
+        )
+    }
+
+    /// Returns `true` for the letter keys, [`KeyA`][Self::KeyA] to [`KeyZ`][Self::KeyZ].
+    ///
+    /// These are named after their position on a US keyboard, the letter they type depends on the
+    /// keyboard layout.
+    pub fn is_alphabetic(&self) -> bool {
+        self.is_between(KeyCode::KeyA, KeyCode::KeyZ)
+    }
+
+    /// Returns `true` for the digit keys, both [`Digit0`][Self::Digit0] to
+    /// [`Digit9`][Self::Digit9] and [`Numpad0`][Self::Numpad0] to [`Numpad9`][Self::Numpad9].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// assert!(KeyCode::Digit1.is_digit());
+    /// assert!(KeyCode::Numpad1.is_digit());
+    /// assert!(!KeyCode::NumpadAdd.is_digit());
+    /// # }
+    /// ```
+    pub fn is_digit(&self) -> bool {
+        self.is_between(KeyCode::Digit0, KeyCode::Digit9)
+            || self.is_between(KeyCode::Numpad0, KeyCode::Numpad9)
+    }
+
+    /// Returns `true` for the general-purpose function keys, [`F1`][Self::F1] to
+    /// [`F35`][Self::F35].
+    pub fn is_function_key(&self) -> bool {
+        self.is_between(KeyCode::F1, KeyCode::F35)
+    }
+
+    /// Returns `true` for the keys of the numeric keypad, the ones whose name starts with
+    /// `Numpad`.
+    ///
+    /// [`NumLock`][Self::NumLock] is a modifier and not included.
+    pub fn is_numpad(&self) -> bool {
+        self.is_between(KeyCode::Numpad0, KeyCode::NumpadSubtract)
+    }
+
+    /// Returns `true` for the arrow keys, [`Home`][Self::Home], [`End`][Self::End],
+    /// [`PageUp`][Self::PageUp] and [`PageDown`][Self::PageDown].
+    pub fn is_navigation(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::ArrowDown
+                | KeyCode::ArrowLeft
+                | KeyCode::ArrowRight
+                | KeyCode::ArrowUp
+                | KeyCode::End
+                | KeyCode::Home
+                | KeyCode::PageDown
+                | KeyCode::PageUp
+        )
+    }
+
+    /// The character the key types on a US layout without modifiers, for the keys of the main
+    /// block that type one.
+    ///
+    /// The keys that don't exist on a US keyboard, such as [`IntlBackslash`], are not included.
+    ///
+    /// [`IntlBackslash`]: Self::IntlBackslash
+    pub(crate) fn us_layout_character(&self) -> Option<&'static str> {
+        const LETTERS: [&str; 26] = [
+            "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q",
+            "r", "s", "t", "u", "v", "w", "x", "y", "z",
+        ];
+        const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+        if self.is_alphabetic() {
+            return Some(LETTERS[*self as usize - KeyCode::KeyA as usize]);
+        }
+        if self.is_between(KeyCode::Digit0, KeyCode::Digit9) {
+            return Some(DIGITS[*self as usize - KeyCode::Digit0 as usize]);
+        }
+        let character = match self {
+            KeyCode::Backquote => "`",
+            KeyCode::Backslash => "\\",
+            KeyCode::BracketLeft => "[",
+            KeyCode::BracketRight => "]",
+            KeyCode::Comma => ",",
+            KeyCode::Equal => "=",
+            KeyCode::Minus => "-",
+            KeyCode::Period => ".",
+            KeyCode::Quote => "'",
+            KeyCode::Semicolon => ";",
+            KeyCode::Slash => "/",
+            _ => return None,
+        };
+        Some(character)
+    }
+
+    /// A short label for the key, given the `key` it produces in the active layout, see
+    /// [`Window::physical_key_label`].
+    ///
+    /// [`Window::physical_key_label`]: crate::window::Window::physical_key_label
+    pub(crate) fn label(&self, key: Option<&Key>) -> String {
+        if self.is_numpad() {
+            // With Num Lock off the digits produce navigation keys, which don't tell them apart
+            // from the keys of the main block.
+            return match key {
+                Some(Key::Character(ch)) => format!("Num {ch}"),
+                _ => format!("Num {}", self.numpad_symbol()),
+            };
+        }
+
+        let label = match key {
+            Some(Key::Character(ch)) if ch == " " => Some(NamedKey::Space.label().to_owned()),
+            Some(Key::Character(ch)) => {
+                let upper = ch.to_uppercase();
+                Some(if upper.chars().count() == 1 { upper } else { ch.to_string() })
+            },
+            Some(Key::Named(named)) => Some(named.label().to_owned()),
+            Some(Key::Dead(Some(ch))) => Some(ch.to_string()),
+            _ => None,
+        };
+        label.unwrap_or_else(|| self.us_label())
+    }
+
+    /// The label of the key on a US layout, for when the active layout doesn't define it.
+    fn us_label(&self) -> String {
+        if let Some(character) = self.us_layout_character() {
+            return character.to_uppercase();
+        }
+
+        // Most keys outside of the main block are named after the key they produce, possibly
+        // with their side appended.
+        let name = self.name();
+        let named = name.strip_suffix("Left").or_else(|| name.strip_suffix("Right"));
+        match NamedKey::from_name(name).or_else(|| named.and_then(NamedKey::from_name)) {
+            Some(named) => named.label().to_owned(),
+            None => name.to_owned(),
+        }
+    }
+
+    /// The symbol printed on a numpad key, without the `Numpad` prefix of its name.
+    fn numpad_symbol(&self) -> &'static str {
+        match self {
+            KeyCode::NumpadAdd => "+",
+            KeyCode::NumpadSubtract => "-",
+            KeyCode::NumpadMultiply | KeyCode::NumpadStar => "*",
+            KeyCode::NumpadDivide => "/",
+            KeyCode::NumpadDecimal => ".",
+            KeyCode::NumpadComma => ",",
+            KeyCode::NumpadEqual => "=",
+            KeyCode::NumpadHash => "#",
+            KeyCode::NumpadParenLeft => "(",
+            KeyCode::NumpadParenRight => ")",
+            code => code.name().strip_prefix("Numpad").unwrap_or(code.name()),
+        }
+    }
+
+    /// Whether the key is declared between `first` and `last`, inclusive.
+    fn is_between(&self, first: KeyCode, last: KeyCode) -> bool {
+        (first as usize..=last as usize).contains(&(*self as usize))
+    }
+
+    /// The UI Events Specification's [`KeyboardEvent.code`] value of the key.
+    ///
+    /// This is the name of the variant, except for [`SuperLeft`] and [`SuperRight`] which are
+    /// called `"MetaLeft"` and `"MetaRight"` by the specification.
+    ///
+    /// [`KeyboardEvent.code`]: https://w3c.github.io/uievents-code/#code-value-tables
+    /// [`SuperLeft`]: Self::SuperLeft
+    /// [`SuperRight`]: Self::SuperRight
+    pub fn name(&self) -> &'static str {
+        self.table_name()
+    }
+
+    /// The key with the given [`name`][Self::name], if any.
+    ///
+    /// This parses the [`KeyboardEvent.code`] values of the UI Events Specification, e.g. to load
+    /// physical key bindings from a configuration file. The names are case-sensitive, and
+    /// unknown names return `None`: there is no name for an unidentified key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::KeyCode;
+    ///
+    /// assert_eq!(KeyCode::from_name("KeyA"), Some(KeyCode::KeyA));
+    /// assert_eq!(KeyCode::from_name("MetaLeft"), Some(KeyCode::SuperLeft));
+    /// assert_eq!(KeyCode::from_name("keya"), None);
+    /// assert_eq!(KeyCode::from_name("Unidentified"), None);
+    /// # }
+    /// ```
+    ///
+    /// [`KeyboardEvent.code`]: https://w3c.github.io/uievents-code/#code-value-tables
+    pub fn from_name(name: &str) -> Option<KeyCode> {
+        Self::from_table_name(name)
+    }
+}
+
 /// A [`Key::Named`] value
 ///
 /// This mostly conforms to the UI Events Specification's [`KeyboardEvent.key`] with a few
@@ -1014,6 +1762,16 @@ pub enum NamedKey {
     MediaPlay,
     /// Toggle media between play and pause states. (`APPCOMMAND_MEDIA_PLAY_PAUSE`,
     /// `KEYCODE_MEDIA_PLAY_PAUSE`)
+    ///
+    /// This is also what the play key of most keyboards reports (`VK_MEDIA_PLAY_PAUSE`,
+    /// `XF86AudioPlay`).
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The media keys arrive as `NSSystemDefined` events rather than key events, and
+    ///   are reported to the key window while the application is active, not globally. The system
+    ///   may handle them as well, e.g. this key also controls the "Now Playing" media, and may not
+    ///   deliver them at all when it consumes them itself.
     MediaPlayPause,
     /// Initiate or resume recording of currently selected media. (`APPCOMMAND_MEDIA_RECORD`,
     /// `KEYCODE_MEDIA_RECORD`)
@@ -1481,13 +2239,21 @@ pub enum Key<Str = SmolStr> {
     /// This variant is used when the key cannot be translated to any other variant.
     ///
     /// The native key is provided (if available) in order to allow the user to specify keybindings
-    /// for keys which are not defined by this API, mainly through some sort of UI.
+    /// for keys which are not defined by this API, mainly through some sort of UI. Such keybinds
+    /// are not portable across platforms, see [`NativeKey::normalize()`].
     Unidentified(NativeKey),
 
     /// Contains the text representation of the dead-key when available.
     ///
     /// ## Platform-specific
+    /// - **X11 / Wayland:** Also reported for the keys continuing a sequence started with the
+    ///   compose key ([`NamedKey::Compose`]), with the character the key types. The character
+    ///   composed by the sequence is reported by its last key, as a [`Key::Character`] and as the
+    ///   text of the event. When the IME handles the compose key, the sequence is instead reported
+    ///   with [`Ime`] events.
     /// - **Web:** Always contains `None`
+    ///
+    /// [`Ime`]: crate::event::Ime
     Dead(Option<char>),
 }
 
@@ -1552,6 +2318,9 @@ impl<Str> PartialEq<Key<Str>> for NativeKey {
 impl Key<SmolStr> {
     /// Convert `Key::Character(SmolStr)` to `Key::Character(&str)` so you can more easily match on
     /// `Key`. All other variants remain unchanged.
+    ///
+    /// With the `serde` feature, the borrowed form serializes exactly like the owned one, so it
+    /// can be serialized without cloning and deserialized back into a `Key<SmolStr>`.
     pub fn as_ref(&self) -> Key<&str> {
         match self {
             Key::Named(a) => Key::Named(*a),
@@ -1562,36 +2331,61 @@ impl Key<SmolStr> {
     }
 }
 
-impl NamedKey {
-    /// Convert an action to its approximate textual equivalent.
+impl<Str: AsRef<str>> Key<Str> {
+    /// Convert a `Key` holding any kind of string, such as the borrowed `Key<&str>`, to the owned
+    /// `Key<SmolStr>`. All other variants remain unchanged.
+    ///
+    /// With the `serde` feature, `Key<&str>` deserializes without copying, borrowing the character
+    /// from the input, so large tables of keys can be read straight from a memory-mapped buffer
+    /// and only converted with this method when they need to outlive it. Note that borrowing fails
+    /// when the deserializer can't hand out the string as it is in the input, e.g. a JSON string
+    /// with escape sequences; deserialize a `Key<SmolStr>` in that case.
     ///
     /// # Examples
     ///
     /// ```
-    /// # #[cfg(web_platform)]
-    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
-    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
-    /// # fn main() {
-    /// use winit::keyboard::NamedKey;
-    ///
-    /// assert_eq!(NamedKey::Enter.to_text(), Some("\r"));
-    /// assert_eq!(NamedKey::F20.to_text(), None);
-    /// # }
+    /// # use winit::keyboard::{Key, NamedKey};
+    /// let text = String::from("a");
+    /// let key: Key<&str> = Key::Character(&text);
+    /// assert_eq!(key.into_owned(), Key::Character("a".into()));
+    /// assert_eq!(Key::<&str>::Named(NamedKey::Enter).into_owned(), Key::Named(NamedKey::Enter));
     /// ```
-    pub fn to_text(&self) -> Option<&str> {
+    pub fn into_owned(self) -> Key<SmolStr> {
         match self {
-            NamedKey::Enter => Some("\r"),
-            NamedKey::Backspace => Some("\x08"),
-            NamedKey::Tab => Some("\t"),
-            NamedKey::Space => Some(" "),
-            NamedKey::Escape => Some("\x1b"),
-            _ => None,
+            Key::Named(a) => Key::Named(a),
+            Key::Character(ch) => Key::Character(SmolStr::new(ch)),
+            Key::Dead(d) => Key::Dead(d),
+            Key::Unidentified(u) => Key::Unidentified(u),
         }
     }
 }
 
-impl Key {
-    /// Convert a key to its approximate textual equivalent.
+impl std::fmt::Display for Key {
+    /// Formats the key in the form accepted by its [`FromStr`] implementation.
+    ///
+    /// Named keys are written as their name, e.g. `Enter` or `ArrowLeft`, and characters as
+    /// themselves. Unidentified and dead keys are written as `Unidentified` and `Dead`, losing
+    /// the native key and the dead-key character.
+    ///
+    /// [`FromStr`]: std::str::FromStr
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Key::Named(named) => f.write_str(named.name()),
+            Key::Character(text) => f.write_str(text),
+            Key::Unidentified(_) => f.write_str("Unidentified"),
+            Key::Dead(_) => f.write_str("Dead"),
+        }
+    }
+}
+
+impl std::str::FromStr for Key {
+    type Err = KeyParseError;
+
+    /// Parses a key from a human-readable name, e.g. in a configuration file.
+    ///
+    /// This accepts the names of [`NamedKey`] variants, which are the UI Events Specification's
+    /// names where there is one, and single characters, optionally followed by combining marks.
+    /// Anything else is an error, rather than an [`Unidentified`][Key::Unidentified] key.
     ///
     /// # Examples
     ///
@@ -1602,25 +2396,1108 @@ impl Key {
     /// # fn main() {
     /// use winit::keyboard::{Key, NamedKey};
     ///
-    /// assert_eq!(Key::Character("a".into()).to_text(), Some("a"));
-    /// assert_eq!(Key::Named(NamedKey::Enter).to_text(), Some("\r"));
-    /// assert_eq!(Key::Named(NamedKey::F20).to_text(), None);
+    /// assert_eq!("Enter".parse::<Key>(), Ok(Key::Named(NamedKey::Enter)));
+    /// assert_eq!("F5".parse::<Key>(), Ok(Key::Named(NamedKey::F5)));
+    /// assert_eq!("é".parse::<Key>(), Ok(Key::Character("é".into())));
+    /// assert!("Entr".parse::<Key>().is_err());
     /// # }
     /// ```
-    pub fn to_text(&self) -> Option<&str> {
-        match self {
-            Key::Named(action) => action.to_text(),
-            Key::Character(ch) => Some(ch.as_str()),
-            _ => None,
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if let Some(named) = NamedKey::from_name(name) {
+            return Ok(Key::Named(named));
+        }
+
+        match name {
+            "Unidentified" => Ok(Key::Unidentified(NativeKey::Unidentified)),
+            "Dead" => Ok(Key::Dead(None)),
+            _ if is_single_character(name) => Ok(Key::Character(SmolStr::new(name))),
+            _ => Err(KeyParseError { name: name.to_owned() }),
         }
     }
 }
 
-/// The location of the key on the keyboard.
-///
-/// Certain physical keys on the keyboard can have the same value, but are in different locations.
-/// For instance, the Shift key can be on the left or right side of the keyboard, or the number
-/// keys can be above the letters or on the numpad. This enum allows the user to differentiate
+/// Whether `text` is a single character, optionally followed by combining marks.
+fn is_single_character(text: &str) -> bool {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if !first.is_control() => chars.all(|ch| {
+            matches!(ch,
+                '\u{0300}'..='\u{036f}'
+                | '\u{1ab0}'..='\u{1aff}'
+                | '\u{1dc0}'..='\u{1dff}'
+                | '\u{20d0}'..='\u{20ff}'
+                | '\u{fe00}'..='\u{fe0f}'
+                | '\u{fe20}'..='\u{fe2f}')
+        }),
+        _ => false,
+    }
+}
+
+/// The error returned when parsing a [`Key`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyParseError {
+    name: String,
+}
+
+impl std::fmt::Display for KeyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.name.is_empty() {
+            f.write_str("empty key name")
+        } else {
+            write!(
+                f,
+                "unknown key name `{}`, expected a `NamedKey` name or a single character",
+                self.name
+            )
+        }
+    }
+}
+
+impl std::error::Error for KeyParseError {}
+
+impl std::fmt::Display for NamedKey {
+    /// Formats the key as its [`name`][Self::name].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for NamedKey {
+    type Err = NamedKeyParseError;
+
+    /// Parses a key from its [`name`][Self::name], e.g. a web `KeyboardEvent.key` value.
+    ///
+    /// The name is case-sensitive, as in the UI Events Specification.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        NamedKey::from_name(name).ok_or_else(|| NamedKeyParseError { name: name.to_owned() })
+    }
+}
+
+impl TryFrom<&str> for NamedKey {
+    type Error = NamedKeyParseError;
+
+    /// See the [`FromStr`][std::str::FromStr] implementation.
+    #[inline]
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        name.parse()
+    }
+}
+
+/// The error returned when parsing a [`NamedKey`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedKeyParseError {
+    name: String,
+}
+
+impl std::fmt::Display for NamedKeyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown named key `{}`", self.name)
+    }
+}
+
+impl std::error::Error for NamedKeyParseError {}
+
+key_table!(NamedKey {
+    Alt,
+    AltGraph,
+    CapsLock,
+    Control,
+    Fn,
+    FnLock,
+    NumLock,
+    ScrollLock,
+    Shift,
+    Symbol,
+    SymbolLock,
+    Meta,
+    Hyper,
+    Super,
+    Enter,
+    Tab,
+    Space,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    End,
+    Home,
+    PageDown,
+    PageUp,
+    Backspace,
+    Clear,
+    Copy,
+    CrSel,
+    Cut,
+    Delete,
+    EraseEof,
+    ExSel,
+    Insert,
+    Paste,
+    Redo,
+    Undo,
+    Accept,
+    Again,
+    Attn,
+    Cancel,
+    ContextMenu,
+    Escape,
+    Execute,
+    Find,
+    Help,
+    Pause,
+    Play,
+    Props,
+    Select,
+    ZoomIn,
+    ZoomOut,
+    BrightnessDown,
+    BrightnessUp,
+    Eject,
+    LogOff,
+    Power,
+    PowerOff,
+    PrintScreen,
+    Hibernate,
+    Standby,
+    WakeUp,
+    AllCandidates,
+    Alphanumeric,
+    CodeInput,
+    Compose,
+    Convert,
+    FinalMode,
+    GroupFirst,
+    GroupLast,
+    GroupNext,
+    GroupPrevious,
+    ModeChange,
+    NextCandidate,
+    NonConvert,
+    PreviousCandidate,
+    Process,
+    SingleCandidate,
+    HangulMode,
+    HanjaMode,
+    JunjaMode,
+    Eisu,
+    Hankaku,
+    Hiragana,
+    HiraganaKatakana,
+    KanaMode,
+    KanjiMode,
+    Katakana,
+    Romaji,
+    Zenkaku,
+    ZenkakuHankaku,
+    Soft1,
+    Soft2,
+    Soft3,
+    Soft4,
+    ChannelDown,
+    ChannelUp,
+    Close,
+    MailForward,
+    MailReply,
+    MailSend,
+    MediaClose,
+    MediaFastForward,
+    MediaPause,
+    MediaPlay,
+    MediaPlayPause,
+    MediaRecord,
+    MediaRewind,
+    MediaStop,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    New,
+    Open,
+    Print,
+    Save,
+    SpellCheck,
+    Key11,
+    Key12,
+    AudioBalanceLeft,
+    AudioBalanceRight,
+    AudioBassBoostDown,
+    AudioBassBoostToggle,
+    AudioBassBoostUp,
+    AudioFaderFront,
+    AudioFaderRear,
+    AudioSurroundModeNext,
+    AudioTrebleDown,
+    AudioTrebleUp,
+    AudioVolumeDown,
+    AudioVolumeUp,
+    AudioVolumeMute,
+    MicrophoneToggle,
+    MicrophoneVolumeDown,
+    MicrophoneVolumeUp,
+    MicrophoneVolumeMute,
+    SpeechCorrectionList,
+    SpeechInputToggle,
+    LaunchApplication1,
+    LaunchApplication2,
+    LaunchCalendar,
+    LaunchContacts,
+    LaunchMail,
+    LaunchMediaPlayer,
+    LaunchMusicPlayer,
+    LaunchPhone,
+    LaunchScreenSaver,
+    LaunchSpreadsheet,
+    LaunchWebBrowser,
+    LaunchWebCam,
+    LaunchWordProcessor,
+    BrowserBack,
+    BrowserFavorites,
+    BrowserForward,
+    BrowserHome,
+    BrowserRefresh,
+    BrowserSearch,
+    BrowserStop,
+    AppSwitch,
+    Call,
+    Camera,
+    CameraFocus,
+    EndCall,
+    GoBack,
+    GoHome,
+    HeadsetHook,
+    LastNumberRedial,
+    Notification,
+    MannerMode,
+    VoiceDial,
+    TV,
+    TV3DMode,
+    TVAntennaCable,
+    TVAudioDescription,
+    TVAudioDescriptionMixDown,
+    TVAudioDescriptionMixUp,
+    TVContentsMenu,
+    TVDataService,
+    TVInput,
+    TVInputComponent1,
+    TVInputComponent2,
+    TVInputComposite1,
+    TVInputComposite2,
+    TVInputHDMI1,
+    TVInputHDMI2,
+    TVInputHDMI3,
+    TVInputHDMI4,
+    TVInputVGA1,
+    TVMediaContext,
+    TVNetwork,
+    TVNumberEntry,
+    TVPower,
+    TVRadioService,
+    TVSatellite,
+    TVSatelliteBS,
+    TVSatelliteCS,
+    TVSatelliteToggle,
+    TVTerrestrialAnalog,
+    TVTerrestrialDigital,
+    TVTimer,
+    AVRInput,
+    AVRPower,
+    ColorF0Red,
+    ColorF1Green,
+    ColorF2Yellow,
+    ColorF3Blue,
+    ColorF4Grey,
+    ColorF5Brown,
+    ClosedCaptionToggle,
+    Dimmer,
+    DisplaySwap,
+    DVR,
+    Exit,
+    FavoriteClear0,
+    FavoriteClear1,
+    FavoriteClear2,
+    FavoriteClear3,
+    FavoriteRecall0,
+    FavoriteRecall1,
+    FavoriteRecall2,
+    FavoriteRecall3,
+    FavoriteStore0,
+    FavoriteStore1,
+    FavoriteStore2,
+    FavoriteStore3,
+    Guide,
+    GuideNextDay,
+    GuidePreviousDay,
+    Info,
+    InstantReplay,
+    Link,
+    ListProgram,
+    LiveContent,
+    Lock,
+    MediaApps,
+    MediaAudioTrack,
+    MediaLast,
+    MediaSkipBackward,
+    MediaSkipForward,
+    MediaStepBackward,
+    MediaStepForward,
+    MediaTopMenu,
+    NavigateIn,
+    NavigateNext,
+    NavigateOut,
+    NavigatePrevious,
+    NextFavoriteChannel,
+    NextUserProfile,
+    OnDemand,
+    Pairing,
+    PinPDown,
+    PinPMove,
+    PinPToggle,
+    PinPUp,
+    PlaySpeedDown,
+    PlaySpeedReset,
+    PlaySpeedUp,
+    RandomToggle,
+    RcLowBattery,
+    RecordSpeedNext,
+    RfBypass,
+    ScanChannelsToggle,
+    ScreenModeNext,
+    Settings,
+    SplitScreenToggle,
+    STBInput,
+    STBPower,
+    Subtitle,
+    Teletext,
+    VideoModeNext,
+    Wink,
+    ZoomToggle,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    F25,
+    F26,
+    F27,
+    F28,
+    F29,
+    F30,
+    F31,
+    F32,
+    F33,
+    F34,
+    F35,
+});
+
+impl NamedKey {
+    /// Convert an action to its approximate textual equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::NamedKey;
+    ///
+    /// assert_eq!(NamedKey::Enter.to_text(), Some("\r"));
+    /// assert_eq!(NamedKey::F20.to_text(), None);
+    /// # }
+    /// ```
+    pub fn to_text(&self) -> Option<&str> {
+        match self {
+            NamedKey::Enter => Some("\r"),
+            NamedKey::Backspace => Some("\x08"),
+            NamedKey::Tab => Some("\t"),
+            NamedKey::Space => Some(" "),
+            NamedKey::Escape => Some("\x1b"),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the key inserts text into a text field.
+    ///
+    /// Named keys are actions rather than characters, so this is `false` for all of them except
+    /// [`Space`][Self::Space], [`Tab`][Self::Tab] and [`Enter`][Self::Enter], whose
+    /// [`to_text`][Self::to_text] is whitespace or a line break. [`Backspace`][Self::Backspace]
+    /// and [`Escape`][Self::Escape] also have a textual equivalent, but edit or cancel rather than
+    /// insert, so they aren't printable.
+    ///
+    /// See [`Key::is_printable`] to filter the keys of a text input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::NamedKey;
+    ///
+    /// assert!(NamedKey::Space.is_printable());
+    /// assert!(NamedKey::Enter.is_printable());
+    /// assert!(!NamedKey::Backspace.is_printable());
+    /// assert!(!NamedKey::ArrowLeft.is_printable());
+    /// assert!(!NamedKey::F5.is_printable());
+    /// # }
+    /// ```
+    pub fn is_printable(&self) -> bool {
+        matches!(self, NamedKey::Space | NamedKey::Tab | NamedKey::Enter)
+    }
+
+    /// Returns `true` if this is a modifier key.
+    ///
+    /// This follows the UI Events Specification's list of [modifier keys], so besides
+    /// <kbd>Shift</kbd>, <kbd>Control</kbd>, <kbd>Alt</kbd> and <kbd>Super</kbd>, the lock keys
+    /// such as [`CapsLock`][Self::CapsLock] and the [`Fn`][Self::Fn] key are modifiers too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::NamedKey;
+    ///
+    /// assert!(NamedKey::Shift.is_modifier());
+    /// assert!(NamedKey::CapsLock.is_modifier());
+    /// assert!(!NamedKey::Enter.is_modifier());
+    /// # }
+    /// ```
+    ///
+    /// [modifier keys]: https://w3c.github.io/uievents-key/#keys-modifier
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            self,
+            NamedKey::Alt
+                | NamedKey::AltGraph
+                | NamedKey::CapsLock
+                | NamedKey::Control
+                | NamedKey::Fn
+                | NamedKey::FnLock
+                | NamedKey::Meta
+                | NamedKey::NumLock
+                | NamedKey::ScrollLock
+                | NamedKey::Shift
+                | NamedKey::Symbol
+                | NamedKey::SymbolLock
+                | NamedKey::Hyper
+                | NamedKey::Super
+        )
+    }
+
+    /// The name of the key, as used by its [`Display`] and [`FromStr`] implementations and by
+    /// [`Key`]'s.
+    ///
+    /// This is the name of the variant, which is the UI Events Specification's
+    /// [`KeyboardEvent.key`] value of the key where there is one. [`Space`][Self::Space] is the
+    /// exception, it's called `"Space"` rather than `" "`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::NamedKey;
+    ///
+    /// assert_eq!(NamedKey::ArrowUp.name(), "ArrowUp");
+    /// assert_eq!("ArrowUp".parse(), Ok(NamedKey::ArrowUp));
+    /// # }
+    /// ```
+    ///
+    /// [`Display`]: std::fmt::Display
+    /// [`FromStr`]: std::str::FromStr
+    /// [`KeyboardEvent.key`]: https://w3c.github.io/uievents-key/
+    pub fn name(&self) -> &'static str {
+        self.table_name()
+    }
+
+    /// The physical key that conventionally produces this key on a standard keyboard, e.g.
+    /// [`KeyCode::Enter`] for [`NamedKey::Enter`].
+    ///
+    /// This is meant for showing a key on a rendered keyboard. It is only a convention, not what
+    /// the active layout does: layouts may produce the key elsewhere, or not at all. The modifiers
+    /// map to the key on the left side, except [`AltGraph`] which maps to [`KeyCode::AltRight`].
+    ///
+    /// Returns `None` for the keys that have no standard physical location, such as
+    /// [`Compose`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{KeyCode, NamedKey};
+    ///
+    /// assert_eq!(NamedKey::ArrowUp.default_code(), Some(KeyCode::ArrowUp));
+    /// assert_eq!(NamedKey::Shift.default_code(), Some(KeyCode::ShiftLeft));
+    /// assert_eq!(NamedKey::Compose.default_code(), None);
+    /// # }
+    /// ```
+    ///
+    /// [`AltGraph`]: Self::AltGraph
+    /// [`Compose`]: Self::Compose
+    pub fn default_code(&self) -> Option<KeyCode> {
+        let code = match self {
+            NamedKey::Alt => KeyCode::AltLeft,
+            NamedKey::AltGraph => KeyCode::AltRight,
+            NamedKey::Control => KeyCode::ControlLeft,
+            NamedKey::Shift => KeyCode::ShiftLeft,
+            NamedKey::Super | NamedKey::Meta => KeyCode::SuperLeft,
+            NamedKey::HangulMode => KeyCode::Lang1,
+            NamedKey::HanjaMode => KeyCode::Lang2,
+            NamedKey::LaunchApplication1 => KeyCode::LaunchApp1,
+            NamedKey::LaunchApplication2 => KeyCode::LaunchApp2,
+            NamedKey::LaunchMediaPlayer => KeyCode::MediaSelect,
+            NamedKey::Standby => KeyCode::Sleep,
+            // The other keys are named after the physical key producing them, if there is one.
+            key => return KeyCode::from_name(key.name()),
+        };
+        Some(code)
+    }
+
+    /// A short label for the key, as printed on keyboards, falling back to its
+    /// [`name`][Self::name].
+    fn label(&self) -> &'static str {
+        match self {
+            NamedKey::ArrowUp => "↑",
+            NamedKey::ArrowDown => "↓",
+            NamedKey::ArrowLeft => "←",
+            NamedKey::ArrowRight => "→",
+            NamedKey::Escape => "Esc",
+            NamedKey::Control => "Ctrl",
+            NamedKey::PageUp => "PgUp",
+            NamedKey::PageDown => "PgDn",
+            NamedKey::Delete => "Del",
+            NamedKey::Insert => "Ins",
+            NamedKey::CapsLock => "Caps Lock",
+            NamedKey::NumLock => "Num Lock",
+            NamedKey::ScrollLock => "Scroll Lock",
+            NamedKey::PrintScreen => "PrtSc",
+            key => key.name(),
+        }
+    }
+
+    /// The key with the given [`name`][Self::name], if any.
+    fn from_name(name: &str) -> Option<Self> {
+        Self::from_table_name(name)
+    }
+}
+
+impl Key {
+    /// Convert a key to its approximate textual equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert_eq!(Key::Character("a".into()).to_text(), Some("a"));
+    /// assert_eq!(Key::Named(NamedKey::Enter).to_text(), Some("\r"));
+    /// assert_eq!(Key::Named(NamedKey::F20).to_text(), None);
+    /// # }
+    /// ```
+    pub fn to_text(&self) -> Option<&str> {
+        match self {
+            Key::Named(action) => action.to_text(),
+            Key::Character(ch) => Some(ch.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the length in bytes of the [`to_text`][Self::to_text] of the key, or `0` if it
+    /// doesn't have a textual equivalent.
+    ///
+    /// The named keys with a textual equivalent, e.g. [`NamedKey::Enter`], count the control
+    /// character they correspond to. See [`text_char_count`][Self::text_char_count] for the
+    /// number of characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert_eq!(Key::Character("é".into()).text_len(), 2);
+    /// assert_eq!(Key::Named(NamedKey::Backspace).text_len(), 1);
+    /// assert_eq!(Key::Named(NamedKey::F20).text_len(), 0);
+    /// assert_eq!(Key::Dead(Some('`')).text_len(), 0);
+    /// # }
+    /// ```
+    pub fn text_len(&self) -> usize {
+        self.to_text().map_or(0, str::len)
+    }
+
+    /// Returns the number of [`char`]s of the [`to_text`][Self::to_text] of the key, or `0` if it
+    /// doesn't have a textual equivalent.
+    ///
+    /// This counts Unicode scalar values, so a character composed of several code points, e.g. an
+    /// emoji with a skin tone modifier, counts as more than one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert_eq!(Key::Character("é".into()).text_char_count(), 1);
+    /// assert_eq!(Key::Named(NamedKey::Enter).text_char_count(), 1);
+    /// assert_eq!(Key::Named(NamedKey::ArrowLeft).text_char_count(), 0);
+    /// # }
+    /// ```
+    pub fn text_char_count(&self) -> usize {
+        self.to_text().map_or(0, |text| text.chars().count())
+    }
+
+    /// Returns `true` if the key inserts text into a text field.
+    ///
+    /// This is the case of all [`Key::Character`]s, and of the named keys that are
+    /// [printable][NamedKey::is_printable]. A text input inserts the [`to_text`][Self::to_text]
+    /// of the pressed keys that are printable, and handles the other ones, e.g.
+    /// [`NamedKey::Backspace`] or [`NamedKey::ArrowLeft`], as editing actions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert!(Key::Character("a".into()).is_printable());
+    /// assert!(Key::Named(NamedKey::Tab).is_printable());
+    /// assert!(!Key::Named(NamedKey::Escape).is_printable());
+    /// assert!(!Key::Dead(Some('`')).is_printable());
+    /// # }
+    /// ```
+    pub fn is_printable(&self) -> bool {
+        match self {
+            Key::Named(action) => action.is_printable(),
+            Key::Character(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the character of a [`Key::Character`] that consists of a single `char`.
+    ///
+    /// Unlike [`to_text`][Self::to_text], named keys are never characters. A character made of
+    /// several Unicode scalar values, such as `é` written as `e` followed by a combining accent or
+    /// an emoji with a skin tone modifier, returns `None` even though it is a single grapheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert_eq!(Key::Character("a".into()).char(), Some('a'));
+    /// assert_eq!(Key::Character("\u{e9}".into()).char(), Some('\u{e9}'));
+    /// assert_eq!(Key::Character("e\u{301}".into()).char(), None);
+    /// assert_eq!(Key::Named(NamedKey::Space).char(), None);
+    /// # }
+    /// ```
+    pub fn char(&self) -> Option<char> {
+        let Key::Character(ch) = self else { return None };
+        let mut chars = ch.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Some(ch),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a modifier key.
+    ///
+    /// See [`NamedKey::is_modifier`] for which keys are considered modifiers. Characters, dead
+    /// keys and unidentified keys are never modifiers.
+    pub fn is_modifier(&self) -> bool {
+        match self {
+            Key::Named(named) => named.is_modifier(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this is a dead key, whether or not its character is known.
+    pub fn is_dead(&self) -> bool {
+        matches!(self, Key::Dead(_))
+    }
+
+    /// Returns the character of a [`Key::Dead`], e.g. ``'`'`` for a dead grave accent.
+    ///
+    /// Returns `None` for the other variants, and for dead keys whose character is unknown. On
+    /// Web, dead keys never carry their character, so this is always `None`; use
+    /// [`is_dead`][Self::is_dead] to detect them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::Key;
+    ///
+    /// assert_eq!(Key::Dead(Some('`')).dead_char(), Some('`'));
+    /// assert_eq!(Key::Dead(None).dead_char(), None);
+    /// assert_eq!(Key::Character("`".into()).dead_char(), None);
+    /// # }
+    /// ```
+    pub fn dead_char(&self) -> Option<char> {
+        match self {
+            Key::Dead(ch) => *ch,
+            _ => None,
+        }
+    }
+
+    /// Returns the key with [`Key::Character`] values in Unicode Normalization Form C.
+    ///
+    /// The same character can be reported as different code point sequences depending on the
+    /// platform and the input method, e.g. `é` as the single code point `U+00E9` or as `e`
+    /// followed by the combining acute accent `U+0301`. Normalizing both sides makes such keys
+    /// compare equal. All other variants are returned unchanged.
+    ///
+    /// This is only available with the `unicode-normalization` Cargo feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert_eq!(Key::Character("e\u{301}".into()).normalized(), Key::Character("\u{e9}".into()));
+    /// assert_eq!(Key::Named(NamedKey::Enter).normalized(), Key::Named(NamedKey::Enter));
+    /// # }
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn normalized(&self) -> Key {
+        match self {
+            Key::Character(ch) => {
+                use unicode_normalization::UnicodeNormalization;
+
+                Key::Character(ch.nfc().collect())
+            },
+            key => key.clone(),
+        }
+    }
+
+    /// Returns `true` if the keys are equal, ignoring the case of [`Key::Character`] values.
+    ///
+    /// This is what keyboard shortcuts usually want, as <kbd>Shift</kbd> or <kbd>Caps Lock</kbd>
+    /// change the case of the reported character: `Ctrl+A` should match `Ctrl+a`. The comparison
+    /// is Unicode-aware, e.g. `ß` matches `SS` and the Greek final `ς` matches `Σ`. All other
+    /// variants are compared exactly.
+    ///
+    /// Characters are compared as they are. Combine this with `normalized`, available with the
+    /// `unicode-normalization` feature, to also ignore how a character is composed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, NamedKey};
+    ///
+    /// assert!(Key::Character("a".into()).eq_ignore_case(&Key::Character("A".into())));
+    /// assert!(Key::Character("É".into()).eq_ignore_case(&Key::Character("é".into())));
+    /// assert!(!Key::Character("a".into()).eq_ignore_case(&Key::Character("b".into())));
+    /// assert!(!Key::Dead(Some('a')).eq_ignore_case(&Key::Dead(Some('A'))));
+    /// assert!(Key::Named(NamedKey::Enter).eq_ignore_case(&Key::Named(NamedKey::Enter)));
+    /// # }
+    /// ```
+    pub fn eq_ignore_case(&self, other: &Key) -> bool {
+        match (self, other) {
+            (Key::Character(ch), Key::Character(other)) => str_eq_ignore_case(ch, other),
+            _ => self == other,
+        }
+    }
+
+    /// Returns the digit `0`–`9` of the key, regardless of whether it is located on the digit row
+    /// or the numpad.
+    ///
+    /// Digits are usually reported as [`Key::Character`]. When <kbd>NumLock</kbd> is off, the
+    /// numpad digits are instead reported as their navigation function, e.g. [`NamedKey::End`] for
+    /// the "1" key. Those are only considered digits when `location` is [`KeyLocation::Numpad`],
+    /// so that the regular navigation keys aren't mistaken for digits.
+    ///
+    /// ```
+    /// use winit::keyboard::{Key, KeyLocation, NamedKey};
+    ///
+    /// assert_eq!(Key::Character("1".into()).logical_digit(KeyLocation::Standard), Some(1));
+    /// assert_eq!(Key::Character("1".into()).logical_digit(KeyLocation::Numpad), Some(1));
+    /// assert_eq!(Key::Named(NamedKey::End).logical_digit(KeyLocation::Numpad), Some(1));
+    /// assert_eq!(Key::Named(NamedKey::End).logical_digit(KeyLocation::Standard), None);
+    /// ```
+    pub fn logical_digit(&self, location: KeyLocation) -> Option<u8> {
+        match self {
+            Key::Character(ch) => match ch.as_bytes() {
+                [digit @ b'0'..=b'9'] => Some(digit - b'0'),
+                _ => None,
+            },
+            Key::Named(named) if location == KeyLocation::Numpad => match named {
+                NamedKey::Insert => Some(0),
+                NamedKey::End => Some(1),
+                NamedKey::ArrowDown => Some(2),
+                NamedKey::PageDown => Some(3),
+                NamedKey::ArrowLeft => Some(4),
+                NamedKey::Clear => Some(5),
+                NamedKey::ArrowRight => Some(6),
+                NamedKey::Home => Some(7),
+                NamedKey::ArrowUp => Some(8),
+                NamedKey::PageUp => Some(9),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Convert a key to its approximate textual equivalent, taking the held modifiers into
+    /// account.
+    ///
+    /// This behaves like [`to_text`][Self::to_text], except that holding <kbd>Control</kbd> while
+    /// pressing a [`Key::Character`] consisting of a single character in the `@`–`_` or `a`–`z`
+    /// range produces the corresponding C0 control character, like a terminal would. Lowercase
+    /// letters map to the same control character as their uppercase form:
+    ///
+    /// | Character              | Text            |
+    /// | ---------------------- | --------------- |
+    /// | `@`                    | `\x00` (NUL)    |
+    /// | `A` / `a` ..= `Z` / `z` | `\x01` ..= `\x1a` |
+    /// | `[`                    | `\x1b` (ESC)    |
+    /// | `\`                    | `\x1c` (FS)     |
+    /// | `]`                    | `\x1d` (GS)     |
+    /// | `^`                    | `\x1e` (RS)     |
+    /// | `_`                    | `\x1f` (US)     |
+    ///
+    /// Any other key, or any key pressed without <kbd>Control</kbd>, is converted exactly like
+    /// [`to_text`][Self::to_text] does. Other modifiers are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, ModifiersState, NamedKey};
+    ///
+    /// let ctrl = ModifiersState::CONTROL;
+    /// assert_eq!(Key::Character("a".into()).to_text_with_modifiers(ctrl), Some("\x01"));
+    /// assert_eq!(Key::Character("[".into()).to_text_with_modifiers(ctrl), Some("\x1b"));
+    /// assert_eq!(Key::Character("1".into()).to_text_with_modifiers(ctrl), Some("1"));
+    /// assert_eq!(Key::Named(NamedKey::Enter).to_text_with_modifiers(ctrl), Some("\r"));
+    /// assert_eq!(
+    ///     Key::Character("a".into()).to_text_with_modifiers(ModifiersState::empty()),
+    ///     Some("a")
+    /// );
+    /// # }
+    /// ```
+    pub fn to_text_with_modifiers(&self, mods: ModifiersState) -> Option<&str> {
+        if mods.control_key() {
+            if let Key::Character(ch) = self {
+                if let Some(control) = control_character(ch) {
+                    return Some(control);
+                }
+            }
+        }
+
+        self.to_text()
+    }
+
+    /// Convert a key to the platform-native form, as found in [`Key::Unidentified`].
+    ///
+    /// This is the inverse of how the key is reported by the platform, which is useful to compare
+    /// stored key bindings against native events, or to synthesize native events.
+    ///
+    /// The result depends on the platform and potentially on the keyboard layout, and many keys
+    /// have no native equivalent, in which case `None` is returned. The conversion is lossy:
+    /// several native keys may be reported as the same [`Key`], only one of them is returned.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** Returns the XKB keysym. Characters are mapped to their Latin-1 or
+    ///   Unicode keysym, which doesn't depend on the layout.
+    /// - **Web:** Returns the key attribute value of characters, named keys aren't supported.
+    /// - **Android / iOS / macOS / Orbital / Windows:** Unsupported, only [`Key::Unidentified`] is
+    ///   converted.
+    pub fn to_native(&self) -> Option<NativeKey> {
+        match self {
+            Key::Unidentified(NativeKey::Unidentified) => None,
+            Key::Unidentified(native) => Some(native.clone()),
+            key => key_to_native(key),
+        }
+    }
+}
+
+#[cfg(any(x11_platform, wayland_platform))]
+fn key_to_native(key: &Key) -> Option<NativeKey> {
+    crate::platform_impl::key_to_keysym(key).map(NativeKey::Xkb)
+}
+
+#[cfg(web_platform)]
+fn key_to_native(key: &Key) -> Option<NativeKey> {
+    match key {
+        Key::Character(text) => Some(NativeKey::Web(text.clone())),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(x11_platform, wayland_platform, web_platform)))]
+fn key_to_native(_: &Key) -> Option<NativeKey> {
+    None
+}
+
+/// The C0 control characters, indexed by their code.
+const C0_CONTROLS: [&str; 32] = [
+    "\x00", "\x01", "\x02", "\x03", "\x04", "\x05", "\x06", "\x07", "\x08", "\x09", "\x0a", "\x0b",
+    "\x0c", "\x0d", "\x0e", "\x0f", "\x10", "\x11", "\x12", "\x13", "\x14", "\x15", "\x16", "\x17",
+    "\x18", "\x19", "\x1a", "\x1b", "\x1c", "\x1d", "\x1e", "\x1f",
+];
+
+/// Compare the strings with the case folded.
+///
+/// The standard library has no case folding, so each character is mapped to uppercase and then
+/// back to lowercase, which folds the characters with several lowercase forms, such as `ß` and
+/// `ss`, or `ς` and `σ`.
+fn str_eq_ignore_case(a: &str, b: &str) -> bool {
+    fn fold(s: &str) -> impl Iterator<Item = char> + '_ {
+        s.chars().flat_map(char::to_uppercase).flat_map(char::to_lowercase)
+    }
+
+    a == b || fold(a).eq(fold(b))
+}
+
+/// The control character produced by pressing <kbd>Control</kbd> together with `text`, if any.
+fn control_character(text: &str) -> Option<&'static str> {
+    let mut chars = text.chars();
+    let ch = match (chars.next(), chars.next()) {
+        (Some(ch), None) => ch.to_ascii_uppercase(),
+        _ => return None,
+    };
+
+    match ch {
+        '@'..='_' => Some(C0_CONTROLS[(ch as usize) & 0x1f]),
+        _ => None,
+    }
+}
+
+/// How [`KeyEvent::key_for_shortcut`] identifies the key of a shortcut.
+///
+/// The two modes differ on layouts other than US QWERTY. Matching [`ByPosition`] keeps shortcuts
+/// at the same place on every keyboard, e.g. <kbd>Ctrl</kbd>+<kbd>Z</kbd> is the key left of
+/// <kbd>X</kbd> even on AZERTY, where that key types `w`, so a row of related shortcuts stays
+/// together; the label on the key doesn't match the shortcut, though. Matching [`ByLayout`]
+/// follows the labels the user sees, but shortcuts may move around or become unreachable: on
+/// AZERTY, the digit row types `&`, `é`, `"` and so on without <kbd>Shift</kbd>, so
+/// <kbd>Ctrl</kbd>+<kbd>1</kbd> becomes <kbd>Ctrl</kbd>+<kbd>&</kbd>. Applications commonly
+/// offer both and let the user pick.
+///
+/// [`KeyEvent::key_for_shortcut`]: crate::event::KeyEvent::key_for_shortcut
+/// [`ByPosition`]: Self::ByPosition
+/// [`ByLayout`]: Self::ByLayout
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShortcutMode {
+    /// The character keys are identified by the character they type on a US layout, whichever
+    /// layout is active.
+    ByPosition,
+    /// The keys are identified by what they type in the active layout, without modifiers.
+    #[default]
+    ByLayout,
+}
+
+/// The direction in which the text typed with a keyboard layout is written, see
+/// [`Window::keyboard_layout_direction`].
+///
+/// [`Window::keyboard_layout_direction`]: crate::window::Window::keyboard_layout_direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextDirection {
+    /// Left-to-right, like the Latin, Cyrillic or Greek scripts.
+    LeftToRight,
+    /// Right-to-left, like the Arabic or Hebrew scripts.
+    RightToLeft,
+}
+
+/// The languages usually written in a right-to-left script, as ISO 639 codes.
+#[cfg(any(macos_platform, test))]
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ji", "ks", "nqo", "ps", "sd", "syr", "ug", "ur",
+    "yi",
+];
+
+/// The right-to-left scripts, as ISO 15924 codes.
+#[cfg(any(macos_platform, test))]
+const RTL_SCRIPTS: &[&str] =
+    &["Adlm", "Arab", "Hebr", "Mand", "Nkoo", "Rohg", "Samr", "Syrc", "Thaa"];
+
+impl TextDirection {
+    /// The direction of the language with the BCP 47 tag `language`, e.g. `"he"` or `"pa-Arab"`.
+    ///
+    /// The script subtag decides when there is one, otherwise the usual script of the language.
+    #[cfg(any(macos_platform, test))]
+    pub(crate) fn from_language(language: &str) -> Self {
+        let mut subtags = language.split(['-', '_']);
+        let primary = subtags.next().unwrap_or_default();
+        let script = subtags
+            .find(|subtag| subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()));
+        let rtl = match script {
+            Some(script) => RTL_SCRIPTS.iter().any(|rtl| rtl.eq_ignore_ascii_case(script)),
+            None => RTL_LANGUAGES.iter().any(|rtl| rtl.eq_ignore_ascii_case(primary)),
+        };
+        if rtl {
+            TextDirection::RightToLeft
+        } else {
+            TextDirection::LeftToRight
+        }
+    }
+}
+
+/// The location of the key on the keyboard.
+///
+/// Certain physical keys on the keyboard can have the same value, but are in different locations.
+/// For instance, the Shift key can be on the left or right side of the keyboard, or the number
+/// keys can be above the letters or on the numpad. This enum allows the user to differentiate
 /// them.
 ///
 /// See the documentation for the [`location`] field on the [`KeyEvent`] struct for more
@@ -1683,6 +3560,12 @@ pub enum KeyLocation {
     ///
     /// For instance, the "1" key on the numpad will use this location.
     ///
+    /// The numpad operator keys are always reported as [`Key::Character`] with the operator,
+    /// i.e. `"+"`, `"-"`, `"*"` and `"/"`, regardless of the active modifiers.
+    ///
+    /// The digit keys are reported as navigation keys while <kbd>NumLock</kbd> is off, use
+    /// [`Key::logical_digit()`] to get the digit either way.
+    ///
     /// ![Numpad 1 key](https://raw.githubusercontent.com/rust-windowing/winit/master/docs/res/keyboard_numpad_1_key.svg)
     ///
     /// <sub>
@@ -1707,13 +3590,66 @@ bitflags! {
         /// The "control" key.
         const CONTROL = 0b100 << 3;
         /// The "alt" key.
+        ///
+        /// This is not set by the <kbd>AltGr</kbd> key, which has its own
+        /// [`ALT_GRAPH`][Self::ALT_GRAPH] flag.
         const ALT = 0b100 << 6;
         /// This is the "windows" key on PC and "command" key on Mac.
         const SUPER = 0b100 << 9;
+        /// Caps Lock is active.
+        ///
+        /// Unlike the other modifiers, this is set while the lock is engaged, not while the key is
+        /// pressed. Mask the lock flags out with [`without_locks`][Self::without_locks] when
+        /// matching exact key combinations.
+        const CAPS_LOCK = 0b100 << 12;
+        /// Num Lock is active.
+        ///
+        /// See [`CAPS_LOCK`][Self::CAPS_LOCK] for details.
+        const NUM_LOCK = 0b100 << 15;
+        /// Scroll Lock is active.
+        ///
+        /// See [`CAPS_LOCK`][Self::CAPS_LOCK] for details.
+        const SCROLL_LOCK = 0b100 << 18;
+        /// The "Fn" key found on laptop keyboards.
+        ///
+        /// See [`fn_key`][Self::fn_key] for the platforms that report it.
+        const FN = 0b100 << 21;
+        /// The "AltGr" key, which selects the third level of characters on many layouts.
+        ///
+        /// See [`alt_graph`][Self::alt_graph] for how the platforms report it.
+        const ALT_GRAPH = 0b100 << 24;
     }
 }
 
 impl ModifiersState {
+    /// Create the state from whether each of the modifiers is pressed.
+    ///
+    /// The lock flags are left unset. Being a `const fn`, this allows writing shortcut tables as
+    /// constants:
+    ///
+    /// ```
+    /// use winit::keyboard::ModifiersState;
+    ///
+    /// const SAVE_AS: ModifiersState = ModifiersState::new(true, true, false, false);
+    /// assert_eq!(SAVE_AS, ModifiersState::SHIFT | ModifiersState::CONTROL);
+    /// ```
+    pub const fn new(shift: bool, control: bool, alt: bool, super_key: bool) -> Self {
+        let mut state = Self::empty();
+        if shift {
+            state = state.union(Self::SHIFT);
+        }
+        if control {
+            state = state.union(Self::CONTROL);
+        }
+        if alt {
+            state = state.union(Self::ALT);
+        }
+        if super_key {
+            state = state.union(Self::SUPER);
+        }
+        state
+    }
+
     /// Returns `true` if the shift key is pressed.
     pub fn shift_key(&self) -> bool {
         self.intersects(Self::SHIFT)
@@ -1724,28 +3660,322 @@ impl ModifiersState {
         self.intersects(Self::CONTROL)
     }
 
-    /// Returns `true` if the alt key is pressed.
-    pub fn alt_key(&self) -> bool {
-        self.intersects(Self::ALT)
+    /// Returns `true` if the alt key is pressed.
+    ///
+    /// This is `false` when only <kbd>AltGr</kbd> is pressed, see [`alt_graph`][Self::alt_graph].
+    pub fn alt_key(&self) -> bool {
+        self.intersects(Self::ALT)
+    }
+
+    /// Returns `true` if the AltGr key is pressed.
+    ///
+    /// While <kbd>AltGr</kbd> is held, [`alt_key`][Self::alt_key] is `false`, so that typing
+    /// characters with it isn't mistaken for <kbd>Alt</kbd> shortcuts.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The system reports <kbd>AltGr</kbd> as a right <kbd>Alt</kbd> together with a
+    ///   fake left <kbd>Ctrl</kbd>. When the active layout has characters on the AltGr level and
+    ///   the right <kbd>Alt</kbd> key is pressed, this is set instead of both
+    ///   [`CONTROL`][Self::CONTROL] and [`ALT`][Self::ALT], even if the left <kbd>Ctrl</kbd> key is
+    ///   pressed too, as it can't be told apart from the fake one. On layouts without an AltGr
+    ///   level, the right <kbd>Alt</kbd> key is a plain <kbd>Alt</kbd>.
+    /// - **X11 / Wayland:** Set while the `LevelThree` modifier of the keymap is active, which is
+    ///   what <kbd>AltGr</kbd> is bound to on the layouts that have it.
+    /// - **Web:** Set from the `AltGraph` modifier state of the browser. When the browser reports
+    ///   <kbd>Ctrl</kbd> and <kbd>Alt</kbd> alongside it, as on Windows, they are removed.
+    /// - **Orbital:** Set while the right <kbd>Alt</kbd> key, which is always AltGr there, is
+    ///   pressed.
+    /// - **macOS / iOS / Android:** Unsupported, always `false`. Both <kbd>Option</kbd> keys are
+    ///   reported as [`ALT`][Self::ALT], use [`Modifiers::ralt_state`] to tell the right one apart.
+    ///
+    /// [`Modifiers::ralt_state`]: crate::event::Modifiers::ralt_state
+    pub fn alt_graph(&self) -> bool {
+        self.intersects(Self::ALT_GRAPH)
+    }
+
+    /// Returns `true` if the super key is pressed.
+    pub fn super_key(&self) -> bool {
+        self.intersects(Self::SUPER)
+    }
+
+    /// Returns `true` if the Fn key is pressed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Supported.
+    /// - **Windows / X11 / Wayland / Web / iOS / Android / Orbital:** Unsupported, always `false`.
+    ///   The key is usually handled by the keyboard itself and invisible to applications.
+    pub fn fn_key(&self) -> bool {
+        self.intersects(Self::FN)
+    }
+
+    /// Returns `true` if caps lock is active.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Orbital:** Unsupported, always `false`.
+    pub fn caps_lock(&self) -> bool {
+        self.intersects(Self::CAPS_LOCK)
+    }
+
+    /// Returns `true` if num lock is active.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / iOS / Android / Orbital:** Unsupported, always `false`.
+    pub fn num_lock(&self) -> bool {
+        self.intersects(Self::NUM_LOCK)
+    }
+
+    /// Returns `true` if scroll lock is active.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Only reported when the keymap binds scroll lock to a modifier.
+    /// - **macOS / iOS / Android / Orbital:** Unsupported, always `false`.
+    pub fn scroll_lock(&self) -> bool {
+        self.intersects(Self::SCROLL_LOCK)
+    }
+
+    /// Returns `true` if exactly the modifiers in `other` are pressed, and no others.
+    ///
+    /// Only the [`SHIFT`][Self::SHIFT], [`CONTROL`][Self::CONTROL], [`ALT`][Self::ALT],
+    /// [`ALT_GRAPH`][Self::ALT_GRAPH], [`SUPER`][Self::SUPER] and [`FN`][Self::FN] flags are
+    /// compared, so an engaged lock such as
+    /// [`CAPS_LOCK`][Self::CAPS_LOCK] doesn't prevent a match. Any other flag, including ones
+    /// added in the future, is ignored as well. This is the primitive to use when matching keyboard
+    /// shortcuts, as `Ctrl+S` shouldn't trigger on `Ctrl+Alt+S`.
+    ///
+    /// ```
+    /// use winit::keyboard::ModifiersState;
+    ///
+    /// let held = ModifiersState::CONTROL | ModifiersState::SHIFT | ModifiersState::CAPS_LOCK;
+    /// assert!(held.matches_exactly(ModifiersState::CONTROL | ModifiersState::SHIFT));
+    /// assert!(!held.matches_exactly(ModifiersState::CONTROL));
+    ///
+    /// let held = held | ModifiersState::ALT;
+    /// assert!(!held.matches_exactly(ModifiersState::CONTROL | ModifiersState::SHIFT));
+    /// ```
+    pub fn matches_exactly(&self, other: ModifiersState) -> bool {
+        self.without_locks() == other.without_locks()
+    }
+
+    /// Returns the state with only the flags of the pressed modifiers: [`SHIFT`][Self::SHIFT],
+    /// [`CONTROL`][Self::CONTROL], [`ALT`][Self::ALT], [`ALT_GRAPH`][Self::ALT_GRAPH],
+    /// [`SUPER`][Self::SUPER] and [`FN`][Self::FN].
+    ///
+    /// The lock flags, such as [`CAPS_LOCK`][Self::CAPS_LOCK], are removed, as well as any flag
+    /// added in the future. This keeps exact comparisons working regardless of the engaged locks:
+    ///
+    /// ```
+    /// use winit::keyboard::ModifiersState;
+    ///
+    /// let held = ModifiersState::CONTROL | ModifiersState::NUM_LOCK;
+    /// assert_ne!(held, ModifiersState::CONTROL);
+    /// assert_eq!(held.without_locks(), ModifiersState::CONTROL);
+    /// ```
+    pub const fn without_locks(self) -> ModifiersState {
+        self.intersection(
+            Self::SHIFT
+                .union(Self::CONTROL)
+                .union(Self::ALT)
+                .union(Self::ALT_GRAPH)
+                .union(Self::SUPER)
+                .union(Self::FN),
+        )
+    }
+
+    /// Returns the modifier flag corresponding to the given key, or `None` if the key is not a
+    /// modifier.
+    ///
+    /// [`NamedKey::AltGraph`] maps to [`ALT_GRAPH`][Self::ALT_GRAPH], both [`NamedKey::Super`]
+    /// and [`NamedKey::Meta`] map to [`SUPER`][Self::SUPER], and [`NamedKey::Fn`] maps to
+    /// [`FN`][Self::FN]. Lock keys are not considered modifiers.
+    pub fn from_named_key(key: NamedKey) -> Option<Self> {
+        match key {
+            NamedKey::Shift => Some(Self::SHIFT),
+            NamedKey::Control => Some(Self::CONTROL),
+            NamedKey::Alt => Some(Self::ALT),
+            NamedKey::AltGraph => Some(Self::ALT_GRAPH),
+            NamedKey::Super | NamedKey::Meta => Some(Self::SUPER),
+            NamedKey::Fn => Some(Self::FN),
+            _ => None,
+        }
+    }
+
+    /// The keys of the active modifiers, e.g. for logging them or showing them in a UI.
+    ///
+    /// The keys are returned in the order of the [`Display`] implementation, with
+    /// [`NamedKey::Super`] for [`SUPER`][Self::SUPER] and the lock keys, such as
+    /// [`NamedKey::CapsLock`], for the locks. Unknown flags are skipped.
+    ///
+    /// ```
+    /// use winit::keyboard::{ModifiersState, NamedKey};
+    ///
+    /// let state = ModifiersState::SHIFT | ModifiersState::CONTROL;
+    /// let keys: Vec<_> = state.named_keys().collect();
+    /// assert_eq!(keys, [NamedKey::Control, NamedKey::Shift]);
+    /// ```
+    ///
+    /// [`Display`]: std::fmt::Display
+    pub fn named_keys(&self) -> impl Iterator<Item = NamedKey> {
+        let state = *self;
+        MODIFIER_KEYS.into_iter().filter(move |(flag, _)| state.contains(*flag)).map(|(_, key)| key)
+    }
+}
+
+/// The keys of the modifiers, whose names are the canonical names of the modifiers, in the order
+/// they are formatted in.
+const MODIFIER_KEYS: [(ModifiersState, NamedKey); 9] = [
+    (ModifiersState::CONTROL, NamedKey::Control),
+    (ModifiersState::SHIFT, NamedKey::Shift),
+    (ModifiersState::ALT, NamedKey::Alt),
+    (ModifiersState::ALT_GRAPH, NamedKey::AltGraph),
+    (ModifiersState::SUPER, NamedKey::Super),
+    (ModifiersState::FN, NamedKey::Fn),
+    (ModifiersState::CAPS_LOCK, NamedKey::CapsLock),
+    (ModifiersState::NUM_LOCK, NamedKey::NumLock),
+    (ModifiersState::SCROLL_LOCK, NamedKey::ScrollLock),
+];
+
+impl std::fmt::Display for ModifiersState {
+    /// Formats the modifiers in the canonical form accepted by its [`FromStr`] implementation.
+    ///
+    /// The modifiers are written as `Control`, `Shift`, `Alt`, `AltGraph`, `Super` and `Fn`,
+    /// followed by the locks `CapsLock`, `NumLock` and `ScrollLock`, in that order and separated
+    /// by `+` without spaces, e.g. `Control+Shift`. The empty state is written as an empty
+    /// string. Unknown flags are not written.
+    ///
+    /// [`FromStr`]: std::str::FromStr
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut keys = self.named_keys();
+        if let Some(key) = keys.next() {
+            f.write_str(key.name())?;
+        }
+        for key in keys {
+            write!(f, "+{}", key.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for ModifiersState {
+    type Err = ModifiersParseError;
+
+    /// Parses modifiers separated by `+`, such as `Ctrl+Shift`.
+    ///
+    /// Besides the canonical names written by the [`Display`] implementation, the following
+    /// aliases are accepted: `Ctrl` for `Control`, `Opt` and `Option` for `Alt`, `AltGr` for
+    /// `AltGraph`, and `Cmd`, `Command`, `Win`, `Windows` and `Meta` for `Super`. Names are
+    /// case-insensitive and may be surrounded by whitespace, and the order doesn't matter. An
+    /// empty string is the empty state.
+    ///
+    /// Together with [`Key`]'s [`FromStr`] implementation, this parses complete key chords:
+    ///
+    /// ```
+    /// # #[cfg(web_platform)]
+    /// # wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+    /// # #[cfg_attr(web_platform, wasm_bindgen_test::wasm_bindgen_test)]
+    /// # fn main() {
+    /// use winit::keyboard::{Key, ModifiersState, NamedKey};
+    ///
+    /// let (mods, key) = "Ctrl+Shift+Enter".rsplit_once('+').unwrap();
+    /// let mods: ModifiersState = mods.parse().unwrap();
+    /// assert_eq!(mods, ModifiersState::CONTROL | ModifiersState::SHIFT);
+    /// assert_eq!(key.parse::<Key>(), Ok(Key::Named(NamedKey::Enter)));
+    /// assert_eq!(mods.to_string(), "Control+Shift");
+    /// # }
+    /// ```
+    ///
+    /// [`Display`]: std::fmt::Display
+    /// [`FromStr`]: std::str::FromStr
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut state = ModifiersState::empty();
+        if s.trim().is_empty() {
+            return Ok(state);
+        }
+
+        for name in s.split('+').map(str::trim) {
+            let flag = MODIFIER_KEYS
+                .iter()
+                .find(|(_, key)| key.name().eq_ignore_ascii_case(name))
+                .map(|(flag, _)| *flag)
+                .or_else(|| match name.to_ascii_lowercase().as_str() {
+                    "ctrl" => Some(ModifiersState::CONTROL),
+                    "opt" | "option" => Some(ModifiersState::ALT),
+                    "altgr" => Some(ModifiersState::ALT_GRAPH),
+                    "cmd" | "command" | "win" | "windows" | "meta" => Some(ModifiersState::SUPER),
+                    _ => None,
+                })
+                .ok_or_else(|| ModifiersParseError { name: name.to_owned() })?;
+            state |= flag;
+        }
+        Ok(state)
     }
+}
 
-    /// Returns `true` if the super key is pressed.
-    pub fn super_key(&self) -> bool {
-        self.intersects(Self::SUPER)
+/// The error returned when parsing a [`ModifiersState`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModifiersParseError {
+    name: String,
+}
+
+impl std::fmt::Display for ModifiersParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.name.is_empty() {
+            f.write_str("empty modifier name")
+        } else {
+            write!(f, "unknown modifier `{}`", self.name)
+        }
     }
 }
 
+impl std::error::Error for ModifiersParseError {}
+
 /// The state of the particular modifiers key.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ModifiersKeyState {
     /// The particular key is pressed.
     Pressed,
+    /// The particular key is not pressed.
+    Released,
     /// The state of the key is unknown.
     #[default]
     Unknown,
 }
 
+/// The state of each of the left and right modifier keys, as returned by
+/// [`Modifiers::keys_state`].
+///
+/// A key is [`ModifiersKeyState::Unknown`] when the platform doesn't report it, which is the case
+/// for all the keys on X11 and Wayland, where the modifiers state doesn't tell which key changed
+/// it. See [`Modifiers`] for the other platforms.
+///
+/// [`Modifiers`]: crate::event::Modifiers
+/// [`Modifiers::keys_state`]: crate::event::Modifiers::keys_state
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ModifiersKeysState {
+    /// The state of the left shift key.
+    pub lshift: ModifiersKeyState,
+    /// The state of the right shift key.
+    pub rshift: ModifiersKeyState,
+    /// The state of the left control key.
+    pub lcontrol: ModifiersKeyState,
+    /// The state of the right control key.
+    pub rcontrol: ModifiersKeyState,
+    /// The state of the left alt key.
+    pub lalt: ModifiersKeyState,
+    /// The state of the right alt key.
+    pub ralt: ModifiersKeyState,
+    /// The state of the left super key.
+    pub lsuper: ModifiersKeyState,
+    /// The state of the right super key.
+    pub rsuper: ModifiersKeyState,
+}
+
 // NOTE: the exact modifier key is not used to represent modifiers state in the
 // first place due to a fact that modifiers state could be changed without any
 // key being pressed and on some platforms like Wayland/X11 which key resulted
@@ -1768,3 +3998,1149 @@ bitflags! {
         const RSUPER   = 0b1000_0000;
     }
 }
+
+/// Tracks the [`ModifiersState`] from [`KeyEvent`]s alone.
+///
+/// The modifier keys are recognized with [`ModifiersState::from_named_key`], and each physical
+/// key is tracked separately, so releasing the left <kbd>Shift</kbd> while the right one is held
+/// keeps [`SHIFT`][ModifiersState::SHIFT] set. Lock keys are not tracked.
+///
+/// Applications that derive the modifiers from the key events instead of
+/// [`WindowEvent::ModifiersChanged`] should [`reset`][Self::reset] the tracker when the window
+/// loses focus, as it doesn't receive the releases that happen while it's unfocused.
+///
+/// ```
+/// use winit::event::WindowEvent;
+/// use winit::keyboard::{ModifiersState, ModifiersTracker};
+///
+/// fn handle(tracker: &mut ModifiersTracker, event: &WindowEvent) -> ModifiersState {
+///     match event {
+///         WindowEvent::KeyboardInput { event, .. } => tracker.update(event),
+///         WindowEvent::Focused(false) => {
+///             tracker.reset();
+///             tracker.state()
+///         },
+///         _ => tracker.state(),
+///     }
+/// }
+/// ```
+///
+/// [`WindowEvent::ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ModifiersTracker {
+    held: Vec<(PhysicalKey, ModifiersState)>,
+}
+
+impl ModifiersTracker {
+    /// Creates a tracker with no modifier pressed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the state with `event`, and returns the new state.
+    pub fn update(&mut self, event: &KeyEvent) -> ModifiersState {
+        self.update_key(event.physical_key, &event.logical_key, event.state)
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> ModifiersState {
+        self.held.iter().fold(ModifiersState::empty(), |state, (_, flag)| state | *flag)
+    }
+
+    /// Forgets all the pressed modifiers.
+    pub fn reset(&mut self) {
+        self.held.clear();
+    }
+
+    fn update_key(
+        &mut self,
+        physical_key: PhysicalKey,
+        logical_key: &Key,
+        state: ElementState,
+    ) -> ModifiersState {
+        // The logical key of the release may differ from the press, e.g. when another modifier
+        // changed the layout level in between, so releases only look at the physical key.
+        self.held.retain(|(key, _)| *key != physical_key);
+        if state.is_pressed() {
+            if let Key::Named(named) = logical_key {
+                if let Some(flag) = ModifiersState::from_named_key(*named) {
+                    self.held.push((physical_key, flag));
+                }
+            }
+        }
+        self.state()
+    }
+}
+
+/// A keyboard shortcut, such as <kbd>Ctrl</kbd>+<kbd>S</kbd>, to match against [`KeyEvent`]s.
+///
+/// A shortcut matches a key event when all of the following hold:
+///
+/// - The key is [pressed][ElementState::Pressed]. Repeats match too, check [`KeyEvent::repeat`] to
+///   ignore them.
+/// - The key matches [`Shortcut::key`], see [`ShortcutKey`] for how each kind is compared.
+/// - Exactly the modifiers in [`Shortcut::mods`] are pressed, in the sense of
+///   [`ModifiersState::matches_exactly`]: lock keys are ignored, but an extra <kbd>Alt</kbd> does
+///   prevent a match. Logical character shortcuts relax this for the modifiers that select the
+///   character, as described on [`ShortcutKey::Logical`].
+///
+/// ```
+/// use winit::keyboard::{KeyCode, ModifiersState, Shortcut};
+///
+/// const SAVE: Shortcut = Shortcut::logical_char(ModifiersState::CONTROL, "s");
+/// const QUICK_SAVE: Shortcut = Shortcut::physical(ModifiersState::empty(), KeyCode::F5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Shortcut {
+    /// The modifiers that must be pressed.
+    pub mods: ModifiersState,
+    /// The key that must be pressed.
+    pub key: ShortcutKey,
+}
+
+/// The key of a [`Shortcut`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShortcutKey {
+    /// Match [`KeyEvent::physical_key`], i.e. the position of the key on the keyboard.
+    ///
+    /// Use this for bindings that depend on the key's position, like <kbd>W</kbd><kbd>A</kbd>
+    /// <kbd>S</kbd><kbd>D</kbd> in games. Modifiers are always matched exactly.
+    Physical(KeyCode),
+    /// Match [`KeyEvent::logical_key`], i.e. what the key means in the current layout.
+    ///
+    /// [`Key::Named`] keys are compared as is, with the modifiers matched exactly.
+    ///
+    /// [`Key::Character`] keys are compared case-insensitively, so `"s"` matches both `"s"` and
+    /// `"S"`. Since the modifiers may be what selects the character, the following are relaxed:
+    ///
+    /// - For characters without case, such as `"+"`, <kbd>Shift</kbd> is ignored, as it's needed
+    ///   to type them on many layouts. For letters, <kbd>Shift</kbd> is matched exactly, so
+    ///   <kbd>Ctrl</kbd>+<kbd>Z</kbd> and <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>Z</kbd> stay
+    ///   distinct.
+    /// - <kbd>AltGr</kbd>, i.e. [`ModifiersState::ALT_GRAPH`], is ignored unless the shortcut
+    ///   includes it.
+    /// - When the key event's text is the character and both <kbd>Ctrl</kbd> and <kbd>Alt</kbd>
+    ///   are pressed, they are taken to be <kbd>AltGr</kbd>, which Windows reports that way on
+    ///   layouts it isn't detected on, and ignored unless the shortcut includes either of them.
+    ///
+    /// [`Key::Dead`] and [`Key::Unidentified`] never match.
+    Logical(Key),
+}
+
+impl Shortcut {
+    /// Create a shortcut matching the physical key `code`.
+    pub const fn physical(mods: ModifiersState, code: KeyCode) -> Self {
+        Self { mods, key: ShortcutKey::Physical(code) }
+    }
+
+    /// Create a shortcut matching the logical key `key`.
+    pub const fn logical(mods: ModifiersState, key: Key) -> Self {
+        Self { mods, key: ShortcutKey::Logical(key) }
+    }
+
+    /// Create a shortcut matching the logical character `ch`.
+    pub const fn logical_char(mods: ModifiersState, ch: &'static str) -> Self {
+        Self::logical(mods, Key::Character(SmolStr::new_static(ch)))
+    }
+
+    /// Returns `true` if `event` triggers this shortcut while `mods` are pressed.
+    ///
+    /// `mods` is the state last reported by [`WindowEvent::ModifiersChanged`]. See [`Shortcut`]
+    /// for the exact semantics.
+    ///
+    /// [`WindowEvent::ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+    pub fn matches(&self, event: &KeyEvent, mods: ModifiersState) -> bool {
+        event.state == ElementState::Pressed
+            && self.matches_key(event.physical_key, &event.logical_key, event.text.as_deref(), mods)
+    }
+
+    fn matches_key(
+        &self,
+        physical_key: PhysicalKey,
+        logical_key: &Key,
+        text: Option<&str>,
+        mut mods: ModifiersState,
+    ) -> bool {
+        let ch = match (&self.key, logical_key) {
+            (ShortcutKey::Physical(code), _) => {
+                return physical_key == *code && mods.matches_exactly(self.mods)
+            },
+            (ShortcutKey::Logical(Key::Character(ch)), Key::Character(pressed))
+                if str_eq_ignore_case(ch, pressed) =>
+            {
+                ch
+            },
+            (ShortcutKey::Logical(Key::Named(named)), Key::Named(pressed)) => {
+                return named == pressed && mods.matches_exactly(self.mods)
+            },
+            _ => return false,
+        };
+
+        let altgr = ModifiersState::CONTROL | ModifiersState::ALT;
+        if !self.mods.intersects(altgr)
+            && mods.contains(altgr)
+            && text.is_some_and(|text| str_eq_ignore_case(text, ch))
+        {
+            mods.remove(altgr);
+        }
+        if !self.mods.alt_graph() {
+            mods.remove(ModifiersState::ALT_GRAPH);
+        }
+        if ch.to_lowercase() == ch.to_uppercase() {
+            mods.set(ModifiersState::SHIFT, self.mods.shift_key());
+        }
+        mods.matches_exactly(self.mods)
+    }
+}
+
+/// Build a key chord, i.e. modifiers and a key, to compare against a `(ModifiersState, Key)`
+/// pair.
+///
+/// The chord is written as the modifiers followed by the key, separated by `+`:
+///
+/// - The modifiers are `Shift`, `Ctrl` or `Control`, `Alt`, `AltGr` and `Super`, in any order.
+/// - The key is either a string literal, for a [`Key::Character`], or the name of a [`NamedKey`].
+///   `Ctrl` is accepted for [`NamedKey::Control`] there too.
+///
+/// It expands to a constant expression, so chords can be used in `const` items too. Being a
+/// tuple holding a [`SmolStr`], the result can't be used as a `match` pattern, compare it with
+/// `==` instead:
+///
+/// ```
+/// use winit::event::KeyEvent;
+/// use winit::keyboard::{key_chord, Key, ModifiersState, NamedKey};
+///
+/// const SAVE_AS: (ModifiersState, Key) = key_chord!(Ctrl + Shift + "s");
+///
+/// fn dispatch(event: &KeyEvent, mods: ModifiersState) {
+///     let chord = (mods.without_locks(), event.key_without_modifiers.clone());
+///     if chord == key_chord!(Ctrl + "s") {
+///         // Save.
+///     } else if chord == SAVE_AS {
+///         // Save as.
+///     } else if chord == key_chord!(Alt + F4) {
+///         // Quit.
+///     }
+/// }
+///
+/// assert_eq!(
+///     key_chord!(Ctrl + Shift + "s"),
+///     (ModifiersState::CONTROL | ModifiersState::SHIFT, Key::Character("s".into()))
+/// );
+/// assert_eq!(key_chord!(Escape), (ModifiersState::empty(), Key::Named(NamedKey::Escape)));
+/// ```
+///
+/// Characters are compared as is, and <kbd>Shift</kbd> usually changes the character of
+/// [`KeyEvent::logical_key`]: <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>S</kbd> is reported as `"S"`
+/// there. Compare against [`KeyEvent::key_without_modifiers`] as above, or use a [`Shortcut`],
+/// which handles this and <kbd>AltGr</kbd>, when that matters.
+///
+/// The last name is always the key, so a modifier key on its own is written as such. For
+/// instance `key_chord!(Ctrl + Shift)` is <kbd>Shift</kbd> pressed while <kbd>Ctrl</kbd> is held.
+/// Note that the modifiers state reported with the press of a modifier key may already include
+/// it, depending on the platform; mask it out with [`ModifiersState::from_named_key`] if needed.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! key_chord {
+    // Accumulate the modifiers until only the key is left.
+    (@chord [$($mods:ident)*] $modifier:ident + $($rest:tt)+) => {
+        $crate::key_chord!(@chord [$($mods)* $modifier] $($rest)+)
+    };
+    (@chord [$($mods:ident)*] $key:literal) => {
+        (
+            $crate::key_chord!(@mods $($mods)*),
+            $crate::keyboard::Key::Character($crate::keyboard::SmolStr::new_static($key)),
+        )
+    };
+    // `Ctrl` is only a modifier name, the key is `Control`.
+    (@chord [$($mods:ident)*] Ctrl) => {
+        $crate::key_chord!(@chord [$($mods)*] Control)
+    };
+    (@chord [$($mods:ident)*] $key:ident) => {
+        (
+            $crate::key_chord!(@mods $($mods)*),
+            $crate::keyboard::Key::<$crate::keyboard::SmolStr>::Named(
+                $crate::keyboard::NamedKey::$key,
+            ),
+        )
+    };
+    (@mods $($modifier:ident)*) => {
+        $crate::keyboard::ModifiersState::empty()
+            $(.union($crate::key_chord!(@modifier $modifier)))*
+    };
+    (@modifier Shift) => {
+        $crate::keyboard::ModifiersState::new(true, false, false, false)
+    };
+    (@modifier Ctrl) => {
+        $crate::keyboard::ModifiersState::new(false, true, false, false)
+    };
+    (@modifier Control) => {
+        $crate::keyboard::ModifiersState::new(false, true, false, false)
+    };
+    (@modifier Alt) => {
+        $crate::keyboard::ModifiersState::new(false, false, true, false)
+    };
+    (@modifier AltGr) => {
+        $crate::keyboard::ModifiersState::ALT_GRAPH
+    };
+    (@modifier Super) => {
+        $crate::keyboard::ModifiersState::new(false, false, false, true)
+    };
+    ($($chord:tt)+) => {
+        $crate::key_chord!(@chord [] $($chord)+)
+    };
+}
+
+#[doc(inline)]
+pub use crate::key_chord;
+
+#[cfg(feature = "keyboard-types")]
+impl From<keyboard_types::Key> for Key {
+    /// Convert a key of the [`keyboard_types`] crate.
+    ///
+    /// The named keys are mapped to the [`NamedKey`] of the same name, except for:
+    /// - `Meta`, the <kbd>Windows</kbd> or <kbd>Command</kbd> key, which becomes
+    ///   [`NamedKey::Super`], while the legacy `Super` becomes the legacy [`NamedKey::Meta`].
+    /// - `Unidentified` which becomes [`Key::Unidentified`] with [`NativeKey::Unidentified`], and
+    ///   `Dead` which becomes [`Key::Dead`] without a character, as the source type doesn't carry
+    ///   the native key nor the dead key's character.
+    ///
+    /// A space character is mapped to [`NamedKey::Space`], like winit reports it.
+    // `Super` is deprecated as a legacy key by `keyboard_types`.
+    #[allow(deprecated)]
+    fn from(key: keyboard_types::Key) -> Self {
+        use keyboard_types::NamedKey as Source;
+
+        match key {
+            keyboard_types::Key::Character(text) if text == " " => Key::Named(NamedKey::Space),
+            keyboard_types::Key::Character(text) => Key::Character(text.into()),
+            keyboard_types::Key::Named(Source::Meta) => Key::Named(NamedKey::Super),
+            keyboard_types::Key::Named(Source::Super) => Key::Named(NamedKey::Meta),
+            keyboard_types::Key::Named(Source::Dead) => Key::Dead(None),
+            // The other keys have the same name, `Unidentified` and the keys unknown to winit
+            // aren't in the table.
+            keyboard_types::Key::Named(key) => NamedKey::from_table_name(&key.to_string())
+                .map_or(Key::Unidentified(NativeKey::Unidentified), Key::Named),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_is_modifier() {
+        for named in [NamedKey::Shift, NamedKey::AltGraph, NamedKey::Meta, NamedKey::NumLock] {
+            assert!(named.is_modifier());
+            assert!(Key::Named(named).is_modifier());
+        }
+        assert!(!Key::Named(NamedKey::Enter).is_modifier());
+        assert!(!Key::Character("a".into()).is_modifier());
+        assert!(!Key::Dead(None).is_modifier());
+
+        // Every key that maps to a modifier flag is a modifier key.
+        for named in [
+            NamedKey::Shift,
+            NamedKey::Control,
+            NamedKey::Alt,
+            NamedKey::AltGraph,
+            NamedKey::Super,
+            NamedKey::Meta,
+        ] {
+            assert!(ModifiersState::from_named_key(named).is_some());
+            assert!(named.is_modifier(), "{named:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn key_normalized() {
+        let character = |text: &str| Key::Character(text.into());
+
+        assert_eq!(character("e\u{301}").normalized(), character("\u{e9}"));
+        assert_eq!(character("\u{e9}").normalized(), character("\u{e9}"));
+        // Dead-key style input with the marks in non-canonical order.
+        assert_eq!(character("a\u{302}\u{323}").normalized(), character("\u{1ead}"));
+        assert_eq!(character("\u{1100}\u{1161}").normalized(), character("\u{ac00}"));
+        assert_eq!(Key::Dead(Some('\u{301}')).normalized(), Key::Dead(Some('\u{301}')));
+    }
+
+    #[test]
+    fn text_direction_from_language() {
+        use TextDirection::{LeftToRight, RightToLeft};
+
+        for (language, direction) in [
+            ("en", LeftToRight),
+            ("en-US", LeftToRight),
+            ("ar", RightToLeft),
+            ("he_IL", RightToLeft),
+            ("fa-IR", RightToLeft),
+            // The script takes precedence over the language.
+            ("pa-Arab", RightToLeft),
+            ("ku-Latn-TR", LeftToRight),
+            ("ku-Arab", RightToLeft),
+            ("sd-Deva-IN", LeftToRight),
+            ("", LeftToRight),
+        ] {
+            assert_eq!(TextDirection::from_language(language), direction, "{language}");
+        }
+    }
+
+    #[test]
+    fn key_chord() {
+        // Characters.
+        assert_eq!(key_chord!("a"), (ModifiersState::empty(), Key::Character("a".into())));
+        assert_eq!(
+            key_chord!(Ctrl + Shift + "a"),
+            (ModifiersState::CONTROL | ModifiersState::SHIFT, Key::Character("a".into()))
+        );
+        assert_eq!(key_chord!(Shift + Ctrl + "a"), key_chord!(Control + Shift + "a"));
+        assert_ne!(key_chord!(Ctrl + "a"), key_chord!(Ctrl + "A"));
+
+        // Named keys.
+        assert_eq!(
+            key_chord!(Alt + Super + F4),
+            (ModifiersState::ALT | ModifiersState::SUPER, Key::Named(NamedKey::F4))
+        );
+        assert_eq!(key_chord!(Enter), (ModifiersState::empty(), Key::Named(NamedKey::Enter)));
+        assert_eq!(
+            key_chord!(AltGr + "@"),
+            (ModifiersState::ALT_GRAPH, Key::Character("@".into()))
+        );
+
+        // Modifier keys are keys too, the last name is always the key.
+        assert_eq!(key_chord!(Shift), (ModifiersState::empty(), Key::Named(NamedKey::Shift)));
+        assert_eq!(
+            key_chord!(Ctrl + Shift),
+            (ModifiersState::CONTROL, Key::Named(NamedKey::Shift))
+        );
+        assert_eq!(
+            key_chord!(Ctrl + Control),
+            (ModifiersState::CONTROL, Key::Named(NamedKey::Control))
+        );
+        assert_eq!(key_chord!(Ctrl), (ModifiersState::empty(), Key::Named(NamedKey::Control)));
+        assert_eq!(
+            key_chord!(Shift + Ctrl),
+            (ModifiersState::SHIFT, Key::Named(NamedKey::Control))
+        );
+
+        // Usable in constants, and agrees with the parser.
+        const SAVE: (ModifiersState, Key) = key_chord!(Ctrl + "s");
+        assert_eq!(SAVE, ("Ctrl".parse().unwrap(), "s".parse().unwrap()));
+    }
+
+    #[test]
+    fn key_is_dead() {
+        let keys = [
+            (Key::Dead(Some('^')), true, Some('^')),
+            // As on Web, where the character is never known.
+            (Key::Dead(None), true, None),
+            (Key::Character("^".into()), false, None),
+            (Key::Named(NamedKey::Compose), false, None),
+            (Key::Unidentified(NativeKey::Unidentified), false, None),
+        ];
+        for (key, is_dead, dead_char) in keys {
+            assert_eq!(key.is_dead(), is_dead, "{key:?}");
+            assert_eq!(key.dead_char(), dead_char, "{key:?}");
+        }
+    }
+
+    #[test]
+    fn key_code_is_modifier() {
+        // The physical modifier keys, with their logical key on a US layout.
+        let modifiers = [
+            (KeyCode::AltLeft, NamedKey::Alt),
+            (KeyCode::AltRight, NamedKey::Alt),
+            (KeyCode::CapsLock, NamedKey::CapsLock),
+            (KeyCode::ControlLeft, NamedKey::Control),
+            (KeyCode::ControlRight, NamedKey::Control),
+            (KeyCode::Fn, NamedKey::Fn),
+            (KeyCode::FnLock, NamedKey::FnLock),
+            (KeyCode::Meta, NamedKey::Meta),
+            (KeyCode::NumLock, NamedKey::NumLock),
+            (KeyCode::ScrollLock, NamedKey::ScrollLock),
+            (KeyCode::ShiftLeft, NamedKey::Shift),
+            (KeyCode::ShiftRight, NamedKey::Shift),
+            (KeyCode::Hyper, NamedKey::Hyper),
+            (KeyCode::SuperLeft, NamedKey::Super),
+            (KeyCode::SuperRight, NamedKey::Super),
+        ];
+        for (code, named) in modifiers {
+            assert!(named.is_modifier(), "{named:?}");
+            assert!(PhysicalKey::Code(code).is_modifier());
+        }
+        for code in KeyCode::all() {
+            let listed = modifiers.iter().any(|&(modifier, _)| modifier == code);
+            assert_eq!(code.is_modifier(), listed, "{code:?}");
+        }
+        assert!(!PhysicalKey::Unidentified(NativeKeyCode::Xkb(0x32)).is_modifier());
+    }
+
+    #[test]
+    fn modifiers_matches_exactly() {
+        let ctrl = ModifiersState::CONTROL;
+        let ctrl_alt = ModifiersState::CONTROL | ModifiersState::ALT;
+
+        assert!(ctrl.matches_exactly(ModifiersState::CONTROL));
+        assert!(!ctrl_alt.matches_exactly(ModifiersState::CONTROL));
+        assert!(ctrl_alt.matches_exactly(ModifiersState::CONTROL | ModifiersState::ALT));
+        assert!(!ctrl.matches_exactly(ModifiersState::CONTROL | ModifiersState::ALT));
+        assert!(!ModifiersState::empty().matches_exactly(ModifiersState::CONTROL));
+        assert!(ModifiersState::empty().matches_exactly(ModifiersState::empty()));
+
+        // Lock flags are ignored on either side.
+        let ctrl_caps = ModifiersState::CONTROL | ModifiersState::CAPS_LOCK;
+        assert!(ctrl_caps.matches_exactly(ModifiersState::CONTROL));
+        assert!(ctrl.matches_exactly(ModifiersState::CONTROL | ModifiersState::NUM_LOCK));
+
+        // So are flags unknown to this version.
+        let unknown = ModifiersState::from_bits_retain(1 << 30);
+        assert!((ctrl | unknown).matches_exactly(ModifiersState::CONTROL));
+        assert!(ctrl.matches_exactly(ModifiersState::CONTROL | unknown));
+
+        let locks =
+            ModifiersState::CAPS_LOCK | ModifiersState::NUM_LOCK | ModifiersState::SCROLL_LOCK;
+        assert_eq!((ctrl_alt | locks | unknown).without_locks(), ctrl_alt);
+        assert_eq!(ModifiersState::all().without_locks(), ModifiersState::all().difference(locks));
+    }
+
+    #[test]
+    fn modifiers_tracker() {
+        let mut tracker = ModifiersTracker::new();
+        let (lshift, rshift) =
+            (PhysicalKey::Code(KeyCode::ShiftLeft), PhysicalKey::Code(KeyCode::ShiftRight));
+        let lctrl = PhysicalKey::Code(KeyCode::ControlLeft);
+        let shift = Key::Named(NamedKey::Shift);
+        let control = Key::Named(NamedKey::Control);
+        let (pressed, released) = (ElementState::Pressed, ElementState::Released);
+
+        assert_eq!(tracker.update_key(lshift, &shift, pressed), ModifiersState::SHIFT);
+        assert_eq!(tracker.update_key(rshift, &shift, pressed), ModifiersState::SHIFT);
+        let both = ModifiersState::SHIFT | ModifiersState::CONTROL;
+        assert_eq!(tracker.update_key(lctrl, &control, pressed), both);
+        // Repeats don't change anything.
+        assert_eq!(tracker.update_key(lctrl, &control, pressed), both);
+
+        // The other shift is still held.
+        assert_eq!(tracker.update_key(lshift, &shift, released), both);
+        // Releases are matched by physical key.
+        let other = Key::Character("a".into());
+        assert_eq!(tracker.update_key(rshift, &other, released), ModifiersState::CONTROL);
+
+        // Non-modifier keys and lock keys are ignored.
+        let caps_lock = Key::Named(NamedKey::CapsLock);
+        tracker.update_key(PhysicalKey::Code(KeyCode::CapsLock), &caps_lock, pressed);
+        tracker.update_key(PhysicalKey::Code(KeyCode::KeyA), &other, pressed);
+        assert_eq!(tracker.state(), ModifiersState::CONTROL);
+
+        tracker.reset();
+        assert_eq!(tracker.state(), ModifiersState::empty());
+        assert_eq!(tracker.update_key(lctrl, &control, released), ModifiersState::empty());
+    }
+
+    #[test]
+    fn modifiers_from_named_key() {
+        // AltGr isn't Alt.
+        assert!(ModifiersState::ALT_GRAPH.alt_graph());
+        assert!(!ModifiersState::ALT_GRAPH.alt_key());
+        assert!(!ModifiersState::ALT.alt_graph());
+
+        assert_eq!(ModifiersState::from_named_key(NamedKey::Shift), Some(ModifiersState::SHIFT));
+        assert_eq!(
+            ModifiersState::from_named_key(NamedKey::Control),
+            Some(ModifiersState::CONTROL)
+        );
+        assert_eq!(ModifiersState::from_named_key(NamedKey::Alt), Some(ModifiersState::ALT));
+        assert_eq!(
+            ModifiersState::from_named_key(NamedKey::AltGraph),
+            Some(ModifiersState::ALT_GRAPH)
+        );
+        assert_eq!(ModifiersState::from_named_key(NamedKey::Super), Some(ModifiersState::SUPER));
+        assert_eq!(ModifiersState::from_named_key(NamedKey::Meta), Some(ModifiersState::SUPER));
+        assert_eq!(ModifiersState::from_named_key(NamedKey::CapsLock), None);
+        assert_eq!(ModifiersState::from_named_key(NamedKey::Enter), None);
+    }
+
+    #[test]
+    fn native_key_code_debug_round_trip() {
+        let codes = [
+            NativeKeyCode::Unidentified,
+            NativeKeyCode::Android(0xab),
+            NativeKeyCode::Android(u32::MAX),
+            NativeKeyCode::MacOS(0x24),
+            NativeKeyCode::MacOS(u16::MAX),
+            NativeKeyCode::Windows(0xe01c),
+            NativeKeyCode::Windows(0),
+            NativeKeyCode::Xkb(0x26),
+            NativeKeyCode::Xkb(0x1_0000),
+        ];
+        for code in codes {
+            assert_eq!(format!("{code:?}").parse::<NativeKeyCode>(), Ok(code));
+        }
+
+        for malformed in [
+            "",
+            "unidentified",
+            "Unidentified()",
+            "Xkb",
+            "Xkb()",
+            "Xkb(0x)",
+            "Xkb(38)",
+            "Xkb(0x+26)",
+            "Xkb(0x0026",
+            "Xkb (0x0026)",
+            "Xkb(0x0026) ",
+            "Xkb(0xG026)",
+            "Linux(0x0026)",
+            "MacOS(0x10000)",
+            "Windows(0x10000)",
+            "Android(0x100000000)",
+        ] {
+            assert_eq!(
+                malformed.parse::<NativeKeyCode>(),
+                Err(NativeKeyCodeParseError { input: malformed.into() }),
+                "{malformed:?}"
+            );
+        }
+        assert_eq!(
+            "Xkb(38)".parse::<NativeKeyCode>().unwrap_err().to_string(),
+            "invalid native key code `Xkb(38)`, expected `Unidentified` or a variant with a hex \
+             code such as `Xkb(0x0026)`"
+        );
+    }
+
+    #[test]
+    fn modifiers_display_round_trip() {
+        let all = ModifiersState::all();
+        assert_eq!(ModifiersState::empty().to_string(), "");
+        assert_eq!((ModifiersState::SHIFT | ModifiersState::CONTROL).to_string(), "Control+Shift");
+        assert_eq!(
+            all.to_string(),
+            "Control+Shift+Alt+AltGraph+Super+Fn+CapsLock+NumLock+ScrollLock"
+        );
+        assert!(all.named_keys().eq(MODIFIER_KEYS.map(|(_, key)| key)));
+        for subset in 0..1 << MODIFIER_KEYS.len() {
+            let state = MODIFIER_KEYS
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| subset & (1 << i) != 0)
+                .fold(ModifiersState::empty(), |state, (_, (flag, _))| state | *flag);
+            assert_eq!(state.to_string().parse::<ModifiersState>(), Ok(state));
+        }
+
+        let aliases = [
+            ("Ctrl", ModifiersState::CONTROL),
+            ("ctrl + SHIFT", ModifiersState::CONTROL | ModifiersState::SHIFT),
+            ("Opt", ModifiersState::ALT),
+            ("Option+Cmd", ModifiersState::ALT | ModifiersState::SUPER),
+            ("AltGr", ModifiersState::ALT_GRAPH),
+            ("alt+altgraph", ModifiersState::ALT | ModifiersState::ALT_GRAPH),
+            ("Command", ModifiersState::SUPER),
+            ("Win", ModifiersState::SUPER),
+            ("Windows", ModifiersState::SUPER),
+            ("Meta", ModifiersState::SUPER),
+            ("Super+Shift", ModifiersState::SHIFT | ModifiersState::SUPER),
+            ("Ctrl+Control", ModifiersState::CONTROL),
+            ("  ", ModifiersState::empty()),
+        ];
+        for (text, state) in aliases {
+            assert_eq!(text.parse::<ModifiersState>(), Ok(state), "{text:?}");
+        }
+
+        let errors = [("Hyper", "Hyper"), ("Ctrl++Shift", ""), ("Shift+", ""), ("Ctrl+A", "A")];
+        for (text, name) in errors {
+            assert_eq!(
+                text.parse::<ModifiersState>(),
+                Err(ModifiersParseError { name: name.into() }),
+                "{text:?}"
+            );
+        }
+        assert_eq!(
+            "Ctrl+Hyper".parse::<ModifiersState>().unwrap_err().to_string(),
+            "unknown modifier `Hyper`"
+        );
+    }
+
+    #[test]
+    fn key_name_round_trip() {
+        let keys = [
+            Key::Named(NamedKey::Enter),
+            Key::Named(NamedKey::ArrowLeft),
+            Key::Named(NamedKey::F5),
+            Key::Named(NamedKey::Super),
+            Key::Named(NamedKey::Space),
+            Key::Character("a".into()),
+            Key::Character("é".into()),
+            Key::Character("e\u{0301}".into()),
+            Key::Unidentified(NativeKey::Unidentified),
+            Key::Dead(None),
+        ];
+        for key in keys {
+            assert_eq!(key.to_string().parse::<Key>(), Ok(key));
+        }
+
+        assert_eq!("ab".parse::<Key>(), Err(KeyParseError { name: "ab".into() }));
+        assert_eq!("".parse::<Key>(), Err(KeyParseError { name: "".into() }));
+        assert_eq!(
+            "Entr".parse::<Key>().unwrap_err().to_string(),
+            "unknown key name `Entr`, expected a `NamedKey` name or a single character"
+        );
+    }
+
+    #[test]
+    fn named_key_name_round_trip() {
+        // Every variant is listed exactly once, in declaration order.
+        assert_eq!(NamedKey::ALL.len(), NamedKey::F35 as usize + 1);
+        for (index, key) in NamedKey::ALL.into_iter().enumerate() {
+            assert_eq!(key as usize, index);
+            assert_eq!(key.to_string().parse::<NamedKey>(), Ok(key));
+            assert_eq!(NamedKey::try_from(key.name()), Ok(key));
+        }
+
+        assert_eq!(NamedKey::ArrowUp.to_string(), "ArrowUp");
+        assert_eq!(
+            "arrowup".parse::<NamedKey>(),
+            Err(NamedKeyParseError { name: "arrowup".into() })
+        );
+        assert_eq!(NamedKey::try_from("a").unwrap_err().to_string(), "unknown named key `a`");
+    }
+
+    #[test]
+    fn key_code_all() {
+        let all: Vec<_> = KeyCode::all().collect();
+        // Every variant is listed exactly once, in declaration order.
+        assert_eq!(all.len(), KeyCode::F35 as usize + 1);
+        for (index, code) in all.iter().enumerate() {
+            assert_eq!(*code as usize, index);
+        }
+
+        assert_eq!(KeyCode::KeyA.name(), "KeyA");
+        assert_eq!(KeyCode::SuperLeft.name(), "MetaLeft");
+
+        for code in KeyCode::all() {
+            assert_eq!(KeyCode::from_name(code.name()), Some(code));
+        }
+        assert_eq!(KeyCode::from_name("SuperLeft"), None);
+        assert_eq!(KeyCode::from_name(""), None);
+    }
+
+    #[test]
+    fn key_code_groups() {
+        let count = |group: fn(&KeyCode) -> bool| KeyCode::all().filter(group).count();
+        assert_eq!(count(KeyCode::is_alphabetic), 26);
+        assert_eq!(count(KeyCode::is_digit), 20);
+        assert_eq!(count(KeyCode::is_function_key), 35);
+        assert_eq!(count(KeyCode::is_navigation), 8);
+
+        for code in [KeyCode::KeyA, KeyCode::KeyQ, KeyCode::KeyZ] {
+            assert!(code.is_alphabetic(), "{code:?}");
+        }
+        for code in [KeyCode::Digit0, KeyCode::Digit9, KeyCode::Numpad0, KeyCode::Numpad9] {
+            assert!(code.is_digit(), "{code:?}");
+        }
+        for code in [KeyCode::F1, KeyCode::F12, KeyCode::F24, KeyCode::F35] {
+            assert!(code.is_function_key(), "{code:?}");
+        }
+        for code in [KeyCode::Numpad0, KeyCode::NumpadEnter, KeyCode::NumpadSubtract] {
+            assert!(code.is_numpad(), "{code:?}");
+        }
+        for code in [KeyCode::ArrowUp, KeyCode::Home, KeyCode::PageDown] {
+            assert!(code.is_navigation(), "{code:?}");
+        }
+
+        // Every numpad key is named after it and no other key is.
+        for code in KeyCode::all() {
+            assert_eq!(code.is_numpad(), code.name().starts_with("Numpad"), "{code:?}");
+        }
+
+        for code in [KeyCode::IntlRo, KeyCode::NumLock, KeyCode::Space, KeyCode::Insert] {
+            assert!(!code.is_alphabetic(), "{code:?}");
+            assert!(!code.is_digit(), "{code:?}");
+            assert!(!code.is_function_key(), "{code:?}");
+            assert!(!code.is_numpad(), "{code:?}");
+            assert!(!code.is_navigation(), "{code:?}");
+        }
+    }
+
+    #[test]
+    fn us_layout_characters() {
+        let characters: Vec<_> =
+            KeyCode::all().filter_map(|code| code.us_layout_character()).collect();
+        assert_eq!(characters.len(), 47);
+        // Every character is typed by a single key.
+        assert_eq!(
+            characters.iter().collect::<std::collections::HashSet<_>>().len(),
+            characters.len()
+        );
+
+        assert_eq!(KeyCode::KeyA.us_layout_character(), Some("a"));
+        assert_eq!(KeyCode::Digit0.us_layout_character(), Some("0"));
+        assert_eq!(KeyCode::Backslash.us_layout_character(), Some("\\"));
+        for code in [KeyCode::IntlBackslash, KeyCode::Numpad1, KeyCode::Space, KeyCode::Enter] {
+            assert_eq!(code.us_layout_character(), None, "{code:?}");
+        }
+    }
+
+    #[test]
+    fn key_code_label() {
+        let character = |ch: &str| Key::Character(ch.into());
+
+        // The layout's key is preferred.
+        assert_eq!(KeyCode::KeyW.label(Some(&character("z"))), "Z");
+        assert_eq!(KeyCode::Digit2.label(Some(&character("é"))), "É");
+        assert_eq!(KeyCode::Minus.label(Some(&character("ß"))), "ß");
+        assert_eq!(KeyCode::Space.label(Some(&character(" "))), "Space");
+        assert_eq!(KeyCode::BracketLeft.label(Some(&Key::Dead(Some('^')))), "^");
+        assert_eq!(KeyCode::ArrowUp.label(Some(&Key::Named(NamedKey::ArrowUp))), "↑");
+        assert_eq!(KeyCode::Escape.label(Some(&Key::Named(NamedKey::Escape))), "Esc");
+        assert_eq!(KeyCode::F5.label(Some(&Key::Named(NamedKey::F5))), "F5");
+
+        // Numpad keys are told apart from the main block, regardless of Num Lock.
+        assert_eq!(KeyCode::Numpad1.label(Some(&character("1"))), "Num 1");
+        assert_eq!(KeyCode::Numpad1.label(Some(&Key::Named(NamedKey::End))), "Num 1");
+        assert_eq!(KeyCode::NumpadAdd.label(None), "Num +");
+        assert_eq!(KeyCode::NumpadEnter.label(None), "Num Enter");
+
+        // Without a layout key, the US layout is used.
+        assert_eq!(KeyCode::KeyQ.label(None), "Q");
+        assert_eq!(KeyCode::KeyQ.label(Some(&Key::Dead(None))), "Q");
+        assert_eq!(KeyCode::ArrowLeft.label(None), "←");
+        assert_eq!(KeyCode::ControlRight.label(None), "Ctrl");
+        assert_eq!(KeyCode::PageDown.label(None), "PgDn");
+        assert_eq!(KeyCode::IntlBackslash.label(None), "IntlBackslash");
+
+        for code in KeyCode::all() {
+            assert!(!code.label(None).is_empty(), "{code:?}");
+        }
+    }
+
+    #[test]
+    fn named_key_default_code() {
+        assert_eq!(NamedKey::Enter.default_code(), Some(KeyCode::Enter));
+        assert_eq!(NamedKey::F12.default_code(), Some(KeyCode::F12));
+        assert_eq!(NamedKey::Meta.default_code(), Some(KeyCode::SuperLeft));
+        assert_eq!(NamedKey::AltGraph.default_code(), Some(KeyCode::AltRight));
+        assert_eq!(NamedKey::AudioVolumeUp.default_code(), Some(KeyCode::AudioVolumeUp));
+        for key in [NamedKey::Compose, NamedKey::Dimmer, NamedKey::ZoomIn] {
+            assert_eq!(key.default_code(), None, "{key:?}");
+        }
+
+        // The code produces the key on a US layout, when it isn't a modifier.
+        for code in KeyCode::all() {
+            let key = NamedKey::from_name(code.name());
+            if let Some(key) = key.filter(|&key| ModifiersState::from_named_key(key).is_none()) {
+                assert_eq!(key.default_code(), Some(code), "{code:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn hid_usage_round_trip() {
+        assert!(HID_USAGES.windows(2).all(|pair| pair[0].1 < pair[1].1));
+
+        let mut mapped = 0;
+        for code in KeyCode::all() {
+            let key = PhysicalKey::Code(code);
+            if let Some(usage) = key.to_hid_usage() {
+                assert_eq!(PhysicalKey::from_hid_usage(usage), key, "{code:?}");
+                mapped += 1;
+            }
+        }
+        assert_eq!(mapped, HID_USAGES.len());
+
+        // The full alphanumeric, function and modifier ranges are covered.
+        for code in KeyCode::all() {
+            let covered = code.is_alphabetic()
+                || code.is_digit()
+                || code.is_navigation()
+                || code.is_between(KeyCode::F1, KeyCode::F24)
+                || code.is_modifier()
+                    && !matches!(
+                        code,
+                        KeyCode::Fn | KeyCode::FnLock | KeyCode::Meta | KeyCode::Hyper
+                    );
+            if covered {
+                assert!(PhysicalKey::Code(code).to_hid_usage().is_some(), "{code:?}");
+            }
+        }
+
+        assert_eq!(PhysicalKey::Code(KeyCode::Digit0).to_hid_usage(), Some(0x27));
+        assert_eq!(PhysicalKey::Code(KeyCode::F13).to_hid_usage(), Some(0x68));
+        assert_eq!(PhysicalKey::Code(KeyCode::SuperRight).to_hid_usage(), Some(0xe7));
+        assert_eq!(PhysicalKey::Code(KeyCode::F25).to_hid_usage(), None);
+        assert_eq!(PhysicalKey::Unidentified(NativeKeyCode::Xkb(0x26)).to_hid_usage(), None);
+        assert_eq!(PhysicalKey::from_hid_usage(0x32), KeyCode::Backslash);
+        for usage in [0x00, 0x03, 0xe8, 0xffff] {
+            assert_eq!(
+                PhysicalKey::from_hid_usage(usage),
+                PhysicalKey::Unidentified(NativeKeyCode::Unidentified)
+            );
+        }
+    }
+
+    #[test]
+    fn native_key_code_current_platform() {
+        let codes = [
+            (NativeKeyCode::Android(0x1d), cfg!(android_platform)),
+            (NativeKeyCode::MacOS(0x00), cfg!(macos_platform)),
+            (NativeKeyCode::Windows(0x1e), cfg!(windows_platform)),
+            (NativeKeyCode::Xkb(0x26), cfg!(any(x11_platform, wayland_platform))),
+        ];
+
+        assert_eq!(NativeKeyCode::Unidentified.current_platform_code(), None);
+        for (code, native) in codes {
+            let raw = code.current_platform_code();
+            assert_eq!(raw.is_some(), native, "{code:?}");
+            if let Some(raw) = raw {
+                assert_eq!(NativeKeyCode::from_current_platform(raw), code);
+            }
+        }
+
+        let truncated = NativeKeyCode::from_current_platform(0x1_0026);
+        if cfg!(any(macos_platform, windows_platform)) {
+            assert_eq!(truncated.current_platform_code(), Some(0x26));
+        } else if truncated != NativeKeyCode::Unidentified {
+            assert_eq!(truncated.current_platform_code(), Some(0x1_0026));
+        }
+    }
+
+    #[test]
+    fn native_key_normalize() {
+        let keys = [
+            (NativeKey::Android(0x1d), cfg!(android_platform)),
+            (NativeKey::MacOS(0x00), cfg!(macos_platform)),
+            (NativeKey::Windows(0x41), cfg!(windows_platform)),
+            (NativeKey::Xkb(0x61), cfg!(any(x11_platform, wayland_platform))),
+            (NativeKey::Web("KeyA".into()), cfg!(web_platform)),
+        ];
+
+        assert_eq!(NativeKey::Unidentified.normalize(), NativeKey::Unidentified);
+        for (key, native) in &keys {
+            let normalized = key.normalize();
+            if *native {
+                assert_eq!(&normalized, key);
+            } else {
+                assert_eq!(normalized, NativeKey::Unidentified);
+            }
+            assert_eq!(normalized.normalize(), normalized);
+
+            // Normalized foreign keys only ever match each other, never a native key.
+            for (other, other_native) in &keys {
+                let equal = normalized == other.normalize();
+                assert_eq!(equal, key == other || (!native && !other_native), "{key:?} {other:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn key_logical_digit() {
+        for digit in 0..=9u8 {
+            let key = Key::Character(digit.to_string().into());
+            assert_eq!(key.logical_digit(KeyLocation::Standard), Some(digit));
+            assert_eq!(key.logical_digit(KeyLocation::Numpad), Some(digit));
+        }
+
+        let navigation = [
+            NamedKey::Insert,
+            NamedKey::End,
+            NamedKey::ArrowDown,
+            NamedKey::PageDown,
+            NamedKey::ArrowLeft,
+            NamedKey::Clear,
+            NamedKey::ArrowRight,
+            NamedKey::Home,
+            NamedKey::ArrowUp,
+            NamedKey::PageUp,
+        ];
+        for (digit, named) in navigation.into_iter().enumerate() {
+            let key = Key::Named(named);
+            assert_eq!(key.logical_digit(KeyLocation::Numpad), Some(digit as u8));
+            assert_eq!(key.logical_digit(KeyLocation::Standard), None);
+        }
+
+        for key in
+            [Key::Character("a".into()), Key::Character("12".into()), Key::Character("+".into())]
+        {
+            assert_eq!(key.logical_digit(KeyLocation::Numpad), None);
+        }
+        assert_eq!(Key::Named(NamedKey::Enter).logical_digit(KeyLocation::Numpad), None);
+    }
+
+    #[test]
+    fn modifiers_new() {
+        const NONE: ModifiersState = ModifiersState::new(false, false, false, false);
+        const ALL: ModifiersState = ModifiersState::new(true, true, true, true);
+        assert_eq!(NONE, ModifiersState::empty());
+        assert_eq!(
+            ALL,
+            ModifiersState::SHIFT
+                | ModifiersState::CONTROL
+                | ModifiersState::ALT
+                | ModifiersState::SUPER
+        );
+
+        assert_eq!(ModifiersState::new(true, false, false, false), ModifiersState::SHIFT);
+        assert_eq!(ModifiersState::new(false, true, false, false), ModifiersState::CONTROL);
+        assert_eq!(ModifiersState::new(false, false, true, false), ModifiersState::ALT);
+        assert_eq!(ModifiersState::new(false, false, false, true), ModifiersState::SUPER);
+        assert!(ALL.matches_exactly(ALL | ModifiersState::CAPS_LOCK));
+    }
+
+    #[test]
+    fn physical_key_matches() {
+        let fallback = NativeKeyCode::Windows(0x1e);
+
+        // The key code wins when the key is identified.
+        assert!(PhysicalKey::Code(KeyCode::KeyA).matches(KeyCode::KeyA, fallback));
+        assert!(!PhysicalKey::Code(KeyCode::KeyB).matches(KeyCode::KeyA, fallback));
+        assert!(
+            PhysicalKey::Code(KeyCode::KeyA).matches(KeyCode::KeyA, NativeKeyCode::Unidentified)
+        );
+
+        // Saved as a key code, but now reported as unidentified.
+        assert!(PhysicalKey::Unidentified(fallback).matches(KeyCode::KeyA, fallback));
+        assert!(!PhysicalKey::Unidentified(NativeKeyCode::Windows(0x1f))
+            .matches(KeyCode::KeyA, fallback));
+        assert!(
+            !PhysicalKey::Unidentified(NativeKeyCode::Xkb(0x1e)).matches(KeyCode::KeyA, fallback)
+        );
+
+        // An unidentified native code carries no information.
+        let unidentified = PhysicalKey::Unidentified(NativeKeyCode::Unidentified);
+        assert!(!unidentified.matches(KeyCode::KeyA, NativeKeyCode::Unidentified));
+    }
+
+    #[test]
+    fn shortcut_physical() {
+        let jump = Shortcut::physical(ModifiersState::empty(), KeyCode::Space);
+        let space = Key::Named(NamedKey::Space);
+        let none = ModifiersState::empty();
+
+        assert!(jump.matches_key(PhysicalKey::Code(KeyCode::Space), &space, Some(" "), none));
+        assert!(jump.matches_key(
+            PhysicalKey::Code(KeyCode::Space),
+            &space,
+            Some(" "),
+            ModifiersState::CAPS_LOCK
+        ));
+        assert!(!jump.matches_key(
+            PhysicalKey::Code(KeyCode::Space),
+            &space,
+            Some(" "),
+            ModifiersState::SHIFT
+        ));
+        assert!(!jump.matches_key(PhysicalKey::Code(KeyCode::Enter), &space, None, none));
+
+        // The logical key doesn't matter, e.g. `KeyW` on AZERTY.
+        let forward = Shortcut::physical(ModifiersState::empty(), KeyCode::KeyW);
+        let z = Key::Character("z".into());
+        assert!(forward.matches_key(PhysicalKey::Code(KeyCode::KeyW), &z, Some("z"), none));
+    }
+
+    #[test]
+    fn key_eq_ignore_case() {
+        let character = |ch: &str| Key::Character(ch.into());
+        let equal = [("a", "A"), ("Straße", "STRASSE"), ("ς", "Σ"), ("σ", "ς"), ("1", "1")];
+        for (a, b) in equal {
+            assert!(character(a).eq_ignore_case(&character(b)), "{a} {b}");
+            assert!(character(b).eq_ignore_case(&character(a)), "{a} {b}");
+        }
+        for (a, b) in [("a", "b"), ("a", "aa"), ("1", "!"), ("", "a")] {
+            assert!(!character(a).eq_ignore_case(&character(b)), "{a} {b}");
+        }
+
+        // Only characters are folded.
+        assert!(!character("a").eq_ignore_case(&Key::Dead(Some('a'))));
+        assert!(!Key::Dead(Some('a')).eq_ignore_case(&Key::Dead(Some('A'))));
+        let enter = Key::Named(NamedKey::Enter);
+        assert!(enter.eq_ignore_case(&enter));
+        assert!(!enter.eq_ignore_case(&character("\r")));
+    }
+
+    #[test]
+    fn shortcut_logical() {
+        let code = PhysicalKey::Code(KeyCode::KeyZ);
+        let lower = Key::Character("z".into());
+        let upper = Key::Character("Z".into());
+        let ctrl = ModifiersState::CONTROL;
+        let ctrl_shift = ModifiersState::CONTROL | ModifiersState::SHIFT;
+
+        // Letters are case-insensitive, but Shift is still exact.
+        let undo = Shortcut::logical_char(ctrl, "z");
+        let redo = Shortcut::logical_char(ctrl_shift, "z");
+        assert!(undo.matches_key(code, &lower, None, ctrl));
+        assert!(undo.matches_key(code, &lower, None, ctrl | ModifiersState::CAPS_LOCK));
+        assert!(!undo.matches_key(code, &upper, None, ctrl_shift));
+        assert!(redo.matches_key(code, &upper, None, ctrl_shift));
+        assert!(!redo.matches_key(code, &lower, None, ctrl));
+        assert!(!undo.matches_key(code, &lower, None, ctrl | ModifiersState::ALT));
+
+        // Shift is ignored for characters without case.
+        let code = PhysicalKey::Code(KeyCode::Equal);
+        let plus = Key::Character("+".into());
+        let zoom_in = Shortcut::logical_char(ctrl, "+");
+        assert!(zoom_in.matches_key(code, &plus, None, ctrl_shift));
+        assert!(zoom_in.matches_key(code, &plus, None, ctrl));
+        assert!(!zoom_in.matches_key(code, &plus, None, ModifiersState::SHIFT));
+
+        // AltGr, reported as Ctrl+Alt, producing the character.
+        let code = PhysicalKey::Code(KeyCode::KeyQ);
+        let at = Key::Character("@".into());
+        let mention = Shortcut::logical_char(ModifiersState::empty(), "@");
+        let altgr = ModifiersState::CONTROL | ModifiersState::ALT;
+        assert!(mention.matches_key(code, &at, Some("@"), altgr));
+        assert!(!mention.matches_key(code, &at, None, altgr));
+
+        // AltGr reported as such only matters for shortcuts that include it.
+        let altgr = ModifiersState::ALT_GRAPH;
+        assert!(mention.matches_key(code, &at, Some("@"), altgr));
+        assert!(!mention.matches_key(code, &at, Some("@"), altgr | ModifiersState::ALT));
+        assert!(Shortcut::logical_char(altgr, "@").matches_key(code, &at, None, altgr));
+        assert!(!Shortcut::logical_char(altgr, "@").matches_key(code, &at, None, ctrl));
+        let undo_physical = Shortcut::physical(ctrl, KeyCode::KeyZ);
+        let code = PhysicalKey::Code(KeyCode::KeyZ);
+        assert!(!undo_physical.matches_key(code, &lower, None, ctrl | altgr));
+
+        let named = Shortcut::logical(ctrl, Key::Named(NamedKey::Enter));
+        let enter = Key::Named(NamedKey::Enter);
+        let code = PhysicalKey::Code(KeyCode::Enter);
+        assert!(named.matches_key(code, &enter, None, ctrl));
+        assert!(!named.matches_key(code, &enter, None, ctrl_shift));
+        assert!(!named.matches_key(code, &Key::Named(NamedKey::Tab), None, ctrl));
+    }
+
+    #[test]
+    #[cfg(feature = "keyboard-types")]
+    fn from_keyboard_types_key() {
+        use keyboard_types::NamedKey as Source;
+
+        let convert = |key: keyboard_types::Key| Key::from(key);
+        assert_eq!(convert(keyboard_types::Key::Character("a".into())), Key::Character("a".into()));
+        assert_eq!(
+            convert(keyboard_types::Key::Character(" ".into())),
+            Key::Named(NamedKey::Space)
+        );
+        assert_eq!(convert(keyboard_types::Key::Named(Source::Enter)), Key::Named(NamedKey::Enter));
+        assert_eq!(convert(keyboard_types::Key::Named(Source::F35)), Key::Named(NamedKey::F35));
+        assert_eq!(convert(keyboard_types::Key::Named(Source::Dead)), Key::Dead(None));
+        assert_eq!(convert(keyboard_types::Key::Named(Source::Meta)), Key::Named(NamedKey::Super));
+        #[allow(deprecated)]
+        {
+            assert_eq!(
+                convert(keyboard_types::Key::Named(Source::Super)),
+                Key::Named(NamedKey::Meta)
+            );
+            assert_eq!(
+                convert(keyboard_types::Key::Named(Source::Hyper)),
+                Key::Named(NamedKey::Hyper)
+            );
+        }
+        assert_eq!(
+            convert(keyboard_types::Key::Named(Source::Unidentified)),
+            Key::Unidentified(NativeKey::Unidentified)
+        );
+    }
+}
@@ -190,6 +190,15 @@
 //! * `rwh_06`: Implement `raw-window-handle v0.6` traits.
 //! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde).
 //! * `mint`: Enables mint (math interoperability standard types) conversions.
+//! * `keyboard-types`: Enables conversions from the key types of the [keyboard-types](https://crates.io/crates/keyboard-types)
+//!   crate.
+//! * `test-util`: Enables `EventLoop::create_event_injector` to inject synthetic events for
+//!   testing.
+//! * `headless`: Adds an in-memory backend that never shows anything, next to the platform's, to
+//!   run application logic in CI without a display. See `platform::headless` for details. Intended
+//!   for testing only, implies `test-util`.
+//! * `unicode-normalization`: Enables `Key::normalized` to compare character keys in Unicode
+//!   Normalization Form C.
 //!
 //! See the [`platform`] module for documentation on platform-specific cargo
 //! features.
@@ -161,9 +161,201 @@ impl MonitorHandle {
         self.inner.current_video_mode()
     }
 
+    /// Returns the number of bits per color channel the monitor is currently driven with, e.g. 8
+    /// for a regular display and 10 or more for a wide gamut or HDR one.
+    ///
+    /// Unlike [`VideoMode::bit_depth`], this counts the bits of a single channel rather than of a
+    /// whole pixel. Returns `None` when the depth is unknown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The depth reported by the display configuration, which matches DXGI's
+    ///   `BitsPerColor`, falling back to the bit depth of the current video mode.
+    /// - **macOS:** The bits per sample of the screen's window depth.
+    /// - **X11:** The bits per channel of the root visual, which RandR scans every output out of.
+    /// - **Web:** Derived from the bit depth of the [current video mode][Self::current_video_mode].
+    /// - **iOS / Android / Wayland / Orbital:** Always returns `None`.
+    #[inline]
+    pub fn bit_depth(&self) -> Option<u32> {
+        self.inner.bit_depth().or_else(|| bits_per_channel(self.current_video_mode()?.bit_depth?))
+    }
+
+    /// Returns the luminance in nits that SDR content's reference white is displayed at, when
+    /// the monitor is in HDR mode.
+    ///
+    /// Use this to map SDR content into an HDR surface so that it matches the brightness of the
+    /// rest of the desktop. Returns `None` when unknown or when the monitor is not in HDR mode.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The SDR white level set in the display settings, while HDR is enabled.
+    /// - **macOS:** Always returns `None`, as EDR is described by a headroom relative to SDR white
+    ///   instead of a luminance.
+    /// - **X11:** Always returns `None`, as RandR doesn't expose a white level.
+    /// - **iOS / Android / Wayland / Web / Orbital:** Always returns `None`.
+    #[inline]
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        self.inner.sdr_white_level_nits()
+    }
+
     /// Returns all fullscreen video modes supported by this monitor.
     #[inline]
     pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
         self.inner.video_modes()
     }
+
+    /// Returns the native resolution of this monitor.
+    ///
+    /// The native resolution is determined as the largest resolution among the
+    /// [`video_modes`][Self::video_modes], which is the panel's physical resolution for virtually
+    /// all displays. If the monitor doesn't report any video mode, the resolution of the
+    /// [`current_video_mode`][Self::current_video_mode] is used instead.
+    pub fn native_resolution(&self) -> Option<PhysicalSize<u32>> {
+        native_resolution(self.video_modes())
+            .or_else(|| self.current_video_mode().map(|mode| mode.size()))
+    }
+
+    /// Returns the video modes at the [native resolution][Self::native_resolution], sorted by
+    /// refresh rate from highest to lowest.
+    ///
+    /// This is useful to offer a refresh rate picker for fullscreen applications.
+    pub fn video_modes_at_native_resolution(&self) -> impl Iterator<Item = VideoMode> {
+        modes_at_native_resolution(self.video_modes()).into_iter()
+    }
+}
+
+/// The bits per color channel of a pixel of `bits_per_pixel` bits, which may include an alpha
+/// channel.
+fn bits_per_channel(bits_per_pixel: NonZeroU16) -> Option<u32> {
+    match bits_per_pixel.get() {
+        32 => Some(8),
+        64 => Some(16),
+        bits if bits % 3 == 0 => Some(u32::from(bits / 3)),
+        _ => None,
+    }
+}
+
+fn native_resolution(modes: impl Iterator<Item = VideoMode>) -> Option<PhysicalSize<u32>> {
+    modes
+        .map(|mode| mode.size)
+        .max_by_key(|size| (size.width as u64 * size.height as u64, size.width))
+}
+
+fn modes_at_native_resolution(modes: impl Iterator<Item = VideoMode>) -> Vec<VideoMode> {
+    let modes: Vec<_> = modes.collect();
+    let Some(native) = native_resolution(modes.iter().copied()) else {
+        return Vec::new();
+    };
+
+    let mut modes: Vec<_> = modes.into_iter().filter(|mode| mode.size == native).collect();
+    // Modes without a known refresh rate go last.
+    modes.sort_by_key(|mode| std::cmp::Reverse(mode.refresh_rate_millihertz));
+    modes
+}
+
+/// Finds the mode to switch to for [`Fullscreen::Exclusive`].
+///
+/// Only a mode equal to `requested` in size, refresh rate *and* bit depth is selected. When none
+/// exists, a warning is logged, noting any modes that only differ in bit depth, and `None` is
+/// returned so that the backend leaves the video mode unchanged.
+///
+/// [`Fullscreen::Exclusive`]: crate::window::Fullscreen::Exclusive
+#[allow(dead_code)]
+pub(crate) fn find_exclusive_mode<T>(
+    candidates: impl IntoIterator<Item = T>,
+    requested: &VideoMode,
+    mode_of: impl Fn(&T) -> &VideoMode,
+) -> Option<T> {
+    let mut other_depths = Vec::new();
+    for candidate in candidates {
+        let mode = mode_of(&candidate);
+        if mode == requested {
+            return Some(candidate);
+        }
+        if mode.size == requested.size
+            && mode.refresh_rate_millihertz == requested.refresh_rate_millihertz
+        {
+            other_depths.push(mode.bit_depth);
+        }
+    }
+
+    if other_depths.is_empty() {
+        tracing::warn!("video mode {requested} is not supported by the monitor");
+    } else {
+        tracing::warn!(
+            "video mode {requested} is not supported by the monitor, it is only available with \
+             bit depths {other_depths:?}"
+        );
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(width: u32, height: u32, refresh_rate: u32) -> VideoMode {
+        VideoMode {
+            size: PhysicalSize::new(width, height),
+            bit_depth: NonZeroU16::new(32),
+            refresh_rate_millihertz: NonZeroU32::new(refresh_rate),
+        }
+    }
+
+    #[test]
+    fn native_resolution_modes() {
+        let modes = [
+            mode(1920, 1080, 60_000),
+            mode(2560, 1440, 60_000),
+            mode(1280, 720, 144_000),
+            mode(2560, 1440, 0),
+            mode(2560, 1440, 144_000),
+            mode(2560, 1440, 120_000),
+        ];
+
+        assert_eq!(native_resolution(modes.into_iter()), Some(PhysicalSize::new(2560, 1440)));
+        assert_eq!(modes_at_native_resolution(modes.into_iter()), [
+            mode(2560, 1440, 144_000),
+            mode(2560, 1440, 120_000),
+            mode(2560, 1440, 60_000),
+            mode(2560, 1440, 0),
+        ]);
+
+        assert_eq!(native_resolution(std::iter::empty()), None);
+        assert!(modes_at_native_resolution(std::iter::empty()).is_empty());
+    }
+
+    #[test]
+    fn bits_per_channel_from_pixel_depth() {
+        let bits = |bits_per_pixel| bits_per_channel(NonZeroU16::new(bits_per_pixel).unwrap());
+        assert_eq!(bits(24), Some(8));
+        assert_eq!(bits(32), Some(8));
+        assert_eq!(bits(30), Some(10));
+        assert_eq!(bits(36), Some(12));
+        assert_eq!(bits(48), Some(16));
+        assert_eq!(bits(64), Some(16));
+        assert_eq!(bits(16), None);
+    }
+
+    #[test]
+    fn exclusive_mode_honors_bit_depth() {
+        let with_depth = |bit_depth| VideoMode {
+            bit_depth: NonZeroU16::new(bit_depth),
+            ..mode(1920, 1080, 60_000)
+        };
+        let modes = [with_depth(16), with_depth(24), mode(1280, 720, 60_000), with_depth(32)];
+
+        for depth in [16, 24, 32] {
+            let requested = with_depth(depth);
+            let found = find_exclusive_mode(modes.iter(), &requested, |mode| mode);
+            assert_eq!(found.and_then(|mode| mode.bit_depth), NonZeroU16::new(depth));
+        }
+
+        assert_eq!(find_exclusive_mode(modes.iter(), &with_depth(30), |mode| mode), None);
+        assert_eq!(find_exclusive_mode(modes.iter(), &with_depth(0), |mode| mode), None);
+        assert_eq!(
+            find_exclusive_mode(modes.iter(), &mode(1920, 1080, 144_000), |mode| mode),
+            None
+        );
+    }
 }
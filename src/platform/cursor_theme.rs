@@ -0,0 +1,46 @@
+//! Per-window cursor themes on X11 and Wayland.
+//!
+//! By default, named cursors are taken from the system's XCursor theme, as configured with the
+//! `XCURSOR_THEME` and `XCURSOR_SIZE` environment variables or the desktop's settings.
+//! [`WindowExtCursorTheme::set_cursor_theme`] lets a window use another theme instead, for
+//! applications with a distinct visual identity.
+
+use crate::window::Window;
+
+pub trait WindowExtCursorTheme {
+    /// Use the XCursor theme `name` at `size` for the named cursors of this window.
+    ///
+    /// When only one of them is given, the other falls back to the system setting, i.e. the
+    /// `XCURSOR_THEME` or `XCURSOR_SIZE` environment variable, then `default` and `24`. Passing
+    /// `None` for both goes back to the system theme.
+    ///
+    /// The theme is searched in the usual XCursor locations, and inherited themes are followed.
+    /// Cursors the theme doesn't have are taken from the system theme. Custom cursors are not
+    /// affected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** `size` is in physical pixels, like `Xcursor.size`.
+    /// - **Wayland:** `size` is in logical pixels, the cursor is loaded at the scale of the output
+    ///   the pointer is on.
+    fn set_cursor_theme(&self, name: Option<String>, size: Option<u32>);
+}
+
+impl WindowExtCursorTheme for dyn Window + '_ {
+    fn set_cursor_theme(&self, name: Option<String>, size: Option<u32>) {
+        #[cfg(wayland_platform)]
+        if let Some(window) = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()
+        {
+            return window.set_cursor_theme(name, size);
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(window) =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::window::Window>()
+        {
+            return window.set_cursor_theme(name, size);
+        }
+
+        let _ = (name, size);
+    }
+}
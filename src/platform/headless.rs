@@ -0,0 +1,120 @@
+//! # Headless
+//!
+//! An in-memory backend to test applications without a display, enabled by the `headless` cargo
+//! feature.
+//!
+//! A [`HeadlessEventLoop`] runs an [`ApplicationHandler`] like [`EventLoop`] does, but its windows
+//! only exist as state: they never show anything, and the only input comes from the
+//! [`EventInjector`]. The backend is available next to the one of the platform, which
+//! [`EventLoop`] keeps using, so enabling the feature doesn't change the behavior of the
+//! application. It is meant for testing only.
+//!
+//! ```no_run
+//! use winit::application::ApplicationHandler;
+//! use winit::event::WindowEvent;
+//! use winit::event_loop::{ActiveEventLoop, EventLoop};
+//! use winit::platform::headless::EventLoopBuilderExtHeadless;
+//! use winit::window::{Window, WindowAttributes, WindowId};
+//!
+//! #[derive(Default)]
+//! struct App {
+//!     window: Option<Box<dyn Window>>,
+//! }
+//!
+//! impl ApplicationHandler for App {
+//!     fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+//!         self.window = Some(event_loop.create_window(WindowAttributes::default()).unwrap());
+//!     }
+//!
+//!     fn window_event(
+//!         &mut self,
+//!         event_loop: &dyn ActiveEventLoop,
+//!         _: WindowId,
+//!         event: WindowEvent,
+//!     ) {
+//!         if event == WindowEvent::CloseRequested {
+//!             event_loop.exit();
+//!         }
+//!     }
+//! }
+//!
+//! let event_loop = EventLoop::builder().build_headless();
+//! let _injector = event_loop.create_event_injector();
+//! // Hand the injector to a thread that drives the application...
+//! event_loop.run_app(App::default()).unwrap();
+//! ```
+//!
+//! [`ApplicationHandler`]: crate::application::ApplicationHandler
+//! [`EventLoop`]: crate::event_loop::EventLoop
+//! [`EventInjector`]: crate::event_loop::EventInjector
+use std::fmt;
+
+use crate::application::ApplicationHandler;
+use crate::error::EventLoopError;
+use crate::event_loop::{
+    ControlFlow, EventInjector, EventLoopBuilder, EventLoopProxy, HandlerOptions,
+};
+use crate::platform_impl::headless;
+
+/// Additional methods on [`EventLoopBuilder`] to build a [`HeadlessEventLoop`].
+pub trait EventLoopBuilderExtHeadless {
+    /// Builds a [`HeadlessEventLoop`] instead of an event loop connected to the windowing system.
+    ///
+    /// The options of the builder apply to it as well, except for the platform-specific ones.
+    /// Unlike [`build`][EventLoopBuilder::build], this can be called any number of times, from
+    /// any thread, and doesn't prevent building the [`EventLoop`][crate::event_loop::EventLoop]
+    /// later; tests commonly run one headless event loop after the other.
+    fn build_headless(&mut self) -> HeadlessEventLoop;
+}
+
+impl EventLoopBuilderExtHeadless for EventLoopBuilder {
+    fn build_headless(&mut self) -> HeadlessEventLoop {
+        HeadlessEventLoop {
+            event_loop: headless::EventLoop::new(),
+            handler_options: self.handler_options(),
+        }
+    }
+}
+
+/// An event loop whose windows only exist in memory, see the [module documentation](self).
+///
+/// The [`ActiveEventLoop`][crate::event_loop::ActiveEventLoop] it passes to the application
+/// reports [`WindowingBackend::Headless`][crate::event_loop::WindowingBackend::Headless]. It has
+/// no monitors, and its windows have a scale factor of 1.
+pub struct HeadlessEventLoop {
+    event_loop: headless::EventLoop,
+    handler_options: HandlerOptions,
+}
+
+impl HeadlessEventLoop {
+    /// Run the application with the event loop on the calling thread, until it
+    /// [exits][crate::event_loop::ActiveEventLoop::exit].
+    ///
+    /// See [`EventLoop::run_app`][crate::event_loop::EventLoop::run_app].
+    pub fn run_app<A: ApplicationHandler>(self, app: A) -> Result<(), EventLoopError> {
+        self.event_loop.run_app(self.handler_options.wrap(app))
+    }
+
+    /// Creates an [`EventLoopProxy`] that can be used to wake up the event loop, possibly from
+    /// another thread.
+    pub fn create_proxy(&self) -> EventLoopProxy {
+        self.event_loop.window_target().create_proxy()
+    }
+
+    /// Creates an [`EventInjector`] that can be used to send synthetic window events to the
+    /// application, possibly from another thread.
+    pub fn create_event_injector(&self) -> EventInjector {
+        self.handler_options.create_event_injector(self.create_proxy())
+    }
+
+    /// Sets the [`ControlFlow`].
+    pub fn set_control_flow(&self, control_flow: ControlFlow) {
+        self.event_loop.window_target().set_control_flow(control_flow);
+    }
+}
+
+impl fmt::Debug for HeadlessEventLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeadlessEventLoop").finish_non_exhaustive()
+    }
+}
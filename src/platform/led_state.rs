@@ -0,0 +1,127 @@
+//! The state of the keyboard's lock LEDs.
+//!
+//! [`ModifiersState`] tells whether <kbd>Caps Lock</kbd>, <kbd>Num Lock</kbd> and
+//! <kbd>Scroll Lock</kbd> are logically active. Some applications, like accessibility tools, are
+//! interested in what the keyboard actually shows instead, which [`WindowExtLedState::led_state`]
+//! reports where the platform allows it.
+//!
+//! There is no dedicated event for LED changes: the LEDs follow the lock modifiers, so query them
+//! again on [`WindowEvent::ModifiersChanged`].
+//!
+//! [`ModifiersState`]: crate::keyboard::ModifiersState
+//! [`WindowEvent::ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+
+use bitflags::bitflags;
+
+use crate::keyboard::ModifiersState;
+use crate::window::Window;
+
+bitflags! {
+    /// The lock LEDs of a keyboard.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub struct Leds: u8 {
+        /// The <kbd>Caps Lock</kbd> LED.
+        const CAPS_LOCK = 1 << 0;
+        /// The <kbd>Num Lock</kbd> LED.
+        const NUM_LOCK = 1 << 1;
+        /// The <kbd>Scroll Lock</kbd> LED.
+        const SCROLL_LOCK = 1 << 2;
+    }
+}
+
+impl Leds {
+    /// The LEDs bound to the lock modifiers in the usual keymaps.
+    #[cfg_attr(not(wayland_platform), allow(dead_code))]
+    pub(crate) fn from_lock_modifiers(modifiers: ModifiersState) -> Self {
+        let mut leds = Leds::empty();
+        leds.set(Leds::CAPS_LOCK, modifiers.contains(ModifiersState::CAPS_LOCK));
+        leds.set(Leds::NUM_LOCK, modifiers.contains(ModifiersState::NUM_LOCK));
+        leds.set(Leds::SCROLL_LOCK, modifiers.contains(ModifiersState::SCROLL_LOCK));
+        leds
+    }
+}
+
+/// The state of the keyboard's lock LEDs, see [`WindowExtLedState::led_state`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LedState {
+    /// The LEDs can't be queried.
+    #[default]
+    Unknown,
+    /// The LEDs which are lit.
+    Known(Leds),
+}
+
+impl LedState {
+    /// The LEDs which are lit, or `None` if they are unknown.
+    pub fn leds(self) -> Option<Leds> {
+        match self {
+            LedState::Known(leds) => Some(leds),
+            LedState::Unknown => None,
+        }
+    }
+}
+
+pub trait WindowExtLedState {
+    /// Query the lock LEDs of the keyboard.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The indicators of the core keyboard named `Caps Lock`, `Num Lock` and `Scroll
+    ///   Lock`, as queried with `XkbGetNamedIndicator`. These reflect the LEDs the server drives,
+    ///   regardless of the window having the focus.
+    /// - **Wayland:** The protocol doesn't expose the LEDs, so they are inferred from the locked
+    ///   modifiers sent by the compositor, which is what they are bound to in the usual keymaps.
+    ///   This is only known while the window has the keyboard focus, and is [`LedState::Unknown`]
+    ///   otherwise.
+    /// - **Windows:** The toggle state of the lock keys, as reported by `GetKeyState` for the
+    ///   calling thread. Windows drives the LEDs from it, but keeps it per thread: it may lag
+    ///   behind while the thread's message queue isn't being processed.
+    /// - **macOS:** There is no reliable API for the LEDs, this is always [`LedState::Unknown`].
+    fn led_state(&self) -> LedState;
+}
+
+impl WindowExtLedState for dyn Window + '_ {
+    fn led_state(&self) -> LedState {
+        #[cfg(wayland_platform)]
+        if let Some(window) = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()
+        {
+            return window.led_state();
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(window) =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::window::Window>()
+        {
+            return window.led_state();
+        }
+
+        #[cfg(windows_platform)]
+        if let Some(window) = self.as_any().downcast_ref::<crate::platform_impl::Window>() {
+            return window.led_state();
+        }
+
+        LedState::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leds_of_lock_modifiers() {
+        assert_eq!(Leds::from_lock_modifiers(ModifiersState::empty()), Leds::empty());
+        // Only the lock modifiers have LEDs.
+        let modifiers = ModifiersState::CAPS_LOCK | ModifiersState::SHIFT | ModifiersState::CONTROL;
+        assert_eq!(Leds::from_lock_modifiers(modifiers), Leds::CAPS_LOCK);
+        let modifiers = ModifiersState::NUM_LOCK | ModifiersState::SCROLL_LOCK;
+        assert_eq!(Leds::from_lock_modifiers(modifiers), Leds::NUM_LOCK | Leds::SCROLL_LOCK);
+    }
+
+    #[test]
+    fn known_leds() {
+        assert_eq!(LedState::default().leds(), None);
+        assert_eq!(LedState::Known(Leds::empty()).leds(), Some(Leds::empty()));
+        assert_eq!(LedState::Known(Leds::NUM_LOCK).leds(), Some(Leds::NUM_LOCK));
+    }
+}
@@ -66,6 +66,8 @@
 //! ```
 
 use std::os::raw::c_void;
+use std::path::PathBuf;
+use std::ptr::NonNull;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -120,6 +122,26 @@ pub trait WindowExtMacOS {
     /// Get the number of tabs in the window tab group.
     fn num_tabs(&self) -> usize;
 
+    /// Set whether the window opens as a tab of a window with the same tabbing identifier, see
+    /// [`TabbingMode`].
+    ///
+    /// Windows created with [`WindowAttributesExtMacOS::with_tabbing_identifier`] use
+    /// [`TabbingMode::Preferred`].
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/tabbingmode>
+    fn set_tabbing_mode(&self, mode: TabbingMode);
+
+    /// Getter for the [`WindowExtMacOS::set_tabbing_mode`].
+    fn tabbing_mode(&self) -> TabbingMode;
+
+    /// Add `tab` as a new tab of this window's tab group, right after this window.
+    ///
+    /// If this window isn't tabbed yet, a new tab group holding both windows is created. Adding the
+    /// window to itself, or a window that is already in the tab group, does nothing.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/addtabbedwindow(_:ordered:)>
+    fn add_tabbed_window(&self, tab: &dyn Window);
+
     /// Get the window's edit state.
     ///
     /// # Examples
@@ -137,8 +159,23 @@ pub trait WindowExtMacOS {
     fn is_document_edited(&self) -> bool;
 
     /// Put the window in a state which indicates a file save is required.
+    ///
+    /// This shows a dot in the window's close button, and dims the proxy icon set with
+    /// [`WindowExtMacOS::set_represented_filename`].
     fn set_document_edited(&self, edited: bool);
 
+    /// Set the file or directory the window represents, or `None` to clear it.
+    ///
+    /// The title bar then displays the file's icon next to the title, which the user can drag like
+    /// the file itself, or command-click to reveal the path. Use this for document-based
+    /// applications; it does not change the window's title.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/representedfilename>
+    fn set_represented_filename(&self, path: Option<PathBuf>);
+
+    /// Getter for the [`WindowExtMacOS::set_represented_filename`].
+    fn represented_filename(&self) -> Option<PathBuf>;
+
     /// Set option as alt behavior as described in [`OptionAsAlt`].
     ///
     /// This will ignore diacritical marks and accent characters from
@@ -225,6 +262,24 @@ impl WindowExtMacOS for dyn Window + '_ {
         window.maybe_wait_on_main(|w| w.num_tabs())
     }
 
+    #[inline]
+    fn set_tabbing_mode(&self, mode: TabbingMode) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(move |w| w.set_tabbing_mode(mode));
+    }
+
+    #[inline]
+    fn tabbing_mode(&self) -> TabbingMode {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.tabbing_mode())
+    }
+
+    #[inline]
+    fn add_tabbed_window(&self, tab: &dyn Window) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.add_tabbed_window(tab));
+    }
+
     #[inline]
     fn is_document_edited(&self) -> bool {
         let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
@@ -237,6 +292,18 @@ impl WindowExtMacOS for dyn Window + '_ {
         window.maybe_wait_on_main(move |w| w.set_document_edited(edited));
     }
 
+    #[inline]
+    fn set_represented_filename(&self, path: Option<PathBuf>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(move |w| w.set_represented_filename(path));
+    }
+
+    #[inline]
+    fn represented_filename(&self) -> Option<PathBuf> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.represented_filename())
+    }
+
     #[inline]
     fn set_option_as_alt(&self, option_as_alt: OptionAsAlt) {
         let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
@@ -475,6 +542,86 @@ pub trait EventLoopBuilderExtMacOS {
     ///
     /// The default behavior is to ignore other applications and activate when launched.
     fn with_activate_ignoring_other_apps(&mut self, ignore: bool) -> &mut Self;
+
+    /// Used to control whether Winit overrides `sendEvent:` on the shared `NSApplication`.
+    ///
+    /// Winit swizzles the method to observe every event the application receives, or subclasses
+    /// `NSApplication` with [`with_app_subclass`][Self::with_app_subclass]. Disable this
+    /// when embedding Winit in an existing AppKit application whose event routing must not be
+    /// touched, such as a plugin inside a host application. Without the override:
+    ///
+    /// - No [`KeyboardInput`] release is reported for keys released while <kbd>Command</kbd> is
+    ///   held, as AppKit doesn't deliver those `keyUp:` events to the window.
+    /// - No [`DeviceEvent`]s are reported.
+    ///
+    /// The override is enabled by default.
+    ///
+    /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    fn with_send_event_override(&mut self, enable: bool) -> &mut Self;
+
+    /// Used to swap <kbd>Command</kbd> and <kbd>Control</kbd> in the modifiers reported by Winit.
+    ///
+    /// When enabled, [`ModifiersState::SUPER`] and [`ModifiersState::CONTROL`] are exchanged, as
+    /// well as the states of the keys, e.g. [`Modifiers::lsuper_state`], in
+    /// [`WindowEvent::ModifiersChanged`] and [`Window::current_modifiers`]. This lets applications
+    /// match <kbd>Command</kbd> shortcuts with the <kbd>Control</kbd> shortcuts they use on other
+    /// platforms.
+    ///
+    /// This only affects the modifiers Winit reports, not the system: the keys keep their
+    /// [`KeyEvent`]s, text input and system shortcuts still use the actual keys, and the
+    /// `keyUp` of keys released while <kbd>Command</kbd> is held is still delivered.
+    ///
+    /// The swap is disabled by default.
+    ///
+    /// [`ModifiersState::SUPER`]: crate::keyboard::ModifiersState::SUPER
+    /// [`ModifiersState::CONTROL`]: crate::keyboard::ModifiersState::CONTROL
+    /// [`Modifiers::lsuper_state`]: crate::event::Modifiers::lsuper_state
+    /// [`WindowEvent::ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+    /// [`Window::current_modifiers`]: crate::window::Window::current_modifiers
+    /// [`KeyEvent`]: crate::event::KeyEvent
+    fn with_swap_command_control(&mut self, swap: bool) -> &mut Self;
+
+    /// Used to override `sendEvent:` with a subclass of `NSApplication` instead of swizzling.
+    ///
+    /// By default, Winit swizzles `sendEvent:` on the class of the shared `NSApplication`, which
+    /// leaves the choice of that class to the application. Swizzling the methods of system classes
+    /// may however be rejected by the App Store review. When enabled, the shared application is
+    /// instead created as an instance of Winit's own `NSApplication` subclass, which overrides the
+    /// method without swizzling. Winit behaves the same either way.
+    ///
+    /// The subclass can only be used when the shared application doesn't exist yet: creating the
+    /// event loop fails with [`EventLoopError::NotSupported`] if something, e.g. another library,
+    /// already created it with `[NSApplication sharedApplication]`.
+    ///
+    /// This has no effect when the override is disabled with
+    /// [`with_send_event_override`][Self::with_send_event_override]. The subclass is disabled by
+    /// default.
+    ///
+    /// [`EventLoopError::NotSupported`]: crate::error::EventLoopError::NotSupported
+    fn with_app_subclass(&mut self, enable: bool) -> &mut Self;
+
+    /// Used to control whether consecutive [`DeviceEvent::PointerMotion`] events are coalesced.
+    ///
+    /// When enabled, the deltas of a device are summed and delivered as a single event before
+    /// the next other [`DeviceEvent`], and at the latest before
+    /// [`ApplicationHandler::about_to_wait`]. With high polling rate mice, this avoids calling the
+    /// handler, and allocating to queue the call, for each of the up to 1000 samples per second:
+    /// with a 1000Hz mouse and an event loop iterating at 60Hz, the handler is called, and a
+    /// closure allocated when the call has to be queued, 60 times per second instead of 1000.
+    ///
+    /// Disable it when every sample is needed, e.g. to analyze the aim or rhythm of the user. The
+    /// device events of a native event are otherwise delivered before its window events, while the
+    /// coalesced motion is delivered after the [`WindowEvent::PointerMoved`] of the same moves, so
+    /// also disable it when the application relates the two.
+    ///
+    /// The coalescing is enabled by default.
+    ///
+    /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    /// [`ApplicationHandler::about_to_wait`]: crate::application::ApplicationHandler::about_to_wait
+    /// [`WindowEvent::PointerMoved`]: crate::event::WindowEvent::PointerMoved
+    fn with_pointer_motion_coalescing(&mut self, enable: bool) -> &mut Self;
 }
 
 impl EventLoopBuilderExtMacOS for EventLoopBuilder {
@@ -495,6 +642,30 @@ impl EventLoopBuilderExtMacOS for EventLoopBuilder {
         self.platform_specific.activate_ignoring_other_apps = ignore;
         self
     }
+
+    #[inline]
+    fn with_send_event_override(&mut self, enable: bool) -> &mut Self {
+        self.platform_specific.send_event_override = enable;
+        self
+    }
+
+    #[inline]
+    fn with_swap_command_control(&mut self, swap: bool) -> &mut Self {
+        self.platform_specific.swap_command_control = swap;
+        self
+    }
+
+    #[inline]
+    fn with_app_subclass(&mut self, enable: bool) -> &mut Self {
+        self.platform_specific.app_subclass = enable;
+        self
+    }
+
+    #[inline]
+    fn with_pointer_motion_coalescing(&mut self, enable: bool) -> &mut Self {
+        self.platform_specific.pointer_motion_coalescing = enable;
+        self
+    }
 }
 
 /// Additional methods on [`MonitorHandle`] that are specific to MacOS.
@@ -568,6 +739,74 @@ impl ActiveEventLoopExtMacOS for dyn ActiveEventLoop + '_ {
     }
 }
 
+/// Returns whether Winit has overridden `sendEvent:` on the given `NSApplication`.
+///
+/// Winit overrides the method when the event loop is created, unless disabled with
+/// [`EventLoopBuilderExtMacOS::with_send_event_override`]. This is meant for embedders that swizzle
+/// `sendEvent:` themselves and need to coordinate with Winit.
+///
+/// Only the override of this version of Winit is detected; another version of Winit in the same
+/// application installs its own, different override.
+///
+/// # Safety
+///
+/// `app` must be a valid pointer to an `NSApplication` or an instance of a subclass of it, and
+/// this must be called on the main thread.
+pub unsafe fn is_send_event_overridden(app: NonNull<c_void>) -> bool {
+    // SAFETY: Upheld by the caller.
+    let app = unsafe { app.cast::<objc2_app_kit::NSApplication>().as_ref() };
+    crate::platform_impl::is_send_event_overridden(app)
+}
+
+/// A function inspecting an `NSEvent` before Winit, see [`set_send_event_hook`].
+///
+/// It's called with a pointer to the event, and returns whether it consumed it.
+pub type SendEventHook = Box<dyn FnMut(NonNull<c_void>) -> bool>;
+
+/// Set a function which is called with every event the application receives, before Winit
+/// handles it, or remove it with `None`.
+///
+/// This is meant for embedders that need to see all the events, e.g. to implement their own global
+/// shortcuts. When the hook returns `true`, the event is consumed: neither Winit nor the original
+/// `sendEvent:` of the application handle it. When it returns `false`, the event goes on as usual.
+///
+/// The hook is called from Winit's override of `sendEvent:`, so it isn't called when the override
+/// is disabled with [`EventLoopBuilderExtMacOS::with_send_event_override`].
+///
+/// The hook always runs on the main thread, and isn't re-entered: events sent while it runs, e.g.
+/// because it runs a nested event loop such as a modal dialog, bypass it. The hook may call this
+/// function to replace or remove itself, which takes effect once it returns.
+///
+/// # Panics
+///
+/// Panics when not called on the main thread.
+pub fn set_send_event_hook(hook: Option<SendEventHook>) {
+    let mtm = objc2::MainThreadMarker::new()
+        .expect("`set_send_event_hook` must be called on the main thread");
+    crate::platform_impl::set_send_event_hook(mtm, hook)
+}
+
+/// Whether a window opens as a tab, see [`WindowExtMacOS::set_tabbing_mode`].
+///
+/// Windows are grouped with the other windows that have the same tabbing identifier, see
+/// [`WindowExtMacOS::set_tabbing_identifier`].
+///
+/// The default is `Automatic`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TabbingMode {
+    /// The window opens as a tab if the user prefers tabs in the system settings, or if the
+    /// window is in full screen.
+    #[default]
+    Automatic,
+
+    /// The window always opens as a tab.
+    Preferred,
+
+    /// The window never opens as a tab, and can't be tabbed by the user.
+    Disallowed,
+}
+
 /// Option as alt behavior.
 ///
 /// The default is `None`.
@@ -4,8 +4,14 @@
 
 #[cfg(any(android_platform, docsrs))]
 pub mod android;
+#[cfg(any(x11_platform, wayland_platform, docsrs))]
+pub mod cursor_theme;
+#[cfg(headless_platform)]
+pub mod headless;
 #[cfg(any(ios_platform, docsrs))]
 pub mod ios;
+#[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform, docsrs))]
+pub mod led_state;
 #[cfg(any(macos_platform, docsrs))]
 pub mod macos;
 #[cfg(any(orbital_platform, docsrs))]
@@ -53,4 +59,14 @@ pub mod pump_events;
 pub mod modifier_supplement;
 
 #[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform, docsrs))]
+pub mod modifier_text;
+
+#[cfg(any(
+    windows_platform,
+    macos_platform,
+    x11_platform,
+    wayland_platform,
+    android_platform,
+    docsrs
+))]
 pub mod scancode;
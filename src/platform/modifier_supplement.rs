@@ -9,16 +9,8 @@ pub trait KeyEventExtModifierSupplement {
     /// For example, pressing <kbd>Ctrl</kbd>+<kbd>a</kbd> produces `Some("\x01")`.
     fn text_with_all_modifiers(&self) -> Option<&str>;
 
-    /// This value ignores all modifiers including,
-    /// but not limited to <kbd>Shift</kbd>, <kbd>Caps Lock</kbd>,
-    /// and <kbd>Ctrl</kbd>. In most cases this means that the
-    /// unicode character in the resulting string is lowercase.
-    ///
-    /// This is useful for key-bindings / shortcut key combinations.
-    ///
-    /// In case `logical_key` reports `Dead`, this will still report the
-    /// key as `Character` according to the current keyboard layout. This value
-    /// cannot be `Dead`.
+    /// Identical to [`KeyEvent::key_without_modifiers`].
+    #[deprecated = "use the `KeyEvent::key_without_modifiers` field instead"]
     fn key_without_modifiers(&self) -> Key;
 }
 
@@ -30,6 +22,6 @@ impl KeyEventExtModifierSupplement for KeyEvent {
 
     #[inline]
     fn key_without_modifiers(&self) -> Key {
-        self.platform_specific.key_without_modifiers.clone()
+        self.key_without_modifiers.clone()
     }
 }
@@ -0,0 +1,49 @@
+use smol_str::SmolStr;
+
+use crate::event::KeyEvent;
+
+/// Additional methods for the `KeyEvent` to look up the text a key produces with and without
+/// <kbd>Shift</kbd> in the current keyboard layout.
+///
+/// Unlike `KeyEvent::text`, these values don't depend on the modifiers that were held when the
+/// key was pressed, and are available for released keys as well. This is useful for displaying
+/// key-bindings, or for matching a shortcut against both levels of a key.
+///
+/// Only <kbd>Shift</kbd> is taken into account: <kbd>Caps Lock</kbd>, <kbd>Ctrl</kbd>,
+/// <kbd>Alt</kbd> and <kbd>AltGr</kbd> are ignored, and dead keys produce the character they'd
+/// insert on their own instead of starting a composition.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Uses the layout active on the calling thread at the time of the call, as resolved
+///   with `ToUnicodeEx`.
+/// - **macOS:** Uses the input source selected at the time of the call, as resolved with
+///   `UCKeyTranslate`.
+/// - **X11 / Wayland:** The XKB state isn't reachable from the event, so the text is resolved
+///   against the layout that was active when the event was delivered. Level 2 of the key is assumed
+///   to be the <kbd>Shift</kbd> level, which holds for regular layouts.
+pub trait KeyEventExtModifiers {
+    /// The text the key produces without any modifiers, e.g. `Some("a")` or `Some("1")` on a
+    /// US layout.
+    ///
+    /// Returns `None` if the key doesn't produce text.
+    fn text_unshifted(&self) -> Option<SmolStr>;
+
+    /// The text the key produces with <kbd>Shift</kbd> held, e.g. `Some("A")` or `Some("!")` on
+    /// a US layout.
+    ///
+    /// Returns `None` if the key doesn't produce text.
+    fn text_shifted(&self) -> Option<SmolStr>;
+}
+
+impl KeyEventExtModifiers for KeyEvent {
+    #[inline]
+    fn text_unshifted(&self) -> Option<SmolStr> {
+        self.platform_specific.text_for_shift(self.physical_key, false)
+    }
+
+    #[inline]
+    fn text_shifted(&self) -> Option<SmolStr> {
+        self.platform_specific.text_for_shift(self.physical_key, true)
+    }
+}
@@ -104,6 +104,32 @@ pub trait EventLoopExtPumpEvents {
         timeout: Option<Duration>,
         app: A,
     ) -> PumpStatus;
+
+    /// Dispatch the events that are already pending, without blocking.
+    ///
+    /// This is the same as [`pump_app_events`][Self::pump_app_events] with a `timeout` of
+    /// `Some(Duration::ZERO)`, and is subject to the same caveats.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** The [`EventLoop`] is backed by a single file descriptor, see its
+    ///   [`AsFd`] implementation, which can be registered into an external reactor such as
+    ///   [`calloop`] or [`mio`]. That file descriptor is the contract: it becomes readable whenever
+    ///   any of Winit's sources is ready, be it the connection to the X or Wayland server or
+    ///   Winit's own wake-ups, so the sources aren't exposed separately. Call this method every
+    ///   time it signals readability, the events are only read and dispatched from here. The file
+    ///   descriptor doesn't signal timeouts, so when using [`ControlFlow::WaitUntil`], also call
+    ///   this method once the deadline passed, and with [`ControlFlow::Poll`], call it on every
+    ///   iteration of the external loop.
+    ///
+    /// [`AsFd`]: std::os::fd::AsFd
+    /// [`calloop`]: https://crates.io/crates/calloop
+    /// [`mio`]: https://crates.io/crates/mio
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    fn dispatch_pending<A: ApplicationHandler>(&mut self, app: A) -> PumpStatus {
+        self.pump_app_events(Some(Duration::ZERO), app)
+    }
 }
 
 impl EventLoopExtPumpEvents for EventLoop {
@@ -112,7 +138,7 @@ impl EventLoopExtPumpEvents for EventLoop {
         timeout: Option<Duration>,
         app: A,
     ) -> PumpStatus {
-        self.event_loop.pump_app_events(timeout, app)
+        self.event_loop.pump_app_events(timeout, self.handler_options.wrap(app))
     }
 }
 
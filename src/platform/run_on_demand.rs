@@ -65,7 +65,7 @@ pub trait EventLoopExtRunOnDemand {
 
 impl EventLoopExtRunOnDemand for EventLoop {
     fn run_app_on_demand<A: ApplicationHandler>(&mut self, app: A) -> Result<(), EventLoopError> {
-        self.event_loop.run_app_on_demand(app)
+        self.event_loop.run_app_on_demand(self.handler_options.wrap(app))
     }
 }
 
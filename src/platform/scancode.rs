@@ -1,25 +1,35 @@
 use crate::keyboard::{KeyCode, PhysicalKey};
 
-// TODO: Describe what this value contains for each platform
-
 /// Additional methods for the [`PhysicalKey`] type that allow the user to access the
 /// platform-specific scancode.
 ///
+/// This is useful to persist key bindings: converting a [`PhysicalKey`] reported by winit to a
+/// scancode and back with [`from_scancode`] yields the same [`PhysicalKey`]. For
+/// [`PhysicalKey::Code`], the hardware scancode is used rather than the [`KeyCode`] itself, so the
+/// stored value doesn't depend on how winit maps it.
+///
 /// [`PhysicalKey`]: crate::keyboard::PhysicalKey
+/// [`from_scancode`]: Self::from_scancode
 pub trait PhysicalKeyExtScancode {
     /// The raw value of the platform-specific physical key identifier.
     ///
-    /// Returns `Some(key_id)` if the conversion was successful; returns `None` otherwise.
+    /// Returns `Some(key_id)` if the conversion was successful; returns `None` otherwise, e.g. for
+    /// a [`NativeKeyCode`] of another platform.
     ///
     /// ## Platform-specific
     /// - **Windows:** A 16bit extended scancode
+    /// - **macOS:** A 16bit virtual key code.
     /// - **Wayland/X11**: A 32-bit linux scancode, which is X11/Wayland keycode subtracted by 8.
+    /// - **Android:** The `AKEYCODE_*` value of the key.
+    ///
+    /// [`NativeKeyCode`]: crate::keyboard::NativeKeyCode
     fn to_scancode(self) -> Option<u32>;
 
     /// Constructs a `PhysicalKey` from a platform-specific physical key identifier.
     ///
     /// Note that this conversion may be lossy, i.e. converting the returned `PhysicalKey` back
-    /// using `to_scancode` might not yield the original value.
+    /// using `to_scancode` might not yield the original value when several scancodes map to the
+    /// same [`KeyCode`]. The other direction is stable, see [`PhysicalKeyExtScancode`].
     ///
     /// ## Platform-specific
     /// - **Wayland/X11**: A 32-bit linux scancode. When building from X11/Wayland keycode subtract
@@ -48,3 +58,21 @@ impl PhysicalKeyExtScancode for KeyCode {
         <PhysicalKey as PhysicalKeyExtScancode>::from_scancode(scancode)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scancode_round_trip() {
+        for scancode in 0..1024 {
+            let key = PhysicalKey::from_scancode(scancode);
+            if let Some(scancode) = key.to_scancode() {
+                assert_eq!(PhysicalKey::from_scancode(scancode), key);
+            }
+        }
+
+        let key = PhysicalKey::Code(KeyCode::KeyA);
+        assert_eq!(PhysicalKey::from_scancode(key.to_scancode().unwrap()), key);
+    }
+}
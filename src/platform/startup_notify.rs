@@ -45,8 +45,41 @@ pub trait EventLoopExtStartupNotify {
 pub trait WindowExtStartupNotify {
     /// Request a new activation token.
     ///
-    /// The token will be delivered inside
+    /// The token will be delivered inside [`WindowEvent::ActivationTokenDone`] with the returned
+    /// serial. Pass it to the window or process that should be activated, which then consumes it
+    /// with [`activate_with_token`] or [`with_activation_token`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Uses `xdg_activation_v1.get_activation_token`, with the window's surface as
+    ///   the requesting surface.
+    ///
+    /// [`WindowEvent::ActivationTokenDone`]: crate::event::WindowEvent::ActivationTokenDone
+    /// [`activate_with_token`]: Self::activate_with_token
+    /// [`with_activation_token`]: WindowAttributesExtStartupNotify::with_activation_token
     fn request_activation_token(&self) -> Result<AsyncRequestSerial, RequestError>;
+
+    /// Raise and focus the window, consuming an [`ActivationToken`].
+    ///
+    /// Use this when activating an existing window on behalf of another window or process, e.g.
+    /// after the user clicked a notification. The token is typically obtained with
+    /// [`request_activation_token`] by whoever triggered the activation.
+    ///
+    /// Tokens are one-shot: once used, either here or with [`with_activation_token`], the
+    /// windowing system ignores them. The windowing system may also decline the activation, for
+    /// instance when the token is stale, in which case nothing happens and no error is returned.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Maps to `xdg_activation_v1.activate`. Returns [`RequestError::Ignored`] when
+    ///   the token was already used by this application, as the compositor would silently ignore
+    ///   it.
+    /// - **X11:** Sets `_NET_STARTUP_ID` on the window, completes the startup sequence of the token
+    ///   and then focuses the window.
+    ///
+    /// [`request_activation_token`]: Self::request_activation_token
+    /// [`with_activation_token`]: WindowAttributesExtStartupNotify::with_activation_token
+    fn activate_with_token(&self, token: ActivationToken) -> Result<(), RequestError>;
 }
 
 pub trait WindowAttributesExtStartupNotify {
@@ -89,6 +122,24 @@ impl WindowExtStartupNotify for dyn Window + '_ {
 
         Err(NotSupportedError::new("startup notify is not supported").into())
     }
+
+    fn activate_with_token(&self, token: ActivationToken) -> Result<(), RequestError> {
+        #[cfg(wayland_platform)]
+        if let Some(window) = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()
+        {
+            return window.activate_with_token(token);
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(window) =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::window::Window>()
+        {
+            return window.activate_with_token(token);
+        }
+
+        let _ = token;
+        Err(NotSupportedError::new("startup notify is not supported").into())
+    }
 }
 
 impl WindowAttributesExtStartupNotify for WindowAttributes {
@@ -73,9 +73,62 @@ impl EventLoopBuilderExtWayland for EventLoopBuilder {
 /// Additional methods on [`Window`] that are specific to Wayland.
 ///
 /// [`Window`]: crate::window::Window
-pub trait WindowExtWayland {}
+pub trait WindowExtWayland {
+    /// Select which sides of the client-side decorations winit should handle.
+    ///
+    /// This is meant for applications drawing parts of their decorations themselves, e.g. a
+    /// custom titlebar, while still relying on winit for the resize borders. Winit ignores the
+    /// pointer input on the disabled sides: it doesn't drag the window, resize it, maximize it on
+    /// double-click or press the titlebar buttons from them, nor shows their resize cursors. The
+    /// frame is still drawn until every side is disabled, in which case it is hidden entirely.
+    ///
+    /// This only affects decorations drawn by winit, it has no effect when the compositor
+    /// provides server-side decorations or when decorations are disabled with
+    /// [`Window::set_decorations`].
+    ///
+    /// The default is to handle all sides.
+    ///
+    /// [`Window::set_decorations`]: crate::window::Window::set_decorations
+    fn set_csd_sides(&self, sides: CsdSides);
+}
+
+impl WindowExtWayland for dyn CoreWindow + '_ {
+    #[inline]
+    fn set_csd_sides(&self, sides: CsdSides) {
+        if let Some(window) = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()
+        {
+            window.set_csd_sides(sides);
+        }
+    }
+}
+
+/// The sides of the client-side decorations handled by winit.
+///
+/// See [`WindowExtWayland::set_csd_sides`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CsdSides {
+    /// Whether winit handles the titlebar.
+    pub top: bool,
+    /// Whether winit handles the left border.
+    pub left: bool,
+    /// Whether winit handles the bottom border.
+    pub bottom: bool,
+    /// Whether winit handles the right border.
+    pub right: bool,
+}
 
-impl WindowExtWayland for dyn CoreWindow + '_ {}
+impl CsdSides {
+    /// Handle every side of the decorations.
+    pub const ALL: Self = Self { top: true, left: true, bottom: true, right: true };
+    /// Handle none of the sides of the decorations.
+    pub const NONE: Self = Self { top: false, left: false, bottom: false, right: false };
+}
+
+impl Default for CsdSides {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
 
 /// Additional methods on [`WindowAttributes`] that are specific to Wayland.
 pub trait WindowAttributesExtWayland {
@@ -264,7 +264,7 @@ pub trait EventLoopExtWeb {
 
 impl EventLoopExtWeb for EventLoop {
     fn spawn_app<A: ApplicationHandler + 'static>(self, app: A) {
-        self.event_loop.spawn_app(app);
+        self.event_loop.spawn_app(self.handler_options.wrap(app));
     }
 
     fn set_poll_strategy(&self, strategy: PollStrategy) {
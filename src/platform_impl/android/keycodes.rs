@@ -157,6 +157,145 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
     })
 }
 
+pub fn physicalkey_to_scancode(key: PhysicalKey) -> Option<u32> {
+    let code = match key {
+        PhysicalKey::Code(code) => code,
+        PhysicalKey::Unidentified(NativeKeyCode::Android(raw)) => return Some(raw),
+        PhysicalKey::Unidentified(_) => return None,
+    };
+
+    Some(
+        match code {
+            KeyCode::KeyA => Keycode::A,
+            KeyCode::KeyB => Keycode::B,
+            KeyCode::KeyC => Keycode::C,
+            KeyCode::KeyD => Keycode::D,
+            KeyCode::KeyE => Keycode::E,
+            KeyCode::KeyF => Keycode::F,
+            KeyCode::KeyG => Keycode::G,
+            KeyCode::KeyH => Keycode::H,
+            KeyCode::KeyI => Keycode::I,
+            KeyCode::KeyJ => Keycode::J,
+            KeyCode::KeyK => Keycode::K,
+            KeyCode::KeyL => Keycode::L,
+            KeyCode::KeyM => Keycode::M,
+            KeyCode::KeyN => Keycode::N,
+            KeyCode::KeyO => Keycode::O,
+            KeyCode::KeyP => Keycode::P,
+            KeyCode::KeyQ => Keycode::Q,
+            KeyCode::KeyR => Keycode::R,
+            KeyCode::KeyS => Keycode::S,
+            KeyCode::KeyT => Keycode::T,
+            KeyCode::KeyU => Keycode::U,
+            KeyCode::KeyV => Keycode::V,
+            KeyCode::KeyW => Keycode::W,
+            KeyCode::KeyX => Keycode::X,
+            KeyCode::KeyY => Keycode::Y,
+            KeyCode::KeyZ => Keycode::Z,
+            KeyCode::Digit0 => Keycode::Keycode0,
+            KeyCode::Digit1 => Keycode::Keycode1,
+            KeyCode::Digit2 => Keycode::Keycode2,
+            KeyCode::Digit3 => Keycode::Keycode3,
+            KeyCode::Digit4 => Keycode::Keycode4,
+            KeyCode::Digit5 => Keycode::Keycode5,
+            KeyCode::Digit6 => Keycode::Keycode6,
+            KeyCode::Digit7 => Keycode::Keycode7,
+            KeyCode::Digit8 => Keycode::Keycode8,
+            KeyCode::Digit9 => Keycode::Keycode9,
+            KeyCode::Numpad0 => Keycode::Numpad0,
+            KeyCode::Numpad1 => Keycode::Numpad1,
+            KeyCode::Numpad2 => Keycode::Numpad2,
+            KeyCode::Numpad3 => Keycode::Numpad3,
+            KeyCode::Numpad4 => Keycode::Numpad4,
+            KeyCode::Numpad5 => Keycode::Numpad5,
+            KeyCode::Numpad6 => Keycode::Numpad6,
+            KeyCode::Numpad7 => Keycode::Numpad7,
+            KeyCode::Numpad8 => Keycode::Numpad8,
+            KeyCode::Numpad9 => Keycode::Numpad9,
+            KeyCode::NumpadAdd => Keycode::NumpadAdd,
+            KeyCode::NumpadSubtract => Keycode::NumpadSubtract,
+            KeyCode::NumpadMultiply => Keycode::NumpadMultiply,
+            KeyCode::NumpadDivide => Keycode::NumpadDivide,
+            KeyCode::NumpadEnter => Keycode::NumpadEnter,
+            KeyCode::NumpadEqual => Keycode::NumpadEquals,
+            KeyCode::NumpadComma => Keycode::NumpadComma,
+            KeyCode::NumpadDecimal => Keycode::NumpadDot,
+            KeyCode::NumLock => Keycode::NumLock,
+            KeyCode::ArrowLeft => Keycode::DpadLeft,
+            KeyCode::ArrowRight => Keycode::DpadRight,
+            KeyCode::ArrowUp => Keycode::DpadUp,
+            KeyCode::ArrowDown => Keycode::DpadDown,
+            KeyCode::F1 => Keycode::F1,
+            KeyCode::F2 => Keycode::F2,
+            KeyCode::F3 => Keycode::F3,
+            KeyCode::F4 => Keycode::F4,
+            KeyCode::F5 => Keycode::F5,
+            KeyCode::F6 => Keycode::F6,
+            KeyCode::F7 => Keycode::F7,
+            KeyCode::F8 => Keycode::F8,
+            KeyCode::F9 => Keycode::F9,
+            KeyCode::F10 => Keycode::F10,
+            KeyCode::F11 => Keycode::F11,
+            KeyCode::F12 => Keycode::F12,
+            KeyCode::Space => Keycode::Space,
+            KeyCode::Escape => Keycode::Escape,
+            KeyCode::Enter => Keycode::Enter,
+            KeyCode::Tab => Keycode::Tab,
+            KeyCode::PageUp => Keycode::PageUp,
+            KeyCode::PageDown => Keycode::PageDown,
+            KeyCode::Home => Keycode::MoveHome,
+            KeyCode::End => Keycode::MoveEnd,
+            KeyCode::Insert => Keycode::Insert,
+            KeyCode::Backspace => Keycode::Del,
+            KeyCode::Delete => Keycode::ForwardDel,
+            KeyCode::Copy => Keycode::Copy,
+            KeyCode::Paste => Keycode::Paste,
+            KeyCode::Cut => Keycode::Cut,
+            KeyCode::AudioVolumeUp => Keycode::VolumeUp,
+            KeyCode::AudioVolumeDown => Keycode::VolumeDown,
+            KeyCode::AudioVolumeMute => Keycode::VolumeMute,
+            KeyCode::MediaPlayPause => Keycode::MediaPlayPause,
+            KeyCode::MediaStop => Keycode::MediaStop,
+            KeyCode::MediaTrackNext => Keycode::MediaNext,
+            KeyCode::MediaTrackPrevious => Keycode::MediaPrevious,
+            KeyCode::Equal => Keycode::Equals,
+            KeyCode::Minus => Keycode::Minus,
+            KeyCode::Semicolon => Keycode::Semicolon,
+            KeyCode::Slash => Keycode::Slash,
+            KeyCode::Backslash => Keycode::Backslash,
+            KeyCode::Comma => Keycode::Comma,
+            KeyCode::Period => Keycode::Period,
+            KeyCode::Quote => Keycode::Apostrophe,
+            KeyCode::Backquote => Keycode::Grave,
+            KeyCode::PrintScreen => Keycode::Sysrq,
+            KeyCode::Pause => Keycode::Break,
+            KeyCode::ScrollLock => Keycode::ScrollLock,
+            KeyCode::IntlYen => Keycode::Yen,
+            KeyCode::Lang1 => Keycode::Kana,
+            KeyCode::KanaMode => Keycode::KatakanaHiragana,
+            KeyCode::ControlLeft => Keycode::CtrlLeft,
+            KeyCode::ControlRight => Keycode::CtrlRight,
+            KeyCode::ShiftLeft => Keycode::ShiftLeft,
+            KeyCode::ShiftRight => Keycode::ShiftRight,
+            KeyCode::AltLeft => Keycode::AltLeft,
+            KeyCode::AltRight => Keycode::AltRight,
+            KeyCode::SuperLeft => Keycode::MetaLeft,
+            KeyCode::SuperRight => Keycode::MetaRight,
+            KeyCode::BracketLeft => Keycode::LeftBracket,
+            KeyCode::BracketRight => Keycode::RightBracket,
+            KeyCode::Power => Keycode::Power,
+            KeyCode::Sleep => Keycode::Sleep,
+            KeyCode::WakeUp => Keycode::Wakeup,
+            _ => return None,
+        }
+        .into(),
+    )
+}
+
+pub fn scancode_to_physicalkey(scancode: u32) -> PhysicalKey {
+    to_physical_key(Keycode::from(scancode))
+}
+
 /// Tries to map the `key_event` to a `KeyMapChar` containing a unicode character or dead key accent
 ///
 /// This takes a `KeyEvent` and looks up its corresponding `KeyCharacterMap` and
@@ -11,25 +11,28 @@ use android_activity::{
 use tracing::{debug, trace, warn};
 
 use crate::application::ApplicationHandler;
-use crate::cursor::Cursor;
+use crate::cursor::{CurrentCursor, Cursor};
 use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{EventLoopError, NotSupportedError, RequestError};
 use crate::event::{self, DeviceId, FingerId, Force, StartCause, SurfaceSizeWriter};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
 };
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
 use crate::monitor::{MonitorHandle as RootMonitorHandle, VideoMode};
 use crate::platform::pump_events::PumpStatus;
 use crate::window::{
-    self, CursorGrabMode, CustomCursor, CustomCursorSource, Fullscreen, ImePurpose,
-    ResizeDirection, Theme, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    self, CommonWindowState, CursorGrabMode, CustomCursor, CustomCursorSource, Fullscreen,
+    HitTestRegion, ImePurpose, InputStateSnapshot, ResizeDirection, SurfaceTransform, Theme,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 mod keycodes;
 
+pub(crate) use self::keycodes::{physicalkey_to_scancode, scancode_to_physicalkey};
 pub(crate) use crate::cursor::{
     NoCustomCursor as PlatformCustomCursor, NoCustomCursor as PlatformCustomCursorSource,
 };
@@ -99,6 +102,13 @@ impl RedrawRequester {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct KeyEventExtra {}
 
+impl KeyEventExtra {
+    #[cfg(any(feature = "test-util", feature = "serde"))]
+    pub(crate) fn synthetic(_: Option<&smol_str::SmolStr>) -> Self {
+        Self {}
+    }
+}
+
 pub struct EventLoop {
     pub(crate) android_app: AndroidApp,
     window_target: ActiveEventLoop,
@@ -110,6 +120,8 @@ pub struct EventLoop {
     primary_pointer: Option<FingerId>,
     ignore_volume_keys: bool,
     combining_accent: Option<char>,
+    /// The last safe area reported with `WindowEvent::SafeAreaChanged`.
+    safe_area: PhysicalInsets<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -149,6 +161,7 @@ impl EventLoop {
                 exit: Cell::new(false),
                 redraw_requester: RedrawRequester::new(&redraw_flag, android_app.create_waker()),
                 event_loop_proxy,
+                common_state: Default::default(),
             },
             redraw_flag,
             loop_running: false,
@@ -157,6 +170,7 @@ impl EventLoop {
             cause: StartCause::Init,
             ignore_volume_keys: attributes.ignore_volume_keys,
             combining_accent: None,
+            safe_area: PhysicalInsets::default(),
         })
     }
 
@@ -189,8 +203,13 @@ impl EventLoop {
                 },
                 MainEvent::WindowResized { .. } => resized = true,
                 MainEvent::RedrawNeeded { .. } => pending_redraw = true,
-                MainEvent::ContentRectChanged { .. } => {
-                    warn!("TODO: find a way to notify application of content rect change");
+                MainEvent::ContentRectChanged { .. } | MainEvent::InsetsChanged { .. } => {
+                    let safe_area = safe_area(&self.android_app);
+                    if safe_area != self.safe_area {
+                        self.safe_area = safe_area;
+                        let event = event::WindowEvent::SafeAreaChanged(safe_area);
+                        app.window_event(&self.window_target, GLOBAL_WINDOW, event);
+                    }
                 },
                 MainEvent::GainedFocus => {
                     HAS_FOCUS.store(true, Ordering::Relaxed);
@@ -246,10 +265,6 @@ impl EventLoop {
                     // killed by the OS?
                     warn!("TODO: forward onDestroy notification to application");
                 },
-                MainEvent::InsetsChanged { .. } => {
-                    // XXX: how to forward this state to applications?
-                    warn!("TODO: handle Android InsetsChanged notification");
-                },
                 unknown => {
                     trace!("Unknown MainEvent {unknown:?} (ignored)");
                 },
@@ -379,6 +394,7 @@ impl EventLoop {
                                     android_activity::input::ToolType::Mouse => continue,
                                     _ => event::ButtonSource::Unknown(0),
                                 },
+                                timestamp: None,
                             };
                             app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                         },
@@ -396,6 +412,7 @@ impl EventLoop {
                                     android_activity::input::ToolType::Mouse => continue,
                                     _ => event::PointerSource::Unknown,
                                 },
+                                timestamp: None,
                             };
                             app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                         },
@@ -422,6 +439,7 @@ impl EventLoop {
                                         android_activity::input::ToolType::Mouse => continue,
                                         _ => event::ButtonSource::Unknown(0),
                                     },
+                                    timestamp: None,
                                 };
                                 app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                             }
@@ -469,16 +487,20 @@ impl EventLoop {
                             &mut self.combining_accent,
                         );
 
+                        let logical_key = keycodes::to_logical(key_char, keycode);
                         let event = event::WindowEvent::KeyboardInput {
                             device_id: Some(DeviceId::from_raw(key.device_id() as i64)),
                             event: event::KeyEvent {
                                 state,
                                 physical_key: keycodes::to_physical_key(keycode),
-                                logical_key: keycodes::to_logical(key_char, keycode),
+                                key_without_modifiers: logical_key.clone(),
+                                logical_key,
                                 location: keycodes::to_location(keycode),
                                 repeat: key.repeat_count() > 0,
                                 text: None,
+                                composed: false,
                                 platform_specific: KeyEventExtra {},
+                                timestamp: None,
                             },
                             is_synthetic: false,
                         };
@@ -660,12 +682,17 @@ pub struct ActiveEventLoop {
     exit: Cell<bool>,
     redraw_requester: RedrawRequester,
     event_loop_proxy: Arc<EventLoopProxy>,
+    common_state: CommonEventLoopState,
 }
 
 impl ActiveEventLoop {
     fn clear_exit(&self) {
         self.exit.set(false);
     }
+
+    pub(crate) fn common_state(&self) -> &CommonEventLoopState {
+        &self.common_state
+    }
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -677,7 +704,9 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         window_attributes: WindowAttributes,
     ) -> Result<Box<dyn CoreWindow>, RequestError> {
-        Ok(Box::new(Window::new(self, window_attributes)?))
+        let window: Box<dyn CoreWindow> = Box::new(Window::new(self, window_attributes)?);
+        self.common_state.register_window(&*window);
+        Ok(window)
     }
 
     fn create_custom_cursor(
@@ -699,6 +728,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        WindowingInfo::new(WindowingBackend::Android, None, WindowingCapabilities::empty())
+    }
+
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
     fn set_control_flow(&self, control_flow: ControlFlow) {
@@ -749,16 +782,25 @@ pub struct PlatformSpecificWindowAttributes;
 pub(crate) struct Window {
     app: AndroidApp,
     redraw_requester: RedrawRequester,
+    ime_allowed: AtomicBool,
+    current_cursor: CurrentCursor,
+    pub(crate) common_state: Arc<CommonWindowState>,
 }
 
 impl Window {
     pub(crate) fn new(
         el: &ActiveEventLoop,
-        _window_attrs: window::WindowAttributes,
+        window_attrs: window::WindowAttributes,
     ) -> Result<Self, RequestError> {
         // FIXME this ignores requested window attributes
 
-        Ok(Self { app: el.app.clone(), redraw_requester: el.redraw_requester.clone() })
+        Ok(Self {
+            app: el.app.clone(),
+            redraw_requester: el.redraw_requester.clone(),
+            ime_allowed: AtomicBool::new(false),
+            current_cursor: CurrentCursor::default(),
+            common_state: Arc::new(CommonWindowState::new(&window_attrs)),
+        })
     }
 
     pub fn config(&self) -> ConfigurationRef {
@@ -857,7 +899,11 @@ impl CoreWindow for Window {
     }
 
     fn safe_area(&self) -> PhysicalInsets<u32> {
-        PhysicalInsets::new(0, 0, 0, 0)
+        safe_area(&self.app)
+    }
+
+    fn surface_transform(&self) -> SurfaceTransform {
+        SurfaceTransform::Identity
     }
 
     fn set_min_surface_size(&self, _: Option<Size>) {}
@@ -876,6 +922,10 @@ impl CoreWindow for Window {
 
     fn set_blur(&self, _blur: bool) {}
 
+    fn set_tearing_allowed(&self, _allowed: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_tearing_allowed is not supported").into())
+    }
+
     fn set_visible(&self, _visibility: bool) {}
 
     fn is_visible(&self) -> Option<bool> {
@@ -906,6 +956,14 @@ impl CoreWindow for Window {
         false
     }
 
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("move_to_workspace is not supported").into())
+    }
+
     fn set_fullscreen(&self, _monitor: Option<Fullscreen>) {
         warn!("Cannot set fullscreen on Android");
     }
@@ -927,6 +985,7 @@ impl CoreWindow for Window {
     fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
 
     fn set_ime_allowed(&self, allowed: bool) {
+        self.ime_allowed.store(allowed, Ordering::Relaxed);
         if allowed {
             self.app.show_soft_input(true);
         } else {
@@ -936,22 +995,64 @@ impl CoreWindow for Window {
 
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    fn set_key_repeat_enabled(&self, _enabled: bool) {}
+
+    fn keyboard_layout_name(&self) -> Option<String> {
+        None
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        None
+    }
+
+    fn key_for_physical(&self, _key: PhysicalKey) -> Option<Key> {
+        None
+    }
+
+    fn compose_dead_key(&self, _dead: char, _base: char) -> Option<char> {
+        None
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        ModifiersState::empty()
+    }
+
+    fn set_hit_test_regions(&self, _regions: &[HitTestRegion]) {}
+
     fn focus_window(&self) {}
 
     fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
-    fn set_cursor(&self, _: Cursor) {}
+    fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
+    }
+
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
 
     fn set_cursor_position(&self, _: Position) -> Result<(), RequestError> {
         Err(NotSupportedError::new("set_cursor_position is not supported").into())
     }
 
+    fn animate_cursor_to(&self, _: PhysicalPosition<i32>, _: Duration) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("animate_cursor_to is not supported").into())
+    }
+
     fn set_cursor_grab(&self, _: CursorGrabMode) -> Result<(), RequestError> {
         Err(NotSupportedError::new("set_cursor_grab is not supported").into())
     }
 
     fn set_cursor_visible(&self, _: bool) {}
 
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        InputStateSnapshot {
+            cursor: self.current_cursor.get(),
+            ime_allowed: self.ime_allowed.load(Ordering::Relaxed),
+            ..Default::default()
+        }
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         Err(NotSupportedError::new("drag_window is not supported").into())
     }
@@ -1020,6 +1121,14 @@ impl MonitorHandle {
         unreachable!()
     }
 
+    pub fn bit_depth(&self) -> Option<u32> {
+        unreachable!()
+    }
+
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        unreachable!()
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         unreachable!()
     }
@@ -1037,6 +1146,24 @@ fn screen_size(app: &AndroidApp) -> PhysicalSize<u32> {
     }
 }
 
+/// The insets of the content rectangle, which excludes the system bars and the display cutouts,
+/// in the native window.
+fn safe_area(app: &AndroidApp) -> PhysicalInsets<u32> {
+    let size = screen_size(app);
+    let rect = app.content_rect();
+    let inset = |inset: i32| inset.max(0) as u32;
+    // The content rectangle is empty until the activity has been laid out.
+    if size.width == 0 || rect.right <= rect.left || rect.bottom <= rect.top {
+        return PhysicalInsets::new(0, 0, 0, 0);
+    }
+    PhysicalInsets::new(
+        inset(rect.top),
+        inset(rect.left),
+        inset(size.height as i32 - rect.bottom),
+        inset(size.width as i32 - rect.right),
+    )
+}
+
 fn scale_factor(app: &AndroidApp) -> f64 {
     app.config().density().map(|dpi| dpi as f64 / 160.0).unwrap_or(1.0)
 }
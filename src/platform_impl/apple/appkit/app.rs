@@ -1,28 +1,243 @@
 #![allow(clippy::unnecessary_cast)]
 
-use std::cell::Cell;
-use std::mem;
+use std::cell::{Cell, RefCell};
+use std::ptr::NonNull;
 use std::rc::Rc;
+use std::{mem, ptr};
 
 use dispatch2::MainThreadBound;
-use objc2::runtime::{Imp, Sel};
-use objc2::sel;
-use objc2_app_kit::{NSApplication, NSEvent, NSEventModifierFlags, NSEventType};
-use objc2_foundation::MainThreadMarker;
+use objc2::rc::Retained;
+use objc2::runtime::{AnyClass, Imp, Sel};
+use objc2::{define_class, msg_send, sel, ClassType};
+use objc2_app_kit::{
+    NSApplication, NSEvent, NSEventSubtype, NSEventType, NSPointingDeviceType, NSResponder,
+    NSScreen, NSWindow,
+};
+use objc2_foundation::{MainThreadMarker, NSObject, NSPoint};
 
 use super::app_state::AppState;
-use crate::event::{DeviceEvent, ElementState};
+use super::event::{
+    command_pressed, create_media_key_event, modifier_key_pressed, scancode_to_physicalkey,
+    scroll_delta,
+};
+use super::window::{window_id, WinitPanel, WinitWindow};
+use crate::error::{EventLoopError, NotSupportedError};
+use crate::event::{
+    DeviceEvent, DeviceId, ElementState, KeyEvent, RawKeyEvent, TabletTool, WindowEvent,
+};
+use crate::platform::macos::SendEventHook;
 
 type SendEvent = extern "C-unwind" fn(&NSApplication, Sel, &NSEvent);
 
-static ORIGINAL: MainThreadBound<Cell<Option<SendEvent>>> = {
+/// The original `sendEvent:` implementations, keyed by the class they were overridden on.
+static ORIGINALS: MainThreadBound<RefCell<Vec<(&'static AnyClass, SendEvent)>>> = {
+    // SAFETY: Creating in a `const` context, where there is no concept of the main thread.
+    MainThreadBound::new(RefCell::new(Vec::new()), unsafe { MainThreadMarker::new_unchecked() })
+};
+
+/// The event currently being passed to an original implementation, and the class it belongs to.
+static DISPATCHING: MainThreadBound<Cell<Option<(*const NSEvent, &'static AnyClass)>>> = {
     // SAFETY: Creating in a `const` context, where there is no concept of the main thread.
     MainThreadBound::new(Cell::new(None), unsafe { MainThreadMarker::new_unchecked() })
 };
 
+/// The hook called with every event before Winit handles it, see `set_send_event_hook`.
+static SEND_EVENT_HOOK: MainThreadBound<RefCell<Option<SendEventHook>>> = {
+    // SAFETY: Creating in a `const` context, where there is no concept of the main thread.
+    MainThreadBound::new(RefCell::new(None), unsafe { MainThreadMarker::new_unchecked() })
+};
+
+/// Whether the hook was set while it was being called, in which case it isn't put back.
+static SEND_EVENT_HOOK_REPLACED: MainThreadBound<Cell<bool>> = {
+    // SAFETY: Creating in a `const` context, where there is no concept of the main thread.
+    MainThreadBound::new(Cell::new(false), unsafe { MainThreadMarker::new_unchecked() })
+};
+
+/// The screen location of the last tablet motion event, used to compute motion deltas when the
+/// event doesn't carry any.
+static LAST_POINTER_LOCATION: MainThreadBound<Cell<Option<NSPoint>>> = {
+    // SAFETY: Creating in a `const` context, where there is no concept of the main thread.
+    MainThreadBound::new(Cell::new(None), unsafe { MainThreadMarker::new_unchecked() })
+};
+
+/// Find the original implementation for `class`, or the closest of its superclasses.
+fn original_for(
+    mtm: MainThreadMarker,
+    mut class: Option<&'static AnyClass>,
+) -> Option<(&'static AnyClass, SendEvent)> {
+    let originals = ORIGINALS.get(mtm).borrow();
+    while let Some(current) = class {
+        if let Some(&(_, original)) = originals.iter().find(|(cls, _)| ptr::eq(*cls, current)) {
+            return Some((current, original));
+        }
+        class = current.superclass();
+    }
+    None
+}
+
+pub(crate) fn set_send_event_hook(mtm: MainThreadMarker, hook: Option<SendEventHook>) {
+    *SEND_EVENT_HOOK.get(mtm).borrow_mut() = hook;
+    SEND_EVENT_HOOK_REPLACED.get(mtm).set(true);
+}
+
+/// Call the hook with `event`, returning whether it consumed the event.
+///
+/// The hook is taken out while it runs, so the events it causes to be sent, e.g. by running a
+/// nested event loop, don't reach it.
+fn call_send_event_hook(mtm: MainThreadMarker, event: &NSEvent) -> bool {
+    let Some(mut hook) = SEND_EVENT_HOOK.get(mtm).take() else {
+        return false;
+    };
+
+    let replaced = SEND_EVENT_HOOK_REPLACED.get(mtm);
+    replaced.set(false);
+    let consumed = hook(NonNull::from(event).cast());
+    if !replaced.get() {
+        *SEND_EVENT_HOOK.get(mtm).borrow_mut() = Some(hook);
+    }
+
+    consumed
+}
+
 extern "C-unwind" fn send_event(app: &NSApplication, sel: Sel, event: &NSEvent) {
     let mtm = MainThreadMarker::from(app);
 
+    // When both a class and its superclass are overridden, the original of the former calling
+    // `[super sendEvent:]` re-enters here with the same event, in which case we continue with the
+    // original of the superclass instead of dispatching the event again.
+    let dispatching = DISPATCHING.get(mtm);
+    let nested = dispatching.get().filter(|(dispatched, _)| ptr::eq(*dispatched, event));
+
+    // The hook sees each event once, before anything else.
+    if nested.is_none() && call_send_event_hook(mtm, event) {
+        return;
+    }
+    let start = match nested {
+        Some((_, class)) => class.superclass(),
+        None => Some(app.class()),
+    };
+    let (class, original) = original_for(mtm, start).expect("no existing sendEvent: handler set");
+
+    let dispatch_to_windows = |event: &NSEvent| {
+        let previous = dispatching.replace(Some((event as *const NSEvent, class)));
+        original(app, sel, event);
+        dispatching.set(previous);
+    };
+
+    if nested.is_some() {
+        // The device events were already dispatched by the outer call.
+        dispatch_to_windows(event);
+        return;
+    }
+
+    handle_event(mtm, app, event, dispatch_to_windows);
+}
+
+/// Winit's handling of an event sent to the application, where `dispatch_to_windows` is the
+/// `sendEvent:` implementation Winit overrode.
+fn handle_event(
+    mtm: MainThreadMarker,
+    app: &NSApplication,
+    event: &NSEvent,
+    dispatch_to_windows: impl FnOnce(&NSEvent),
+) {
+    // Events are generally scoped to the window level, so the best way
+    // to get device events is to listen for them on NSApplication.
+    let app_state = AppState::get(mtm);
+    dispatch_in_order(
+        app,
+        event,
+        |event| maybe_dispatch_device_event(mtm, &app_state, event),
+        |event| {
+            dispatch_to_windows(event);
+            maybe_dispatch_media_key(app, &app_state, event);
+        },
+    );
+}
+
+/// The media keys only generate a `SystemDefined` event on the application, which is delivered as
+/// a key event to the key window.
+fn maybe_dispatch_media_key(app: &NSApplication, app_state: &Rc<AppState>, event: &NSEvent) {
+    let Some(key_event) = create_media_key_event(event) else {
+        return;
+    };
+    let Some(window) = app.keyWindow() else {
+        return;
+    };
+    if !window.isKindOfClass(WinitWindow::class()) && !window.isKindOfClass(WinitPanel::class()) {
+        return;
+    }
+
+    let window_id = window_id(&window);
+    app_state.maybe_queue_with_handler(move |app, event_loop| {
+        app.window_event(event_loop, window_id, WindowEvent::KeyboardInput {
+            device_id: None,
+            event: key_event,
+            is_synthetic: false,
+        });
+    });
+}
+
+define_class!(
+    /// The application class installed with `with_app_subclass`, which overrides `sendEvent:`
+    /// the regular way instead of swizzling it.
+    #[unsafe(super(NSApplication, NSResponder, NSObject))]
+    #[name = "WinitApplication"]
+    struct WinitApplication;
+
+    impl WinitApplication {
+        #[unsafe(method(sendEvent:))]
+        fn send_event(&self, event: &NSEvent) {
+            let mtm = MainThreadMarker::from(self);
+            if call_send_event_hook(mtm, event) {
+                return;
+            }
+
+            handle_event(mtm, self, event, |event| {
+                let _: () = unsafe { msg_send![super(self), sendEvent: event] };
+            });
+        }
+    }
+);
+
+/// Create the shared application as an instance of Winit's subclass of [`NSApplication`].
+///
+/// This is the alternative to [`override_send_event`] for applications that can't swizzle
+/// methods of system classes. It only works if nothing created the shared application before,
+/// since its class can't be changed afterwards.
+pub(crate) fn shared_app_subclass(
+    mtm: MainThreadMarker,
+) -> Result<Retained<NSApplication>, EventLoopError> {
+    // `sharedApplication` creates the application with the class it is called on, if it doesn't
+    // exist yet.
+    let app: Retained<NSApplication> =
+        unsafe { msg_send![WinitApplication::class(), sharedApplication] };
+    if !app.isKindOfClass(WinitApplication::class()) {
+        return Err(EventLoopError::NotSupported(NotSupportedError::new(
+            "the shared `NSApplication` was created before the event loop, so Winit's subclass \
+             can't be used",
+        )));
+    }
+
+    debug_assert!(ptr::eq(&*app, &*NSApplication::sharedApplication(mtm)));
+    Ok(app)
+}
+
+/// Dispatch the device events of `event`, and then `event` itself to the windows, which produces
+/// the window events.
+///
+/// Both go through [`AppState::maybe_queue_with_handler`]. The handler can't become available or
+/// busy while the event is being dispatched, so either both are delivered right away, or both are
+/// queued in order: the device events of an `NSEvent` are always delivered before its window
+/// events. The only exception is the pointer motion held back by `with_pointer_motion_coalescing`.
+fn dispatch_in_order(
+    app: &NSApplication,
+    event: &NSEvent,
+    dispatch_device_events: impl FnOnce(&NSEvent),
+    dispatch_to_windows: impl FnOnce(&NSEvent),
+) {
+    dispatch_device_events(event);
+
     // Normally, holding Cmd + any key never sends us a `keyUp` event for that key.
     // Overriding `sendEvent:` fixes that. (https://stackoverflow.com/a/15294196)
     // Fun fact: Firefox still has this bug! (https://bugzilla.mozilla.org/show_bug.cgi?id=1299553)
@@ -30,29 +245,53 @@ extern "C-unwind" fn send_event(app: &NSApplication, sel: Sel, event: &NSEvent)
     // For posterity, there are some undocumented event types
     // (https://github.com/servo/cocoa-rs/issues/155)
     // but that doesn't really matter here.
+    //
+    // Both Command keys set the device-independent Command flag, but when the right one is
+    // remapped to act as AltGr only its device-dependent bit remains, which used to lose the
+    // `keyUp` of keys typed with it. `command_pressed` checks both.
+    //
+    // These are the flags of the actual Command keys, so this still applies when the reported
+    // modifiers have Command and Control swapped with `with_swap_command_control`.
     let event_type = unsafe { event.r#type() };
-    let modifier_flags = unsafe { event.modifierFlags() };
-    if event_type == NSEventType::KeyUp && modifier_flags.contains(NSEventModifierFlags::Command) {
-        if let Some(key_window) = app.keyWindow() {
-            key_window.sendEvent(event);
+    if event_type == NSEventType::KeyUp && command_pressed(event) {
+        if let Some(window) = key_up_window(app, event) {
+            window.sendEvent(event);
         }
         return;
     }
 
-    // Events are generally scoped to the window level, so the best way
-    // to get device events is to listen for them on NSApplication.
-    let app_state = AppState::get(mtm);
-    maybe_dispatch_device_event(&app_state, event);
+    dispatch_to_windows(event);
+}
 
-    let original = ORIGINAL.get(mtm).get().expect("no existing sendEvent: handler set");
-    original(app, sel, event)
+/// The window to send a `keyUp` event to directly.
+///
+/// This is the window the event targets, which differs from the key window when the key window
+/// changed while the key was held, e.g. after a Cmd+` window switch. Events without a window go
+/// to the key window.
+fn key_up_window(app: &NSApplication, event: &NSEvent) -> Option<Retained<NSWindow>> {
+    let mtm = MainThreadMarker::from(app);
+    unsafe { event.window(mtm) }.or_else(|| app.keyWindow())
+}
+
+/// The class that defines the method that `class` uses for `sel`.
+fn defining_class(class: &'static AnyClass, sel: Sel) -> &'static AnyClass {
+    let method = class.instance_method(sel);
+    let mut class = class;
+    while let Some(superclass) = class.superclass() {
+        match (superclass.instance_method(sel), method) {
+            (Some(inherited), Some(method)) if ptr::eq(inherited, method) => class = superclass,
+            _ => break,
+        }
+    }
+    class
 }
 
 /// Override the [`sendEvent:`][NSApplication::sendEvent] method on the given application class.
 ///
 /// The previous implementation created a subclass of [`NSApplication`], however we would like to
 /// give the user full control over their `NSApplication`, so we override the method here using
-/// method swizzling instead.
+/// method swizzling instead. The subclass remains available with `with_app_subclass`, for
+/// applications that can't swizzle, see [`shared_app_subclass`].
 ///
 /// This _should_ also allow two versions of Winit to exist in the same application.
 ///
@@ -66,7 +305,14 @@ extern "C-unwind" fn send_event(app: &NSApplication, sel: Sel, event: &NSEvent)
 /// For testing though, we allow it to be a different object.
 pub(crate) fn override_send_event(global_app: &NSApplication) {
     let mtm = MainThreadMarker::from(global_app);
-    let class = global_app.class();
+    // If we've already overridden the method, don't do anything.
+    if is_send_event_overridden(global_app) {
+        return;
+    }
+
+    // Override the method on the class that defines it, so that the original is keyed by the
+    // class whose implementation it is.
+    let class = defining_class(global_app.class(), sel!(sendEvent:));
 
     let method =
         class.instance_method(sel!(sendEvent:)).expect("NSApplication must have sendEvent: method");
@@ -74,13 +320,6 @@ pub(crate) fn override_send_event(global_app: &NSApplication) {
     // SAFETY: Converting our `sendEvent:` implementation to an IMP.
     let overridden = unsafe { mem::transmute::<SendEvent, Imp>(send_event) };
 
-    // If we've already overridden the method, don't do anything.
-    // FIXME(madsmtm): Use `std::ptr::fn_addr_eq` (Rust 1.85) once available in MSRV.
-    #[allow(unknown_lints, unpredictable_function_pointer_comparisons)]
-    if overridden == method.implementation() {
-        return;
-    }
-
     // SAFETY: Our implementation has:
     // 1. The same signature as `sendEvent:`.
     // 2. Does not impose extra safety requirements on callers.
@@ -94,56 +333,252 @@ pub(crate) fn override_send_event(global_app: &NSApplication) {
     // stored here.
     //
     // It is only usable from the main thread, however, so we're good!
-    ORIGINAL.get(mtm).set(Some(original));
+    ORIGINALS.get(mtm).borrow_mut().push((class, original));
+}
+
+/// Whether [`override_send_event`] has replaced the `sendEvent:` method of the given application.
+// FIXME(madsmtm): Use `std::ptr::fn_addr_eq` (Rust 1.85) once available in MSRV.
+#[allow(unknown_lints, unpredictable_function_pointer_comparisons)]
+pub(crate) fn is_send_event_overridden(app: &NSApplication) -> bool {
+    let Some(method) = app.class().instance_method(sel!(sendEvent:)) else {
+        return false;
+    };
+
+    // SAFETY: Converting our `sendEvent:` implementation to an IMP.
+    let overridden = unsafe { mem::transmute::<SendEvent, Imp>(send_event) };
+    let subclassed = WinitApplication::class()
+        .instance_method(sel!(sendEvent:))
+        .expect("WinitApplication must have sendEvent: method")
+        .implementation();
+    overridden == method.implementation() || subclassed == method.implementation()
+}
+
+/// The location of a mouse event in screen coordinates.
+fn screen_location(mtm: MainThreadMarker, event: &NSEvent) -> NSPoint {
+    let location = unsafe { event.locationInWindow() };
+    match unsafe { event.window(mtm) } {
+        Some(window) => window.convertPointToScreen(location),
+        // Events without a window are already in screen coordinates.
+        None => location,
+    }
 }
 
-fn maybe_dispatch_device_event(app_state: &Rc<AppState>, event: &NSEvent) {
+fn maybe_dispatch_device_event(mtm: MainThreadMarker, app_state: &Rc<AppState>, event: &NSEvent) {
     let event_type = unsafe { event.r#type() };
+    let is_motion = matches!(
+        event_type,
+        NSEventType::MouseMoved
+            | NSEventType::LeftMouseDragged
+            | NSEventType::OtherMouseDragged
+            | NSEventType::RightMouseDragged
+    );
+    if !is_motion {
+        // Keep the coalesced motion ordered before the other device events.
+        app_state.flush_pointer_motion();
+    }
+
     #[allow(non_upper_case_globals)]
     match event_type {
         NSEventType::MouseMoved
         | NSEventType::LeftMouseDragged
         | NSEventType::OtherMouseDragged
         | NSEventType::RightMouseDragged => {
-            let delta_x = unsafe { event.deltaX() } as f64;
-            let delta_y = unsafe { event.deltaY() } as f64;
+            let device_id = mouse_device_id(event);
+            maybe_dispatch_tablet_event(app_state, event, device_id);
+
+            let mut delta_x = unsafe { event.deltaX() } as f64;
+            let mut delta_y = unsafe { event.deltaY() } as f64;
+
+            // Tablets in absolute mode move the cursor without reporting deltas, so fall back to
+            // the distance between their successive absolute positions. Other devices may also
+            // report no delta, e.g. after the cursor was warped, where the distance would be a
+            // jump rather than motion.
+            // NOTE: `subtype` may only be queried on mouse events.
+            let is_tablet = unsafe { event.subtype() } == NSEventSubtype::TabletPoint;
+            let location = is_tablet.then(|| screen_location(mtm, event));
+            let last_location = LAST_POINTER_LOCATION.get(mtm).replace(location);
+            if let (Some(location), Some(last_location)) = (location, last_location) {
+                if delta_x == 0.0 && delta_y == 0.0 {
+                    delta_x = location.x - last_location.x;
+                    // Screen coordinates are flipped, while `deltaY` grows downwards.
+                    delta_y = last_location.y - location.y;
+                }
+            }
 
             if delta_x != 0.0 || delta_y != 0.0 {
-                app_state.maybe_queue_with_handler(move |app, event_loop| {
-                    app.device_event(event_loop, None, DeviceEvent::PointerMotion {
-                        delta: (delta_x, delta_y),
-                    });
-                });
+                app_state.queue_pointer_motion(device_id, (delta_x, delta_y));
             }
         },
         NSEventType::LeftMouseDown | NSEventType::RightMouseDown | NSEventType::OtherMouseDown => {
+            let device_id = mouse_device_id(event);
+            maybe_dispatch_tablet_event(app_state, event, device_id);
+
+            // `buttonNumber` is 0 for left, 1 for right and 2 for middle, followed by the other
+            // buttons, which is the numbering documented on `DeviceEvent::Button`. The window
+            // events map it with `MouseButton::from_device_button` too.
             let button = unsafe { event.buttonNumber() } as u32;
             app_state.maybe_queue_with_handler(move |app, event_loop| {
-                app.device_event(event_loop, None, DeviceEvent::Button {
+                app.device_event(event_loop, device_id, DeviceEvent::Button {
                     button,
                     state: ElementState::Pressed,
                 });
             });
         },
         NSEventType::LeftMouseUp | NSEventType::RightMouseUp | NSEventType::OtherMouseUp => {
+            let device_id = mouse_device_id(event);
+            maybe_dispatch_tablet_event(app_state, event, device_id);
+
             let button = unsafe { event.buttonNumber() } as u32;
             app_state.maybe_queue_with_handler(move |app, event_loop| {
-                app.device_event(event_loop, None, DeviceEvent::Button {
+                app.device_event(event_loop, device_id, DeviceEvent::Button {
                     button,
                     state: ElementState::Released,
                 });
             });
         },
+        NSEventType::ScrollWheel => {
+            // This handles the events sent to windows too, `scrollWheel:` only emits the window
+            // event. There may be no window to take the scale factor from, so the main screen is
+            // used instead.
+            let (x, y) = unsafe { (event.scrollingDeltaX(), event.scrollingDeltaY()) };
+            if x != 0.0 || y != 0.0 {
+                let scale_factor = NSScreen::mainScreen(mtm)
+                    .map(|screen| screen.backingScaleFactor() as f64)
+                    .unwrap_or(1.0);
+                let delta = scroll_delta(event, scale_factor);
+                app_state.maybe_queue_with_handler(move |app, event_loop| {
+                    app.device_event(event_loop, None, DeviceEvent::MouseWheel { delta });
+                });
+            }
+        },
+        NSEventType::KeyDown | NSEventType::KeyUp => {
+            let physical_key = scancode_to_physicalkey(unsafe { event.keyCode() } as u32);
+            let (state, repeat) = if event_type == NSEventType::KeyDown {
+                (ElementState::Pressed, unsafe { event.isARepeat() })
+            } else {
+                (ElementState::Released, false)
+            };
+            app_state.maybe_queue_with_handler(move |app, event_loop| {
+                app.device_event(
+                    event_loop,
+                    None,
+                    DeviceEvent::Key(RawKeyEvent { physical_key, state, repeat }),
+                );
+            });
+        },
+        NSEventType::FlagsChanged => {
+            // Modifier keys don't generate `KeyDown`/`KeyUp`, whether the key went down or up has
+            // to be read from its device-dependent flag instead.
+            let physical_key = scancode_to_physicalkey(unsafe { event.keyCode() } as u32);
+            let Some(pressed) = modifier_key_pressed(event, physical_key) else {
+                return;
+            };
+            let state = if pressed { ElementState::Pressed } else { ElementState::Released };
+            app_state.maybe_queue_with_handler(move |app, event_loop| {
+                app.device_event(
+                    event_loop,
+                    None,
+                    DeviceEvent::Key(RawKeyEvent { physical_key, state, repeat: false }),
+                );
+            });
+        },
+        NSEventType::SystemDefined => {
+            let Some(KeyEvent { physical_key, state, repeat, .. }) = create_media_key_event(event)
+            else {
+                return;
+            };
+            app_state.maybe_queue_with_handler(move |app, event_loop| {
+                app.device_event(
+                    event_loop,
+                    None,
+                    DeviceEvent::Key(RawKeyEvent { physical_key, state, repeat }),
+                );
+            });
+        },
+        NSEventType::TabletPoint => {
+            dispatch_tablet_point(app_state, event, tablet_device_id(event))
+        },
+        NSEventType::TabletProximity => {
+            dispatch_tablet_proximity(app_state, event, tablet_device_id(event))
+        },
+        // No motion is seen while the pointer is outside of our windows, so the next position may
+        // be anywhere, which must not be reported as a single jump.
+        NSEventType::MouseEntered | NSEventType::MouseExited => {
+            LAST_POINTER_LOCATION.get(mtm).set(None);
+        },
         _ => (),
     }
 }
 
+/// The device that generated a mouse event.
+///
+/// AppKit only identifies the device of tablet events, regular mice and trackpads are all
+/// reported as one and the same pointer, so this is `None` for them.
+fn mouse_device_id(event: &NSEvent) -> Option<DeviceId> {
+    // NOTE: `subtype` may only be queried on mouse events.
+    match unsafe { event.subtype() } {
+        NSEventSubtype::TabletPoint | NSEventSubtype::TabletProximity => tablet_device_id(event),
+        _ => None,
+    }
+}
+
+/// The device of a tablet event, as assigned by the tablet driver.
+fn tablet_device_id(event: &NSEvent) -> Option<DeviceId> {
+    Some(DeviceId::from_raw(unsafe { event.deviceID() } as i64))
+}
+
+/// Mouse events generated by a tablet carry the tablet data, as indicated by their subtype.
+fn maybe_dispatch_tablet_event(
+    app_state: &Rc<AppState>,
+    event: &NSEvent,
+    device_id: Option<DeviceId>,
+) {
+    // NOTE: `subtype` may only be queried on mouse events.
+    match unsafe { event.subtype() } {
+        NSEventSubtype::TabletPoint => dispatch_tablet_point(app_state, event, device_id),
+        NSEventSubtype::TabletProximity => dispatch_tablet_proximity(app_state, event, device_id),
+        _ => (),
+    }
+}
+
+fn dispatch_tablet_point(app_state: &Rc<AppState>, event: &NSEvent, device_id: Option<DeviceId>) {
+    let pressure = unsafe { event.pressure() };
+    let tilt = unsafe { event.tilt() };
+    let tilt = (tilt.x as f64, tilt.y as f64);
+    app_state.maybe_queue_with_handler(move |app, event_loop| {
+        app.device_event(event_loop, device_id, DeviceEvent::TabletPoint { pressure, tilt });
+    });
+}
+
+fn dispatch_tablet_proximity(
+    app_state: &Rc<AppState>,
+    event: &NSEvent,
+    device_id: Option<DeviceId>,
+) {
+    let tool = tablet_tool(unsafe { event.pointingDeviceType() });
+    let event = if unsafe { event.isEnteringProximity() } {
+        DeviceEvent::TabletProximityEntered { tool }
+    } else {
+        DeviceEvent::TabletProximityLeft { tool }
+    };
+    app_state.maybe_queue_with_handler(move |app, event_loop| {
+        app.device_event(event_loop, device_id, event);
+    });
+}
+
+fn tablet_tool(device_type: NSPointingDeviceType) -> TabletTool {
+    match device_type {
+        NSPointingDeviceType::Pen => TabletTool::Pen,
+        NSPointingDeviceType::Eraser => TabletTool::Eraser,
+        NSPointingDeviceType::Cursor => TabletTool::Cursor,
+        _ => TabletTool::Unknown,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use objc2::rc::Retained;
-    use objc2::{define_class, msg_send, ClassType};
-    use objc2_app_kit::NSResponder;
-    use objc2_foundation::NSObject;
+    use objc2_app_kit::{NSBackingStoreType, NSEventModifierFlags, NSWindowStyleMask};
+    use objc2_foundation::{NSRect, NSString};
 
     use super::*;
 
@@ -155,8 +590,10 @@ mod tests {
         // Create a new application, without making it the shared application.
         let app = unsafe { NSApplication::new(mtm) };
         override_send_event(&app);
+        assert!(is_send_event_overridden(&app));
         // Test calling twice works.
         override_send_event(&app);
+        assert!(is_send_event_overridden(&app));
 
         // FIXME(madsmtm): Can't test this yet, need some way to mock AppState.
         // unsafe {
@@ -165,9 +602,157 @@ mod tests {
         // }
     }
 
+    #[test]
+    fn device_events_before_window_events() {
+        let Some(mtm) = MainThreadMarker::new() else { return };
+        let app = unsafe { NSApplication::new(mtm) };
+
+        let order = RefCell::new(Vec::new());
+        let dispatch = |event: &NSEvent| {
+            order.borrow_mut().clear();
+            dispatch_in_order(
+                &app,
+                event,
+                |_| order.borrow_mut().push("device"),
+                |_| order.borrow_mut().push("window"),
+            );
+            order.borrow().clone()
+        };
+
+        let mouse_down = unsafe {
+            NSEvent::mouseEventWithType_location_modifierFlags_timestamp_windowNumber_context_eventNumber_clickCount_pressure(
+                NSEventType::LeftMouseDown,
+                NSPoint::new(0.0, 0.0),
+                NSEventModifierFlags(0),
+                0.0,
+                0,
+                None,
+                0,
+                1,
+                1.0,
+            )
+            .unwrap()
+        };
+        assert_eq!(dispatch(&mouse_down), ["device", "window"]);
+
+        // The `keyUp` of a key typed with Command is sent to the key window directly, but its
+        // device events are still dispatched first.
+        let key_up = unsafe {
+            NSEvent::keyEventWithType_location_modifierFlags_timestamp_windowNumber_context_characters_charactersIgnoringModifiers_isARepeat_keyCode(
+                NSEventType::KeyUp,
+                NSPoint::new(0.0, 0.0),
+                NSEventModifierFlags::Command,
+                0.0,
+                0,
+                None,
+                &NSString::from_str("a"),
+                &NSString::from_str("a"),
+                false,
+                0x00,
+            )
+            .unwrap()
+        };
+        assert_eq!(dispatch(&key_up), ["device"]);
+    }
+
+    #[test]
+    fn key_up_to_event_window() {
+        let Some(mtm) = MainThreadMarker::new() else { return };
+        let app = NSApplication::sharedApplication(mtm);
+
+        let window = || unsafe {
+            let window = NSWindow::initWithContentRect_styleMask_backing_defer(
+                NSWindow::alloc(mtm),
+                NSRect::ZERO,
+                NSWindowStyleMask::Titled,
+                NSBackingStoreType::Buffered,
+                false,
+            );
+            window.setReleasedWhenClosed(false);
+            window
+        };
+        let key_up = |window_number| unsafe {
+            NSEvent::keyEventWithType_location_modifierFlags_timestamp_windowNumber_context_characters_charactersIgnoringModifiers_isARepeat_keyCode(
+                NSEventType::KeyUp,
+                NSPoint::new(0.0, 0.0),
+                NSEventModifierFlags::Command,
+                0.0,
+                window_number,
+                None,
+                &NSString::from_str("a"),
+                &NSString::from_str("a"),
+                false,
+                0x00,
+            )
+            .unwrap()
+        };
+
+        // Each window gets its own events, regardless of which one is the key window.
+        let first = window();
+        let second = window();
+        for window in [&first, &second] {
+            let target = key_up_window(&app, &key_up(window.windowNumber()));
+            assert!(target.is_some_and(|target| ptr::eq(&*target, &**window)));
+        }
+
+        // Events without a window go to the key window.
+        let target = key_up_window(&app, &key_up(0));
+        assert_eq!(target.is_some(), app.keyWindow().is_some());
+
+        first.close();
+        second.close();
+    }
+
+    #[test]
+    fn app_subclass() {
+        let Some(mtm) = MainThreadMarker::new() else { return };
+
+        let app: Retained<NSApplication> = unsafe { msg_send![WinitApplication::class(), new] };
+        assert!(is_send_event_overridden(&app));
+        // Swizzling isn't needed on top of the subclass.
+        override_send_event(&app);
+        assert!(ORIGINALS
+            .get(mtm)
+            .borrow()
+            .iter()
+            .all(|(class, _)| { !ptr::eq(*class, WinitApplication::class()) }));
+    }
+
+    #[test]
+    fn send_event_hook() {
+        let Some(mtm) = MainThreadMarker::new() else { return };
+        let event = super::super::event::dummy_event().unwrap();
+
+        assert!(!call_send_event_hook(mtm, &event));
+
+        let calls = Rc::new(Cell::new(0));
+        let hook_calls = calls.clone();
+        set_send_event_hook(
+            mtm,
+            Some(Box::new(move |_| {
+                hook_calls.set(hook_calls.get() + 1);
+                true
+            })),
+        );
+        assert!(call_send_event_hook(mtm, &event));
+        assert!(call_send_event_hook(mtm, &event));
+        assert_eq!(calls.get(), 2);
+
+        // A hook removing itself while running stays removed.
+        set_send_event_hook(
+            mtm,
+            Some(Box::new(move |_| {
+                set_send_event_hook(MainThreadMarker::new().unwrap(), None);
+                false
+            })),
+        );
+        assert!(!call_send_event_hook(mtm, &event));
+        assert!(SEND_EVENT_HOOK.get(mtm).borrow().is_none());
+    }
+
     #[test]
     fn test_custom_class() {
-        let Some(_mtm) = MainThreadMarker::new() else { return };
+        let Some(mtm) = MainThreadMarker::new() else { return };
 
         define_class!(
             #[unsafe(super(NSApplication, NSResponder, NSObject))]
@@ -182,7 +767,43 @@ mod tests {
             }
         );
 
+        define_class!(
+            #[unsafe(super(NSApplication, NSResponder, NSObject))]
+            #[name = "OtherTestApplication"]
+            pub(super) struct OtherTestApplication;
+
+            impl OtherTestApplication {
+                #[unsafe(method(sendEvent:))]
+                fn send_event(&self, _event: &NSEvent) {
+                    unimplemented!()
+                }
+            }
+        );
+
+        let implementation =
+            |class: &AnyClass| class.instance_method(sel!(sendEvent:)).unwrap().implementation();
+        let test_original = implementation(TestApplication::class());
+        let other_original = implementation(OtherTestApplication::class());
+
         let app: Retained<TestApplication> = unsafe { msg_send![TestApplication::class(), new] };
+        assert!(!is_send_event_overridden(&app));
         override_send_event(&app);
+        assert!(is_send_event_overridden(&app));
+        let other: Retained<OtherTestApplication> =
+            unsafe { msg_send![OtherTestApplication::class(), new] };
+        override_send_event(&other);
+
+        // Each class dispatches to its own original.
+        let original = |class: &'static AnyClass| {
+            let (found, original) = original_for(mtm, Some(class)).unwrap();
+            assert!(ptr::eq(found, class));
+            // SAFETY: Converting back to the IMP it was created from.
+            unsafe { mem::transmute::<SendEvent, Imp>(original) }
+        };
+        #[allow(unknown_lints, unpredictable_function_pointer_comparisons)]
+        {
+            assert!(original(TestApplication::class()) == test_original);
+            assert!(original(OtherTestApplication::class()) == other_original);
+        }
     }
 }
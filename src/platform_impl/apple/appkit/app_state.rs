@@ -1,11 +1,12 @@
 use std::cell::{Cell, OnceCell, RefCell};
-use std::mem;
 use std::rc::{Rc, Weak};
 use std::sync::atomic::Ordering as AtomicOrdering;
 use std::sync::Arc;
 use std::time::Instant;
+use std::{mem, ptr};
 
 use dispatch2::MainThreadBound;
+use objc2::rc::Weak as ObjcWeak;
 use objc2::MainThreadMarker;
 use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSRunningApplication};
 use objc2_foundation::NSNotification;
@@ -14,9 +15,10 @@ use super::super::event_handler::EventHandler;
 use super::event_loop::{stop_app_immediately, ActiveEventLoop, EventLoopProxy, PanicInfo};
 use super::menu;
 use super::observer::{EventLoopWaker, RunLoop};
+use super::view::WinitView;
 use crate::application::ApplicationHandler;
-use crate::event::{StartCause, WindowEvent};
-use crate::event_loop::ControlFlow;
+use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
+use crate::event_loop::{CommonEventLoopState, ControlFlow};
 use crate::window::WindowId;
 
 #[derive(Debug)]
@@ -25,6 +27,8 @@ pub(super) struct AppState {
     activation_policy: Option<NSApplicationActivationPolicy>,
     default_menu: bool,
     activate_ignoring_other_apps: bool,
+    swap_command_control: bool,
+    coalesce_pointer_motion: bool,
     run_loop: RunLoop,
     event_loop_proxy: Arc<EventLoopProxy>,
     event_handler: EventHandler,
@@ -43,6 +47,9 @@ pub(super) struct AppState {
     start_time: Cell<Option<Instant>>,
     wait_timeout: Cell<Option<Instant>>,
     pending_redraw: RefCell<Vec<WindowId>>,
+    pending_pointer_motion: RefCell<PointerMotionAccumulator>,
+    cursor_animations: RefCell<Vec<ObjcWeak<WinitView>>>,
+    common_state: CommonEventLoopState,
     // NOTE: This is strongly referenced by our `NSWindowDelegate` and our `NSView` subclass, and
     // as such should be careful to not add fields that, in turn, strongly reference those.
 }
@@ -58,6 +65,8 @@ impl AppState {
         activation_policy: Option<NSApplicationActivationPolicy>,
         default_menu: bool,
         activate_ignoring_other_apps: bool,
+        swap_command_control: bool,
+        coalesce_pointer_motion: bool,
     ) -> Rc<Self> {
         let this = Rc::new(AppState {
             mtm,
@@ -65,6 +74,8 @@ impl AppState {
             event_loop_proxy: Arc::new(EventLoopProxy::new()),
             default_menu,
             activate_ignoring_other_apps,
+            swap_command_control,
+            coalesce_pointer_motion,
             run_loop: RunLoop::main(mtm),
             event_handler: EventHandler::new(),
             stop_on_launch: Cell::new(false),
@@ -79,12 +90,19 @@ impl AppState {
             start_time: Cell::new(None),
             wait_timeout: Cell::new(None),
             pending_redraw: RefCell::new(vec![]),
+            pending_pointer_motion: RefCell::new(PointerMotionAccumulator::default()),
+            cursor_animations: RefCell::new(vec![]),
+            common_state: Default::default(),
         });
 
         GLOBAL.get(mtm).set(this.clone()).expect("application state can only be set once");
         this
     }
 
+    pub(super) fn common_state(&self) -> &CommonEventLoopState {
+        &self.common_state
+    }
+
     pub fn get(mtm: MainThreadMarker) -> Rc<Self> {
         GLOBAL
             .get(mtm)
@@ -93,6 +111,11 @@ impl AppState {
             .clone()
     }
 
+    /// Whether the Command and Control modifiers are swapped in the reported modifiers.
+    pub fn swap_command_control(&self) -> bool {
+        self.swap_command_control
+    }
+
     // NOTE: This notification will, globally, only be emitted once,
     // no matter how many `EventLoop`s the user creates.
     pub fn did_finish_launching(self: &Rc<Self>, _notification: &NSNotification) {
@@ -267,6 +290,62 @@ impl AppState {
         self.run_loop.wakeup();
     }
 
+    /// Step the cursor animation of `view` whenever the event loop is about to wait, until it
+    /// finishes.
+    pub fn queue_cursor_animation(&self, view: &WinitView) {
+        let mut views = self.cursor_animations.borrow_mut();
+        if !views.iter().any(|other| other.load().is_some_and(|other| ptr::eq(&*other, view))) {
+            views.push(ObjcWeak::new(view));
+        }
+        self.run_loop.wakeup();
+    }
+
+    /// Advance the cursor animations, returning when the next step is due.
+    fn step_cursor_animations(&self, now: Instant) -> Option<Instant> {
+        let mut next_step = None;
+        self.cursor_animations.borrow_mut().retain(|view| {
+            match view.load().and_then(|view| view.step_cursor_animation(now)) {
+                Some(deadline) => {
+                    next_step = min_timeout(next_step, Some(deadline));
+                    true
+                },
+                None => false,
+            }
+        });
+        next_step
+    }
+
+    /// Deliver a `DeviceEvent::PointerMotion`, accumulated with the other motion of the device
+    /// until the event loop is about to wait if `with_pointer_motion_coalescing` is enabled.
+    pub fn queue_pointer_motion(self: &Rc<Self>, device_id: Option<DeviceId>, delta: (f64, f64)) {
+        if !self.coalesce_pointer_motion {
+            self.dispatch_pointer_motion(device_id, delta);
+            return;
+        }
+
+        // Release the borrow before calling the handler.
+        let previous = self.pending_pointer_motion.borrow_mut().push(device_id, delta);
+        if let Some((device_id, delta)) = previous {
+            self.dispatch_pointer_motion(device_id, delta);
+        }
+    }
+
+    /// Deliver the motion accumulated by `queue_pointer_motion`, if any.
+    ///
+    /// This must be called before any other device event, to preserve their order.
+    pub fn flush_pointer_motion(self: &Rc<Self>) {
+        let pending = self.pending_pointer_motion.borrow_mut().take();
+        if let Some((device_id, delta)) = pending {
+            self.dispatch_pointer_motion(device_id, delta);
+        }
+    }
+
+    fn dispatch_pointer_motion(self: &Rc<Self>, device_id: Option<DeviceId>, delta: (f64, f64)) {
+        self.maybe_queue_with_handler(move |app, event_loop| {
+            app.device_event(event_loop, device_id, DeviceEvent::PointerMotion { delta });
+        });
+    }
+
     #[track_caller]
     pub fn maybe_queue_with_handler(
         self: &Rc<Self>,
@@ -344,10 +423,20 @@ impl AppState {
             .upgrade()
             .expect("The panic info must exist here. This failure indicates a developer error.");
 
+        if panic_info.is_panicking() {
+            return;
+        }
+
+        // Deliver the coalesced motion even if the handler is in use, in which case it is queued
+        // like the other events, rather than holding it back for another iteration.
+        if self.is_running() {
+            self.flush_pointer_motion();
+        }
+
         // Return when in event handler due to https://github.com/rust-windowing/winit/issues/1779
         // XXX: how does it make sense that `event_handler.ready()` can ever return `false` here if
         // we're about to return to the `CFRunLoop` to poll for new events?
-        if panic_info.is_panicking() || !self.event_handler.ready() || !self.is_running() {
+        if !self.event_handler.ready() || !self.is_running() {
             return;
         }
 
@@ -381,7 +470,9 @@ impl AppState {
             ControlFlow::Poll => Some(Instant::now()),
             ControlFlow::WaitUntil(instant) => Some(instant),
         };
-        self.waker.borrow_mut().start_at(min_timeout(wait_timeout, app_timeout));
+        let timeout = min_timeout(wait_timeout, app_timeout);
+        let animation_timeout = self.step_cursor_animations(Instant::now());
+        self.waker.borrow_mut().start_at(min_timeout(timeout, animation_timeout));
     }
 }
 
@@ -391,3 +482,83 @@ impl AppState {
 fn min_timeout(a: Option<Instant>, b: Option<Instant>) -> Option<Instant> {
     a.map_or(b, |a_timeout| b.map_or(Some(a_timeout), |b_timeout| Some(a_timeout.min(b_timeout))))
 }
+
+/// The motion of a device, as the device and the sum of its deltas.
+type PointerMotion = (Option<DeviceId>, (f64, f64));
+
+/// The `DeviceEvent::PointerMotion` accumulated since it was last delivered.
+///
+/// High polling rate mice report a motion event per millisecond, each of which needs a closure to
+/// be allocated when it has to be queued. Accumulating the deltas instead allocates nothing per
+/// event, and calls the handler once per device and iteration of the event loop, see
+/// `pointer_motion_dispatches_per_second` for the reduction.
+#[derive(Debug, Default)]
+struct PointerMotionAccumulator {
+    pending: Option<PointerMotion>,
+}
+
+impl PointerMotionAccumulator {
+    /// Add the motion of `device_id`, returning the motion of another device accumulated so far,
+    /// which has to be delivered first.
+    fn push(&mut self, device_id: Option<DeviceId>, delta: (f64, f64)) -> Option<PointerMotion> {
+        match self.pending.replace((device_id, delta)) {
+            Some((pending_id, pending)) if pending_id == device_id => {
+                self.pending = Some((device_id, (pending.0 + delta.0, pending.1 + delta.1)));
+                None
+            },
+            previous => previous,
+        }
+    }
+
+    /// Take the motion accumulated so far.
+    fn take(&mut self) -> Option<PointerMotion> {
+        self.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointer_motion_accumulates_per_device() {
+        let mouse = None;
+        let tablet = Some(DeviceId::from_raw(7));
+        let mut accumulator = PointerMotionAccumulator::default();
+        assert_eq!(accumulator.take(), None);
+
+        assert_eq!(accumulator.push(mouse, (1.0, 2.0)), None);
+        assert_eq!(accumulator.push(mouse, (3.0, -1.0)), None);
+        // The motion of another device delivers the one accumulated so far.
+        assert_eq!(accumulator.push(tablet, (0.5, 0.5)), Some((mouse, (4.0, 1.0))));
+        assert_eq!(accumulator.push(mouse, (1.0, 0.0)), Some((tablet, (0.5, 0.5))));
+
+        assert_eq!(accumulator.take(), Some((mouse, (1.0, 0.0))));
+        assert_eq!(accumulator.take(), None);
+    }
+
+    #[test]
+    fn pointer_motion_dispatches_per_second() {
+        // One second of a 1000Hz mouse, with the event loop waiting every 1/60th of a second.
+        let mut accumulator = PointerMotionAccumulator::default();
+        let mut dispatches = 0;
+        let mut delta = (0.0, 0.0);
+        for sample in 1..=1000 {
+            if let Some((_, previous)) = accumulator.push(None, (1.0, -1.0)) {
+                dispatches += 1;
+                delta = (delta.0 + previous.0, delta.1 + previous.1);
+            }
+            if sample * 60 % 1000 < 60 {
+                if let Some((_, pending)) = accumulator.take() {
+                    dispatches += 1;
+                    delta = (delta.0 + pending.0, delta.1 + pending.1);
+                }
+            }
+        }
+
+        // A handler call, and a closure when it is queued, per iteration instead of per sample,
+        // without losing any motion.
+        assert_eq!(dispatches, 60);
+        assert_eq!(delta, (1000.0, -1000.0));
+    }
+}
@@ -217,7 +217,7 @@ pub(crate) fn cursor_from_icon(icon: CursorIcon) -> Retained<NSCursor> {
         // This is the wrong semantics for `Wait`, but it's the same as
         // what's used in Safari and Chrome.
         CursorIcon::Wait | CursorIcon::Progress => busyButClickableCursor(),
-        CursorIcon::Move | CursorIcon::AllScroll => webkit_move(),
+        CursorIcon::Move | CursorIcon::AllScroll | CursorIcon::AllResize => webkit_move(),
         CursorIcon::Cell => webkit_cell(),
         _ => default_cursor(),
     }
@@ -1,74 +1,244 @@
 use std::ptr::NonNull;
+use std::time::Duration;
 
 use dispatch2::run_on_main;
 use objc2::rc::Retained;
 use objc2_app_kit::{NSEvent, NSEventModifierFlags, NSEventSubtype, NSEventType};
-use objc2_core_foundation::{CFData, CFDataGetBytePtr, CFRetained};
+use objc2_core_foundation::{
+    CFArray, CFArrayGetCount, CFArrayGetValueAtIndex, CFData, CFDataGetBytePtr, CFRetained,
+    CFString,
+};
 use objc2_foundation::NSPoint;
 use smol_str::SmolStr;
 
 use super::ffi;
-use crate::event::{ElementState, KeyEvent, Modifiers};
+use crate::dpi::LogicalPosition;
+use crate::event::{ElementState, KeyEvent, Modifiers, MouseScrollDelta};
 use crate::keyboard::{
     Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
-    PhysicalKey,
+    PhysicalKey, TextDirection,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyEventExtra {
     pub text_with_all_modifiers: Option<SmolStr>,
-    pub key_without_modifiers: Key,
+}
+
+impl KeyEventExtra {
+    #[cfg(any(feature = "test-util", feature = "serde"))]
+    pub(crate) fn synthetic(text: Option<&SmolStr>) -> Self {
+        Self { text_with_all_modifiers: text.cloned() }
+    }
+
+    /// Translates the key with the input source that is selected at the time of the call.
+    pub(crate) fn text_for_shift(&self, physical_key: PhysicalKey, shift: bool) -> Option<SmolStr> {
+        let scancode = physicalkey_to_scancode(physical_key)?;
+        get_char_for_shift(scancode as u16, shift)
+    }
 }
 
 /// Ignores ALL modifiers.
 pub fn get_modifierless_char(scancode: u16) -> Key {
-    let Some(ptr) = NonNull::new(unsafe { ffi::TISCopyCurrentKeyboardLayoutInputSource() }) else {
-        tracing::error!("`TISCopyCurrentKeyboardLayoutInputSource` returned null ptr");
-        return Key::Unidentified(NativeKey::MacOS(scancode));
-    };
+    match translate_scancode(scancode, 0) {
+        Some(chars) => Key::Character(chars),
+        None => Key::Unidentified(NativeKey::MacOS(scancode)),
+    }
+}
+
+/// `shiftKey` from `Events.h`, shifted down by 8 bits as `UCKeyTranslate` expects.
+const UC_SHIFT_KEY: u32 = 0x200 >> 8;
+
+/// Translates the scancode with the keyboard layout that is currently selected, optionally with
+/// <kbd>Shift</kbd> held.
+pub(crate) fn get_char_for_shift(scancode: u16, shift: bool) -> Option<SmolStr> {
+    translate_scancode(scancode, if shift { UC_SHIFT_KEY } else { 0 })
+}
+
+/// The identifier of the keyboard layout that is currently selected, e.g.
+/// `com.apple.keylayout.US`.
+pub(crate) fn keyboard_layout_name() -> Option<String> {
+    let ptr = NonNull::new(unsafe { ffi::TISCopyCurrentKeyboardLayoutInputSource() })?;
     let input_source = unsafe { CFRetained::from_raw(ptr) };
 
-    let layout_data = unsafe {
-        ffi::TISGetInputSourceProperty(&input_source, ffi::kTISPropertyUnicodeKeyLayoutData)
-    };
-    let Some(layout_data) = (unsafe { layout_data.cast::<CFData>().as_ref() }) else {
-        tracing::error!("`TISGetInputSourceProperty` returned null ptr");
-        return Key::Unidentified(NativeKey::MacOS(scancode));
+    let id =
+        unsafe { ffi::TISGetInputSourceProperty(&input_source, ffi::kTISPropertyInputSourceID) };
+    let id = unsafe { id.cast::<CFString>().as_ref() }?;
+    Some(id.to_string())
+}
+
+/// The direction of the text typed with the keyboard layout that is currently selected, from the
+/// first of its languages, which is the primary one.
+pub(crate) fn keyboard_layout_direction() -> Option<TextDirection> {
+    let ptr = NonNull::new(unsafe { ffi::TISCopyCurrentKeyboardLayoutInputSource() })?;
+    let input_source = unsafe { CFRetained::from_raw(ptr) };
+
+    let languages = unsafe {
+        ffi::TISGetInputSourceProperty(&input_source, ffi::kTISPropertyInputSourceLanguages)
     };
+    let languages = unsafe { languages.cast::<CFArray>().as_ref() }?;
+    if unsafe { CFArrayGetCount(languages) } == 0 {
+        return None;
+    }
+    let language = unsafe { CFArrayGetValueAtIndex(languages, 0) };
+    let language = unsafe { language.cast::<CFString>().as_ref() }?;
+    Some(TextDirection::from_language(&language.to_string()))
+}
 
-    let layout = unsafe { CFDataGetBytePtr(layout_data).cast() };
-    let keyboard_type = run_on_main(|_mtm| unsafe { ffi::LMGetKbdType() });
-
-    let mut result_len = 0;
-    let mut dead_keys = 0;
-    let modifiers = 0;
-    let mut string = [0; 16];
-    let translate_result = unsafe {
-        ffi::UCKeyTranslate(
-            layout,
-            scancode,
-            ffi::kUCKeyActionDisplay,
-            modifiers,
-            keyboard_type as u32,
-            ffi::kUCKeyTranslateNoDeadKeysMask,
-            &mut dead_keys,
-            string.len() as ffi::UniCharCount,
-            &mut result_len,
-            string.as_mut_ptr(),
-        )
+fn translate_scancode(scancode: u16, modifiers: u32) -> Option<SmolStr> {
+    let layout = KeyboardLayout::current()?;
+    let mut dead_key_state = 0;
+    layout.translate(
+        scancode,
+        ffi::kUCKeyActionDisplay,
+        modifiers,
+        ffi::kUCKeyTranslateNoDeadKeysMask,
+        &mut dead_key_state,
+    )
+}
+
+/// `optionKey` from `Events.h`, shifted down by 8 bits as `UCKeyTranslate` expects.
+const UC_OPTION_KEY: u32 = 0x800 >> 8;
+
+/// The character composed by the dead key and the base character in the selected input source,
+/// see [`Window::compose_dead_key`][crate::window::Window::compose_dead_key].
+pub(crate) fn compose_dead_key(dead: char, base: char) -> Option<char> {
+    const MODIFIERS: [u32; 4] = [0, UC_SHIFT_KEY, UC_OPTION_KEY, UC_SHIFT_KEY | UC_OPTION_KEY];
+    // The virtual key codes of the keys that produce text.
+    const SCANCODES: std::ops::Range<u16> = 0..0x80;
+
+    let layout = KeyboardLayout::current()?;
+    let is_char = |text: Option<SmolStr>, ch: char| {
+        text.is_some_and(|text| text.chars().eq(std::iter::once(ch)))
     };
-    if translate_result != 0 {
-        tracing::error!("`UCKeyTranslate` returned with the non-zero value: {}", translate_result);
-        return Key::Unidentified(NativeKey::MacOS(scancode));
+
+    // `Key::Dead` holds the character of the key with dead keys disabled, either with or without
+    // the modifiers depending on the layout, so accept both.
+    let mut dead_key_state = MODIFIERS.iter().find_map(|&modifiers| {
+        SCANCODES.clone().find_map(|scancode| {
+            let mut dead_key_state = 0;
+            let text = layout.translate(
+                scancode,
+                ffi::kUCKeyActionDown,
+                modifiers,
+                0,
+                &mut dead_key_state,
+            );
+            if text.is_some() || dead_key_state == 0 {
+                return None;
+            }
+            let matches = [modifiers, 0].iter().any(|&modifiers| {
+                let text = layout.translate(
+                    scancode,
+                    ffi::kUCKeyActionDisplay,
+                    modifiers,
+                    ffi::kUCKeyTranslateNoDeadKeysMask,
+                    &mut 0,
+                );
+                is_char(text, dead)
+            });
+            matches.then_some(dead_key_state)
+        })
+    })?;
+
+    let (base_scancode, base_modifiers) = MODIFIERS.iter().find_map(|&modifiers| {
+        SCANCODES.clone().find_map(|scancode| {
+            let text = layout.translate(
+                scancode,
+                ffi::kUCKeyActionDisplay,
+                modifiers,
+                ffi::kUCKeyTranslateNoDeadKeysMask,
+                &mut 0,
+            );
+            is_char(text, base).then_some((scancode, modifiers))
+        })
+    })?;
+
+    let text = layout.translate(
+        base_scancode,
+        ffi::kUCKeyActionDown,
+        base_modifiers,
+        0,
+        &mut dead_key_state,
+    )?;
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Some(ch),
+        _ => None,
     }
-    if result_len == 0 {
-        // This is fine - not all keys have text representation.
-        // For instance, users that have mapped the `Fn` key to toggle
-        // keyboard layouts will hit this code path.
-        return Key::Unidentified(NativeKey::MacOS(scancode));
+}
+
+/// The Unicode keyboard layout of the input source that is currently selected.
+struct KeyboardLayout {
+    /// Owns the layout data.
+    _input_source: CFRetained<ffi::TISInputSource>,
+    layout: *const ffi::UCKeyboardLayout,
+    keyboard_type: u32,
+}
+
+impl KeyboardLayout {
+    fn current() -> Option<Self> {
+        let Some(ptr) = NonNull::new(unsafe { ffi::TISCopyCurrentKeyboardLayoutInputSource() })
+        else {
+            tracing::error!("`TISCopyCurrentKeyboardLayoutInputSource` returned null ptr");
+            return None;
+        };
+        let input_source = unsafe { CFRetained::from_raw(ptr) };
+
+        let layout_data = unsafe {
+            ffi::TISGetInputSourceProperty(&input_source, ffi::kTISPropertyUnicodeKeyLayoutData)
+        };
+        let Some(layout_data) = (unsafe { layout_data.cast::<CFData>().as_ref() }) else {
+            tracing::error!("`TISGetInputSourceProperty` returned null ptr");
+            return None;
+        };
+
+        let layout = unsafe { CFDataGetBytePtr(layout_data).cast() };
+        let keyboard_type = run_on_main(|_mtm| unsafe { ffi::LMGetKbdType() }) as u32;
+        Some(Self { _input_source: input_source, layout, keyboard_type })
+    }
+
+    /// Translates the scancode, updating the dead key state. Returns `None` when the key doesn't
+    /// produce text, e.g. when it starts a dead key sequence.
+    fn translate(
+        &self,
+        scancode: u16,
+        action: u16,
+        modifiers: u32,
+        options: ffi::OptionBits,
+        dead_key_state: &mut u32,
+    ) -> Option<SmolStr> {
+        let mut result_len = 0;
+        let mut string = [0; 16];
+        let translate_result = unsafe {
+            ffi::UCKeyTranslate(
+                self.layout,
+                scancode,
+                action,
+                modifiers,
+                self.keyboard_type,
+                options,
+                dead_key_state,
+                string.len() as ffi::UniCharCount,
+                &mut result_len,
+                string.as_mut_ptr(),
+            )
+        };
+        if translate_result != 0 {
+            tracing::error!(
+                "`UCKeyTranslate` returned with the non-zero value: {}",
+                translate_result
+            );
+            return None;
+        }
+        if result_len == 0 {
+            // This is fine - not all keys have text representation.
+            // For instance, users that have mapped the `Fn` key to toggle
+            // keyboard layouts will hit this code path.
+            return None;
+        }
+        let chars = String::from_utf16_lossy(&string[0..result_len as usize]);
+        Some(SmolStr::new(chars))
     }
-    let chars = String::from_utf16_lossy(&string[0..result_len as usize]);
-    Key::Character(SmolStr::new(chars))
 }
 
 // Ignores all modifiers except for SHIFT (yes, even ALT is ignored).
@@ -153,11 +323,99 @@ pub(crate) fn create_key_event(ns_event: &NSEvent, is_press: bool, is_repeat: bo
     KeyEvent {
         location,
         logical_key,
+        key_without_modifiers,
         physical_key,
         repeat: is_repeat,
         state,
         text,
-        platform_specific: KeyEventExtra { text_with_all_modifiers, key_without_modifiers },
+        composed: false,
+        timestamp: event_timestamp(ns_event),
+        platform_specific: KeyEventExtra { text_with_all_modifiers },
+    }
+}
+
+// The subtype of the `SystemDefined` events of the media keys, and the values of their `data1`.
+const NX_SUBTYPE_AUX_CONTROL_BUTTONS: NSEventSubtype = NSEventSubtype(8);
+const NX_KEYTYPE_SOUND_UP: isize = 0;
+const NX_KEYTYPE_SOUND_DOWN: isize = 1;
+const NX_KEYTYPE_BRIGHTNESS_UP: isize = 2;
+const NX_KEYTYPE_BRIGHTNESS_DOWN: isize = 3;
+const NX_KEYTYPE_MUTE: isize = 7;
+const NX_KEYTYPE_EJECT: isize = 14;
+const NX_KEYTYPE_PLAY: isize = 16;
+const NX_KEYTYPE_NEXT: isize = 17;
+const NX_KEYTYPE_PREVIOUS: isize = 18;
+const NX_KEYTYPE_FAST: isize = 19;
+const NX_KEYTYPE_REWIND: isize = 20;
+const NX_KEYDOWN: isize = 0xa;
+
+/// The key event of a media key, such as the volume and playback keys of the function row.
+///
+/// AppKit doesn't send regular key events for these keys, but a `SystemDefined` event with the key
+/// and its state packed in `data1`. Returns `None` for the other events.
+pub(crate) fn create_media_key_event(ns_event: &NSEvent) -> Option<KeyEvent> {
+    // NOTE: `subtype` and `data1` may only be queried on `SystemDefined` events here.
+    if unsafe { ns_event.r#type() } != NSEventType::SystemDefined
+        || unsafe { ns_event.subtype() } != NX_SUBTYPE_AUX_CONTROL_BUTTONS
+    {
+        return None;
+    }
+
+    let data = unsafe { ns_event.data1() };
+    let (code, key) = match (data >> 16) & 0xffff {
+        NX_KEYTYPE_SOUND_UP => (Some(KeyCode::AudioVolumeUp), NamedKey::AudioVolumeUp),
+        NX_KEYTYPE_SOUND_DOWN => (Some(KeyCode::AudioVolumeDown), NamedKey::AudioVolumeDown),
+        NX_KEYTYPE_MUTE => (Some(KeyCode::AudioVolumeMute), NamedKey::AudioVolumeMute),
+        NX_KEYTYPE_BRIGHTNESS_UP => (None, NamedKey::BrightnessUp),
+        NX_KEYTYPE_BRIGHTNESS_DOWN => (None, NamedKey::BrightnessDown),
+        NX_KEYTYPE_EJECT => (Some(KeyCode::Eject), NamedKey::Eject),
+        NX_KEYTYPE_PLAY => (Some(KeyCode::MediaPlayPause), NamedKey::MediaPlayPause),
+        NX_KEYTYPE_NEXT => (Some(KeyCode::MediaTrackNext), NamedKey::MediaTrackNext),
+        NX_KEYTYPE_PREVIOUS => (Some(KeyCode::MediaTrackPrevious), NamedKey::MediaTrackPrevious),
+        NX_KEYTYPE_FAST => (None, NamedKey::MediaFastForward),
+        NX_KEYTYPE_REWIND => (None, NamedKey::MediaRewind),
+        // Caps Lock is already reported by `FlagsChanged`, the other keys are handled by the
+        // system.
+        _ => return None,
+    };
+    let state = if (data >> 8) & 0xff == NX_KEYDOWN {
+        ElementState::Pressed
+    } else {
+        ElementState::Released
+    };
+
+    Some(KeyEvent {
+        physical_key: code
+            .map_or(PhysicalKey::Unidentified(NativeKeyCode::Unidentified), PhysicalKey::Code),
+        logical_key: Key::Named(key),
+        key_without_modifiers: Key::Named(key),
+        text: None,
+        composed: false,
+        location: KeyLocation::Standard,
+        state,
+        repeat: data & 0x1 != 0,
+        timestamp: event_timestamp(ns_event),
+        platform_specific: KeyEventExtra { text_with_all_modifiers: None },
+    })
+}
+
+/// The time of the event since the system started, see `KeyEvent::timestamp`.
+pub(crate) fn event_timestamp(event: &NSEvent) -> Option<Duration> {
+    Some(Duration::from_secs_f64(unsafe { event.timestamp() }))
+}
+
+/// The key produced by the physical key without modifiers in the selected input source, like the
+/// `key_without_modifiers` of key events.
+pub(crate) fn key_for_physical(physical_key: PhysicalKey) -> Option<Key> {
+    let PhysicalKey::Code(_) = physical_key else { return None };
+    let scancode = physicalkey_to_scancode(physical_key)? as u16;
+    let key = match code_to_key(physical_key, scancode) {
+        Key::Unidentified(_) => get_modifierless_char(scancode),
+        key => key,
+    };
+    match key {
+        Key::Unidentified(_) => None,
+        key => Some(key),
     }
 }
 
@@ -181,13 +439,20 @@ pub fn code_to_key(key: PhysicalKey, scancode: u16) -> Key {
         KeyCode::ShiftRight => NamedKey::Shift,
         KeyCode::AltRight => NamedKey::Alt,
         KeyCode::ControlRight => NamedKey::Control,
+        KeyCode::Fn => NamedKey::Fn,
 
         KeyCode::NumLock => NamedKey::NumLock,
         KeyCode::AudioVolumeUp => NamedKey::AudioVolumeUp,
         KeyCode::AudioVolumeDown => NamedKey::AudioVolumeDown,
+        KeyCode::AudioVolumeMute => NamedKey::AudioVolumeMute,
 
-        // Other numpad keys all generate text on macOS (if I understand correctly)
+        // Other numpad keys all generate text on macOS (if I understand correctly), the operators
+        // are reported regardless of the modifiers.
         KeyCode::NumpadEnter => NamedKey::Enter,
+        KeyCode::NumpadAdd => return Key::Character(SmolStr::new_static("+")),
+        KeyCode::NumpadSubtract => return Key::Character(SmolStr::new_static("-")),
+        KeyCode::NumpadMultiply => return Key::Character(SmolStr::new_static("*")),
+        KeyCode::NumpadDivide => return Key::Character(SmolStr::new_static("/")),
 
         KeyCode::F1 => NamedKey::F1,
         KeyCode::F2 => NamedKey::F2,
@@ -298,8 +563,47 @@ pub(super) fn ralt_pressed(event: &NSEvent) -> bool {
     unsafe { event.modifierFlags() }.contains(NX_DEVICERALTKEYMASK)
 }
 
-pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
+/// Whether the modifier key `physical_key` is held during the `flagsChanged:` `event`.
+///
+/// Returns `None` for keys without a device-dependent flag, such as Caps Lock.
+pub(super) fn modifier_key_pressed(event: &NSEvent, physical_key: PhysicalKey) -> Option<bool> {
+    let mask = match physical_key {
+        PhysicalKey::Code(KeyCode::ShiftLeft) => NX_DEVICELSHIFTKEYMASK,
+        PhysicalKey::Code(KeyCode::ShiftRight) => NX_DEVICERSHIFTKEYMASK,
+        PhysicalKey::Code(KeyCode::ControlLeft) => NX_DEVICELCTLKEYMASK,
+        PhysicalKey::Code(KeyCode::ControlRight) => NX_DEVICERCTLKEYMASK,
+        PhysicalKey::Code(KeyCode::AltLeft) => NX_DEVICELALTKEYMASK,
+        PhysicalKey::Code(KeyCode::AltRight) => NX_DEVICERALTKEYMASK,
+        PhysicalKey::Code(KeyCode::SuperLeft) => NX_DEVICELCMDKEYMASK,
+        PhysicalKey::Code(KeyCode::SuperRight) => NX_DEVICERCMDKEYMASK,
+        PhysicalKey::Code(KeyCode::Fn) => NSEventModifierFlags::Function,
+        _ => return None,
+    };
+    Some(unsafe { event.modifierFlags() }.contains(mask))
+}
+
+/// Whether either Command key is held during `event`.
+///
+/// Besides the device-independent flag, this checks the device-dependent bits of the left and
+/// right Command keys. Tools that let the right Command key act as AltGr clear the former while
+/// the key is still physically held, and the `keyUp` of keys released during that time must
+/// still be delivered, see `send_event`.
+pub(super) fn command_pressed(event: &NSEvent) -> bool {
     let flags = unsafe { event.modifierFlags() };
+    flags.contains(NSEventModifierFlags::Command)
+        || flags.intersects(NX_DEVICELCMDKEYMASK | NX_DEVICERCMDKEYMASK)
+}
+
+pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
+    flags_mods(unsafe { event.modifierFlags() })
+}
+
+/// The modifiers currently held, which AppKit tracks regardless of the focus.
+pub(super) fn current_modifiers() -> Modifiers {
+    flags_mods(unsafe { NSEvent::modifierFlags_class() })
+}
+
+fn flags_mods(flags: NSEventModifierFlags) -> Modifiers {
     let mut state = ModifiersState::empty();
     let mut pressed_mods = ModifiersKeys::empty();
 
@@ -319,7 +623,68 @@ pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
     pressed_mods.set(ModifiersKeys::LSUPER, flags.contains(NX_DEVICELCMDKEYMASK));
     pressed_mods.set(ModifiersKeys::RSUPER, flags.contains(NX_DEVICERCMDKEYMASK));
 
-    Modifiers { state, pressed_mods }
+    state.set(ModifiersState::FN, flags.contains(NSEventModifierFlags::Function));
+
+    state.set(ModifiersState::CAPS_LOCK, flags.contains(NSEventModifierFlags::CapsLock));
+
+    Modifiers { state, pressed_mods, known_mods: ModifiersKeys::all() }
+}
+
+/// Exchange Command and Control in `modifiers`, see `with_swap_command_control`.
+pub(super) fn swap_command_control(modifiers: Modifiers) -> Modifiers {
+    fn swap<T: bitflags::Flags + Copy>(flags: T, a: T, b: T) -> T {
+        let mut swapped = flags;
+        swapped.set(a, flags.contains(b));
+        swapped.set(b, flags.contains(a));
+        swapped
+    }
+
+    Modifiers {
+        state: swap(modifiers.state, ModifiersState::SUPER, ModifiersState::CONTROL),
+        pressed_mods: swap(
+            swap(modifiers.pressed_mods, ModifiersKeys::LSUPER, ModifiersKeys::LCONTROL),
+            ModifiersKeys::RSUPER,
+            ModifiersKeys::RCONTROL,
+        ),
+        known_mods: swap(
+            swap(modifiers.known_mods, ModifiersKeys::LSUPER, ModifiersKeys::LCONTROL),
+            ModifiersKeys::RSUPER,
+            ModifiersKeys::RCONTROL,
+        ),
+    }
+}
+
+/// Whether AppKit sets [`NSEventModifierFlags::Function`] on the key `event` regardless of the Fn
+/// key, which it does for the arrow, navigation and function keys.
+pub(super) fn implies_function_flag(event: &NSEvent) -> bool {
+    let event_type = unsafe { event.r#type() };
+    if event_type != NSEventType::KeyDown && event_type != NSEventType::KeyUp {
+        return false;
+    }
+    match scancode_to_physicalkey(unsafe { event.keyCode() } as u32) {
+        PhysicalKey::Code(code) => {
+            code.is_function_key()
+                || code.is_navigation()
+                || matches!(code, KeyCode::Delete | KeyCode::Insert | KeyCode::Help)
+        },
+        PhysicalKey::Unidentified(_) => false,
+    }
+}
+
+/// The delta of a `ScrollWheel` event, in pixels when the device reports precise deltas, like
+/// trackpads and the Magic Mouse, and in lines for regular mouse wheels.
+pub(super) fn scroll_delta(event: &NSEvent, scale_factor: f64) -> MouseScrollDelta {
+    let (x, y) = unsafe { (event.scrollingDeltaX(), event.scrollingDeltaY()) };
+    let precise = unsafe { event.hasPreciseScrollingDeltas() };
+    scroll_delta_from(x, y, precise, scale_factor)
+}
+
+fn scroll_delta_from(x: f64, y: f64, precise: bool, scale_factor: f64) -> MouseScrollDelta {
+    if precise {
+        MouseScrollDelta::PixelDelta(LogicalPosition::new(x, y).to_physical(scale_factor))
+    } else {
+        MouseScrollDelta::LineDelta(x as f32, y as f32)
+    }
 }
 
 pub(super) fn dummy_event() -> Option<Retained<NSEvent>> {
@@ -341,6 +706,7 @@ pub(super) fn dummy_event() -> Option<Retained<NSEvent>> {
 pub(crate) fn physicalkey_to_scancode(physical_key: PhysicalKey) -> Option<u32> {
     let code = match physical_key {
         PhysicalKey::Code(code) => code,
+        PhysicalKey::Unidentified(NativeKeyCode::MacOS(scancode)) => return Some(scancode as u32),
         PhysicalKey::Unidentified(_) => return None,
     };
 
@@ -621,3 +987,138 @@ pub(crate) fn scancode_to_physicalkey(scancode: u32) -> PhysicalKey {
         _ => return PhysicalKey::Unidentified(NativeKeyCode::MacOS(scancode as u16)),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use objc2_foundation::NSString;
+
+    use super::*;
+    use crate::dpi::PhysicalPosition;
+    use crate::keyboard::ModifiersKeyState;
+
+    #[test]
+    fn numpad_operators() {
+        let operators = [
+            (KeyCode::NumpadAdd, "+"),
+            (KeyCode::NumpadSubtract, "-"),
+            (KeyCode::NumpadMultiply, "*"),
+            (KeyCode::NumpadDivide, "/"),
+        ];
+        for (code, text) in operators {
+            let scancode = physicalkey_to_scancode(PhysicalKey::Code(code)).unwrap() as u16;
+            assert_eq!(code_to_key(PhysicalKey::Code(code), scancode), Key::Character(text.into()));
+            assert_eq!(code_to_location(PhysicalKey::Code(code)), KeyLocation::Numpad);
+        }
+    }
+
+    #[test]
+    fn swap_command_control_keys() {
+        let flags = NSEventModifierFlags::Command
+            | NX_DEVICELCMDKEYMASK
+            | NSEventModifierFlags::Shift
+            | NX_DEVICERSHIFTKEYMASK;
+        let swapped = swap_command_control(flags_mods(flags));
+        assert_eq!(swapped.state(), ModifiersState::CONTROL | ModifiersState::SHIFT);
+        assert_eq!(swapped.lcontrol_state(), ModifiersKeyState::Pressed);
+        assert_eq!(swapped.lsuper_state(), ModifiersKeyState::Released);
+        assert_eq!(swapped.rshift_state(), ModifiersKeyState::Pressed);
+        assert_eq!(swap_command_control(swapped), flags_mods(flags));
+    }
+
+    #[test]
+    fn scroll_delta_precision() {
+        // Trackpads report precise deltas in points.
+        assert_eq!(
+            scroll_delta_from(1.5, -3.0, true, 2.0),
+            MouseScrollDelta::PixelDelta(PhysicalPosition::new(3.0, -6.0))
+        );
+        // Mouse wheels report lines, regardless of the scale factor.
+        assert_eq!(scroll_delta_from(0.0, 1.0, false, 2.0), MouseScrollDelta::LineDelta(0.0, 1.0));
+    }
+
+    #[test]
+    fn enter_locations() {
+        for (scancode, location) in [(0x24, KeyLocation::Standard), (0x4c, KeyLocation::Numpad)] {
+            let physical_key = scancode_to_physicalkey(scancode);
+            assert_eq!(code_to_key(physical_key, scancode as u16), Key::Named(NamedKey::Enter));
+            assert_eq!(code_to_location(physical_key), location);
+        }
+    }
+
+    #[test]
+    fn media_keys() {
+        let system_defined = |event_type, subtype: NSEventSubtype, data1| unsafe {
+            NSEvent::otherEventWithType_location_modifierFlags_timestamp_windowNumber_context_subtype_data1_data2(
+                event_type,
+                NSPoint::new(0.0, 0.0),
+                NSEventModifierFlags(0),
+                0.0,
+                0,
+                None,
+                subtype.0,
+                data1,
+                -1,
+            )
+            .unwrap()
+        };
+        let media_key = |key_type, flags| {
+            let event = system_defined(
+                NSEventType::SystemDefined,
+                NX_SUBTYPE_AUX_CONTROL_BUTTONS,
+                (key_type << 16) | flags,
+            );
+            create_media_key_event(&event)
+        };
+
+        let play = media_key(NX_KEYTYPE_PLAY, 0xa00).unwrap();
+        assert_eq!(play.physical_key, PhysicalKey::Code(KeyCode::MediaPlayPause));
+        assert_eq!(play.logical_key, Key::Named(NamedKey::MediaPlayPause));
+        assert_eq!((play.state, play.repeat), (ElementState::Pressed, false));
+
+        let volume_up = media_key(NX_KEYTYPE_SOUND_UP, 0xa01).unwrap();
+        assert_eq!(volume_up.logical_key, Key::Named(NamedKey::AudioVolumeUp));
+        assert_eq!((volume_up.state, volume_up.repeat), (ElementState::Pressed, true));
+
+        let brightness_down = media_key(NX_KEYTYPE_BRIGHTNESS_DOWN, 0xb00).unwrap();
+        assert_eq!(
+            brightness_down.physical_key,
+            PhysicalKey::Unidentified(NativeKeyCode::Unidentified)
+        );
+        assert_eq!(brightness_down.state, ElementState::Released);
+
+        // Caps Lock.
+        assert_eq!(media_key(4, 0xa00), None);
+        // Other subtypes and event types.
+        let power_off = system_defined(NSEventType::SystemDefined, NSEventSubtype::PowerOff, 0);
+        assert_eq!(create_media_key_event(&power_off), None);
+        assert_eq!(create_media_key_event(&dummy_event().unwrap()), None);
+    }
+
+    #[test]
+    fn command_pressed_either_side() {
+        let key_up = |flags| unsafe {
+            NSEvent::keyEventWithType_location_modifierFlags_timestamp_windowNumber_context_characters_charactersIgnoringModifiers_isARepeat_keyCode(
+                NSEventType::KeyUp,
+                NSPoint::new(0.0, 0.0),
+                flags,
+                0.0,
+                0,
+                None,
+                &NSString::from_str("a"),
+                &NSString::from_str("a"),
+                false,
+                0x00,
+            )
+            .unwrap()
+        };
+
+        let left = NSEventModifierFlags::Command | NX_DEVICELCMDKEYMASK;
+        let right = NSEventModifierFlags::Command | NX_DEVICERCMDKEYMASK;
+        assert!(command_pressed(&key_up(left)));
+        assert!(command_pressed(&key_up(right)));
+        // The right Command key remapped to act as AltGr.
+        assert!(command_pressed(&key_up(NX_DEVICERCMDKEYMASK | NSEventModifierFlags::Option)));
+        assert!(!command_pressed(&key_up(NSEventModifierFlags::Option | NX_DEVICERALTKEYMASK)));
+        assert!(!command_pressed(&key_up(NSEventModifierFlags(0))));
+    }
+}
@@ -24,7 +24,7 @@ use objc2_foundation::{NSNotificationCenter, NSObjectProtocol};
 use rwh_06::HasDisplayHandle;
 
 use super::super::notification_center::create_observer;
-use super::app::override_send_event;
+use super::app::{override_send_event, shared_app_subclass};
 use super::app_state::AppState;
 use super::cursor::CustomCursor;
 use super::event::dummy_event;
@@ -33,9 +33,10 @@ use super::observer::setup_control_flow_observers;
 use crate::application::ApplicationHandler;
 use crate::error::{EventLoopError, RequestError};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
 };
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::macos::ActivationPolicy;
@@ -80,6 +81,10 @@ pub struct ActiveEventLoop {
 }
 
 impl ActiveEventLoop {
+    pub(crate) fn common_state(&self) -> &CommonEventLoopState {
+        self.app_state.common_state()
+    }
+
     pub(crate) fn hide_application(&self) {
         NSApplication::sharedApplication(self.mtm).hide(None)
     }
@@ -106,7 +111,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         window_attributes: crate::window::WindowAttributes,
     ) -> Result<Box<dyn crate::window::Window>, RequestError> {
-        Ok(Box::new(Window::new(self, window_attributes)?))
+        let window: Box<dyn crate::window::Window> =
+            Box::new(Window::new(self, window_attributes)?);
+        self.common_state().register_window(&*window);
+        Ok(window)
     }
 
     fn create_custom_cursor(
@@ -138,6 +146,14 @@ impl RootActiveEventLoop for ActiveEventLoop {
         }
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        WindowingInfo::new(
+            WindowingBackend::AppKit,
+            None,
+            WindowingCapabilities::SERVER_SIDE_DECORATIONS | WindowingCapabilities::ALWAYS_ON_TOP,
+        )
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.app_state.set_control_flow(control_flow)
     }
@@ -194,11 +210,23 @@ pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) activation_policy: Option<ActivationPolicy>,
     pub(crate) default_menu: bool,
     pub(crate) activate_ignoring_other_apps: bool,
+    pub(crate) send_event_override: bool,
+    pub(crate) swap_command_control: bool,
+    pub(crate) app_subclass: bool,
+    pub(crate) pointer_motion_coalescing: bool,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
     fn default() -> Self {
-        Self { activation_policy: None, default_menu: true, activate_ignoring_other_apps: true }
+        Self {
+            activation_policy: None,
+            default_menu: true,
+            activate_ignoring_other_apps: true,
+            send_event_override: true,
+            swap_command_control: false,
+            app_subclass: false,
+            pointer_motion_coalescing: true,
+        }
     }
 }
 
@@ -221,13 +249,21 @@ impl EventLoop {
             activation_policy,
             attributes.default_menu,
             attributes.activate_ignoring_other_apps,
+            attributes.swap_command_control,
+            attributes.pointer_motion_coalescing,
         );
 
-        // Initialize the application (if it has not already been).
-        let app = NSApplication::sharedApplication(mtm);
-
-        // Override `sendEvent:` on the application to forward to our application state.
-        override_send_event(&app);
+        // Initialize the application (if it has not already been), and override `sendEvent:` on
+        // it to forward to our application state.
+        let app = if attributes.send_event_override && attributes.app_subclass {
+            shared_app_subclass(mtm)?
+        } else {
+            let app = NSApplication::sharedApplication(mtm);
+            if attributes.send_event_override {
+                override_send_event(&app);
+            }
+            app
+        };
 
         let center = unsafe { NSNotificationCenter::defaultCenter() };
 
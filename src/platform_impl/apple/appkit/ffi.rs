@@ -70,6 +70,8 @@ pub type UniCharCount = std::os::raw::c_ulong;
 pub type UniChar = std::os::raw::c_ushort;
 pub type OSStatus = i32;
 
+#[allow(non_upper_case_globals)]
+pub const kUCKeyActionDown: u16 = 0;
 #[allow(non_upper_case_globals)]
 pub const kUCKeyActionDisplay: u16 = 3;
 #[allow(non_upper_case_globals)]
@@ -78,6 +80,8 @@ pub const kUCKeyTranslateNoDeadKeysMask: OptionBits = 1;
 #[link(name = "Carbon", kind = "framework")]
 extern "C" {
     pub static kTISPropertyUnicodeKeyLayoutData: &'static CFString;
+    pub static kTISPropertyInputSourceID: &'static CFString;
+    pub static kTISPropertyInputSourceLanguages: &'static CFString;
 
     #[allow(non_snake_case)]
     pub fn TISGetInputSourceProperty(
@@ -14,6 +14,7 @@ mod view;
 mod window;
 mod window_delegate;
 
+pub(crate) use self::app::{is_send_event_overridden, set_send_event_hook};
 pub(crate) use self::cursor::CustomCursor as PlatformCustomCursor;
 pub(crate) use self::event::{physicalkey_to_scancode, scancode_to_physicalkey, KeyEventExtra};
 pub(crate) use self::event_loop::{
@@ -224,6 +224,19 @@ impl MonitorHandle {
         refresh_rate_millihertz(self.0, &current_display_mode)
     }
 
+    pub fn bit_depth(&self) -> Option<u32> {
+        run_on_main(|mtm| {
+            // The low byte of a window depth is its bits per sample, which is what
+            // `NSBitsPerSampleFromDepth` returns.
+            let depth = self.ns_screen(mtm)?.depth().0 as u32 & 0xff;
+            (depth != 0).then_some(depth)
+        })
+    }
+
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        None
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         let mode = NativeDisplayMode(unsafe { CGDisplayCopyDisplayMode(self.0) }.unwrap());
         let refresh_rate_millihertz = refresh_rate_millihertz(self.0, &mode);
@@ -3,6 +3,7 @@ use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::ptr;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use objc2::rc::Retained;
 use objc2::runtime::{AnyObject, Sel};
@@ -19,16 +20,19 @@ use objc2_foundation::{
 use super::app_state::AppState;
 use super::cursor::{default_cursor, invisible_cursor};
 use super::event::{
-    code_to_key, code_to_location, create_key_event, event_mods, lalt_pressed, ralt_pressed,
-    scancode_to_physicalkey, KeyEventExtra,
+    code_to_key, code_to_location, create_key_event, event_mods, event_timestamp,
+    implies_function_flag, keyboard_layout_direction, keyboard_layout_name, lalt_pressed,
+    ralt_pressed, scancode_to_physicalkey, scroll_delta, swap_command_control, KeyEventExtra,
 };
 use super::window::window_id;
-use crate::dpi::{LogicalPosition, LogicalSize};
+use super::window_delegate::warp_cursor;
+use crate::cursor::CursorAnimation;
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition};
 use crate::event::{
-    DeviceEvent, ElementState, Ime, KeyEvent, Modifiers, MouseButton, MouseScrollDelta,
-    PointerKind, PointerSource, TouchPhase, WindowEvent,
+    DeadKeyComposition, ElementState, Ime, KeyEvent, Modifiers, MouseButton, PointerKind,
+    PointerSource, TouchPhase, WindowEvent,
 };
-use crate::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey};
+use crate::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, TextDirection};
 use crate::platform::macos::OptionAsAlt;
 
 #[derive(Debug)]
@@ -83,6 +87,7 @@ fn key_to_modifier(key: &Key) -> Option<ModifiersState> {
         Key::Named(NamedKey::Control) => Some(ModifiersState::CONTROL),
         Key::Named(NamedKey::Super) => Some(ModifiersState::SUPER),
         Key::Named(NamedKey::Shift) => Some(ModifiersState::SHIFT),
+        Key::Named(NamedKey::Fn) => Some(ModifiersState::FN),
         _ => None,
     }
 }
@@ -126,6 +131,17 @@ pub struct ViewState {
     /// Can be set using `set_ime_allowed`
     ime_allowed: Cell<bool>,
 
+    /// False if the application doesn't want key repeats reported through `KeyboardInput`.
+    ///
+    /// Can be set using `set_key_repeat_enabled`
+    key_repeat_enabled: Cell<bool>,
+
+    /// The keyboard layout seen with the last keyboard event.
+    keyboard_layout: RefCell<Option<String>>,
+
+    /// The direction of the keyboard layout seen with the last keyboard event.
+    keyboard_layout_direction: Cell<Option<TextDirection>>,
+
     /// True if the current key event should be forwarded
     /// to the application, even during IME
     forward_key_to_app: Cell<bool>,
@@ -135,6 +151,12 @@ pub struct ViewState {
 
     /// The state of the `Option` as `Alt`.
     option_as_alt: Cell<OptionAsAlt>,
+
+    /// Tracks dead keys to report `KeyEvent::composed`.
+    dead_keys: RefCell<DeadKeyComposition>,
+
+    /// Stepped by the application state until it finishes or the user moves the mouse.
+    cursor_animation: RefCell<Option<CursorAnimation>>,
 }
 
 define_class!(
@@ -434,6 +456,7 @@ define_class!(
         #[unsafe(method(keyDown:))]
         fn key_down(&self, event: &NSEvent) {
             trace_scope!("keyDown:");
+            self.update_keyboard_layout();
             {
                 let mut prev_input_source = self.ivars().input_source.borrow_mut();
                 let current_input_source = self.current_input_source();
@@ -480,8 +503,11 @@ define_class!(
                 _ => old_ime_state != self.ivars().ime_state.get(),
             };
 
-            if !had_ime_input || self.ivars().forward_key_to_app.get() {
-                let key_event = create_key_event(&event, true, unsafe { event.isARepeat() });
+            let is_repeat = unsafe { event.isARepeat() };
+            let suppress_repeat = is_repeat && !self.ivars().key_repeat_enabled.get();
+
+            if (!had_ime_input || self.ivars().forward_key_to_app.get()) && !suppress_repeat {
+                let key_event = self.create_key_event(&event, true, is_repeat);
                 self.queue_event(WindowEvent::KeyboardInput {
                     device_id: None,
                     event: key_event,
@@ -501,7 +527,7 @@ define_class!(
             if matches!(self.ivars().ime_state.get(), ImeState::Ground | ImeState::Disabled) {
                 self.queue_event(WindowEvent::KeyboardInput {
                     device_id: None,
-                    event: create_key_event(&event, false, false),
+                    event: self.create_key_event(&event, false, false),
                     is_synthetic: false,
                 });
             }
@@ -511,6 +537,8 @@ define_class!(
         fn flags_changed(&self, event: &NSEvent) {
             trace_scope!("flagsChanged:");
 
+            // Layouts are commonly switched with a modifier shortcut.
+            self.update_keyboard_layout();
             self.update_modifiers(event, true);
         }
 
@@ -548,7 +576,11 @@ define_class!(
                 .expect("could not find current event");
 
             self.update_modifiers(&event, false);
-            let event = create_key_event(&event, true, unsafe { event.isARepeat() });
+            let is_repeat = unsafe { event.isARepeat() };
+            if is_repeat && !self.ivars().key_repeat_enabled.get() {
+                return;
+            }
+            let event = self.create_key_event(&event, true, is_repeat);
 
             self.queue_event(WindowEvent::KeyboardInput {
                 device_id: None,
@@ -666,24 +698,18 @@ define_class!(
 
             self.mouse_motion(event);
 
-            let delta = {
-                let (x, y) = unsafe { (event.scrollingDeltaX(), event.scrollingDeltaY()) };
-                if unsafe { event.hasPreciseScrollingDeltas() } {
-                    let delta = LogicalPosition::new(x, y).to_physical(self.scale_factor());
-                    MouseScrollDelta::PixelDelta(delta)
-                } else {
-                    MouseScrollDelta::LineDelta(x as f32, y as f32)
-                }
-            };
+            let delta = scroll_delta(event, self.scale_factor());
+
+            let momentum_phase = momentum_phase(unsafe { event.momentumPhase() });
 
             // The "momentum phase," if any, has higher priority than touch phase (the two should
             // be mutually exclusive anyhow, which is why the API is rather incoherent). If no
             // momentum phase is recorded (or rather, the started/ended cases of the
             // momentum phase) then we report the touch phase.
             #[allow(non_upper_case_globals)]
-            let phase = match unsafe { event.momentumPhase() } {
-                NSEventPhase::MayBegin | NSEventPhase::Began => TouchPhase::Started,
-                NSEventPhase::Ended | NSEventPhase::Cancelled => TouchPhase::Ended,
+            let phase = match momentum_phase {
+                Some(TouchPhase::Started) => TouchPhase::Started,
+                Some(TouchPhase::Ended | TouchPhase::Cancelled) => TouchPhase::Ended,
                 _ => match unsafe { event.phase() } {
                     NSEventPhase::MayBegin | NSEventPhase::Began => TouchPhase::Started,
                     NSEventPhase::Ended | NSEventPhase::Cancelled => TouchPhase::Ended,
@@ -693,10 +719,13 @@ define_class!(
 
             self.update_modifiers(event, false);
 
-            self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
-                app.device_event(event_loop, None, DeviceEvent::MouseWheel { delta })
+            // The device event is emitted by the application's `sendEvent:`, for all scroll events.
+            self.queue_event(WindowEvent::MouseWheel {
+                device_id: None,
+                delta,
+                phase,
+                momentum_phase,
             });
-            self.queue_event(WindowEvent::MouseWheel { device_id: None, delta, phase });
         }
 
         #[unsafe(method(magnifyWithEvent:))]
@@ -705,20 +734,9 @@ define_class!(
 
             self.mouse_motion(event);
 
-            #[allow(non_upper_case_globals)]
-            let phase = match unsafe { event.phase() } {
-                NSEventPhase::Began => TouchPhase::Started,
-                NSEventPhase::Changed => TouchPhase::Moved,
-                NSEventPhase::Cancelled => TouchPhase::Cancelled,
-                NSEventPhase::Ended => TouchPhase::Ended,
-                _ => return,
-            };
-
-            self.queue_event(WindowEvent::PinchGesture {
-                device_id: None,
-                delta: unsafe { event.magnification() },
-                phase,
-            });
+            if let Some(event) = pinch_gesture(event) {
+                self.queue_event(event);
+            }
         }
 
         #[unsafe(method(smartMagnifyWithEvent:))]
@@ -736,20 +754,9 @@ define_class!(
 
             self.mouse_motion(event);
 
-            #[allow(non_upper_case_globals)]
-            let phase = match unsafe { event.phase() } {
-                NSEventPhase::Began => TouchPhase::Started,
-                NSEventPhase::Changed => TouchPhase::Moved,
-                NSEventPhase::Cancelled => TouchPhase::Cancelled,
-                NSEventPhase::Ended => TouchPhase::Ended,
-                _ => return,
-            };
-
-            self.queue_event(WindowEvent::RotationGesture {
-                device_id: None,
-                delta: unsafe { event.rotation() },
-                phase,
-            });
+            if let Some(event) = rotation_gesture(event) {
+                self.queue_event(event);
+            }
         }
 
         #[unsafe(method(pressureChangeWithEvent:))]
@@ -798,10 +805,15 @@ impl WinitView {
             ime_state: Default::default(),
             input_source: Default::default(),
             ime_allowed: Default::default(),
+            key_repeat_enabled: Cell::new(true),
+            keyboard_layout: RefCell::new(keyboard_layout_name()),
+            keyboard_layout_direction: Cell::new(keyboard_layout_direction()),
             forward_key_to_app: Default::default(),
             marked_text: Default::default(),
             accepts_first_mouse,
             option_as_alt: Cell::new(option_as_alt),
+            dead_keys: Default::default(),
+            cursor_animation: Default::default(),
         });
         let this: Retained<Self> = unsafe { msg_send![super(this), init] };
 
@@ -821,6 +833,17 @@ impl WinitView {
         });
     }
 
+    fn create_key_event(&self, event: &NSEvent, is_press: bool, is_repeat: bool) -> KeyEvent {
+        let mut key_event = create_key_event(event, is_press, is_repeat);
+        key_event.composed = self.ivars().dead_keys.borrow_mut().feed(
+            key_event.state,
+            &key_event.logical_key,
+            key_event.text.is_some(),
+        );
+        key_event.debug_check_text();
+        key_event
+    }
+
     fn scale_factor(&self) -> f64 {
         self.window().backingScaleFactor() as f64
     }
@@ -846,6 +869,10 @@ impl WinitView {
         cursor_state.cursor = icon;
     }
 
+    pub(super) fn cursor_visible(&self) -> bool {
+        self.ivars().cursor_state.borrow().visible
+    }
+
     /// Set whether the cursor should be visible or not.
     ///
     /// Returns whether the state changed.
@@ -859,6 +886,63 @@ impl WinitView {
         }
     }
 
+    pub(super) fn animate_cursor_to(&self, target: PhysicalPosition<i32>, duration: Duration) {
+        let window_point = self.window().mouseLocationOutsideOfEventStream();
+        let view_point = self.convertPoint_fromView(window_point, None);
+        let from =
+            LogicalPosition::new(view_point.x, view_point.y).to_physical(self.scale_factor());
+
+        *self.ivars().cursor_animation.borrow_mut() =
+            Some(CursorAnimation::new(from, target, Instant::now(), duration));
+        self.ivars().app_state.queue_cursor_animation(self);
+    }
+
+    /// Warp the cursor to where the running animation should be at `now`.
+    ///
+    /// Returns when the animation wants to be stepped again.
+    pub(super) fn step_cursor_animation(&self, now: Instant) -> Option<Instant> {
+        let mut animation = self.ivars().cursor_animation.borrow_mut();
+        let position = animation.as_mut()?.step(now);
+        let finished = animation.as_ref().is_some_and(|animation| animation.is_finished(now));
+        if finished {
+            *animation = None;
+        }
+        drop(animation);
+
+        if let Err(err) = warp_cursor(&self.window(), position.to_logical(self.scale_factor())) {
+            tracing::warn!("failed to animate cursor: {err}");
+            *self.ivars().cursor_animation.borrow_mut() = None;
+            return None;
+        }
+
+        (!finished).then(|| now + CursorAnimation::FRAME_INTERVAL)
+    }
+
+    pub(super) fn ime_allowed(&self) -> bool {
+        self.ivars().ime_allowed.get()
+    }
+
+    /// Notify the application if the keyboard layout changed since the last keyboard event.
+    fn update_keyboard_layout(&self) {
+        let layout = keyboard_layout_name();
+        let mut current = self.ivars().keyboard_layout.borrow_mut();
+        if *current != layout {
+            *current = layout;
+            drop(current);
+            self.queue_event(WindowEvent::KeyboardLayoutChanged);
+
+            let direction = keyboard_layout_direction();
+            let previous = self.ivars().keyboard_layout_direction.replace(direction);
+            if let Some(direction) = direction.filter(|&d| Some(d) != previous) {
+                self.queue_event(WindowEvent::KeyboardLayoutDirectionChanged(direction));
+            }
+        }
+    }
+
+    pub(super) fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.ivars().key_repeat_enabled.set(enabled);
+    }
+
     pub(super) fn set_ime_allowed(&self, ime_allowed: bool) {
         if self.ivars().ime_allowed.get() == ime_allowed {
             return;
@@ -904,8 +988,12 @@ impl WinitView {
     fn update_modifiers(&self, ns_event: &NSEvent, is_flags_changed_event: bool) {
         use ElementState::{Pressed, Released};
 
-        let current_modifiers = event_mods(ns_event);
+        let mut current_modifiers = event_mods(ns_event);
         let prev_modifiers = self.ivars().modifiers.get();
+        if implies_function_flag(ns_event) {
+            let fn_pressed = prev_modifiers.state().contains(ModifiersState::FN);
+            current_modifiers.state.set(ModifiersState::FN, fn_pressed);
+        }
         self.ivars().modifiers.set(current_modifiers);
 
         // This function was called form the flagsChanged event, which is triggered
@@ -935,17 +1023,30 @@ impl WinitView {
                 let mut event = KeyEvent {
                     location: code_to_location(physical_key),
                     logical_key: logical_key.clone(),
+                    key_without_modifiers: logical_key.clone(),
                     physical_key,
                     repeat: false,
                     // We'll correct this later.
                     state: Pressed,
                     text: None,
-                    platform_specific: KeyEventExtra {
-                        text_with_all_modifiers: None,
-                        key_without_modifiers: logical_key.clone(),
-                    },
+                    composed: false,
+                    timestamp: event_timestamp(ns_event),
+                    platform_specific: KeyEventExtra { text_with_all_modifiers: None },
                 };
 
+                if event_modifier == ModifiersState::FN {
+                    // There is a single Fn key, so it's pressed whenever the modifier is active.
+                    if !current_modifiers.state().contains(ModifiersState::FN) {
+                        event.state = Released;
+                    }
+                    self.queue_event(WindowEvent::KeyboardInput {
+                        device_id: None,
+                        event,
+                        is_synthetic: false,
+                    });
+                    break 'send_event;
+                }
+
                 let location_mask = ModLocationMask::from_location(event.location);
 
                 let mut phys_mod_state = self.ivars().phys_modifiers.borrow_mut();
@@ -1025,7 +1126,16 @@ impl WinitView {
             return;
         }
 
-        self.queue_event(WindowEvent::ModifiersChanged(self.ivars().modifiers.get()));
+        self.queue_event(WindowEvent::ModifiersChanged(self.reported_modifiers(current_modifiers)));
+    }
+
+    /// The modifiers as reported to the application, which may have Command and Control swapped.
+    pub(super) fn reported_modifiers(&self, modifiers: Modifiers) -> Modifiers {
+        if self.ivars().app_state.swap_command_control() {
+            swap_command_control(modifiers)
+        } else {
+            modifiers
+        }
     }
 
     fn mouse_click(&self, event: &NSEvent, button_state: ElementState) {
@@ -1040,6 +1150,7 @@ impl WinitView {
             state: button_state,
             position,
             button: button.into(),
+            timestamp: event_timestamp(event),
         });
     }
 
@@ -1047,6 +1158,13 @@ impl WinitView {
         let view_point = self.mouse_view_point(event);
         let frame = self.frame();
 
+        let mut animation = self.ivars().cursor_animation.borrow_mut();
+        let position = view_point.to_physical(self.scale_factor());
+        if animation.as_ref().is_some_and(|animation| animation.is_user_motion(position)) {
+            *animation = None;
+        }
+        drop(animation);
+
         if view_point.x.is_sign_negative()
             || view_point.y.is_sign_negative()
             || view_point.x > frame.size.width
@@ -1066,6 +1184,7 @@ impl WinitView {
             primary: true,
             position: view_point.to_physical(self.scale_factor()),
             source: PointerSource::Mouse,
+            timestamp: event_timestamp(event),
         });
     }
 
@@ -1077,6 +1196,50 @@ impl WinitView {
     }
 }
 
+/// The phase of a magnification or rotation gesture event, if it is one that is reported.
+fn gesture_phase(phase: NSEventPhase) -> Option<TouchPhase> {
+    #[allow(non_upper_case_globals)]
+    match phase {
+        NSEventPhase::Began => Some(TouchPhase::Started),
+        NSEventPhase::Changed => Some(TouchPhase::Moved),
+        NSEventPhase::Cancelled => Some(TouchPhase::Cancelled),
+        NSEventPhase::Ended => Some(TouchPhase::Ended),
+        _ => None,
+    }
+}
+
+/// The [`WindowEvent::PinchGesture`] of a `magnifyWithEvent:` event, if its phase is reported.
+fn pinch_gesture(event: &NSEvent) -> Option<WindowEvent> {
+    let phase = gesture_phase(unsafe { event.phase() })?;
+    Some(WindowEvent::PinchGesture {
+        device_id: None,
+        delta: unsafe { event.magnification() },
+        phase,
+    })
+}
+
+/// The [`WindowEvent::RotationGesture`] of a `rotateWithEvent:` event, if its phase is reported.
+fn rotation_gesture(event: &NSEvent) -> Option<WindowEvent> {
+    let phase = gesture_phase(unsafe { event.phase() })?;
+    Some(WindowEvent::RotationGesture {
+        device_id: None,
+        delta: unsafe { event.rotation() },
+        phase,
+    })
+}
+
+/// The phase of the momentum scrolling, if the scroll event is part of it.
+fn momentum_phase(phase: NSEventPhase) -> Option<TouchPhase> {
+    #[allow(non_upper_case_globals)]
+    match phase {
+        NSEventPhase::Began => Some(TouchPhase::Started),
+        NSEventPhase::Changed | NSEventPhase::Stationary => Some(TouchPhase::Moved),
+        NSEventPhase::Ended => Some(TouchPhase::Ended),
+        NSEventPhase::Cancelled => Some(TouchPhase::Cancelled),
+        _ => None,
+    }
+}
+
 /// Get the mouse button from the NSEvent.
 fn mouse_button(event: &NSEvent) -> MouseButton {
     // The buttonNumber property only makes sense for the mouse events:
@@ -1084,14 +1247,8 @@ fn mouse_button(event: &NSEvent) -> MouseButton {
     // For the other events, it's always set to 0.
     // MacOS only defines the left, right and middle buttons, 3..=31 are left as generic buttons,
     // but 3 and 4 are very commonly used as Back and Forward by hardware vendors and applications.
-    match unsafe { event.buttonNumber() } {
-        0 => MouseButton::Left,
-        1 => MouseButton::Right,
-        2 => MouseButton::Middle,
-        3 => MouseButton::Back,
-        4 => MouseButton::Forward,
-        n => MouseButton::Other(n as u16),
-    }
+    // This is the numbering of `DeviceEvent::Button`, which reports `buttonNumber` as is.
+    MouseButton::from_device_button(unsafe { event.buttonNumber() } as u32)
 }
 
 // NOTE: to get option as alt working we need to rewrite events
@@ -1131,3 +1288,103 @@ fn replace_event(event: &NSEvent, option_as_alt: OptionAsAlt) -> Retained<NSEven
         event.copy()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use objc2::rc::Retained;
+    use objc2::{define_class, msg_send, AllocAnyThread, DefinedClass};
+    use objc2_app_kit::{NSEvent, NSEventPhase};
+    use objc2_foundation::NSObject;
+
+    use super::{gesture_phase, momentum_phase, pinch_gesture, rotation_gesture};
+    use crate::event::{TouchPhase, WindowEvent};
+
+    define_class!(
+        /// A gesture event with the given phase and values, AppKit can't create those.
+        #[unsafe(super(NSEvent, NSObject))]
+        #[ivars = (NSEventPhase, f64, f32)]
+        #[name = "WinitTestGestureEvent"]
+        struct TestGestureEvent;
+
+        impl TestGestureEvent {
+            #[unsafe(method(phase))]
+            fn phase(&self) -> NSEventPhase {
+                self.ivars().0
+            }
+
+            #[unsafe(method(magnification))]
+            fn magnification(&self) -> f64 {
+                self.ivars().1
+            }
+
+            #[unsafe(method(rotation))]
+            fn rotation(&self) -> f32 {
+                self.ivars().2
+            }
+        }
+    );
+
+    impl TestGestureEvent {
+        fn new(phase: NSEventPhase, magnification: f64, rotation: f32) -> Retained<Self> {
+            let this = Self::alloc().set_ivars((phase, magnification, rotation));
+            unsafe { msg_send![super(this), init] }
+        }
+    }
+
+    #[test]
+    fn gesture_event_phases() {
+        assert_eq!(gesture_phase(NSEventPhase::Began), Some(TouchPhase::Started));
+        assert_eq!(gesture_phase(NSEventPhase::Changed), Some(TouchPhase::Moved));
+        assert_eq!(gesture_phase(NSEventPhase::Ended), Some(TouchPhase::Ended));
+        assert_eq!(gesture_phase(NSEventPhase::Cancelled), Some(TouchPhase::Cancelled));
+        // Momentum and stationary phases don't apply to gestures.
+        assert_eq!(gesture_phase(NSEventPhase::MayBegin), None);
+        assert_eq!(gesture_phase(NSEventPhase::Stationary), None);
+        assert_eq!(gesture_phase(NSEventPhase::None), None);
+    }
+
+    #[test]
+    fn gesture_events() {
+        let event = TestGestureEvent::new(NSEventPhase::Began, 0.0, 12.5);
+        assert_eq!(
+            rotation_gesture(&event),
+            Some(WindowEvent::RotationGesture {
+                device_id: None,
+                delta: 12.5,
+                phase: TouchPhase::Started
+            })
+        );
+        let event = TestGestureEvent::new(NSEventPhase::Changed, 0.0, -3.0);
+        assert_eq!(
+            rotation_gesture(&event),
+            Some(WindowEvent::RotationGesture {
+                device_id: None,
+                delta: -3.0,
+                phase: TouchPhase::Moved
+            })
+        );
+        let event = TestGestureEvent::new(NSEventPhase::Ended, 0.25, 0.0);
+        assert_eq!(
+            pinch_gesture(&event),
+            Some(WindowEvent::PinchGesture {
+                device_id: None,
+                delta: 0.25,
+                phase: TouchPhase::Ended
+            })
+        );
+        // The events of the other phases aren't reported.
+        let event = TestGestureEvent::new(NSEventPhase::MayBegin, 0.0, 1.0);
+        assert_eq!(rotation_gesture(&event), None);
+    }
+
+    #[test]
+    fn scroll_momentum_phases() {
+        assert_eq!(momentum_phase(NSEventPhase::Began), Some(TouchPhase::Started));
+        assert_eq!(momentum_phase(NSEventPhase::Changed), Some(TouchPhase::Moved));
+        assert_eq!(momentum_phase(NSEventPhase::Stationary), Some(TouchPhase::Moved));
+        assert_eq!(momentum_phase(NSEventPhase::Ended), Some(TouchPhase::Ended));
+        assert_eq!(momentum_phase(NSEventPhase::Cancelled), Some(TouchPhase::Cancelled));
+        // Scroll events outside of momentum scrolling.
+        assert_eq!(momentum_phase(NSEventPhase::None), None);
+    }
+}
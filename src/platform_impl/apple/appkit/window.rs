@@ -1,7 +1,10 @@
 #![allow(clippy::unnecessary_cast)]
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use dispatch2::MainThreadBound;
-use dpi::{Position, Size};
+use dpi::{PhysicalPosition, Position, Size};
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::{define_class, MainThreadMarker, Message};
 use objc2_app_kit::{NSPanel, NSResponder, NSWindow};
@@ -9,17 +12,22 @@ use objc2_foundation::NSObject;
 
 use super::event_loop::ActiveEventLoop;
 use super::window_delegate::WindowDelegate;
-use crate::error::RequestError;
+use crate::cursor::CurrentCursor;
+use crate::error::{NotSupportedError, RequestError};
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
 use crate::monitor::MonitorHandle as CoreMonitorHandle;
 use crate::window::{
-    Cursor, Fullscreen, Icon, ImePurpose, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    CommonWindowState, Cursor, Fullscreen, HitTestRegion, Icon, ImePurpose, InputStateSnapshot,
+    SurfaceTransform, Theme, UserAttentionType, Window as CoreWindow, WindowAttributes,
+    WindowButtons, WindowId, WindowLevel,
 };
 
 pub(crate) struct Window {
     window: MainThreadBound<Retained<NSWindow>>,
     /// The window only keeps a weak reference to this, so we must keep it around here.
     delegate: MainThreadBound<Retained<WindowDelegate>>,
+    current_cursor: CurrentCursor,
+    pub(crate) common_state: Arc<CommonWindowState>,
 }
 
 impl Window {
@@ -28,11 +36,17 @@ impl Window {
         attributes: WindowAttributes,
     ) -> Result<Self, RequestError> {
         let mtm = window_target.mtm;
+        let common_state = Arc::new(CommonWindowState::new(&attributes));
+        // The delegate applies the initial cursor.
+        let current_cursor = CurrentCursor::default();
+        current_cursor.set(&attributes.cursor);
         let delegate =
             autoreleasepool(|_| WindowDelegate::new(&window_target.app_state, attributes, mtm))?;
         Ok(Window {
             window: MainThreadBound::new(delegate.window().retain(), mtm),
             delegate: MainThreadBound::new(delegate, mtm),
+            current_cursor,
+            common_state,
         })
     }
 
@@ -43,6 +57,10 @@ impl Window {
         self.delegate.get_on_main(|delegate| f(delegate))
     }
 
+    pub(crate) fn ns_window(&self, mtm: MainThreadMarker) -> &NSWindow {
+        self.window.get(mtm)
+    }
+
     #[inline]
     pub(crate) fn raw_window_handle_rwh_06(
         &self,
@@ -136,6 +154,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.safe_area())
     }
 
+    fn surface_transform(&self) -> SurfaceTransform {
+        SurfaceTransform::Identity
+    }
+
     fn set_min_surface_size(&self, min_size: Option<Size>) {
         self.maybe_wait_on_main(|delegate| delegate.set_min_surface_size(min_size))
     }
@@ -164,6 +186,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_blur(blur));
     }
 
+    fn set_tearing_allowed(&self, _allowed: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_tearing_allowed is not supported").into())
+    }
+
     fn set_visible(&self, visible: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_visible(visible));
     }
@@ -204,6 +230,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.is_maximized())
     }
 
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("move_to_workspace is not supported").into())
+    }
+
     fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
         self.maybe_wait_on_main(|delegate| delegate.set_fullscreen(fullscreen.map(Into::into)))
     }
@@ -240,6 +274,32 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.maybe_wait_on_main(|delegate| delegate.set_key_repeat_enabled(enabled));
+    }
+
+    fn keyboard_layout_name(&self) -> Option<String> {
+        self.maybe_wait_on_main(|delegate| delegate.keyboard_layout_name())
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        self.maybe_wait_on_main(|delegate| delegate.keyboard_layout_direction())
+    }
+
+    fn key_for_physical(&self, key: PhysicalKey) -> Option<Key> {
+        self.maybe_wait_on_main(|delegate| delegate.key_for_physical(key))
+    }
+
+    fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        self.maybe_wait_on_main(|delegate| delegate.compose_dead_key(dead, base))
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        self.maybe_wait_on_main(|delegate| delegate.current_modifiers())
+    }
+
+    fn set_hit_test_regions(&self, _regions: &[HitTestRegion]) {}
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -269,13 +329,27 @@ impl CoreWindow for Window {
     }
 
     fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
         self.maybe_wait_on_main(|delegate| delegate.set_cursor(cursor));
     }
 
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
+
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError> {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_position(position))
     }
 
+    fn animate_cursor_to(
+        &self,
+        target: PhysicalPosition<i32>,
+        duration: Duration,
+    ) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.animate_cursor_to(target, duration));
+        Ok(())
+    }
+
     fn set_cursor_grab(&self, mode: crate::window::CursorGrabMode) -> Result<(), RequestError> {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab(mode))
     }
@@ -284,6 +358,11 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_visible(visible))
     }
 
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        let cursor = self.current_cursor.get();
+        self.maybe_wait_on_main(|delegate| delegate.capture_input_state(cursor))
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         self.maybe_wait_on_main(|delegate| delegate.drag_window())
     }
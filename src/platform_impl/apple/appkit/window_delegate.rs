@@ -2,9 +2,11 @@
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::ffi::c_void;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::runtime::{AnyObject, ProtocolObject};
@@ -13,13 +15,15 @@ use objc2::{
     MainThreadOnly, Message,
 };
 use objc2_app_kit::{
-    NSAppKitVersionNumber, NSAppKitVersionNumber10_12, NSAppearance, NSAppearanceCustomization,
-    NSAppearanceNameAqua, NSApplication, NSApplicationPresentationOptions, NSBackingStoreType,
-    NSColor, NSDraggingDestination, NSDraggingInfo, NSFilenamesPboardType,
-    NSRequestUserAttentionType, NSScreen, NSToolbar, NSView, NSViewFrameDidChangeNotification,
-    NSWindow, NSWindowButton, NSWindowDelegate, NSWindowFullScreenButton, NSWindowLevel,
-    NSWindowOcclusionState, NSWindowOrderingMode, NSWindowSharingType, NSWindowStyleMask,
-    NSWindowTabbingMode, NSWindowTitleVisibility, NSWindowToolbarStyle,
+    NSAccessibilityNotificationName, NSAccessibilityPostNotification,
+    NSAccessibilityTitleChangedNotification, NSAppKitVersionNumber, NSAppKitVersionNumber10_12,
+    NSAppearance, NSAppearanceCustomization, NSAppearanceNameAqua, NSApplication,
+    NSApplicationPresentationOptions, NSBackingStoreType, NSColor, NSDraggingDestination,
+    NSDraggingInfo, NSFilenamesPboardType, NSRequestUserAttentionType, NSScreen, NSToolbar, NSView,
+    NSViewFrameDidChangeNotification, NSWindow, NSWindowButton, NSWindowDelegate,
+    NSWindowFullScreenButton, NSWindowLevel, NSWindowOcclusionState, NSWindowOrderingMode,
+    NSWindowSharingType, NSWindowStyleMask, NSWindowTabbingMode, NSWindowTitleVisibility,
+    NSWindowToolbarStyle,
 };
 use objc2_core_foundation::{CGFloat, CGPoint};
 use objc2_core_graphics::{
@@ -49,10 +53,14 @@ use crate::dpi::{
 };
 use crate::error::{NotSupportedError, RequestError};
 use crate::event::{SurfaceSizeWriter, WindowEvent};
-use crate::platform::macos::{OptionAsAlt, WindowExtMacOS};
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
+use crate::monitor::find_exclusive_mode;
+use crate::platform::macos::{OptionAsAlt, TabbingMode, WindowExtMacOS};
+use crate::platform_impl::OccludedRedraw;
 use crate::window::{
-    Cursor, CursorGrabMode, Icon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    Cursor, CursorGrabMode, Icon, ImePurpose, InputStateSnapshot, ResizeDirection, Theme,
+    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
+    WindowLevel,
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -109,6 +117,7 @@ pub(crate) struct State {
 
     // Used to prevent redundant events.
     previous_scale_factor: Cell<f64>,
+    previous_safe_area: Cell<PhysicalInsets<u32>>,
 
     /// The current resize increments for the window content.
     surface_resize_increments: Cell<NSSize>,
@@ -140,6 +149,9 @@ pub(crate) struct State {
     is_simple_fullscreen: Cell<bool>,
     saved_style: Cell<Option<NSWindowStyleMask>>,
     is_borderless_game: Cell<bool>,
+    /// The last cursor grab mode that was successfully applied.
+    cursor_grab_mode: Cell<CursorGrabMode>,
+    occluded_redraw: Cell<OccludedRedraw>,
 }
 
 define_class!(
@@ -176,6 +188,8 @@ define_class!(
             trace_scope!("windowDidResize:");
             // NOTE: WindowEvent::SurfaceResized is reported using NSViewFrameDidChangeNotification.
             self.emit_move_event();
+            // Entering fullscreen on a display with a notch changes the safe area.
+            self.emit_safe_area_event();
         }
 
         #[unsafe(method(windowWillStartLiveResize:))]
@@ -361,6 +375,12 @@ define_class!(
             trace_scope!("windowDidChangeOcclusionState:");
             let visible = self.window().occlusionState().contains(NSWindowOcclusionState::Visible);
             self.queue_event(WindowEvent::Occluded(!visible));
+
+            let mut occluded_redraw = self.ivars().occluded_redraw.get();
+            if occluded_redraw.set_occluded(!visible) {
+                self.ivars().app_state.queue_redraw(window_id(self.window()));
+            }
+            self.ivars().occluded_redraw.set(occluded_redraw);
         }
 
         #[unsafe(method(windowDidChangeScreen:))]
@@ -618,11 +638,7 @@ fn new_window(
 
         if attrs.platform_specific.fullsize_content_view {
             // NOTE: If we decide to add an option to change this at runtime, we must emit a
-            // `SurfaceResized` event to let applications know that the safe area changed.
-            //
-            // An alternative would be to add a `WindowEvent::SafeAreaChanged` event, this could be
-            // done with an observer on `safeAreaRect` / `contentLayoutRect`, see:
-            // <https://github.com/rust-windowing/winit/issues/3911>
+            // `SafeAreaChanged` event to let applications know that the safe area changed.
             masks |= NSWindowStyleMask::FullSizeContentView;
         }
 
@@ -825,6 +841,7 @@ impl WindowDelegate {
             window: window.retain(),
             previous_position: Cell::new(flip_window_screen_coordinates(window.frame())),
             previous_scale_factor: Cell::new(scale_factor),
+            previous_safe_area: Cell::new(PhysicalInsets::default()),
             surface_resize_increments: Cell::new(surface_resize_increments),
             decorations: Cell::new(attrs.decorations),
             resizable: Cell::new(attrs.resizable),
@@ -838,8 +855,11 @@ impl WindowDelegate {
             is_simple_fullscreen: Cell::new(false),
             saved_style: Cell::new(None),
             is_borderless_game: Cell::new(attrs.platform_specific.borderless_game),
+            cursor_grab_mode: Cell::new(CursorGrabMode::None),
+            occluded_redraw: Cell::new(OccludedRedraw::new(attrs.redraw_while_occluded)),
         });
         let delegate: Retained<WindowDelegate> = unsafe { msg_send![super(delegate), init] };
+        delegate.ivars().previous_safe_area.set(delegate.safe_area());
 
         window.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
 
@@ -936,6 +956,14 @@ impl WindowDelegate {
             window.setContentSize(size);
         }
         self.queue_event(WindowEvent::SurfaceResized(physical_size));
+        self.emit_safe_area_event();
+    }
+
+    fn emit_safe_area_event(&self) {
+        let safe_area = self.safe_area();
+        if self.ivars().previous_safe_area.replace(safe_area) != safe_area {
+            self.queue_event(WindowEvent::SafeAreaChanged(safe_area));
+        }
     }
 
     fn emit_move_event(&self) {
@@ -958,7 +986,7 @@ impl WindowDelegate {
     }
 
     pub fn set_title(&self, title: &str) {
-        self.window().setTitle(&NSString::from_str(title))
+        set_window_title(self.window(), title, post_accessibility_notification)
     }
 
     pub fn set_transparent(&self, transparent: bool) {
@@ -1008,7 +1036,12 @@ impl WindowDelegate {
     }
 
     pub fn request_redraw(&self) {
-        self.ivars().app_state.queue_redraw(window_id(self.window()));
+        let mut occluded_redraw = self.ivars().occluded_redraw.get();
+        let deliver = occluded_redraw.request();
+        self.ivars().occluded_redraw.set(occluded_redraw);
+        if deliver {
+            self.ivars().app_state.queue_redraw(window_id(self.window()));
+        }
     }
 
     #[inline]
@@ -1078,15 +1111,7 @@ impl WindowDelegate {
             // This includes NSWindowStyleMask::FullSizeContentView.
             let layout_rect = unsafe { self.window().contentLayoutRect() };
 
-            // Calculate the insets from window coordinates in AppKit's coordinate system.
-            NSEdgeInsets {
-                top: (window_rect.size.height + window_rect.origin.y)
-                    - (layout_rect.size.height + layout_rect.origin.y),
-                left: layout_rect.origin.x - window_rect.origin.x,
-                bottom: layout_rect.origin.y - window_rect.origin.y,
-                right: (window_rect.size.width + window_rect.origin.x)
-                    - (layout_rect.size.width + layout_rect.origin.x),
-            }
+            insets_of_layout_rect(window_rect, layout_rect)
         };
         let insets = LogicalInsets::new(insets.top, insets.left, insets.bottom, insets.right);
         insets.to_physical(self.scale_factor())
@@ -1268,6 +1293,12 @@ impl WindowDelegate {
 
         // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
         cgerr(unsafe { CGAssociateMouseAndMouseCursorPosition(associate_mouse_cursor) })?;
+        let old_mode = self.ivars().cursor_grab_mode.replace(mode);
+
+        let locked = mode == CursorGrabMode::Locked;
+        if locked != (old_mode == CursorGrabMode::Locked) {
+            self.queue_event(WindowEvent::PointerLock { locked });
+        }
 
         Ok(())
     }
@@ -1281,6 +1312,16 @@ impl WindowDelegate {
         }
     }
 
+    pub fn capture_input_state(&self, cursor: Cursor) -> InputStateSnapshot {
+        let view = self.view();
+        InputStateSnapshot {
+            cursor_grab: self.ivars().cursor_grab_mode.get(),
+            cursor_visible: view.cursor_visible(),
+            cursor,
+            ime_allowed: view.ime_allowed(),
+        }
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.window().backingScaleFactor() as _
@@ -1288,17 +1329,12 @@ impl WindowDelegate {
 
     #[inline]
     pub fn set_cursor_position(&self, cursor_position: Position) -> Result<(), RequestError> {
-        let content_rect = self.window().contentRectForFrameRect(self.window().frame());
-        let window_position = flip_window_screen_coordinates(content_rect);
-        let cursor_position = cursor_position.to_logical::<CGFloat>(self.scale_factor());
-        let point = CGPoint {
-            x: window_position.x + cursor_position.x,
-            y: window_position.y + cursor_position.y,
-        };
-        cgerr(unsafe { CGWarpMouseCursorPosition(point) })?;
-        cgerr(unsafe { CGAssociateMouseAndMouseCursorPosition(true) })?;
+        warp_cursor(&self.window(), cursor_position.to_logical(self.scale_factor()))
+    }
 
-        Ok(())
+    #[inline]
+    pub fn animate_cursor_to(&self, target: PhysicalPosition<i32>, duration: Duration) {
+        self.view().animate_cursor_to(target, duration)
     }
 
     #[inline]
@@ -1515,7 +1551,9 @@ impl WindowDelegate {
             }
 
             let video_mode =
-                match monitor.video_modes_handles().find(|mode| &mode.mode == video_mode) {
+                match find_exclusive_mode(monitor.video_modes_handles(), video_mode, |mode| {
+                    &mode.mode
+                }) {
                     Some(video_mode) => video_mode,
                     None => return,
                 };
@@ -1676,9 +1714,8 @@ impl WindowDelegate {
         // `setRepresentedFilename`, but that's semantically distinct and should
         // only be used when the window is in some way representing a specific
         // file/directory. For instance, Terminal.app uses this for the CWD.
-        // Anyway, that should eventually be implemented as
-        // `WindowAttributesExt::with_represented_file` or something, and doesn't
-        // have anything to do with `set_window_icon`.
+        // That is exposed as `WindowExtMacOS::set_represented_filename`, and
+        // doesn't have anything to do with `set_window_icon`.
         // https://developer.apple.com/library/content/documentation/Cocoa/Conceptual/WinPanel/Tasks/SettingWindowTitle.html
     }
 
@@ -1702,6 +1739,35 @@ impl WindowDelegate {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.view().set_key_repeat_enabled(enabled);
+    }
+
+    #[inline]
+    pub fn keyboard_layout_name(&self) -> Option<String> {
+        super::event::keyboard_layout_name()
+    }
+
+    #[inline]
+    pub fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        super::event::keyboard_layout_direction()
+    }
+
+    #[inline]
+    pub fn key_for_physical(&self, key: PhysicalKey) -> Option<Key> {
+        super::event::key_for_physical(key)
+    }
+
+    #[inline]
+    pub fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        super::event::compose_dead_key(dead, base)
+    }
+
+    pub fn current_modifiers(&self) -> ModifiersState {
+        self.view().reported_modifiers(super::event::current_modifiers()).state()
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         let mtm = MainThreadMarker::from(self);
@@ -1810,6 +1876,20 @@ impl WindowDelegate {
     }
 }
 
+/// Warp the cursor to `position`, relative to the content area of `window`.
+pub(super) fn warp_cursor(
+    window: &NSWindow,
+    position: LogicalPosition<CGFloat>,
+) -> Result<(), RequestError> {
+    let content_rect = window.contentRectForFrameRect(window.frame());
+    let window_position = flip_window_screen_coordinates(content_rect);
+    let point = CGPoint { x: window_position.x + position.x, y: window_position.y + position.y };
+    cgerr(unsafe { CGWarpMouseCursorPosition(point) })?;
+    cgerr(unsafe { CGAssociateMouseAndMouseCursorPosition(true) })?;
+
+    Ok(())
+}
+
 fn restore_and_release_display(monitor: &MonitorHandle) {
     let available_monitors = monitor::available_monitors();
     if available_monitors.contains(monitor) {
@@ -1960,6 +2040,29 @@ impl WindowExtMacOS for WindowDelegate {
         unsafe { self.window().tabbedWindows() }.map(|windows| windows.len()).unwrap_or(1)
     }
 
+    #[inline]
+    fn set_tabbing_mode(&self, mode: TabbingMode) {
+        self.window().setTabbingMode(tabbing_mode_to_ns(mode))
+    }
+
+    #[inline]
+    fn tabbing_mode(&self) -> TabbingMode {
+        tabbing_mode_from_ns(self.window().tabbingMode())
+    }
+
+    fn add_tabbed_window(&self, tab: &dyn CoreWindow) {
+        let mtm = MainThreadMarker::from(self);
+        let Some(tab) = tab.as_any().downcast_ref::<crate::platform_impl::Window>() else {
+            return;
+        };
+        let (window, tab) = (self.window(), tab.ns_window(mtm));
+        let tab_group = unsafe { window.tabbedWindows() };
+        let tabbed = tab_group.iter().flat_map(|windows| windows.iter());
+        if needs_tabbing(window, tab, tabbed.map(|tabbed| Retained::as_ptr(&tabbed))) {
+            unsafe { window.addTabbedWindow_ordered(tab, NSWindowOrderingMode::Above) };
+        }
+    }
+
     fn is_document_edited(&self) -> bool {
         self.window().isDocumentEdited()
     }
@@ -1968,6 +2071,15 @@ impl WindowExtMacOS for WindowDelegate {
         self.window().setDocumentEdited(edited)
     }
 
+    fn set_represented_filename(&self, path: Option<PathBuf>) {
+        let filename = represented_filename_to_ns(path.as_deref());
+        self.window().setRepresentedFilename(&NSString::from_str(&filename))
+    }
+
+    fn represented_filename(&self) -> Option<PathBuf> {
+        represented_filename_from_ns(&self.window().representedFilename().to_string())
+    }
+
     fn set_option_as_alt(&self, option_as_alt: OptionAsAlt) {
         self.view().set_option_as_alt(option_as_alt);
     }
@@ -2052,3 +2164,170 @@ fn theme_to_appearance(theme: Option<Theme>) -> Option<Retained<NSAppearance>> {
         None
     }
 }
+
+/// `NSWindow` uses an empty string to mean that no file is represented.
+fn represented_filename_to_ns(path: Option<&Path>) -> String {
+    path.map(|path| path.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn represented_filename_from_ns(filename: &str) -> Option<PathBuf> {
+    (!filename.is_empty()).then(|| PathBuf::from(filename))
+}
+
+fn tabbing_mode_to_ns(mode: TabbingMode) -> NSWindowTabbingMode {
+    match mode {
+        TabbingMode::Automatic => NSWindowTabbingMode::Automatic,
+        TabbingMode::Preferred => NSWindowTabbingMode::Preferred,
+        TabbingMode::Disallowed => NSWindowTabbingMode::Disallowed,
+    }
+}
+
+fn tabbing_mode_from_ns(mode: NSWindowTabbingMode) -> TabbingMode {
+    match mode {
+        NSWindowTabbingMode::Preferred => TabbingMode::Preferred,
+        NSWindowTabbingMode::Disallowed => TabbingMode::Disallowed,
+        _ => TabbingMode::Automatic,
+    }
+}
+
+/// Whether `tab` must be added to the tab group of `window`, given the windows already in it.
+///
+/// AppKit throws when a window is added to itself or to a group it's already in.
+fn needs_tabbing<T: ?Sized>(
+    window: &T,
+    tab: &T,
+    mut tabbed: impl Iterator<Item = *const T>,
+) -> bool {
+    !ptr::eq(window, tab) && !tabbed.any(|tabbed| ptr::eq(tabbed, tab))
+}
+
+/// Set the title of the window, and notify the assistive technologies of the change with
+/// `post_notification`.
+fn set_window_title(
+    window: &NSWindow,
+    title: &str,
+    post_notification: impl FnOnce(&NSWindow, &NSAccessibilityNotificationName),
+) {
+    window.setTitle(&NSString::from_str(title));
+    post_notification(window, unsafe { NSAccessibilityTitleChangedNotification });
+}
+
+/// Post an accessibility `notification` about `window`.
+fn post_accessibility_notification(
+    window: &NSWindow,
+    notification: &NSAccessibilityNotificationName,
+) {
+    // SAFETY: Windows are accessibility elements.
+    unsafe { NSAccessibilityPostNotification(window, notification) };
+}
+
+/// The insets of the content layout rectangle in the content rectangle, both in window coordinates
+/// in AppKit's coordinate system, with the origin at the bottom-left corner.
+fn insets_of_layout_rect(window_rect: NSRect, layout_rect: NSRect) -> NSEdgeInsets {
+    NSEdgeInsets {
+        top: (window_rect.size.height + window_rect.origin.y)
+            - (layout_rect.size.height + layout_rect.origin.y),
+        left: layout_rect.origin.x - window_rect.origin.x,
+        bottom: layout_rect.origin.y - window_rect.origin.y,
+        right: (window_rect.size.width + window_rect.origin.x)
+            - (layout_rect.size.width + layout_rect.origin.x),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::ptr;
+
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::{
+        NSAccessibilityTitleChangedNotification, NSBackingStoreType, NSWindow, NSWindowStyleMask,
+    };
+    use objc2_foundation::{NSPoint, NSRect, NSSize};
+
+    use super::{
+        insets_of_layout_rect, needs_tabbing, represented_filename_from_ns,
+        represented_filename_to_ns, set_window_title, tabbing_mode_from_ns, tabbing_mode_to_ns,
+    };
+    use crate::platform::macos::TabbingMode;
+
+    #[test]
+    fn set_title_posts_accessibility_notification() {
+        let Some(mtm) = MainThreadMarker::new() else { return };
+
+        let window = unsafe {
+            NSWindow::initWithContentRect_styleMask_backing_defer(
+                NSWindow::alloc(mtm),
+                NSRect::ZERO,
+                NSWindowStyleMask::Titled,
+                NSBackingStoreType::Buffered,
+                false,
+            )
+        };
+        unsafe { window.setReleasedWhenClosed(false) };
+
+        let mut posted = Vec::new();
+        set_window_title(&window, "Title", |element, notification| {
+            assert!(ptr::eq(element, &*window));
+            posted.push(notification.to_string());
+        });
+        assert_eq!(window.title().to_string(), "Title");
+        let expected = unsafe { NSAccessibilityTitleChangedNotification }.to_string();
+        assert_eq!(posted, [expected]);
+    }
+
+    #[test]
+    fn tabbing_mode_round_trip() {
+        for mode in [TabbingMode::Automatic, TabbingMode::Preferred, TabbingMode::Disallowed] {
+            assert_eq!(tabbing_mode_from_ns(tabbing_mode_to_ns(mode)), mode);
+        }
+    }
+
+    #[test]
+    fn add_tabbed_window_bookkeeping() {
+        let windows = [1, 2, 3];
+        let [first, second, third] = &windows;
+
+        // A window is never added to itself.
+        assert!(!needs_tabbing(first, first, std::iter::empty()));
+        // An untabbed window starts a new group.
+        assert!(needs_tabbing(first, second, std::iter::empty()));
+        // Windows already in the group are skipped.
+        let group: [*const i32; 2] = [first, second];
+        assert!(!needs_tabbing(first, second, group.into_iter()));
+        assert!(needs_tabbing(first, third, group.into_iter()));
+    }
+
+    #[test]
+    fn represented_filename_round_trip() {
+        let path = Path::new("/Users/winit/Documents/notes.txt");
+        let filename = represented_filename_to_ns(Some(path));
+        assert_eq!(filename, "/Users/winit/Documents/notes.txt");
+        assert_eq!(represented_filename_from_ns(&filename), Some(PathBuf::from(path)));
+
+        let cleared = represented_filename_to_ns(None);
+        assert_eq!(cleared, "");
+        assert_eq!(represented_filename_from_ns(&cleared), None);
+    }
+
+    #[test]
+    fn safe_area_insets_of_layout_rect() {
+        let window_rect = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(800.0, 600.0));
+
+        // The whole content is visible.
+        let insets = insets_of_layout_rect(window_rect, window_rect);
+        assert_eq!((insets.top, insets.left, insets.bottom, insets.right), (0.0, 0.0, 0.0, 0.0));
+
+        // A full-size content view below the title bar, the origin is at the bottom-left.
+        let layout_rect = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(800.0, 572.0));
+        let insets = insets_of_layout_rect(window_rect, layout_rect);
+        assert_eq!((insets.top, insets.left, insets.bottom, insets.right), (28.0, 0.0, 0.0, 0.0));
+
+        let layout_rect = NSRect::new(NSPoint::new(10.0, 20.0), NSSize::new(770.0, 550.0));
+        let insets = insets_of_layout_rect(window_rect, layout_rect);
+        assert_eq!(
+            (insets.top, insets.left, insets.bottom, insets.right),
+            (30.0, 10.0, 20.0, 20.0)
+        );
+    }
+}
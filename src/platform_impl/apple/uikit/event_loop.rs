@@ -1,7 +1,7 @@
 use std::ffi::{c_char, c_int, c_void};
 use std::ptr::{self, NonNull};
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use objc2::rc::Retained;
 use objc2::runtime::ProtocolObject;
@@ -28,9 +28,10 @@ use super::{app_state, monitor, MonitorHandle};
 use crate::application::ApplicationHandler;
 use crate::error::{EventLoopError, NotSupportedError, RequestError};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
 };
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform_impl::Window;
@@ -41,6 +42,15 @@ pub(crate) struct ActiveEventLoop {
     pub(super) mtm: MainThreadMarker,
 }
 
+impl ActiveEventLoop {
+    pub(crate) fn common_state(&self) -> &CommonEventLoopState {
+        // The `ActiveEventLoop` is recreated for every event, but like the `AppState`, the state
+        // outlives it: there is only one event loop, and it runs until the process exits.
+        static COMMON_STATE: OnceLock<CommonEventLoopState> = OnceLock::new();
+        COMMON_STATE.get_or_init(Default::default)
+    }
+}
+
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> CoreEventLoopProxy {
         CoreEventLoopProxy::new(AppState::get_mut(self.mtm).event_loop_proxy().clone())
@@ -50,7 +60,9 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         window_attributes: crate::window::WindowAttributes,
     ) -> Result<Box<dyn CoreWindow>, RequestError> {
-        Ok(Box::new(Window::new(self, window_attributes)?))
+        let window: Box<dyn CoreWindow> = Box::new(Window::new(self, window_attributes)?);
+        self.common_state().register_window(&*window);
+        Ok(window)
     }
 
     fn create_custom_cursor(
@@ -80,6 +92,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        WindowingInfo::new(WindowingBackend::UIKit, None, WindowingCapabilities::empty())
+    }
+
     fn control_flow(&self) -> ControlFlow {
         AppState::get_mut(self.mtm).control_flow()
     }
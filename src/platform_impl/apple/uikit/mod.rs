@@ -23,6 +23,13 @@ pub(crate) use crate::platform_impl::Fullscreen;
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyEventExtra {}
 
+impl KeyEventExtra {
+    #[cfg(any(feature = "test-util", feature = "serde"))]
+    pub(crate) fn synthetic(_: Option<&smol_str::SmolStr>) -> Self {
+        Self {}
+    }
+}
+
 #[derive(Debug)]
 pub enum OsError {}
 
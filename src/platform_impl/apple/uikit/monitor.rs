@@ -164,6 +164,14 @@ impl MonitorHandle {
         self.ui_screen.get_on_main(|ui_screen| ui_screen.nativeScale()) as f64
     }
 
+    pub fn bit_depth(&self) -> Option<u32> {
+        None
+    }
+
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        None
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         Some(run_on_main(|mtm| {
             VideoModeHandle::new(
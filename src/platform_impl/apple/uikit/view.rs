@@ -16,7 +16,7 @@ use tracing::debug;
 
 use super::app_state::{self, EventWrapper};
 use super::window::WinitUIWindow;
-use crate::dpi::PhysicalPosition;
+use crate::dpi::{LogicalInsets, PhysicalPosition};
 use crate::event::{
     ButtonSource, ElementState, FingerId, Force, KeyEvent, PointerKind, PointerSource, TouchPhase,
     WindowEvent,
@@ -127,6 +127,18 @@ define_class!(
         #[unsafe(method(safeAreaInsetsDidChange))]
         fn safe_area_changed(&self) {
             debug!("safeAreaInsetsDidChange was called, requesting redraw");
+            let mtm = MainThreadMarker::new().unwrap();
+            // Only called on iOS 11.0 and later, where `safeAreaInsets` is available.
+            if let Some(window) = self.window() {
+                let insets = self.safeAreaInsets();
+                let insets =
+                    LogicalInsets::new(insets.top, insets.left, insets.bottom, insets.right)
+                        .to_physical(self.contentScaleFactor() as f64);
+                app_state::handle_nonuser_event(mtm, EventWrapper::Window {
+                    window_id: window.id(),
+                    event: WindowEvent::SafeAreaChanged(insets),
+                });
+            }
             // When the safe area changes we want to make sure to emit a redraw event
             self.setNeedsDisplay();
         }
@@ -545,6 +557,7 @@ impl WinitView {
                             } else {
                                 ButtonSource::Touch { finger_id, force }
                             },
+                            timestamp: None,
                         },
                     });
                 },
@@ -565,6 +578,7 @@ impl WinitView {
                             primary,
                             position,
                             source,
+                            timestamp: None,
                         },
                     });
                 },
@@ -594,6 +608,7 @@ impl WinitView {
                                 } else {
                                     ButtonSource::Touch { finger_id, force }
                                 },
+                                timestamp: None,
                             },
                         });
                     }
@@ -639,12 +654,15 @@ impl WinitView {
                             } else {
                                 None
                             },
+                            composed: false,
                             state,
                             location: KeyLocation::Standard,
                             repeat: false,
                             logical_key: Key::Character(text.clone()),
+                            key_without_modifiers: Key::Character(text.clone()),
                             physical_key: PhysicalKey::Unidentified(NativeKeyCode::Unidentified),
                             platform_specific: KeyEventExtra {},
+                            timestamp: None,
                         },
                         is_synthetic: false,
                     },
@@ -666,11 +684,14 @@ impl WinitView {
                     event: KeyEvent {
                         state,
                         logical_key: Key::Named(NamedKey::Backspace),
+                        key_without_modifiers: Key::Named(NamedKey::Backspace),
                         physical_key: PhysicalKey::Code(KeyCode::Backspace),
                         platform_specific: KeyEventExtra {},
                         repeat: false,
                         location: KeyLocation::Standard,
                         text: None,
+                        composed: false,
+                        timestamp: None,
                     },
                     is_synthetic: false,
                 },
@@ -1,6 +1,8 @@
 #![allow(clippy::unnecessary_cast)]
 
 use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
 
 use dispatch2::MainThreadBound;
 use objc2::rc::Retained;
@@ -17,7 +19,7 @@ use super::app_state::EventWrapper;
 use super::view::WinitView;
 use super::view_controller::WinitViewController;
 use super::{app_state, monitor, ActiveEventLoop, Fullscreen, MonitorHandle};
-use crate::cursor::Cursor;
+use crate::cursor::{CurrentCursor, Cursor};
 use crate::dpi::{
     LogicalInsets, LogicalPosition, LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize,
     Position, Size,
@@ -25,10 +27,12 @@ use crate::dpi::{
 use crate::error::{NotSupportedError, RequestError};
 use crate::event::WindowEvent;
 use crate::icon::Icon;
-use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
+use crate::monitor::{find_exclusive_mode, MonitorHandle as CoreMonitorHandle};
 use crate::platform::ios::{ScreenEdge, StatusBarStyle, ValidOrientations};
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
+    CommonWindowState, CursorGrabMode, HitTestRegion, ImePurpose, InputStateSnapshot,
+    ResizeDirection, SurfaceTransform, Theme, UserAttentionType, Window as CoreWindow,
     WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
@@ -82,7 +86,9 @@ impl WinitUIWindow {
             Some(Fullscreen::Exclusive(ref monitor, ref video_mode)) => {
                 let screen = monitor.ui_screen(mtm);
                 if let Some(video_mode) =
-                    monitor.video_modes_handles().find(|mode| &mode.mode == video_mode)
+                    find_exclusive_mode(monitor.video_modes_handles(), video_mode, |mode| {
+                        &mode.mode
+                    })
                 {
                     screen.setCurrentMode(Some(video_mode.screen_mode(mtm)));
                 }
@@ -266,6 +272,12 @@ impl Inner {
         debug!("`Window::set_cursor_visible` is ignored on iOS")
     }
 
+    pub fn capture_input_state(&self, cursor: Cursor) -> InputStateSnapshot {
+        // IME is allowed while the view is showing the soft keyboard, see `set_ime_allowed`.
+        let ime_allowed: bool = unsafe { msg_send![&self.view, isFirstResponder] };
+        InputStateSnapshot { cursor, ime_allowed, ..Default::default() }
+    }
+
     pub fn drag_window(&self) -> Result<(), NotSupportedError> {
         Err(NotSupportedError::new("drag_window is not supported"))
     }
@@ -305,7 +317,9 @@ impl Inner {
             Some(Fullscreen::Exclusive(monitor, video_mode)) => {
                 let uiscreen = monitor.ui_screen(mtm);
                 if let Some(video_mode) =
-                    monitor.video_modes_handles().find(|mode| &mode.mode == video_mode)
+                    find_exclusive_mode(monitor.video_modes_handles(), video_mode, |mode| {
+                        &mode.mode
+                    })
                 {
                     uiscreen.setCurrentMode(Some(video_mode.screen_mode(mtm)));
                 }
@@ -393,6 +407,10 @@ impl Inner {
         warn!("`Window::set_ime_purpose` is ignored on iOS")
     }
 
+    pub fn set_key_repeat_enabled(&self, _enabled: bool) {
+        warn!("`Window::set_key_repeat_enabled` is ignored on iOS")
+    }
+
     pub fn focus_window(&self) {
         warn!("`Window::set_focus` is ignored on iOS")
     }
@@ -461,6 +479,8 @@ impl Inner {
 
 pub struct Window {
     inner: MainThreadBound<Inner>,
+    current_cursor: CurrentCursor,
+    pub(crate) common_state: Arc<CommonWindowState>,
 }
 
 impl Window {
@@ -469,6 +489,7 @@ impl Window {
         window_attributes: WindowAttributes,
     ) -> Result<Window, RequestError> {
         let mtm = event_loop.mtm;
+        let common_state = Arc::new(CommonWindowState::new(&window_attributes));
 
         if window_attributes.min_surface_size.is_some() {
             warn!("`WindowAttributes::min_surface_size` is ignored on iOS");
@@ -512,7 +533,11 @@ impl Window {
         window.makeKeyAndVisible();
 
         let inner = Inner { window, view_controller, view, gl_or_metal_backed };
-        Ok(Window { inner: MainThreadBound::new(inner, mtm) })
+        Ok(Window {
+            inner: MainThreadBound::new(inner, mtm),
+            current_cursor: CurrentCursor::default(),
+            common_state,
+        })
     }
 
     pub(crate) fn maybe_wait_on_main<R: Send>(&self, f: impl FnOnce(&Inner) -> R + Send) -> R {
@@ -601,6 +626,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.safe_area())
     }
 
+    fn surface_transform(&self) -> SurfaceTransform {
+        SurfaceTransform::Identity
+    }
+
     fn set_min_surface_size(&self, min_size: Option<Size>) {
         self.maybe_wait_on_main(|delegate| delegate.set_min_surface_size(min_size))
     }
@@ -629,6 +658,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_blur(blur));
     }
 
+    fn set_tearing_allowed(&self, _allowed: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_tearing_allowed is not supported").into())
+    }
+
     fn set_visible(&self, visible: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_visible(visible));
     }
@@ -669,6 +702,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.is_maximized())
     }
 
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("move_to_workspace is not supported").into())
+    }
+
     fn set_fullscreen(&self, fullscreen: Option<crate::window::Fullscreen>) {
         self.maybe_wait_on_main(|delegate| delegate.set_fullscreen(fullscreen.map(Into::into)))
     }
@@ -705,6 +746,32 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.maybe_wait_on_main(|delegate| delegate.set_key_repeat_enabled(enabled));
+    }
+
+    fn keyboard_layout_name(&self) -> Option<String> {
+        None
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        None
+    }
+
+    fn key_for_physical(&self, _key: PhysicalKey) -> Option<Key> {
+        None
+    }
+
+    fn compose_dead_key(&self, _dead: char, _base: char) -> Option<char> {
+        None
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        ModifiersState::empty()
+    }
+
+    fn set_hit_test_regions(&self, _regions: &[HitTestRegion]) {}
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -734,13 +801,22 @@ impl CoreWindow for Window {
     }
 
     fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
         self.maybe_wait_on_main(|delegate| delegate.set_cursor(cursor));
     }
 
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
+
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError> {
         Ok(self.maybe_wait_on_main(|delegate| delegate.set_cursor_position(position))?)
     }
 
+    fn animate_cursor_to(&self, _: PhysicalPosition<i32>, _: Duration) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("animate_cursor_to is not supported").into())
+    }
+
     fn set_cursor_grab(&self, mode: crate::window::CursorGrabMode) -> Result<(), RequestError> {
         Ok(self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab(mode))?)
     }
@@ -749,6 +825,11 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_visible(visible))
     }
 
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        let cursor = self.current_cursor.get();
+        self.maybe_wait_on_main(|delegate| delegate.capture_input_state(cursor))
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         Ok(self.maybe_wait_on_main(|delegate| delegate.drag_window())?)
     }
@@ -0,0 +1,266 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
+
+use super::Window;
+use crate::application::ApplicationHandler;
+use crate::error::{EventLoopError, NotSupportedError, RequestError};
+use crate::event::{StartCause, WindowEvent};
+use crate::event_loop::{
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
+    EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
+};
+use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::window::{
+    CustomCursor as CoreCustomCursor, CustomCursorSource, Theme, Window as CoreWindow,
+    WindowAttributes, WindowId,
+};
+
+/// The events the windows generate and the pending wake ups, shared with the windows and the
+/// proxies.
+#[derive(Debug, Default)]
+pub(super) struct Shared {
+    queue: Mutex<Queue>,
+    wake: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    events: VecDeque<(WindowId, WindowEvent)>,
+    redraws: Vec<WindowId>,
+    proxy_wake_up: bool,
+}
+
+impl Queue {
+    fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.redraws.is_empty() && !self.proxy_wake_up
+    }
+}
+
+impl Shared {
+    pub(super) fn push_event(&self, window_id: WindowId, event: WindowEvent) {
+        self.queue.lock().unwrap().events.push_back((window_id, event));
+        self.wake.notify_one();
+    }
+
+    pub(super) fn destroy(&self, window_id: WindowId) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.redraws.retain(|&id| id != window_id);
+        queue.events.push_back((window_id, WindowEvent::Destroyed));
+        self.wake.notify_one();
+    }
+
+    pub(super) fn request_redraw(&self, window_id: WindowId) {
+        let mut queue = self.queue.lock().unwrap();
+        if !queue.redraws.contains(&window_id) {
+            queue.redraws.push(window_id);
+            self.wake.notify_one();
+        }
+    }
+
+    /// Block until there is something to process or the `deadline` is reached, returning whether
+    /// the wait was cancelled.
+    fn wait(&self, deadline: Option<Instant>) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+        while queue.is_empty() {
+            queue = match deadline {
+                None => self.wake.wait(queue).unwrap(),
+                Some(deadline) => {
+                    let Some(timeout) = deadline.checked_duration_since(Instant::now()) else {
+                        return false;
+                    };
+                    self.wake.wait_timeout(queue, timeout).unwrap().0
+                },
+            };
+        }
+
+        true
+    }
+}
+
+pub struct EventLoop {
+    window_target: ActiveEventLoop,
+}
+
+impl EventLoop {
+    pub(crate) fn new() -> Self {
+        let shared = Arc::new(Shared::default());
+        Self {
+            window_target: ActiveEventLoop {
+                control_flow: Cell::new(ControlFlow::default()),
+                exit: Cell::new(false),
+                event_loop_proxy: Arc::new(EventLoopProxy { shared: shared.clone() }),
+                shared,
+                common_state: Default::default(),
+            },
+        }
+    }
+
+    pub fn run_app<A: ApplicationHandler>(self, mut app: A) -> Result<(), EventLoopError> {
+        let window_target = &self.window_target;
+        let mut start_cause = StartCause::Init;
+        loop {
+            app.new_events(window_target, start_cause);
+
+            if start_cause == StartCause::Init {
+                app.can_create_surfaces(window_target);
+            }
+
+            // Don't hold the lock while dispatching, the handler may generate more events.
+            loop {
+                let mut queue = window_target.shared.queue.lock().unwrap();
+                if let Some((window_id, event)) = queue.events.pop_front() {
+                    drop(queue);
+                    app.window_event(window_target, window_id, event);
+                } else if std::mem::take(&mut queue.proxy_wake_up) {
+                    drop(queue);
+                    app.proxy_wake_up(window_target);
+                } else {
+                    break;
+                }
+            }
+
+            // Redraws requested while redrawing are delivered in the next iteration.
+            let redraws = std::mem::take(&mut window_target.shared.queue.lock().unwrap().redraws);
+            for window_id in redraws {
+                app.window_event(window_target, window_id, WindowEvent::RedrawRequested);
+            }
+
+            app.about_to_wait(window_target);
+
+            if window_target.exiting() {
+                break;
+            }
+
+            let start = Instant::now();
+            start_cause = match window_target.control_flow() {
+                ControlFlow::Poll => StartCause::Poll,
+                ControlFlow::Wait => {
+                    window_target.shared.wait(None);
+                    StartCause::WaitCancelled { start, requested_resume: None }
+                },
+                ControlFlow::WaitUntil(requested_resume) => {
+                    if window_target.shared.wait(Some(requested_resume)) {
+                        StartCause::WaitCancelled {
+                            start,
+                            requested_resume: Some(requested_resume),
+                        }
+                    } else {
+                        StartCause::ResumeTimeReached { start, requested_resume }
+                    }
+                },
+            };
+        }
+
+        app.exiting(window_target);
+
+        Ok(())
+    }
+
+    pub fn window_target(&self) -> &dyn RootActiveEventLoop {
+        &self.window_target
+    }
+}
+
+pub struct EventLoopProxy {
+    shared: Arc<Shared>,
+}
+
+impl EventLoopProxyProvider for EventLoopProxy {
+    fn wake_up(&self) {
+        self.shared.queue.lock().unwrap().proxy_wake_up = true;
+        self.shared.wake.notify_one();
+    }
+}
+
+pub struct ActiveEventLoop {
+    control_flow: Cell<ControlFlow>,
+    exit: Cell<bool>,
+    pub(super) shared: Arc<Shared>,
+    event_loop_proxy: Arc<EventLoopProxy>,
+    pub(crate) common_state: CommonEventLoopState,
+}
+
+impl RootActiveEventLoop for ActiveEventLoop {
+    fn create_proxy(&self) -> CoreEventLoopProxy {
+        CoreEventLoopProxy::new(self.event_loop_proxy.clone())
+    }
+
+    fn create_window(
+        &self,
+        window_attributes: WindowAttributes,
+    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+        let window: Box<dyn CoreWindow> = Box::new(Window::new(self, window_attributes));
+        self.common_state.register_window(&*window);
+        Ok(window)
+    }
+
+    fn create_custom_cursor(
+        &self,
+        _: CustomCursorSource,
+    ) -> Result<CoreCustomCursor, RequestError> {
+        Err(NotSupportedError::new("create_custom_cursor is not supported").into())
+    }
+
+    fn available_monitors(&self) -> Box<dyn Iterator<Item = CoreMonitorHandle>> {
+        Box::new(std::iter::empty())
+    }
+
+    fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
+        None
+    }
+
+    fn listen_device_events(&self, _allowed: DeviceEvents) {}
+
+    fn system_theme(&self) -> Option<Theme> {
+        None
+    }
+
+    fn windowing_info(&self) -> WindowingInfo {
+        // There are no decorations, moves and resizes can't be started, and the window level is
+        // ignored.
+        WindowingInfo::new(WindowingBackend::Headless, None, WindowingCapabilities::empty())
+    }
+
+    fn set_control_flow(&self, control_flow: ControlFlow) {
+        self.control_flow.set(control_flow)
+    }
+
+    fn control_flow(&self) -> ControlFlow {
+        self.control_flow.get()
+    }
+
+    fn exit(&self) {
+        self.exit.set(true);
+    }
+
+    fn exiting(&self) -> bool {
+        self.exit.get()
+    }
+
+    fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
+        CoreOwnedDisplayHandle::new(Arc::new(OwnedDisplayHandle))
+    }
+
+    fn rwh_06_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
+        self
+    }
+}
+
+impl rwh_06::HasDisplayHandle for ActiveEventLoop {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct OwnedDisplayHandle;
+
+impl rwh_06::HasDisplayHandle for OwnedDisplayHandle {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
@@ -0,0 +1,11 @@
+//! A virtual backend without a display, enabled by the `headless` cargo feature.
+//!
+//! Windows only exist as in-memory state: they never show anything, and the only input comes from
+//! the `EventInjector`. This allows running application logic in CI. The backend is available next
+//! to the native one, through `EventLoopBuilderExtHeadless::build_headless`.
+
+pub(crate) use self::event_loop::{ActiveEventLoop, EventLoop};
+pub(crate) use self::window::Window;
+
+mod event_loop;
+mod window;
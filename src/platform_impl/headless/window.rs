@@ -0,0 +1,462 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::event_loop::Shared;
+use super::ActiveEventLoop;
+use crate::cursor::{CurrentCursor, Cursor};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::WindowEvent;
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
+use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::window::{
+    self, CommonWindowState, Fullscreen, HitTestRegion, ImePurpose, Window as CoreWindow, WindowId,
+};
+
+/// The scale factor of every window, there are no monitors to take it from.
+const SCALE_FACTOR: f64 = 1.0;
+
+/// The size of windows created without a surface size.
+const DEFAULT_SURFACE_SIZE: PhysicalSize<u32> = PhysicalSize::new(800, 600);
+
+static WINDOW_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+struct State {
+    surface_size: PhysicalSize<u32>,
+    min_surface_size: Option<PhysicalSize<u32>>,
+    max_surface_size: Option<PhysicalSize<u32>>,
+    surface_resize_increments: Option<PhysicalSize<u32>>,
+    position: PhysicalPosition<i32>,
+    title: String,
+    visible: bool,
+    resizable: bool,
+    minimized: bool,
+    maximized: bool,
+    fullscreen: Option<Fullscreen>,
+    decorations: bool,
+    enabled_buttons: window::WindowButtons,
+    theme: Option<window::Theme>,
+    input_state: window::InputStateSnapshot,
+}
+
+impl State {
+    fn clamp_surface_size(&self, size: PhysicalSize<u32>) -> PhysicalSize<u32> {
+        let mut size = size;
+        if let Some(min) = self.min_surface_size {
+            size = PhysicalSize::new(size.width.max(min.width), size.height.max(min.height));
+        }
+        if let Some(max) = self.max_surface_size {
+            size = PhysicalSize::new(size.width.min(max.width), size.height.min(max.height));
+        }
+        size
+    }
+}
+
+/// A window that only exists in memory, see the [module documentation](super).
+pub struct Window {
+    id: WindowId,
+    shared: Arc<Shared>,
+    state: Mutex<State>,
+    current_cursor: CurrentCursor,
+    pub(crate) common_state: Arc<CommonWindowState>,
+}
+
+impl Window {
+    pub(crate) fn new(el: &ActiveEventLoop, attrs: window::WindowAttributes) -> Self {
+        let id = WindowId::from_raw(WINDOW_ID.fetch_add(1, Ordering::Relaxed));
+        let common_state = Arc::new(CommonWindowState::new(&attrs));
+
+        let mut state = State {
+            surface_size: DEFAULT_SURFACE_SIZE,
+            min_surface_size: attrs.min_surface_size.map(|size| size.to_physical(SCALE_FACTOR)),
+            max_surface_size: attrs.max_surface_size.map(|size| size.to_physical(SCALE_FACTOR)),
+            surface_resize_increments: attrs
+                .surface_resize_increments
+                .map(|size| size.to_physical(SCALE_FACTOR)),
+            position: attrs
+                .position
+                .map_or(PhysicalPosition::default(), |position| position.to_physical(SCALE_FACTOR)),
+            title: attrs.title,
+            visible: attrs.visible,
+            resizable: attrs.resizable,
+            minimized: false,
+            maximized: attrs.maximized,
+            fullscreen: attrs.fullscreen,
+            decorations: attrs.decorations,
+            enabled_buttons: attrs.enabled_buttons,
+            theme: attrs.preferred_theme,
+            input_state: Default::default(),
+        };
+        let surface_size =
+            attrs.surface_size.map_or(DEFAULT_SURFACE_SIZE, |size| size.to_physical(SCALE_FACTOR));
+        state.surface_size = state.clamp_surface_size(surface_size);
+
+        // Report the initial size, like the platforms do once the window is mapped.
+        el.shared.push_event(id, WindowEvent::SurfaceResized(state.surface_size));
+
+        let current_cursor = CurrentCursor::default();
+        current_cursor.set(&attrs.cursor);
+
+        Self {
+            id,
+            shared: el.shared.clone(),
+            state: Mutex::new(state),
+            current_cursor,
+            common_state,
+        }
+    }
+}
+
+impl CoreWindow for Window {
+    fn id(&self) -> WindowId {
+        self.id
+    }
+
+    #[inline]
+    fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
+        None
+    }
+
+    #[inline]
+    fn available_monitors(&self) -> Box<dyn Iterator<Item = CoreMonitorHandle>> {
+        Box::new(std::iter::empty())
+    }
+
+    #[inline]
+    fn current_monitor(&self) -> Option<CoreMonitorHandle> {
+        None
+    }
+
+    #[inline]
+    fn scale_factor(&self) -> f64 {
+        SCALE_FACTOR
+    }
+
+    #[inline]
+    fn request_redraw(&self) {
+        self.shared.request_redraw(self.id);
+    }
+
+    #[inline]
+    fn pre_present_notify(&self) {}
+
+    #[inline]
+    fn reset_dead_keys(&self) {}
+
+    #[inline]
+    fn surface_position(&self) -> PhysicalPosition<i32> {
+        (0, 0).into()
+    }
+
+    #[inline]
+    fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
+        Ok(self.state.lock().unwrap().position)
+    }
+
+    #[inline]
+    fn set_outer_position(&self, position: Position) {
+        let position = position.to_physical(SCALE_FACTOR);
+        let mut state = self.state.lock().unwrap();
+        if state.position != position {
+            state.position = position;
+            self.shared.push_event(self.id, WindowEvent::Moved(position));
+        }
+    }
+
+    #[inline]
+    fn surface_size(&self) -> PhysicalSize<u32> {
+        self.state.lock().unwrap().surface_size
+    }
+
+    #[inline]
+    fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let mut state = self.state.lock().unwrap();
+        let size = state.clamp_surface_size(size.to_physical(SCALE_FACTOR));
+        if state.surface_size != size {
+            state.surface_size = size;
+            self.shared.push_event(self.id, WindowEvent::SurfaceResized(size));
+        }
+        None
+    }
+
+    #[inline]
+    fn outer_size(&self) -> PhysicalSize<u32> {
+        self.surface_size()
+    }
+
+    fn safe_area(&self) -> PhysicalInsets<u32> {
+        PhysicalInsets::new(0, 0, 0, 0)
+    }
+
+    fn surface_transform(&self) -> window::SurfaceTransform {
+        window::SurfaceTransform::Identity
+    }
+
+    #[inline]
+    fn set_min_surface_size(&self, min_size: Option<Size>) {
+        self.state.lock().unwrap().min_surface_size =
+            min_size.map(|size| size.to_physical(SCALE_FACTOR));
+    }
+
+    #[inline]
+    fn set_max_surface_size(&self, max_size: Option<Size>) {
+        self.state.lock().unwrap().max_surface_size =
+            max_size.map(|size| size.to_physical(SCALE_FACTOR));
+    }
+
+    #[inline]
+    fn title(&self) -> String {
+        self.state.lock().unwrap().title.clone()
+    }
+
+    #[inline]
+    fn set_title(&self, title: &str) {
+        self.state.lock().unwrap().title = title.to_owned();
+    }
+
+    #[inline]
+    fn set_transparent(&self, _transparent: bool) {}
+
+    #[inline]
+    fn set_blur(&self, _blur: bool) {}
+
+    fn set_tearing_allowed(&self, _allowed: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_tearing_allowed is not supported").into())
+    }
+
+    #[inline]
+    fn set_visible(&self, visible: bool) {
+        self.state.lock().unwrap().visible = visible;
+    }
+
+    #[inline]
+    fn is_visible(&self) -> Option<bool> {
+        Some(self.state.lock().unwrap().visible)
+    }
+
+    #[inline]
+    fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
+        self.state.lock().unwrap().surface_resize_increments
+    }
+
+    #[inline]
+    fn set_surface_resize_increments(&self, increments: Option<Size>) {
+        self.state.lock().unwrap().surface_resize_increments =
+            increments.map(|size| size.to_physical(SCALE_FACTOR));
+    }
+
+    #[inline]
+    fn set_resizable(&self, resizable: bool) {
+        self.state.lock().unwrap().resizable = resizable;
+    }
+
+    #[inline]
+    fn is_resizable(&self) -> bool {
+        self.state.lock().unwrap().resizable
+    }
+
+    #[inline]
+    fn set_minimized(&self, minimized: bool) {
+        self.state.lock().unwrap().minimized = minimized;
+    }
+
+    #[inline]
+    fn is_minimized(&self) -> Option<bool> {
+        Some(self.state.lock().unwrap().minimized)
+    }
+
+    #[inline]
+    fn set_maximized(&self, maximized: bool) {
+        self.state.lock().unwrap().maximized = maximized;
+    }
+
+    #[inline]
+    fn is_maximized(&self) -> bool {
+        self.state.lock().unwrap().maximized
+    }
+
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("move_to_workspace is not supported").into())
+    }
+
+    fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.state.lock().unwrap().fullscreen = fullscreen;
+    }
+
+    fn fullscreen(&self) -> Option<Fullscreen> {
+        self.state.lock().unwrap().fullscreen.clone()
+    }
+
+    #[inline]
+    fn set_decorations(&self, decorations: bool) {
+        self.state.lock().unwrap().decorations = decorations;
+    }
+
+    #[inline]
+    fn is_decorated(&self) -> bool {
+        self.state.lock().unwrap().decorations
+    }
+
+    #[inline]
+    fn set_window_level(&self, _level: window::WindowLevel) {}
+
+    #[inline]
+    fn set_window_icon(&self, _window_icon: Option<crate::icon::Icon>) {}
+
+    #[inline]
+    fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
+
+    #[inline]
+    fn set_ime_allowed(&self, allowed: bool) {
+        self.state.lock().unwrap().input_state.ime_allowed = allowed;
+    }
+
+    #[inline]
+    fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+
+    #[inline]
+    fn set_key_repeat_enabled(&self, _enabled: bool) {}
+
+    #[inline]
+    fn keyboard_layout_name(&self) -> Option<String> {
+        None
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        None
+    }
+
+    fn key_for_physical(&self, _key: PhysicalKey) -> Option<Key> {
+        None
+    }
+
+    fn compose_dead_key(&self, _dead: char, _base: char) -> Option<char> {
+        None
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        ModifiersState::empty()
+    }
+
+    #[inline]
+    fn set_hit_test_regions(&self, _regions: &[HitTestRegion]) {}
+
+    #[inline]
+    fn focus_window(&self) {}
+
+    #[inline]
+    fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
+
+    #[inline]
+    fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
+    }
+
+    #[inline]
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
+
+    #[inline]
+    fn set_cursor_position(&self, _: Position) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_position is not supported").into())
+    }
+
+    fn animate_cursor_to(&self, _: PhysicalPosition<i32>, _: Duration) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("animate_cursor_to is not supported").into())
+    }
+
+    #[inline]
+    fn set_cursor_grab(&self, mode: window::CursorGrabMode) -> Result<(), RequestError> {
+        self.state.lock().unwrap().input_state.cursor_grab = mode;
+        Ok(())
+    }
+
+    #[inline]
+    fn set_cursor_visible(&self, visible: bool) {
+        self.state.lock().unwrap().input_state.cursor_visible = visible;
+    }
+
+    #[inline]
+    fn capture_input_state(&self) -> window::InputStateSnapshot {
+        let cursor = self.current_cursor.get();
+        window::InputStateSnapshot { cursor, ..self.state.lock().unwrap().input_state.clone() }
+    }
+
+    #[inline]
+    fn drag_window(&self) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("drag_window is not supported").into())
+    }
+
+    #[inline]
+    fn drag_resize_window(&self, _direction: window::ResizeDirection) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("drag_resize_window is not supported").into())
+    }
+
+    #[inline]
+    fn show_window_menu(&self, _position: Position) {}
+
+    #[inline]
+    fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), RequestError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn set_enabled_buttons(&self, buttons: window::WindowButtons) {
+        self.state.lock().unwrap().enabled_buttons = buttons;
+    }
+
+    #[inline]
+    fn enabled_buttons(&self) -> window::WindowButtons {
+        self.state.lock().unwrap().enabled_buttons
+    }
+
+    #[inline]
+    fn theme(&self) -> Option<window::Theme> {
+        self.state.lock().unwrap().theme
+    }
+
+    #[inline]
+    fn has_focus(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn set_theme(&self, theme: Option<window::Theme>) {
+        self.state.lock().unwrap().theme = theme;
+    }
+
+    fn set_content_protected(&self, _protected: bool) {}
+
+    fn rwh_06_window_handle(&self) -> &dyn rwh_06::HasWindowHandle {
+        self
+    }
+
+    fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
+        self
+    }
+}
+
+impl rwh_06::HasWindowHandle for Window {
+    fn window_handle(&self) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
+
+impl rwh_06::HasDisplayHandle for Window {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        self.shared.destroy(self.id);
+    }
+}
@@ -1 +1,2 @@
+pub mod xcursor;
 pub mod xkb;
@@ -0,0 +1,180 @@
+//! Loading cursors from an XCursor theme, for windows overriding the system cursor theme.
+
+use std::{env, fs, iter};
+
+use cursor_icon::CursorIcon;
+use xcursor::parser::{parse_xcursor, Image};
+use xcursor::CursorTheme;
+
+use crate::cursor::CursorImage;
+
+/// The cursor size used when neither the window nor `XCURSOR_SIZE` specify one.
+const DEFAULT_SIZE: u32 = 24;
+
+/// The cursor theme and size a window uses instead of the system ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CursorThemeOverride {
+    pub name: String,
+    pub size: u32,
+}
+
+impl CursorThemeOverride {
+    /// Create the override of a window, unset values fall back to the system settings.
+    ///
+    /// Returns `None` when neither is set, in which case the system theme is used as is.
+    pub fn new(name: Option<String>, size: Option<u32>) -> Option<Self> {
+        if name.is_none() && size.is_none() {
+            return None;
+        }
+
+        let name =
+            name.or_else(|| env::var("XCURSOR_THEME").ok()).unwrap_or_else(|| "default".into());
+        let size = size
+            .or_else(|| env::var("XCURSOR_SIZE").ok().and_then(|size| size.parse().ok()))
+            .unwrap_or(DEFAULT_SIZE);
+        Some(Self { name, size })
+    }
+
+    /// Load `icon` from the theme, at the size of the override multiplied by `scale`.
+    ///
+    /// Animated cursors are loaded as their first frame.
+    pub fn load(&self, icon: CursorIcon, scale: u32) -> Option<CursorImage> {
+        let theme = CursorTheme::load(&self.name);
+        let path = iter::once(&icon.name())
+            .chain(icon.alt_names())
+            .find_map(|name| theme.load_icon(name))?;
+        let images = parse_xcursor(&fs::read(path).ok()?)?;
+        to_cursor_image(select_image(&images, self.size * scale)?)
+    }
+}
+
+/// Select the first frame of the images with the nominal size closest to `size`.
+fn select_image(images: &[Image], size: u32) -> Option<&Image> {
+    let nearest =
+        images.iter().map(|image| image.size).min_by_key(|&nominal| nominal.abs_diff(size))?;
+    images.iter().find(|image| image.size == nearest)
+}
+
+/// Convert the premultiplied, little-endian ARGB pixels of an XCursor image.
+fn to_cursor_image(image: &Image) -> Option<CursorImage> {
+    let rgba = image
+        .pixels_rgba
+        .chunks_exact(4)
+        .flat_map(|pixel| {
+            let [b, g, r, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+            let unpremultiply = |channel: u8| {
+                if a == 0 {
+                    0
+                } else {
+                    (channel as u32 * 255 / a as u32).min(255) as u8
+                }
+            };
+            [unpremultiply(r), unpremultiply(g), unpremultiply(b), a]
+        })
+        .collect();
+
+    CursorImage::from_rgba(
+        rgba,
+        image.width.try_into().ok()?,
+        image.height.try_into().ok()?,
+        // XCursor allows the hotspot right at the edge, while we don't.
+        image.xhot.min(image.width - 1).try_into().ok()?,
+        image.yhot.min(image.height - 1).try_into().ok()?,
+    )
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an XCursor file containing a single-pixel image for each nominal size.
+    fn xcursor_file(sizes: &[u32], pixel: [u8; 4]) -> Vec<u8> {
+        const HEADER_SIZE: u32 = 16;
+        const TOC_ENTRY_SIZE: u32 = 12;
+        const IMAGE_SIZE: u32 = 36 + 4;
+
+        let mut file = Vec::new();
+        for value in [u32::from_le_bytes(*b"Xcur"), HEADER_SIZE, 0x1_0000, sizes.len() as u32] {
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+        let images_start = HEADER_SIZE + TOC_ENTRY_SIZE * sizes.len() as u32;
+        for (index, &size) in sizes.iter().enumerate() {
+            let position = images_start + IMAGE_SIZE * index as u32;
+            for value in [0xfffd_0002, size, position] {
+                file.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        for &size in sizes {
+            // Header size, type, nominal size, version, width, height, hotspot and delay.
+            for value in [36, 0xfffd_0002, size, 1, 1, 1, 0, 0, 0] {
+                file.extend_from_slice(&u32::to_le_bytes(value));
+            }
+            file.extend_from_slice(&pixel);
+        }
+        file
+    }
+
+    /// Set an environment variable, restoring its previous value when dropped.
+    struct EnvVarGuard {
+        name: &'static str,
+        previous: Option<std::ffi::OsString>,
+    }
+
+    impl EnvVarGuard {
+        fn set(name: &'static str, value: impl AsRef<std::ffi::OsStr>) -> Self {
+            let previous = env::var_os(name);
+            env::set_var(name, value);
+            Self { name, previous }
+        }
+    }
+
+    impl Drop for EnvVarGuard {
+        fn drop(&mut self) {
+            match &self.previous {
+                Some(previous) => env::set_var(self.name, previous),
+                None => env::remove_var(self.name),
+            }
+        }
+    }
+
+    #[test]
+    fn themed_cursor_loading() {
+        let theme = env::temp_dir().join(format!("winit-cursor-theme-{}", std::process::id()));
+        let cursors = theme.join("winit-test").join("cursors");
+        fs::create_dir_all(&cursors).unwrap();
+        // Half-transparent red, premultiplied and in little-endian ARGB.
+        fs::write(cursors.join("default"), xcursor_file(&[16, 32, 48], [0, 0, 0x80, 0x80]))
+            .unwrap();
+        fs::write(cursors.join("pointer"), xcursor_file(&[24], [0xff, 0, 0, 0xff])).unwrap();
+        // `xcursor` only looks up themes in the `XCURSOR_PATH` directories.
+        let _xcursor_path = EnvVarGuard::set("XCURSOR_PATH", &theme);
+
+        let theme_override = CursorThemeOverride::new(Some("winit-test".into()), Some(30)).unwrap();
+        let image = theme_override.load(CursorIcon::Default, 1).unwrap();
+        assert_eq!(image.rgba, [0xff, 0, 0, 0x80]);
+        assert_eq!((image.width, image.height, image.hotspot_x, image.hotspot_y), (1, 1, 0, 0));
+        // `Pointer` is found under its XCursor name.
+        let image = theme_override.load(CursorIcon::Pointer, 2).unwrap();
+        assert_eq!(image.rgba, [0, 0, 0xff, 0xff]);
+        assert!(theme_override.load(CursorIcon::Wait, 1).is_none());
+
+        fs::remove_dir_all(&theme).unwrap();
+    }
+
+    #[test]
+    fn selects_nearest_size() {
+        let images = parse_xcursor(&xcursor_file(&[16, 32, 48], [0; 4])).unwrap();
+        assert_eq!(select_image(&images, 24).unwrap().size, 16);
+        assert_eq!(select_image(&images, 30).unwrap().size, 32);
+        assert_eq!(select_image(&images, 64).unwrap().size, 48);
+        assert!(select_image(&[], 24).is_none());
+    }
+
+    #[test]
+    fn override_falls_back_to_system() {
+        assert_eq!(CursorThemeOverride::new(None, None), None);
+        let theme = CursorThemeOverride::new(Some("Adwaita".into()), Some(48)).unwrap();
+        assert_eq!(theme, CursorThemeOverride { name: "Adwaita".into(), size: 48 });
+    }
+}
@@ -1,7 +1,7 @@
 //! XKB compose handling.
 
 use std::env;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use std::ops::Deref;
 use std::os::unix::ffi::OsStringExt;
 use std::ptr::NonNull;
@@ -29,7 +29,11 @@ impl XkbComposeTable {
             .and_then(|v| if v.is_empty() { None } else { Some(v) })
             .unwrap_or_else(|| "C".into());
         let locale = CString::new(locale.into_vec()).unwrap();
+        Self::from_locale(context, &locale)
+    }
 
+    /// Load the compose table of the given locale.
+    pub fn from_locale(context: &XkbContext, locale: &CStr) -> Option<Self> {
         let table = unsafe {
             (XKBCH.xkb_compose_table_new_from_locale)(
                 context.as_ptr(),
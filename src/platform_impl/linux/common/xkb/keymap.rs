@@ -1,20 +1,24 @@
 //! XKB keymap.
 
-use std::ffi::c_char;
-use std::ops::Deref;
+use std::ffi::{c_char, CStr};
+use std::ops::{Deref, RangeInclusive};
 use std::ptr::{self, NonNull};
 
+use smol_str::SmolStr;
 #[cfg(x11_platform)]
 use x11_dl::xlib_xcb::xcb_connection_t;
 use xkb::XKB_MOD_INVALID;
 use xkbcommon_dl::{
-    self as xkb, xkb_keycode_t, xkb_keymap, xkb_keymap_compile_flags, xkb_keysym_t,
-    xkb_layout_index_t, xkb_mod_index_t,
+    self as xkb, xkb_key_direction, xkb_keycode_t, xkb_keymap, xkb_keymap_compile_flags,
+    xkb_keysym_t, xkb_layout_index_t, xkb_level_index_t, xkb_mod_index_t, xkb_mod_mask_t,
+    xkb_state_component,
 };
 #[cfg(wayland_platform)]
 use {memmap2::MmapOptions, std::os::unix::io::OwnedFd};
 
-use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
+use crate::keyboard::{
+    Key, KeyCode, KeyLocation, NamedKey, NativeKey, NativeKeyCode, PhysicalKey, TextDirection,
+};
 #[cfg(x11_platform)]
 use crate::platform_impl::common::xkb::XKBXH;
 use crate::platform_impl::common::xkb::{XkbContext, XKBH};
@@ -560,12 +564,12 @@ pub fn keysym_to_key(keysym: u32) -> Key {
         keysyms::KP_Insert => NamedKey::Insert,
         keysyms::KP_Delete => NamedKey::Delete,
         // keysyms::KP_Equal => NamedKey::Equal,
-        // keysyms::KP_Multiply => NamedKey::Multiply,
-        // keysyms::KP_Add => NamedKey::Add,
+        keysyms::KP_Multiply => return Key::Character(SmolStr::new_static("*")),
+        keysyms::KP_Add => return Key::Character(SmolStr::new_static("+")),
         // keysyms::KP_Separator => NamedKey::Separator,
-        // keysyms::KP_Subtract => NamedKey::Subtract,
+        keysyms::KP_Subtract => return Key::Character(SmolStr::new_static("-")),
         // keysyms::KP_Decimal => NamedKey::Decimal,
-        // keysyms::KP_Divide => NamedKey::Divide,
+        keysyms::KP_Divide => return Key::Character(SmolStr::new_static("/")),
 
         // keysyms::KP_0 => return Key::Character("0"),
         // keysyms::KP_1 => return Key::Character("1"),
@@ -741,7 +745,8 @@ pub fn keysym_to_key(keysym: u32) -> Key {
         keysyms::XF86_Standby => NamedKey::Standby,
         keysyms::XF86_AudioLowerVolume => NamedKey::AudioVolumeDown,
         keysyms::XF86_AudioRaiseVolume => NamedKey::AudioVolumeUp,
-        keysyms::XF86_AudioPlay => NamedKey::MediaPlay,
+        // The play key of keyboards is a play/pause toggle, like `VK_MEDIA_PLAY_PAUSE` on Windows.
+        keysyms::XF86_AudioPlay => NamedKey::MediaPlayPause,
         keysyms::XF86_AudioStop => NamedKey::MediaStop,
         keysyms::XF86_AudioPrev => NamedKey::MediaTrackPrevious,
         keysyms::XF86_AudioNext => NamedKey::MediaTrackNext,
@@ -765,7 +770,7 @@ pub fn keysym_to_key(keysym: u32) -> Key {
         // XFree86 - More "Internet"
         keysyms::XF86_Back => NamedKey::BrowserBack,
         keysyms::XF86_Forward => NamedKey::BrowserForward,
-        // keysyms::XF86_Stop => NamedKey::Stop,
+        keysyms::XF86_Stop => NamedKey::BrowserStop,
         keysyms::XF86_Refresh => NamedKey::BrowserRefresh,
         keysyms::XF86_PowerOff => NamedKey::Power,
         keysyms::XF86_WakeUp => NamedKey::WakeUp,
@@ -775,7 +780,7 @@ pub fn keysym_to_key(keysym: u32) -> Key {
         keysyms::XF86_Sleep => NamedKey::Standby,
         keysyms::XF86_Favorites => NamedKey::BrowserFavorites,
         keysyms::XF86_AudioPause => NamedKey::MediaPause,
-        // keysyms::XF86_AudioMedia => NamedKey::AudioMedia,
+        keysyms::XF86_AudioMedia => NamedKey::LaunchMediaPlayer,
         keysyms::XF86_MyComputer => NamedKey::LaunchApplication1,
         // keysyms::XF86_VendorHome => NamedKey::VendorHome,
         // keysyms::XF86_LightBulb => NamedKey::LightBulb,
@@ -879,6 +884,184 @@ pub fn keysym_to_key(keysym: u32) -> Key {
     })
 }
 
+/// Map the key to the XKB keysym producing it.
+///
+/// Characters use the Latin-1 keysyms where possible and the Unicode keysyms otherwise.
+pub fn key_to_keysym(key: &Key) -> Option<u32> {
+    use xkbcommon_dl::keysyms;
+    let named = match key {
+        Key::Named(named) => named,
+        Key::Character(text) => {
+            let mut chars = text.chars();
+            let ch = chars.next().filter(|_| chars.next().is_none())? as u32;
+            return match ch {
+                0x20..=0x7e | 0xa0..=0xff => Some(ch),
+                // Control characters don't have a keysym.
+                0..=0x1f | 0x7f..=0x9f => None,
+                _ => Some(0x0100_0000 | ch),
+            };
+        },
+        Key::Unidentified(NativeKey::Xkb(keysym)) => return Some(*keysym),
+        Key::Unidentified(_) | Key::Dead(_) => return None,
+    };
+
+    Some(match named {
+        NamedKey::Backspace => keysyms::BackSpace,
+        NamedKey::Tab => keysyms::Tab,
+        NamedKey::Clear => keysyms::Clear,
+        NamedKey::Enter => keysyms::Return,
+        NamedKey::Pause => keysyms::Pause,
+        NamedKey::ScrollLock => keysyms::Scroll_Lock,
+        NamedKey::PrintScreen => keysyms::Print,
+        NamedKey::Escape => keysyms::Escape,
+        NamedKey::Delete => keysyms::Delete,
+        NamedKey::Compose => keysyms::Multi_key,
+        NamedKey::CodeInput => keysyms::Codeinput,
+        NamedKey::SingleCandidate => keysyms::SingleCandidate,
+        NamedKey::AllCandidates => keysyms::MultipleCandidate,
+        NamedKey::PreviousCandidate => keysyms::PreviousCandidate,
+        NamedKey::KanjiMode => keysyms::Kanji,
+        NamedKey::NonConvert => keysyms::Muhenkan,
+        NamedKey::Convert => keysyms::Henkan_Mode,
+        NamedKey::Romaji => keysyms::Romaji,
+        NamedKey::Hiragana => keysyms::Hiragana,
+        NamedKey::HiraganaKatakana => keysyms::Hiragana_Katakana,
+        NamedKey::Zenkaku => keysyms::Zenkaku,
+        NamedKey::Hankaku => keysyms::Hankaku,
+        NamedKey::ZenkakuHankaku => keysyms::Zenkaku_Hankaku,
+        NamedKey::KanaMode => keysyms::Kana_Lock,
+        NamedKey::Alphanumeric => keysyms::Eisu_Shift,
+        NamedKey::Home => keysyms::Home,
+        NamedKey::ArrowLeft => keysyms::Left,
+        NamedKey::ArrowUp => keysyms::Up,
+        NamedKey::ArrowRight => keysyms::Right,
+        NamedKey::ArrowDown => keysyms::Down,
+        NamedKey::PageUp => keysyms::Page_Up,
+        NamedKey::PageDown => keysyms::Page_Down,
+        NamedKey::End => keysyms::End,
+        NamedKey::Select => keysyms::Select,
+        NamedKey::Execute => keysyms::Execute,
+        NamedKey::Insert => keysyms::Insert,
+        NamedKey::Undo => keysyms::Undo,
+        NamedKey::Redo => keysyms::Redo,
+        NamedKey::ContextMenu => keysyms::Menu,
+        NamedKey::Find => keysyms::Find,
+        NamedKey::Cancel => keysyms::Cancel,
+        NamedKey::Help => keysyms::Help,
+        NamedKey::ModeChange => keysyms::Mode_switch,
+        NamedKey::NumLock => keysyms::Num_Lock,
+        NamedKey::F1 => keysyms::F1,
+        NamedKey::F2 => keysyms::F2,
+        NamedKey::F3 => keysyms::F3,
+        NamedKey::F4 => keysyms::F4,
+        NamedKey::F5 => keysyms::F5,
+        NamedKey::F6 => keysyms::F6,
+        NamedKey::F7 => keysyms::F7,
+        NamedKey::F8 => keysyms::F8,
+        NamedKey::F9 => keysyms::F9,
+        NamedKey::F10 => keysyms::F10,
+        NamedKey::F11 => keysyms::F11,
+        NamedKey::F12 => keysyms::F12,
+        NamedKey::F13 => keysyms::F13,
+        NamedKey::F14 => keysyms::F14,
+        NamedKey::F15 => keysyms::F15,
+        NamedKey::F16 => keysyms::F16,
+        NamedKey::F17 => keysyms::F17,
+        NamedKey::F18 => keysyms::F18,
+        NamedKey::F19 => keysyms::F19,
+        NamedKey::F20 => keysyms::F20,
+        NamedKey::F21 => keysyms::F21,
+        NamedKey::F22 => keysyms::F22,
+        NamedKey::F23 => keysyms::F23,
+        NamedKey::F24 => keysyms::F24,
+        NamedKey::F25 => keysyms::F25,
+        NamedKey::F26 => keysyms::F26,
+        NamedKey::F27 => keysyms::F27,
+        NamedKey::F28 => keysyms::F28,
+        NamedKey::F29 => keysyms::F29,
+        NamedKey::F30 => keysyms::F30,
+        NamedKey::F31 => keysyms::F31,
+        NamedKey::F32 => keysyms::F32,
+        NamedKey::F33 => keysyms::F33,
+        NamedKey::F34 => keysyms::F34,
+        NamedKey::F35 => keysyms::F35,
+        NamedKey::Shift => keysyms::Shift_L,
+        NamedKey::Control => keysyms::Control_L,
+        NamedKey::CapsLock => keysyms::Caps_Lock,
+        NamedKey::Alt => keysyms::Alt_L,
+        NamedKey::Super => keysyms::Super_L,
+        NamedKey::Hyper => keysyms::Hyper_L,
+        NamedKey::AltGraph => keysyms::ISO_Level3_Shift,
+        NamedKey::GroupNext => keysyms::ISO_Next_Group,
+        NamedKey::GroupPrevious => keysyms::ISO_Prev_Group,
+        NamedKey::GroupFirst => keysyms::ISO_First_Group,
+        NamedKey::GroupLast => keysyms::ISO_Last_Group,
+        NamedKey::EraseEof => keysyms::_3270_EraseEOF,
+        NamedKey::Attn => keysyms::_3270_Attn,
+        NamedKey::Play => keysyms::_3270_Play,
+        NamedKey::ExSel => keysyms::_3270_ExSelect,
+        NamedKey::CrSel => keysyms::_3270_CursorSelect,
+        NamedKey::Space => keysyms::space,
+        NamedKey::BrightnessUp => keysyms::XF86_MonBrightnessUp,
+        NamedKey::BrightnessDown => keysyms::XF86_MonBrightnessDown,
+        NamedKey::Standby => keysyms::XF86_Standby,
+        NamedKey::AudioVolumeDown => keysyms::XF86_AudioLowerVolume,
+        NamedKey::AudioVolumeUp => keysyms::XF86_AudioRaiseVolume,
+        NamedKey::MediaPlayPause | NamedKey::MediaPlay => keysyms::XF86_AudioPlay,
+        NamedKey::MediaStop => keysyms::XF86_AudioStop,
+        NamedKey::MediaTrackPrevious => keysyms::XF86_AudioPrev,
+        NamedKey::MediaTrackNext => keysyms::XF86_AudioNext,
+        NamedKey::BrowserHome => keysyms::XF86_HomePage,
+        NamedKey::LaunchMail => keysyms::XF86_Mail,
+        NamedKey::BrowserSearch => keysyms::XF86_Search,
+        NamedKey::MediaRecord => keysyms::XF86_AudioRecord,
+        NamedKey::LaunchApplication2 => keysyms::XF86_Calculator,
+        NamedKey::LaunchCalendar => keysyms::XF86_Calendar,
+        NamedKey::Power => keysyms::XF86_PowerDown,
+        NamedKey::BrowserBack => keysyms::XF86_Back,
+        NamedKey::BrowserForward => keysyms::XF86_Forward,
+        NamedKey::BrowserRefresh => keysyms::XF86_Refresh,
+        NamedKey::BrowserStop => keysyms::XF86_Stop,
+        NamedKey::WakeUp => keysyms::XF86_WakeUp,
+        NamedKey::Eject => keysyms::XF86_Eject,
+        NamedKey::LaunchScreenSaver => keysyms::XF86_ScreenSaver,
+        NamedKey::LaunchWebBrowser => keysyms::XF86_WWW,
+        NamedKey::BrowserFavorites => keysyms::XF86_Favorites,
+        NamedKey::MediaPause => keysyms::XF86_AudioPause,
+        NamedKey::LaunchApplication1 => keysyms::XF86_MyComputer,
+        NamedKey::MediaRewind => keysyms::XF86_AudioRewind,
+        NamedKey::Close => keysyms::XF86_Close,
+        NamedKey::Copy => keysyms::XF86_Copy,
+        NamedKey::Cut => keysyms::XF86_Cut,
+        NamedKey::LaunchSpreadsheet => keysyms::XF86_Excel,
+        NamedKey::LogOff => keysyms::XF86_LogOff,
+        NamedKey::New => keysyms::XF86_New,
+        NamedKey::Open => keysyms::XF86_Open,
+        NamedKey::Paste => keysyms::XF86_Paste,
+        NamedKey::LaunchPhone => keysyms::XF86_Phone,
+        NamedKey::MailReply => keysyms::XF86_Reply,
+        NamedKey::Save => keysyms::XF86_Save,
+        NamedKey::MailSend => keysyms::XF86_Send,
+        NamedKey::SpellCheck => keysyms::XF86_Spell,
+        NamedKey::SplitScreenToggle => keysyms::XF86_SplitScreen,
+        NamedKey::LaunchMediaPlayer => keysyms::XF86_Video,
+        NamedKey::LaunchWordProcessor => keysyms::XF86_Word,
+        NamedKey::ZoomIn => keysyms::XF86_ZoomIn,
+        NamedKey::ZoomOut => keysyms::XF86_ZoomOut,
+        NamedKey::LaunchWebCam => keysyms::XF86_WebCam,
+        NamedKey::MailForward => keysyms::XF86_MailForward,
+        NamedKey::LaunchMusicPlayer => keysyms::XF86_Music,
+        NamedKey::MediaFastForward => keysyms::XF86_AudioForward,
+        NamedKey::RandomToggle => keysyms::XF86_AudioRandomPlay,
+        NamedKey::Subtitle => keysyms::XF86_Subtitle,
+        NamedKey::MediaAudioTrack => keysyms::XF86_AudioCycleTrack,
+        NamedKey::Hibernate => keysyms::XF86_Hibernate,
+        NamedKey::AudioVolumeMute => keysyms::XF86_AudioMute,
+        NamedKey::VideoModeNext => keysyms::XF86_Next_VMode,
+        _ => return None,
+    })
+}
+
 pub fn keysym_location(keysym: u32) -> KeyLocation {
     use xkbcommon_dl::keysyms;
     match keysym {
@@ -938,6 +1121,8 @@ pub struct XkbKeymap {
     keymap: NonNull<xkb_keymap>,
     _mods_indices: ModsIndices,
     pub _core_keyboard_id: i32,
+    layout_names: Vec<String>,
+    level_three_mask: xkb_mod_mask_t,
 }
 
 impl XkbKeymap {
@@ -958,6 +1143,20 @@ impl XkbKeymap {
         Some(Self::new_inner(keymap, 0))
     }
 
+    /// Compile the keymap from its text.
+    #[cfg(all(test, wayland_platform))]
+    pub fn from_string(context: &XkbContext, keymap: &CStr) -> Option<Self> {
+        let keymap = unsafe {
+            (XKBH.xkb_keymap_new_from_string)(
+                context.as_ptr(),
+                keymap.as_ptr(),
+                xkb::xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+                xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            )
+        };
+        Some(Self::new_inner(NonNull::new(keymap)?, 0))
+    }
+
     #[cfg(x11_platform)]
     pub fn from_x11_keymap(
         context: &XkbContext,
@@ -988,7 +1187,25 @@ impl XkbKeymap {
             mod5: mod_index_for_name(keymap, b"Mod5\0"),
         };
 
-        Self { keymap, _mods_indices: mods_indices, _core_keyboard_id }
+        let layout_names = keymap_layout_names(keymap);
+        let level_three_mask = level_three_mask(keymap, mods_indices.mod5);
+        Self {
+            keymap,
+            _mods_indices: mods_indices,
+            _core_keyboard_id,
+            layout_names,
+            level_three_mask,
+        }
+    }
+
+    /// The real modifiers AltGr sets, which the `LevelThree` virtual modifier maps to.
+    pub fn level_three_mask(&self) -> xkb_mod_mask_t {
+        self.level_three_mask
+    }
+
+    /// The name of the layout at the given index, e.g. `English (US)`.
+    pub fn layout_name(&self, layout: xkb_layout_index_t) -> Option<&str> {
+        self.layout_names.get(layout as usize).map(String::as_str).filter(|name| !name.is_empty())
     }
 
     #[cfg(x11_platform)]
@@ -1000,6 +1217,17 @@ impl XkbKeymap {
         &mut self,
         layout: xkb_layout_index_t,
         keycode: xkb_keycode_t,
+    ) -> xkb_keysym_t {
+        // NOTE: The level should be zero to ignore modifiers.
+        self.keysym_by_level(layout, keycode, 0)
+    }
+
+    /// The keysym at the given shift level, level `1` being <kbd>Shift</kbd> on regular layouts.
+    pub fn keysym_by_level(
+        &mut self,
+        layout: xkb_layout_index_t,
+        keycode: xkb_keycode_t,
+        level: xkb_level_index_t,
     ) -> xkb_keysym_t {
         unsafe {
             let mut keysyms = ptr::null();
@@ -1007,8 +1235,7 @@ impl XkbKeymap {
                 self.keymap.as_ptr(),
                 keycode,
                 layout,
-                // NOTE: The level should be zero to ignore modifiers.
-                0,
+                level,
                 &mut keysyms,
             );
 
@@ -1020,6 +1247,13 @@ impl XkbKeymap {
         }
     }
 
+    /// The range of keycodes the keymap defines keys for.
+    pub fn keycodes(&self) -> RangeInclusive<xkb_keycode_t> {
+        let min = unsafe { (XKBH.xkb_keymap_min_keycode)(self.keymap.as_ptr()) };
+        let max = unsafe { (XKBH.xkb_keymap_max_keycode)(self.keymap.as_ptr()) };
+        min..=max
+    }
+
     /// Check whether the given key repeats.
     pub fn key_repeats(&mut self, keycode: xkb_keycode_t) -> bool {
         unsafe { (XKBH.xkb_keymap_key_repeats)(self.keymap.as_ptr(), keycode) == 1 }
@@ -1067,3 +1301,271 @@ fn mod_index_for_name(keymap: NonNull<xkb_keymap>, name: &[u8]) -> Option<xkb_mo
         }
     }
 }
+
+/// Resolve the real modifiers the `LevelThree` virtual modifier maps to.
+///
+/// The state only tracks the real modifiers, and libxkbcommon doesn't expose the mapping of the
+/// virtual ones, so press the keys typing `ISO_Level3_Shift` in a scratch state and see which
+/// modifiers they set. This is usually `Mod5`, which keymaps without such a key fall back to.
+fn level_three_mask(keymap: NonNull<xkb_keymap>, mod5: Option<xkb_mod_index_t>) -> xkb_mod_mask_t {
+    let fallback = mod5.map_or(0, |mod5| 1 << mod5);
+    let Some(state) = NonNull::new(unsafe { (XKBH.xkb_state_new)(keymap.as_ptr()) }) else {
+        return fallback;
+    };
+
+    let min = unsafe { (XKBH.xkb_keymap_min_keycode)(keymap.as_ptr()) };
+    let max = unsafe { (XKBH.xkb_keymap_max_keycode)(keymap.as_ptr()) };
+    let mut mask = 0;
+    for keycode in min..=max {
+        let mut keysyms = ptr::null();
+        let count = unsafe {
+            (XKBH.xkb_keymap_key_get_syms_by_level)(keymap.as_ptr(), keycode, 0, 0, &mut keysyms)
+        };
+        if count != 1 || unsafe { *keysyms } != xkb::keysyms::ISO_Level3_Shift {
+            continue;
+        }
+
+        unsafe {
+            (XKBH.xkb_state_update_key)(state.as_ptr(), keycode, xkb_key_direction::XKB_KEY_DOWN);
+            mask |= (XKBH.xkb_state_serialize_mods)(
+                state.as_ptr(),
+                xkb_state_component::XKB_STATE_MODS_DEPRESSED,
+            );
+            (XKBH.xkb_state_update_key)(state.as_ptr(), keycode, xkb_key_direction::XKB_KEY_UP);
+        }
+    }
+    unsafe { (XKBH.xkb_state_unref)(state.as_ptr()) };
+
+    if mask == 0 {
+        fallback
+    } else {
+        mask
+    }
+}
+
+/// Collect the layout names of the keymap, indexed by layout.
+///
+/// `xkb_keymap_layout_get_name` isn't exposed by `xkbcommon-dl`, so read them back from the
+/// serialized keymap instead.
+fn keymap_layout_names(keymap: NonNull<xkb_keymap>) -> Vec<String> {
+    let text = unsafe {
+        (XKBH.xkb_keymap_get_as_string)(
+            keymap.as_ptr(),
+            xkb::xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+        )
+    };
+    if text.is_null() {
+        return Vec::new();
+    }
+
+    let names = parse_layout_names(&unsafe { CStr::from_ptr(text) }.to_string_lossy());
+    unsafe { libc::free(text as *mut _) };
+    names
+}
+
+/// Parse the `name[GroupN]="..."` entries of the `xkb_symbols` section.
+fn parse_layout_names(keymap: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for line in keymap.lines() {
+        let Some(rest) = line.trim().strip_prefix("name[") else {
+            continue;
+        };
+        let Some((group, value)) = rest.split_once(']') else {
+            continue;
+        };
+        let Some(index) = group
+            .strip_prefix("Group")
+            .or_else(|| group.strip_prefix("group"))
+            .and_then(|index| index.parse::<usize>().ok())
+            .filter(|&index| index > 0)
+        else {
+            continue;
+        };
+        let Some(name) = value
+            .trim()
+            .strip_prefix('=')
+            .map(str::trim)
+            .and_then(|value| value.strip_prefix('"'))
+            .and_then(|value| value.split_once('"'))
+            .map(|(name, _)| name)
+        else {
+            continue;
+        };
+
+        if names.len() < index {
+            names.resize(index, String::new());
+        }
+        names[index - 1] = name.to_owned();
+    }
+    names
+}
+
+/// The layouts named after a language usually written in a right-to-left script.
+const RTL_LAYOUTS: &[&str] =
+    &["Arabic", "Dhivehi", "Hebrew", "Pashto", "Persian", "Syriac", "Urdu", "Uyghur", "Yiddish"];
+
+/// The layouts named after a language usually written in a left-to-right script.
+#[rustfmt::skip]
+const LTR_LAYOUTS: &[&str] = &[
+    "Albanian", "Amharic", "Armenian", "Azerbaijani", "Bangla", "Belarusian", "Belgian", "Bosnian",
+    "Bulgarian", "Burmese", "Chinese", "Croatian", "Czech", "Danish", "Dutch", "English",
+    "Esperanto", "Estonian", "Faroese", "Filipino", "Finnish", "French", "Georgian", "German",
+    "Greek", "Gujarati", "Hindi", "Hungarian", "Icelandic", "Indonesian", "Irish", "Italian",
+    "Japanese", "Kannada", "Kazakh", "Khmer", "Korean", "Kyrgyz", "Lao", "Latvian", "Lithuanian",
+    "Macedonian", "Malayalam", "Maltese", "Maori", "Marathi", "Mongolian", "Montenegrin", "Nepali",
+    "Norwegian", "Polish", "Portuguese", "Punjabi", "Romanian", "Russian", "Serbian", "Sinhala",
+    "Slovak", "Slovenian", "Spanish", "Swahili", "Swedish", "Tajik", "Tamil", "Telugu", "Thai",
+    "Tibetan", "Turkish", "Ukrainian", "Uzbek", "Vietnamese",
+];
+
+/// The direction of the text typed with the layout named `name`, e.g. `Arabic (Syria)`.
+///
+/// XKB doesn't tell the language of a layout, so this recognizes the variants naming their script,
+/// e.g. `Uyghur (Latin)`, and otherwise the layouts named after common languages. Returns `None`
+/// for other layouts, or when the variant names scripts of both directions.
+pub fn layout_direction(name: &str) -> Option<TextDirection> {
+    let (language, variant) = name.split_once(" (").unwrap_or((name, ""));
+    match (variant.contains("Latin"), variant.contains("Arabic")) {
+        (true, true) => None,
+        (true, false) => Some(TextDirection::LeftToRight),
+        (false, true) => Some(TextDirection::RightToLeft),
+        (false, false) if RTL_LAYOUTS.contains(&language) => Some(TextDirection::RightToLeft),
+        (false, false) if LTR_LAYOUTS.contains(&language) => Some(TextDirection::LeftToRight),
+        (false, false) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use xkbcommon_dl::keysyms;
+
+    use super::*;
+
+    #[test]
+    fn numpad_operators() {
+        let operators = [
+            (keysyms::KP_Add, "+"),
+            (keysyms::KP_Subtract, "-"),
+            (keysyms::KP_Multiply, "*"),
+            (keysyms::KP_Divide, "/"),
+        ];
+        for (keysym, text) in operators {
+            assert_eq!(keysym_to_key(keysym), Key::Character(text.into()));
+            assert_eq!(keysym_location(keysym), KeyLocation::Numpad);
+        }
+    }
+
+    #[test]
+    fn enter_locations() {
+        for (keysym, location) in
+            [(keysyms::Return, KeyLocation::Standard), (keysyms::KP_Enter, KeyLocation::Numpad)]
+        {
+            assert_eq!(keysym_to_key(keysym), Key::Named(NamedKey::Enter));
+            assert_eq!(keysym_location(keysym), location);
+        }
+    }
+
+    #[test]
+    fn key_to_keysym_fixture() {
+        let named = [
+            (NamedKey::Enter, keysyms::Return),
+            (NamedKey::Escape, keysyms::Escape),
+            (NamedKey::ArrowLeft, keysyms::Left),
+            (NamedKey::F1, keysyms::F1),
+            (NamedKey::PrintScreen, keysyms::Print),
+            (NamedKey::Shift, keysyms::Shift_L),
+        ];
+        for (named, keysym) in named {
+            let key = Key::Named(named);
+            assert_eq!(key_to_keysym(&key), Some(keysym));
+            assert_eq!(keysym_to_key(keysym), key);
+        }
+
+        let characters = [
+            ("a", keysyms::a),
+            ("A", keysyms::A),
+            ("1", keysyms::_1),
+            ("é", keysyms::eacute),
+            ("€", 0x0100_20ac),
+        ];
+        for (text, keysym) in characters {
+            assert_eq!(key_to_keysym(&Key::Character(text.into())), Some(keysym));
+        }
+
+        assert_eq!(key_to_keysym(&Key::Character("ab".into())), None);
+        assert_eq!(key_to_keysym(&Key::Named(NamedKey::Soft1)), None);
+        assert_eq!(key_to_keysym(&Key::Unidentified(NativeKey::Xkb(0x1234))), Some(0x1234));
+    }
+
+    #[test]
+    fn media_and_browser_keysyms() {
+        let keys = [
+            (keysyms::XF86_AudioPlay, NamedKey::MediaPlayPause),
+            (keysyms::XF86_AudioPause, NamedKey::MediaPause),
+            (keysyms::XF86_AudioStop, NamedKey::MediaStop),
+            (keysyms::XF86_AudioNext, NamedKey::MediaTrackNext),
+            (keysyms::XF86_AudioPrev, NamedKey::MediaTrackPrevious),
+            (keysyms::XF86_AudioRaiseVolume, NamedKey::AudioVolumeUp),
+            (keysyms::XF86_AudioLowerVolume, NamedKey::AudioVolumeDown),
+            (keysyms::XF86_AudioMute, NamedKey::AudioVolumeMute),
+            (keysyms::XF86_Back, NamedKey::BrowserBack),
+            (keysyms::XF86_Forward, NamedKey::BrowserForward),
+            (keysyms::XF86_Refresh, NamedKey::BrowserRefresh),
+            (keysyms::XF86_Stop, NamedKey::BrowserStop),
+            (keysyms::XF86_Search, NamedKey::BrowserSearch),
+            (keysyms::XF86_Favorites, NamedKey::BrowserFavorites),
+            (keysyms::XF86_HomePage, NamedKey::BrowserHome),
+        ];
+        for (keysym, named) in keys {
+            assert_eq!(keysym_to_key(keysym), Key::Named(named));
+            assert_eq!(key_to_keysym(&Key::Named(named)), Some(keysym));
+        }
+
+        assert_eq!(
+            keysym_to_key(keysyms::XF86_AudioMedia),
+            Key::Named(NamedKey::LaunchMediaPlayer)
+        );
+        assert_eq!(key_to_keysym(&Key::Named(NamedKey::MediaPlay)), Some(keysyms::XF86_AudioPlay));
+    }
+
+    #[test]
+    fn layout_names_from_keymap() {
+        let keymap = r#"xkb_symbols "pc+us+de:2+inet(evdev)" {
+	name[Group1]="English (US)";
+	name[group2]="German";
+
+	key <AE01> { [ 1, exclam ] };
+};"#;
+        assert_eq!(parse_layout_names(keymap), ["English (US)", "German"]);
+
+        // Holes are kept so the names stay indexed by layout.
+        let keymap = "\tname[Group2]=\"French\";\n\tname[Group1]=\"\";";
+        assert_eq!(parse_layout_names(keymap), ["", "French"]);
+
+        assert!(parse_layout_names("xkb_keycodes \"evdev\" { <ESC> = 9; };").is_empty());
+    }
+
+    #[test]
+    fn layout_names_direction() {
+        use TextDirection::{LeftToRight, RightToLeft};
+
+        for (name, direction) in [
+            ("English (US)", Some(LeftToRight)),
+            ("German (Switzerland)", Some(LeftToRight)),
+            ("Arabic", Some(RightToLeft)),
+            ("Arabic (Syria)", Some(RightToLeft)),
+            ("Hebrew (Biblical, Tiro)", Some(RightToLeft)),
+            ("Persian (with Persian keypad)", Some(RightToLeft)),
+            ("Urdu (Pakistan)", Some(RightToLeft)),
+            ("Kurdish (Iraq, Latin Q)", Some(LeftToRight)),
+            ("Kurdish (Iran, Arabic-Latin)", None),
+            ("Kurdish (Iraq, Arabic-Latin)", None),
+            ("Pashto (Afghanistan, OLPC)", Some(RightToLeft)),
+            ("Uyghur (Latin)", Some(LeftToRight)),
+            ("N'Ko", None),
+            ("", None),
+        ] {
+            assert_eq!(layout_direction(name), direction, "{name}");
+        }
+    }
+}
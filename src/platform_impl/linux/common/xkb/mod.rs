@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::os::raw::c_char;
 #[cfg(wayland_platform)]
 use std::os::unix::io::OwnedFd;
 use std::ptr::{self, NonNull};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use smol_str::SmolStr;
 use tracing::warn;
@@ -15,7 +17,7 @@ use xkbcommon_dl::{
 use {x11_dl::xlib_xcb::xcb_connection_t, xkbcommon_dl::x11::xkbcommon_x11_handle};
 
 use crate::event::{ElementState, KeyEvent};
-use crate::keyboard::{Key, KeyLocation};
+use crate::keyboard::{Key, KeyLocation, PhysicalKey};
 use crate::platform_impl::KeyEventExtra;
 use crate::utils::Lazy;
 
@@ -27,7 +29,9 @@ use compose::{ComposeStatus, XkbComposeState, XkbComposeTable};
 #[cfg(x11_platform)]
 pub use keymap::raw_keycode_to_physicalkey;
 use keymap::XkbKeymap;
-pub use keymap::{physicalkey_to_scancode, scancode_to_physicalkey};
+pub use keymap::{
+    key_to_keysym, layout_direction, physicalkey_to_scancode, scancode_to_physicalkey,
+};
 pub use state::XkbState;
 
 // TODO: Wire this up without using a static `AtomicBool`.
@@ -58,7 +62,7 @@ pub struct Context {
     keymap: Option<XkbKeymap>,
     compose_state1: Option<XkbComposeState>,
     compose_state2: Option<XkbComposeState>,
-    _compose_table: Option<XkbComposeTable>,
+    compose_table: Option<XkbComposeTable>,
     context: XkbContext,
     scratch_buffer: Vec<u8>,
 }
@@ -88,7 +92,7 @@ impl Context {
             compose_state2,
             #[cfg(x11_platform)]
             core_keyboard_id: 0,
-            _compose_table: compose_table,
+            compose_table,
             context,
             scratch_buffer: Vec::with_capacity(8),
         })
@@ -127,6 +131,100 @@ impl Context {
         self.keymap.as_mut()
     }
 
+    /// The real modifiers AltGr sets in the keymap, see `XkbKeymap::level_three_mask`.
+    #[cfg(x11_platform)]
+    pub fn level_three_mask(&self) -> Option<xkb::xkb_mod_mask_t> {
+        self.keymap.as_ref().map(XkbKeymap::level_three_mask)
+    }
+
+    /// The name of the layout currently in effect.
+    pub fn layout_name(&self) -> Option<String> {
+        let layout = self.state.as_ref()?.active_layout();
+        self.keymap.as_ref()?.layout_name(layout).map(str::to_owned)
+    }
+
+    /// The keys of the layout currently in effect, as produced without modifiers, and the
+    /// characters its dead keys compose.
+    pub fn layout_keys(&mut self) -> LayoutKeys {
+        let mut keys = HashMap::new();
+        if let Some(mut key_context) = self.key_context() {
+            for keycode in key_context.keymap.keycodes() {
+                let physical_key = keymap::raw_keycode_to_physicalkey(keycode);
+                if let PhysicalKey::Unidentified(_) = physical_key {
+                    continue;
+                }
+                let mut results = KeyEventResults::new(&mut key_context, keycode, false);
+                let (key, _) = results.key_without_modifiers();
+                if !matches!(key, Key::Unidentified(_)) {
+                    keys.insert(physical_key, key);
+                }
+            }
+        }
+        LayoutKeys { keys, compositions: self.dead_key_compositions() }
+    }
+
+    /// The characters composed by each dead key of the layout currently in effect followed by each
+    /// character of that layout.
+    fn dead_key_compositions(&mut self) -> HashMap<(char, char), char> {
+        let mut compositions = HashMap::new();
+        let (Some(state), Some(keymap), Some(compose_table)) =
+            (self.state.as_ref(), self.keymap.as_mut(), self.compose_table.as_ref())
+        else {
+            return compositions;
+        };
+        // Use a separate state, not to disturb a sequence the user is composing.
+        let Some(mut compose_state) = compose_table.new_state() else { return compositions };
+
+        let layout = state.active_layout();
+        let mut dead_keys = Vec::new();
+        let mut base_chars = Vec::new();
+        for keycode in keymap.keycodes() {
+            // The unmodified, Shift, AltGr and Shift+AltGr levels, where layouts put their dead
+            // keys.
+            for level in 0..4 {
+                let keysym = keymap.keysym_by_level(layout, keycode, level);
+                if keysym == 0 {
+                    continue;
+                }
+
+                compose_state.reset();
+                if let ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSING) =
+                    compose_state.feed(keysym)
+                {
+                    // Like when pressing a dead key twice, this produces its non-combining
+                    // variant, which is what `Key::Dead` holds.
+                    compose_state.feed(keysym);
+                    let text = compose_state.get_string(&mut self.scratch_buffer);
+                    if let Some(dead) = text.as_deref().and_then(single_char) {
+                        dead_keys.push((keysym, dead));
+                    }
+                } else if let Some(base) =
+                    char::from_u32(unsafe { (XKBH.xkb_keysym_to_utf32)(keysym) })
+                        .filter(|base| *base != '\0')
+                {
+                    base_chars.push((keysym, base));
+                }
+            }
+        }
+
+        for &(dead_keysym, dead) in &dead_keys {
+            for &(base_keysym, base) in &base_chars {
+                compose_state.reset();
+                compose_state.feed(dead_keysym);
+                if let ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSED) =
+                    compose_state.feed(base_keysym)
+                {
+                    let text = compose_state.get_string(&mut self.scratch_buffer);
+                    if let Some(composed) = text.as_deref().and_then(single_char) {
+                        compositions.insert((dead, base), composed);
+                    }
+                }
+            }
+        }
+
+        compositions
+    }
+
     #[cfg(wayland_platform)]
     pub fn set_keymap_from_fd(&mut self, fd: OwnedFd, size: usize) {
         let keymap = XkbKeymap::from_fd(&self.context, fd, size);
@@ -175,6 +273,32 @@ impl Context {
     }
 }
 
+/// A snapshot of the keys of a layout, see [`Context::layout_keys`].
+///
+/// XKB state can't leave the event loop, so this is what the windows look the keys up in.
+#[derive(Debug, Default, Clone)]
+pub struct LayoutKeys {
+    keys: HashMap<PhysicalKey, Key>,
+    /// The composed character by dead key and base character.
+    compositions: HashMap<(char, char), char>,
+}
+
+impl LayoutKeys {
+    pub fn get(&self, physical_key: PhysicalKey) -> Option<Key> {
+        self.keys.get(&physical_key).cloned()
+    }
+
+    pub fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        self.compositions.get(&(dead, base)).copied()
+    }
+}
+
+/// The only character of `text`, if it has exactly one.
+fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    chars.next().filter(|_| chars.next().is_none())
+}
+
 pub struct KeyContext<'a> {
     pub state: &'a mut XkbState,
     pub keymap: &'a mut XkbKeymap,
@@ -189,18 +313,36 @@ impl KeyContext<'_> {
         keycode: u32,
         state: ElementState,
         repeat: bool,
+        timestamp: Option<Duration>,
     ) -> KeyEvent {
         let mut event =
             KeyEventResults::new(self, keycode, !repeat && state == ElementState::Pressed);
         let physical_key = keymap::raw_keycode_to_physicalkey(keycode);
         let (logical_key, location) = event.key();
         let text = event.text();
+        let composed = event.is_composed() && text.is_some();
         let (key_without_modifiers, _) = event.key_without_modifiers();
         let text_with_all_modifiers = event.text_with_all_modifiers();
-
-        let platform_specific = KeyEventExtra { text_with_all_modifiers, key_without_modifiers };
-
-        KeyEvent { physical_key, logical_key, text, location, state, repeat, platform_specific }
+        let text_unshifted = event.text_at_level(0);
+        let text_shifted = event.text_at_level(1);
+
+        let platform_specific =
+            KeyEventExtra { text_with_all_modifiers, text_unshifted, text_shifted };
+
+        let event = KeyEvent {
+            physical_key,
+            logical_key,
+            key_without_modifiers,
+            text,
+            composed,
+            location,
+            state,
+            repeat,
+            timestamp,
+            platform_specific,
+        };
+        event.debug_check_text();
+        event
     }
 
     fn keysym_to_utf8_raw(&mut self, keysym: u32) -> Option<SmolStr> {
@@ -260,30 +402,41 @@ impl<'a, 'b> KeyEventResults<'a, 'b> {
             Err(undefined) => undefined,
         };
 
-        if let ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSING) = self.compose {
-            let compose_state = self.context.compose_state2.as_mut().unwrap();
-            // When pressing a dead key twice, the non-combining variant of that character will
-            // be produced. Since this function only concerns itself with a single keypress, we
-            // simulate this double press here by feeding the keysym to the compose state
-            // twice.
-
-            compose_state.feed(self.keysym);
-            if matches!(compose_state.feed(self.keysym), ComposeStatus::Accepted(_)) {
+        match self.compose {
+            ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSING) => {
+                let compose_state = self.context.compose_state2.as_mut().unwrap();
+                // When pressing a dead key twice, the non-combining variant of that character
+                // will be produced. Since this function only concerns itself with a single
+                // keypress, we simulate this double press here by feeding the keysym to the
+                // compose state twice.
+                compose_state.reset();
+                compose_state.feed(self.keysym);
+                let dead_key = matches!(
+                    compose_state.feed(self.keysym),
+                    ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSED)
+                );
+                // Otherwise, the key continues a sequence started with the compose key
+                // (`Multi_key`), and is reported like a dead key with the character it types.
+                let text = if dead_key {
+                    compose_state.get_string(self.context.scratch_buffer)
+                } else {
+                    self.context.keysym_to_utf8_raw(self.keysym)
+                };
                 // Extracting only a single `char` here *should* be fine, assuming that no
                 // dead key's non-combining variant ever occupies more than one `char`.
-                let text = compose_state.get_string(self.context.scratch_buffer);
-                let key = Key::Dead(text.and_then(|s| s.chars().next()));
+                (Key::Dead(text.and_then(|s| s.chars().next())), location)
+            },
+            ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSED) => {
+                let key = self.composed_text().ok().flatten().map(Key::Character).unwrap_or(key);
                 (key, location)
-            } else {
+            },
+            // A cancelled sequence doesn't produce text, but the key is still reported with the
+            // character it types.
+            _ => {
+                let key =
+                    self.context.keysym_to_utf8_raw(self.keysym).map(Key::Character).unwrap_or(key);
                 (key, location)
-            }
-        } else {
-            let key = self
-                .composed_text()
-                .unwrap_or_else(|_| self.context.keysym_to_utf8_raw(self.keysym))
-                .map(Key::Character)
-                .unwrap_or(key);
-            (key, location)
+            },
         }
     }
 
@@ -303,6 +456,30 @@ impl<'a, 'b> KeyEventResults<'a, 'b> {
         }
     }
 
+    /// The text the key produces at the given shift level of its current layout, ignoring compose
+    /// sequences. Dead keys produce their non-combining character.
+    pub fn text_at_level(&mut self, level: u32) -> Option<SmolStr> {
+        let layout = self.context.state.layout(self.keycode);
+        let keysym = self.context.keymap.keysym_by_level(layout, self.keycode, level);
+        if keysym == 0 {
+            return None;
+        }
+        if let Some(text) = self.context.keysym_to_utf8_raw(keysym) {
+            return Some(text);
+        }
+
+        // Like when pressing a dead key twice, which produces its non-combining variant.
+        let compose_state = self.context.compose_state2.as_mut()?;
+        compose_state.reset();
+        compose_state.feed(keysym);
+        match compose_state.feed(keysym) {
+            ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSED) => {
+                compose_state.get_string(self.context.scratch_buffer)
+            },
+            _ => None,
+        }
+    }
+
     fn keysym_to_key(&self, keysym: u32) -> Result<(Key, KeyLocation), (Key, KeyLocation)> {
         let location = keymap::keysym_location(keysym);
         let key = keymap::keysym_to_key(keysym);
@@ -327,6 +504,10 @@ impl<'a, 'b> KeyEventResults<'a, 'b> {
         }
     }
 
+    fn is_composed(&self) -> bool {
+        matches!(self.compose, ComposeStatus::Accepted(xkb_compose_status::XKB_COMPOSE_COMPOSED))
+    }
+
     fn composed_text(&mut self) -> Result<Option<SmolStr>, ()> {
         match self.compose {
             ComposeStatus::Accepted(status) => match status {
@@ -413,3 +594,105 @@ fn byte_slice_to_smol_str(bytes: &[u8]) -> Option<SmolStr> {
         })
         .ok()
 }
+
+#[cfg(all(test, wayland_platform))]
+mod tests {
+    use std::ffi::CString;
+
+    use super::*;
+    use crate::keyboard::NamedKey;
+
+    const KEYMAP: &str = r#"xkb_keymap {
+    xkb_keycodes "test" {
+        minimum = 8;
+        maximum = 255;
+        <AE06> = 15;
+        <AD03> = 26;
+        <AC01> = 38;
+        <AC11> = 48;
+        <AB02> = 53;
+        <RALT> = 108;
+    };
+    xkb_types "test" {
+        type "TWO_LEVEL" {
+            modifiers = Shift;
+            map[Shift] = Level2;
+            level_name[Level1] = "Base";
+            level_name[Level2] = "Shift";
+        };
+    };
+    xkb_compatibility "test" {};
+    xkb_symbols "test" {
+        key <AE06> { type = "TWO_LEVEL", [ 6, dead_circumflex ] };
+        key <AD03> { type = "TWO_LEVEL", [ e, E ] };
+        key <AC01> { type = "TWO_LEVEL", [ a, A ] };
+        key <AC11> { type = "TWO_LEVEL", [ dead_acute, dead_diaeresis ] };
+        key <AB02> { type = "TWO_LEVEL", [ x, X ] };
+        key <RALT> { type = "TWO_LEVEL", [ Multi_key, Multi_key ] };
+    };
+};"#;
+
+    /// A context with the test keymap and the compose sequences of `en_US.UTF-8`.
+    ///
+    /// Returns `None` when libxkbcommon or the compose files of libX11 aren't installed.
+    fn test_context() -> Option<Context> {
+        xkb::xkbcommon_option()?;
+
+        let mut context = Context::new().unwrap();
+        // Don't depend on the locale of the environment.
+        let locale = CString::new("en_US.UTF-8").unwrap();
+        let compose_table = XkbComposeTable::from_locale(&context.context, &locale)?;
+        context.compose_state1 = compose_table.new_state();
+        context.compose_state2 = compose_table.new_state();
+        context.compose_table = Some(compose_table);
+        let keymap = CString::new(KEYMAP).unwrap();
+        let keymap = XkbKeymap::from_string(&context.context, &keymap).unwrap();
+        context.state = XkbState::new_wayland(&keymap);
+        context.keymap = Some(keymap);
+        Some(context)
+    }
+
+    #[test]
+    fn dead_key_compositions() {
+        let Some(mut context) = test_context() else { return };
+
+        let keys = context.layout_keys();
+        assert_eq!(keys.compose_dead_key('^', 'e'), Some('ê'));
+        assert_eq!(keys.compose_dead_key('^', 'A'), Some('Â'));
+        assert_eq!(keys.compose_dead_key('´', 'e'), Some('é'));
+        assert_eq!(keys.compose_dead_key('¨', 'a'), Some('ä'));
+        // No such composition, nor such dead key.
+        assert_eq!(keys.compose_dead_key('^', 'x'), None);
+        assert_eq!(keys.compose_dead_key('`', 'e'), None);
+        // The composition has to be with a character of the layout.
+        assert_eq!(keys.compose_dead_key('^', 'o'), None);
+    }
+
+    #[test]
+    fn compose_key_sequence() {
+        const MULTI_KEY: u32 = 108;
+        const A: u32 = 38;
+        const E: u32 = 26;
+
+        let Some(mut context) = test_context() else { return };
+        let mut key_context = context.key_context().unwrap();
+        let mut press = |keycode| {
+            let event = key_context.process_key_event(keycode, ElementState::Pressed, false, None);
+            (event.logical_key, event.text)
+        };
+
+        // The keys following the compose key are reported like dead keys, with the character they
+        // type.
+        assert_eq!(press(MULTI_KEY), (Key::Named(NamedKey::Compose), None));
+        assert_eq!(press(A), (Key::Dead(Some('a')), None));
+        // The last one is reported with the composed character.
+        assert_eq!(press(E), (Key::Character("æ".into()), Some("æ".into())));
+        // The sequence is over.
+        assert_eq!(press(E), (Key::Character("e".into()), Some("e".into())));
+
+        // Releases don't take part in the sequence.
+        press(MULTI_KEY);
+        let release = key_context.process_key_event(A, ElementState::Released, false, None);
+        assert_eq!(release.logical_key, Key::Character("a".into()));
+    }
+}
@@ -15,17 +15,23 @@ use crate::platform_impl::common::xkb::keymap::XkbKeymap;
 use crate::platform_impl::common::xkb::XKBXH;
 use crate::platform_impl::common::xkb::{make_string_with, XKBH};
 
+/// The virtual modifier scroll lock is bound to in the common keymaps, there's no real modifier
+/// for it.
+const MOD_NAME_SCROLL: &[u8] = b"ScrollLock\0";
+
 #[derive(Debug)]
 pub struct XkbState {
     state: NonNull<xkb_state>,
     modifiers: ModifiersState,
+    /// The real modifiers AltGr sets, see `XkbKeymap::level_three_mask`.
+    level_three_mask: xkb::xkb_mod_mask_t,
 }
 
 impl XkbState {
     #[cfg(wayland_platform)]
     pub fn new_wayland(keymap: &XkbKeymap) -> Option<Self> {
         let state = NonNull::new(unsafe { (XKBH.xkb_state_new)(keymap.as_ptr()) })?;
-        Some(Self::new_inner(state))
+        Some(Self::new_inner(state, keymap))
     }
 
     #[cfg(x11_platform)]
@@ -34,12 +40,13 @@ impl XkbState {
             (XKBXH.xkb_x11_state_new_from_device)(keymap.as_ptr(), xcb, keymap._core_keyboard_id)
         };
         let state = NonNull::new(state)?;
-        Some(Self::new_inner(state))
+        Some(Self::new_inner(state, keymap))
     }
 
-    fn new_inner(state: NonNull<xkb_state>) -> Self {
+    fn new_inner(state: NonNull<xkb_state>, keymap: &XkbKeymap) -> Self {
         let modifiers = ModifiersState::default();
-        let mut this = Self { state, modifiers };
+        let level_three_mask = keymap.level_three_mask();
+        let mut this = Self { state, modifiers, level_three_mask };
         this.reload_modifiers();
         this
     }
@@ -52,6 +59,16 @@ impl XkbState {
         unsafe { (XKBH.xkb_state_key_get_layout)(self.state.as_ptr(), key) }
     }
 
+    /// The layout currently in effect.
+    pub fn active_layout(&self) -> xkb_layout_index_t {
+        unsafe {
+            (XKBH.xkb_state_serialize_layout)(
+                self.state.as_ptr(),
+                xkb_state_component::XKB_STATE_LAYOUT_EFFECTIVE,
+            )
+        }
+    }
+
     #[cfg(x11_platform)]
     pub fn depressed_modifiers(&mut self) -> xkb::xkb_mod_mask_t {
         unsafe {
@@ -127,19 +144,45 @@ impl XkbState {
     fn reload_modifiers(&mut self) {
         self.modifiers.ctrl = self.mod_name_is_active(xkb::XKB_MOD_NAME_CTRL);
         self.modifiers.alt = self.mod_name_is_active(xkb::XKB_MOD_NAME_ALT);
+        self.modifiers.alt_graph = self.mods_are_active(self.level_three_mask);
         self.modifiers.shift = self.mod_name_is_active(xkb::XKB_MOD_NAME_SHIFT);
-        self.modifiers.caps_lock = self.mod_name_is_active(xkb::XKB_MOD_NAME_CAPS);
         self.modifiers.logo = self.mod_name_is_active(xkb::XKB_MOD_NAME_LOGO);
-        self.modifiers.num_lock = self.mod_name_is_active(xkb::XKB_MOD_NAME_NUM);
+        self.modifiers.caps_lock = self.mod_name_is_locked(xkb::XKB_MOD_NAME_CAPS);
+        self.modifiers.num_lock = self.mod_name_is_locked(xkb::XKB_MOD_NAME_NUM);
+        self.modifiers.scroll_lock = self.mod_name_is_locked(MOD_NAME_SCROLL);
     }
 
     /// Check if the modifier is active within xkb.
     fn mod_name_is_active(&mut self, name: &[u8]) -> bool {
+        self.mod_name_in_component(name, xkb_state_component::XKB_STATE_MODS_EFFECTIVE)
+    }
+
+    /// Check if any of the real modifiers of `mask` is active within xkb.
+    fn mods_are_active(&mut self, mask: xkb::xkb_mod_mask_t) -> bool {
+        let active = unsafe {
+            (XKBH.xkb_state_serialize_mods)(
+                self.state.as_ptr(),
+                xkb_state_component::XKB_STATE_MODS_EFFECTIVE,
+            )
+        };
+        active & mask != 0
+    }
+
+    /// Check if the modifier is latched or locked within xkb.
+    fn mod_name_is_locked(&mut self, name: &[u8]) -> bool {
+        self.mod_name_in_component(
+            name,
+            xkb_state_component::XKB_STATE_MODS_LATCHED
+                | xkb_state_component::XKB_STATE_MODS_LOCKED,
+        )
+    }
+
+    fn mod_name_in_component(&mut self, name: &[u8], component: xkb_state_component) -> bool {
         unsafe {
             (XKBH.xkb_state_mod_name_is_active)(
                 self.state.as_ptr(),
                 name.as_ptr() as *const c_char,
-                xkb_state_component::XKB_STATE_MODS_EFFECTIVE,
+                component,
             ) > 0
         }
     }
@@ -165,6 +208,8 @@ pub struct ModifiersState {
     pub ctrl: bool,
     /// The "alt" key
     pub alt: bool,
+    /// The "AltGr" key
+    pub alt_graph: bool,
     /// The "shift" key
     pub shift: bool,
     /// The "Caps lock" key
@@ -175,6 +220,8 @@ pub struct ModifiersState {
     pub logo: bool,
     /// The "Num lock" key
     pub num_lock: bool,
+    /// The "Scroll lock" key
+    pub scroll_lock: bool,
 }
 
 impl From<ModifiersState> for crate::keyboard::ModifiersState {
@@ -183,7 +230,99 @@ impl From<ModifiersState> for crate::keyboard::ModifiersState {
         to_mods.set(crate::keyboard::ModifiersState::SHIFT, mods.shift);
         to_mods.set(crate::keyboard::ModifiersState::CONTROL, mods.ctrl);
         to_mods.set(crate::keyboard::ModifiersState::ALT, mods.alt);
+        to_mods.set(crate::keyboard::ModifiersState::ALT_GRAPH, mods.alt_graph);
         to_mods.set(crate::keyboard::ModifiersState::SUPER, mods.logo);
+        to_mods.set(crate::keyboard::ModifiersState::CAPS_LOCK, mods.caps_lock);
+        to_mods.set(crate::keyboard::ModifiersState::NUM_LOCK, mods.num_lock);
+        to_mods.set(crate::keyboard::ModifiersState::SCROLL_LOCK, mods.scroll_lock);
         to_mods
     }
 }
+
+#[cfg(all(test, wayland_platform))]
+mod tests {
+    use std::ffi::CString;
+
+    use super::*;
+    use crate::platform_impl::common::xkb::XkbContext;
+
+    /// A keymap with AltGr on the right Alt key, where `level_three` binds it to a real modifier.
+    fn keymap_text(level_three: &str) -> CString {
+        let keymap = format!(
+            r#"xkb_keymap {{
+    xkb_keycodes "test" {{
+        minimum = 8;
+        maximum = 255;
+        <AE01> = 10;
+        <RALT> = 108;
+    }};
+    xkb_types "test" {{
+        virtual_modifiers LevelThree;
+        type "ONE_LEVEL" {{
+            modifiers = none;
+            level_name[Level1] = "Any";
+        }};
+        type "FOUR_LEVEL" {{
+            modifiers = Shift + LevelThree;
+            map[Shift] = Level2;
+            map[LevelThree] = Level3;
+            map[Shift + LevelThree] = Level4;
+            level_name[Level1] = "Base";
+            level_name[Level2] = "Shift";
+            level_name[Level3] = "AltGr";
+            level_name[Level4] = "Shift AltGr";
+        }};
+    }};
+    xkb_compatibility "test" {{
+        virtual_modifiers LevelThree;
+        interpret ISO_Level3_Shift {{
+            useModMapMods = level1;
+            virtualModifier = LevelThree;
+            action = SetMods(modifiers = LevelThree);
+        }};
+    }};
+    xkb_symbols "test" {{
+        key <AE01> {{ type = "FOUR_LEVEL", [ 1, exclam, onesuperior, exclamdown ] }};
+        {level_three}
+    }};
+}};"#
+        );
+        CString::new(keymap).unwrap()
+    }
+
+    #[test]
+    fn alt_graph_follows_level_three_mapping() {
+        if xkb::xkbcommon_option().is_none() {
+            // libxkbcommon isn't installed.
+            return;
+        }
+
+        const MOD1: u32 = 1 << 3;
+        const MOD3: u32 = 1 << 5;
+        const MOD5: u32 = 1 << 7;
+        let context = XkbContext::new().unwrap();
+
+        // An unusual mapping, which can't be assumed.
+        let keymap = keymap_text(
+            r#"key <RALT> { type = "ONE_LEVEL", [ ISO_Level3_Shift ] };
+        modifier_map Mod3 { <RALT> };"#,
+        );
+        let keymap = XkbKeymap::from_string(&context, &keymap).unwrap();
+        assert_eq!(keymap.level_three_mask(), MOD3);
+
+        let mut state = XkbState::new_wayland(&keymap).unwrap();
+        assert!(!state.modifiers().alt_graph);
+        state.update_modifiers(MOD3, 0, 0, 0, 0, 0);
+        assert!(state.modifiers().alt_graph);
+        assert!(!state.modifiers().alt);
+        state.update_modifiers(MOD5, 0, 0, 0, 0, 0);
+        assert!(!state.modifiers().alt_graph);
+        state.update_modifiers(MOD1, 0, 0, 0, 0, 0);
+        assert!(!state.modifiers().alt_graph);
+        assert!(state.modifiers().alt);
+
+        // Without a key for it, fall back to the usual mapping.
+        let keymap = XkbKeymap::from_string(&context, &keymap_text("")).unwrap();
+        assert_eq!(keymap.level_three_mask(), MOD5);
+    }
+}
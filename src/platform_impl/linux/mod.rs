@@ -11,7 +11,9 @@ use std::{ffi::CStr, mem::MaybeUninit, os::raw::*, sync::Arc, sync::Mutex};
 
 use smol_str::SmolStr;
 
-pub(crate) use self::common::xkb::{physicalkey_to_scancode, scancode_to_physicalkey};
+pub(crate) use self::common::xkb::{
+    key_to_keysym, physicalkey_to_scancode, scancode_to_physicalkey,
+};
 #[cfg(x11_platform)]
 use self::x11::{XConnection, XError, XNotSupported};
 use crate::application::ApplicationHandler;
@@ -22,7 +24,7 @@ use crate::dpi::Size;
 use crate::error::{EventLoopError, NotSupportedError};
 use crate::event_loop::ActiveEventLoop;
 pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
-use crate::keyboard::Key;
+use crate::keyboard::PhysicalKey;
 use crate::monitor::VideoMode;
 use crate::platform::pump_events::PumpStatus;
 #[cfg(x11_platform)]
@@ -164,6 +166,21 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.scale_factor() as _)
     }
 
+    #[inline]
+    pub fn bit_depth(&self) -> Option<u32> {
+        match self {
+            #[cfg(x11_platform)]
+            MonitorHandle::X(m) => m.bit_depth(),
+            #[cfg(wayland_platform)]
+            MonitorHandle::Wayland(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        None
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         x11_or_wayland!(match self; MonitorHandle(m) => m.current_video_mode())
@@ -178,7 +195,28 @@ impl MonitorHandle {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct KeyEventExtra {
     pub text_with_all_modifiers: Option<SmolStr>,
-    pub key_without_modifiers: Key,
+    pub text_unshifted: Option<SmolStr>,
+    pub text_shifted: Option<SmolStr>,
+}
+
+impl KeyEventExtra {
+    #[cfg(any(feature = "test-util", feature = "serde"))]
+    pub(crate) fn synthetic(text: Option<&SmolStr>) -> Self {
+        Self { text_with_all_modifiers: text.cloned(), text_unshifted: None, text_shifted: None }
+    }
+
+    /// XKB state lives on the event loop, so the text is resolved when the event is created.
+    pub(crate) fn text_for_shift(
+        &self,
+        _physical_key: PhysicalKey,
+        shift: bool,
+    ) -> Option<SmolStr> {
+        if shift {
+            self.text_shifted.clone()
+        } else {
+            self.text_unshifted.clone()
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
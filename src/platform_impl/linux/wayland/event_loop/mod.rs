@@ -17,8 +17,9 @@ use crate::dpi::LogicalSize;
 use crate::error::{EventLoopError, OsError, RequestError};
 use crate::event::{DeviceEvent, StartCause, SurfaceSizeWriter, WindowEvent};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
 };
 use crate::platform::pump_events::PumpStatus;
 use crate::platform_impl::platform::min_timeout;
@@ -136,6 +137,7 @@ impl EventLoop {
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
             state: RefCell::new(winit_state),
+            common_state: Default::default(),
         };
 
         let event_loop = Self {
@@ -439,6 +441,12 @@ impl EventLoop {
                 // Redraw the frame while at it.
                 redraw_requested |= window.refresh_frame();
 
+                // The application is expected to draw its first frame now, even when it doesn't
+                // use `pre_present_notify`.
+                if redraw_requested {
+                    window.clear_placeholder();
+                }
+
                 redraw_requested.then_some(WindowEvent::RedrawRequested)
             });
 
@@ -571,6 +579,9 @@ pub struct ActiveEventLoop {
 
     /// Handle for the underlying event loop.
     pub handle: Arc<OwnedDisplayHandle>,
+
+    /// The state Winit keeps for the event loop.
+    pub(crate) common_state: CommonEventLoopState,
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -611,12 +622,24 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        let state = self.state.borrow();
+        let mut capabilities = WindowingCapabilities::MOVE_RESIZE;
+        let decoration_manager =
+            state.registry_state.globals_by_interface("zxdg_decoration_manager_v1").next();
+        capabilities
+            .set(WindowingCapabilities::SERVER_SIDE_DECORATIONS, decoration_manager.is_some());
+        WindowingInfo::new(WindowingBackend::Wayland, None, capabilities)
+    }
+
     fn create_window(
         &self,
         window_attributes: crate::window::WindowAttributes,
     ) -> Result<Box<dyn crate::window::Window>, RequestError> {
-        let window = crate::platform_impl::wayland::Window::new(self, window_attributes)?;
-        Ok(Box::new(window))
+        let window: Box<dyn crate::window::Window> =
+            Box::new(crate::platform_impl::wayland::Window::new(self, window_attributes)?);
+        self.common_state.register_window(&*window);
+        Ok(window)
     }
 
     fn available_monitors(&self) -> Box<dyn Iterator<Item = crate::monitor::MonitorHandle>> {
@@ -2,13 +2,14 @@
 
 pub use event_loop::{ActiveEventLoop, EventLoop};
 pub use output::MonitorHandle;
+use sctk::reexports::client::protocol::wl_output::Transform;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::Proxy;
 pub use window::Window;
 
 pub(super) use crate::cursor::OnlyCursorImage as CustomCursor;
 use crate::dpi::{LogicalSize, PhysicalSize};
-use crate::window::WindowId;
+use crate::window::{SurfaceTransform, WindowId};
 
 mod event_loop;
 mod output;
@@ -29,3 +30,37 @@ fn logical_to_physical_rounded(size: LogicalSize<u32>, scale_factor: f64) -> Phy
     let height = size.height as f64 * scale_factor;
     (width.round(), height.round()).into()
 }
+
+/// The rotations of Wayland transforms are counter-clockwise, while they are clockwise in winit.
+fn surface_transform(transform: Transform) -> SurfaceTransform {
+    match transform {
+        Transform::_90 => SurfaceTransform::Rotate270,
+        Transform::_180 => SurfaceTransform::Rotate180,
+        Transform::_270 => SurfaceTransform::Rotate90,
+        Transform::Flipped => SurfaceTransform::Mirrored,
+        Transform::Flipped90 => SurfaceTransform::MirroredRotate270,
+        Transform::Flipped180 => SurfaceTransform::MirroredRotate180,
+        Transform::Flipped270 => SurfaceTransform::MirroredRotate90,
+        _ => SurfaceTransform::Identity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotated_surface_transform() {
+        assert_eq!(surface_transform(Transform::Normal), SurfaceTransform::Identity);
+
+        // A surface on an output rotated counter-clockwise, e.g. a phone held in landscape.
+        let transform = surface_transform(Transform::_90);
+        assert_eq!(transform, SurfaceTransform::Rotate270);
+        assert!(transform.swaps_dimensions());
+
+        assert_eq!(surface_transform(Transform::_180), SurfaceTransform::Rotate180);
+        assert!(!SurfaceTransform::Rotate180.swaps_dimensions());
+        assert_eq!(surface_transform(Transform::Flipped90), SurfaceTransform::MirroredRotate270);
+        assert!(SurfaceTransform::MirroredRotate270.swaps_dimensions());
+    }
+}
@@ -1,8 +1,9 @@
 //! The keyboard input handling.
 
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use ahash::AHashMap;
 use calloop::timer::{TimeoutAction, Timer};
 use calloop::{LoopHandle, RegistrationToken};
 use sctk::reexports::client::protocol::wl_keyboard::{
@@ -17,6 +18,7 @@ use crate::keyboard::ModifiersState;
 use crate::platform_impl::common::xkb::Context;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::window::WindowState;
 use crate::platform_impl::wayland::{self, WindowId};
 
 impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
@@ -52,6 +54,13 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     WlKeymapFormat::XkbV1 => {
                         let context = &mut keyboard_state.xkb_context;
                         context.set_keymap_from_fd(fd, size as usize);
+                        update_keyboard_layout(
+                            state.windows.get_mut(),
+                            &mut state.events_sink,
+                            keyboard_state,
+                            data,
+                            true,
+                        );
                     },
                     _ => unreachable!(),
                 },
@@ -68,6 +77,8 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                         let mut window = window.lock().unwrap();
                         let was_unfocused = !window.has_focus();
                         window.add_seat_focus(data.seat.id());
+                        // Updated by the `Modifiers` event following the `Enter`.
+                        window.set_modifiers(seat_state.modifiers);
                         was_unfocused
                     },
                     None => return,
@@ -86,6 +97,14 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     state.events_sink.push_window_event(WindowEvent::Focused(true), window_id);
                 }
 
+                update_keyboard_layout(
+                    state.windows.get_mut(),
+                    &mut state.events_sink,
+                    keyboard_state,
+                    data,
+                    true,
+                );
+
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
                 if std::mem::take(&mut seat_state.modifiers_pending) {
                     state.events_sink.push_window_event(
@@ -110,6 +129,9 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     Some(window) => {
                         let mut window = window.lock().unwrap();
                         window.remove_seat_focus(&data.seat.id());
+                        if !window.has_focus() {
+                            window.set_modifiers(ModifiersState::empty());
+                        }
                         window.has_focus()
                     },
                     None => return,
@@ -129,7 +151,9 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     state.events_sink.push_window_event(WindowEvent::Focused(false), window_id);
                 }
             },
-            WlKeyboardEvent::Key { key, state: WEnum::Value(WlKeyState::Pressed), .. } => {
+            WlKeyboardEvent::Key {
+                key, state: WEnum::Value(WlKeyState::Pressed), time, ..
+            } => {
                 let key = key + 8;
 
                 key_input(
@@ -139,6 +163,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     key,
                     ElementState::Pressed,
                     false,
+                    Some(Duration::from_millis(time as u64)),
                 );
 
                 let delay = match keyboard_state.repeat_info {
@@ -146,7 +171,9 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     RepeatInfo::Disable => return,
                 };
 
-                if !keyboard_state.xkb_context.keymap_mut().unwrap().key_repeats(key) {
+                if !keyboard_state.xkb_context.keymap_mut().unwrap().key_repeats(key)
+                    || !key_repeat_enabled(state.windows.get_mut(), data)
+                {
                     return;
                 }
 
@@ -167,6 +194,12 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                         state.dispatched_events = true;
 
                         let data = wl_keyboard.data::<KeyboardData>().unwrap();
+
+                        // The window could've disabled the repeat while the key is being held.
+                        if !key_repeat_enabled(state.windows.get_mut(), data) {
+                            return TimeoutAction::Drop;
+                        }
+
                         let seat_state = match state.seats.get_mut(&data.seat.id()) {
                             Some(seat_state) => seat_state,
                             None => return TimeoutAction::Drop,
@@ -191,6 +224,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                             repeat_keycode,
                             ElementState::Pressed,
                             true,
+                            None,
                         );
 
                         // NOTE: the gap could change dynamically while repeat is going.
@@ -201,7 +235,9 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     })
                     .ok();
             },
-            WlKeyboardEvent::Key { key, state: WEnum::Value(WlKeyState::Released), .. } => {
+            WlKeyboardEvent::Key {
+                key, state: WEnum::Value(WlKeyState::Released), time, ..
+            } => {
                 let key = key + 8;
 
                 key_input(
@@ -211,6 +247,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     key,
                     ElementState::Released,
                     false,
+                    Some(Duration::from_millis(time as u64)),
                 );
 
                 if keyboard_state.repeat_info != RepeatInfo::Disable
@@ -235,6 +272,15 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 xkb_state.update_modifiers(mods_depressed, mods_latched, mods_locked, 0, 0, group);
                 seat_state.modifiers = xkb_state.modifiers().into();
 
+                // The layout is switched by changing the group.
+                update_keyboard_layout(
+                    state.windows.get_mut(),
+                    &mut state.events_sink,
+                    keyboard_state,
+                    data,
+                    false,
+                );
+
                 // HACK: part of the workaround from `WlKeyboardEvent::Enter`.
                 let window_id = match *data.window_id.lock().unwrap() {
                     Some(window_id) => window_id,
@@ -243,6 +289,9 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                         return;
                     },
                 };
+                if let Some(window) = state.windows.get_mut().get(&window_id) {
+                    window.lock().unwrap().set_modifiers(seat_state.modifiers);
+                }
 
                 state.events_sink.push_window_event(
                     WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
@@ -356,6 +405,57 @@ impl KeyboardData {
     }
 }
 
+/// Whether the window focused by the keyboard wants to receive key repeats.
+fn key_repeat_enabled(
+    windows: &AHashMap<WindowId, Arc<Mutex<WindowState>>>,
+    data: &KeyboardData,
+) -> bool {
+    data.window_id
+        .lock()
+        .unwrap()
+        .and_then(|window_id| windows.get(&window_id))
+        .map_or(true, |window| window.lock().unwrap().key_repeat_enabled())
+}
+
+/// Update the layout of the focused window, notifying it when the layout changed.
+///
+/// The keys of the layout are also refreshed when `keys_outdated` is set, as the keymap or the
+/// window may have changed without changing the name of the layout.
+fn update_keyboard_layout(
+    windows: &AHashMap<WindowId, Arc<Mutex<WindowState>>>,
+    event_sink: &mut EventSink,
+    keyboard_state: &mut KeyboardState,
+    data: &KeyboardData,
+    keys_outdated: bool,
+) {
+    let window_id = match *data.window_id.lock().unwrap() {
+        Some(window_id) => window_id,
+        None => return,
+    };
+    let window = match windows.get(&window_id) {
+        Some(window) => window,
+        None => return,
+    };
+
+    let layout = keyboard_state.xkb_context.layout_name();
+    let mut window = window.lock().unwrap();
+    let previous_direction = window.keyboard_layout_direction();
+    let changed = window.set_keyboard_layout(layout);
+    let direction = window.keyboard_layout_direction();
+    if changed || keys_outdated {
+        window.set_layout_keys(keyboard_state.xkb_context.layout_keys());
+    }
+    drop(window);
+
+    if changed {
+        event_sink.push_window_event(WindowEvent::KeyboardLayoutChanged, window_id);
+        if let Some(direction) = direction.filter(|&d| Some(d) != previous_direction) {
+            let event = WindowEvent::KeyboardLayoutDirectionChanged(direction);
+            event_sink.push_window_event(event, window_id);
+        }
+    }
+}
+
 fn key_input(
     keyboard_state: &mut KeyboardState,
     event_sink: &mut EventSink,
@@ -363,6 +463,7 @@ fn key_input(
     keycode: u32,
     state: ElementState,
     repeat: bool,
+    timestamp: Option<Duration>,
 ) {
     let window_id = match *data.window_id.lock().unwrap() {
         Some(window_id) => window_id,
@@ -370,7 +471,7 @@ fn key_input(
     };
 
     if let Some(mut key_context) = keyboard_state.xkb_context.key_context() {
-        let event = key_context.process_key_event(keycode, state, repeat);
+        let event = key_context.process_key_event(keycode, state, repeat, timestamp);
         let event = WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: false };
         event_sink.push_window_event(event, window_id);
     }
@@ -7,6 +7,7 @@ use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_touch::WlTouch;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1;
 use sctk::reexports::protocols::wp::relative_pointer::zv1::client::zwp_relative_pointer_v1::ZwpRelativePointerV1;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
@@ -23,6 +24,8 @@ mod text_input;
 mod touch;
 
 use keyboard::{KeyboardData, KeyboardState};
+use pointer::pointer_gestures::PinchGestureData;
+pub use pointer::pointer_gestures::PointerGesturesState;
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
 use text_input::TextInputData;
@@ -49,6 +52,9 @@ pub struct WinitSeatState {
     /// The relative pointer bound on the seat.
     relative_pointer: Option<ZwpRelativePointerV1>,
 
+    /// The pinch gesture bound on the seat.
+    pinch_gesture: Option<ZwpPointerGesturePinchV1>,
+
     /// The keyboard bound on the seat.
     keyboard_state: Option<KeyboardState>,
 
@@ -118,6 +124,14 @@ impl SeatHandler for WinitState {
                     )
                 });
 
+                seat_state.pinch_gesture = self.pointer_gestures.as_ref().map(|manager| {
+                    manager.get_pinch_gesture(
+                        themed_pointer.pointer(),
+                        queue_handle,
+                        PinchGestureData::default(),
+                    )
+                });
+
                 let themed_pointer = Arc::new(themed_pointer);
 
                 // Register cursor surface.
@@ -171,6 +185,10 @@ impl SeatHandler for WinitState {
                     relative_pointer.destroy();
                 }
 
+                if let Some(pinch_gesture) = seat_state.pinch_gesture.take() {
+                    pinch_gesture.destroy();
+                }
+
                 if let Some(pointer) = seat_state.pointer.take() {
                     let pointer_data = pointer.pointer().winit_data();
 
@@ -179,7 +197,10 @@ impl SeatHandler for WinitState {
                     let _ = self.pointer_surfaces.remove(&surface_id);
 
                     // Remove the inner locks/confines before dropping the pointer.
-                    pointer_data.unlock_pointer();
+                    if let Some(window_id) = pointer_data.unlock_pointer() {
+                        let event = WindowEvent::PointerLock { locked: false };
+                        self.events_sink.push_window_event(event, window_id);
+                    }
                     pointer_data.unconfine_pointer();
 
                     if pointer.pointer().version() >= 3 {
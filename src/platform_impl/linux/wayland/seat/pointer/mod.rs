@@ -12,7 +12,7 @@ use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
 use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_confined_pointer_v1::ZwpConfinedPointerV1;
-use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_locked_pointer_v1::ZwpLockedPointerV1;
+use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_locked_pointer_v1::{self, ZwpLockedPointerV1};
 use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_device_v1::WpCursorShapeDeviceV1;
 use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_manager_v1::WpCursorShapeManagerV1;
 use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_pointer_constraints_v1::{Lifetime, ZwpPointerConstraintsV1};
@@ -35,6 +35,7 @@ use crate::event::{
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, WindowId};
 
+pub mod pointer_gestures;
 pub mod relative_pointer;
 
 impl PointerHandler for WinitState {
@@ -156,19 +157,20 @@ impl PointerHandler for WinitState {
                         window_id,
                     );
                 },
-                PointerEventKind::Motion { .. } => {
+                PointerEventKind::Motion { time } => {
                     self.events_sink.push_window_event(
                         WindowEvent::PointerMoved {
                             primary: true,
                             device_id: None,
                             position,
                             source: PointerSource::Mouse,
+                            timestamp: Some(Duration::from_millis(time as u64)),
                         },
                         window_id,
                     );
                 },
-                ref kind @ PointerEventKind::Press { button, serial, .. }
-                | ref kind @ PointerEventKind::Release { button, serial, .. } => {
+                ref kind @ PointerEventKind::Press { button, serial, time }
+                | ref kind @ PointerEventKind::Release { button, serial, time } => {
                     // Update the last button serial.
                     pointer.winit_data().inner.lock().unwrap().latest_button_serial = serial;
 
@@ -185,9 +187,20 @@ impl PointerHandler for WinitState {
                             state,
                             position,
                             button: button.into(),
+                            timestamp: Some(Duration::from_millis(time as u64)),
                         },
                         window_id,
                     );
+
+                    if state == ElementState::Pressed && button == MouseButton::Left {
+                        window.hit_test_press(
+                            position,
+                            seat,
+                            serial,
+                            window_id,
+                            &mut self.window_compositor_updates,
+                        );
+                    }
                 },
                 PointerEventKind::Axis { horizontal, vertical, .. } => {
                     // Get the current phase.
@@ -230,7 +243,12 @@ impl PointerHandler for WinitState {
                     };
 
                     self.events_sink.push_window_event(
-                        WindowEvent::MouseWheel { device_id: None, delta, phase },
+                        WindowEvent::MouseWheel {
+                            device_id: None,
+                            delta,
+                            phase,
+                            momentum_phase: None,
+                        },
                         window_id,
                     )
                 },
@@ -271,16 +289,28 @@ impl WinitPointerData {
                 None,
                 Lifetime::Persistent,
                 queue_handle,
-                GlobalData,
+                LockedPointerData::new(wayland::make_wid(surface)),
             ));
         }
     }
 
-    pub fn unlock_pointer(&self) {
-        let mut inner = self.inner.lock().unwrap();
-        if let Some(locked_pointer) = inner.locked_pointer.take() {
-            locked_pointer.destroy();
-        }
+    /// Remove the pointer lock, returning the window it was engaged on, if any.
+    pub fn unlock_pointer(&self) -> Option<WindowId> {
+        let locked_pointer = self.inner.lock().unwrap().locked_pointer.take()?;
+        let data = locked_pointer.data::<LockedPointerData>();
+        let window_id = data.filter(|data| data.is_locked()).map(|data| data.window_id);
+        locked_pointer.destroy();
+        window_id
+    }
+
+    /// Whether the compositor has engaged the pointer lock.
+    pub fn is_pointer_locked(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .locked_pointer
+            .as_ref()
+            .and_then(|locked_pointer| locked_pointer.data::<LockedPointerData>())
+            .is_some_and(LockedPointerData::is_locked)
     }
 
     pub fn confine_pointer(
@@ -448,15 +478,47 @@ impl Dispatch<ZwpPointerConstraintsV1, GlobalData, WinitState> for PointerConstr
     }
 }
 
-impl Dispatch<ZwpLockedPointerV1, GlobalData, WinitState> for PointerConstraintsState {
+/// The state of a pointer lock, as reported by the compositor.
+#[derive(Debug)]
+pub struct LockedPointerData {
+    window_id: WindowId,
+    locked: Mutex<bool>,
+}
+
+impl LockedPointerData {
+    fn new(window_id: WindowId) -> Self {
+        Self { window_id, locked: Mutex::new(false) }
+    }
+
+    fn is_locked(&self) -> bool {
+        *self.locked.lock().unwrap()
+    }
+
+    /// Record the new lock state, returning the event to send if it changed.
+    fn update(&self, locked: bool) -> Option<WindowEvent> {
+        let was_locked = std::mem::replace(&mut *self.locked.lock().unwrap(), locked);
+        (was_locked != locked).then_some(WindowEvent::PointerLock { locked })
+    }
+}
+
+impl Dispatch<ZwpLockedPointerV1, LockedPointerData, WinitState> for PointerConstraintsState {
     fn event(
-        _state: &mut WinitState,
+        state: &mut WinitState,
         _proxy: &ZwpLockedPointerV1,
-        _event: <ZwpLockedPointerV1 as wayland_client::Proxy>::Event,
-        _data: &GlobalData,
+        event: <ZwpLockedPointerV1 as wayland_client::Proxy>::Event,
+        data: &LockedPointerData,
         _conn: &Connection,
         _qhandle: &QueueHandle<WinitState>,
     ) {
+        let locked = match event {
+            zwp_locked_pointer_v1::Event::Locked => true,
+            zwp_locked_pointer_v1::Event::Unlocked => false,
+            _ => return,
+        };
+
+        if let Some(event) = data.update(locked) {
+            state.events_sink.push_window_event(event, data.window_id);
+        }
     }
 }
 
@@ -502,5 +564,25 @@ delegate_dispatch!(WinitState: [ WlPointer: WinitPointerData] => SeatState);
 delegate_dispatch!(WinitState: [ WpCursorShapeManagerV1: GlobalData] => SeatState);
 delegate_dispatch!(WinitState: [ WpCursorShapeDeviceV1: GlobalData] => SeatState);
 delegate_dispatch!(WinitState: [ZwpPointerConstraintsV1: GlobalData] => PointerConstraintsState);
-delegate_dispatch!(WinitState: [ZwpLockedPointerV1: GlobalData] => PointerConstraintsState);
+delegate_dispatch!(WinitState: [ZwpLockedPointerV1: LockedPointerData] => PointerConstraintsState);
 delegate_dispatch!(WinitState: [ZwpConfinedPointerV1: GlobalData] => PointerConstraintsState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointer_lock_changes() {
+        let data = LockedPointerData::new(WindowId::from_raw(0));
+        assert!(!data.is_locked());
+        assert!(data.update(false).is_none());
+
+        // The lock is reported once the compositor engages it.
+        assert_eq!(data.update(true), Some(WindowEvent::PointerLock { locked: true }));
+        assert!(data.is_locked());
+        assert!(data.update(true).is_none());
+
+        assert_eq!(data.update(false), Some(WindowEvent::PointerLock { locked: false }));
+        assert!(!data.is_locked());
+    }
+}
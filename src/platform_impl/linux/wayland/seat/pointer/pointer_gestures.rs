@@ -0,0 +1,123 @@
+//! Pointer gestures.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gesture_pinch_v1::{
+    self, ZwpPointerGesturePinchV1,
+};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1;
+
+use crate::event::{TouchPhase, WindowEvent};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// Wrapper around the pointer gestures.
+pub struct PointerGesturesState {
+    manager: ZwpPointerGesturesV1,
+}
+
+impl PointerGesturesState {
+    /// Create new pointer gestures manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=3, GlobalData)?;
+        Ok(Self { manager })
+    }
+}
+
+impl Deref for PointerGesturesState {
+    type Target = ZwpPointerGesturesV1;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+/// The state of the pinch gesture in progress.
+#[derive(Debug, Default)]
+pub struct PinchGestureData {
+    inner: Mutex<Option<(WindowId, f64)>>,
+}
+
+/// The pinch and rotation deltas of an update, from the scale relative to the start of the gesture
+/// and the clockwise rotation in degrees since the previous update.
+fn pinch_deltas(last_scale: f64, scale: f64, rotation: f64) -> (f64, f32) {
+    // Winit's rotation is counterclockwise, like on macOS.
+    (scale - last_scale, -rotation as f32)
+}
+
+impl Dispatch<ZwpPointerGesturesV1, GlobalData, WinitState> for PointerGesturesState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpPointerGesturesV1,
+        _: <ZwpPointerGesturesV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("no events defined for zwp_pointer_gestures_v1");
+    }
+}
+
+impl Dispatch<ZwpPointerGesturePinchV1, PinchGestureData, WinitState> for PointerGesturesState {
+    fn event(
+        state: &mut WinitState,
+        _: &ZwpPointerGesturePinchV1,
+        event: <ZwpPointerGesturePinchV1 as Proxy>::Event,
+        data: &PinchGestureData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let mut gesture = data.inner.lock().unwrap();
+        let (window_id, phase, pinch_delta, rotation_delta) = match event {
+            zwp_pointer_gesture_pinch_v1::Event::Begin { surface, .. } => {
+                let window_id = wayland::make_wid(&surface);
+                *gesture = Some((window_id, 1.0));
+                (window_id, TouchPhase::Started, 0.0, 0.0)
+            },
+            zwp_pointer_gesture_pinch_v1::Event::Update { scale, rotation, .. } => {
+                let Some((window_id, last_scale)) = gesture.as_mut() else { return };
+                let (pinch_delta, rotation_delta) = pinch_deltas(*last_scale, scale, rotation);
+                *last_scale = scale;
+                (*window_id, TouchPhase::Moved, pinch_delta, rotation_delta)
+            },
+            zwp_pointer_gesture_pinch_v1::Event::End { cancelled, .. } => {
+                let Some((window_id, _)) = gesture.take() else { return };
+                let phase = if cancelled == 1 { TouchPhase::Cancelled } else { TouchPhase::Ended };
+                (window_id, phase, 0.0, 0.0)
+            },
+            _ => return,
+        };
+
+        state.events_sink.push_window_event(
+            WindowEvent::PinchGesture { device_id: None, delta: pinch_delta, phase },
+            window_id,
+        );
+        state.events_sink.push_window_event(
+            WindowEvent::RotationGesture { device_id: None, delta: rotation_delta, phase },
+            window_id,
+        );
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpPointerGesturesV1: GlobalData] => PointerGesturesState);
+delegate_dispatch!(WinitState: [ZwpPointerGesturePinchV1: PinchGestureData] => PointerGesturesState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinch_update_deltas() {
+        // Spreading the fingers while turning them clockwise.
+        assert_eq!(pinch_deltas(1.0, 1.25, 10.0), (0.25, -10.0));
+        assert_eq!(pinch_deltas(1.25, 1.0, -5.0), (-0.25, 5.0));
+        assert_eq!(pinch_deltas(1.0, 1.0, 0.0), (0.0, 0.0));
+    }
+}
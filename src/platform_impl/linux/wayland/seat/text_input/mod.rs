@@ -81,6 +81,11 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
             TextInputEvent::Leave { surface } => {
                 text_input_data.surface = None;
 
+                // Drop the composition of the window we're leaving, so it doesn't get delivered
+                // to the next one.
+                text_input_data.pending_commit = None;
+                text_input_data.pending_preedit = None;
+
                 // Always issue a disable.
                 text_input.disable();
                 text_input.commit();
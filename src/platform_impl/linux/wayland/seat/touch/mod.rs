@@ -1,5 +1,7 @@
 //! Touch handling.
 
+use std::time::Duration;
+
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::protocol::wl_touch::WlTouch;
@@ -19,7 +21,7 @@ impl TouchHandler for WinitState {
         _: &QueueHandle<Self>,
         touch: &WlTouch,
         _: u32,
-        _: u32,
+        time: u32,
         surface: WlSurface,
         id: i32,
         position: (f64, f64),
@@ -66,6 +68,7 @@ impl TouchHandler for WinitState {
                 state: ElementState::Pressed,
                 position,
                 button: ButtonSource::Touch { finger_id, force: None },
+                timestamp: Some(Duration::from_millis(time as u64)),
             },
             window_id,
         );
@@ -77,7 +80,7 @@ impl TouchHandler for WinitState {
         _: &QueueHandle<Self>,
         touch: &WlTouch,
         _: u32,
-        _: u32,
+        time: u32,
         id: i32,
     ) {
         let seat_state = match self.seats.get_mut(&touch.seat().id()) {
@@ -119,6 +122,7 @@ impl TouchHandler for WinitState {
                 state: ElementState::Released,
                 position,
                 button: ButtonSource::Touch { finger_id, force: None },
+                timestamp: Some(Duration::from_millis(time as u64)),
             },
             window_id,
         );
@@ -138,7 +142,7 @@ impl TouchHandler for WinitState {
         _: &Connection,
         _: &QueueHandle<Self>,
         touch: &WlTouch,
-        _: u32,
+        time: u32,
         id: i32,
         position: (f64, f64),
     ) {
@@ -175,6 +179,7 @@ impl TouchHandler for WinitState {
                     finger_id: FingerId::from_raw(id as usize),
                     force: None,
                 },
+                timestamp: Some(Duration::from_millis(time as u64)),
             },
             window_id,
         );
@@ -2,6 +2,8 @@
 
 pub mod cursor;
 pub mod kwin_blur;
+pub mod placeholder;
 pub mod wp_fractional_scaling;
+pub mod wp_tearing_control;
 pub mod wp_viewporter;
 pub mod xdg_activation;
@@ -0,0 +1,143 @@
+use sctk::reexports::client::protocol::wl_shm::Format;
+use sctk::shm::slot::{Buffer, CreateBufferError, SlotPool};
+
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::icon::RgbaIcon;
+
+/// Bookkeeping for
+/// [`WindowAttributes::with_placeholder_image`][crate::window::WindowAttributes::with_placeholder_image].
+///
+///
+/// Platforms paint the image whenever the surface changes, until the application presents its
+/// first frame.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Placeholder<I> {
+    image: Option<I>,
+}
+
+impl<I> Placeholder<I> {
+    pub(crate) fn new(image: Option<I>) -> Self {
+        Self { image }
+    }
+
+    /// The image to paint, or `None` once the application presented a frame.
+    pub(crate) fn image(&self) -> Option<&I> {
+        self.image.as_ref()
+    }
+
+    /// Records that the application presented a frame, returning whether the placeholder was
+    /// still shown.
+    pub(crate) fn present(&mut self) -> bool {
+        self.image.take().is_some()
+    }
+}
+
+/// Compute where an image of `image` size is drawn to be centered on `surface` and fit it while
+/// keeping the aspect ratio.
+pub(crate) fn letterbox(
+    image: PhysicalSize<u32>,
+    surface: PhysicalSize<u32>,
+) -> (PhysicalPosition<u32>, PhysicalSize<u32>) {
+    if image.width == 0 || image.height == 0 {
+        return (
+            PhysicalPosition::new(surface.width / 2, surface.height / 2),
+            PhysicalSize::new(0, 0),
+        );
+    }
+
+    // Compare `surface.width / image.width` against `surface.height / image.height` without
+    // rounding.
+    let size = if surface.width as u64 * image.height as u64
+        <= surface.height as u64 * image.width as u64
+    {
+        let height = surface.width as u64 * image.height as u64 / image.width as u64;
+        PhysicalSize::new(surface.width, height as u32)
+    } else {
+        let width = surface.height as u64 * image.width as u64 / image.height as u64;
+        PhysicalSize::new(width as u32, surface.height)
+    };
+
+    let position =
+        PhysicalPosition::new((surface.width - size.width) / 2, (surface.height - size.height) / 2);
+    (position, size)
+}
+
+/// Paint the placeholder `image` letterboxed into a buffer of the given `size`.
+pub(crate) fn paint(
+    pool: &mut SlotPool,
+    image: &RgbaIcon,
+    size: PhysicalSize<u32>,
+) -> Result<Buffer, CreateBufferError> {
+    let (buffer, canvas) = pool.create_buffer(
+        size.width as i32,
+        size.height as i32,
+        4 * size.width as i32,
+        Format::Argb8888,
+    )?;
+
+    let (position, scaled) = letterbox(PhysicalSize::new(image.width, image.height), size);
+
+    for (y, row) in canvas.chunks_exact_mut(4 * size.width as usize).enumerate() {
+        for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+            let (x, y) = (x as u32, y as u32);
+            let inside = (position.x..position.x + scaled.width).contains(&x)
+                && (position.y..position.y + scaled.height).contains(&y);
+
+            let color = if inside {
+                // Sample the nearest source pixel.
+                let src_x = (x - position.x) as u64 * image.width as u64 / scaled.width as u64;
+                let src_y = (y - position.y) as u64 * image.height as u64 / scaled.height as u64;
+                let offset = 4 * (src_y as usize * image.width as usize + src_x as usize);
+                let rgba = &image.rgba[offset..offset + 4];
+
+                // Alpha in buffer is premultiplied.
+                let alpha = rgba[3] as f32 / 255.;
+                let r = (rgba[0] as f32 * alpha) as u32;
+                let g = (rgba[1] as f32 * alpha) as u32;
+                let b = (rgba[2] as f32 * alpha) as u32;
+                ((rgba[3] as u32) << 24) + (r << 16) + (g << 8) + b
+            } else {
+                // Opaque black for the letterbox.
+                0xff00_0000
+            };
+
+            let pixel: &mut [u8; 4] = pixel.try_into().unwrap();
+            *pixel = color.to_le_bytes();
+        }
+    }
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholder_cleared_on_first_present() {
+        let mut placeholder = Placeholder::new(Some("splash"));
+        assert_eq!(placeholder.image(), Some(&"splash"));
+        assert!(placeholder.present());
+        assert_eq!(placeholder.image(), None);
+        assert!(!placeholder.present());
+
+        let mut placeholder = Placeholder::<&str>::new(None);
+        assert_eq!(placeholder.image(), None);
+        assert!(!placeholder.present());
+    }
+
+    #[test]
+    fn placeholder_letterbox() {
+        let size = PhysicalSize::new;
+        let position = PhysicalPosition::new;
+
+        // Wider surface, bars on the sides.
+        assert_eq!(letterbox(size(100, 100), size(400, 200)), (position(100, 0), size(200, 200)));
+        // Taller surface, bars on the top and bottom.
+        assert_eq!(letterbox(size(200, 100), size(200, 400)), (position(0, 150), size(200, 100)));
+        // Images are scaled up as well.
+        assert_eq!(letterbox(size(16, 9), size(1920, 1080)), (position(0, 0), size(1920, 1080)));
+        assert_eq!(letterbox(size(0, 10), size(100, 100)), (position(50, 50), size(0, 0)));
+        assert_eq!(letterbox(size(10, 10), size(0, 0)), (position(0, 0), size(0, 0)));
+    }
+}
@@ -0,0 +1,88 @@
+//! Handling of the tearing control.
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::tearing_control::v1::client::wp_tearing_control_manager_v1::WpTearingControlManagerV1;
+use sctk::reexports::protocols::wp::tearing_control::v1::client::wp_tearing_control_v1::{
+    PresentationHint, WpTearingControlV1,
+};
+
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Tearing control manager.
+#[derive(Debug, Clone)]
+pub struct TearingControlManager {
+    manager: WpTearingControlManagerV1,
+}
+
+impl TearingControlManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    pub fn tearing_control(
+        &self,
+        surface: &WlSurface,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> WpTearingControlV1 {
+        self.manager.get_tearing_control(surface, queue_handle, ())
+    }
+}
+
+/// The presentation hint to request, tearing is allowed with asynchronous presentation.
+pub fn presentation_hint(tearing_allowed: bool) -> PresentationHint {
+    if tearing_allowed {
+        PresentationHint::Async
+    } else {
+        PresentationHint::Vsync
+    }
+}
+
+impl Dispatch<WpTearingControlManagerV1, GlobalData, WinitState> for TearingControlManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpTearingControlManagerV1,
+        _: <WpTearingControlManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("no events defined for wp_tearing_control_manager_v1");
+    }
+}
+
+impl Dispatch<WpTearingControlV1, (), WinitState> for TearingControlManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpTearingControlV1,
+        _: <WpTearingControlV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("no events defined for wp_tearing_control_v1");
+    }
+}
+
+delegate_dispatch!(WinitState: [WpTearingControlManagerV1: GlobalData] => TearingControlManager);
+delegate_dispatch!(WinitState: [WpTearingControlV1: ()] => TearingControlManager);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tearing_presentation_hint() {
+        assert_eq!(presentation_hint(true), PresentationHint::Async);
+        assert_eq!(presentation_hint(false), PresentationHint::Vsync);
+        // The values sent on the wire, as defined by the protocol.
+        assert_eq!(u32::from(presentation_hint(false)), 0);
+        assert_eq!(u32::from(presentation_hint(true)), 1);
+    }
+}
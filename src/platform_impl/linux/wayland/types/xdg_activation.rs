@@ -1,7 +1,8 @@
 //! Handling of xdg activation, which is used for user attention requests.
 
+use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
-use std::sync::Weak;
+use std::sync::{Arc, Mutex, Weak};
 
 use sctk::globals::GlobalData;
 use sctk::reexports::client::globals::{BindError, GlobalList};
@@ -18,6 +19,7 @@ use crate::window::{ActivationToken, WindowId};
 
 pub struct XdgActivationState {
     xdg_activation: XdgActivationV1,
+    consumed_tokens: Arc<ConsumedTokens>,
 }
 
 impl XdgActivationState {
@@ -26,12 +28,30 @@ impl XdgActivationState {
         queue_handle: &QueueHandle<WinitState>,
     ) -> Result<Self, BindError> {
         let xdg_activation = globals.bind(queue_handle, 1..=1, GlobalData)?;
-        Ok(Self { xdg_activation })
+        Ok(Self { xdg_activation, consumed_tokens: Default::default() })
     }
 
     pub fn global(&self) -> &XdgActivationV1 {
         &self.xdg_activation
     }
+
+    pub fn consumed_tokens(&self) -> &Arc<ConsumedTokens> {
+        &self.consumed_tokens
+    }
+}
+
+/// The tokens this client has already activated a surface with.
+///
+/// Tokens are one-shot, and the compositor silently ignores a token used a second time, so we
+/// track them to report that to the user instead.
+#[derive(Debug, Default)]
+pub struct ConsumedTokens(Mutex<HashSet<String>>);
+
+impl ConsumedTokens {
+    /// Mark the `token` as consumed, returning `false` if it already was.
+    pub fn consume(&self, token: &str) -> bool {
+        self.0.lock().unwrap().insert(token.to_owned())
+    }
 }
 
 impl Dispatch<XdgActivationV1, GlobalData, WinitState> for XdgActivationState {
@@ -99,3 +119,17 @@ pub enum XdgActivationTokenData {
 
 delegate_dispatch!(WinitState: [ XdgActivationV1: GlobalData] => XdgActivationState);
 delegate_dispatch!(WinitState: [ XdgActivationTokenV1: XdgActivationTokenData] => XdgActivationState);
+
+#[cfg(test)]
+mod tests {
+    use super::ConsumedTokens;
+
+    #[test]
+    fn tokens_are_consumed_once() {
+        let tokens = ConsumedTokens::default();
+        assert!(tokens.consume("token-1"));
+        assert!(tokens.consume("token-2"));
+        assert!(!tokens.consume("token-1"));
+        assert!(!tokens.consume("token-2"));
+    }
+}
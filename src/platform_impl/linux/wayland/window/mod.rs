@@ -2,6 +2,7 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData};
 use sctk::reexports::client::protocol::wl_display::WlDisplay;
@@ -15,16 +16,21 @@ use tracing::warn;
 use super::event_loop::sink::EventSink;
 use super::output::MonitorHandle;
 use super::state::WinitState;
-use super::types::xdg_activation::XdgActivationTokenData;
+use super::types::xdg_activation::{ConsumedTokens, XdgActivationTokenData};
 use super::ActiveEventLoop;
+use crate::cursor::CurrentCursor;
 use crate::dpi::{LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
 use crate::event::{Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
 use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::platform::led_state::{LedState, Leds};
+use crate::platform::wayland::CsdSides;
 use crate::platform_impl::{Fullscreen, MonitorHandle as PlatformMonitorHandle};
 use crate::window::{
-    Cursor, CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose, ResizeDirection, Theme,
+    ActivationToken, CommonWindowState, Cursor, CursorGrabMode, Fullscreen as CoreFullscreen,
+    HitTestRegion, ImePurpose, InputStateSnapshot, ResizeDirection, SurfaceTransform, Theme,
     UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
     WindowLevel,
 };
@@ -54,6 +60,9 @@ pub struct Window {
     /// Xdg activation to request user attention.
     xdg_activation: Option<XdgActivationV1>,
 
+    /// The activation tokens already used by this client.
+    consumed_tokens: Arc<ConsumedTokens>,
+
     /// The state of the requested attention from the `xdg_activation`.
     attention_requested: Arc<AtomicBool>,
 
@@ -71,6 +80,12 @@ pub struct Window {
 
     /// The event sink to deliver synthetic events.
     window_events_sink: Arc<Mutex<EventSink>>,
+
+    /// The cursor last set by the user.
+    current_cursor: CurrentCursor,
+
+    /// The state Winit keeps for the window.
+    pub(crate) common_state: Arc<CommonWindowState>,
 }
 
 impl Window {
@@ -78,6 +93,7 @@ impl Window {
         event_loop_window_target: &ActiveEventLoop,
         attributes: WindowAttributes,
     ) -> Result<Self, RequestError> {
+        let common_state = Arc::new(CommonWindowState::new(&attributes));
         let queue_handle = event_loop_window_target.queue_handle.clone();
         let mut state = event_loop_window_target.state.borrow_mut();
 
@@ -87,6 +103,11 @@ impl Window {
         let compositor = state.compositor_state.clone();
         let xdg_activation =
             state.xdg_activation.as_ref().map(|activation_state| activation_state.global().clone());
+        let consumed_tokens = state
+            .xdg_activation
+            .as_ref()
+            .map(|activation_state| activation_state.consumed_tokens().clone())
+            .unwrap_or_default();
         let display = event_loop_window_target.handle.connection.display();
 
         let size: Size = attributes.surface_size.unwrap_or(LogicalSize::new(800., 600.).into());
@@ -116,6 +137,8 @@ impl Window {
 
         window_state.set_blur(attributes.blur);
 
+        window_state.set_placeholder_image(attributes.placeholder_image.map(|icon| icon.inner));
+
         // Set the decorations hint.
         window_state.set_decorate(attributes.decorations);
 
@@ -156,6 +179,8 @@ impl Window {
             _ => (),
         };
 
+        let current_cursor = CurrentCursor::default();
+        current_cursor.set(&attributes.cursor);
         match attributes.cursor {
             Cursor::Icon(icon) => window_state.set_cursor(icon),
             Cursor::Custom(cursor) => window_state.set_custom_cursor(cursor),
@@ -165,6 +190,7 @@ impl Window {
         if let (Some(xdg_activation), Some(token)) =
             (xdg_activation.as_ref(), attributes.platform_specific.activation_token)
         {
+            consumed_tokens.consume(&token.token);
             xdg_activation.activate(token.token, &surface);
         }
 
@@ -210,10 +236,13 @@ impl Window {
             window_state,
             queue_handle,
             xdg_activation,
+            consumed_tokens,
             attention_requested: Arc::new(AtomicBool::new(false)),
             event_loop_awakener,
             window_requests,
             window_events_sink,
+            current_cursor,
+            common_state,
         })
     }
 }
@@ -235,10 +264,44 @@ impl Window {
         Ok(serial)
     }
 
+    pub fn activate_with_token(&self, token: ActivationToken) -> Result<(), RequestError> {
+        let xdg_activation = match self.xdg_activation.as_ref() {
+            Some(xdg_activation) => xdg_activation,
+            None => return Err(NotSupportedError::new("xdg_activation_v1 is not available").into()),
+        };
+
+        if !self.consumed_tokens.consume(&token.token) {
+            return Err(RequestError::Ignored);
+        }
+
+        xdg_activation.activate(token.token, self.surface());
+
+        Ok(())
+    }
+
     #[inline]
     pub fn surface(&self) -> &WlSurface {
         self.window.wl_surface()
     }
+
+    #[inline]
+    pub fn set_csd_sides(&self, sides: CsdSides) {
+        self.window_state.lock().unwrap().set_csd_sides(sides)
+    }
+
+    #[inline]
+    pub fn set_cursor_theme(&self, name: Option<String>, size: Option<u32>) {
+        self.window_state.lock().unwrap().set_cursor_theme(name, size)
+    }
+
+    pub fn led_state(&self) -> LedState {
+        let window_state = self.window_state.lock().unwrap();
+        if !window_state.has_focus() {
+            return LedState::Unknown;
+        }
+
+        LedState::Known(Leds::from_lock_modifiers(window_state.modifiers()))
+    }
 }
 
 impl Drop for Window {
@@ -296,7 +359,9 @@ impl CoreWindow for Window {
     }
 
     fn pre_present_notify(&self) {
-        self.window_state.lock().unwrap().request_frame_callback();
+        let mut window_state = self.window_state.lock().unwrap();
+        window_state.clear_placeholder();
+        window_state.request_frame_callback();
     }
 
     fn reset_dead_keys(&self) {
@@ -339,6 +404,10 @@ impl CoreWindow for Window {
         PhysicalInsets::new(0, 0, 0, 0)
     }
 
+    fn surface_transform(&self) -> SurfaceTransform {
+        self.window_state.lock().unwrap().surface_transform()
+    }
+
     fn set_min_surface_size(&self, min_size: Option<Size>) {
         let scale_factor = self.scale_factor();
         let min_size = min_size.map(|size| size.to_logical(scale_factor));
@@ -436,6 +505,17 @@ impl CoreWindow for Window {
             .unwrap_or_default()
     }
 
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new(
+            "the virtual desktop protocols are restricted to privileged clients",
+        )
+        .into())
+    }
+
     fn set_fullscreen(&self, fullscreen: Option<CoreFullscreen>) {
         match fullscreen {
             Some(CoreFullscreen::Exclusive(..)) => {
@@ -483,6 +563,10 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_blur(blur);
     }
 
+    fn set_tearing_allowed(&self, allowed: bool) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_tearing_allowed(allowed)
+    }
+
     #[inline]
     fn set_decorations(&self, decorate: bool) {
         self.window_state.lock().unwrap().set_decorate(decorate)
@@ -524,6 +608,34 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_ime_purpose(purpose);
     }
 
+    fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.window_state.lock().unwrap().set_key_repeat_enabled(enabled);
+    }
+
+    fn keyboard_layout_name(&self) -> Option<String> {
+        self.window_state.lock().unwrap().keyboard_layout()
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        self.window_state.lock().unwrap().keyboard_layout_direction()
+    }
+
+    fn key_for_physical(&self, key: PhysicalKey) -> Option<Key> {
+        self.window_state.lock().unwrap().key_for_physical(key)
+    }
+
+    fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        self.window_state.lock().unwrap().compose_dead_key(dead, base)
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        self.window_state.lock().unwrap().modifiers()
+    }
+
+    fn set_hit_test_regions(&self, regions: &[HitTestRegion]) {
+        self.window_state.lock().unwrap().set_hit_test_regions(regions);
+    }
+
     fn focus_window(&self) {}
 
     fn has_focus(&self) -> bool {
@@ -567,6 +679,7 @@ impl CoreWindow for Window {
     fn set_content_protected(&self, _protected: bool) {}
 
     fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
         let window_state = &mut self.window_state.lock().unwrap();
 
         match cursor {
@@ -575,6 +688,10 @@ impl CoreWindow for Window {
         }
     }
 
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
+
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError> {
         let scale_factor = self.scale_factor();
         let position = position.to_logical(scale_factor);
@@ -586,14 +703,43 @@ impl CoreWindow for Window {
             .map(|_| self.request_redraw())
     }
 
+    fn animate_cursor_to(
+        &self,
+        target: PhysicalPosition<i32>,
+        _duration: Duration,
+    ) -> Result<(), RequestError> {
+        self.set_cursor_position(target.into())
+    }
+
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
-        self.window_state.lock().unwrap().set_cursor_grab(mode)
+        let mut window_state = self.window_state.lock().unwrap();
+        let was_locked = window_state.pointer_locked();
+        window_state.set_cursor_grab(mode)?;
+
+        // Releasing the lock destroys it without the compositor reporting it.
+        if was_locked && !window_state.pointer_locked() {
+            let event = WindowEvent::PointerLock { locked: false };
+            self.window_events_sink.lock().unwrap().push_window_event(event, self.window_id);
+            self.event_loop_awakener.ping();
+        }
+
+        Ok(())
     }
 
     fn set_cursor_visible(&self, visible: bool) {
         self.window_state.lock().unwrap().set_cursor_visible(visible);
     }
 
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        let window_state = self.window_state.lock().unwrap();
+        InputStateSnapshot {
+            cursor_grab: window_state.cursor_grab_mode(),
+            cursor_visible: window_state.cursor_visible,
+            cursor: self.current_cursor.get(),
+            ime_allowed: window_state.ime_allowed(),
+        }
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         self.window_state.lock().unwrap().drag_window()
     }
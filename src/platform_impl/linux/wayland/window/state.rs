@@ -15,6 +15,7 @@ use sctk::reexports::csd_frame::{
     DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowState as XdgWindowState,
 };
 use sctk::reexports::protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use sctk::reexports::protocols::wp::tearing_control::v1::client::wp_tearing_control_v1::WpTearingControlV1;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
 use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::ResizeEdge as XdgResizeEdge;
@@ -22,25 +23,37 @@ use sctk::seat::pointer::{PointerDataExt, ThemedPointer};
 use sctk::shell::xdg::window::{DecorationMode, Window, WindowConfigure};
 use sctk::shell::xdg::XdgSurface;
 use sctk::shell::WaylandSurface;
-use sctk::shm::slot::SlotPool;
+use sctk::shm::slot::{Buffer, SlotPool};
 use sctk::shm::Shm;
 use sctk::subcompositor::SubcompositorState;
 use tracing::{info, warn};
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 
 use crate::cursor::CustomCursor as RootCustomCursor;
-use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Size};
 use crate::error::{NotSupportedError, RequestError};
+use crate::icon::RgbaIcon;
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
+use crate::platform::wayland::CsdSides;
+use crate::platform_impl::common::xcursor::CursorThemeOverride;
+use crate::platform_impl::common::xkb::{layout_direction, LayoutKeys};
 use crate::platform_impl::wayland::event_loop::OwnedDisplayHandle;
-use crate::platform_impl::wayland::logical_to_physical_rounded;
 use crate::platform_impl::wayland::seat::{
     PointerConstraintsState, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
 };
 use crate::platform_impl::wayland::state::{WindowCompositorUpdate, WinitState};
 use crate::platform_impl::wayland::types::cursor::{CustomCursor, SelectedCursor};
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::placeholder::{self, Placeholder};
+use crate::platform_impl::wayland::types::wp_tearing_control::{
+    presentation_hint, TearingControlManager,
+};
+use crate::platform_impl::wayland::{logical_to_physical_rounded, surface_transform};
 use crate::platform_impl::PlatformCustomCursor;
-use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, WindowId};
+use crate::window::{
+    self, CursorGrabMode, CursorIcon, HitTestRegion, HitTestRole, ImePurpose, ResizeDirection,
+    SurfaceTransform, Theme, TitlebarButton, WindowId,
+};
 
 #[cfg(feature = "sctk-adwaita")]
 pub type WinitFrame = sctk_adwaita::AdwaitaFrame<WinitState>;
@@ -69,6 +82,12 @@ pub struct WindowState {
 
     selected_cursor: SelectedCursor,
 
+    /// The cursor theme used instead of the system one.
+    cursor_theme: Option<CursorThemeOverride>,
+
+    /// The cursors loaded from `cursor_theme`, with the scale they were loaded for.
+    themed_cursors: Vec<(CursorIcon, i32, CustomCursor)>,
+
     /// Whether the cursor is visible.
     pub cursor_visible: bool,
 
@@ -110,6 +129,21 @@ pub struct WindowState {
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
+    /// Whether the key repeat events are delivered to that window.
+    key_repeat_enabled: bool,
+
+    /// The keyboard layout active when the window last had keyboard focus.
+    keyboard_layout: Option<String>,
+
+    /// The keys of that keyboard layout.
+    layout_keys: LayoutKeys,
+
+    /// The modifiers last sent to the window, empty when it doesn't have keyboard focus.
+    modifiers: ModifiersState,
+
+    /// The regions of the surface acting as client-side decorations.
+    hit_test_regions: Vec<HitTestRegion>,
+
     /// The text inputs observed on the window.
     text_inputs: Vec<ZwpTextInputV3>,
 
@@ -122,6 +156,9 @@ pub struct WindowState {
     /// Whether we should decorate the frame.
     decorate: bool,
 
+    /// The sides of the frame handled by winit.
+    csd_sides: CsdSides,
+
     /// Min size.
     min_surface_size: LogicalSize<u32>,
     max_surface_size: Option<LogicalSize<u32>>,
@@ -138,10 +175,17 @@ pub struct WindowState {
     /// The state of the frame callback.
     frame_callback_state: FrameCallbackState,
 
+    /// The image shown until the first frame is presented.
+    placeholder: Placeholder<RgbaIcon>,
+    /// The pool and the buffer currently holding the placeholder.
+    placeholder_buffer: Option<(SlotPool, Buffer)>,
+
     viewport: Option<WpViewport>,
     fractional_scale: Option<WpFractionalScaleV1>,
     blur: Option<OrgKdeKwinBlur>,
     blur_manager: Option<KWinBlurManager>,
+    tearing_control: Option<WpTearingControlV1>,
+    tearing_control_manager: Option<TearingControlManager>,
 
     /// Whether the client side decorations have pending move operations.
     ///
@@ -183,13 +227,18 @@ impl WindowState {
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            tearing_control: None,
+            tearing_control_manager: winit_state.tearing_control_manager.clone(),
             compositor,
             handle,
             csd_fails: false,
             cursor_grab_mode: GrabState::new(),
             selected_cursor: Default::default(),
+            cursor_theme: None,
+            themed_cursors: Vec::new(),
             cursor_visible: true,
             decorate: true,
+            csd_sides: CsdSides::ALL,
             fractional_scale,
             frame: None,
             frame_callback_state: FrameCallbackState::None,
@@ -197,6 +246,11 @@ impl WindowState {
             has_pending_move: None,
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            key_repeat_enabled: true,
+            keyboard_layout: None,
+            layout_keys: Default::default(),
+            modifiers: ModifiersState::empty(),
+            hit_test_regions: Vec::new(),
             last_configure: None,
             max_surface_size: None,
             min_surface_size: MIN_WINDOW_SIZE,
@@ -214,6 +268,8 @@ impl WindowState {
             theme,
             title: String::default(),
             transparent: false,
+            placeholder: Placeholder::new(None),
+            placeholder_buffer: None,
             viewport,
             window,
         }
@@ -290,7 +346,7 @@ impl WindowState {
                     frame.set_title(&self.title);
                     frame.set_scaling_factor(self.scale_factor);
                     // Hide the frame if we were asked to not decorate.
-                    frame.set_hidden(!self.decorate);
+                    frame.set_hidden(!self.decorate || self.csd_sides == CsdSides::NONE);
                     self.frame = Some(frame);
                 },
                 Err(err) => {
@@ -356,12 +412,68 @@ impl WindowState {
 
         if state_change_requires_resize || new_size != self.surface_size() {
             self.resize(new_size);
+            self.paint_placeholder(shm);
             true
         } else {
             false
         }
     }
 
+    /// Set the image shown until the first frame is presented.
+    pub(crate) fn set_placeholder_image(&mut self, image: Option<RgbaIcon>) {
+        self.placeholder = Placeholder::new(image);
+    }
+
+    /// Attach the placeholder to the surface, if the first frame wasn't presented yet.
+    fn paint_placeholder(&mut self, shm: &Shm) {
+        let Some(image) = self.placeholder.image() else {
+            return;
+        };
+
+        let size = logical_to_physical_rounded(self.surface_size(), self.scale_factor);
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        let mut pool = match self.placeholder_buffer.take() {
+            Some((pool, _)) => pool,
+            None => match SlotPool::new(4 * size.width as usize * size.height as usize, shm) {
+                Ok(pool) => pool,
+                Err(err) => {
+                    warn!("Failed to create placeholder pool: {err}");
+                    return;
+                },
+            },
+        };
+
+        let buffer = match placeholder::paint(&mut pool, image, size) {
+            Ok(buffer) => buffer,
+            Err(err) => {
+                warn!("Failed to paint placeholder: {err}");
+                return;
+            },
+        };
+
+        let surface = self.window.wl_surface();
+        if let Err(err) = buffer.attach_to(surface) {
+            warn!("Failed to attach placeholder: {err}");
+            return;
+        }
+        surface.damage_buffer(0, 0, size.width as i32, size.height as i32);
+        surface.commit();
+
+        self.placeholder_buffer = Some((pool, buffer));
+    }
+
+    /// Stop painting the placeholder, since the application is about to present its first frame.
+    ///
+    /// The last painted placeholder stays attached until the application commits a buffer.
+    pub fn clear_placeholder(&mut self) {
+        if self.placeholder.present() {
+            self.placeholder_buffer = None;
+        }
+    }
+
     /// Compute the bounds for the surface size of the surface.
     fn surface_size_bounds(
         &self,
@@ -427,7 +539,12 @@ impl WindowState {
         window_id: WindowId,
         updates: &mut Vec<WindowCompositorUpdate>,
     ) -> Option<bool> {
-        match self.frame.as_mut()?.on_click(timestamp, click, pressed)? {
+        let action = self.frame.as_mut()?.on_click(timestamp, click, pressed)?;
+        if !frame_action_allowed(self.csd_sides, &action) {
+            return None;
+        }
+
+        match action {
             FrameAction::Minimize => self.window.set_minimized(),
             FrameAction::Maximize => self.window.set_maximized(),
             FrameAction::UnMaximize => self.window.unset_maximized(),
@@ -455,6 +572,46 @@ impl WindowState {
         Some(false)
     }
 
+    /// Set the regions of the surface acting as client-side decorations.
+    #[inline]
+    pub fn set_hit_test_regions(&mut self, regions: &[HitTestRegion]) {
+        self.hit_test_regions = regions.to_vec();
+    }
+
+    /// Start the action of the hit-test region pressed at `position`.
+    pub fn hit_test_press(
+        &mut self,
+        position: PhysicalPosition<f64>,
+        seat: &WlSeat,
+        serial: u32,
+        window_id: WindowId,
+        updates: &mut Vec<WindowCompositorUpdate>,
+    ) {
+        let role = match window::hit_test(&self.hit_test_regions, position) {
+            Some(role) => role,
+            None => return,
+        };
+
+        match role {
+            HitTestRole::Drag => self.window.move_(seat, serial),
+            HitTestRole::ResizeEdge(direction) => {
+                self.window.resize(seat, serial, direction.into())
+            },
+            HitTestRole::Button(TitlebarButton::Minimize) => self.window.set_minimized(),
+            HitTestRole::Button(TitlebarButton::Maximize) => {
+                if self.last_configure.as_ref().is_some_and(|configure| configure.is_maximized()) {
+                    self.window.unset_maximized();
+                } else {
+                    self.window.set_maximized();
+                }
+            },
+            HitTestRole::Button(TitlebarButton::Close) => {
+                WinitState::queue_close(updates, window_id)
+            },
+            HitTestRole::Client => (),
+        }
+    }
+
     pub fn frame_point_left(&mut self) {
         if let Some(frame) = self.frame.as_mut() {
             frame.click_point_left();
@@ -481,7 +638,7 @@ impl WindowState {
                 self.window.move_(seat, serial);
                 None
             } else {
-                cursor
+                cursor.map(|cursor| frame_cursor(self.csd_sides, cursor))
             }
         } else {
             None
@@ -532,6 +689,12 @@ impl WindowState {
         self.ime_allowed
     }
 
+    /// Whether the key repeat is enabled.
+    #[inline]
+    pub fn key_repeat_enabled(&self) -> bool {
+        self.key_repeat_enabled
+    }
+
     /// Get the size of the window.
     #[inline]
     pub fn surface_size(&self) -> LogicalSize<u32> {
@@ -685,6 +848,13 @@ impl WindowState {
         self.scale_factor
     }
 
+    /// Get the transform the compositor prefers the content to be rendered with.
+    #[inline]
+    pub fn surface_transform(&self) -> SurfaceTransform {
+        let data = self.window.wl_surface().data::<SurfaceData>().unwrap();
+        surface_transform(data.transform())
+    }
+
     /// Set the cursor icon.
     pub fn set_cursor(&mut self, cursor_icon: CursorIcon) {
         self.selected_cursor = SelectedCursor::Named(cursor_icon);
@@ -693,6 +863,16 @@ impl WindowState {
             return;
         }
 
+        if let Some(theme) = self.cursor_theme.clone() {
+            if self.apply_themed_cursor(cursor_icon, &theme) {
+                return;
+            }
+            warn!(
+                "Cursor {cursor_icon:?} not found in theme {:?}, using the system theme",
+                theme.name
+            );
+        }
+
         self.apply_on_pointer(|pointer, _| {
             if pointer.set_cursor(&self.handle.connection, cursor_icon).is_err() {
                 warn!("Failed to set cursor to {:?}", cursor_icon);
@@ -700,6 +880,53 @@ impl WindowState {
         })
     }
 
+    /// Set the cursor theme used instead of the system one.
+    pub fn set_cursor_theme(&mut self, name: Option<String>, size: Option<u32>) {
+        let theme = CursorThemeOverride::new(name, size);
+        if self.cursor_theme == theme {
+            return;
+        }
+
+        self.cursor_theme = theme;
+        self.themed_cursors.clear();
+        self.reload_cursor_style();
+    }
+
+    /// Set the cursor icon from `theme`, returning whether the theme has it.
+    fn apply_themed_cursor(
+        &mut self,
+        cursor_icon: CursorIcon,
+        theme: &CursorThemeOverride,
+    ) -> bool {
+        let pointers: Vec<_> = self.pointers.iter().filter_map(Weak::upgrade).collect();
+        for pointer in pointers {
+            // Load the cursor for the scale of the output the pointer is on.
+            let scale =
+                pointer.surface().data::<SurfaceData>().unwrap().surface_data().scale_factor();
+            let cached = self
+                .themed_cursors
+                .iter()
+                .position(|(icon, cached_scale, _)| *icon == cursor_icon && *cached_scale == scale);
+            let index = match cached {
+                Some(index) => index,
+                None => {
+                    let Some(image) = theme.load(cursor_icon, scale as u32) else {
+                        return false;
+                    };
+                    let cursor = {
+                        let mut pool = self.custom_cursor_pool.lock().unwrap();
+                        CustomCursor::new(&mut pool, &image)
+                    };
+                    self.themed_cursors.push((cursor_icon, scale, cursor));
+                    self.themed_cursors.len() - 1
+                },
+            };
+            attach_custom_cursor(&pointer, &self.themed_cursors[index].2);
+        }
+
+        true
+    }
+
     /// Set the custom cursor icon.
     pub(crate) fn set_custom_cursor(&mut self, cursor: RootCustomCursor) {
         let cursor = match cursor {
@@ -724,33 +951,7 @@ impl WindowState {
     }
 
     fn apply_custom_cursor(&self, cursor: &CustomCursor) {
-        self.apply_on_pointer(|pointer, _| {
-            let surface = pointer.surface();
-
-            let scale = surface.data::<SurfaceData>().unwrap().surface_data().scale_factor();
-
-            surface.set_buffer_scale(scale);
-            surface.attach(Some(cursor.buffer.wl_buffer()), 0, 0);
-            if surface.version() >= 4 {
-                surface.damage_buffer(0, 0, cursor.w, cursor.h);
-            } else {
-                surface.damage(0, 0, cursor.w / scale, cursor.h / scale);
-            }
-            surface.commit();
-
-            let serial = pointer
-                .pointer()
-                .data::<WinitPointerData>()
-                .and_then(|data| data.pointer_data().latest_enter_serial())
-                .unwrap();
-
-            pointer.pointer().set_cursor(
-                serial,
-                Some(surface),
-                cursor.hotspot_x / scale,
-                cursor.hotspot_y / scale,
-            );
-        });
+        self.apply_on_pointer(|pointer, _| attach_custom_cursor(pointer, cursor));
     }
 
     /// Set maximum inner window size.
@@ -799,6 +1000,20 @@ impl WindowState {
         self.theme
     }
 
+    /// The cursor grabbing mode requested by the user.
+    #[inline]
+    pub fn cursor_grab_mode(&self) -> CursorGrabMode {
+        self.cursor_grab_mode.user_grab_mode
+    }
+
+    /// Whether the compositor has engaged a pointer lock on the top-level.
+    pub fn pointer_locked(&self) -> bool {
+        self.pointers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .any(|pointer| pointer.pointer().winit_data().is_pointer_locked())
+    }
+
     /// Set the cursor grabbing state on the top-level.
     pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) -> Result<(), RequestError> {
         if self.cursor_grab_mode.user_grab_mode == mode {
@@ -838,7 +1053,9 @@ impl WindowState {
                 data.unconfine_pointer();
             }),
             CursorGrabMode::Locked => {
-                self.apply_on_pointer(|_, data| data.unlock_pointer());
+                self.apply_on_pointer(|_, data| {
+                    data.unlock_pointer();
+                });
             },
         }
 
@@ -927,7 +1144,23 @@ impl WindowState {
         }
 
         if let Some(frame) = self.frame.as_mut() {
-            frame.set_hidden(!decorate);
+            frame.set_hidden(!decorate || self.csd_sides == CsdSides::NONE);
+            // Force the resize.
+            self.resize(self.size);
+        }
+    }
+
+    /// Set the sides of the client side decorations handled by winit.
+    #[inline]
+    pub fn set_csd_sides(&mut self, sides: CsdSides) {
+        if sides == self.csd_sides {
+            return;
+        }
+
+        self.csd_sides = sides;
+
+        if let Some(frame) = self.frame.as_mut() {
+            frame.set_hidden(!self.decorate || sides == CsdSides::NONE);
             // Force the resize.
             self.resize(self.size);
         }
@@ -987,6 +1220,65 @@ impl WindowState {
         }
     }
 
+    /// Set whether the key repeat is enabled.
+    #[inline]
+    pub fn set_key_repeat_enabled(&mut self, enabled: bool) {
+        self.key_repeat_enabled = enabled;
+    }
+
+    /// The name of the keyboard layout.
+    #[inline]
+    pub fn keyboard_layout(&self) -> Option<String> {
+        self.keyboard_layout.clone()
+    }
+
+    /// The direction of the text typed with the keyboard layout.
+    #[inline]
+    pub fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        self.keyboard_layout.as_deref().and_then(layout_direction)
+    }
+
+    /// Set the name of the keyboard layout, returning whether it changed.
+    #[inline]
+    pub fn set_keyboard_layout(&mut self, layout: Option<String>) -> bool {
+        if self.keyboard_layout == layout {
+            return false;
+        }
+
+        self.keyboard_layout = layout;
+        true
+    }
+
+    /// The key produced by the physical key without modifiers in the keyboard layout.
+    #[inline]
+    pub fn key_for_physical(&self, key: PhysicalKey) -> Option<Key> {
+        self.layout_keys.get(key)
+    }
+
+    /// The character composed by the dead key and the base character in the keyboard layout.
+    #[inline]
+    pub fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        self.layout_keys.compose_dead_key(dead, base)
+    }
+
+    /// Set the keys of the keyboard layout.
+    #[inline]
+    pub fn set_layout_keys(&mut self, layout_keys: LayoutKeys) {
+        self.layout_keys = layout_keys;
+    }
+
+    /// The modifiers last sent to the window.
+    #[inline]
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Set the modifiers sent to the window.
+    #[inline]
+    pub fn set_modifiers(&mut self, modifiers: ModifiersState) {
+        self.modifiers = modifiers;
+    }
+
     /// Get the IME purpose.
     pub fn ime_purpose(&self) -> ImePurpose {
         self.ime_purpose
@@ -1024,6 +1316,24 @@ impl WindowState {
         }
     }
 
+    /// Allow or disallow tearing with the presentation hint of the tearing control protocol.
+    pub fn set_tearing_allowed(&mut self, allowed: bool) -> Result<(), RequestError> {
+        if self.tearing_control.is_none() {
+            let Some(manager) = self.tearing_control_manager.as_ref() else {
+                return Err(NotSupportedError::new(
+                    "wp_tearing_control_manager_v1 is not available",
+                )
+                .into());
+            };
+            self.tearing_control =
+                Some(manager.tearing_control(self.window.wl_surface(), &self.queue_handle));
+        }
+
+        // The hint is double-buffered, and applied with the next commit of the surface.
+        self.tearing_control.as_ref().unwrap().set_presentation_hint(presentation_hint(allowed));
+        Ok(())
+    }
+
     /// Set the window title to a new value.
     ///
     /// This will automatically truncate the title to something meaningful.
@@ -1083,6 +1393,10 @@ impl Drop for WindowState {
             blur.release();
         }
 
+        if let Some(tearing_control) = self.tearing_control.take() {
+            tearing_control.destroy();
+        }
+
         if let Some(fs) = self.fractional_scale.take() {
             fs.destroy();
         }
@@ -1148,3 +1462,123 @@ fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
         None => sctk_adwaita::FrameConfig::auto(),
     }
 }
+
+/// Whether the frame `action` originates from a side of the decorations handled by winit.
+fn frame_action_allowed(sides: CsdSides, action: &FrameAction) -> bool {
+    match action {
+        FrameAction::Resize(edge) => resize_edge_allowed(sides, *edge),
+        // Everything else comes from the titlebar: dragging it, double-clicking it to maximize and
+        // its buttons.
+        _ => sides.top,
+    }
+}
+
+/// Whether the resize `edge` belongs to the sides of the decorations handled by winit.
+fn resize_edge_allowed(sides: CsdSides, edge: ResizeEdge) -> bool {
+    match edge {
+        ResizeEdge::Top => sides.top,
+        ResizeEdge::Bottom => sides.bottom,
+        ResizeEdge::Left => sides.left,
+        ResizeEdge::Right => sides.right,
+        ResizeEdge::TopLeft => sides.top && sides.left,
+        ResizeEdge::TopRight => sides.top && sides.right,
+        ResizeEdge::BottomLeft => sides.bottom && sides.left,
+        ResizeEdge::BottomRight => sides.bottom && sides.right,
+        _ => true,
+    }
+}
+
+/// The cursor to show for the `cursor` the frame requested, without the resize cursors of the
+/// sides of the decorations that winit doesn't handle.
+fn frame_cursor(sides: CsdSides, cursor: CursorIcon) -> CursorIcon {
+    let edge = match cursor {
+        CursorIcon::NResize => ResizeEdge::Top,
+        CursorIcon::SResize => ResizeEdge::Bottom,
+        CursorIcon::WResize => ResizeEdge::Left,
+        CursorIcon::EResize => ResizeEdge::Right,
+        CursorIcon::NwResize => ResizeEdge::TopLeft,
+        CursorIcon::NeResize => ResizeEdge::TopRight,
+        CursorIcon::SwResize => ResizeEdge::BottomLeft,
+        CursorIcon::SeResize => ResizeEdge::BottomRight,
+        _ => return cursor,
+    };
+    if resize_edge_allowed(sides, edge) {
+        cursor
+    } else {
+        CursorIcon::Default
+    }
+}
+
+/// Show `cursor` on the cursor surface of `pointer`.
+fn attach_custom_cursor(pointer: &ThemedPointer<WinitPointerData>, cursor: &CustomCursor) {
+    let surface = pointer.surface();
+
+    let scale = surface.data::<SurfaceData>().unwrap().surface_data().scale_factor();
+
+    surface.set_buffer_scale(scale);
+    surface.attach(Some(cursor.buffer.wl_buffer()), 0, 0);
+    if surface.version() >= 4 {
+        surface.damage_buffer(0, 0, cursor.w, cursor.h);
+    } else {
+        surface.damage(0, 0, cursor.w / scale, cursor.h / scale);
+    }
+    surface.commit();
+
+    let serial = pointer
+        .pointer()
+        .data::<WinitPointerData>()
+        .and_then(|data| data.pointer_data().latest_enter_serial())
+        .unwrap();
+
+    pointer.pointer().set_cursor(
+        serial,
+        Some(surface),
+        cursor.hotspot_x / scale,
+        cursor.hotspot_y / scale,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_action_gating() {
+        let all = CsdSides::ALL;
+        assert!(frame_action_allowed(all, &FrameAction::Move));
+        assert!(frame_action_allowed(all, &FrameAction::Resize(ResizeEdge::TopLeft)));
+
+        // The application draws its own titlebar, but keeps the resize borders.
+        let borders = CsdSides { top: false, ..CsdSides::ALL };
+        assert!(!frame_action_allowed(borders, &FrameAction::Move));
+        assert!(!frame_action_allowed(borders, &FrameAction::Close));
+        assert!(!frame_action_allowed(borders, &FrameAction::ShowMenu(0, 0)));
+        assert!(!frame_action_allowed(borders, &FrameAction::Resize(ResizeEdge::Top)));
+        assert!(!frame_action_allowed(borders, &FrameAction::Resize(ResizeEdge::TopRight)));
+        assert!(frame_action_allowed(borders, &FrameAction::Resize(ResizeEdge::Left)));
+        assert!(frame_action_allowed(borders, &FrameAction::Resize(ResizeEdge::BottomRight)));
+
+        // Double-clicking the titlebar.
+        assert!(!frame_action_allowed(borders, &FrameAction::Maximize));
+        assert!(!frame_action_allowed(borders, &FrameAction::UnMaximize));
+
+        let none = CsdSides::NONE;
+        assert!(!frame_action_allowed(none, &FrameAction::Maximize));
+        assert!(!frame_action_allowed(none, &FrameAction::Resize(ResizeEdge::Bottom)));
+    }
+
+    #[test]
+    fn frame_cursor_gating() {
+        let all = CsdSides::ALL;
+        assert_eq!(frame_cursor(all, CursorIcon::NResize), CursorIcon::NResize);
+        assert_eq!(frame_cursor(all, CursorIcon::SeResize), CursorIcon::SeResize);
+
+        // The borders of the titlebar drawn by the application don't show resize cursors.
+        let borders = CsdSides { top: false, ..CsdSides::ALL };
+        assert_eq!(frame_cursor(borders, CursorIcon::NResize), CursorIcon::Default);
+        assert_eq!(frame_cursor(borders, CursorIcon::NwResize), CursorIcon::Default);
+        assert_eq!(frame_cursor(borders, CursorIcon::WResize), CursorIcon::WResize);
+        assert_eq!(frame_cursor(borders, CursorIcon::SwResize), CursorIcon::SwResize);
+        assert_eq!(frame_cursor(borders, CursorIcon::Default), CursorIcon::Default);
+    }
+}
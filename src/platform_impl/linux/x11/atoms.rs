@@ -43,6 +43,7 @@ atom_manager! {
     XIM_SERVERS,
 
     // Assorted ICCCM Atoms
+    _NET_WM_DESKTOP,
     _NET_WM_ICON,
     _NET_WM_MOVERESIZE,
     _NET_WM_NAME,
@@ -94,14 +95,22 @@ atom_manager! {
     TextUriList: b"text/uri-list",
     None: b"None",
 
+    // Keyboard indicators, as named by XKB.
+    CapsLockIndicator: b"Caps Lock",
+    NumLockIndicator: b"Num Lock",
+    ScrollLockIndicator: b"Scroll Lock",
+
     // Miscellaneous Atoms
     _GTK_THEME_VARIANT,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
     _NET_CLIENT_LIST,
+    _NET_CURRENT_DESKTOP,
     _NET_FRAME_EXTENTS,
+    _NET_NUMBER_OF_DESKTOPS,
     _NET_SUPPORTED,
     _NET_SUPPORTING_WM_CHECK,
+    _NET_WORKAREA,
     _XEMBED,
     _XSETTINGS_SETTINGS
 }
@@ -3,6 +3,7 @@ use std::collections::{HashMap, VecDeque};
 use std::os::raw::{c_char, c_int, c_long, c_ulong};
 use std::slice;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use x11_dl::xinput2::{
     self, XIDeviceEvent, XIEnterEvent, XIFocusInEvent, XIFocusOutEvent, XIHierarchyEvent,
@@ -11,7 +12,7 @@ use x11_dl::xinput2::{
 use x11_dl::xlib::{
     self, Display as XDisplay, Window as XWindow, XAnyEvent, XClientMessageEvent, XConfigureEvent,
     XDestroyWindowEvent, XEvent, XExposeEvent, XKeyEvent, XMapEvent, XPropertyEvent,
-    XReparentEvent, XSelectionEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
+    XReparentEvent, XSelectionEvent, XUnmapEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
 };
 use x11rb::protocol::sync::{ConnectionExt, Int64};
 use x11rb::protocol::xinput;
@@ -24,13 +25,15 @@ use crate::application::ApplicationHandler;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::event::{
     ButtonSource, DeviceEvent, DeviceId, ElementState, FingerId, Ime, MouseButton,
-    MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter, TouchPhase,
-    WindowEvent,
+    MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, RawKeyRepeat, SurfaceSizeWriter,
+    TouchPhase, WindowEvent,
 };
 use crate::keyboard::ModifiersState;
 use crate::platform_impl::common::xkb::{self, XkbState};
 use crate::platform_impl::platform::common::xkb::Context;
-use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventReceiver, ImeRequest};
+use crate::platform_impl::platform::x11::ime::{
+    ComposingWindows, ImeEvent, ImeEventReceiver, ImeRequest,
+};
 use crate::platform_impl::platform::x11::ActiveEventLoop;
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::util::cookie::GenericEventCookie;
@@ -38,6 +41,7 @@ use crate::platform_impl::x11::{
     mkdid, mkwid, util, CookieResultExt, Device, DeviceInfo, Dnd, DndState, ImeReceiver,
     ScrollOrientation, UnownedWindow, WindowId,
 };
+use crate::window::{HitTestRole, TitlebarButton};
 
 /// The maximum amount of X modifiers to replay.
 pub const MAX_MOD_REPLAY_LEN: usize = 32;
@@ -72,7 +76,8 @@ pub struct EventProcessor {
     // delivered at near the same time.
     pub xfiltered_modifiers: VecDeque<u8>,
     pub xmodmap: util::ModifierKeymap,
-    pub is_composing: bool,
+    pub composing: ComposingWindows,
+    pub raw_key_repeat: RawKeyRepeat,
 }
 
 impl EventProcessor {
@@ -103,19 +108,19 @@ impl EventProcessor {
             let event = match event {
                 ImeEvent::Enabled => WindowEvent::Ime(Ime::Enabled),
                 ImeEvent::Start => {
-                    self.is_composing = true;
+                    self.composing.start(window);
                     WindowEvent::Ime(Ime::Preedit("".to_owned(), None))
                 },
-                ImeEvent::Update(text, position) if self.is_composing => {
+                ImeEvent::Update(text, position) if self.composing.is_composing(window) => {
                     WindowEvent::Ime(Ime::Preedit(text, Some((position, position))))
                 },
                 ImeEvent::End => {
-                    self.is_composing = false;
+                    self.composing.end(window);
                     // Issue empty preedit on `Done`.
                     WindowEvent::Ime(Ime::Preedit(String::new(), None))
                 },
                 ImeEvent::Disabled => {
-                    self.is_composing = false;
+                    self.composing.end(window);
                     WindowEvent::Ime(Ime::Disabled)
                 },
                 _ => continue,
@@ -182,6 +187,7 @@ impl EventProcessor {
             xlib::ConfigureNotify => self.configure_notify(xev.as_ref(), app),
             xlib::ReparentNotify => self.reparent_notify(xev.as_ref()),
             xlib::MapNotify => self.map_notify(xev.as_ref(), app),
+            xlib::UnmapNotify => self.unmap_notify(xev.as_ref()),
             xlib::DestroyNotify => self.destroy_notify(xev.as_ref(), app),
             xlib::PropertyNotify => self.property_notify(xev.as_ref(), app),
             xlib::VisibilityNotify => self.visibility_notify(xev.as_ref(), app),
@@ -771,10 +777,19 @@ impl EventProcessor {
         // The purpose of it is to deliver initial focused state of the newly created
         // window, given that we can't rely on `CreateNotify`, due to it being not
         // sent.
-        let focus = self.with_window(window, |window| window.has_focus()).unwrap_or_default();
+        let focus = self
+            .with_window(window, |window| {
+                window.set_mapped(true);
+                window.has_focus()
+            })
+            .unwrap_or_default();
         app.window_event(&self.target, window_id, WindowEvent::Focused(focus));
     }
 
+    fn unmap_notify(&self, xev: &XUnmapEvent) {
+        self.with_window(xev.window as xproto::Window, |window| window.set_mapped(false));
+    }
+
     fn destroy_notify(&self, xev: &XDestroyWindowEvent, app: &mut dyn ApplicationHandler) {
         let window = xev.window as xproto::Window;
         let window_id = mkwid(window);
@@ -809,10 +824,11 @@ impl EventProcessor {
         let xwindow = xev.window as xproto::Window;
 
         let window_id = mkwid(xwindow);
-        let event = WindowEvent::Occluded(xev.state == xlib::VisibilityFullyObscured);
-        app.window_event(&self.target, window_id, event);
+        let occluded = xev.state == xlib::VisibilityFullyObscured;
+        app.window_event(&self.target, window_id, WindowEvent::Occluded(occluded));
 
         self.with_window(xwindow, |window| {
+            window.set_occluded(occluded);
             window.visibility_notify();
         });
     }
@@ -895,17 +911,25 @@ impl EventProcessor {
             self.update_mods_from_core_event(window_id, xev.state as u16, app);
         }
 
-        if keycode != 0 && !self.is_composing {
+        if keycode != 0 && !self.composing.is_composing(window as XWindow) {
             // Don't alter the modifiers state from replaying.
             if replay {
                 self.send_synthic_modifier_from_core(window_id, xev.state as u16, app);
             }
 
+            let suppress_repeat = repeat
+                && !self
+                    .with_window(window as xproto::Window, |window| window.key_repeat_enabled())
+                    .unwrap_or(true);
+
             if let Some(mut key_processor) = self.xkb_context.key_context() {
-                let event = key_processor.process_key_event(keycode, state, repeat);
-                let event =
-                    WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: false };
-                app.window_event(&self.target, window_id, event);
+                let timestamp = event_timestamp(xev.time);
+                let event = key_processor.process_key_event(keycode, state, repeat, timestamp);
+                if !suppress_repeat {
+                    let event =
+                        WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: false };
+                    app.window_event(&self.target, window_id, event);
+                }
             }
 
             // Restore the client's modifiers state after replay.
@@ -925,7 +949,7 @@ impl EventProcessor {
                 app.window_event(&self.target, window_id, event);
 
                 let event = WindowEvent::Ime(Ime::Commit(written));
-                self.is_composing = false;
+                self.composing.end(window as XWindow);
                 app.window_event(&self.target, window_id, event);
             }
         }
@@ -977,14 +1001,18 @@ impl EventProcessor {
         }
 
         let position = PhysicalPosition::new(event.event_x, event.event_y);
+        let window = event.event as xproto::Window;
+        let detail = event.detail as u32;
+        let timestamp = event_timestamp(event.time);
 
-        let event = match event.detail as u32 {
+        let event = match detail {
             xlib::Button1 => WindowEvent::PointerButton {
                 device_id,
                 primary: true,
                 state,
                 position,
                 button: MouseButton::Left.into(),
+                timestamp,
             },
             xlib::Button2 => WindowEvent::PointerButton {
                 device_id,
@@ -992,6 +1020,7 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Middle.into(),
+                timestamp,
             },
 
             xlib::Button3 => WindowEvent::PointerButton {
@@ -1000,6 +1029,7 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Right.into(),
+                timestamp,
             },
 
             // Suppress emulated scroll wheel clicks, since we handle the real motion events for
@@ -1016,6 +1046,7 @@ impl EventProcessor {
                     _ => unreachable!(),
                 },
                 phase: TouchPhase::Moved,
+                momentum_phase: None,
             },
             8 => WindowEvent::PointerButton {
                 device_id,
@@ -1023,6 +1054,7 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Back.into(),
+                timestamp,
             },
 
             9 => WindowEvent::PointerButton {
@@ -1031,6 +1063,7 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Forward.into(),
+                timestamp,
             },
             x => WindowEvent::PointerButton {
                 device_id,
@@ -1038,10 +1071,23 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Other(x as u16).into(),
+                timestamp,
             },
         };
 
         app.window_event(&self.target, window_id, event);
+
+        if state == ElementState::Pressed && detail == xlib::Button1 {
+            match self.with_window(window, |window| window.hit_test(position)).flatten() {
+                Some(HitTestRole::Button(TitlebarButton::Close)) => {
+                    app.window_event(&self.target, window_id, WindowEvent::CloseRequested);
+                },
+                Some(role) => {
+                    self.with_window(window, |window| window.apply_hit_test_role(role));
+                },
+                None => (),
+            }
+        }
     }
 
     fn xinput2_mouse_motion(&self, event: &XIDeviceEvent, app: &mut dyn ApplicationHandler) {
@@ -1054,6 +1100,7 @@ impl EventProcessor {
         let new_cursor_pos = (event.event_x, event.event_y);
 
         let cursor_moved = self.with_window(window, |window| {
+            window.cancel_cursor_animation_on_user_motion(new_cursor_pos.into());
             let mut shared_state_lock = window.shared_state_lock();
             util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
         });
@@ -1066,6 +1113,7 @@ impl EventProcessor {
                 primary: true,
                 position,
                 source: PointerSource::Mouse,
+                timestamp: event_timestamp(event.time),
             };
             app.window_event(&self.target, window_id, event);
         } else if cursor_moved.is_none() {
@@ -1104,7 +1152,12 @@ impl EventProcessor {
                     ScrollOrientation::Vertical => MouseScrollDelta::LineDelta(0.0, -delta as f32),
                 };
 
-                let event = WindowEvent::MouseWheel { device_id, delta, phase: TouchPhase::Moved };
+                let event = WindowEvent::MouseWheel {
+                    device_id,
+                    delta,
+                    phase: TouchPhase::Moved,
+                    momentum_phase: None,
+                };
                 events.push(event);
             }
 
@@ -1226,6 +1279,7 @@ impl EventProcessor {
             primary: true,
             position,
             source: PointerSource::Mouse,
+            timestamp: event_timestamp(xev.time),
         };
         app.window_event(&self.target, window_id, event);
     }
@@ -1244,6 +1298,12 @@ impl EventProcessor {
             ime.borrow_mut().unfocus(xev.event).expect("Failed to unfocus input context");
         }
 
+        // Cancel the composition, it doesn't carry over to other windows.
+        if self.composing.end(xev.event) {
+            let event = WindowEvent::Ime(Ime::Preedit(String::new(), None));
+            app.window_event(&self.target, mkwid(window), event);
+        }
+
         if self.active_window.take() == Some(window) {
             let window_id = mkwid(window);
 
@@ -1286,6 +1346,7 @@ impl EventProcessor {
             let window_id = mkwid(window);
             let id = xev.detail as u32;
             let position = PhysicalPosition::new(xev.event_x, xev.event_y);
+            let timestamp = event_timestamp(xev.time);
 
             // Mouse cursor position changes when touch events are received.
             // Only the first concurrently active touch ID moves the mouse cursor.
@@ -1297,6 +1358,7 @@ impl EventProcessor {
                     primary: true,
                     position: position.cast(),
                     source: PointerSource::Mouse,
+                    timestamp,
                 };
                 app.window_event(&self.target, window_id, event);
             }
@@ -1319,6 +1381,7 @@ impl EventProcessor {
                         state: ElementState::Pressed,
                         position,
                         button: ButtonSource::Touch { finger_id, force: None },
+                        timestamp,
                     };
                     app.window_event(&self.target, window_id, event);
                 },
@@ -1328,6 +1391,7 @@ impl EventProcessor {
                         primary: is_first_touch,
                         position,
                         source: PointerSource::Touch { finger_id, force: None },
+                        timestamp,
                     };
                     app.window_event(&self.target, window_id, event);
                 },
@@ -1338,6 +1402,7 @@ impl EventProcessor {
                         state: ElementState::Released,
                         position,
                         button: ButtonSource::Touch { finger_id, force: None },
+                        timestamp,
                     };
                     app.window_event(&self.target, window_id, event);
                     let event = WindowEvent::PointerLeft {
@@ -1426,7 +1491,8 @@ impl EventProcessor {
         }
         let physical_key = xkb::raw_keycode_to_physicalkey(keycode);
 
-        let event = DeviceEvent::Key(RawKeyEvent { physical_key, state });
+        let repeat = self.raw_key_repeat.feed(physical_key, state);
+        let event = DeviceEvent::Key(RawKeyEvent { physical_key, state, repeat });
         app.device_event(&self.target, device_id, event);
     }
 
@@ -1445,6 +1511,7 @@ impl EventProcessor {
     }
 
     fn xkb_event(&mut self, xev: &XkbAnyEvent, app: &mut dyn ApplicationHandler) {
+        let mut keymap_changed = false;
         match xev.xkb_type {
             xlib::XkbNewKeyboardNotify => {
                 let xev = unsafe { &*(xev as *const _ as *const xlib::XkbNewKeyboardNotifyEvent) };
@@ -1463,14 +1530,12 @@ impl EventProcessor {
                 {
                     let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                     self.xkb_context.set_keymap_from_x11(xcb);
+                    keymap_changed = true;
                     self.xmodmap.reload_from_x_connection(&self.target.xconn);
 
-                    let window_id = match self.active_window.map(super::mkwid) {
-                        Some(window_id) => window_id,
-                        None => return,
-                    };
-
-                    if let Some(state) = self.xkb_context.state_mut() {
+                    if let (Some(window_id), Some(state)) =
+                        (self.active_window.map(super::mkwid), self.xkb_context.state_mut())
+                    {
                         let mods = state.modifiers().into();
                         self.send_modifiers(window_id, mods, true, app);
                     }
@@ -1479,13 +1544,12 @@ impl EventProcessor {
             xlib::XkbMapNotify => {
                 let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                 self.xkb_context.set_keymap_from_x11(xcb);
+                keymap_changed = true;
                 self.xmodmap.reload_from_x_connection(&self.target.xconn);
-                let window_id = match self.active_window.map(super::mkwid) {
-                    Some(window_id) => window_id,
-                    None => return,
-                };
 
-                if let Some(state) = self.xkb_context.state_mut() {
+                if let (Some(window_id), Some(state)) =
+                    (self.active_window.map(super::mkwid), self.xkb_context.state_mut())
+                {
                     let mods = state.modifiers().into();
                     self.send_modifiers(window_id, mods, true, app);
                 }
@@ -1506,16 +1570,45 @@ impl EventProcessor {
                         xev.locked_group as u32,
                     );
 
-                    let window_id = match self.active_window.map(super::mkwid) {
-                        Some(window_id) => window_id,
-                        None => return,
-                    };
-
-                    let mods = state.modifiers().into();
-                    self.send_modifiers(window_id, mods, true, app);
+                    if let Some(window_id) = self.active_window.map(super::mkwid) {
+                        let mods = state.modifiers().into();
+                        self.send_modifiers(window_id, mods, true, app);
+                    }
                 }
             },
-            _ => {},
+            _ => return,
+        }
+
+        self.update_keyboard_layout(keymap_changed, app);
+    }
+
+    /// Store the name and the keys of the active layout and notify the focused window when it
+    /// changed.
+    ///
+    /// The keys are also refreshed when the keymap was reloaded, which may keep the name.
+    fn update_keyboard_layout(&mut self, keymap_changed: bool, app: &mut dyn ApplicationHandler) {
+        let layout = self.xkb_context.layout_name();
+        let mut current = self.target.xconn.keyboard_layout.lock().unwrap();
+        let layout_changed = *current != layout;
+        if !layout_changed && !keymap_changed {
+            return;
+        }
+        let previous_direction = current.as_deref().and_then(xkb::layout_direction);
+        let direction = layout.as_deref().and_then(xkb::layout_direction);
+        *current = layout;
+        drop(current);
+
+        *self.target.xconn.layout_keys.lock().unwrap() = self.xkb_context.layout_keys();
+        if let Some(mask) = self.xkb_context.level_three_mask() {
+            *self.target.xconn.level_three_mods.lock().unwrap() = ModMask::from(mask as u16);
+        }
+
+        if let Some(window_id) = self.active_window.map(super::mkwid).filter(|_| layout_changed) {
+            app.window_event(&self.target, window_id, WindowEvent::KeyboardLayoutChanged);
+            if let Some(direction) = direction.filter(|&d| Some(d) != previous_direction) {
+                let event = WindowEvent::KeyboardLayoutDirectionChanged(direction);
+                app.window_event(&self.target, window_id, event);
+            }
         }
     }
 
@@ -1538,13 +1631,13 @@ impl EventProcessor {
 
             // NOTE: we use active window since generally sub windows don't have keyboard input,
             // and winit assumes that unfocused window doesn't have modifiers.
-            let window_id = match self.active_window.map(super::mkwid) {
-                Some(window_id) => window_id,
-                None => return,
-            };
+            if let Some(window_id) = self.active_window.map(super::mkwid) {
+                let mods = state.modifiers();
+                self.send_modifiers(window_id, mods.into(), force, app);
+            }
 
-            let mods = state.modifiers();
-            self.send_modifiers(window_id, mods.into(), force, app);
+            // The group is part of the state, so the layout could've changed as well.
+            self.update_keyboard_layout(false, app);
         }
     }
 
@@ -1699,7 +1792,7 @@ impl EventProcessor {
         };
 
         for keycode in target.xconn.query_keymap().into_iter().filter(|k| *k >= KEYCODE_OFFSET) {
-            let event = key_processor.process_key_event(keycode as u32, state, false);
+            let event = key_processor.process_key_event(keycode as u32, state, false, None);
             let event = WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: true };
             app.window_event(target, window_id, event);
         }
@@ -1744,6 +1837,11 @@ impl EventProcessor {
     }
 }
 
+/// The timestamp of an event from its X server time, see `KeyEvent::timestamp`.
+fn event_timestamp(time: xlib::Time) -> Option<Duration> {
+    Some(Duration::from_millis((time as xproto::Timestamp).into()))
+}
+
 fn is_first_touch(first: &mut Option<u32>, num: &mut u32, id: u32, phase: i32) -> bool {
     match phase {
         xinput2::XI_TouchBegin => {
@@ -5,6 +5,7 @@ mod context;
 mod inner;
 mod input_method;
 
+use std::collections::HashSet;
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
 
@@ -42,6 +43,28 @@ pub enum ImeRequest {
     Allow(ffi::Window, bool),
 }
 
+/// The windows with an ongoing composition.
+///
+/// Each window has its own input context, so a composition started in one window must not affect
+/// the others.
+#[derive(Debug, Default)]
+pub(crate) struct ComposingWindows(HashSet<ffi::Window>);
+
+impl ComposingWindows {
+    pub fn start(&mut self, window: ffi::Window) {
+        self.0.insert(window);
+    }
+
+    /// End the composition, returns whether one was ongoing.
+    pub fn end(&mut self, window: ffi::Window) -> bool {
+        self.0.remove(&window)
+    }
+
+    pub fn is_composing(&self, window: ffi::Window) -> bool {
+        self.0.contains(&window)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum ImeCreationError {
     // Boxed to prevent large error type
@@ -223,6 +246,34 @@ impl Ime {
     }
 }
 
+/// Where the candidate window is placed relative to the IME cursor area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandidateAnchor {
+    /// Below the area, which is where IM servers put it by default.
+    Below,
+    /// Above the area.
+    Above,
+}
+
+/// Decide where the candidate window goes for the area spanning `area_top..area_bottom`.
+///
+/// The candidate window is flipped above the area when `candidate_height` doesn't fit between the
+/// area and the bottom of the work area, but does fit between its top and the area.
+pub fn candidate_anchor(
+    area_top: i32,
+    area_bottom: i32,
+    (work_area_top, work_area_bottom): (i32, i32),
+    candidate_height: i32,
+) -> CandidateAnchor {
+    let fits_below = area_bottom.saturating_add(candidate_height) <= work_area_bottom;
+    let fits_above = area_top.saturating_sub(candidate_height) >= work_area_top;
+    if !fits_below && fits_above {
+        CandidateAnchor::Above
+    } else {
+        CandidateAnchor::Below
+    }
+}
+
 impl Drop for Ime {
     fn drop(&mut self) {
         unsafe {
@@ -231,3 +282,45 @@ impl Drop for Ime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn composition_is_per_window() {
+        let (a, b) = (1, 2);
+        let mut composing = ComposingWindows::default();
+
+        composing.start(a);
+        assert!(composing.is_composing(a));
+        assert!(!composing.is_composing(b));
+
+        // Ending the composition of another window doesn't affect the ongoing one.
+        assert!(!composing.end(b));
+        assert!(composing.is_composing(a));
+
+        composing.start(b);
+        assert!(composing.end(a));
+        assert!(!composing.is_composing(a));
+        assert!(composing.is_composing(b));
+    }
+
+    #[test]
+    fn candidate_anchor_flips_near_bottom_edge() {
+        let work_area = (0, 1000);
+        // Plenty of room below.
+        assert_eq!(candidate_anchor(100, 120, work_area, 200), CandidateAnchor::Below);
+        // Fits exactly.
+        assert_eq!(candidate_anchor(780, 800, work_area, 200), CandidateAnchor::Below);
+        // Near the bottom edge.
+        assert_eq!(candidate_anchor(800, 820, work_area, 200), CandidateAnchor::Above);
+        assert_eq!(candidate_anchor(970, 990, work_area, 200), CandidateAnchor::Above);
+        // Fits nowhere, keep the default.
+        assert_eq!(candidate_anchor(150, 900, work_area, 200), CandidateAnchor::Below);
+        // A panel at the bottom shrinks the work area.
+        assert_eq!(candidate_anchor(750, 770, (0, 960), 200), CandidateAnchor::Above);
+        // The work area is in window coordinates, so the area may be above it.
+        assert_eq!(candidate_anchor(-50, -30, (-400, 600), 200), CandidateAnchor::Below);
+    }
+}
@@ -26,9 +26,10 @@ use crate::application::ApplicationHandler;
 use crate::error::{EventLoopError, RequestError};
 use crate::event::{DeviceId, StartCause, WindowEvent};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
 };
 use crate::platform::pump_events::PumpStatus;
 use crate::platform_impl::common::xkb::Context;
@@ -142,6 +143,7 @@ pub struct ActiveEventLoop {
     activation_sender: WakeSender<ActivationToken>,
     event_loop_proxy: CoreEventLoopProxy,
     device_events: Cell<DeviceEvents>,
+    pub(crate) common_state: CommonEventLoopState,
 }
 
 pub struct EventLoop {
@@ -282,9 +284,15 @@ impl EventLoop {
             .expect("Failed to register the event loop waker source");
         let event_loop_proxy = EventLoopProxy::new(user_waker);
 
-        let xkb_context =
+        let mut xkb_context =
             Context::from_x11_xkb(xconn.xcb_connection().get_raw_xcb_connection()).unwrap();
 
+        *xconn.keyboard_layout.lock().unwrap() = xkb_context.layout_name();
+        *xconn.layout_keys.lock().unwrap() = xkb_context.layout_keys();
+        if let Some(mask) = xkb_context.level_three_mask() {
+            *xconn.level_three_mods.lock().unwrap() = xproto::ModMask::from(mask as u16);
+        }
+
         let mut xmodmap = util::ModifierKeymap::new();
         xmodmap.reload_from_x_connection(&xconn);
 
@@ -309,6 +317,7 @@ impl EventLoop {
             },
             event_loop_proxy: event_loop_proxy.into(),
             device_events: Default::default(),
+            common_state: Default::default(),
         };
 
         // Set initial device event filter.
@@ -331,7 +340,8 @@ impl EventLoop {
             first_touch: None,
             active_window: None,
             modifiers: Default::default(),
-            is_composing: false,
+            composing: Default::default(),
+            raw_key_repeat: Default::default(),
         };
 
         // Register for device hotplug events
@@ -466,6 +476,7 @@ impl EventLoop {
 
             min_timeout(control_flow_timeout, timeout)
         };
+        timeout = min_timeout(timeout, self.step_cursor_animations(start));
 
         self.state.x11_readiness = Readiness::EMPTY;
         if let Err(error) =
@@ -569,6 +580,19 @@ impl EventLoop {
         app.about_to_wait(&self.event_processor.target);
     }
 
+    /// Advance the cursor animations of all windows, returning the time until the next step.
+    fn step_cursor_animations(&self, now: Instant) -> Option<Duration> {
+        self.event_processor
+            .target
+            .windows
+            .borrow()
+            .values()
+            .filter_map(Weak::upgrade)
+            .filter_map(|window| window.step_cursor_animation(now))
+            .min()
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
     fn drain_events<A: ApplicationHandler>(&mut self, app: &mut A) {
         let mut xev = MaybeUninit::uninit();
 
@@ -655,7 +679,9 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         window_attributes: WindowAttributes,
     ) -> Result<Box<dyn CoreWindow>, RequestError> {
-        Ok(Box::new(Window::new(self, window_attributes)?))
+        let window: Box<dyn CoreWindow> = Box::new(Window::new(self, window_attributes)?);
+        self.common_state.register_window(&*window);
+        Ok(window)
     }
 
     fn create_custom_cursor(
@@ -690,6 +716,23 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        let atoms = self.xconn.atoms();
+        let mut capabilities = WindowingCapabilities::empty();
+        let name = util::wm_name();
+        // Decorations are drawn by the window manager, if there is any.
+        capabilities.set(WindowingCapabilities::SERVER_SIDE_DECORATIONS, name.is_some());
+        capabilities.set(
+            WindowingCapabilities::MOVE_RESIZE,
+            util::hint_is_supported(atoms[_NET_WM_MOVERESIZE]),
+        );
+        capabilities.set(
+            WindowingCapabilities::ALWAYS_ON_TOP,
+            util::hint_is_supported(atoms[_NET_WM_STATE_ABOVE]),
+        );
+        WindowingInfo::new(WindowingBackend::X11, name, capabilities)
+    }
+
     fn listen_device_events(&self, allowed: DeviceEvents) {
         self.device_events.set(allowed);
     }
@@ -44,6 +44,8 @@ pub struct MonitorHandle {
     primary: bool,
     /// The DPI scale factor
     pub(crate) scale_factor: f64,
+    /// The bits per color channel of the root visual
+    bit_depth: Option<u32>,
     /// Used to determine which windows are on this monitor
     pub(crate) rect: util::AaRect,
     /// Supported video modes on this monitor
@@ -101,8 +103,18 @@ impl MonitorHandle {
         let position = (crtc.x as i32, crtc.y as i32);
 
         let rect = util::AaRect::new(position, dimensions);
-
-        Some(MonitorHandle { id, name, scale_factor, position, primary, rect, video_modes })
+        let bit_depth = root_visual_bits_per_channel(xconn.default_root());
+
+        Some(MonitorHandle {
+            id,
+            name,
+            scale_factor,
+            bit_depth,
+            position,
+            primary,
+            rect,
+            video_modes,
+        })
     }
 
     pub fn dummy() -> Self {
@@ -110,6 +122,7 @@ impl MonitorHandle {
             id: 0,
             name: "<dummy monitor>".into(),
             scale_factor: 1.0,
+            bit_depth: None,
             position: (0, 0),
             primary: true,
             rect: util::AaRect::new((0, 0), (1, 1)),
@@ -140,6 +153,11 @@ impl MonitorHandle {
         self.scale_factor
     }
 
+    #[inline]
+    pub fn bit_depth(&self) -> Option<u32> {
+        self.bit_depth
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         self.video_modes.iter().find(|mode| mode.current).cloned().map(Into::into)
@@ -151,6 +169,17 @@ impl MonitorHandle {
     }
 }
 
+/// RandR scans every output out of the root window, so its visual tells how many bits each color
+/// channel has.
+fn root_visual_bits_per_channel(root: &xproto::Screen) -> Option<u32> {
+    root.allowed_depths
+        .iter()
+        .flat_map(|depth| &depth.visuals)
+        .find(|visual| visual.visual_id == root.root_visual)
+        .map(|visual| u32::from(visual.bits_per_rgb_value))
+        .filter(|&bits| bits != 0)
+}
+
 impl XConnection {
     pub fn get_monitor_for_window(
         &self,
@@ -310,3 +339,32 @@ impl ScreenResources {
         Self { modes: reply.modes, crtcs: reply.crtcs }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_per_channel_of_the_root_visual() {
+        let visual = |visual_id, bits_per_rgb_value| xproto::Visualtype {
+            visual_id,
+            bits_per_rgb_value,
+            ..Default::default()
+        };
+        let mut root = xproto::Screen {
+            root_visual: 0x21,
+            allowed_depths: vec![
+                xproto::Depth { depth: 24, visuals: vec![visual(0x21, 8), visual(0x22, 8)] },
+                xproto::Depth { depth: 30, visuals: vec![visual(0x31, 10)] },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(root_visual_bits_per_channel(&root), Some(8));
+
+        root.root_visual = 0x31;
+        assert_eq!(root_visual_bits_per_channel(&root), Some(10));
+
+        root.root_visual = 0x41;
+        assert_eq!(root_visual_bits_per_channel(&root), None);
+    }
+}
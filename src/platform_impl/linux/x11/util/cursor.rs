@@ -9,7 +9,9 @@ use x11rb::protocol::xproto;
 
 use super::super::ActiveEventLoop;
 use super::*;
+use crate::cursor::CursorImage;
 use crate::error::RequestError;
+use crate::platform_impl::common::xcursor::CursorThemeOverride;
 use crate::platform_impl::PlatformCustomCursorSource;
 use crate::window::CursorIcon;
 
@@ -18,7 +20,18 @@ impl XConnection {
         &self,
         window: xproto::Window,
         cursor: Option<CursorIcon>,
+        theme: Option<&CursorThemeOverride>,
     ) -> Result<(), X11Error> {
+        if let (Some(icon), Some(theme)) = (cursor, theme) {
+            match self.get_themed_cursor(icon, theme)? {
+                Some(cursor) => return self.update_cursor(window, cursor),
+                None => tracing::warn!(
+                    "cursor {icon:?} not found in theme {:?}, using the system theme",
+                    theme.name
+                ),
+            }
+        }
+
         let cursor = {
             let mut cache = self.cursor_cache.lock().unwrap_or_else(|e| e.into_inner());
 
@@ -39,6 +52,48 @@ impl XConnection {
         self.update_cursor(window, cursor.inner.cursor)
     }
 
+    /// Load `icon` from the cursor theme of a window, caching the result.
+    fn get_themed_cursor(
+        &self,
+        icon: CursorIcon,
+        theme: &CursorThemeOverride,
+    ) -> Result<Option<xproto::Cursor>, X11Error> {
+        let mut cache = self.themed_cursor_cache.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (theme.clone(), icon);
+        if let Some(&cursor) = cache.get(&key) {
+            return Ok(Some(cursor));
+        }
+
+        // The size is used as is, like `Xcursor.size`.
+        let Some(image) = theme.load(icon, 1) else { return Ok(None) };
+        let cursor = self.create_cursor_from_rgba(image)?;
+        cache.insert(key, cursor);
+        Ok(Some(cursor))
+    }
+
+    /// Create a cursor from an RGBA image.
+    fn create_cursor_from_rgba(&self, mut image: CursorImage) -> Result<xproto::Cursor, X11Error> {
+        // Reverse RGBA order to BGRA.
+        image.rgba.chunks_mut(4).for_each(|chunk| {
+            let chunk: &mut [u8; 4] = chunk.try_into().unwrap();
+            chunk[0..3].reverse();
+
+            // Byteswap if we need to.
+            if self.needs_endian_swap() {
+                let value = u32::from_ne_bytes(*chunk).swap_bytes();
+                *chunk = value.to_ne_bytes();
+            }
+        });
+
+        self.create_cursor_from_image(
+            image.width,
+            image.height,
+            image.hotspot_x,
+            image.hotspot_y,
+            &image.rgba,
+        )
+    }
+
     /// Create a cursor from an image.
     fn create_cursor_from_image(
         &self,
@@ -192,30 +247,10 @@ impl Eq for CustomCursor {}
 impl CustomCursor {
     pub(crate) fn new(
         event_loop: &ActiveEventLoop,
-        mut cursor: PlatformCustomCursorSource,
+        cursor: PlatformCustomCursorSource,
     ) -> Result<CustomCursor, RequestError> {
-        // Reverse RGBA order to BGRA.
-        cursor.0.rgba.chunks_mut(4).for_each(|chunk| {
-            let chunk: &mut [u8; 4] = chunk.try_into().unwrap();
-            chunk[0..3].reverse();
-
-            // Byteswap if we need to.
-            if event_loop.xconn.needs_endian_swap() {
-                let value = u32::from_ne_bytes(*chunk).swap_bytes();
-                *chunk = value.to_ne_bytes();
-            }
-        });
-
-        let cursor = event_loop
-            .xconn
-            .create_cursor_from_image(
-                cursor.0.width,
-                cursor.0.height,
-                cursor.0.hotspot_x,
-                cursor.0.hotspot_y,
-                &cursor.0.rgba,
-            )
-            .map_err(|err| os_error!(err))?;
+        let cursor =
+            event_loop.xconn.create_cursor_from_rgba(cursor.0).map_err(|err| os_error!(err))?;
 
         Ok(Self { inner: Arc::new(CustomCursorInner { xconn: event_loop.xconn.clone(), cursor }) })
     }
@@ -18,6 +18,14 @@ impl AaRect {
         AaRect { x, y, width, height }
     }
 
+    pub fn top(&self) -> i64 {
+        self.y
+    }
+
+    pub fn bottom(&self) -> i64 {
+        self.y + self.height
+    }
+
     pub fn contains_point(&self, x: i64, y: i64) -> bool {
         x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
     }
@@ -1,9 +1,12 @@
-use std::{slice, str};
+use std::{ptr, slice, str};
 
 use x11rb::protocol::xinput::{self, ConnectionExt as _};
 use x11rb::protocol::xkb;
+use x11rb::protocol::xproto::ModMask;
 
 use super::*;
+use crate::keyboard::ModifiersState;
+use crate::platform::led_state::Leds;
 
 pub const VIRTUAL_CORE_POINTER: u16 = 2;
 
@@ -56,6 +59,47 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    /// The modifiers of the core keyboard, assuming the usual mapping of the real modifiers, as
+    /// XKB does, except for AltGr which follows the keymap.
+    pub fn query_modifiers(&self) -> ModifiersState {
+        let mut state: ffi::XkbStateRec = unsafe { std::mem::zeroed() };
+        let status = unsafe {
+            (self.xlib.XkbGetState)(self.display, xkb::ID::USE_CORE_KBD.into(), &mut state)
+        };
+        if status != ffi::Success as _ {
+            tracing::warn!("Failed to query the state of the keyboard");
+            return ModifiersState::empty();
+        }
+
+        let level_three_mods = *self.level_three_mods.lock().unwrap();
+        modifiers_from_masks(state.mods.into(), state.locked_mods.into(), level_three_mods)
+    }
+
+    /// The lock LEDs of the core keyboard. Indicators missing from the keymap are reported off.
+    pub fn query_leds(&self) -> Leds {
+        let atoms = self.atoms();
+        let mut leds = Leds::empty();
+        for (led, name) in [
+            (Leds::CAPS_LOCK, CapsLockIndicator),
+            (Leds::NUM_LOCK, NumLockIndicator),
+            (Leds::SCROLL_LOCK, ScrollLockIndicator),
+        ] {
+            let mut lit = ffi::False;
+            let found = unsafe {
+                (self.xlib.XkbGetNamedIndicator)(
+                    self.display,
+                    atoms[name] as c_ulong,
+                    ptr::null_mut(),
+                    &mut lit,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                )
+            };
+            leds.set(led, found == ffi::True && lit == ffi::True);
+        }
+        leds
+    }
+
     fn lookup_utf8_inner(
         &self,
         ic: ffi::XIC,
@@ -104,3 +148,47 @@ impl XConnection {
         str::from_utf8(bytes).unwrap_or("").to_string()
     }
 }
+
+/// The modifiers of the effective `mods` and `locked_mods` real modifiers, with the usual mapping,
+/// and AltGr on `level_three_mods`.
+fn modifiers_from_masks(
+    mods: ModMask,
+    locked_mods: ModMask,
+    level_three_mods: ModMask,
+) -> ModifiersState {
+    let mut modifiers = ModifiersState::empty();
+    modifiers.set(ModifiersState::SHIFT, mods.contains(ModMask::SHIFT));
+    modifiers.set(ModifiersState::CONTROL, mods.contains(ModMask::CONTROL));
+    modifiers.set(ModifiersState::ALT, mods.contains(ModMask::M1));
+    modifiers.set(ModifiersState::SUPER, mods.contains(ModMask::M4));
+    modifiers.set(ModifiersState::ALT_GRAPH, mods.intersects(level_three_mods));
+    modifiers.set(ModifiersState::CAPS_LOCK, locked_mods.contains(ModMask::LOCK));
+    modifiers.set(ModifiersState::NUM_LOCK, locked_mods.contains(ModMask::M2));
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifiers_of_real_modifiers() {
+        let none = ModMask::from(0u16);
+        assert_eq!(modifiers_from_masks(none, none, ModMask::M5), ModifiersState::empty());
+        assert_eq!(
+            modifiers_from_masks(ModMask::SHIFT | ModMask::M1 | ModMask::M4, none, ModMask::M5),
+            ModifiersState::SHIFT | ModifiersState::ALT | ModifiersState::SUPER
+        );
+        assert_eq!(
+            modifiers_from_masks(ModMask::CONTROL | ModMask::M5, none, ModMask::M5),
+            ModifiersState::CONTROL | ModifiersState::ALT_GRAPH
+        );
+        // Without a level three modifier, there is no AltGr.
+        assert_eq!(modifiers_from_masks(ModMask::M5, none, none), ModifiersState::empty());
+        // Only the locked modifiers are locks.
+        assert_eq!(
+            modifiers_from_masks(ModMask::LOCK, ModMask::LOCK | ModMask::M2, ModMask::M5),
+            ModifiersState::CAPS_LOCK | ModifiersState::NUM_LOCK
+        );
+    }
+}
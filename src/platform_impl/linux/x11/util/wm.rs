@@ -21,6 +21,10 @@ pub fn hint_is_supported(hint: xproto::Atom) -> bool {
     (*SUPPORTED_HINTS.lock().unwrap()).contains(&hint)
 }
 
+pub fn wm_name() -> Option<String> {
+    WM_NAME.lock().unwrap().clone()
+}
+
 pub fn wm_name_is_one_of(names: &[&str]) -> bool {
     if let Some(ref name) = *WM_NAME.lock().unwrap() {
         names.contains(&name.as_str())
@@ -132,6 +136,31 @@ impl XConnection {
         }
         .ok();
 
-        wm_name.and_then(|wm_name| String::from_utf8(wm_name).ok())
+        wm_name.and_then(parse_wm_name)
+    }
+}
+
+/// Decode the `_NET_WM_NAME` of the window manager, dropping the null terminator IceWM sends.
+fn parse_wm_name(mut wm_name: Vec<u8>) -> Option<String> {
+    if wm_name.last() == Some(&0) {
+        wm_name.pop();
+    }
+    String::from_utf8(wm_name).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_wm_name;
+
+    #[test]
+    fn supporting_wm_name() {
+        assert_eq!(parse_wm_name(b"KWin".to_vec()).as_deref(), Some("KWin"));
+        assert_eq!(parse_wm_name(b"Xfwm4".to_vec()).as_deref(), Some("Xfwm4"));
+        // IceWM null-terminates the name and includes version information.
+        assert_eq!(
+            parse_wm_name(b"IceWM 1.3.8 (Linux 3.2.0/x86_64)\0".to_vec()).as_deref(),
+            Some("IceWM 1.3.8 (Linux 3.2.0/x86_64)")
+        );
+        assert_eq!(parse_wm_name(b"\xff\xfe".to_vec()), None);
     }
 }
@@ -5,6 +5,7 @@ use std::ops::Deref;
 use std::os::raw::*;
 use std::path::Path;
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use std::{cmp, env};
 
 use tracing::{debug, info, warn};
@@ -16,27 +17,35 @@ use x11rb::protocol::xfixes::{ConnectionExt, RegionWrapper};
 use x11rb::protocol::xproto::{self, ConnectionExt as _, Rectangle};
 use x11rb::protocol::{randr, xinput};
 
+use super::ime::{self, CandidateAnchor};
 use super::util::{self, SelectedCursor};
 use super::{
     ffi, ActiveEventLoop, CookieResultExt, ImeRequest, ImeSender, VoidCookie, XConnection,
 };
 use crate::application::ApplicationHandler;
-use crate::cursor::{Cursor, CustomCursor as RootCustomCursor};
+use crate::cursor::{CurrentCursor, Cursor, CursorAnimation, CustomCursor as RootCustomCursor};
 use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
 use crate::event::{SurfaceSizeWriter, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
+use crate::monitor::find_exclusive_mode;
+use crate::platform::led_state::LedState;
 use crate::platform::x11::WindowType;
+use crate::platform_impl::common::xcursor::CursorThemeOverride;
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::{
     xinput_fp1616_to_float, MonitorHandle as X11MonitorHandle, WakeSender, X11Error,
 };
 use crate::platform_impl::{
-    common, Fullscreen, MonitorHandle as PlatformMonitorHandle, PlatformCustomCursor, PlatformIcon,
+    common, Fullscreen, MonitorHandle as PlatformMonitorHandle, OccludedRedraw,
+    PlatformCustomCursor, PlatformIcon,
 };
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    self, CommonWindowState, CursorGrabMode, HitTestRegion, HitTestRole, ImePurpose,
+    InputStateSnapshot, ResizeDirection, SurfaceTransform, Theme, TitlebarButton,
+    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
+    WindowLevel,
 };
 
 pub(crate) struct Window(Arc<UnownedWindow>);
@@ -110,6 +119,10 @@ impl CoreWindow for Window {
         self.0.safe_area()
     }
 
+    fn surface_transform(&self) -> SurfaceTransform {
+        SurfaceTransform::Identity
+    }
+
     fn set_min_surface_size(&self, min_size: Option<Size>) {
         self.0.set_min_surface_size(min_size)
     }
@@ -138,6 +151,10 @@ impl CoreWindow for Window {
         self.0.set_blur(blur);
     }
 
+    fn set_tearing_allowed(&self, _allowed: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_tearing_allowed is not supported").into())
+    }
+
     fn set_visible(&self, visible: bool) {
         self.0.set_visible(visible);
     }
@@ -178,6 +195,14 @@ impl CoreWindow for Window {
         self.0.is_maximized()
     }
 
+    fn workspace(&self) -> Option<u32> {
+        self.0.workspace()
+    }
+
+    fn move_to_workspace(&self, index: u32) -> Result<(), RequestError> {
+        self.0.move_to_workspace(index)
+    }
+
     fn set_fullscreen(&self, fullscreen: Option<crate::window::Fullscreen>) {
         self.0.set_fullscreen(fullscreen.map(Into::into))
     }
@@ -214,6 +239,40 @@ impl CoreWindow for Window {
         self.0.set_ime_purpose(purpose);
     }
 
+    fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.0.set_key_repeat_enabled(enabled);
+    }
+
+    fn keyboard_layout_name(&self) -> Option<String> {
+        self.0.xconn.keyboard_layout.lock().unwrap().clone()
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        self.0
+            .xconn
+            .keyboard_layout
+            .lock()
+            .unwrap()
+            .as_deref()
+            .and_then(common::xkb::layout_direction)
+    }
+
+    fn key_for_physical(&self, key: PhysicalKey) -> Option<Key> {
+        self.0.xconn.layout_keys.lock().unwrap().get(key)
+    }
+
+    fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        self.0.xconn.layout_keys.lock().unwrap().compose_dead_key(dead, base)
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        self.0.xconn.query_modifiers()
+    }
+
+    fn set_hit_test_regions(&self, regions: &[HitTestRegion]) {
+        self.0.shared_state_lock().hit_test_regions = regions.to_vec();
+    }
+
     fn focus_window(&self) {
         self.0.focus_window();
     }
@@ -246,10 +305,22 @@ impl CoreWindow for Window {
         self.0.set_cursor(cursor);
     }
 
+    fn current_cursor(&self) -> Cursor {
+        self.0.current_cursor.get()
+    }
+
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError> {
         self.0.set_cursor_position(position)
     }
 
+    fn animate_cursor_to(
+        &self,
+        target: PhysicalPosition<i32>,
+        duration: Duration,
+    ) -> Result<(), RequestError> {
+        self.0.animate_cursor_to(target, duration)
+    }
+
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
         self.0.set_cursor_grab(mode)
     }
@@ -258,6 +329,10 @@ impl CoreWindow for Window {
         self.0.set_cursor_visible(visible);
     }
 
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        self.0.capture_input_state()
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         self.0.drag_window()
     }
@@ -366,6 +441,9 @@ pub struct SharedState {
     pub has_focus: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    pub ime_allowed: bool,
+    pub key_repeat_enabled: bool,
+    pub hit_test_regions: Vec<HitTestRegion>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -404,6 +482,9 @@ impl SharedState {
             base_size: None,
             has_focus: false,
             cursor_hittest: None,
+            ime_allowed: false,
+            key_repeat_enabled: true,
+            hit_test_regions: Vec::new(),
         })
     }
 }
@@ -421,12 +502,17 @@ pub struct UnownedWindow {
     screen_id: i32, // never changes
     sync_counter_id: Option<NonZeroU32>, // never changes
     selected_cursor: Mutex<SelectedCursor>,
+    cursor_theme: Mutex<Option<CursorThemeOverride>>,
     cursor_grabbed_mode: Mutex<CursorGrabMode>,
     #[allow(clippy::mutex_atomic)]
     cursor_visible: Mutex<bool>,
+    cursor_animation: Mutex<Option<CursorAnimation>>,
+    current_cursor: CurrentCursor,
+    pub(crate) common_state: Arc<CommonWindowState>,
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
     redraw_sender: WakeSender<WindowId>,
+    occluded_redraw: Mutex<OccludedRedraw>,
     activation_sender: WakeSender<super::ActivationToken>,
 }
 macro_rules! leap {
@@ -642,11 +728,16 @@ impl UnownedWindow {
             screen_id,
             sync_counter_id: None,
             selected_cursor: Default::default(),
+            cursor_theme: Mutex::new(None),
             cursor_grabbed_mode: Mutex::new(CursorGrabMode::None),
             cursor_visible: Mutex::new(true),
+            cursor_animation: Mutex::new(None),
+            current_cursor: CurrentCursor::default(),
+            common_state: Arc::new(CommonWindowState::new(&window_attrs)),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
             redraw_sender: event_loop.redraw_sender.clone(),
+            occluded_redraw: Mutex::new(OccludedRedraw::new(window_attrs.redraw_while_occluded)),
             activation_sender: event_loop.activation_sender.clone(),
         };
 
@@ -1088,13 +1179,8 @@ impl UnownedWindow {
                 }
 
                 if let Some(native_mode) = video_mode.and_then(|requested| {
-                    monitor.video_modes.iter().find_map(|mode| {
-                        if mode.mode == requested {
-                            Some(mode.native_mode)
-                        } else {
-                            None
-                        }
-                    })
+                    find_exclusive_mode(monitor.video_modes.iter(), &requested, |mode| &mode.mode)
+                        .map(|mode| mode.native_mode)
                 }) {
                     // FIXME: this is actually not correct if we're setting the
                     // video mode to a resolution higher than the current
@@ -1153,6 +1239,22 @@ impl UnownedWindow {
         }
     }
 
+    /// Update the occlusion state, delivering a redraw held back while the window was occluded.
+    pub(crate) fn set_occluded(&self, occluded: bool) {
+        if self.occluded_redraw.lock().unwrap().set_occluded(occluded) {
+            self.redraw_sender.send(WindowId::from_raw(self.xwindow as _));
+        }
+    }
+
+    /// Update the mapping state, delivering a redraw held back while the window was unmapped.
+    ///
+    /// Window managers unmap minimized windows, as required by the ICCCM.
+    pub(crate) fn set_mapped(&self, mapped: bool) {
+        if self.occluded_redraw.lock().unwrap().set_minimized(!mapped) {
+            self.redraw_sender.send(WindowId::from_raw(self.xwindow as _));
+        }
+    }
+
     // Called by EventProcessor when a VisibilityNotify event is received
     pub(crate) fn visibility_notify(&self) {
         let mut shared_state = self.shared_state_lock();
@@ -1307,6 +1409,65 @@ impl UnownedWindow {
         }
     }
 
+    #[inline]
+    pub fn workspace(&self) -> Option<u32> {
+        let atoms = self.xconn.atoms();
+        let desktop = self
+            .xconn
+            .get_property::<u32>(
+                self.xwindow,
+                atoms[_NET_WM_DESKTOP],
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+            )
+            .ok()?;
+        desktop_from_property(&desktop)
+    }
+
+    pub fn move_to_workspace(&self, index: u32) -> Result<(), RequestError> {
+        let atoms = self.xconn.atoms();
+        let desktops = self
+            .xconn
+            .get_property::<u32>(
+                self.root,
+                atoms[_NET_NUMBER_OF_DESKTOPS],
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+            )
+            .ok()
+            .and_then(|desktops| desktops.first().copied())
+            .ok_or_else(|| NotSupportedError::new("the window manager has no workspaces"))?;
+        if index >= desktops {
+            return Err(RequestError::Ignored);
+        }
+
+        // The property may only be set directly on withdrawn windows, the window manager owns it
+        // afterwards and has to be asked with a client message instead.
+        if self.shared_state_lock().visibility == Visibility::Yes {
+            leap!(self.xconn.send_client_msg(
+                self.xwindow,
+                self.root,
+                atoms[_NET_WM_DESKTOP],
+                Some(
+                    xproto::EventMask::SUBSTRUCTURE_REDIRECT
+                        | xproto::EventMask::SUBSTRUCTURE_NOTIFY
+                ),
+                move_to_desktop_message(index),
+            ))
+            .ignore_error();
+        } else {
+            leap!(self.xconn.change_property(
+                self.xwindow,
+                atoms[_NET_WM_DESKTOP],
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &[index],
+            ))
+            .ignore_error();
+        }
+        leap!(self.xconn.flush_requests());
+
+        Ok(())
+    }
+
     fn set_maximized_inner(&self, maximized: bool) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let horz_atom = atoms[_NET_WM_STATE_MAXIMIZED_HORZ];
@@ -1796,6 +1957,7 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
         match cursor {
             Cursor::Icon(icon) => {
                 let old_cursor = replace(
@@ -1806,7 +1968,10 @@ impl UnownedWindow {
                 #[allow(clippy::mutex_atomic)]
                 if SelectedCursor::Named(icon) != old_cursor && *self.cursor_visible.lock().unwrap()
                 {
-                    if let Err(err) = self.xconn.set_cursor_icon(self.xwindow, Some(icon)) {
+                    let theme = self.cursor_theme.lock().unwrap();
+                    if let Err(err) =
+                        self.xconn.set_cursor_icon(self.xwindow, Some(icon), theme.as_ref())
+                    {
                         tracing::error!("failed to set cursor icon: {err}");
                     }
                 }
@@ -1926,9 +2091,10 @@ impl UnownedWindow {
                 self.xconn.set_custom_cursor(self.xwindow, &cursor)
             },
             Some(SelectedCursor::Named(cursor)) => {
-                self.xconn.set_cursor_icon(self.xwindow, Some(cursor))
+                let theme = self.cursor_theme.lock().unwrap();
+                self.xconn.set_cursor_icon(self.xwindow, Some(cursor), theme.as_ref())
             },
-            None => self.xconn.set_cursor_icon(self.xwindow, None),
+            None => self.xconn.set_cursor_icon(self.xwindow, None, None),
         };
 
         if let Err(err) = result {
@@ -1936,6 +2102,41 @@ impl UnownedWindow {
         }
     }
 
+    #[inline]
+    pub fn led_state(&self) -> LedState {
+        LedState::Known(self.xconn.query_leds())
+    }
+
+    pub fn set_cursor_theme(&self, name: Option<String>, size: Option<u32>) {
+        let theme = CursorThemeOverride::new(name, size);
+        let mut theme_lock = self.cursor_theme.lock().unwrap();
+        if *theme_lock == theme {
+            return;
+        }
+        *theme_lock = theme;
+
+        #[allow(clippy::mutex_atomic)]
+        let visible = *self.cursor_visible.lock().unwrap();
+        if let SelectedCursor::Named(icon) = *self.selected_cursor.lock().unwrap() {
+            if visible {
+                if let Err(err) =
+                    self.xconn.set_cursor_icon(self.xwindow, Some(icon), theme_lock.as_ref())
+                {
+                    tracing::error!("failed to set cursor icon: {err}");
+                }
+            }
+        }
+    }
+
+    pub fn capture_input_state(&self) -> InputStateSnapshot {
+        InputStateSnapshot {
+            cursor_grab: *self.cursor_grabbed_mode.lock().unwrap(),
+            cursor_visible: *self.cursor_visible.lock().unwrap(),
+            cursor: self.current_cursor.get(),
+            ime_allowed: self.shared_state_lock().ime_allowed,
+        }
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.shared_state_lock().last_monitor.scale_factor
@@ -1956,6 +2157,62 @@ impl UnownedWindow {
         self.set_cursor_position_physical(x, y)
     }
 
+    pub fn animate_cursor_to(
+        &self,
+        target: PhysicalPosition<i32>,
+        duration: Duration,
+    ) -> Result<(), RequestError> {
+        if duration.is_zero() {
+            *self.cursor_animation.lock().unwrap() = None;
+            return self.set_cursor_position_physical(target.x, target.y);
+        }
+
+        let pointer = self
+            .xconn
+            .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| os_error!(err))?;
+        let from = PhysicalPosition::new(
+            xinput_fp1616_to_float(pointer.win_x).round() as i32,
+            xinput_fp1616_to_float(pointer.win_y).round() as i32,
+        );
+
+        *self.cursor_animation.lock().unwrap() =
+            Some(CursorAnimation::new(from, target, Instant::now(), duration));
+
+        // The event loop drives the animation, make sure it picks it up.
+        self.redraw_sender.waker.ping();
+        Ok(())
+    }
+
+    /// Warp the cursor to where the running animation should be at `now`.
+    ///
+    /// Returns when the animation wants to be stepped again.
+    pub(crate) fn step_cursor_animation(&self, now: Instant) -> Option<Instant> {
+        let mut animation = self.cursor_animation.lock().unwrap();
+        let position = animation.as_mut()?.step(now);
+        let finished = animation.as_ref().is_some_and(|animation| animation.is_finished(now));
+        if finished {
+            *animation = None;
+        }
+        drop(animation);
+
+        if let Err(err) = self.set_cursor_position_physical(position.x, position.y) {
+            warn!("failed to animate cursor: {err}");
+            *self.cursor_animation.lock().unwrap() = None;
+            return None;
+        }
+
+        (!finished).then(|| now + CursorAnimation::FRAME_INTERVAL)
+    }
+
+    /// Stop the running cursor animation if `position` was not reached by our own warps.
+    pub(crate) fn cancel_cursor_animation_on_user_motion(&self, position: PhysicalPosition<f64>) {
+        let mut animation = self.cursor_animation.lock().unwrap();
+        if animation.as_ref().is_some_and(|animation| animation.is_user_motion(position)) {
+            *animation = None;
+        }
+    }
+
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError> {
         let mut rectangles: Vec<Rectangle> = Vec::new();
@@ -2000,6 +2257,34 @@ impl UnownedWindow {
         })
     }
 
+    /// The role of the hit-test region under `position`, see `Window::set_hit_test_regions`.
+    pub fn hit_test(&self, position: PhysicalPosition<f64>) -> Option<HitTestRole> {
+        window::hit_test(&self.shared_state_lock().hit_test_regions, position)
+    }
+
+    /// Start the action of a hit-test region that was pressed.
+    ///
+    /// Closing is left to the caller, as it is reported to the application.
+    pub fn apply_hit_test_role(&self, role: HitTestRole) {
+        let result = match role {
+            HitTestRole::Drag => self.drag_window(),
+            HitTestRole::ResizeEdge(direction) => self.drag_resize_window(direction),
+            HitTestRole::Button(TitlebarButton::Minimize) => {
+                self.set_minimized(true);
+                Ok(())
+            },
+            HitTestRole::Button(TitlebarButton::Maximize) => {
+                self.set_maximized(!self.is_maximized());
+                Ok(())
+            },
+            HitTestRole::Button(TitlebarButton::Close) | HitTestRole::Client => Ok(()),
+        };
+
+        if let Err(err) = result {
+            warn!("Failed to start the action of the hit-test region: {err}");
+        }
+    }
+
     /// Initiates a drag operation while the left mouse button is pressed.
     fn drag_initiate(&self, action: isize) -> Result<(), RequestError> {
         let pointer = self
@@ -2050,23 +2335,79 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_ime_cursor_area(&self, spot: Position, size: Size) {
-        let PhysicalPosition { x, y } = spot.to_physical::<i16>(self.scale_factor());
-        let PhysicalSize { width, height } = size.to_physical::<i16>(self.scale_factor());
+        let scale_factor = self.scale_factor();
+        let PhysicalPosition { x, y } = spot.to_physical::<i16>(scale_factor);
+        let PhysicalSize { width, height } = size.to_physical::<i16>(scale_factor);
         // We only currently support reporting a caret position via XIM.
         // No IM servers currently process preedit area information from XIM clients
         // and it is unclear this is even part of the standard protocol.
         // Fcitx and iBus both assume that the position reported is at the insertion
         // caret, and by default will place the candidate window under and to the
         // right of the reported point.
+        //
+        // Since the server doesn't know the area, it can't flip the candidate window above it
+        // when it would be clipped at the bottom of the screen, so we do that here by
+        // reporting a point high enough above the area instead.
+        let bottom = y.saturating_add(height);
+        let candidate_height = (ESTIMATED_CANDIDATE_HEIGHT * scale_factor).round() as i16;
+        let spot_y = match self.ime_work_area() {
+            Some(work_area)
+                if ime::candidate_anchor(
+                    y.into(),
+                    bottom.into(),
+                    work_area,
+                    candidate_height.into(),
+                ) == CandidateAnchor::Above =>
+            {
+                y.saturating_sub(candidate_height)
+            },
+            _ => bottom,
+        };
         let _ = self.ime_sender.lock().unwrap().send(ImeRequest::Position(
             self.xwindow as ffi::Window,
             x.saturating_add(width),
-            y.saturating_add(height),
+            spot_y,
         ));
     }
 
+    /// The vertical extent of the work area of the window's monitor, in surface coordinates.
+    fn ime_work_area(&self) -> Option<(i32, i32)> {
+        let monitor = self.shared_state_lock().last_monitor.clone();
+        let (mut top, mut bottom) = (monitor.rect.top(), monitor.rect.bottom());
+
+        // Send all the requests before waiting for any reply, so this costs a single round-trip.
+        let atoms = self.xconn.atoms();
+        let conn = self.xconn.xcb_connection();
+        let cardinal = xproto::AtomEnum::CARDINAL;
+        let desktop =
+            conn.get_property(false, self.root, atoms[_NET_CURRENT_DESKTOP], cardinal, 0, 1);
+        let work_areas =
+            conn.get_property(false, self.root, atoms[_NET_WORKAREA], cardinal, 0, u32::MAX);
+        let coords = conn.translate_coordinates(self.xwindow, self.root, 0, 0);
+
+        // Exclude panels and docks, `_NET_WORKAREA` has one rectangle per desktop.
+        let desktop = desktop
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| reply.value32()?.next())
+            .unwrap_or(0) as usize;
+        let work_area = work_areas
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| Some(reply.value32()?.skip(desktop * 4).take(4).collect::<Vec<_>>()));
+        if let Some(&[_, y, _, height]) = work_area.as_deref() {
+            top = top.max(y.into());
+            bottom = bottom.min(i64::from(y) + i64::from(height));
+        }
+
+        let coords = coords.ok()?.reply().ok()?;
+        let surface_y = i64::from(coords.dst_y);
+        Some(((top - surface_y) as i32, (bottom - surface_y) as i32))
+    }
+
     #[inline]
     pub fn set_ime_allowed(&self, allowed: bool) {
+        self.shared_state_lock().ime_allowed = allowed;
         let _ = self
             .ime_sender
             .lock()
@@ -2077,6 +2418,16 @@ impl UnownedWindow {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.shared_state_lock().key_repeat_enabled = enabled;
+    }
+
+    #[inline]
+    pub fn key_repeat_enabled(&self) -> bool {
+        self.shared_state_lock().key_repeat_enabled
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         let atoms = self.xconn.atoms();
@@ -2157,6 +2508,18 @@ impl UnownedWindow {
         Ok(serial)
     }
 
+    #[inline]
+    pub fn activate_with_token(
+        &self,
+        token: crate::window::ActivationToken,
+    ) -> Result<(), RequestError> {
+        self.xconn
+            .remove_activation_token(self.xwindow, &token.token)
+            .map_err(|err| RequestError::Os(os_error!(err)))?;
+        self.focus_window();
+        Ok(())
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId::from_raw(self.xwindow as _)
@@ -2168,6 +2531,9 @@ impl UnownedWindow {
 
     #[inline]
     pub fn request_redraw(&self) {
+        if !self.occluded_redraw.lock().unwrap().request() {
+            return;
+        }
         self.redraw_sender.send(WindowId::from_raw(self.xwindow as _));
     }
 
@@ -2232,3 +2598,42 @@ fn cast_size_to_hint(size: Size, scale_factor: f64) -> (i32, i32) {
         Size::Logical(size) => size.to_physical::<i32>(scale_factor).into(),
     }
 }
+
+/// The assumed height of IME candidate windows, in logical pixels, as the IM server doesn't report
+/// it.
+const ESTIMATED_CANDIDATE_HEIGHT: f64 = 200.0;
+
+/// The `_NET_WM_DESKTOP` value of windows shown on all desktops.
+const ALL_DESKTOPS: u32 = 0xffffffff;
+
+/// Get the workspace index from the value of the `_NET_WM_DESKTOP` property.
+fn desktop_from_property(value: &[u32]) -> Option<u32> {
+    value.first().copied().filter(|&desktop| desktop != ALL_DESKTOPS)
+}
+
+/// The data of the `_NET_WM_DESKTOP` client message asking to move a window to `index`.
+fn move_to_desktop_message(index: u32) -> [u32; 5] {
+    // The second field is the source indication, `1` for normal applications.
+    [index, 1, 0, 0, 0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn net_wm_desktop_property() {
+        assert_eq!(desktop_from_property(&[2]), Some(2));
+        assert_eq!(desktop_from_property(&[0]), Some(0));
+        assert_eq!(desktop_from_property(&[ALL_DESKTOPS]), None);
+        // Not set by the window manager (yet).
+        assert_eq!(desktop_from_property(&[]), None);
+    }
+
+    #[test]
+    fn net_wm_desktop_message() {
+        assert_eq!(move_to_desktop_message(3), [3, 1, 0, 0, 0]);
+        let data = xproto::ClientMessageData::from(move_to_desktop_message(3));
+        assert_eq!(data.as_data32(), [3, 1, 0, 0, 0]);
+    }
+}
@@ -16,6 +16,8 @@ use x11rb::xcb_ffi::XCBConnection;
 use super::atoms::Atoms;
 use super::ffi;
 use super::monitor::MonitorHandle;
+use crate::platform_impl::common::xcursor::CursorThemeOverride;
+use crate::platform_impl::common::xkb::LayoutKeys;
 use crate::window::CursorIcon;
 
 /// A connection to an X server.
@@ -62,6 +64,14 @@ pub struct XConnection {
 
     pub latest_error: Mutex<Option<XError>>,
     pub cursor_cache: Mutex<HashMap<Option<CursorIcon>, xproto::Cursor>>,
+    pub themed_cursor_cache: Mutex<HashMap<(CursorThemeOverride, CursorIcon), xproto::Cursor>>,
+
+    /// Name of the active keyboard layout, kept up to date by the event processor.
+    pub keyboard_layout: Mutex<Option<String>>,
+    /// Keys of the active keyboard layout, kept up to date along with its name.
+    pub layout_keys: Mutex<LayoutKeys>,
+    /// The real modifiers AltGr sets in the active keymap, kept up to date along with its keys.
+    pub level_three_mods: Mutex<xproto::ModMask>,
 }
 
 impl HasDisplayHandle for XConnection {
@@ -155,6 +165,10 @@ impl XConnection {
             monitor_handles: Mutex::new(None),
             database: RwLock::new(database),
             cursor_cache: Default::default(),
+            themed_cursor_cache: Default::default(),
+            keyboard_layout: Default::default(),
+            layout_keys: Default::default(),
+            level_three_mods: Mutex::new(xproto::ModMask::M5),
             randr_version: (randr_version.major_version, randr_version.minor_version),
             render_formats: formats,
             xsettings_screen,
@@ -1,10 +1,16 @@
+use std::sync::Arc;
+
+use crate::event_loop::{ActiveEventLoop as CoreActiveEventLoop, CommonEventLoopState};
+use crate::keyboard::{NativeKeyCode, PhysicalKey};
 use crate::monitor::{MonitorHandle as RootMonitorHandle, VideoMode};
-use crate::window::Fullscreen as RootFullscreen;
+use crate::window::{CommonWindowState, Fullscreen as RootFullscreen, Window as CoreWindow};
 
 #[cfg(android_platform)]
 mod android;
 #[cfg(target_vendor = "apple")]
 mod apple;
+#[cfg(headless_platform)]
+pub(crate) mod headless;
 #[cfg(any(x11_platform, wayland_platform))]
 mod linux;
 #[cfg(orbital_platform)]
@@ -29,7 +35,135 @@ use self::web as platform;
 #[cfg(windows_platform)]
 use self::windows as platform;
 
-/// Helper for converting between platform-specific and generic
+/// The [`CommonWindowState`] of a window created by one of the backends.
+pub(crate) fn common_window_state(window: &dyn CoreWindow) -> Option<&Arc<CommonWindowState>> {
+    let window = window.as_any();
+
+    #[cfg(headless_platform)]
+    if let Some(window) = window.downcast_ref::<headless::Window>() {
+        return Some(&window.common_state);
+    }
+
+    #[cfg(x11_platform)]
+    if let Some(window) = window.downcast_ref::<x11::window::Window>() {
+        return Some(&window.common_state);
+    }
+
+    #[cfg(wayland_platform)]
+    if let Some(window) = window.downcast_ref::<wayland::Window>() {
+        return Some(&window.common_state);
+    }
+
+    #[cfg(not(any(x11_platform, wayland_platform)))]
+    if let Some(window) = window.downcast_ref::<Window>() {
+        return Some(&window.common_state);
+    }
+
+    None
+}
+
+/// The [`CommonEventLoopState`] of the event loop of one of the backends.
+pub(crate) fn common_event_loop_state(
+    event_loop: &dyn CoreActiveEventLoop,
+) -> Option<&CommonEventLoopState> {
+    let event_loop = event_loop.as_any();
+
+    #[cfg(headless_platform)]
+    if let Some(event_loop) = event_loop.downcast_ref::<headless::ActiveEventLoop>() {
+        return Some(&event_loop.common_state);
+    }
+
+    #[cfg(x11_platform)]
+    if let Some(event_loop) = event_loop.downcast_ref::<x11::ActiveEventLoop>() {
+        return Some(&event_loop.common_state);
+    }
+
+    #[cfg(wayland_platform)]
+    if let Some(event_loop) = event_loop.downcast_ref::<wayland::ActiveEventLoop>() {
+        return Some(&event_loop.common_state);
+    }
+
+    #[cfg(not(any(x11_platform, wayland_platform)))]
+    if let Some(event_loop) = event_loop.downcast_ref::<ActiveEventLoop>() {
+        return Some(event_loop.common_state());
+    }
+
+    None
+}
+
+/// The [`NativeKeyCode`] the backend reports `physical_key` for, or `None` if the backend doesn't
+/// expose the native code of the keys it identified.
+pub(crate) fn native_key_code(physical_key: PhysicalKey) -> Option<NativeKeyCode> {
+    match physical_key {
+        PhysicalKey::Unidentified(native) => Some(native),
+        #[cfg(any(x11_platform, wayland_platform))]
+        PhysicalKey::Code(_) => physicalkey_to_scancode(physical_key).map(NativeKeyCode::Xkb),
+        #[cfg(windows_platform)]
+        PhysicalKey::Code(_) => physicalkey_to_scancode(physical_key)
+            .map(|scancode| NativeKeyCode::Windows(scancode as u16)),
+        #[cfg(macos_platform)]
+        PhysicalKey::Code(_) => physicalkey_to_scancode(physical_key)
+            .map(|scancode| NativeKeyCode::MacOS(scancode as u16)),
+        #[cfg(android_platform)]
+        PhysicalKey::Code(_) => physicalkey_to_scancode(physical_key).map(NativeKeyCode::Android),
+        #[cfg(not(any(
+            x11_platform,
+            wayland_platform,
+            windows_platform,
+            macos_platform,
+            android_platform
+        )))]
+        PhysicalKey::Code(_) => None,
+    }
+}
+
+/// Redraw bookkeeping for windows built with
+/// [`WindowAttributes::with_redraw_while_occluded(false)`][crate::window::WindowAttributes::with_redraw_while_occluded].
+///
+/// The macOS and X11 backends consult this when a redraw is requested and whenever the occlusion
+/// or minimization state changes.
+#[cfg(any(macos_platform, x11_platform))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OccludedRedraw {
+    suppress: bool,
+    occluded: bool,
+    minimized: bool,
+    pending: bool,
+}
+
+#[cfg(any(macos_platform, x11_platform))]
+impl OccludedRedraw {
+    pub(crate) fn new(redraw_while_occluded: bool) -> Self {
+        Self { suppress: !redraw_while_occluded, occluded: false, minimized: false, pending: false }
+    }
+
+    /// Records a redraw request, returning whether it should be delivered now.
+    pub(crate) fn request(&mut self) -> bool {
+        if self.suppress && (self.occluded || self.minimized) {
+            self.pending = true;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Records an occlusion change, returning whether a held back redraw should be delivered.
+    pub(crate) fn set_occluded(&mut self, occluded: bool) -> bool {
+        self.occluded = occluded;
+        self.take_pending()
+    }
+
+    /// Records a minimization change, returning whether a held back redraw should be delivered.
+    pub(crate) fn set_minimized(&mut self, minimized: bool) -> bool {
+        self.minimized = minimized;
+        self.take_pending()
+    }
+
+    fn take_pending(&mut self) -> bool {
+        !self.occluded && !self.minimized && std::mem::take(&mut self.pending)
+    }
+}
+
 /// [`VideoMode`]/[`MonitorHandle`]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum Fullscreen {
@@ -74,3 +208,41 @@ impl From<Fullscreen> for RootFullscreen {
     not(orbital_platform),
 ))]
 compile_error!("The platform you're compiling for is not supported by winit");
+
+#[cfg(all(test, any(macos_platform, x11_platform)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn occluded_redraw_suppression() {
+        let mut redraw = OccludedRedraw::new(false);
+        assert!(redraw.request());
+
+        assert!(!redraw.set_occluded(true));
+        assert!(!redraw.request());
+        assert!(!redraw.request());
+        // The held back requests are coalesced into a single redraw.
+        assert!(redraw.set_occluded(false));
+        assert!(!redraw.set_occluded(false));
+        assert!(redraw.request());
+
+        // Becoming visible without a pending request doesn't redraw.
+        assert!(!redraw.set_occluded(true));
+        assert!(!redraw.set_occluded(false));
+
+        // Minimizing holds back redraws too, until the window is neither minimized nor occluded.
+        assert!(!redraw.set_minimized(true));
+        assert!(!redraw.request());
+        assert!(!redraw.set_occluded(true));
+        assert!(!redraw.set_minimized(false));
+        assert!(!redraw.request());
+        assert!(redraw.set_occluded(false));
+
+        let mut redraw = OccludedRedraw::new(true);
+        assert!(!redraw.set_minimized(true));
+        assert!(redraw.request());
+        assert!(!redraw.set_occluded(true));
+        assert!(redraw.request());
+        assert!(!redraw.set_occluded(false));
+    }
+}
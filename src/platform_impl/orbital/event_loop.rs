@@ -19,9 +19,10 @@ use crate::application::ApplicationHandler;
 use crate::error::{EventLoopError, NotSupportedError, RequestError};
 use crate::event::{self, Ime, Modifiers, StartCause};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
 };
 use crate::keyboard::{
     Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
@@ -255,10 +256,15 @@ impl EventState {
         pressed_mods
             .set(ModifiersKeys::RCONTROL, self.keyboard.contains(KeyboardModifierState::RCTRL));
 
-        if self.keyboard.intersects(KeyboardModifierState::LALT | KeyboardModifierState::RALT) {
+        if self.keyboard.contains(KeyboardModifierState::LALT) {
             state |= ModifiersState::ALT;
         }
 
+        // The right alt key is always AltGr.
+        if self.keyboard.contains(KeyboardModifierState::RALT) {
+            state |= ModifiersState::ALT_GRAPH;
+        }
+
         pressed_mods.set(ModifiersKeys::LALT, self.keyboard.contains(KeyboardModifierState::LALT));
         pressed_mods.set(ModifiersKeys::RALT, self.keyboard.contains(KeyboardModifierState::RALT));
 
@@ -271,7 +277,7 @@ impl EventState {
         pressed_mods
             .set(ModifiersKeys::RSUPER, self.keyboard.contains(KeyboardModifierState::RSUPER));
 
-        Modifiers { state, pressed_mods }
+        Modifiers { state, pressed_mods, known_mods: ModifiersKeys::all() }
     }
 }
 
@@ -310,6 +316,7 @@ impl EventLoop {
                 destroys: Arc::new(Mutex::new(VecDeque::new())),
                 event_socket,
                 event_loop_proxy: Arc::new(EventLoopProxy { wake_socket, user_events_sender }),
+                common_state: Default::default(),
             },
             user_events_receiver,
         })
@@ -367,15 +374,15 @@ impl EventLoop {
                     device_id: None,
                     event: event::KeyEvent {
                         logical_key,
+                        key_without_modifiers,
                         physical_key,
                         location: KeyLocation::Standard,
                         state: element_state(pressed),
                         repeat: false,
                         text,
-                        platform_specific: KeyEventExtra {
-                            key_without_modifiers,
-                            text_with_all_modifiers,
-                        },
+                        composed: false,
+                        platform_specific: KeyEventExtra { text_with_all_modifiers },
+                        timestamp: None,
                     },
                     is_synthetic: false,
                 };
@@ -409,6 +416,7 @@ impl EventLoop {
                     primary: true,
                     position: (x, y).into(),
                     source: event::PointerSource::Mouse,
+                    timestamp: None,
                 });
             },
             EventOption::MouseRelative(MouseRelativeEvent { dx, dy }) => {
@@ -424,6 +432,7 @@ impl EventLoop {
                         state,
                         position: dpi::PhysicalPosition::default(),
                         button: button.into(),
+                        timestamp: None,
                     });
                 }
             },
@@ -432,6 +441,7 @@ impl EventLoop {
                     device_id: None,
                     delta: event::MouseScrollDelta::LineDelta(x as f32, y as f32),
                     phase: event::TouchPhase::Moved,
+                    momentum_phase: None,
                 });
             },
             EventOption::Quit(QuitEvent {}) => {
@@ -689,6 +699,13 @@ pub struct ActiveEventLoop {
     pub(super) destroys: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) event_socket: Arc<RedoxSocket>,
     pub(super) event_loop_proxy: Arc<EventLoopProxy>,
+    common_state: CommonEventLoopState,
+}
+
+impl ActiveEventLoop {
+    pub(crate) fn common_state(&self) -> &CommonEventLoopState {
+        &self.common_state
+    }
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -700,7 +717,9 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         window_attributes: crate::window::WindowAttributes,
     ) -> Result<Box<dyn CoreWindow>, RequestError> {
-        Ok(Box::new(Window::new(self, window_attributes)?))
+        let window: Box<dyn CoreWindow> = Box::new(Window::new(self, window_attributes)?);
+        self.common_state.register_window(&*window);
+        Ok(window)
     }
 
     fn create_custom_cursor(
@@ -720,6 +739,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        WindowingInfo::new(WindowingBackend::Orbital, None, WindowingCapabilities::all())
+    }
+
     fn primary_monitor(&self) -> Option<crate::monitor::MonitorHandle> {
         Some(crate::monitor::MonitorHandle { inner: MonitorHandle })
     }
@@ -7,7 +7,6 @@ use smol_str::SmolStr;
 pub(crate) use self::event_loop::{ActiveEventLoop, EventLoop};
 pub use self::window::Window;
 use crate::dpi::PhysicalPosition;
-use crate::keyboard::Key;
 use crate::monitor::VideoMode;
 
 mod event_loop;
@@ -151,6 +150,14 @@ impl MonitorHandle {
         1.0 // TODO
     }
 
+    pub fn bit_depth(&self) -> Option<u32> {
+        None
+    }
+
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        None
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         // (it is guaranteed to support 32 bit color though)
         None
@@ -163,6 +170,12 @@ impl MonitorHandle {
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct KeyEventExtra {
-    pub key_without_modifiers: Key,
     pub text_with_all_modifiers: Option<SmolStr>,
 }
+
+impl KeyEventExtra {
+    #[cfg(any(feature = "test-util", feature = "serde"))]
+    pub(crate) fn synthetic(text: Option<&SmolStr>) -> Self {
+        Self { text_with_all_modifiers: text.cloned() }
+    }
+}
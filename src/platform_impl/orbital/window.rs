@@ -1,13 +1,17 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use super::event_loop::EventLoopProxy;
 use super::{ActiveEventLoop, MonitorHandle, RedoxSocket, WindowProperties};
-use crate::cursor::Cursor;
+use crate::cursor::{CurrentCursor, Cursor};
 use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
 use crate::monitor::MonitorHandle as CoreMonitorHandle;
-use crate::window::{self, Fullscreen, ImePurpose, Window as CoreWindow, WindowId};
+use crate::window::{
+    self, CommonWindowState, Fullscreen, HitTestRegion, ImePurpose, Window as CoreWindow, WindowId,
+};
 
 // These values match the values uses in the `window_new` function in orbital:
 // https://gitlab.redox-os.org/redox-os/orbital/-/blob/master/src/scheme.rs
@@ -25,6 +29,9 @@ pub struct Window {
     redraws: Arc<Mutex<VecDeque<WindowId>>>,
     destroys: Arc<Mutex<VecDeque<WindowId>>>,
     event_loop_proxy: Arc<EventLoopProxy>,
+    input_state: Mutex<window::InputStateSnapshot>,
+    current_cursor: CurrentCursor,
+    pub(crate) common_state: Arc<CommonWindowState>,
 }
 
 impl Window {
@@ -32,6 +39,7 @@ impl Window {
         el: &ActiveEventLoop,
         attrs: window::WindowAttributes,
     ) -> Result<Self, RequestError> {
+        let common_state = Arc::new(CommonWindowState::new(&attrs));
         let scale = MonitorHandle.scale_factor();
 
         let (x, y) = if let Some(pos) = attrs.position {
@@ -121,6 +129,9 @@ impl Window {
             redraws: el.redraws.clone(),
             destroys: el.destroys.clone(),
             event_loop_proxy: el.event_loop_proxy.clone(),
+            input_state: Mutex::new(Default::default()),
+            current_cursor: CurrentCursor::default(),
+            common_state,
         })
     }
 
@@ -243,6 +254,10 @@ impl CoreWindow for Window {
         PhysicalInsets::new(0, 0, 0, 0)
     }
 
+    fn surface_transform(&self) -> window::SurfaceTransform {
+        window::SurfaceTransform::Identity
+    }
+
     #[inline]
     fn set_min_surface_size(&self, _: Option<Size>) {}
 
@@ -270,6 +285,10 @@ impl CoreWindow for Window {
     #[inline]
     fn set_blur(&self, _blur: bool) {}
 
+    fn set_tearing_allowed(&self, _allowed: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_tearing_allowed is not supported").into())
+    }
+
     #[inline]
     fn set_visible(&self, visible: bool) {
         let _ = self.set_flag(ORBITAL_FLAG_HIDDEN, !visible);
@@ -316,6 +335,14 @@ impl CoreWindow for Window {
         self.get_flag(ORBITAL_FLAG_MAXIMIZED).unwrap_or(false)
     }
 
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("move_to_workspace is not supported").into())
+    }
+
     fn set_fullscreen(&self, _monitor: Option<Fullscreen>) {}
 
     fn fullscreen(&self) -> Option<Fullscreen> {
@@ -360,6 +387,33 @@ impl CoreWindow for Window {
     #[inline]
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    fn set_key_repeat_enabled(&self, _enabled: bool) {}
+
+    #[inline]
+    fn keyboard_layout_name(&self) -> Option<String> {
+        None
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        None
+    }
+
+    fn key_for_physical(&self, _key: PhysicalKey) -> Option<Key> {
+        None
+    }
+
+    fn compose_dead_key(&self, _dead: char, _base: char) -> Option<char> {
+        None
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        ModifiersState::empty()
+    }
+
+    #[inline]
+    fn set_hit_test_regions(&self, _regions: &[HitTestRegion]) {}
+
     #[inline]
     fn focus_window(&self) {}
 
@@ -367,13 +421,24 @@ impl CoreWindow for Window {
     fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
     #[inline]
-    fn set_cursor(&self, _: Cursor) {}
+    fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
+    }
+
+    #[inline]
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
 
     #[inline]
     fn set_cursor_position(&self, _: Position) -> Result<(), RequestError> {
         Err(NotSupportedError::new("set_cursor_position is not supported").into())
     }
 
+    fn animate_cursor_to(&self, _: PhysicalPosition<i32>, _: Duration) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("animate_cursor_to is not supported").into())
+    }
+
     #[inline]
     fn set_cursor_grab(&self, mode: window::CursorGrabMode) -> Result<(), RequestError> {
         let (grab, relative) = match mode {
@@ -387,12 +452,25 @@ impl CoreWindow for Window {
         self.window_socket
             .write(format!("M,R,{}", if relative { 1 } else { 0 }).as_bytes())
             .map_err(|err| os_error!(format!("{err}")))?;
+        self.input_state.lock().unwrap().cursor_grab = mode;
         Ok(())
     }
 
     #[inline]
     fn set_cursor_visible(&self, visible: bool) {
-        let _ = self.window_socket.write(format!("M,C,{}", if visible { 1 } else { 0 }).as_bytes());
+        if self
+            .window_socket
+            .write(format!("M,C,{}", if visible { 1 } else { 0 }).as_bytes())
+            .is_ok()
+        {
+            self.input_state.lock().unwrap().cursor_visible = visible;
+        }
+    }
+
+    #[inline]
+    fn capture_input_state(&self) -> window::InputStateSnapshot {
+        let cursor = self.current_cursor.get();
+        window::InputStateSnapshot { cursor, ..self.input_state.lock().unwrap().clone() }
     }
 
     #[inline]
@@ -301,6 +301,10 @@ impl CursorHandler {
         }
     }
 
+    pub fn is_cursor_visible(&self) -> bool {
+        self.0.borrow().visible
+    }
+
     pub fn set_cursor_visible(&self, visible: bool) {
         let mut this = self.0.borrow_mut();
 
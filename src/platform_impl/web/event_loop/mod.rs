@@ -9,7 +9,7 @@ pub(crate) mod runner;
 mod state;
 mod window_target;
 
-pub(crate) use window_target::ActiveEventLoop;
+pub(crate) use window_target::{ActiveEventLoop, ModifiersShared};
 
 pub struct EventLoop {
     elw: ActiveEventLoop,
@@ -397,6 +397,7 @@ impl Shared {
                     event: DeviceEvent::Key(RawKeyEvent {
                         physical_key: backend::event::key_code(&event),
                         state: ElementState::Pressed,
+                        repeat: event.repeat(),
                     }),
                 });
             }),
@@ -415,6 +416,7 @@ impl Shared {
                     event: DeviceEvent::Key(RawKeyEvent {
                         physical_key: backend::event::key_code(&event),
                         state: ElementState::Released,
+                        repeat: false,
                     }),
                 });
             }),
@@ -12,10 +12,11 @@ use super::runner::Event;
 use super::{backend, runner};
 use crate::application::ApplicationHandler;
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{ElementState, KeyEvent, TouchPhase, WindowEvent};
+use crate::event::{DeadKeyComposition, ElementState, KeyEvent, TouchPhase, WindowEvent};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
     EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    WindowingBackend, WindowingCapabilities, WindowingInfo,
 };
 use crate::keyboard::ModifiersState;
 use crate::monitor::MonitorHandle as RootMonitorHandle;
@@ -25,15 +26,16 @@ use crate::platform_impl::web::event_loop::proxy::EventLoopProxy;
 use crate::platform_impl::Window;
 use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource, Theme, WindowId};
 
+/// The modifiers of the last input event, shared by the windows.
 #[derive(Default)]
-struct ModifiersShared(Rc<Cell<ModifiersState>>);
+pub(crate) struct ModifiersShared(Rc<Cell<ModifiersState>>);
 
 impl ModifiersShared {
     fn set(&self, new: ModifiersState) {
         self.0.set(new)
     }
 
-    fn get(&self) -> ModifiersState {
+    pub(crate) fn get(&self) -> ModifiersState {
         self.0.get()
     }
 }
@@ -48,11 +50,24 @@ impl Clone for ModifiersShared {
 pub struct ActiveEventLoop {
     pub(crate) runner: runner::Shared,
     modifiers: ModifiersShared,
+    common_state: CommonEventLoopState,
 }
 
 impl ActiveEventLoop {
     pub fn new() -> Self {
-        Self { runner: runner::Shared::new(), modifiers: ModifiersShared::default() }
+        Self {
+            runner: runner::Shared::new(),
+            modifiers: ModifiersShared::default(),
+            common_state: CommonEventLoopState::default(),
+        }
+    }
+
+    pub(crate) fn common_state(&self) -> &CommonEventLoopState {
+        &self.common_state
+    }
+
+    pub(crate) fn modifiers(&self) -> &ModifiersShared {
+        &self.modifiers
     }
 
     pub(crate) fn run(&self, app: Box<dyn ApplicationHandler>, event_loop_recreation: bool) {
@@ -123,8 +138,14 @@ impl ActiveEventLoop {
 
         let runner = self.runner.clone();
         let modifiers = self.modifiers.clone();
+        let key_repeat_enabled = canvas.key_repeat_enabled.clone();
+        let mut dead_keys = DeadKeyComposition::default();
         canvas.on_keyboard_press(
             move |physical_key, logical_key, text, location, repeat, active_modifiers| {
+                if repeat && !key_repeat_enabled.get() {
+                    return;
+                }
+
                 let modifiers_changed = (modifiers.get() != active_modifiers).then(|| {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
@@ -133,6 +154,7 @@ impl ActiveEventLoop {
                     }
                 });
 
+                let composed = dead_keys.feed(ElementState::Pressed, &logical_key, text.is_some());
                 runner.send_events(
                     iter::once(Event::WindowEvent {
                         window_id,
@@ -140,12 +162,15 @@ impl ActiveEventLoop {
                             device_id: None,
                             event: KeyEvent {
                                 physical_key,
+                                key_without_modifiers: logical_key.clone(),
                                 logical_key,
                                 text,
+                                composed,
                                 location,
                                 state: ElementState::Pressed,
                                 repeat,
                                 platform_specific: KeyEventExtra,
+                                timestamp: None,
                             },
                             is_synthetic: false,
                         },
@@ -174,12 +199,15 @@ impl ActiveEventLoop {
                             device_id: None,
                             event: KeyEvent {
                                 physical_key,
+                                key_without_modifiers: logical_key.clone(),
                                 logical_key,
                                 text,
+                                composed: false,
                                 location,
                                 state: ElementState::Released,
                                 repeat,
                                 platform_specific: KeyEventExtra,
+                                timestamp: None,
                             },
                             is_synthetic: false,
                         },
@@ -265,6 +293,7 @@ impl ActiveEventLoop {
                                     primary,
                                     position,
                                     source,
+                                    timestamp: None,
                                 },
                             }))
                         },
@@ -294,6 +323,7 @@ impl ActiveEventLoop {
                             state,
                             position,
                             button,
+                            timestamp: None,
                         },
                     }]));
                 }
@@ -321,6 +351,7 @@ impl ActiveEventLoop {
                         state: ElementState::Pressed,
                         position,
                         button,
+                        timestamp: None,
                     },
                 })));
             }
@@ -349,6 +380,7 @@ impl ActiveEventLoop {
                         state: ElementState::Released,
                         position,
                         button,
+                        timestamp: None,
                     },
                 })));
             }
@@ -373,6 +405,7 @@ impl ActiveEventLoop {
                         device_id: None,
                         delta,
                         phase: TouchPhase::Moved,
+                        momentum_phase: None,
                     },
                 },
             )));
@@ -489,8 +522,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         window_attributes: crate::window::WindowAttributes,
     ) -> Result<Box<dyn crate::window::Window>, RequestError> {
-        let window = Window::new(self, window_attributes)?;
-        Ok(Box::new(window))
+        let window: Box<dyn crate::window::Window> =
+            Box::new(Window::new(self, window_attributes)?);
+        self.common_state.register_window(&*window);
+        Ok(window)
     }
 
     fn create_custom_cursor(
@@ -528,6 +563,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         })
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        WindowingInfo::new(WindowingBackend::Web, None, WindowingCapabilities::empty())
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.runner.set_control_flow(control_flow)
     }
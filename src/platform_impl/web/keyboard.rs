@@ -5,6 +5,13 @@ use crate::keyboard::{Key, KeyCode, NamedKey, NativeKey, NativeKeyCode, Physical
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub(crate) struct KeyEventExtra;
 
+impl KeyEventExtra {
+    #[cfg(any(feature = "test-util", feature = "serde"))]
+    pub(crate) fn synthetic(_: Option<&SmolStr>) -> Self {
+        Self
+    }
+}
+
 impl Key {
     pub(crate) fn from_key_attribute_value(kav: &str) -> Self {
         Key::Named(match kav {
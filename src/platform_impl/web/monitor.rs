@@ -58,6 +58,14 @@ impl MonitorHandle {
         self.inner.queue(|inner| inner.name())
     }
 
+    pub fn bit_depth(&self) -> Option<u32> {
+        None
+    }
+
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        None
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         Some(VideoMode {
             size: self.inner.queue(|inner| inner.size()),
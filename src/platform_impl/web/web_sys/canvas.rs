@@ -37,6 +37,7 @@ pub struct Canvas {
     id: WindowId,
     pub has_focus: Rc<Cell<bool>>,
     pub prevent_default: Rc<Cell<bool>>,
+    pub key_repeat_enabled: Rc<Cell<bool>>,
     pub is_intersecting: Cell<Option<bool>>,
     pub cursor: CursorHandler,
     handlers: RefCell<Handlers>,
@@ -168,6 +169,7 @@ impl Canvas {
             id,
             has_focus: Rc::new(Cell::new(false)),
             prevent_default: Rc::new(Cell::new(attr.platform_specific.prevent_default)),
+            key_repeat_enabled: Rc::new(Cell::new(true)),
             is_intersecting: Cell::new(None),
             cursor,
             handlers: RefCell::new(Handlers {
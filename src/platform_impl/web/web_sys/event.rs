@@ -8,7 +8,7 @@ use web_sys::{KeyboardEvent, MouseEvent, Navigator, PointerEvent, WheelEvent};
 
 use super::Engine;
 use crate::event::{FingerId, MouseButton, MouseScrollDelta, PointerKind};
-use crate::keyboard::{Key, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
+use crate::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
 
 bitflags::bitflags! {
     // https://www.w3.org/TR/pointerevents3/#the-buttons-property
@@ -180,14 +180,7 @@ pub fn key(event: &KeyboardEvent) -> Key {
 pub fn key_text(event: &KeyboardEvent) -> Option<SmolStr> {
     let key = event.key();
     let key = Key::from_key_attribute_value(&key);
-    match &key {
-        Key::Character(text) => Some(text.clone()),
-        Key::Named(NamedKey::Tab) => Some(SmolStr::new("\t")),
-        Key::Named(NamedKey::Enter) => Some(SmolStr::new("\r")),
-        Key::Named(NamedKey::Space) => Some(SmolStr::new(" ")),
-        _ => None,
-    }
-    .map(SmolStr::new)
+    key.is_printable().then(|| key.to_text().map(SmolStr::new)).flatten()
 }
 
 pub fn key_location(event: &KeyboardEvent) -> KeyLocation {
@@ -218,6 +211,18 @@ pub fn keyboard_modifiers(event: &KeyboardEvent) -> ModifiersState {
     if event.meta_key() {
         state |= ModifiersState::SUPER;
     }
+    if event.get_modifier_state("CapsLock") {
+        state |= ModifiersState::CAPS_LOCK;
+    }
+    if event.get_modifier_state("NumLock") {
+        state |= ModifiersState::NUM_LOCK;
+    }
+    if event.get_modifier_state("ScrollLock") {
+        state |= ModifiersState::SCROLL_LOCK;
+    }
+    if event.get_modifier_state("AltGraph") {
+        with_alt_graph(&mut state);
+    }
 
     state
 }
@@ -237,10 +242,31 @@ pub fn mouse_modifiers(event: &MouseEvent) -> ModifiersState {
     if event.meta_key() {
         state |= ModifiersState::SUPER;
     }
+    if event.get_modifier_state("CapsLock") {
+        state |= ModifiersState::CAPS_LOCK;
+    }
+    if event.get_modifier_state("NumLock") {
+        state |= ModifiersState::NUM_LOCK;
+    }
+    if event.get_modifier_state("ScrollLock") {
+        state |= ModifiersState::SCROLL_LOCK;
+    }
+    if event.get_modifier_state("AltGraph") {
+        with_alt_graph(&mut state);
+    }
 
     state
 }
 
+/// Browsers on Windows report AltGr as Ctrl+Alt in addition to the `AltGraph` modifier.
+fn with_alt_graph(state: &mut ModifiersState) {
+    state.insert(ModifiersState::ALT_GRAPH);
+    let ctrl_alt = ModifiersState::CONTROL | ModifiersState::ALT;
+    if state.contains(ctrl_alt) {
+        state.remove(ctrl_alt);
+    }
+}
+
 pub fn pointer_move_event(event: PointerEvent) -> impl Iterator<Item = PointerEvent> {
     // make a single iterator depending on the availability of coalesced events
     if has_coalesced_events_support(&event) {
@@ -1,26 +1,32 @@
 use std::cell::Ref;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
 use dpi::{LogicalPosition, LogicalSize};
-use web_sys::HtmlCanvasElement;
+use web_sys::{Element, HtmlCanvasElement};
 
+use super::event_loop::ModifiersShared;
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::monitor::MonitorHandler;
 use super::r#async::Dispatcher;
 use super::{backend, lock, ActiveEventLoop};
+use crate::cursor::CurrentCursor;
 use crate::dpi::{LogicalInsets, PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
 use crate::icon::Icon;
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::window::{
-    Cursor, CursorGrabMode, Fullscreen as RootFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as RootWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    CommonWindowState, Cursor, CursorGrabMode, Fullscreen as RootFullscreen, HitTestRegion,
+    ImePurpose, InputStateSnapshot, ResizeDirection, SurfaceTransform, Theme, UserAttentionType,
+    Window as RootWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 pub struct Window {
     inner: Dispatcher<Inner>,
+    current_cursor: CurrentCursor,
+    pub(crate) common_state: Arc<CommonWindowState>,
 }
 
 pub struct Inner {
@@ -28,6 +34,7 @@ pub struct Inner {
     pub window: web_sys::Window,
     monitor: Rc<MonitorHandler>,
     safe_area: Rc<backend::SafeAreaHandle>,
+    modifiers: ModifiersShared,
     canvas: Rc<backend::Canvas>,
     destroy_fn: Option<Box<dyn FnOnce()>>,
 }
@@ -38,6 +45,7 @@ impl Window {
         attr: WindowAttributes,
     ) -> Result<Self, RequestError> {
         let id = target.generate_id();
+        let common_state = Arc::new(CommonWindowState::new(&attr));
 
         let window = target.runner.window();
         let navigator = target.runner.navigator();
@@ -62,6 +70,7 @@ impl Window {
             window: window.clone(),
             monitor: Rc::clone(target.runner.monitor()),
             safe_area: Rc::clone(target.runner.safe_area()),
+            modifiers: target.modifiers().clone(),
             canvas,
             destroy_fn: Some(destroy_fn),
         };
@@ -70,7 +79,7 @@ impl Window {
         let (dispatcher, runner) = Dispatcher::new(target.runner.main_thread(), inner);
         target.runner.add_canvas(id, canvas, runner);
 
-        Ok(Window { inner: dispatcher })
+        Ok(Window { inner: dispatcher, current_cursor: CurrentCursor::default(), common_state })
     }
 
     pub fn canvas(&self) -> Option<Ref<'_, HtmlCanvasElement>> {
@@ -183,6 +192,10 @@ impl RootWindow for Window {
         })
     }
 
+    fn surface_transform(&self) -> SurfaceTransform {
+        SurfaceTransform::Identity
+    }
+
     fn set_min_surface_size(&self, min_size: Option<Size>) {
         self.inner.dispatch(move |inner| {
             let dimensions = min_size.map(|min_size| min_size.to_logical(inner.scale_factor()));
@@ -223,6 +236,10 @@ impl RootWindow for Window {
 
     fn set_blur(&self, _: bool) {}
 
+    fn set_tearing_allowed(&self, _allowed: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_tearing_allowed is not supported").into())
+    }
+
     fn set_visible(&self, _: bool) {
         // Intentionally a no-op
     }
@@ -263,6 +280,14 @@ impl RootWindow for Window {
         false
     }
 
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("move_to_workspace is not supported").into())
+    }
+
     fn set_fullscreen(&self, fullscreen: Option<RootFullscreen>) {
         self.inner.dispatch(move |inner| {
             if let Some(fullscreen) = fullscreen {
@@ -311,6 +336,40 @@ impl RootWindow for Window {
         // Currently not implemented
     }
 
+    fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.inner.dispatch(move |inner| inner.canvas.key_repeat_enabled.set(enabled))
+    }
+
+    fn keyboard_layout_name(&self) -> Option<String> {
+        None
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        None
+    }
+
+    fn key_for_physical(&self, _key: PhysicalKey) -> Option<Key> {
+        None
+    }
+
+    fn compose_dead_key(&self, _dead: char, _base: char) -> Option<char> {
+        None
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        self.inner.queue(|inner| {
+            if inner.canvas.has_focus.get() {
+                inner.modifiers.get()
+            } else {
+                ModifiersState::empty()
+            }
+        })
+    }
+
+    fn set_hit_test_regions(&self, _: &[HitTestRegion]) {
+        // Intentionally a no-op, there are no client-side decorations
+    }
+
     fn focus_window(&self) {
         self.inner.dispatch(|inner| {
             let _ = inner.canvas.raw().focus();
@@ -346,13 +405,22 @@ impl RootWindow for Window {
     }
 
     fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
         self.inner.dispatch(move |inner| inner.canvas.cursor.set_cursor(cursor))
     }
 
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
+
     fn set_cursor_position(&self, _: Position) -> Result<(), RequestError> {
         Err(NotSupportedError::new("set_cursor_position is not supported").into())
     }
 
+    fn animate_cursor_to(&self, _: PhysicalPosition<i32>, _: Duration) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("animate_cursor_to is not supported").into())
+    }
+
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
         Ok(self.inner.queue(|inner| {
             match mode {
@@ -375,6 +443,21 @@ impl RootWindow for Window {
         self.inner.dispatch(move |inner| inner.canvas.cursor.set_cursor_visible(visible))
     }
 
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        let cursor = self.current_cursor.get();
+        self.inner.queue(|inner| {
+            let canvas: &Element = inner.canvas.raw();
+            let locked = inner.canvas.document().pointer_lock_element().as_ref() == Some(canvas);
+
+            InputStateSnapshot {
+                cursor_grab: if locked { CursorGrabMode::Locked } else { CursorGrabMode::None },
+                cursor_visible: inner.canvas.cursor.is_cursor_visible(),
+                cursor,
+                ime_allowed: false,
+            }
+        })
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         Err(NotSupportedError::new("drag_window is not supported").into())
     }
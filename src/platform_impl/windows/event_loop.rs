@@ -2,7 +2,7 @@
 
 mod runner;
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ffi::c_void;
 use std::os::windows::io::{AsRawHandle as _, FromRawHandle as _, OwnedHandle, RawHandle};
 use std::rc::Rc;
@@ -16,8 +16,9 @@ use windows_sys::Win32::Foundation::{
     GetLastError, FALSE, HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, WAIT_FAILED, WPARAM,
 };
 use windows_sys::Win32::Graphics::Gdi::{
-    GetMonitorInfoW, MonitorFromRect, MonitorFromWindow, RedrawWindow, ScreenToClient,
-    ValidateRect, MONITORINFO, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT, SC_SCREENSAVE,
+    ClientToScreen, GetMonitorInfoW, MonitorFromRect, MonitorFromWindow, RedrawWindow,
+    ScreenToClient, ValidateRect, MONITORINFO, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT,
+    SC_SCREENSAVE,
 };
 use windows_sys::Win32::System::Ole::RevokeDragDrop;
 use windows_sys::Win32::System::Threading::{
@@ -41,23 +42,24 @@ use windows_sys::Win32::UI::Input::{
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetClientRect, GetCursorPos,
-    GetMenu, LoadCursorW, MsgWaitForMultipleObjectsEx, PeekMessageW, PostMessageW,
-    RegisterClassExW, RegisterWindowMessageA, SetCursor, SetWindowPos, TranslateMessage,
-    CREATESTRUCTW, GWL_STYLE, GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO, MNC_CLOSE, MSG,
-    MWMO_INPUTAVAILABLE, NCCALCSIZE_PARAMS, PM_REMOVE, PT_TOUCH, QS_ALLINPUT, RI_MOUSE_HWHEEL,
-    RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SWP_NOACTIVATE, SWP_NOMOVE,
-    SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT,
-    WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT,
-    WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE,
-    WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
-    WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS,
-    WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL,
-    WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY,
-    WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_RBUTTONDOWN,
-    WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SIZING, WM_SYSCOMMAND,
-    WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING,
-    WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
-    WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP, WS_VISIBLE,
+    GetMenu, KillTimer, LoadCursorW, MsgWaitForMultipleObjectsEx, PeekMessageW, PostMessageW,
+    RegisterClassExW, RegisterWindowMessageA, SetCursor, SetCursorPos, SetWindowPos,
+    TranslateMessage, CREATESTRUCTW, GWL_STYLE, GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO,
+    MNC_CLOSE, MSG, MWMO_INPUTAVAILABLE, NCCALCSIZE_PARAMS, PM_REMOVE, PT_TOUCH, QS_ALLINPUT,
+    RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SIZE_MINIMIZED,
+    SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM,
+    WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT,
+    WMSZ_TOPRIGHT, WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED,
+    WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
+    WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_INPUTLANGCHANGE, WM_KEYDOWN, WM_KEYUP,
+    WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR,
+    WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE,
+    WM_NCDESTROY, WM_NCHITTEST, WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP,
+    WM_POINTERUPDATE, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE,
+    WM_SIZE, WM_SIZING, WM_SYSCOMMAND, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TIMER, WM_TOUCH,
+    WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING, WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW,
+    WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP,
+    WS_VISIBLE,
 };
 
 use super::window::set_skip_taskbar;
@@ -66,14 +68,15 @@ use crate::application::ApplicationHandler;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::error::{EventLoopError, RequestError};
 use crate::event::{
-    Event, FingerId, Force, Ime, RawKeyEvent, SurfaceSizeWriter, TouchPhase, WindowEvent,
+    Event, FingerId, Force, Ime, Modifiers, RawKeyEvent, RawKeyRepeat, SurfaceSizeWriter,
+    TouchPhase, WindowEvent,
 };
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, CommonEventLoopState, ControlFlow, DeviceEvents,
     EventLoopProxy as RootEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, WindowingBackend, WindowingCapabilities,
+    WindowingInfo,
 };
-use crate::keyboard::ModifiersState;
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::pump_events::PumpStatus;
 use crate::platform_impl::platform::dark_mode::try_theme;
@@ -82,7 +85,7 @@ use crate::platform_impl::platform::drop_handler::FileDropHandler;
 use crate::platform_impl::platform::icon::WinCursor;
 use crate::platform_impl::platform::ime::ImeContext;
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
-use crate::platform_impl::platform::keyboard_layout::LAYOUT_CACHE;
+use crate::platform_impl::platform::keyboard_layout::{self, LAYOUT_CACHE};
 use crate::platform_impl::platform::monitor::{self, MonitorHandle};
 use crate::platform_impl::platform::window::InitData;
 use crate::platform_impl::platform::window_state::{
@@ -92,8 +95,8 @@ use crate::platform_impl::platform::{raw_input, util, wrap_device_id, Fullscreen
 use crate::platform_impl::Window;
 use crate::utils::Lazy;
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, Window as CoreWindow,
-    WindowAttributes, WindowId,
+    CustomCursor as RootCustomCursor, CustomCursorSource, HitTestRole, ResizeDirection, Theme,
+    TitlebarButton, Window as CoreWindow, WindowAttributes, WindowId,
 };
 
 pub(crate) struct WindowData {
@@ -117,6 +120,7 @@ impl WindowData {
 
 struct ThreadMsgTargetData {
     event_loop_runner: Rc<EventLoopRunner>,
+    raw_key_repeat: RefCell<RawKeyRepeat>,
 }
 
 impl ThreadMsgTargetData {
@@ -179,6 +183,7 @@ pub struct ActiveEventLoop {
     thread_id: u32,
     thread_msg_target: HWND,
     pub(crate) runner_shared: Rc<EventLoopRunner>,
+    common_state: CommonEventLoopState,
 }
 
 impl EventLoop {
@@ -211,7 +216,12 @@ impl EventLoop {
         );
 
         Ok(EventLoop {
-            window_target: ActiveEventLoop { thread_id, thread_msg_target, runner_shared },
+            window_target: ActiveEventLoop {
+                thread_id,
+                thread_msg_target,
+                runner_shared,
+                common_state: Default::default(),
+            },
             msg_hook: attributes.msg_hook.take(),
             high_resolution_timer: None,
         })
@@ -447,6 +457,10 @@ impl Drop for EventLoop {
 }
 
 impl ActiveEventLoop {
+    pub(crate) fn common_state(&self) -> &CommonEventLoopState {
+        &self.common_state
+    }
+
     #[inline(always)]
     pub(crate) fn create_thread_executor(&self) -> EventLoopThreadExecutor {
         EventLoopThreadExecutor { thread_id: self.thread_id, target_window: self.thread_msg_target }
@@ -471,7 +485,9 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         window_attributes: WindowAttributes,
     ) -> Result<Box<dyn CoreWindow>, RequestError> {
-        Ok(Box::new(Window::new(self, window_attributes)?))
+        let window: Box<dyn CoreWindow> = Box::new(Window::new(self, window_attributes)?);
+        self.common_state.register_window(&*window);
+        Ok(window)
     }
 
     fn create_custom_cursor(
@@ -501,6 +517,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         Some(if super::dark_mode::should_use_dark_mode() { Theme::Dark } else { Theme::Light })
     }
 
+    fn windowing_info(&self) -> WindowingInfo {
+        WindowingInfo::new(WindowingBackend::Windows, None, WindowingCapabilities::all())
+    }
+
     fn listen_device_events(&self, allowed: DeviceEvents) {
         raw_input::register_all_mice_and_keyboards_for_raw_input(self.thread_msg_target, allowed);
     }
@@ -880,6 +900,8 @@ pub(crate) static DESTROY_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::Des
 // documentation in the `window_state` module for more information.
 pub(crate) static SET_RETAIN_STATE_ON_SIZE_MSG_ID: LazyMessageId =
     LazyMessageId::new("Winit::SetRetainMaximized\0");
+// Identifier of the `WM_TIMER` that steps the animation started by `Window::animate_cursor_to`.
+pub(crate) const CURSOR_ANIMATION_TIMER_ID: usize = 1;
 static THREAD_EVENT_TARGET_WINDOW_CLASS: Lazy<Vec<u16>> =
     Lazy::new(|| util::encode_wide("Winit Thread Event Target"));
 /// When the taskbar is created, it registers a message with the "TaskbarCreated" string and then
@@ -946,7 +968,7 @@ fn insert_event_target_window_data(
     thread_msg_target: HWND,
     event_loop_runner: Rc<EventLoopRunner>,
 ) {
-    let userdata = ThreadMsgTargetData { event_loop_runner };
+    let userdata = ThreadMsgTargetData { event_loop_runner, raw_key_repeat: Default::default() };
     let input_ptr = Box::into_raw(Box::new(userdata));
 
     unsafe { super::set_window_long(thread_msg_target, GWL_USERDATA, input_ptr as isize) };
@@ -984,7 +1006,7 @@ fn update_modifiers(window: HWND, userdata: &WindowData) {
 
     let modifiers = {
         let mut layouts = LAYOUT_CACHE.lock().unwrap();
-        layouts.get_agnostic_mods()
+        layouts.get_mods()
     };
 
     let mut window_state = userdata.window_state.lock().unwrap();
@@ -996,11 +1018,37 @@ fn update_modifiers(window: HWND, userdata: &WindowData) {
 
         userdata.send_event(Event::WindowEvent {
             window_id: WindowId::from_raw(window as usize),
-            event: ModifiersChanged(modifiers.into()),
+            event: ModifiersChanged(modifiers),
         });
     }
 }
 
+/// The `WM_NCHITTEST` result matching the role of a hit-test region.
+fn hit_test_role_to_ht(role: HitTestRole) -> u32 {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCLOSE, HTLEFT, HTMAXBUTTON, HTMINBUTTON, HTRIGHT,
+        HTTOP, HTTOPLEFT, HTTOPRIGHT,
+    };
+
+    match role {
+        HitTestRole::Client => HTCLIENT,
+        HitTestRole::Drag => HTCAPTION,
+        HitTestRole::ResizeEdge(direction) => match direction {
+            ResizeDirection::East => HTRIGHT,
+            ResizeDirection::North => HTTOP,
+            ResizeDirection::NorthEast => HTTOPRIGHT,
+            ResizeDirection::NorthWest => HTTOPLEFT,
+            ResizeDirection::South => HTBOTTOM,
+            ResizeDirection::SouthEast => HTBOTTOMRIGHT,
+            ResizeDirection::SouthWest => HTBOTTOMLEFT,
+            ResizeDirection::West => HTLEFT,
+        },
+        HitTestRole::Button(TitlebarButton::Close) => HTCLOSE,
+        HitTestRole::Button(TitlebarButton::Minimize) => HTMINBUTTON,
+        HitTestRole::Button(TitlebarButton::Maximize) => HTMAXBUTTON,
+    }
+}
+
 unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
     use crate::event::WindowEvent::Focused;
 
@@ -1015,10 +1063,10 @@ unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
 unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
     use crate::event::WindowEvent::{Focused, ModifiersChanged};
 
-    userdata.window_state_lock().modifiers_state = ModifiersState::empty();
+    userdata.window_state_lock().modifiers_state = Modifiers::default();
     userdata.send_event(Event::WindowEvent {
         window_id: WindowId::from_raw(window as usize),
-        event: ModifiersChanged(ModifiersState::empty().into()),
+        event: ModifiersChanged(Modifiers::default()),
     });
 
     userdata.send_event(Event::WindowEvent {
@@ -1118,7 +1166,14 @@ unsafe fn public_window_callback_inner(
         use crate::event::WindowEvent::KeyboardInput;
         let events =
             userdata.key_event_builder.process_message(window, msg, wparam, lparam, &mut result);
+        let key_repeat_enabled = userdata.window_state_lock().key_repeat_enabled;
         for event in events {
+            // Repeats are reported through bit 30 of `WM_KEYDOWN`, drop them at the source.
+            if event.event.repeat && !key_repeat_enabled {
+                continue;
+            }
+            event.event.debug_check_text();
+
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
                 event: KeyboardInput {
@@ -1354,12 +1409,10 @@ unsafe fn public_window_callback_inner(
             let h = super::hiword(lparam as u32) as u32;
 
             let physical_size = PhysicalSize::new(w, h);
-            let event = Event::WindowEvent {
-                window_id: WindowId::from_raw(window as usize),
-                event: SurfaceResized(physical_size),
-            };
+            // The client area of a minimized window is empty, don't report that.
+            let minimized = wparam == SIZE_MINIMIZED as usize;
 
-            {
+            let surface_size = {
                 let mut w = userdata.window_state_lock();
                 // See WindowFlags::MARKER_RETAIN_STATE_ON_SIZE docs for info on why this `if` check
                 // exists.
@@ -1367,8 +1420,14 @@ unsafe fn public_window_callback_inner(
                     let maximized = wparam == SIZE_MAXIMIZED as usize;
                     w.set_window_flags_in_place(|f| f.set(WindowFlags::MAXIMIZED, maximized));
                 }
+                w.last_surface_size.update(physical_size, minimized)
+            };
+            if let Some(surface_size) = surface_size {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: WindowId::from_raw(window as usize),
+                    event: SurfaceResized(surface_size),
+                });
             }
-            userdata.send_event(event);
             result = ProcResult::Value(0);
         },
 
@@ -1660,6 +1719,11 @@ unsafe fn public_window_callback_inner(
                 let mut w = userdata.window_state_lock();
                 cursor_moved = w.mouse.last_position != Some(position);
                 w.mouse.last_position = Some(position);
+
+                if w.cursor_animation.as_ref().is_some_and(|a| a.is_user_motion(position)) {
+                    w.cursor_animation = None;
+                    unsafe { KillTimer(window, CURSOR_ANIMATION_TIMER_ID) };
+                }
             }
 
             if cursor_moved {
@@ -1672,6 +1736,7 @@ unsafe fn public_window_callback_inner(
                         primary: true,
                         position,
                         source: PointerSource::Mouse,
+                        timestamp: util::message_timestamp(),
                     },
                 });
             }
@@ -1679,6 +1744,27 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_TIMER if wparam == CURSOR_ANIMATION_TIMER_ID => {
+            let now = Instant::now();
+            let mut w = userdata.window_state_lock();
+            let position = w.cursor_animation.as_mut().map(|animation| animation.step(now));
+            if w.cursor_animation.as_ref().map_or(true, |animation| animation.is_finished(now)) {
+                w.cursor_animation = None;
+                unsafe { KillTimer(window, CURSOR_ANIMATION_TIMER_ID) };
+            }
+            drop(w);
+
+            if let Some(position) = position {
+                let mut point = POINT { x: position.x, y: position.y };
+                unsafe {
+                    ClientToScreen(window, &mut point);
+                    SetCursorPos(point.x, point.y);
+                }
+            }
+
+            result = ProcResult::Value(0);
+        },
+
         WM_MOUSELEAVE => {
             use crate::event::PointerKind::Mouse;
             use crate::event::WindowEvent::PointerLeft;
@@ -1710,6 +1796,7 @@ unsafe fn public_window_callback_inner(
                     device_id: None,
                     delta: LineDelta(0.0, value),
                     phase: TouchPhase::Moved,
+                    momentum_phase: None,
                 },
             });
 
@@ -1730,6 +1817,7 @@ unsafe fn public_window_callback_inner(
                     device_id: None,
                     delta: LineDelta(value, 0.0),
                     phase: TouchPhase::Moved,
+                    momentum_phase: None,
                 },
             });
 
@@ -1771,6 +1859,7 @@ unsafe fn public_window_callback_inner(
                     state: Pressed,
                     position,
                     button: Left.into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1797,6 +1886,7 @@ unsafe fn public_window_callback_inner(
                     state: Released,
                     position,
                     button: Left.into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1823,6 +1913,7 @@ unsafe fn public_window_callback_inner(
                     state: Pressed,
                     position,
                     button: Right.into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1849,6 +1940,7 @@ unsafe fn public_window_callback_inner(
                     state: Released,
                     position,
                     button: Right.into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1875,6 +1967,7 @@ unsafe fn public_window_callback_inner(
                     state: Pressed,
                     position,
                     button: Middle.into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1901,6 +1994,7 @@ unsafe fn public_window_callback_inner(
                     state: Released,
                     position,
                     button: Middle.into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1933,6 +2027,7 @@ unsafe fn public_window_callback_inner(
                         _ => Other(xbutton),
                     }
                     .into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1965,6 +2060,7 @@ unsafe fn public_window_callback_inner(
                         _ => Other(xbutton),
                     }
                     .into(),
+                    timestamp: util::message_timestamp(),
                 },
             });
             result = ProcResult::Value(0);
@@ -2031,6 +2127,7 @@ unsafe fn public_window_callback_inner(
                                 state: Pressed,
                                 position,
                                 button: Touch { finger_id, force: None },
+                                timestamp: util::message_timestamp(),
                             },
                         });
                     } else if util::has_flag(input.dwFlags, TOUCHEVENTF_UP) {
@@ -2042,6 +2139,7 @@ unsafe fn public_window_callback_inner(
                                 state: Released,
                                 position,
                                 button: Touch { finger_id, force: None },
+                                timestamp: util::message_timestamp(),
                             },
                         });
                         userdata.send_event(Event::WindowEvent {
@@ -2061,6 +2159,7 @@ unsafe fn public_window_callback_inner(
                                 primary,
                                 position,
                                 source: PointerSource::Touch { finger_id, force: None },
+                                timestamp: util::message_timestamp(),
                             },
                         });
                     } else {
@@ -2210,6 +2309,7 @@ unsafe fn public_window_callback_inner(
                                 } else {
                                     ButtonSource::Unknown(0)
                                 },
+                                timestamp: util::message_timestamp(),
                             },
                         });
                     } else if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_UP) {
@@ -2225,6 +2325,7 @@ unsafe fn public_window_callback_inner(
                                 } else {
                                     ButtonSource::Unknown(0)
                                 },
+                                timestamp: util::message_timestamp(),
                             },
                         });
                         userdata.send_event(Event::WindowEvent {
@@ -2252,6 +2353,7 @@ unsafe fn public_window_callback_inner(
                                 } else {
                                     PointerSource::Unknown
                                 },
+                                timestamp: util::message_timestamp(),
                             },
                         });
                     } else {
@@ -2293,6 +2395,28 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_NCHITTEST => {
+            let window_state = userdata.window_state_lock();
+            let role = if window_state.hit_test_regions.is_empty() {
+                None
+            } else {
+                // The position is in screen coordinates.
+                let mut point = POINT {
+                    x: super::get_x_lparam(lparam as u32) as i32,
+                    y: super::get_y_lparam(lparam as u32) as i32,
+                };
+                unsafe { ScreenToClient(window, &mut point) };
+                let position = PhysicalPosition::new(point.x as f64, point.y as f64);
+                crate::window::hit_test(&window_state.hit_test_regions, position)
+            };
+            drop(window_state);
+
+            result = match role {
+                Some(role) => ProcResult::Value(hit_test_role_to_ht(role) as LRESULT),
+                None => ProcResult::DefWindowProc(wparam),
+            };
+        },
+
         WM_SETCURSOR => {
             let set_cursor_to = {
                 let window_state = userdata.window_state_lock();
@@ -2555,6 +2679,29 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_INPUTLANGCHANGE => {
+            use crate::event::WindowEvent::{
+                KeyboardLayoutChanged, KeyboardLayoutDirectionChanged,
+            };
+
+            // Sent to the window's thread, so this reads the layout the window is using.
+            let layout = keyboard_layout::current_layout_name();
+            let direction = layout.as_deref().and_then(keyboard_layout::layout_direction);
+            let previous = mem::replace(&mut userdata.window_state_lock().keyboard_layout, layout);
+            let previous_direction =
+                previous.as_deref().and_then(keyboard_layout::layout_direction);
+
+            let window_id = WindowId::from_raw(window as usize);
+            userdata.send_event(Event::WindowEvent { window_id, event: KeyboardLayoutChanged });
+            if let Some(direction) = direction.filter(|&d| Some(d) != previous_direction) {
+                userdata.send_event(Event::WindowEvent {
+                    window_id,
+                    event: KeyboardLayoutDirectionChanged(direction),
+                });
+            }
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
         WM_SETTINGCHANGE => {
             use crate::event::WindowEvent::ThemeChanged;
 
@@ -2738,10 +2885,11 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
 
         if let Some(physical_key) = raw_input::get_keyboard_physical_key(keyboard) {
             let state = if pressed { Pressed } else { Released };
+            let repeat = userdata.raw_key_repeat.borrow_mut().feed(physical_key, state);
 
             userdata.send_event(Event::DeviceEvent {
                 device_id,
-                event: Key(RawKeyEvent { physical_key, state }),
+                event: Key(RawKeyEvent { physical_key, state, repeat }),
             });
         }
     }
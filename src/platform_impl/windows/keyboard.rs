@@ -5,6 +5,7 @@ use std::os::windows::ffi::OsStringExt;
 use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering::Relaxed;
 use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
 
 use smol_str::SmolStr;
 use tracing::{trace, warn};
@@ -25,13 +26,13 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     WM_KEYUP, WM_KILLFOCUS, WM_SETFOCUS, WM_SYSCHAR, WM_SYSDEADCHAR, WM_SYSKEYDOWN, WM_SYSKEYUP,
 };
 
-use crate::event::{ElementState, KeyEvent};
+use crate::event::{DeadKeyComposition, ElementState, KeyEvent};
 use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
 use crate::platform_impl::platform::event_loop::ProcResult;
 use crate::platform_impl::platform::keyboard_layout::{
     Layout, LayoutCache, WindowsModifiers, LAYOUT_CACHE,
 };
-use crate::platform_impl::platform::{loword, primarylangid, KeyEventExtra};
+use crate::platform_impl::platform::{loword, primarylangid, util, KeyEventExtra};
 
 pub type ExScancode = u16;
 
@@ -63,10 +64,15 @@ pub struct MessageAsKeyEvent {
 pub struct KeyEventBuilder {
     event_info: Mutex<Option<PartialKeyEventInfo>>,
     pending: PendingEventQueue<MessageAsKeyEvent>,
+    dead_keys: Mutex<DeadKeyComposition>,
 }
 impl Default for KeyEventBuilder {
     fn default() -> Self {
-        KeyEventBuilder { event_info: Mutex::new(None), pending: Default::default() }
+        KeyEventBuilder {
+            event_info: Mutex::new(None),
+            pending: Default::default(),
+            dead_keys: Default::default(),
+        }
     }
 }
 impl KeyEventBuilder {
@@ -299,11 +305,18 @@ impl KeyEventBuilder {
             }
         };
         let matcher_result = matcher();
-        match matcher_result {
+        let mut events = match matcher_result {
             MatchResult::TokenToRemove(t) => self.pending.remove_pending(t),
             MatchResult::MessagesToDispatch(m) => m,
             MatchResult::Nothing => Vec::new(),
+        };
+
+        let mut dead_keys = self.dead_keys.lock().unwrap();
+        for message in events.iter_mut().filter(|message| !message.is_synthetic) {
+            let event = &mut message.event;
+            event.composed = dead_keys.feed(event.state, &event.logical_key, event.text.is_some());
         }
+        events
     }
 
     // Allowing nominimal_bool lint because the `is_key_pressed` macro triggers this warning
@@ -447,6 +460,7 @@ impl KeyEventBuilder {
             location: get_location(scancode, locale_id),
             utf16parts: Vec::with_capacity(8),
             text: PartialText::Text(text.clone()),
+            timestamp: None,
         };
 
         let mut event = event_info.finalize();
@@ -488,6 +502,8 @@ struct PartialKeyEventInfo {
     utf16parts: Vec<u16>,
 
     text: PartialText,
+
+    timestamp: Option<Duration>,
 }
 
 impl PartialKeyEventInfo {
@@ -580,6 +596,7 @@ impl PartialKeyEventInfo {
             location,
             utf16parts: Vec::with_capacity(8),
             text: PartialText::System(Vec::new()),
+            timestamp: util::message_timestamp(),
         }
     }
 
@@ -625,14 +642,14 @@ impl PartialKeyEventInfo {
         KeyEvent {
             physical_key: self.physical_key,
             logical_key,
+            key_without_modifiers: self.key_without_modifiers,
             text,
             location: self.location,
             state: self.key_state,
             repeat: self.is_repeat,
-            platform_specific: KeyEventExtra {
-                text_with_all_modifiers: char_with_all_modifiers,
-                key_without_modifiers: self.key_without_modifiers,
-            },
+            composed: false,
+            timestamp: self.timestamp,
+            platform_specific: KeyEventExtra { text_with_all_modifiers: char_with_all_modifiers },
         }
     }
 }
@@ -872,11 +889,16 @@ pub fn next_kbd_msg(hwnd: HWND) -> Option<MSG> {
 }
 
 fn get_location(scancode: ExScancode, hkl: HKL) -> KeyLocation {
-    const ABNT_C2: VIRTUAL_KEY = VK_ABNT_C2 as VIRTUAL_KEY;
-
     let extension = 0xe000;
     let extended = (scancode & extension) == extension;
     let vkey = unsafe { MapVirtualKeyExW(scancode as u32, MAPVK_VSC_TO_VK_EX, hkl) as VIRTUAL_KEY };
+    vkey_location(vkey, extended)
+}
+
+/// The numpad Enter key shares `VK_RETURN` with the main one and is only told apart by the
+/// extended flag of its scancode.
+fn vkey_location(vkey: VIRTUAL_KEY, extended: bool) -> KeyLocation {
+    const ABNT_C2: VIRTUAL_KEY = VK_ABNT_C2 as VIRTUAL_KEY;
 
     // Use the native VKEY and the extended flag to cover most cases
     // This is taken from the `druid` GUI library, specifically
@@ -1268,3 +1290,16 @@ pub(crate) fn scancode_to_physicalkey(scancode: u32) -> PhysicalKey {
         _ => return PhysicalKey::Unidentified(NativeKeyCode::Windows(scancode as u16)),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enter_locations() {
+        assert_eq!(scancode_to_physicalkey(0x001c), PhysicalKey::Code(KeyCode::Enter));
+        assert_eq!(scancode_to_physicalkey(0xe01c), PhysicalKey::Code(KeyCode::NumpadEnter));
+        assert_eq!(vkey_location(VK_RETURN, false), KeyLocation::Standard);
+        assert_eq!(vkey_location(VK_RETURN, true), KeyLocation::Numpad);
+    }
+}
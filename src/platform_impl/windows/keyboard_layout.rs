@@ -5,10 +5,13 @@ use std::os::windows::ffi::OsStringExt;
 use std::sync::Mutex;
 
 use smol_str::SmolStr;
-use windows_sys::Win32::System::SystemServices::{LANG_JAPANESE, LANG_KOREAN};
+use windows_sys::Win32::System::SystemServices::{
+    LANG_ARABIC, LANG_CENTRAL_KURDISH, LANG_DIVEHI, LANG_HEBREW, LANG_JAPANESE, LANG_KOREAN,
+    LANG_PASHTO, LANG_PERSIAN, LANG_SYRIAC, LANG_UIGHUR, LANG_URDU,
+};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
-    GetKeyState, GetKeyboardLayout, MapVirtualKeyExW, ToUnicodeEx, HKL, MAPVK_VK_TO_VSC_EX,
-    VIRTUAL_KEY, VK_ACCEPT, VK_ADD, VK_APPS, VK_ATTN, VK_BACK, VK_BROWSER_BACK,
+    GetKeyState, GetKeyboardLayout, GetKeyboardLayoutNameW, MapVirtualKeyExW, ToUnicodeEx, HKL,
+    MAPVK_VK_TO_VSC_EX, VIRTUAL_KEY, VK_ACCEPT, VK_ADD, VK_APPS, VK_ATTN, VK_BACK, VK_BROWSER_BACK,
     VK_BROWSER_FAVORITES, VK_BROWSER_FORWARD, VK_BROWSER_HOME, VK_BROWSER_REFRESH,
     VK_BROWSER_SEARCH, VK_BROWSER_STOP, VK_CANCEL, VK_CAPITAL, VK_CLEAR, VK_CONTROL, VK_CONVERT,
     VK_CRSEL, VK_DECIMAL, VK_DELETE, VK_DIVIDE, VK_DOWN, VK_END, VK_EREOF, VK_ESCAPE, VK_EXECUTE,
@@ -40,8 +43,13 @@ use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
     VK_SCROLL, VK_SELECT, VK_SEPARATOR, VK_SHIFT, VK_SLEEP, VK_SNAPSHOT, VK_SPACE, VK_SUBTRACT,
     VK_TAB, VK_UP, VK_VOLUME_DOWN, VK_VOLUME_MUTE, VK_VOLUME_UP, VK_XBUTTON1, VK_XBUTTON2, VK_ZOOM,
 };
+use windows_sys::Win32::UI::WindowsAndMessaging::KL_NAMELENGTH;
 
-use crate::keyboard::{Key, KeyCode, ModifiersState, NamedKey, NativeKey, PhysicalKey};
+use crate::event::Modifiers;
+use crate::keyboard::{
+    Key, KeyCode, ModifiersKeys, ModifiersState, NamedKey, NativeKey, PhysicalKey, TextDirection,
+};
+use crate::platform::led_state::Leds;
 use crate::platform_impl::{loword, primarylangid, scancode_to_physicalkey};
 use crate::utils::Lazy;
 
@@ -52,6 +60,15 @@ fn key_pressed(vkey: VIRTUAL_KEY) -> bool {
     unsafe { (GetKeyState(vkey as i32) & (1 << 15)) == (1 << 15) }
 }
 
+fn key_toggled(vkey: VIRTUAL_KEY) -> bool {
+    unsafe { (GetKeyState(vkey as i32) & 1) == 1 }
+}
+
+/// The flag of `ToUnicodeEx` to leave the keyboard state of the thread unchanged.
+///
+/// Older versions than Windows 10 1607 ignore it, and a pending dead key is then consumed.
+const TO_UNICODE_KEEP_STATE: u32 = 1 << 2;
+
 const NUMPAD_VKEYS: [VIRTUAL_KEY; 16] = [
     VK_NUMPAD0,
     VK_NUMPAD1,
@@ -249,6 +266,141 @@ impl Layout {
         }
         Key::Unidentified(native_code)
     }
+
+    /// The key produced by the physical key without modifiers, see
+    /// [`Window::key_for_physical`][crate::window::Window::key_for_physical].
+    pub fn key_for_physical(&self, physical_key: PhysicalKey) -> Option<Key> {
+        let PhysicalKey::Code(code) = physical_key else { return None };
+        let vkey = keycode_to_vkey(code, self.hkl);
+        let num_lock_on = key_toggled(VK_NUMLOCK);
+        match self.get_key(WindowsModifiers::empty(), num_lock_on, vkey, &physical_key) {
+            Key::Unidentified(_) => None,
+            key => Some(key),
+        }
+    }
+
+    /// The character composed by the dead key and the base character, see
+    /// [`Window::compose_dead_key`][crate::window::Window::compose_dead_key].
+    pub fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        // Prefer the keys with the fewest modifiers.
+        let find = |matches: &dyn Fn(&Key) -> bool| {
+            (0..WindowsModifiers::FLAGS_END.bits()).find_map(|mods| {
+                let mods = WindowsModifiers::from_bits_retain(mods);
+                let keys = self.keys.get(&mods)?;
+                keys.iter().find(|(_, key)| matches(key)).map(|(&code, _)| (mods, code))
+            })
+        };
+        let (dead_mods, dead_code) = find(&|key| *key == Key::Dead(Some(dead)))?;
+        let (base_mods, base_code) = find(&|key| match key {
+            Key::Character(text) => text.chars().eq(std::iter::once(base)),
+            _ => false,
+        })?;
+
+        let hkl = self.hkl as HKL;
+        let translate = |mods: WindowsModifiers, vkey: u32, flags: u32| {
+            let mut key_state = [0u8; 256];
+            mods.apply_to_kbd_state(&mut key_state);
+            let scancode = unsafe { MapVirtualKeyExW(vkey, MAPVK_VK_TO_VSC_EX, hkl) };
+            let mut wide = [0u16; 8];
+            let len = unsafe {
+                ToUnicodeEx(
+                    vkey,
+                    scancode,
+                    key_state.as_ptr(),
+                    wide.as_mut_ptr(),
+                    wide.len() as i32,
+                    flags,
+                    hkl,
+                )
+            };
+            (len, wide)
+        };
+        let single_char = |len: i32, wide: &[u16]| {
+            let mut chars = char::decode_utf16(wide[..len.max(0) as usize].iter().copied());
+            match (chars.next(), chars.next()) {
+                (Some(Ok(ch)), None) => Some(ch),
+                _ => None,
+            }
+        };
+
+        // The dead key the user typed last, if it is still waiting for its base character in the
+        // keyboard state of the thread. A space after a dead key produces its character, and the
+        // state is left as is.
+        let (len, wide) =
+            translate(WindowsModifiers::empty(), VK_SPACE as u32, TO_UNICODE_KEEP_STATE);
+        let pending = single_char(len, &wide)
+            .filter(|ch| *ch != ' ')
+            .and_then(|ch| find(&|key| *key == Key::Dead(Some(ch))));
+
+        // Pressing the dead key completes the pending one if any, so press it again then.
+        let dead_vkey = keycode_to_vkey(dead_code, self.hkl) as u32;
+        let mut len = translate(dead_mods, dead_vkey, 0).0;
+        if len >= 0 && pending.is_some() {
+            len = translate(dead_mods, dead_vkey, 0).0;
+        }
+        let composed = if len < 0 {
+            let (len, wide) = translate(base_mods, keycode_to_vkey(base_code, self.hkl) as u32, 0);
+            single_char(len, &wide)
+        } else {
+            None
+        };
+
+        // Restore the dead key the user typed.
+        if let Some((mods, code)) = pending {
+            translate(mods, keycode_to_vkey(code, self.hkl) as u32, 0);
+        }
+
+        composed
+    }
+
+    /// The text produced by the key under the given modifiers, if it produces any.
+    ///
+    /// Dead keys produce the character they'd insert on their own.
+    pub fn text_for_code(&self, mods: WindowsModifiers, code: KeyCode) -> Option<SmolStr> {
+        match self.keys.get(&mods)?.get(&code)? {
+            Key::Character(text) => Some(text.clone()),
+            Key::Dead(Some(ch)) => Some(SmolStr::from_iter([*ch])),
+            _ => None,
+        }
+    }
+}
+
+/// The lock LEDs, which Windows drives from the toggle state of the lock keys.
+pub(crate) fn lit_leds() -> Leds {
+    let mut leds = Leds::empty();
+    leds.set(Leds::CAPS_LOCK, key_toggled(VK_CAPITAL));
+    leds.set(Leds::NUM_LOCK, key_toggled(VK_NUMLOCK));
+    leds.set(Leds::SCROLL_LOCK, key_toggled(VK_SCROLL));
+    leds
+}
+
+/// The identifier of the keyboard layout active on the calling thread, e.g. `00000409`.
+pub(crate) fn current_layout_name() -> Option<String> {
+    let mut name = [0u16; KL_NAMELENGTH as usize];
+    if unsafe { GetKeyboardLayoutNameW(name.as_mut_ptr()) } == 0 {
+        return None;
+    }
+    let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+    Some(String::from_utf16_lossy(&name[..len]))
+}
+
+/// The direction of the text typed with the layout identifier `name`, e.g. `00000401`, whose low
+/// word is the language of the layout.
+pub(crate) fn layout_direction(name: &str) -> Option<TextDirection> {
+    let layout_id = u32::from_str_radix(name, 16).ok()?;
+    let rtl = matches!(
+        primarylangid(loword(layout_id)) as u32,
+        LANG_ARABIC
+            | LANG_CENTRAL_KURDISH
+            | LANG_DIVEHI
+            | LANG_HEBREW
+            | LANG_PASHTO
+            | LANG_PERSIAN
+            | LANG_SYRIAC
+            | LANG_UIGHUR
+            | LANG_URDU
+    );
+    Some(if rtl { TextDirection::RightToLeft } else { TextDirection::LeftToRight })
 }
 
 #[derive(Default)]
@@ -263,23 +415,54 @@ impl LayoutCache {
     /// The current layout is then returned.
     pub fn get_current_layout(&mut self) -> (u64, &Layout) {
         let locale_id = unsafe { GetKeyboardLayout(0) } as u64;
+        (locale_id, self.get_layout(locale_id))
+    }
+
+    /// Returns the layout with the given locale identifier, preparing it if it isn't known.
+    pub fn get_layout(&mut self, locale_id: u64) -> &Layout {
         match self.layouts.entry(locale_id) {
-            Entry::Occupied(entry) => (locale_id, entry.into_mut()),
-            Entry::Vacant(entry) => {
-                let layout = Self::prepare_layout(locale_id);
-                (locale_id, entry.insert(layout))
-            },
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Self::prepare_layout(locale_id)),
+        }
+    }
+
+    pub fn get_mods(&mut self) -> Modifiers {
+        let (_, layout) = self.get_current_layout();
+        let filter_out_altgr = layout.has_alt_graph && key_pressed(VK_RMENU);
+
+        let mut pressed_mods = ModifiersKeys::empty();
+        pressed_mods.set(ModifiersKeys::LSHIFT, key_pressed(VK_LSHIFT));
+        pressed_mods.set(ModifiersKeys::RSHIFT, key_pressed(VK_RSHIFT));
+        // AltGr is reported as a fake left control together with right alt.
+        pressed_mods.set(ModifiersKeys::LCONTROL, key_pressed(VK_LCONTROL) && !filter_out_altgr);
+        pressed_mods.set(ModifiersKeys::RCONTROL, key_pressed(VK_RCONTROL));
+        pressed_mods.set(ModifiersKeys::LALT, key_pressed(VK_LMENU));
+        pressed_mods.set(ModifiersKeys::RALT, key_pressed(VK_RMENU) && !filter_out_altgr);
+        pressed_mods.set(ModifiersKeys::LSUPER, key_pressed(VK_LWIN));
+        pressed_mods.set(ModifiersKeys::RSUPER, key_pressed(VK_RWIN));
+
+        Modifiers {
+            state: self.get_agnostic_mods(),
+            pressed_mods,
+            known_mods: ModifiersKeys::all(),
         }
     }
 
     pub fn get_agnostic_mods(&mut self) -> ModifiersState {
         let (_, layout) = self.get_current_layout();
+        // Windows reports AltGr as right Alt together with a fake left Control, which can't be
+        // told apart from a real one. So on layouts with an AltGr level, right Alt is taken to be
+        // AltGr and replaces both Control and Alt. On the other layouts it's a plain Alt.
         let filter_out_altgr = layout.has_alt_graph && key_pressed(VK_RMENU);
         let mut mods = ModifiersState::empty();
         mods.set(ModifiersState::SHIFT, key_pressed(VK_SHIFT));
         mods.set(ModifiersState::CONTROL, key_pressed(VK_CONTROL) && !filter_out_altgr);
         mods.set(ModifiersState::ALT, key_pressed(VK_MENU) && !filter_out_altgr);
+        mods.set(ModifiersState::ALT_GRAPH, filter_out_altgr);
         mods.set(ModifiersState::SUPER, key_pressed(VK_LWIN) || key_pressed(VK_RWIN));
+        mods.set(ModifiersState::CAPS_LOCK, key_toggled(VK_CAPITAL));
+        mods.set(ModifiersState::NUM_LOCK, key_toggled(VK_NUMLOCK));
+        mods.set(ModifiersState::SCROLL_LOCK, key_toggled(VK_SCROLL));
         mods
     }
 
@@ -819,12 +1002,12 @@ fn vkey_to_non_char_key(
         VK_NUMPAD7 => Key::Unidentified(native_code),
         VK_NUMPAD8 => Key::Unidentified(native_code),
         VK_NUMPAD9 => Key::Unidentified(native_code),
-        VK_MULTIPLY => Key::Unidentified(native_code),
-        VK_ADD => Key::Unidentified(native_code),
+        VK_MULTIPLY => Key::Character(SmolStr::new_static("*")),
+        VK_ADD => Key::Character(SmolStr::new_static("+")),
         VK_SEPARATOR => Key::Unidentified(native_code),
-        VK_SUBTRACT => Key::Unidentified(native_code),
+        VK_SUBTRACT => Key::Character(SmolStr::new_static("-")),
         VK_DECIMAL => Key::Unidentified(native_code),
-        VK_DIVIDE => Key::Unidentified(native_code),
+        VK_DIVIDE => Key::Character(SmolStr::new_static("/")),
 
         VK_F1 => Key::Named(NamedKey::F1),
         VK_F2 => Key::Named(NamedKey::F2),
@@ -984,3 +1167,55 @@ fn vkey_to_non_char_key(
         _ => Key::Unidentified(native_code),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_directions() {
+        use TextDirection::{LeftToRight, RightToLeft};
+
+        assert_eq!(layout_direction("00000409"), Some(LeftToRight));
+        assert_eq!(layout_direction("00000401"), Some(RightToLeft));
+        assert_eq!(layout_direction("0000040D"), Some(RightToLeft));
+        // A variant of the Persian layout.
+        assert_eq!(layout_direction("00050429"), Some(RightToLeft));
+        assert_eq!(layout_direction(""), None);
+    }
+
+    #[test]
+    fn numpad_operators() {
+        let operators = [(VK_ADD, "+"), (VK_SUBTRACT, "-"), (VK_MULTIPLY, "*"), (VK_DIVIDE, "/")];
+        for (vkey, text) in operators {
+            let key = vkey_to_non_char_key(vkey, NativeKey::Windows(vkey), 0, false);
+            assert_eq!(key, Key::Character(text.into()));
+        }
+    }
+
+    #[test]
+    fn media_and_browser_keys() {
+        let keys = [
+            (VK_MEDIA_PLAY_PAUSE, KeyCode::MediaPlayPause, NamedKey::MediaPlayPause),
+            (VK_MEDIA_STOP, KeyCode::MediaStop, NamedKey::MediaStop),
+            (VK_MEDIA_NEXT_TRACK, KeyCode::MediaTrackNext, NamedKey::MediaTrackNext),
+            (VK_MEDIA_PREV_TRACK, KeyCode::MediaTrackPrevious, NamedKey::MediaTrackPrevious),
+            (VK_VOLUME_UP, KeyCode::AudioVolumeUp, NamedKey::AudioVolumeUp),
+            (VK_VOLUME_DOWN, KeyCode::AudioVolumeDown, NamedKey::AudioVolumeDown),
+            (VK_VOLUME_MUTE, KeyCode::AudioVolumeMute, NamedKey::AudioVolumeMute),
+            (VK_BROWSER_BACK, KeyCode::BrowserBack, NamedKey::BrowserBack),
+            (VK_BROWSER_FORWARD, KeyCode::BrowserForward, NamedKey::BrowserForward),
+            (VK_BROWSER_REFRESH, KeyCode::BrowserRefresh, NamedKey::BrowserRefresh),
+            (VK_BROWSER_STOP, KeyCode::BrowserStop, NamedKey::BrowserStop),
+            (VK_BROWSER_SEARCH, KeyCode::BrowserSearch, NamedKey::BrowserSearch),
+            (VK_BROWSER_FAVORITES, KeyCode::BrowserFavorites, NamedKey::BrowserFavorites),
+            (VK_BROWSER_HOME, KeyCode::BrowserHome, NamedKey::BrowserHome),
+            (VK_LAUNCH_MEDIA_SELECT, KeyCode::MediaSelect, NamedKey::LaunchMediaPlayer),
+        ];
+        for (vkey, code, named) in keys {
+            let key = vkey_to_non_char_key(vkey, NativeKey::Windows(vkey), 0, false);
+            assert_eq!(key, Key::Named(named));
+            assert_eq!(keycode_to_vkey(code, 0), vkey);
+        }
+    }
+}
@@ -2,16 +2,19 @@ use smol_str::SmolStr;
 use windows_sys::Win32::Foundation::HWND;
 use windows_sys::Win32::UI::WindowsAndMessaging::{HMENU, WINDOW_LONG_PTR_INDEX};
 
-pub(crate) use self::event_loop::{EventLoop, PlatformSpecificEventLoopAttributes};
+pub(crate) use self::event_loop::{
+    ActiveEventLoop, EventLoop, PlatformSpecificEventLoopAttributes,
+};
 pub use self::icon::WinIcon as PlatformIcon;
 pub(crate) use self::icon::{SelectedCursor, WinCursor as PlatformCustomCursor, WinIcon};
 pub(crate) use self::keyboard::{physicalkey_to_scancode, scancode_to_physicalkey};
+use self::keyboard_layout::{WindowsModifiers, LAYOUT_CACHE};
 pub(crate) use self::monitor::MonitorHandle;
 pub(crate) use self::window::Window;
 pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSource;
 use crate::event::DeviceId;
 use crate::icon::Icon;
-use crate::keyboard::Key;
+use crate::keyboard::PhysicalKey;
 use crate::platform::windows::{BackdropType, Color, CornerPreference};
 use crate::platform_impl::Fullscreen;
 
@@ -64,7 +67,22 @@ fn wrap_device_id(id: u32) -> DeviceId {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct KeyEventExtra {
     pub text_with_all_modifiers: Option<SmolStr>,
-    pub key_without_modifiers: Key,
+}
+
+impl KeyEventExtra {
+    #[cfg(any(feature = "test-util", feature = "serde"))]
+    pub(crate) fn synthetic(text: Option<&SmolStr>) -> Self {
+        Self { text_with_all_modifiers: text.cloned() }
+    }
+
+    /// Looks the key up in the layout that is active on the calling thread.
+    pub(crate) fn text_for_shift(&self, physical_key: PhysicalKey, shift: bool) -> Option<SmolStr> {
+        let PhysicalKey::Code(code) = physical_key else { return None };
+        let mods = if shift { WindowsModifiers::SHIFT } else { WindowsModifiers::empty() };
+        let mut layouts = LAYOUT_CACHE.lock().unwrap();
+        let (_, layout) = layouts.get_current_layout();
+        layout.text_for_code(mods, code)
+    }
 }
 
 #[inline(always)]
@@ -3,12 +3,23 @@ use std::hash::Hash;
 use std::num::{NonZeroU16, NonZeroU32};
 use std::{io, mem, ptr};
 
-use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM, POINT, RECT};
+use tracing::warn;
+use windows_sys::Win32::Devices::Display::{
+    DisplayConfigGetDeviceInfo, GetDisplayConfigBufferSizes, QueryDisplayConfig,
+    DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO,
+    DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL, DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_HEADER, DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO,
+    DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO, DISPLAYCONFIG_SDR_WHITE_LEVEL,
+    DISPLAYCONFIG_SOURCE_DEVICE_NAME, QDC_ONLY_ACTIVE_PATHS,
+};
+use windows_sys::Win32::Foundation::{
+    BOOL, ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS, HWND, LPARAM, LUID, POINT, RECT,
+};
 use windows_sys::Win32::Graphics::Gdi::{
-    EnumDisplayMonitors, EnumDisplaySettingsExW, GetMonitorInfoW, MonitorFromPoint,
-    MonitorFromWindow, DEVMODEW, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
-    ENUM_CURRENT_SETTINGS, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
-    MONITOR_DEFAULTTOPRIMARY,
+    ChangeDisplaySettingsExW, EnumDisplayMonitors, EnumDisplaySettingsExW, GetMonitorInfoW,
+    MonitorFromPoint, MonitorFromWindow, CDS_FULLSCREEN, DEVMODEW, DISP_CHANGE_SUCCESSFUL,
+    DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH, ENUM_CURRENT_SETTINGS, HDC,
+    HMONITOR, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTOPRIMARY,
 };
 
 use super::util::decode_wide;
@@ -177,6 +188,88 @@ impl MonitorHandle {
         }
     }
 
+    /// Returns the number of bits per color channel the monitor is driven with.
+    ///
+    /// This is what DXGI reports as `DXGI_OUTPUT_DESC1::BitsPerColor`, queried here through the
+    /// display configuration API so that no DXGI factory needs to be created.
+    pub fn bit_depth(&self) -> Option<u32> {
+        let path = self.display_config_path()?;
+        advanced_color_bit_depth(&advanced_color_info(&path)?)
+    }
+
+    /// Returns the SDR white level, which Windows only applies while the monitor is in HDR mode.
+    pub fn sdr_white_level_nits(&self) -> Option<f64> {
+        let path = self.display_config_path()?;
+        let info = advanced_color_info(&path)?;
+        let mut level = DISPLAYCONFIG_SDR_WHITE_LEVEL {
+            header: device_info_header::<DISPLAYCONFIG_SDR_WHITE_LEVEL>(
+                DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL,
+                path.targetInfo.adapterId,
+                path.targetInfo.id,
+            ),
+            SDRWhiteLevel: 0,
+        };
+        if unsafe { DisplayConfigGetDeviceInfo(&mut level.header) } != ERROR_SUCCESS as i32 {
+            return None;
+        }
+
+        sdr_white_level_nits(&info, level.SDRWhiteLevel)
+    }
+
+    /// Finds the active display configuration path whose source is this monitor.
+    fn display_config_path(&self) -> Option<DISPLAYCONFIG_PATH_INFO> {
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let device_name = decode_wide(&monitor_info.szDevice);
+
+        let paths = loop {
+            let mut path_count = 0;
+            let mut mode_count = 0;
+            if unsafe {
+                GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count)
+            } != ERROR_SUCCESS
+            {
+                return None;
+            }
+
+            let mut paths: Vec<DISPLAYCONFIG_PATH_INFO> =
+                vec![unsafe { mem::zeroed() }; path_count as usize];
+            let mut modes: Vec<DISPLAYCONFIG_MODE_INFO> =
+                vec![unsafe { mem::zeroed() }; mode_count as usize];
+            match unsafe {
+                QueryDisplayConfig(
+                    QDC_ONLY_ACTIVE_PATHS,
+                    &mut path_count,
+                    paths.as_mut_ptr(),
+                    &mut mode_count,
+                    modes.as_mut_ptr(),
+                    ptr::null_mut(),
+                )
+            } {
+                ERROR_SUCCESS => {
+                    paths.truncate(path_count as usize);
+                    break paths;
+                },
+                // The configuration changed in between the two calls.
+                ERROR_INSUFFICIENT_BUFFER => continue,
+                _ => return None,
+            }
+        };
+
+        paths.into_iter().find(|path| {
+            let mut source_name = DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+                header: device_info_header::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>(
+                    DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+                    path.sourceInfo.adapterId,
+                    path.sourceInfo.id,
+                ),
+                viewGdiDeviceName: [0; 32],
+            };
+            let status = unsafe { DisplayConfigGetDeviceInfo(&mut source_name.header) };
+            status == ERROR_SUCCESS as i32
+                && decode_wide(&source_name.viewGdiDeviceName) == device_name
+        })
+    }
+
     pub(crate) fn video_mode_handles(&self) -> impl Iterator<Item = VideoModeHandle> {
         // EnumDisplaySettingsExW can return duplicate values (or some of the
         // fields are probably changing, but we aren't looking at those fields
@@ -213,3 +306,176 @@ impl MonitorHandle {
         self.video_mode_handles().map(|mode| mode.mode)
     }
 }
+
+/// The name of a display device, as in `MONITORINFOEXW::szDevice`.
+pub(crate) type DeviceName = [u16; 32];
+
+/// The display whose video mode was changed by an exclusive fullscreen window.
+///
+/// The mode is restored when the window leaves exclusive fullscreen or moves to another display,
+/// and when this is dropped while the mode is still changed, e.g. when the window is destroyed
+/// while unwinding from a panic.
+#[derive(Debug, Default)]
+pub(crate) struct DisplayModeChange {
+    device: Option<DeviceName>,
+}
+
+impl DisplayModeChange {
+    /// Prepare changing the video mode of `device`, returning the display whose mode must be
+    /// restored first.
+    pub(crate) fn begin(&mut self, device: &DeviceName) -> Option<DeviceName> {
+        if self.device.as_ref() == Some(device) {
+            // Restoring would only add a mode change, the original mode is restored later.
+            None
+        } else {
+            self.device.take()
+        }
+    }
+
+    /// Record that the video mode of `device` was changed.
+    pub(crate) fn changed(&mut self, device: DeviceName) {
+        self.device = Some(device);
+    }
+
+    /// Forget the changed display, returning it to restore its video mode.
+    pub(crate) fn take(&mut self) -> Option<DeviceName> {
+        self.device.take()
+    }
+}
+
+impl Drop for DisplayModeChange {
+    fn drop(&mut self) {
+        if let Some(device) = self.device.take() {
+            restore_display_mode(&device);
+        }
+    }
+}
+
+/// Change the video mode of `device`, returning whether it succeeded.
+pub(crate) fn change_display_mode(device: &DeviceName, mode: &DEVMODEW) -> bool {
+    let result = unsafe {
+        ChangeDisplaySettingsExW(
+            device.as_ptr(),
+            mode,
+            ptr::null_mut(),
+            CDS_FULLSCREEN,
+            ptr::null(),
+        )
+    };
+
+    if result != DISP_CHANGE_SUCCESSFUL {
+        warn!("failed to change the video mode of the display: {result}");
+    }
+    result == DISP_CHANGE_SUCCESSFUL
+}
+
+/// Restore the video mode of `device` to the one stored in the registry.
+pub(crate) fn restore_display_mode(device: &DeviceName) {
+    let result = unsafe {
+        ChangeDisplaySettingsExW(device.as_ptr(), ptr::null(), ptr::null_mut(), 0, ptr::null())
+    };
+
+    if result != DISP_CHANGE_SUCCESSFUL {
+        warn!("failed to restore the video mode of the display: {result}");
+    }
+}
+
+fn device_info_header<T>(
+    r#type: i32,
+    adapter_id: LUID,
+    id: u32,
+) -> DISPLAYCONFIG_DEVICE_INFO_HEADER {
+    DISPLAYCONFIG_DEVICE_INFO_HEADER {
+        r#type,
+        size: mem::size_of::<T>() as u32,
+        adapterId: adapter_id,
+        id,
+    }
+}
+
+fn advanced_color_info(
+    path: &DISPLAYCONFIG_PATH_INFO,
+) -> Option<DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO> {
+    let mut info: DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO = unsafe { mem::zeroed() };
+    info.header = device_info_header::<DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO>(
+        DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO,
+        path.targetInfo.adapterId,
+        path.targetInfo.id,
+    );
+    if unsafe { DisplayConfigGetDeviceInfo(&mut info.header) } != ERROR_SUCCESS as i32 {
+        return None;
+    }
+
+    Some(info)
+}
+
+// Bits of `DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO`'s bitfield.
+const ADVANCED_COLOR_ENABLED: u32 = 1 << 1;
+const WIDE_COLOR_ENFORCED: u32 = 1 << 2;
+
+/// The bits per color channel, which is zero when the driver doesn't report it.
+fn advanced_color_bit_depth(info: &DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO) -> Option<u32> {
+    (info.bitsPerColorChannel != 0).then_some(info.bitsPerColorChannel)
+}
+
+/// `SDRWhiteLevel` is expressed in thousandths of the 80 nits sRGB reference white.
+///
+/// Windows reports it regardless of the color mode, so it is ignored unless advanced color is
+/// enabled for HDR, rather than enforced for a wide color gamut SDR display.
+fn sdr_white_level_nits(info: &DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO, level: u32) -> Option<f64> {
+    let flags = unsafe { info.Anonymous.value };
+    let hdr = flags & ADVANCED_COLOR_ENABLED != 0 && flags & WIDE_COLOR_ENFORCED == 0;
+    (hdr && level != 0).then(|| level as f64 / 1000.0 * 80.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advanced_color_info_bit_depth() {
+        let mut info: DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO = unsafe { mem::zeroed() };
+        assert_eq!(advanced_color_bit_depth(&info), None);
+
+        info.bitsPerColorChannel = 8;
+        assert_eq!(advanced_color_bit_depth(&info), Some(8));
+        info.bitsPerColorChannel = 10;
+        assert_eq!(advanced_color_bit_depth(&info), Some(10));
+    }
+
+    #[test]
+    fn display_mode_change_bookkeeping() {
+        let primary: DeviceName = [1; 32];
+        let secondary: DeviceName = [2; 32];
+
+        let mut change = DisplayModeChange::default();
+        assert_eq!(change.begin(&primary), None);
+        change.changed(primary);
+
+        // Switching the mode of the same display keeps the original mode to restore.
+        assert_eq!(change.begin(&primary), None);
+        change.changed(primary);
+
+        // Moving to another display restores the first one.
+        assert_eq!(change.begin(&secondary), Some(primary));
+        assert_eq!(change.begin(&secondary), None);
+        change.changed(secondary);
+
+        assert_eq!(change.take(), Some(secondary));
+        assert_eq!(change.take(), None);
+    }
+
+    #[test]
+    fn sdr_white_level() {
+        let mut info: DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO = unsafe { mem::zeroed() };
+        // Not in HDR mode.
+        assert_eq!(sdr_white_level_nits(&info, 1000), None);
+        info.Anonymous.value = WIDE_COLOR_ENFORCED | ADVANCED_COLOR_ENABLED;
+        assert_eq!(sdr_white_level_nits(&info, 1000), None);
+
+        info.Anonymous.value = ADVANCED_COLOR_ENABLED;
+        assert_eq!(sdr_white_level_nits(&info, 0), None);
+        assert_eq!(sdr_white_level_nits(&info, 1000), Some(80.0));
+        assert_eq!(sdr_white_level_nits(&info, 2500), Some(200.0));
+    }
+}
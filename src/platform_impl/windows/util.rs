@@ -3,6 +3,7 @@ use std::iter::once;
 use std::ops::BitAnd;
 use std::os::windows::prelude::{OsStrExt, OsStringExt};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use std::{io, mem, ptr};
 
 use windows_sys::core::{HRESULT, PCWSTR};
@@ -16,9 +17,9 @@ use windows_sys::Win32::UI::HiDpi::{
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetActiveWindow;
 use windows_sys::Win32::UI::Input::Pointer::{POINTER_INFO, POINTER_TOUCH_INFO};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    ClipCursor, GetClientRect, GetClipCursor, GetSystemMetrics, GetWindowPlacement, GetWindowRect,
-    IsIconic, ShowCursor, IDC_APPSTARTING, IDC_ARROW, IDC_CROSS, IDC_HAND, IDC_HELP, IDC_IBEAM,
-    IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT,
+    ClipCursor, GetClientRect, GetClipCursor, GetMessageTime, GetSystemMetrics, GetWindowPlacement,
+    GetWindowRect, IsIconic, ShowCursor, IDC_APPSTARTING, IDC_ARROW, IDC_CROSS, IDC_HAND, IDC_HELP,
+    IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT,
     SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SW_MAXIMIZE,
     WINDOWPLACEMENT,
 };
@@ -137,6 +138,12 @@ pub fn is_minimized(window: HWND) -> bool {
     unsafe { IsIconic(window) != false.into() }
 }
 
+/// The time of the message being processed, see `KeyEvent::timestamp`.
+pub fn message_timestamp() -> Option<Duration> {
+    // The time is a `DWORD` of milliseconds, reported as a signed integer.
+    Some(Duration::from_millis(unsafe { GetMessageTime() } as u32 as u64))
+}
+
 pub fn get_instance_handle() -> HMODULE {
     // Gets the instance handle by taking the address of the
     // pseudo-variable created by the microsoft linker:
@@ -156,12 +163,14 @@ pub(crate) fn to_windows_cursor(cursor: CursorIcon) -> PCWSTR {
     match cursor {
         CursorIcon::Default => IDC_ARROW,
         CursorIcon::Pointer => IDC_HAND,
-        CursorIcon::Crosshair => IDC_CROSS,
+        CursorIcon::Crosshair | CursorIcon::Cell => IDC_CROSS,
         CursorIcon::Text | CursorIcon::VerticalText => IDC_IBEAM,
         CursorIcon::NotAllowed | CursorIcon::NoDrop => IDC_NO,
-        CursorIcon::Grab | CursorIcon::Grabbing | CursorIcon::Move | CursorIcon::AllScroll => {
-            IDC_SIZEALL
-        },
+        CursorIcon::Grab
+        | CursorIcon::Grabbing
+        | CursorIcon::Move
+        | CursorIcon::AllScroll
+        | CursorIcon::AllResize => IDC_SIZEALL,
         CursorIcon::EResize
         | CursorIcon::WResize
         | CursorIcon::EwResize
@@ -266,3 +275,17 @@ pub(crate) static GET_POINTER_DEVICE_RECTS: Lazy<Option<GetPointerDeviceRects>>
     Lazy::new(|| get_function!("user32.dll", GetPointerDeviceRects));
 pub(crate) static GET_POINTER_TOUCH_INFO: Lazy<Option<GetPointerTouchInfo>> =
     Lazy::new(|| get_function!("user32.dll", GetPointerTouchInfo));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_icon_fallbacks() {
+        assert_eq!(to_windows_cursor(CursorIcon::Cell), IDC_CROSS);
+        assert_eq!(to_windows_cursor(CursorIcon::VerticalText), IDC_IBEAM);
+        assert_eq!(to_windows_cursor(CursorIcon::AllScroll), IDC_SIZEALL);
+        assert_eq!(to_windows_cursor(CursorIcon::AllResize), IDC_SIZEALL);
+        assert_eq!(to_windows_cursor(CursorIcon::ZoomIn), IDC_ARROW);
+    }
+}
@@ -5,6 +5,7 @@ use std::ffi::c_void;
 use std::mem::{self, MaybeUninit};
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use std::{io, panic, ptr};
 
 use tracing::warn;
@@ -12,44 +13,47 @@ use windows_sys::Win32::Foundation::{
     HWND, LPARAM, OLE_E_WRONGCOMPOBJ, POINT, POINTS, RECT, RPC_E_CHANGED_MODE, S_OK, WPARAM,
 };
 use windows_sys::Win32::Graphics::Dwm::{
-    DwmEnableBlurBehindWindow, DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR,
-    DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_TEXT_COLOR, DWMWA_WINDOW_CORNER_PREFERENCE, DWM_BB_BLURREGION,
-    DWM_BB_ENABLE, DWM_BLURBEHIND, DWM_SYSTEMBACKDROP_TYPE, DWM_WINDOW_CORNER_PREFERENCE,
+    DwmEnableBlurBehindWindow, DwmEnableMMCSS, DwmSetWindowAttribute, DWMWA_BORDER_COLOR,
+    DWMWA_CAPTION_COLOR, DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_TEXT_COLOR,
+    DWMWA_WINDOW_CORNER_PREFERENCE, DWM_BB_BLURREGION, DWM_BB_ENABLE, DWM_BLURBEHIND,
+    DWM_SYSTEMBACKDROP_TYPE, DWM_WINDOW_CORNER_PREFERENCE,
 };
 use windows_sys::Win32::Graphics::Gdi::{
-    ChangeDisplaySettingsExW, ClientToScreen, CreateRectRgn, DeleteObject, InvalidateRgn,
-    RedrawWindow, CDS_FULLSCREEN, DISP_CHANGE_BADFLAGS, DISP_CHANGE_BADMODE, DISP_CHANGE_BADPARAM,
-    DISP_CHANGE_FAILED, DISP_CHANGE_SUCCESSFUL, RDW_INTERNALPAINT,
+    ClientToScreen, CreateRectRgn, DeleteObject, InvalidateRgn, RedrawWindow, ScreenToClient,
+    RDW_INTERNALPAINT,
 };
 use windows_sys::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
 };
 use windows_sys::Win32::System::Ole::{OleInitialize, RegisterDragDrop};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
-    EnableWindow, GetActiveWindow, MapVirtualKeyW, ReleaseCapture, SendInput, ToUnicode, INPUT,
-    INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP, MAPVK_VK_TO_VSC,
-    VIRTUAL_KEY, VK_LMENU, VK_MENU, VK_SPACE,
+    EnableWindow, GetActiveWindow, GetKeyboardLayout, MapVirtualKeyW, ReleaseCapture, SendInput,
+    ToUnicode, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP,
+    MAPVK_VK_TO_VSC, VIRTUAL_KEY, VK_LMENU, VK_MENU, VK_SPACE,
 };
 use windows_sys::Win32::UI::Input::Touch::{RegisterTouchWindow, TWF_WANTPALM};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, EnableMenuItem, FlashWindowEx, GetClientRect, GetCursorPos,
     GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindowPlacement, GetWindowTextLengthW,
-    GetWindowTextW, IsWindowVisible, LoadCursorW, PeekMessageW, PostMessageW, RegisterClassExW,
-    SetCursor, SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetWindowDisplayAffinity,
-    SetWindowPlacement, SetWindowPos, SetWindowTextW, TrackPopupMenu, CS_HREDRAW, CS_VREDRAW,
-    CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY,
-    GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP,
-    HTTOPLEFT, HTTOPRIGHT, MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND, NID_READY,
-    PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE, SM_DIGITIZER,
-    SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN, TPM_RETURNCMD,
-    WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WM_NCLBUTTONDOWN, WM_SYSCOMMAND, WNDCLASSEXW,
+    GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible, LoadCursorW, PeekMessageW,
+    PostMessageW, RegisterClassExW, SetCursor, SetCursorPos, SetForegroundWindow,
+    SetMenuDefaultItem, SetTimer, SetWindowDisplayAffinity, SetWindowPlacement, SetWindowPos,
+    SetWindowTextW, TrackPopupMenu, CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL,
+    FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY, GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT,
+    HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP, HTTOPLEFT, HTTOPRIGHT, MENU_ITEM_STATE,
+    MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND, NID_READY, PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE,
+    SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE, SM_DIGITIZER, SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE,
+    SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN, TPM_RETURNCMD, WDA_EXCLUDEFROMCAPTURE, WDA_NONE,
+    WM_NCLBUTTONDOWN, WM_SYSCOMMAND, WNDCLASSEXW,
 };
 
-use crate::cursor::Cursor;
+use crate::cursor::{CurrentCursor, Cursor, CursorAnimation};
 use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
 use crate::icon::Icon;
-use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
+use crate::monitor::{find_exclusive_mode, MonitorHandle as CoreMonitorHandle};
+use crate::platform::led_state::LedState;
 use crate::platform::windows::{BackdropType, Color, CornerPreference};
 use crate::platform_impl::platform::dark_mode::try_theme;
 use crate::platform_impl::platform::definitions::{
@@ -59,18 +63,21 @@ use crate::platform_impl::platform::dpi::{
     dpi_to_scale_factor, enable_non_client_dpi_scaling, hwnd_dpi,
 };
 use crate::platform_impl::platform::drop_handler::FileDropHandler;
-use crate::platform_impl::platform::event_loop::{self, ActiveEventLoop, DESTROY_MSG_ID};
+use crate::platform_impl::platform::event_loop::{
+    self, ActiveEventLoop, CURSOR_ANIMATION_TIMER_ID, DESTROY_MSG_ID,
+};
 use crate::platform_impl::platform::icon::{self, IconType};
 use crate::platform_impl::platform::ime::ImeContext;
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
+use crate::platform_impl::platform::keyboard_layout::{self, LAYOUT_CACHE};
 use crate::platform_impl::platform::window_state::{
     CursorFlags, SavedWindow, WindowFlags, WindowState,
 };
 use crate::platform_impl::platform::{monitor, util, Fullscreen, SelectedCursor};
 use crate::window::{
-    CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    CommonWindowState, CursorGrabMode, Fullscreen as CoreFullscreen, HitTestRegion, ImePurpose,
+    InputStateSnapshot, ResizeDirection, SurfaceTransform, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 #[derive(Clone, Copy)]
@@ -98,6 +105,12 @@ pub(crate) struct Window {
 
     // The events loop proxy.
     thread_executor: event_loop::EventLoopThreadExecutor,
+
+    /// The cursor last set by the user.
+    current_cursor: CurrentCursor,
+
+    /// The state Winit keeps for the window.
+    pub(crate) common_state: Arc<CommonWindowState>,
 }
 
 impl Window {
@@ -343,6 +356,11 @@ impl Window {
             );
         }
     }
+
+    #[inline]
+    pub fn led_state(&self) -> LedState {
+        LedState::Known(keyboard_layout::lit_leds())
+    }
 }
 
 impl Drop for Window {
@@ -395,6 +413,16 @@ impl CoreWindow for Window {
 
     fn set_blur(&self, _blur: bool) {}
 
+    fn set_tearing_allowed(&self, allowed: bool) -> Result<(), RequestError> {
+        // DWM has no per-window tearing control, the closest it offers is scheduling its
+        // composition with MMCSS, which lowers the latency of the presented frames.
+        let hr = unsafe { DwmEnableMMCSS(allowed.into()) };
+        if hr < 0 {
+            return Err(os_error!(io::Error::from_raw_os_error(hr)).into());
+        }
+        Ok(())
+    }
+
     fn set_visible(&self, visible: bool) {
         let window = self.window;
         let window_state = Arc::clone(&self.window_state);
@@ -475,7 +503,10 @@ impl CoreWindow for Window {
                  rust-windowing/winit"
             )
         }
-        PhysicalSize::new((rect.right - rect.left) as u32, (rect.bottom - rect.top) as u32)
+        let current =
+            PhysicalSize::new((rect.right - rect.left) as u32, (rect.bottom - rect.top) as u32);
+        let minimized = util::is_minimized(self.hwnd());
+        self.window_state_lock().last_surface_size.surface_size(current, minimized)
     }
 
     fn outer_size(&self) -> PhysicalSize<u32> {
@@ -512,6 +543,10 @@ impl CoreWindow for Window {
         PhysicalInsets::new(0, 0, 0, 0)
     }
 
+    fn surface_transform(&self) -> SurfaceTransform {
+        SurfaceTransform::Identity
+    }
+
     fn set_min_surface_size(&self, size: Option<Size>) {
         self.window_state_lock().min_size = size;
         // Make windows re-check the window size bounds.
@@ -583,6 +618,7 @@ impl CoreWindow for Window {
     }
 
     fn set_cursor(&self, cursor: Cursor) {
+        self.current_cursor.set(&cursor);
         match cursor {
             Cursor::Icon(icon) => {
                 self.window_state_lock().mouse.selected_cursor = SelectedCursor::Named(icon);
@@ -601,6 +637,10 @@ impl CoreWindow for Window {
         }
     }
 
+    fn current_cursor(&self) -> Cursor {
+        self.current_cursor.get()
+    }
+
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
         let confine = match mode {
             CursorGrabMode::None => false,
@@ -646,6 +686,21 @@ impl CoreWindow for Window {
         rx.recv().unwrap().ok();
     }
 
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        let window_state = self.window_state_lock();
+        let cursor_flags = window_state.mouse.cursor_flags();
+        InputStateSnapshot {
+            cursor_grab: if cursor_flags.contains(CursorFlags::GRABBED) {
+                CursorGrabMode::Confined
+            } else {
+                CursorGrabMode::None
+            },
+            cursor_visible: !cursor_flags.contains(CursorFlags::HIDDEN),
+            cursor: self.current_cursor.get(),
+            ime_allowed: window_state.ime_allowed,
+        }
+    }
+
     fn scale_factor(&self) -> f64 {
         self.window_state_lock().scale_factor
     }
@@ -666,6 +721,35 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    fn animate_cursor_to(
+        &self,
+        target: PhysicalPosition<i32>,
+        duration: Duration,
+    ) -> Result<(), RequestError> {
+        let mut point = POINT { x: 0, y: 0 };
+        unsafe {
+            if GetCursorPos(&mut point) == false.into() {
+                return Err(os_error!(io::Error::last_os_error()).into());
+            }
+            if ScreenToClient(self.hwnd(), &mut point) == false.into() {
+                return Err(os_error!(io::Error::last_os_error()).into());
+            }
+        }
+
+        let from = PhysicalPosition::new(point.x, point.y);
+        self.window_state_lock().cursor_animation =
+            Some(CursorAnimation::new(from, target, Instant::now(), duration));
+
+        // The timer is stepped by the thread owning the window, where it must be set.
+        let window = self.window;
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let interval = CursorAnimation::FRAME_INTERVAL.as_millis() as u32;
+            SetTimer(window.hwnd(), CURSOR_ANIMATION_TIMER_ID, interval, None);
+        });
+
+        Ok(())
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         unsafe {
             self.handle_os_dragging(HTCAPTION as WPARAM);
@@ -751,6 +835,14 @@ impl CoreWindow for Window {
         window_state.window_flags.contains(WindowFlags::MAXIMIZED)
     }
 
+    fn workspace(&self) -> Option<u32> {
+        None
+    }
+
+    fn move_to_workspace(&self, _index: u32) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("move_to_workspace is not supported").into())
+    }
+
     fn fullscreen(&self) -> Option<CoreFullscreen> {
         let window_state = self.window_state_lock();
         window_state.fullscreen.clone().map(Into::into)
@@ -786,45 +878,36 @@ impl CoreWindow for Window {
             // fullscreen
             match (&old_fullscreen, &fullscreen) {
                 (_, Some(Fullscreen::Exclusive(monitor, video_mode))) => {
-                    let monitor_info = monitor::get_monitor_info(monitor.hmonitor()).unwrap();
-                    let video_mode =
-                        match monitor.video_mode_handles().find(|mode| &mode.mode == video_mode) {
-                            Some(monitor) => monitor,
-                            None => return,
-                        };
-
-                    let res = unsafe {
-                        ChangeDisplaySettingsExW(
-                            monitor_info.szDevice.as_ptr(),
-                            &*video_mode.native_video_mode,
-                            ptr::null_mut(),
-                            CDS_FULLSCREEN,
-                            ptr::null(),
-                        )
+                    let device = monitor::get_monitor_info(monitor.hmonitor()).unwrap().szDevice;
+                    let video_mode = match find_exclusive_mode(
+                        monitor.video_mode_handles(),
+                        video_mode,
+                        |mode| &mode.mode,
+                    ) {
+                        Some(video_mode) => video_mode,
+                        None => return,
                     };
 
-                    debug_assert!(res != DISP_CHANGE_BADFLAGS);
-                    debug_assert!(res != DISP_CHANGE_BADMODE);
-                    debug_assert!(res != DISP_CHANGE_BADPARAM);
-                    debug_assert!(res != DISP_CHANGE_FAILED);
-                    assert_eq!(res, DISP_CHANGE_SUCCESSFUL);
+                    // Don't hold the lock while changing modes, `WM_DISPLAYCHANGE` is sent to the
+                    // window synchronously.
+                    let restore = window_state.lock().unwrap().display_mode.begin(&device);
+                    if let Some(restore) = restore {
+                        monitor::restore_display_mode(&restore);
+                    }
+
+                    // Changing the mode blanks the display for a moment, skip it when the display
+                    // already uses the requested mode.
+                    if monitor.current_video_mode().as_ref() != Some(&video_mode.mode)
+                        && monitor::change_display_mode(&device, &video_mode.native_video_mode)
+                    {
+                        window_state.lock().unwrap().display_mode.changed(device);
+                    }
                 },
                 (Some(Fullscreen::Exclusive(..)), _) => {
-                    let res = unsafe {
-                        ChangeDisplaySettingsExW(
-                            ptr::null(),
-                            ptr::null(),
-                            ptr::null_mut(),
-                            CDS_FULLSCREEN,
-                            ptr::null(),
-                        )
-                    };
-
-                    debug_assert!(res != DISP_CHANGE_BADFLAGS);
-                    debug_assert!(res != DISP_CHANGE_BADMODE);
-                    debug_assert!(res != DISP_CHANGE_BADPARAM);
-                    debug_assert!(res != DISP_CHANGE_FAILED);
-                    assert_eq!(res, DISP_CHANGE_SUCCESSFUL);
+                    let restore = window_state.lock().unwrap().display_mode.take();
+                    if let Some(restore) = restore {
+                        monitor::restore_display_mode(&restore);
+                    }
                 },
                 _ => (),
             }
@@ -982,6 +1065,39 @@ impl CoreWindow for Window {
 
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.window_state_lock().key_repeat_enabled = enabled;
+    }
+
+    fn keyboard_layout_name(&self) -> Option<String> {
+        self.window_state_lock().keyboard_layout.clone()
+    }
+
+    fn keyboard_layout_direction(&self) -> Option<TextDirection> {
+        let layout = self.window_state_lock().keyboard_layout.clone()?;
+        keyboard_layout::layout_direction(&layout)
+    }
+
+    fn key_for_physical(&self, key: PhysicalKey) -> Option<Key> {
+        let thread_id = unsafe { GetWindowThreadProcessId(self.hwnd(), ptr::null_mut()) };
+        let hkl = unsafe { GetKeyboardLayout(thread_id) } as u64;
+        LAYOUT_CACHE.lock().unwrap().get_layout(hkl).key_for_physical(key)
+    }
+
+    fn compose_dead_key(&self, dead: char, base: char) -> Option<char> {
+        let thread_id = unsafe { GetWindowThreadProcessId(self.hwnd(), ptr::null_mut()) };
+        let hkl = unsafe { GetKeyboardLayout(thread_id) } as u64;
+        LAYOUT_CACHE.lock().unwrap().get_layout(hkl).compose_dead_key(dead, base)
+    }
+
+    fn current_modifiers(&self) -> ModifiersState {
+        LAYOUT_CACHE.lock().unwrap().get_mods().state()
+    }
+
+    fn set_hit_test_regions(&self, regions: &[HitTestRegion]) {
+        self.window_state_lock().hit_test_regions = regions.to_vec();
+    }
+
     fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let window = self.window;
         let active_window_handle = unsafe { GetActiveWindow() };
@@ -1129,6 +1245,8 @@ impl InitData<'_> {
             window: SyncWindowHandle(window),
             window_state,
             thread_executor: self.event_loop.create_thread_executor(),
+            current_cursor: CurrentCursor::default(),
+            common_state: Arc::new(CommonWindowState::new(&self.attributes)),
         }
     }
 
@@ -16,11 +16,15 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     WS_MINIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP, WS_SIZEBOX, WS_SYSMENU, WS_VISIBLE,
 };
 
+use crate::cursor::CursorAnimation;
 use crate::dpi::{PhysicalPosition, PhysicalSize, Size};
+use crate::event::Modifiers;
 use crate::icon::Icon;
-use crate::keyboard::ModifiersState;
-use crate::platform_impl::platform::{event_loop, util, Fullscreen, SelectedCursor};
-use crate::window::{Theme, WindowAttributes};
+use crate::platform_impl::platform::monitor::DisplayModeChange;
+use crate::platform_impl::platform::{
+    event_loop, keyboard_layout, util, Fullscreen, SelectedCursor,
+};
+use crate::window::{HitTestRegion, Theme, WindowAttributes};
 
 /// Contains information about states and the window that the callback is going to use.
 pub(crate) struct WindowState {
@@ -36,9 +40,11 @@ pub(crate) struct WindowState {
     pub taskbar_icon: Option<Icon>,
 
     pub saved_window: Option<SavedWindow>,
+    /// The display whose video mode was changed for exclusive fullscreen.
+    pub display_mode: DisplayModeChange,
     pub scale_factor: f64,
 
-    pub modifiers_state: ModifiersState,
+    pub modifiers_state: Modifiers,
     pub fullscreen: Option<Fullscreen>,
     pub current_theme: Theme,
     pub preferred_theme: Option<Theme>,
@@ -48,6 +54,12 @@ pub(crate) struct WindowState {
     pub ime_state: ImeState,
     pub ime_allowed: bool,
 
+    pub key_repeat_enabled: bool,
+    pub keyboard_layout: Option<String>,
+
+    // Reported from `WM_NCHITTEST`.
+    pub hit_test_regions: Vec<HitTestRegion>,
+
     // Used by WM_NCACTIVATE, WM_SETFOCUS and WM_KILLFOCUS
     pub is_active: bool,
     pub is_focused: bool,
@@ -57,7 +69,13 @@ pub(crate) struct WindowState {
 
     pub dragging: bool,
 
+    /// Stepped by `WM_TIMER` until it finishes or the user moves the mouse.
+    pub cursor_animation: Option<CursorAnimation>,
+
     pub skip_taskbar: bool,
+
+    /// Reported in place of the empty client area while the window is minimized.
+    pub last_surface_size: LastSurfaceSize,
 }
 
 #[derive(Clone)]
@@ -136,6 +154,42 @@ pub enum ImeState {
     Preedit,
 }
 
+/// The last size of a window's surface while it wasn't minimized.
+///
+/// Windows reports a zero-sized surface while the window is minimized, which renderers may not
+/// be able to configure their swapchain with, so it is replaced by the last one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LastSurfaceSize(Option<PhysicalSize<u32>>);
+
+impl LastSurfaceSize {
+    /// Record a size change, returning the size to emit in
+    /// [`WindowEvent::SurfaceResized`][crate::event::WindowEvent::SurfaceResized], if any.
+    pub(crate) fn update(
+        &mut self,
+        size: PhysicalSize<u32>,
+        minimized: bool,
+    ) -> Option<PhysicalSize<u32>> {
+        if minimized {
+            return None;
+        }
+        self.0 = Some(size);
+        Some(size)
+    }
+
+    /// The size to report from [`Window::surface_size`][crate::window::Window::surface_size] for
+    /// the `current` size of the surface.
+    pub(crate) fn surface_size(
+        &self,
+        current: PhysicalSize<u32>,
+        minimized: bool,
+    ) -> PhysicalSize<u32> {
+        match self.0 {
+            Some(last) if minimized => last,
+            _ => current,
+        }
+    }
+}
+
 impl WindowState {
     pub(crate) fn new(
         attributes: &WindowAttributes,
@@ -160,9 +214,10 @@ impl WindowState {
             taskbar_icon: None,
 
             saved_window: None,
+            display_mode: DisplayModeChange::default(),
             scale_factor,
 
-            modifiers_state: ModifiersState::default(),
+            modifiers_state: Modifiers::default(),
             fullscreen: None,
             current_theme,
             preferred_theme,
@@ -171,13 +226,22 @@ impl WindowState {
             ime_state: ImeState::Disabled,
             ime_allowed: false,
 
+            key_repeat_enabled: true,
+            keyboard_layout: keyboard_layout::current_layout_name(),
+
+            hit_test_regions: Vec::new(),
+
             is_active: false,
             is_focused: false,
             redraw_requested: false,
 
             dragging: false,
 
+            cursor_animation: None,
+
             skip_taskbar: false,
+
+            last_surface_size: LastSurfaceSize::default(),
         }
     }
 
@@ -529,3 +593,29 @@ impl CursorFlags {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimizing_keeps_last_surface_size() {
+        let mut last = LastSurfaceSize::default();
+        let (size, larger, zero) =
+            (PhysicalSize::new(800, 600), PhysicalSize::new(1024, 768), PhysicalSize::new(0, 0));
+
+        assert_eq!(last.update(size, false), Some(size));
+        assert_eq!(last.surface_size(size, false), size);
+
+        // Minimizing doesn't emit a resize, and the last size is retained.
+        assert_eq!(last.update(zero, true), None);
+        assert_eq!(last.surface_size(zero, true), size);
+
+        // Restoring emits the size again.
+        assert_eq!(last.update(larger, false), Some(larger));
+        assert_eq!(last.surface_size(larger, false), larger);
+
+        // Without a previous size, the current one is all there is.
+        assert_eq!(LastSurfaceSize::default().surface_size(zero, true), zero);
+    }
+}
@@ -44,3 +44,54 @@ impl<T: Any> AsAny for T {
         self
     }
 }
+
+/// Serializes the opaque identifiers carried by events through their raw value, with
+/// `#[serde(with = "crate::utils::serde_raw")]`, without making the identifiers themselves
+/// serializable.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_raw {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// An identifier with a raw value.
+    pub(crate) trait Raw: Sized {
+        type Raw: Serialize + DeserializeOwned;
+
+        fn to_raw(&self) -> Self::Raw;
+
+        fn from_raw(raw: Self::Raw) -> Self;
+    }
+
+    pub(crate) fn serialize<T: Raw, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_raw().serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, T: Raw, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::Raw::deserialize(deserializer).map(T::from_raw)
+    }
+
+    /// The same, for an optional identifier.
+    pub(crate) mod option {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        use super::Raw;
+
+        pub(crate) fn serialize<T: Raw, S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value.as_ref().map(T::to_raw).serialize(serializer)
+        }
+
+        pub(crate) fn deserialize<'de, T: Raw, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<T>, D::Error> {
+            Ok(Option::<T::Raw>::deserialize(deserializer)?.map(T::from_raw))
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! The [`Window`] struct and associated types.
-use std::fmt;
+use std::collections::HashSet;
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+use std::{fmt, mem};
 
 #[doc(inline)]
 pub use cursor_icon::{CursorIcon, ParseError as CursorIconParseError};
@@ -9,7 +12,9 @@ use serde::{Deserialize, Serialize};
 pub use crate::cursor::{BadImage, Cursor, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE};
 use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::RequestError;
+use crate::event::WindowEvent;
 pub use crate::icon::{BadIcon, Icon};
+use crate::keyboard::{Key, ModifiersState, PhysicalKey, TextDirection};
 use crate::monitor::{MonitorHandle, VideoMode};
 use crate::platform_impl::PlatformSpecificWindowAttributes;
 use crate::utils::AsAny;
@@ -39,6 +44,19 @@ impl WindowId {
     }
 }
 
+#[cfg(feature = "serde")]
+impl crate::utils::serde_raw::Raw for WindowId {
+    type Raw = usize;
+
+    fn to_raw(&self) -> usize {
+        self.0
+    }
+
+    fn from_raw(raw: usize) -> Self {
+        Self(raw)
+    }
+}
+
 impl fmt::Debug for WindowId {
     fn fmt(&self, fmtr: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(fmtr)
@@ -62,10 +80,13 @@ pub struct WindowAttributes {
     pub blur: bool,
     pub decorations: bool,
     pub window_icon: Option<Icon>,
+    pub placeholder_image: Option<Icon>,
     pub preferred_theme: Option<Theme>,
     pub content_protected: bool,
     pub window_level: WindowLevel,
     pub active: bool,
+    pub redraw_while_occluded: bool,
+    pub user_tag: Option<u64>,
     pub cursor: Cursor,
     pub(crate) parent_window: Option<SendSyncRawWindowHandle>,
     pub fullscreen: Option<Fullscreen>,
@@ -94,11 +115,14 @@ impl Default for WindowAttributes {
             decorations: true,
             window_level: Default::default(),
             window_icon: None,
+            placeholder_image: None,
             preferred_theme: None,
             content_protected: false,
             cursor: Cursor::default(),
             parent_window: None,
             active: true,
+            redraw_while_occluded: true,
+            user_tag: None,
             platform_specific: Default::default(),
         }
     }
@@ -377,6 +401,66 @@ impl WindowAttributes {
         self
     }
 
+    /// Sets an image that is shown in the window until the application presents its first frame.
+    ///
+    /// The image is centered and scaled to fit the surface while keeping its aspect ratio, with
+    /// the remaining area filled with black. This avoids showing garbage or a flash of the
+    /// background color while the application prepares its first frame.
+    ///
+    /// The placeholder is repainted whenever the surface is resized, until the first
+    /// [`WindowEvent::RedrawRequested`] is delivered or [`Window::pre_present_notify()`] is called,
+    /// whichever comes first. It then stays on screen until the application presents a frame.
+    ///
+    /// The default is `None`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Windows / X11 / iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn with_placeholder_image(mut self, image: Icon) -> Self {
+        self.placeholder_image = Some(image);
+        self
+    }
+
+    /// Whether [`Window::request_redraw()`] is honored while the window is occluded.
+    ///
+    /// When `false`, redraw requests made while the window is fully occluded or minimized are
+    /// held back, and a single [`WindowEvent::RedrawRequested`] is delivered once the window
+    /// becomes visible again. See [`WindowEvent::Occluded`] for when a window is considered
+    /// occluded.
+    ///
+    /// The default is `true`, so redraws are always delivered.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / Wayland / iOS / Android / Web / Orbital:** Unsupported, redraw requests are
+    ///   always delivered. Only macOS and X11 hold them back.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`WindowEvent::Occluded`]: crate::event::WindowEvent::Occluded
+    #[inline]
+    pub fn with_redraw_while_occluded(mut self, redraw_while_occluded: bool) -> Self {
+        self.redraw_while_occluded = redraw_while_occluded;
+        self
+    }
+
+    /// Attaches a tag of your choosing to the window.
+    ///
+    /// The tag is opaque to winit, which only stores it. It's meant to hold e.g. an index into
+    /// your own window storage, and can be retrieved from the window with [`user_tag`], and from
+    /// the [`WindowId`] of an event with [`ActiveEventLoop::window_tag()`], which spares the
+    /// lookup of the window from its ID.
+    ///
+    /// The default is `None`.
+    ///
+    /// [`user_tag`]: trait.Window.html#method.user_tag
+    /// [`ActiveEventLoop::window_tag()`]: ../event_loop/trait.ActiveEventLoop.html#method.window_tag
+    #[inline]
+    pub fn with_user_tag(mut self, tag: u64) -> Self {
+        self.user_tag = Some(tag);
+        self
+    }
+
     /// Modifies the cursor icon of the window.
     ///
     /// The default is [`CursorIcon::Default`].
@@ -514,6 +598,9 @@ pub trait Window: AsAny + Send + Sync {
     /// However as the event aligns with the windowing system drawing loop, it may not arrive in
     /// same or even next event loop iteration.
     ///
+    /// On macOS and X11, requests made while the window is occluded can be held back with
+    /// [`WindowAttributes::with_redraw_while_occluded`].
+    ///
     /// ## Platform-specific
     ///
     /// - **Windows** This API uses `RedrawWindow` to request a `WM_PAINT` message and
@@ -659,10 +746,16 @@ pub trait Window: AsAny + Send + Sync {
     /// ## Platform-specific
     ///
     /// - **Web:** Returns the size of the canvas element. Doesn't account for CSS [`transform`].
+    /// - **Wayland:** Already reflects the size of the initial configure right after the window was
+    ///   created, see [`ActiveEventLoop::create_window()`].
+    /// - **Windows:** While the window is minimized, returns the size it had before being minimized
+    ///   instead of the zero size of the hidden client area. A window created minimized reports a
+    ///   zero size until it is restored.
     ///
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
     /// [`safe_area()`]: Window::safe_area
+    /// [`ActiveEventLoop::create_window()`]: crate::event_loop::ActiveEventLoop::create_window
     fn surface_size(&self) -> PhysicalSize<u32>;
 
     /// Request the new size for the surface.
@@ -730,9 +823,13 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// If the entire content of the surface is visible, this returns `(0, 0, 0, 0)`.
     ///
+    /// [`WindowEvent::SafeAreaChanged`] is emitted when the safe area changes, e.g. on rotation.
+    ///
     /// ## Platform-specific
     ///
-    /// - **Android / Orbital / Wayland / Windows / X11:** Unimplemented, returns `(0, 0, 0, 0)`.
+    /// - **Android:** The part of the surface outside of the content rectangle of the activity,
+    ///   which excludes the system bars and the display cutouts.
+    /// - **Orbital / Wayland / Windows / X11:** Unimplemented, returns `(0, 0, 0, 0)`.
     ///
     /// ## Examples
     ///
@@ -756,6 +853,27 @@ pub trait Window: AsAny + Send + Sync {
     /// ```
     fn safe_area(&self) -> PhysicalInsets<u32>;
 
+    /// Returns the transform the compositor expects the content of the surface to be rendered
+    /// with.
+    ///
+    /// This is only reported by Wayland compositors, typically on rotated displays of embedded
+    /// devices: rendering with this transform avoids a composition pass when presenting.
+    /// [`WindowEvent::SurfaceTransformChanged`] is emitted when it changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The `preferred_buffer_transform` of the surface, or the transform of its
+    ///   output when `wl_compositor` is older than version 6. The renderer tells the compositor
+    ///   which transform it applied with `wl_surface.set_buffer_transform`.
+    /// - **Android:** Unsupported, always [`SurfaceTransform::Identity`]. The NDK doesn't expose
+    ///   the rotation of the display, Vulkan applications have to read the `currentTransform` of
+    ///   the surface capabilities and pass it as the `preTransform` of the swapchain.
+    /// - **iOS / macOS / Windows / X11 / Web / Orbital:** Always [`SurfaceTransform::Identity`],
+    ///   the compositor presents the content as is.
+    ///
+    /// [`WindowEvent::SurfaceTransformChanged`]: crate::event::WindowEvent::SurfaceTransformChanged
+    fn surface_transform(&self) -> SurfaceTransform;
+
     /// Sets a minimum dimensions of the window's surface.
     ///
     /// ```no_run
@@ -816,8 +934,16 @@ pub trait Window: AsAny + Send + Sync {
 
     /// Modifies the title of the window.
     ///
+    /// The title is also the accessible name of the window, and assistive technologies such as
+    /// screen readers are notified of the change.
+    ///
     /// ## Platform-specific
     ///
+    /// - **macOS:** Posts `NSAccessibilityTitleChangedNotification`.
+    /// - **Windows:** The system raises `EVENT_OBJECT_NAMECHANGE`, which UI Automation exposes as a
+    ///   change of the `Name` property.
+    /// - **X11 / Wayland:** Winit doesn't provide an AT-SPI tree, the window manager or compositor
+    ///   exposes the new title. Use a crate such as AccessKit to expose the window's content.
     /// - **iOS / Android:** Unsupported.
     fn set_title(&self, title: &str);
 
@@ -848,6 +974,24 @@ pub trait Window: AsAny + Send + Sync {
     /// - **Wayland:** Only works with org_kde_kwin_blur_manager protocol.
     fn set_blur(&self, blur: bool);
 
+    /// Sets whether the compositor may present the window's content with tearing.
+    ///
+    /// Allowing tearing lets the compositor present new frames as soon as they are ready instead
+    /// of waiting for the next vertical blank, which lowers the input latency, e.g. of a game
+    /// rendering with vsync disabled. It's only a hint, the compositor may keep presenting without
+    /// tearing. Tearing is not allowed by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Uses the `wp_tearing_control_v1` protocol and returns
+    ///   [`RequestError::NotSupported`] when the compositor doesn't support it. The hint takes
+    ///   effect with the next commit of the surface, usually the next presented frame.
+    /// - **Windows:** Best-effort, opts the process in or out of the MMCSS scheduling of the DWM
+    ///   composition, which affects all the windows of the process. Actual tearing is requested by
+    ///   the renderer, with the `DXGI_PRESENT_ALLOW_TEARING` flag of its swap chain.
+    /// - **macOS / iOS / X11 / Web / Android / Orbital:** Returns [`RequestError::NotSupported`].
+    fn set_tearing_allowed(&self, allowed: bool) -> Result<(), RequestError>;
+
     /// Modifies the window's visibility.
     ///
     /// If `false`, this will hide the window. If `true`, this will show the window.
@@ -945,6 +1089,35 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Web:** Unsupported.
     fn is_maximized(&self) -> bool;
 
+    /// Returns the index of the workspace (also called virtual desktop) the window is on.
+    ///
+    /// `None` is returned if the window is shown on all workspaces, or if the workspace couldn't
+    /// be determined.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Reads the `_NET_WM_DESKTOP` property, which the window manager may only set once
+    ///   the window is mapped.
+    /// - **Wayland:** Always `None`, the protocols exposing virtual desktops are restricted to
+    ///   privileged clients such as task bars.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Always `None`.
+    fn workspace(&self) -> Option<u32>;
+
+    /// Moves the window to the workspace (also called virtual desktop) at `index`.
+    ///
+    /// Workspaces are numbered from `0`. The window manager may choose not to honor the request,
+    /// so check [`Window::workspace()`] afterwards if it matters.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Uses the `_NET_WM_DESKTOP` hint. Returns [`RequestError::Ignored`] if `index` is
+    ///   not below `_NET_NUMBER_OF_DESKTOPS`, and [`RequestError::NotSupported`] if the window
+    ///   manager doesn't support workspaces. When called before the window is shown, the window is
+    ///   placed on that workspace once it's mapped.
+    /// - **Wayland / Windows / macOS / iOS / Android / Web / Orbital:** Unsupported, always returns
+    ///   [`RequestError::NotSupported`].
+    fn move_to_workspace(&self, index: u32) -> Result<(), RequestError>;
+
     /// Set the window's fullscreen state.
     ///
     /// ## Platform-specific
@@ -962,6 +1135,14 @@ pub trait Window: AsAny + Send + Sync {
     ///   The dock and the menu bar are disabled in exclusive fullscreen mode.
     /// - **Wayland:** Does not support exclusive fullscreen mode and will no-op a request.
     /// - **Windows:** Screen saver is disabled in fullscreen mode.
+    ///
+    ///   [`Fullscreen::Exclusive`] changes the video mode of the display with
+    ///   `ChangeDisplaySettingsExW`, unless the display already uses the requested mode, and
+    ///   restores it when the window leaves exclusive fullscreen, moves to another display or is
+    ///   destroyed. The mode change blanks the display for a moment and disturbs the other windows,
+    ///   so unless a different resolution or refresh rate is needed, prefer
+    ///   [`Fullscreen::Borderless`]: a flip model swap chain covering the display is presented
+    ///   without composition, with the same latency as exclusive fullscreen.
     /// - **Android / Orbital:** Unsupported.
     /// - **Web:** Passing a [`MonitorHandle`] or [`VideoMode`] that was not created with
     #[cfg_attr(
@@ -1063,7 +1244,9 @@ pub trait Window: AsAny + Send + Sync {
     /// ## Platform-specific
     ///
     /// - **X11:** Area is not supported, only position. The bottom-right corner of the provided
-    ///   area is reported as the position.
+    ///   area is reported as the position, so the candidate box appears below it. When a candidate
+    ///   box of an assumed height wouldn't fit between the area and the bottom of the monitor's
+    ///   work area, a position above the area is reported instead.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     ///
     /// [chinese]: https://support.apple.com/guide/chinese-input-method/use-the-candidate-window-cim12992/104/mac/12.0
@@ -1082,6 +1265,11 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// IME is **not** allowed by default.
     ///
+    /// The IME state is scoped to the window: allowing IME, the [cursor area] and an ongoing
+    /// composition of one window never affect another window. In particular, a composition
+    /// doesn't carry over to another window when the focus moves; on X11 and Wayland it is
+    /// cancelled with an empty [`Ime::Preedit`] or with [`Ime::Disabled`].
+    ///
     /// ## Platform-specific
     ///
     /// - **macOS:** IME must be enabled to receive text-input where dead-key sequences are
@@ -1092,6 +1280,9 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// [`Ime`]: crate::event::WindowEvent::Ime
     /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    /// [cursor area]: Window::set_ime_cursor_area
+    /// [`Ime::Preedit`]: crate::event::Ime::Preedit
+    /// [`Ime::Disabled`]: crate::event::Ime::Disabled
     fn set_ime_allowed(&self, allowed: bool);
 
     /// Sets the IME purpose for the window using [`ImePurpose`].
@@ -1101,6 +1292,119 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
     fn set_ime_purpose(&self, purpose: ImePurpose);
 
+    /// Sets whether key repeats held down by the user are reported for this window.
+    ///
+    /// When disabled, pressing and holding a key only emits a single
+    /// [`KeyboardInput`] press event, those with [`KeyEvent::repeat`] set are dropped at the
+    /// source. This only affects [`KeyboardInput`] events of this window, text produced through
+    /// [`Ime`] events (including composition) is delivered as usual.
+    ///
+    /// Key repeat is enabled by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    /// [`KeyEvent::repeat`]: crate::event::KeyEvent::repeat
+    /// [`Ime`]: crate::event::WindowEvent::Ime
+    fn set_key_repeat_enabled(&self, enabled: bool);
+
+    /// Returns the name of the active keyboard layout.
+    ///
+    /// [`WindowEvent::KeyboardLayoutChanged`] is emitted when it changes. The format of the
+    /// string is platform-specific and shouldn't be parsed, only compared or shown to the user.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** The XKB layout name of the active group, e.g. `"English (US)"`. On
+    ///   Wayland, `None` until the window had keyboard focus.
+    /// - **macOS:** The input source identifier, e.g. `"com.apple.keylayout.US"`.
+    /// - **Windows:** The layout identifier returned by `GetKeyboardLayoutName`, e.g. `"00000409"`.
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always returns `None`.
+    ///
+    /// [`WindowEvent::KeyboardLayoutChanged`]: crate::event::WindowEvent::KeyboardLayoutChanged
+    fn keyboard_layout_name(&self) -> Option<String>;
+
+    /// Returns the direction of the text typed with the active keyboard layout.
+    ///
+    /// [`WindowEvent::KeyboardLayoutDirectionChanged`] is emitted when it changes. This is derived
+    /// from the language of the layout, and is meant for laying out the text being typed, e.g. to
+    /// right-align an empty text field when switching to an Arabic layout.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Derived from the XKB layout name, as returned by
+    ///   [`keyboard_layout_name`][Self::keyboard_layout_name], which only recognizes the layouts of
+    ///   common languages and the variants naming their script. `None` when the layout name is
+    ///   unknown or not recognized.
+    /// - **macOS:** Derived from the first language of the input source.
+    /// - **Windows:** Derived from the language of the layout identifier.
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always returns `None`.
+    ///
+    /// [`WindowEvent::KeyboardLayoutDirectionChanged`]: crate::event::WindowEvent::KeyboardLayoutDirectionChanged
+    fn keyboard_layout_direction(&self) -> Option<TextDirection>;
+
+    /// Returns the key that the physical key produces without modifiers in the active keyboard
+    /// layout.
+    ///
+    /// This is the label to show for a key binding stored as a [`PhysicalKey`], e.g. `"z"` for
+    /// [`KeyCode::KeyW`] on an AZERTY layout. Query it again on
+    /// [`WindowEvent::KeyboardLayoutChanged`] to keep the label up to date.
+    ///
+    /// Returns `None` for [`PhysicalKey::Unidentified`], and for keys the layout doesn't define.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** `None` until the window had keyboard focus.
+    /// - **Windows:** Uses the layout of the thread that created the window.
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always returns `None`.
+    ///
+    /// [`KeyCode::KeyW`]: crate::keyboard::KeyCode::KeyW
+    /// [`WindowEvent::KeyboardLayoutChanged`]: crate::event::WindowEvent::KeyboardLayoutChanged
+    fn key_for_physical(&self, key: PhysicalKey) -> Option<Key>;
+
+    /// Returns the character that typing the dead key `dead` followed by `base` composes in the
+    /// active keyboard layout, e.g. `'é'` for `'´'` and `'e'`.
+    ///
+    /// `dead` is the character of a [`Key::Dead`], and `base` the character typed after it. This
+    /// allows applications that handle [`Key::Dead`] themselves, like terminal emulators or games
+    /// with text input, to compose the character the same way the platform would.
+    ///
+    /// Returns `None` when the layout doesn't have this dead key, or when the sequence doesn't
+    /// compose a single character.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Uses the XKB compose table of the locale, for the dead keys and
+    ///   characters of the active layout. On Wayland, `None` until the window had keyboard focus.
+    /// - **macOS:** Uses `UCKeyTranslate` with the selected input source.
+    /// - **Windows:** Uses `ToUnicodeEx` with the layout of the thread that created the window. A
+    ///   dead key pending on the calling thread is typed again afterwards, so that it still applies
+    ///   to the next key press.
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always returns `None`.
+    fn compose_dead_key(&self, dead: char, base: char) -> Option<char>;
+
+    /// Queries the state of the modifiers from the platform.
+    ///
+    /// [`WindowEvent::ModifiersChanged`] is only emitted when the modifiers change, so this
+    /// allows knowing the modifiers held before the window was created or focused, e.g.
+    /// <kbd>Shift</kbd> held when launching the application.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Queries the state of the core keyboard with `XkbGetState`, regardless of the
+    ///   focus. Scroll Lock is not reported.
+    /// - **Wayland:** The compositor only sends the modifiers to the focused window, this returns
+    ///   the modifiers of the last [`WindowEvent::ModifiersChanged`], or empty modifiers when the
+    ///   window doesn't have the keyboard focus.
+    /// - **macOS:** Uses `NSEvent.modifierFlags`, regardless of the focus.
+    /// - **Windows:** Uses `GetKeyState`, which reflects the input processed by the calling thread.
+    /// - **Web:** The modifiers of the last input event, or empty modifiers when the canvas doesn't
+    ///   have the focus.
+    /// - **iOS / Android / Orbital:** Unsupported, always returns empty modifiers.
+    fn current_modifiers(&self) -> ModifiersState;
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -1183,8 +1487,24 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Orbital:** Unsupported.
     /// - **Web:** Custom cursors have to be loaded and decoded first, until then the previous
     ///   cursor is shown.
+    /// - **Windows:** Icons without a system cursor fall back to the closest one, e.g.
+    ///   [`CursorIcon::Cell`] to the crosshair and [`CursorIcon::VerticalText`] to the I-beam, or
+    ///   to the arrow if there is none.
+    /// - **macOS:** Icons without a system cursor fall back to the arrow.
+    /// - **X11 / Wayland:** The icon is looked up in the cursor theme by its [CSS name] and its
+    ///   alternative names, so the available icons depend on the theme.
+    ///
+    /// [CSS name]: CursorIcon::name
     fn set_cursor(&self, cursor: Cursor);
 
+    /// Returns the cursor last set with [`Window::set_cursor()`], or [`Cursor::default()`] if
+    /// none was set yet.
+    ///
+    /// This is the value cached by winit, not a query of the cursor currently shown by the
+    /// system, so it is cheap to call, e.g. to skip redundant calls to [`Window::set_cursor()`]
+    /// or to restore the cursor after a temporary change.
+    fn current_cursor(&self) -> Cursor;
+
     /// Changes the position of the cursor in window coordinates.
     ///
     /// ```no_run
@@ -1205,6 +1525,33 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError>;
 
+    /// Smoothly moves the cursor to `target`, in window coordinates, over `duration`.
+    ///
+    /// The warps are driven from the event loop, so the animation only progresses while the event
+    /// loop is running. It is cancelled as soon as the user moves the pointer themselves, and a
+    /// new call replaces any animation that is still in progress.
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use winit::dpi::PhysicalPosition;
+    /// # use winit::window::Window;
+    /// # fn scope(window: &dyn Window) {
+    /// window.animate_cursor_to(PhysicalPosition::new(400, 200), Duration::from_millis(250));
+    /// # }
+    /// ```
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The warps are driven by a timer of the window's thread.
+    /// - **Wayland:** The cursor jumps to `target`, see
+    ///   [`set_cursor_position`][Self::set_cursor_position].
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
+    fn animate_cursor_to(
+        &self,
+        target: PhysicalPosition<i32>,
+        duration: Duration,
+    ) -> Result<(), RequestError>;
+
     /// Set grabbing [mode][CursorGrabMode] on the cursor preventing it from leaving the window.
     ///
     /// # Example
@@ -1236,6 +1583,20 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android:** Unsupported.
     fn set_cursor_visible(&self, visible: bool);
 
+    /// Captures the cursor grab mode, the cursor visibility, the cursor and whether IME is
+    /// allowed.
+    ///
+    /// The returned snapshot can later be passed to [`restore_input_state`] to go back to this
+    /// state in one go, for example when leaving a pause menu.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android:** The cursor is always reported as visible and not grabbed.
+    /// - **Web / Orbital:** IME is always reported as not allowed.
+    ///
+    /// [`restore_input_state`]: #method.restore_input_state
+    fn capture_input_state(&self) -> InputStateSnapshot;
+
     /// Moves the window with the left mouse button until the button is released.
     ///
     /// There's no guarantee that this will work unless the left mouse button was pressed
@@ -1271,6 +1632,40 @@ pub trait Window: AsAny + Send + Sync {
     /// [window menu]: https://en.wikipedia.org/wiki/Common_menus_in_Microsoft_Windows#System_menu
     fn show_window_menu(&self, position: Position);
 
+    /// Sets the regions of the surface that act as parts of client-side decorations.
+    ///
+    /// When the primary button is pressed inside a region, winit starts the action of its
+    /// [`HitTestRole`] itself: a [`Drag`] region moves the window like [`Window::drag_window`],
+    /// a [`ResizeEdge`] region resizes it like [`Window::drag_resize_window`], and a [`Button`]
+    /// region closes, minimizes or toggles the maximized state of the window. Closing sends
+    /// [`WindowEvent::CloseRequested`] instead of destroying the window.
+    ///
+    /// Regions are in surface coordinates. When regions overlap, the one that comes later in
+    /// `regions` wins, so a [`Client`] region can be put on top of a title bar to keep e.g. a
+    /// text field inside of it interactive. Points outside of every region behave like
+    /// [`Client`]. Passing an empty slice removes all regions, which is the default.
+    ///
+    /// The regions only start an action for the presses that hit them, so don't call
+    /// [`Window::drag_window`] or [`Window::drag_resize_window`] for those presses yourself;
+    /// both keep working as before for the presses in [`Client`] areas.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** The [`PointerButton`] event of the press is still delivered, before the
+    ///   action is started.
+    /// - **Windows:** The regions are reported from `WM_NCHITTEST`, so the system handles the
+    ///   press, [`Button`] regions act on release like native caption buttons, and no
+    ///   [`PointerButton`] event is delivered for presses outside of [`Client`] areas.
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`Drag`]: HitTestRole::Drag
+    /// [`ResizeEdge`]: HitTestRole::ResizeEdge
+    /// [`Button`]: HitTestRole::Button
+    /// [`Client`]: HitTestRole::Client
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    /// [`PointerButton`]: crate::event::WindowEvent::PointerButton
+    fn set_hit_test_regions(&self, regions: &[HitTestRegion]);
+
     /// Modifies whether the window catches cursor events.
     ///
     /// If `true`, the window will catch the cursor events. If `false`, events are passed through
@@ -1341,6 +1736,589 @@ impl dyn Window {
     }
 }
 
+impl dyn Window + '_ {
+    /// Restores the input state captured with [`Window::capture_input_state`].
+    ///
+    /// Only the parts of the state that differ from the current one are changed. The IME, the
+    /// cursor and its visibility are restored even if restoring the cursor grab fails, in which
+    /// case the error from [`Window::set_cursor_grab`] is returned.
+    ///
+    /// ```no_run
+    /// # use winit::window::Window;
+    /// # fn scope(window: &dyn Window) {
+    /// // Entering the pause menu.
+    /// let snapshot = window.capture_input_state();
+    /// window.set_cursor_visible(true);
+    /// let _ = window.set_cursor_grab(winit::window::CursorGrabMode::None);
+    ///
+    /// // Leaving it again.
+    /// window.restore_input_state(snapshot).unwrap();
+    /// # }
+    /// ```
+    pub fn restore_input_state(&self, snapshot: InputStateSnapshot) -> Result<(), RequestError> {
+        restore_input_state(self, snapshot)
+    }
+
+    /// Applies an input state captured from another window, such as one this window replaces.
+    ///
+    /// Unlike [`restore_input_state`], every part of the snapshot is applied, without comparing it
+    /// to the state the window reports. A freshly created window may not report its state
+    /// accurately until it has been mapped and focused. The IME, the cursor and its visibility are
+    /// applied even if applying the cursor grab fails, in which case the error from
+    /// [`Window::set_cursor_grab`] is returned; the grab can be retried once the window is
+    /// focused.
+    ///
+    /// See [`InputStateSnapshot`] for what is restored.
+    ///
+    /// ```no_run
+    /// # use winit::event_loop::ActiveEventLoop;
+    /// # use winit::window::{Window, WindowAttributes};
+    /// # fn scope(event_loop: &dyn ActiveEventLoop, old: Box<dyn Window>) {
+    /// let snapshot = old.capture_input_state();
+    /// drop(old);
+    ///
+    /// let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+    /// let _ = window.apply_input_state(snapshot);
+    /// # }
+    /// ```
+    ///
+    /// [`restore_input_state`]: #method.restore_input_state
+    pub fn apply_input_state(&self, snapshot: InputStateSnapshot) -> Result<(), RequestError> {
+        apply_input_state(self, snapshot)
+    }
+
+    /// Returns a snapshot of the window's position, sizes, scale factor and monitor.
+    ///
+    /// This is meant for persisting the window state, where querying each value separately
+    /// risks mixing values from before and after the window was moved.
+    ///
+    /// The backends have no way to query all of these at once, so the values are read one after
+    /// another. If the window's position changed while doing so, the snapshot is taken again, up
+    /// to a few times. A resize racing with the snapshot can thus still produce a mix of old and
+    /// new values, but they are consistent once the window is at rest.
+    pub fn geometry(&self) -> WindowGeometry {
+        capture_geometry(self)
+    }
+
+    /// Hides the window, including its taskbar entry.
+    ///
+    /// Unlike [`Window::set_minimized`], which keeps the window reachable from the taskbar, the
+    /// user has no way to bring a hidden window back. This is meant for applications minimizing
+    /// to the system tray, which call [`show_and_activate`] when the tray icon is clicked.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / Wayland / Web:** Unsupported, see [`Window::set_visible`].
+    ///
+    /// [`show_and_activate`]: #method.show_and_activate
+    pub fn hide(&self) {
+        Window::set_visible(self, false)
+    }
+
+    /// Shows a window hidden with [`hide`], un-minimizes it, and brings it to the front with
+    /// focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / Wayland / Web:** Showing the window is unsupported, see
+    ///   [`Window::set_visible`].
+    /// - **iOS / Android / Web / Orbital:** Un-minimizing is unsupported, see
+    ///   [`Window::set_minimized`].
+    /// - **iOS / Android / Wayland / Orbital:** Focusing is unsupported, see
+    ///   [`Window::focus_window`].
+    ///
+    /// [`hide`]: #method.hide
+    pub fn show_and_activate(&self) {
+        Window::set_visible(self, true);
+
+        // The window may have been minimized before being hidden, e.g. by the user.
+        if Window::is_minimized(self) == Some(true) {
+            Window::set_minimized(self, false);
+        }
+
+        Window::focus_window(self);
+    }
+
+    /// Shows the [`CursorIcon::Wait`] cursor until the returned guard is dropped.
+    ///
+    /// This is meant for the duration of a long operation. Guards nest: while any guard for the
+    /// window is alive, the busy cursor stays, and dropping the last one, in whichever order they
+    /// are dropped, restores the cursor that was current when the first one was acquired, as
+    /// returned by [`Window::current_cursor`]. A cursor set with [`Window::set_cursor`] in the
+    /// meantime is thus replaced when the last guard is dropped.
+    ///
+    /// ```no_run
+    /// # use winit::window::Window;
+    /// # fn scope(window: &dyn Window) {
+    /// let _busy = window.busy_cursor();
+    /// // Load the file...
+    /// # }
+    /// ```
+    pub fn busy_cursor(&self) -> BusyGuard<'_> {
+        if let Some(state) = CommonWindowState::of(self) {
+            if state.busy_cursor.lock().unwrap().acquire(|| self.current_cursor()) {
+                self.set_cursor(CursorIcon::Wait.into());
+            }
+        }
+        BusyGuard { window: self }
+    }
+
+    /// Returns a receiver for the surface size and scale factor changes of the window, for use on
+    /// another thread.
+    ///
+    /// This supports running the renderer on a dedicated thread while the event loop handles the
+    /// input: hand the receiver to the render thread along with the window, which implements
+    /// [`HasWindowHandle`] and is [`Send`] and [`Sync`], and reconfigure the surface there. The
+    /// notifications are sent by the event loop right before the corresponding
+    /// [`WindowEvent::SurfaceResized`] and [`WindowEvent::ScaleFactorChanged`] reach the
+    /// [`ApplicationHandler`], so the handler doesn't need to forward them. Each call returns an
+    /// independent receiver, and once the window is destroyed, [`ResizeReceiver::recv`] returns
+    /// `None`.
+    ///
+    /// Only the notification is moved off the main thread. Calling other methods of [`Window`]
+    /// from the render thread may block on the main thread, see [`Window`]. Changing the surface
+    /// size in response to [`WindowEvent::ScaleFactorChanged`] still has to happen in the
+    /// handler, on the main thread.
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use winit::window::{ResizeNotification, Window};
+    /// # fn scope(window: Arc<dyn Window>) {
+    /// let resizes = window.resize_notifier();
+    /// std::thread::spawn(move || {
+    ///     let _window = window;
+    ///     while let Some(notification) = resizes.recv() {
+    ///         if let ResizeNotification::SurfaceResized(size) = notification {
+    ///             // Reconfigure the swapchain...
+    ///         }
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// [`HasWindowHandle`]: rwh_06::HasWindowHandle
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    /// [`ApplicationHandler`]: crate::application::ApplicationHandler
+    pub fn resize_notifier(&self) -> ResizeReceiver {
+        let (sender, receiver) = mpsc::channel();
+        if let Some(state) = CommonWindowState::of(self) {
+            state.resize_senders.lock().unwrap().push(sender);
+        }
+        ResizeReceiver { receiver }
+    }
+
+    /// Returns the physical keys that are currently held down while the window has focus.
+    ///
+    /// This is meant for polling, e.g. once per frame in a game, instead of accumulating
+    /// [`WindowEvent::KeyboardInput`] events yourself. The keys are [`PhysicalKey`]s, so the set
+    /// doesn't depend on the keyboard layout or on the modifiers.
+    ///
+    /// The set is tracked from the keyboard events delivered to the window, including the
+    /// synthetic ones, and is up to date with the event currently being handled. It is cleared
+    /// when the window loses focus, as the window doesn't receive the releases that happen while
+    /// it's unfocused; keys that are still held when the focus comes back are only reported again
+    /// if the backend sends synthetic presses for them, or once they repeat.
+    ///
+    /// The set is only maintained while the event loop is running through the
+    /// [`ApplicationHandler`].
+    ///
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    /// [`ApplicationHandler`]: crate::application::ApplicationHandler
+    pub fn held_keys(&self) -> HashSet<PhysicalKey> {
+        CommonWindowState::of(self)
+            .map(|state| state.held_keys.lock().unwrap().clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the tag the window was created with, see [`WindowAttributes::with_user_tag()`].
+    pub fn user_tag(&self) -> Option<u64> {
+        CommonWindowState::of(self).and_then(|state| state.user_tag)
+    }
+
+    /// Sets which key repeats are delivered to the window, see [`KeyRepeatFilter`].
+    ///
+    /// The filter is applied to the [`WindowEvent::KeyboardInput`] events before they reach the
+    /// [`ApplicationHandler`], and only while the event loop is running through it. The default
+    /// is [`KeyRepeatFilter::DeliverAll`].
+    ///
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    /// [`ApplicationHandler`]: crate::application::ApplicationHandler
+    pub fn set_key_repeat_filter(&self, filter: KeyRepeatFilter) {
+        if let Some(state) = CommonWindowState::of(self) {
+            *state.key_repeat_filter.lock().unwrap() = filter;
+        }
+    }
+
+    /// Returns the filter set with [`set_key_repeat_filter`].
+    ///
+    /// [`set_key_repeat_filter`]: #method.set_key_repeat_filter
+    pub fn key_repeat_filter(&self) -> KeyRepeatFilter {
+        CommonWindowState::of(self)
+            .map(|state| *state.key_repeat_filter.lock().unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Returns the positions that were coalesced into the last [`WindowEvent::PointerMoved`]
+    /// delivered to the window, in order and ending with the position of the event itself.
+    ///
+    /// This is meant for applications that need the whole path of the pointer, e.g. for drawing,
+    /// while [`EventLoopBuilder::with_pointer_move_coalescing()`] only delivers the latest
+    /// position per event loop iteration. It is always empty when the coalescing is disabled.
+    ///
+    /// [`WindowEvent::PointerMoved`]: crate::event::WindowEvent::PointerMoved
+    /// [`EventLoopBuilder::with_pointer_move_coalescing()`]: crate::event_loop::EventLoopBuilder::with_pointer_move_coalescing
+    pub fn pointer_move_history(&self) -> Vec<PhysicalPosition<f64>> {
+        CommonWindowState::of(self)
+            .map(|state| state.pointer_move_history.lock().unwrap().clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns a short label for the physical key, to show it to the user, e.g. in the settings
+    /// of a key binding stored as a [`PhysicalKey`].
+    ///
+    /// The label is derived from the key the physical key produces in the active keyboard layout,
+    /// as returned by [`key_for_physical`]: characters are uppercased like on keycaps (`"Z"` for
+    /// [`KeyCode::KeyW`] on an AZERTY layout), numpad keys are prefixed (`"Num 1"`, `"Num +"`),
+    /// arrows are shown as `"↑"`, `"↓"`, `"←"` and `"→"`, and some keys are abbreviated, e.g.
+    /// `"Esc"`, `"Ctrl"` or `"PgUp"`. Other named keys use their [`NamedKey::name`].
+    ///
+    /// When the layout doesn't define the key, or [`key_for_physical`] is unsupported, the label
+    /// is the one of the key on a US layout, or the [`KeyCode::name`] for the keys without one.
+    /// Returns `None` for [`PhysicalKey::Unidentified`], as there is nothing meaningful to show
+    /// for a native scancode; display its [`NativeKeyCode`] instead if needed.
+    ///
+    /// [`key_for_physical`]: Window::key_for_physical
+    /// [`KeyCode::KeyW`]: crate::keyboard::KeyCode::KeyW
+    /// [`KeyCode::name`]: crate::keyboard::KeyCode::name
+    /// [`NamedKey::name`]: crate::keyboard::NamedKey::name
+    /// [`NativeKeyCode`]: crate::keyboard::NativeKeyCode
+    pub fn physical_key_label(&self, key: PhysicalKey) -> Option<String> {
+        let PhysicalKey::Code(code) = key else {
+            return None;
+        };
+        Some(code.label(self.key_for_physical(key).as_ref()))
+    }
+}
+
+/// Guard returned by [`busy_cursor`], see its documentation for details.
+///
+/// [`busy_cursor`]: trait.Window.html#method.busy_cursor
+#[must_use = "the cursor is restored as soon as the guard is dropped"]
+pub struct BusyGuard<'a> {
+    window: &'a dyn Window,
+}
+
+impl fmt::Debug for BusyGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BusyGuard").field("window", &self.window.id()).finish()
+    }
+}
+
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        let Some(state) = CommonWindowState::of(self.window) else { return };
+        let restore = state.busy_cursor.lock().unwrap().release();
+        if let Some(cursor) = restore {
+            self.window.set_cursor(cursor);
+        }
+    }
+}
+
+/// A change of the window's surface, as delivered by a [`ResizeReceiver`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResizeNotification {
+    /// The surface was resized, see [`WindowEvent::SurfaceResized`].
+    ///
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    SurfaceResized(PhysicalSize<u32>),
+    /// The scale factor changed, see [`WindowEvent::ScaleFactorChanged`].
+    ///
+    /// A [`SurfaceResized`][Self::SurfaceResized] notification follows if the surface size
+    /// changed along with it.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    ScaleFactorChanged(f64),
+}
+
+/// Receives the [`ResizeNotification`]s of a window on another thread, see
+/// [`resize_notifier`].
+///
+/// [`resize_notifier`]: trait.Window.html#method.resize_notifier
+#[derive(Debug)]
+pub struct ResizeReceiver {
+    receiver: mpsc::Receiver<ResizeNotification>,
+}
+
+impl ResizeReceiver {
+    /// Returns the next notification without blocking, if any.
+    pub fn try_recv(&self) -> Option<ResizeNotification> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Blocks until the next notification, or returns `None` once the window was destroyed and
+    /// all notifications were received.
+    pub fn recv(&self) -> Option<ResizeNotification> {
+        self.receiver.recv().ok()
+    }
+
+    /// Like [`recv`][Self::recv], but gives up after `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ResizeNotification> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+}
+
+/// The state Winit keeps for each window on top of the one of the backend.
+///
+/// The backends hold it in their window, and register it with the [`CommonEventLoopState`] of
+/// the event loop that created the window, which gives the event loop access to it until the
+/// window is destroyed.
+///
+/// [`CommonEventLoopState`]: crate::event_loop::CommonEventLoopState
+#[derive(Debug)]
+pub(crate) struct CommonWindowState {
+    /// See [`WindowAttributes::with_user_tag()`].
+    pub(crate) user_tag: Option<u64>,
+    /// See [`set_key_repeat_filter`].
+    ///
+    /// [`set_key_repeat_filter`]: trait.Window.html#method.set_key_repeat_filter
+    key_repeat_filter: Mutex<KeyRepeatFilter>,
+    /// The senders of the [`ResizeReceiver`]s of the window.
+    resize_senders: Mutex<Vec<mpsc::Sender<ResizeNotification>>>,
+    /// See [`held_keys`].
+    ///
+    /// [`held_keys`]: trait.Window.html#method.held_keys
+    held_keys: Mutex<HashSet<PhysicalKey>>,
+    /// The positions coalesced into the last pointer move, see [`pointer_move_history`].
+    ///
+    /// [`pointer_move_history`]: trait.Window.html#method.pointer_move_history
+    pub(crate) pointer_move_history: Mutex<Vec<PhysicalPosition<f64>>>,
+    /// See [`busy_cursor`].
+    ///
+    /// [`busy_cursor`]: trait.Window.html#method.busy_cursor
+    busy_cursor: Mutex<BusyCursor>,
+}
+
+impl CommonWindowState {
+    pub(crate) fn new(attributes: &WindowAttributes) -> Self {
+        Self {
+            user_tag: attributes.user_tag,
+            key_repeat_filter: Mutex::default(),
+            resize_senders: Mutex::default(),
+            held_keys: Mutex::default(),
+            pointer_move_history: Mutex::default(),
+            busy_cursor: Mutex::default(),
+        }
+    }
+
+    /// The state of `window`, or `None` if it wasn't created by one of the backends.
+    pub(crate) fn of(window: &dyn Window) -> Option<&Self> {
+        crate::platform_impl::common_window_state(window).map(|state| &**state)
+    }
+
+    /// Forward `event` to the [`ResizeReceiver`]s of the window, if it's relevant to them.
+    pub(crate) fn notify_resize(&self, event: &WindowEvent) {
+        let notification = match event {
+            WindowEvent::SurfaceResized(size) => ResizeNotification::SurfaceResized(*size),
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                ResizeNotification::ScaleFactorChanged(*scale_factor)
+            },
+            WindowEvent::Destroyed => {
+                // Dropping the senders lets the receivers know that no more notifications
+                // follow, even if the application keeps the window around.
+                self.resize_senders.lock().unwrap().clear();
+                return;
+            },
+            _ => return,
+        };
+
+        // Also drop the senders whose receivers are gone.
+        self.resize_senders.lock().unwrap().retain(|sender| sender.send(notification).is_ok());
+    }
+
+    /// Update the [`held_keys`] of the window with `event`.
+    ///
+    /// [`held_keys`]: trait.Window.html#method.held_keys
+    pub(crate) fn track_held_keys(&self, event: &WindowEvent) {
+        match event {
+            WindowEvent::KeyboardInput { event, .. } => {
+                let mut held_keys = self.held_keys.lock().unwrap();
+                if event.state.is_pressed() {
+                    held_keys.insert(event.physical_key);
+                } else {
+                    held_keys.remove(&event.physical_key);
+                }
+            },
+            WindowEvent::Focused(false) | WindowEvent::Destroyed => {
+                self.held_keys.lock().unwrap().clear()
+            },
+            _ => (),
+        }
+    }
+
+    /// Whether `event` passes the [`key_repeat_filter`] of the window.
+    ///
+    /// [`key_repeat_filter`]: trait.Window.html#method.key_repeat_filter
+    pub(crate) fn filter_key_repeat(&self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::KeyboardInput { event, .. } if event.repeat => {
+                match *self.key_repeat_filter.lock().unwrap() {
+                    KeyRepeatFilter::DeliverAll => true,
+                    KeyRepeatFilter::SuppressRepeats => false,
+                    KeyRepeatFilter::SuppressRepeatsForNonText => event.text.is_some(),
+                }
+            },
+            _ => true,
+        }
+    }
+}
+
+/// The number of live [`BusyGuard`]s of a window, and the cursor to restore once they're gone.
+#[derive(Debug, Default)]
+struct BusyCursor {
+    guards: usize,
+    restore: Cursor,
+}
+
+impl BusyCursor {
+    /// Returns `true` if this is the first guard, saving the cursor to restore.
+    fn acquire(&mut self, current: impl FnOnce() -> Cursor) -> bool {
+        self.guards += 1;
+        if self.guards == 1 {
+            self.restore = current();
+        }
+        self.guards == 1
+    }
+
+    /// Returns the cursor to restore if this was the last guard.
+    fn release(&mut self) -> Option<Cursor> {
+        self.guards = self.guards.checked_sub(1)?;
+        (self.guards == 0).then(|| mem::take(&mut self.restore))
+    }
+}
+
+/// The subset of [`Window`] that [`InputStateSnapshot`] is captured from and restored to.
+trait InputStateTarget {
+    fn capture_input_state(&self) -> InputStateSnapshot;
+    fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError>;
+    fn set_cursor_visible(&self, visible: bool);
+    fn set_cursor(&self, cursor: Cursor);
+    fn set_ime_allowed(&self, allowed: bool);
+}
+
+impl InputStateTarget for dyn Window + '_ {
+    fn capture_input_state(&self) -> InputStateSnapshot {
+        Window::capture_input_state(self)
+    }
+
+    fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
+        Window::set_cursor_grab(self, mode)
+    }
+
+    fn set_cursor_visible(&self, visible: bool) {
+        Window::set_cursor_visible(self, visible)
+    }
+
+    fn set_cursor(&self, cursor: Cursor) {
+        Window::set_cursor(self, cursor)
+    }
+
+    fn set_ime_allowed(&self, allowed: bool) {
+        Window::set_ime_allowed(self, allowed)
+    }
+}
+
+fn restore_input_state<T: InputStateTarget + ?Sized>(
+    target: &T,
+    snapshot: InputStateSnapshot,
+) -> Result<(), RequestError> {
+    let current = target.capture_input_state();
+
+    if current.ime_allowed != snapshot.ime_allowed {
+        target.set_ime_allowed(snapshot.ime_allowed);
+    }
+
+    if current.cursor_visible != snapshot.cursor_visible {
+        target.set_cursor_visible(snapshot.cursor_visible);
+    }
+
+    if current.cursor != snapshot.cursor {
+        target.set_cursor(snapshot.cursor);
+    }
+
+    if current.cursor_grab != snapshot.cursor_grab {
+        target.set_cursor_grab(snapshot.cursor_grab)?;
+    }
+
+    Ok(())
+}
+
+fn apply_input_state<T: InputStateTarget + ?Sized>(
+    target: &T,
+    snapshot: InputStateSnapshot,
+) -> Result<(), RequestError> {
+    target.set_ime_allowed(snapshot.ime_allowed);
+    target.set_cursor_visible(snapshot.cursor_visible);
+    target.set_cursor(snapshot.cursor);
+    target.set_cursor_grab(snapshot.cursor_grab)
+}
+
+/// The subset of [`Window`] that [`WindowGeometry`] is captured from.
+trait GeometrySource {
+    fn outer_position(&self) -> Option<PhysicalPosition<i32>>;
+    fn outer_size(&self) -> PhysicalSize<u32>;
+    fn surface_size(&self) -> PhysicalSize<u32>;
+    fn scale_factor(&self) -> f64;
+    fn current_monitor(&self) -> Option<MonitorHandle>;
+}
+
+impl GeometrySource for dyn Window + '_ {
+    fn outer_position(&self) -> Option<PhysicalPosition<i32>> {
+        Window::outer_position(self).ok()
+    }
+
+    fn outer_size(&self) -> PhysicalSize<u32> {
+        Window::outer_size(self)
+    }
+
+    fn surface_size(&self) -> PhysicalSize<u32> {
+        Window::surface_size(self)
+    }
+
+    fn scale_factor(&self) -> f64 {
+        Window::scale_factor(self)
+    }
+
+    fn current_monitor(&self) -> Option<MonitorHandle> {
+        Window::current_monitor(self)
+    }
+}
+
+fn capture_geometry<T: GeometrySource + ?Sized>(source: &T) -> WindowGeometry {
+    // How many times to retry when the window moved during the snapshot.
+    const MAX_ATTEMPTS: usize = 3;
+
+    let mut outer_position = source.outer_position();
+    let mut attempt = 1;
+    loop {
+        let geometry = WindowGeometry {
+            outer_position,
+            outer_size: source.outer_size(),
+            surface_size: source.surface_size(),
+            scale_factor: source.scale_factor(),
+            current_monitor: source.current_monitor(),
+        };
+
+        let position_after = source.outer_position();
+        if position_after == outer_position || attempt == MAX_ATTEMPTS {
+            return geometry;
+        }
+
+        outer_position = position_after;
+        attempt += 1;
+    }
+}
+
 impl PartialEq for dyn Window + '_ {
     fn eq(&self, other: &dyn Window) -> bool {
         self.id().eq(&other.id())
@@ -1400,6 +2378,64 @@ pub enum CursorGrabMode {
     Locked,
 }
 
+/// The input state of a window, as captured by [`Window::capture_input_state`].
+///
+/// Bundles everything a pause menu typically toggles, so it can be restored with a single call to
+/// [`restore_input_state`], or carried over to a recreated window with [`apply_input_state`].
+/// Cursor confinement is covered by [`CursorGrabMode::Confined`].
+///
+/// The cursor grab, the cursor visibility, the cursor and whether IME is allowed are restored. The
+/// IME cursor area is not part of the snapshot and has to be set again on a recreated window.
+///
+/// [`restore_input_state`]: trait.Window.html#method.restore_input_state
+/// [`apply_input_state`]: trait.Window.html#method.apply_input_state
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InputStateSnapshot {
+    /// The mode the cursor is grabbed with, see [`Window::set_cursor_grab`].
+    pub cursor_grab: CursorGrabMode,
+    /// Whether the cursor is visible, see [`Window::set_cursor_visible`].
+    pub cursor_visible: bool,
+    /// The cursor, see [`Window::set_cursor`].
+    ///
+    /// This is not serialized, since a custom cursor only lives as long as its event loop.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cursor: Cursor,
+    /// Whether IME is allowed, see [`Window::set_ime_allowed`].
+    pub ime_allowed: bool,
+}
+
+impl Default for InputStateSnapshot {
+    /// The input state of a newly created window.
+    fn default() -> Self {
+        Self {
+            cursor_grab: CursorGrabMode::None,
+            cursor_visible: true,
+            cursor: Cursor::default(),
+            ime_allowed: false,
+        }
+    }
+}
+
+/// The geometry of a window, as captured by [`geometry`].
+///
+/// [`geometry`]: trait.Window.html#method.geometry
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowGeometry {
+    /// The position of the window, see [`Window::outer_position`].
+    ///
+    /// `None` if the platform doesn't support it.
+    pub outer_position: Option<PhysicalPosition<i32>>,
+    /// The size of the window including decorations, see [`Window::outer_size`].
+    pub outer_size: PhysicalSize<u32>,
+    /// The inner size of the window, see [`Window::surface_size`].
+    pub surface_size: PhysicalSize<u32>,
+    /// The scale factor of the window, see [`Window::scale_factor`].
+    pub scale_factor: f64,
+    /// The monitor the window is on, see [`Window::current_monitor`].
+    pub current_monitor: Option<MonitorHandle>,
+}
+
 /// Defines the orientation that a window resize will be performed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1430,12 +2466,88 @@ impl From<ResizeDirection> for CursorIcon {
     }
 }
 
+/// A region of the surface with a [`HitTestRole`], see [`Window::set_hit_test_regions`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HitTestRegion {
+    /// The top-left corner of the region in surface coordinates.
+    pub position: PhysicalPosition<i32>,
+    /// The size of the region.
+    pub size: PhysicalSize<u32>,
+    /// What a press inside of the region does.
+    pub role: HitTestRole,
+}
+
+impl HitTestRegion {
+    /// Creates a region covering `size` from `position` onwards.
+    pub const fn new(
+        position: PhysicalPosition<i32>,
+        size: PhysicalSize<u32>,
+        role: HitTestRole,
+    ) -> Self {
+        Self { position, size, role }
+    }
+
+    fn contains(&self, position: PhysicalPosition<f64>) -> bool {
+        let x = position.x - self.position.x as f64;
+        let y = position.y - self.position.y as f64;
+        (0.0..self.size.width as f64).contains(&x) && (0.0..self.size.height as f64).contains(&y)
+    }
+}
+
+/// The role of a [`HitTestRegion`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HitTestRole {
+    /// Presses are handled by the application.
+    Client,
+    /// Presses move the window, like a title bar.
+    Drag,
+    /// Presses resize the window in the given direction.
+    ResizeEdge(ResizeDirection),
+    /// Presses act like the given title bar button.
+    Button(TitlebarButton),
+}
+
+/// A button of a title bar, see [`HitTestRole::Button`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TitlebarButton {
+    /// Requests the window to be closed.
+    Close,
+    /// Minimizes the window.
+    Minimize,
+    /// Toggles the maximized state of the window.
+    Maximize,
+}
+
+/// Returns the role of the topmost region containing `position`.
+///
+/// `None` means the press is left to the application, either because no region contains it or
+/// because the topmost one is a [`HitTestRole::Client`] region.
+pub(crate) fn hit_test(
+    regions: &[HitTestRegion],
+    position: PhysicalPosition<f64>,
+) -> Option<HitTestRole> {
+    regions
+        .iter()
+        .rev()
+        .find(|region| region.contains(position))
+        .map(|region| region.role)
+        .filter(|role| *role != HitTestRole::Client)
+}
+
 /// Fullscreen modes.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Fullscreen {
     /// This changes the video mode of the monitor for fullscreen windows and,
     /// if applicable, captures the monitor for exclusive use by this
     /// application.
+    ///
+    /// The video mode should be one of [`MonitorHandle::video_modes()`]. It is matched exactly:
+    /// the size, the refresh rate and the bit depth must all be equal, so a mode with the
+    /// requested size and refresh rate but a different bit depth is never substituted. If no
+    /// video mode of the monitor matches, a warning is logged and the video mode is left
+    /// unchanged.
     Exclusive(MonitorHandle, VideoMode),
 
     /// Providing `None` to `Borderless` will fullscreen on the current monitor.
@@ -1453,6 +2565,54 @@ pub enum Theme {
     Dark,
 }
 
+/// The transform to render the content of a window's surface with.
+///
+/// When the display is rotated, some compositors expect the content to be rendered already
+/// rotated, instead of rotating it themselves when presenting it. Rotations are clockwise and the
+/// mirrored variants mirror the content around its vertical axis before rotating it, like the
+/// `VkSurfaceTransformFlagBitsKHR` of Vulkan.
+///
+/// See [`Window::surface_transform`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SurfaceTransform {
+    /// The content is presented as is.
+    #[default]
+    Identity,
+
+    /// The content is rotated by 90 degrees.
+    Rotate90,
+
+    /// The content is rotated by 180 degrees.
+    Rotate180,
+
+    /// The content is rotated by 270 degrees.
+    Rotate270,
+
+    /// The content is mirrored.
+    Mirrored,
+
+    /// The content is mirrored, then rotated by 90 degrees.
+    MirroredRotate90,
+
+    /// The content is mirrored, then rotated by 180 degrees.
+    MirroredRotate180,
+
+    /// The content is mirrored, then rotated by 270 degrees.
+    MirroredRotate270,
+}
+
+impl SurfaceTransform {
+    /// Returns `true` if the transform rotates by 90 or 270 degrees, so that the width and height
+    /// of the rendered content are swapped relative to the surface size.
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::MirroredRotate90 | Self::MirroredRotate270
+        )
+    }
+}
+
 /// ## Platform-specific
 ///
 /// - **X11:** Sets the WM's `XUrgencyHint`. No distinction between [`Critical`] and
@@ -1487,6 +2647,34 @@ bitflags::bitflags! {
     }
 }
 
+/// Which key repeats are delivered to a window, see [`set_key_repeat_filter`].
+///
+/// Only [`WindowEvent::KeyboardInput`] events with [`KeyEvent::repeat`] set are filtered, the
+/// initial presses and the releases are always delivered. [`WindowEvent::Ime`] events are never
+/// filtered; while IME composition is active the keys are usually consumed by the input method and
+/// not delivered as [`WindowEvent::KeyboardInput`] in the first place, so holding a key during
+/// composition is up to the input method.
+///
+/// [`set_key_repeat_filter`]: trait.Window.html#method.set_key_repeat_filter
+/// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+/// [`WindowEvent::Ime`]: crate::event::WindowEvent::Ime
+/// [`KeyEvent::repeat`]: crate::event::KeyEvent::repeat
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum KeyRepeatFilter {
+    /// Every repeat is delivered.
+    #[default]
+    DeliverAll,
+    /// No repeat is delivered, e.g. for the action keys of a game.
+    SuppressRepeats,
+    /// Only the repeats of keys producing [`KeyEvent::text`] are delivered, so holding a letter
+    /// in a text field still types it repeatedly while holding e.g. an arrow key doesn't move
+    /// repeatedly.
+    ///
+    /// [`KeyEvent::text`]: crate::event::KeyEvent::text
+    SuppressRepeatsForNonText,
+}
+
 /// A window level groups windows with respect to their z-position.
 ///
 /// The relative ordering between windows in different window levels is fixed.
@@ -1575,3 +2763,249 @@ impl ActivationToken {
         self.token
     }
 }
+
+#[cfg(feature = "serde")]
+impl crate::utils::serde_raw::Raw for ActivationToken {
+    type Raw = String;
+
+    fn to_raw(&self) -> String {
+        self.token.clone()
+    }
+
+    fn from_raw(raw: String) -> Self {
+        Self { token: raw }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    #[test]
+    fn hit_test_resolves_topmost_region() {
+        let region = |x, y, width, height, role| {
+            HitTestRegion::new(PhysicalPosition::new(x, y), PhysicalSize::new(width, height), role)
+        };
+        let regions = [
+            region(0, 0, 800, 32, HitTestRole::Drag),
+            region(0, 0, 800, 4, HitTestRole::ResizeEdge(ResizeDirection::North)),
+            region(768, 0, 32, 32, HitTestRole::Button(TitlebarButton::Close)),
+            region(300, 4, 200, 24, HitTestRole::Client),
+        ];
+        let at = |x, y| hit_test(&regions, PhysicalPosition::new(x, y));
+
+        assert_eq!(at(100.0, 16.0), Some(HitTestRole::Drag));
+        assert_eq!(at(100.0, 2.0), Some(HitTestRole::ResizeEdge(ResizeDirection::North)));
+        // The button is listed after the edge, so it wins in the corner.
+        assert_eq!(at(780.0, 2.0), Some(HitTestRole::Button(TitlebarButton::Close)));
+        // A client region punches a hole into the title bar.
+        assert_eq!(at(400.0, 16.0), None);
+        // The edges are exclusive on the far side.
+        assert_eq!(at(100.0, 32.0), None);
+        assert_eq!(at(-1.0, 16.0), None);
+        assert_eq!(hit_test(&[], PhysicalPosition::new(0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn busy_cursor_nesting() {
+        let mut busy = BusyCursor::default();
+        let text = Cursor::Icon(CursorIcon::Text);
+
+        assert!(busy.acquire(|| text.clone()));
+        // Nested guards don't query the cursor, which is the busy one by now.
+        assert!(!busy.acquire(|| unreachable!()));
+        assert!(!busy.acquire(|| unreachable!()));
+
+        assert_eq!(busy.release(), None);
+        assert_eq!(busy.release(), None);
+        assert_eq!(busy.release(), Some(text.clone()));
+        assert_eq!(busy.release(), None);
+
+        // The state is reset after the last release.
+        assert!(busy.acquire(Cursor::default));
+        assert_eq!(busy.release(), Some(Cursor::default()));
+    }
+
+    #[test]
+    #[cfg(headless_platform)]
+    fn busy_cursor_per_window() {
+        // A real event loop to create the windows with, it isn't run.
+        let headless = crate::platform_impl::headless::EventLoop::new();
+        let event_loop = headless.window_target();
+        let text = Cursor::Icon(CursorIcon::Text);
+        let wait = Cursor::Icon(CursorIcon::Wait);
+        let attributes = WindowAttributes::default().with_cursor(text.clone());
+        let first = event_loop.create_window(attributes).unwrap();
+        let second = event_loop.create_window(WindowAttributes::default()).unwrap();
+
+        let outer = first.busy_cursor();
+        let inner = first.busy_cursor();
+        let other = second.busy_cursor();
+        assert_eq!(first.current_cursor(), wait);
+        assert_eq!(second.current_cursor(), wait);
+
+        // Whichever order the guards are dropped in, the last one of the window restores it.
+        drop(outer);
+        assert_eq!(first.current_cursor(), wait);
+        drop(other);
+        assert_eq!(second.current_cursor(), Cursor::default());
+        drop(inner);
+        assert_eq!(first.current_cursor(), text);
+    }
+
+    struct MockWindow {
+        state: RefCell<InputStateSnapshot>,
+        calls: Cell<usize>,
+    }
+
+    impl MockWindow {
+        fn new(state: InputStateSnapshot) -> Self {
+            Self { state: RefCell::new(state), calls: Cell::new(0) }
+        }
+
+        fn update(&self, f: impl FnOnce(&mut InputStateSnapshot)) {
+            self.calls.set(self.calls.get() + 1);
+            f(&mut self.state.borrow_mut());
+        }
+    }
+
+    impl InputStateTarget for MockWindow {
+        fn capture_input_state(&self) -> InputStateSnapshot {
+            self.state.borrow().clone()
+        }
+
+        fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
+            self.update(|state| state.cursor_grab = mode);
+            Ok(())
+        }
+
+        fn set_cursor_visible(&self, visible: bool) {
+            self.update(|state| state.cursor_visible = visible);
+        }
+
+        fn set_cursor(&self, cursor: Cursor) {
+            self.update(|state| state.cursor = cursor);
+        }
+
+        fn set_ime_allowed(&self, allowed: bool) {
+            self.update(|state| state.ime_allowed = allowed);
+        }
+    }
+
+    #[test]
+    fn input_state_round_trip() {
+        let playing = InputStateSnapshot {
+            cursor_grab: CursorGrabMode::Confined,
+            cursor_visible: false,
+            cursor: CursorIcon::Crosshair.into(),
+            ime_allowed: false,
+        };
+        let window = MockWindow::new(playing.clone());
+
+        // Pause.
+        let snapshot = window.capture_input_state();
+        window.set_cursor_grab(CursorGrabMode::None).unwrap();
+        window.set_cursor_visible(true);
+        window.set_cursor(CursorIcon::Default.into());
+        window.set_ime_allowed(true);
+        assert_ne!(window.capture_input_state(), playing);
+
+        // Resume.
+        window.calls.set(0);
+        restore_input_state(&window, snapshot.clone()).unwrap();
+        assert_eq!(window.capture_input_state(), playing);
+        assert_eq!(window.calls.get(), 4);
+
+        // Restoring the current state is a no-op.
+        window.calls.set(0);
+        restore_input_state(&window, snapshot).unwrap();
+        assert_eq!(window.capture_input_state(), playing);
+        assert_eq!(window.calls.get(), 0);
+    }
+
+    #[test]
+    fn input_state_applies_to_new_window() {
+        let playing = InputStateSnapshot {
+            cursor_grab: CursorGrabMode::Locked,
+            cursor_visible: false,
+            cursor: CursorIcon::Crosshair.into(),
+            ime_allowed: true,
+        };
+        let old = MockWindow::new(playing.clone());
+        let snapshot = old.capture_input_state();
+        drop(old);
+
+        // The new window wrongly reports the state of the old one until it's mapped.
+        let new = MockWindow::new(playing.clone());
+        apply_input_state(&new, snapshot.clone()).unwrap();
+        assert_eq!(new.capture_input_state(), playing);
+        assert_eq!(new.calls.get(), 4);
+
+        let new = MockWindow::new(InputStateSnapshot::default());
+        apply_input_state(&new, snapshot).unwrap();
+        assert_eq!(new.capture_input_state(), playing);
+    }
+
+    /// A window being dragged to another monitor, every query advances the drag by one step.
+    struct DraggedWindow {
+        step: Cell<i32>,
+    }
+
+    impl DraggedWindow {
+        fn advance(&self) -> i32 {
+            let step = self.step.get();
+            self.step.set(step + 1);
+            step
+        }
+
+        /// Whether the window is on the second monitor, with a scale factor of 2.
+        fn on_second_monitor(step: i32) -> bool {
+            step >= 3
+        }
+    }
+
+    impl GeometrySource for DraggedWindow {
+        fn outer_position(&self) -> Option<PhysicalPosition<i32>> {
+            // The drag stops once the window reached the second monitor.
+            let step = self.advance().min(6);
+            Some(PhysicalPosition::new(step * 100, 0))
+        }
+
+        fn outer_size(&self) -> PhysicalSize<u32> {
+            let scale = if Self::on_second_monitor(self.advance()) { 2 } else { 1 };
+            PhysicalSize::new(800 * scale, 630 * scale)
+        }
+
+        fn surface_size(&self) -> PhysicalSize<u32> {
+            let scale = if Self::on_second_monitor(self.advance()) { 2 } else { 1 };
+            PhysicalSize::new(800 * scale, 600 * scale)
+        }
+
+        fn scale_factor(&self) -> f64 {
+            if Self::on_second_monitor(self.advance()) {
+                2.
+            } else {
+                1.
+            }
+        }
+
+        fn current_monitor(&self) -> Option<MonitorHandle> {
+            self.advance();
+            None
+        }
+    }
+
+    #[test]
+    fn geometry_is_consistent() {
+        let window = DraggedWindow { step: Cell::new(0) };
+        let geometry = capture_geometry(&window);
+
+        let scale = geometry.scale_factor as u32;
+        assert_eq!(scale, 2);
+        assert_eq!(geometry.outer_size, PhysicalSize::new(800 * scale, 630 * scale));
+        assert_eq!(geometry.surface_size, PhysicalSize::new(800 * scale, 600 * scale));
+        assert_eq!(geometry.outer_position, Some(PhysicalPosition::new(600, 0)));
+    }
+}
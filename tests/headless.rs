@@ -0,0 +1,270 @@
+#![cfg(feature = "headless")]
+
+use std::thread;
+use std::time::Duration;
+
+use winit::application::ApplicationHandler;
+use winit::dpi::{LogicalSize, PhysicalSize};
+use winit::event::WindowEvent;
+use winit::event_loop::{
+    ActiveEventLoop, EventInjector, EventLoop, FrameStats, WindowingBackend, WindowingCapabilities,
+};
+use winit::platform::headless::EventLoopBuilderExtHeadless;
+use winit::window::{CursorGrabMode, CursorIcon, Window, WindowAttributes, WindowId};
+
+fn run_app(app: impl FnOnce(EventInjector) -> App) -> App {
+    let event_loop = EventLoop::builder().build_headless();
+    let mut app = app(event_loop.create_event_injector());
+    event_loop.run_app(&mut app).unwrap();
+    app
+}
+
+struct App {
+    injector: EventInjector,
+    on_create: fn(&App, &dyn Window),
+    window: Option<Box<dyn Window>>,
+    events: Vec<WindowEvent>,
+}
+
+impl App {
+    fn new(injector: EventInjector, on_create: fn(&App, &dyn Window)) -> Self {
+        Self { injector, on_create, window: None, events: Vec::new() }
+    }
+}
+
+impl ApplicationHandler for App {
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let info = event_loop.windowing_info();
+        assert_eq!(info.backend(), WindowingBackend::Headless);
+        assert_eq!(info.capabilities(), WindowingCapabilities::empty());
+        let attributes =
+            WindowAttributes::default().with_surface_size(LogicalSize::new(320.0, 240.0));
+        let window = event_loop.create_window(attributes).unwrap();
+        (self.on_create)(self, &*window);
+        self.window = Some(window);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        match event {
+            // Close the window once it's drawn.
+            WindowEvent::RedrawRequested => {
+                self.injector.inject(window_id, WindowEvent::CloseRequested)
+            },
+            WindowEvent::CloseRequested => self.window = None,
+            WindowEvent::Destroyed => event_loop.exit(),
+            _ => (),
+        }
+        self.events.push(event);
+    }
+}
+
+#[test]
+fn window_state_and_redraws() {
+    let app = run_app(|injector| {
+        App::new(injector, |app, window| {
+            assert_eq!(window.surface_size(), PhysicalSize::new(320, 240));
+            assert_eq!(window.request_surface_size(PhysicalSize::new(640, 480).into()), None);
+            assert_eq!(window.surface_size(), PhysicalSize::new(640, 480));
+
+            window.request_redraw();
+            app.injector.inject(window.id(), WindowEvent::Focused(true));
+        })
+    });
+
+    // Redraws are delivered after the other events of the iteration.
+    assert_eq!(app.events, [
+        WindowEvent::SurfaceResized(PhysicalSize::new(320, 240)),
+        WindowEvent::SurfaceResized(PhysicalSize::new(640, 480)),
+        WindowEvent::Focused(true),
+        WindowEvent::RedrawRequested,
+        WindowEvent::CloseRequested,
+        WindowEvent::Destroyed,
+    ]);
+}
+
+#[test]
+fn inject_from_another_thread() {
+    let app = run_app(|injector| {
+        App::new(injector, |app, window| {
+            let injector = app.injector.clone();
+            let window_id = window.id();
+            // The event loop waits for the injected event to wake it up.
+            thread::spawn(move || injector.inject(window_id, WindowEvent::CloseRequested));
+        })
+    });
+
+    assert_eq!(app.events, [
+        WindowEvent::SurfaceResized(PhysicalSize::new(320, 240)),
+        WindowEvent::CloseRequested,
+        WindowEvent::Destroyed,
+    ]);
+}
+
+#[test]
+fn user_tag_follows_window() {
+    #[derive(Default)]
+    struct TagApp {
+        window_id: Option<WindowId>,
+        tags: Vec<Option<u64>>,
+    }
+
+    impl ApplicationHandler for TagApp {
+        fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+            let untagged = event_loop.create_window(WindowAttributes::default()).unwrap();
+            assert_eq!(untagged.user_tag(), None);
+            assert_eq!(event_loop.window_tag(untagged.id()), None);
+
+            let window =
+                event_loop.create_window(WindowAttributes::default().with_user_tag(42)).unwrap();
+            assert_eq!(window.user_tag(), Some(42));
+            self.tags.push(event_loop.window_tag(window.id()));
+            self.window_id = Some(window.id());
+        }
+
+        fn window_event(
+            &mut self,
+            event_loop: &dyn ActiveEventLoop,
+            window_id: WindowId,
+            event: WindowEvent,
+        ) {
+            if event == WindowEvent::Destroyed && Some(window_id) == self.window_id {
+                self.tags.push(event_loop.window_tag(window_id));
+            }
+        }
+
+        fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+            self.tags.push(event_loop.window_tag(self.window_id.unwrap()));
+            event_loop.exit();
+        }
+    }
+
+    let mut app = TagApp::default();
+    EventLoop::builder().build_headless().run_app(&mut app).unwrap();
+
+    // The tag remains available while the destruction is handled, and is forgotten afterwards.
+    assert_eq!(app.tags, [Some(42), Some(42), None]);
+}
+
+#[test]
+fn frame_stats_of_redraws() {
+    struct Redraws {
+        window: Option<Box<dyn Window>>,
+        redraws: usize,
+        stats: FrameStats,
+    }
+
+    impl ApplicationHandler for Redraws {
+        fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+            let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+            window.request_redraw();
+            self.window = Some(window);
+        }
+
+        fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, event: WindowEvent) {
+            if event == WindowEvent::RedrawRequested {
+                thread::sleep(Duration::from_millis(2));
+                self.redraws += 1;
+                if self.redraws < 3 {
+                    self.window.as_ref().unwrap().request_redraw();
+                }
+            }
+        }
+
+        fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+            if self.redraws == 3 {
+                self.stats = event_loop.frame_stats();
+                event_loop.exit();
+            }
+        }
+    }
+
+    let run = |frame_stats| {
+        let mut app = Redraws { window: None, redraws: 0, stats: FrameStats::default() };
+        let event_loop = EventLoop::builder().with_frame_stats(frame_stats).build_headless();
+        event_loop.run_app(&mut app).unwrap();
+        app.stats
+    };
+
+    let stats = run(true);
+    assert!(stats.handler_duration >= Duration::from_millis(2));
+    assert!(stats.redraw_interval >= Duration::from_millis(2));
+    // The stats are only collected when enabled.
+    assert_eq!(run(false), FrameStats::default());
+}
+
+#[test]
+fn input_state_carries_over_to_new_window() {
+    struct Recreate;
+
+    impl ApplicationHandler for Recreate {
+        fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+            let old = event_loop.create_window(WindowAttributes::default()).unwrap();
+            old.set_cursor_grab(CursorGrabMode::Locked).unwrap();
+            old.set_cursor_visible(false);
+            old.set_cursor(CursorIcon::Crosshair.into());
+            old.set_ime_allowed(true);
+            let snapshot = old.capture_input_state();
+            drop(old);
+
+            let new = event_loop.create_window(WindowAttributes::default()).unwrap();
+            assert_ne!(new.capture_input_state(), snapshot);
+            new.apply_input_state(snapshot.clone()).unwrap();
+            assert_eq!(new.capture_input_state(), snapshot);
+            assert_eq!(new.current_cursor(), CursorIcon::Crosshair.into());
+
+            event_loop.exit();
+        }
+
+        fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+    }
+
+    EventLoop::builder().build_headless().run_app(&mut Recreate).unwrap();
+}
+
+#[test]
+fn hide_and_show_and_activate() {
+    struct Tray;
+
+    impl ApplicationHandler for Tray {
+        fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+            let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+            window.hide();
+            assert_eq!(window.is_visible(), Some(false));
+            window.show_and_activate();
+            assert_eq!(window.is_visible(), Some(true));
+
+            // A window minimized before being sent to the tray comes back restored.
+            window.set_minimized(true);
+            window.hide();
+            assert_eq!(window.is_visible(), Some(false));
+            assert_eq!(window.is_minimized(), Some(true));
+            window.show_and_activate();
+            assert_eq!(window.is_visible(), Some(true));
+            assert_eq!(window.is_minimized(), Some(false));
+
+            event_loop.exit();
+        }
+
+        fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+    }
+
+    EventLoop::builder().build_headless().run_app(&mut Tray).unwrap();
+}
+
+#[test]
+#[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform))]
+fn led_state_of_unsupported_window() {
+    use winit::platform::led_state::{LedState, WindowExtLedState};
+
+    run_app(|injector| {
+        App::new(injector, |_, window| {
+            assert_eq!(window.led_state(), LedState::Unknown);
+            window.request_redraw();
+        })
+    });
+}
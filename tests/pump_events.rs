@@ -0,0 +1,45 @@
+#![cfg(wayland_platform)]
+
+use std::time::{Duration, Instant};
+
+use winit::application::ApplicationHandler;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+use winit::window::WindowId;
+
+#[derive(Default)]
+struct App {
+    woken_up: bool,
+}
+
+impl ApplicationHandler for App {
+    fn can_create_surfaces(&mut self, _: &dyn ActiveEventLoop) {}
+
+    fn proxy_wake_up(&mut self, _: &dyn ActiveEventLoop) {
+        self.woken_up = true;
+    }
+
+    fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+}
+
+#[test]
+#[ignore = "needs a running Wayland compositor"]
+fn wayland_dispatch_pending_doesnt_block() {
+    let mut event_loop = EventLoop::builder().with_wayland().with_any_thread(true).build().unwrap();
+    event_loop.create_proxy().wake_up();
+
+    let mut app = App::default();
+    let start = Instant::now();
+    assert!(matches!(event_loop.dispatch_pending(&mut app), PumpStatus::Continue));
+    assert!(app.woken_up);
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // Nothing is pending anymore, which must not block either.
+    app.woken_up = false;
+    let start = Instant::now();
+    assert!(matches!(event_loop.dispatch_pending(&mut app), PumpStatus::Continue));
+    assert!(!app.woken_up);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
@@ -1,17 +1,30 @@
 #![cfg(feature = "serde")]
 
+use std::fmt::Debug;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase};
+use winit::event::{
+    ButtonSource, DeviceEvent, DeviceId, ElementState, MouseButton, MouseScrollDelta, PointerKind,
+    PointerSource, RawKeyEvent, TabletTool, TouchPhase, WindowEvent,
+};
 use winit::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
-use winit::window::CursorIcon;
+use winit::window::{CursorIcon, InputStateSnapshot};
 
 #[allow(dead_code)]
 fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value, "{json}");
+}
+
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<InputStateSnapshot>();
 }
 
 #[test]
@@ -20,7 +33,10 @@ fn events_serde() {
     needs_serde::<ElementState>();
     needs_serde::<MouseButton>();
     needs_serde::<MouseScrollDelta>();
+    needs_serde::<TabletTool>();
     needs_serde::<Key>();
+    // The borrowed form returned by `Key::as_ref()`.
+    needs_serde::<Key<&'static str>>();
     needs_serde::<NamedKey>();
     needs_serde::<KeyCode>();
     needs_serde::<PhysicalKey>();
@@ -36,3 +52,129 @@ fn dpi_serde() {
     needs_serde::<LogicalSize<f64>>();
     needs_serde::<PhysicalSize<u32>>();
 }
+
+#[test]
+fn key_serialize_borrowed() {
+    let keys = [Key::Character("a".into()), Key::Named(NamedKey::Enter)];
+    for key in keys {
+        let json = serde_json::to_string(&key.as_ref()).unwrap();
+        assert_eq!(json, serde_json::to_string(&key).unwrap());
+        assert_eq!(serde_json::from_str::<Key>(&json).unwrap(), key, "{json}");
+    }
+}
+
+#[test]
+fn key_deserialize_borrowed() {
+    let input = r#"[{"Character":"a"},{"Named":"Enter"}]"#;
+    let keys: Vec<Key<&str>> = serde_json::from_str(input).unwrap();
+    assert_eq!(keys, [Key::Character("a"), Key::Named(NamedKey::Enter)]);
+
+    // The character points into the input, it wasn't copied.
+    let Key::Character(text) = keys[0] else { unreachable!() };
+    assert!(input.as_bytes().as_ptr_range().contains(&text.as_ptr()));
+
+    let keys: Vec<Key> = keys.into_iter().map(Key::into_owned).collect();
+    assert_eq!(keys, [Key::Character("a".into()), Key::Named(NamedKey::Enter)]);
+
+    // Escaped strings can't be borrowed, but can still be deserialized into owned keys.
+    let input = r#"{"Character":"\u00e9"}"#;
+    assert!(serde_json::from_str::<Key<&str>>(input).is_err());
+    assert_eq!(serde_json::from_str::<Key>(input).unwrap(), Key::Character("é".into()));
+}
+
+#[test]
+fn window_event_round_trip() {
+    let device_id = Some(DeviceId::from_raw(7));
+    let position = PhysicalPosition::new(10.5, 20.0);
+
+    round_trip(WindowEvent::Focused(true));
+    round_trip(WindowEvent::SurfaceResized(PhysicalSize::new(800, 600)));
+    round_trip(WindowEvent::PointerEntered {
+        device_id,
+        position,
+        primary: true,
+        kind: PointerKind::Mouse,
+    });
+    round_trip(WindowEvent::PointerMoved {
+        device_id: None,
+        position,
+        primary: true,
+        source: PointerSource::Mouse,
+        timestamp: Some(Duration::from_millis(16)),
+    });
+    round_trip(WindowEvent::PointerButton {
+        device_id,
+        state: ElementState::Pressed,
+        position,
+        primary: false,
+        button: ButtonSource::Mouse(MouseButton::Right),
+        timestamp: None,
+    });
+    round_trip(WindowEvent::MouseWheel {
+        device_id,
+        delta: MouseScrollDelta::LineDelta(0.0, -1.0),
+        phase: TouchPhase::Moved,
+        momentum_phase: None,
+    });
+}
+
+#[test]
+fn window_event_identifiers_are_raw() {
+    let event = WindowEvent::PointerEntered {
+        device_id: Some(DeviceId::from_raw(7)),
+        position: PhysicalPosition::new(0.0, 0.0),
+        primary: true,
+        kind: PointerKind::Mouse,
+    };
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["PointerEntered"]["device_id"], 7);
+
+    // Identifiers that can't be created outside of winit still round-trip through their value.
+    let json =
+        r#"{"PointerLeft":{"device_id":null,"position":null,"primary":true,"kind":{"Touch":3}}}"#;
+    let event: WindowEvent = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&event).unwrap(), json);
+    let json = r#"{"ActivationTokenDone":{"serial":5,"token":"token"}}"#;
+    let event: WindowEvent = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&event).unwrap(), json);
+}
+
+#[test]
+fn device_event_round_trip() {
+    round_trip(DeviceEvent::PointerMotion { delta: (1.0, -2.5) });
+    round_trip(DeviceEvent::MouseWheel { delta: MouseScrollDelta::PixelDelta((0.0, 3.0).into()) });
+    round_trip(DeviceEvent::Key(RawKeyEvent {
+        physical_key: PhysicalKey::Code(KeyCode::KeyA),
+        state: ElementState::Released,
+        repeat: false,
+    }));
+}
+
+#[test]
+#[cfg(feature = "test-util")]
+fn recorded_event_round_trip() {
+    use winit::event::KeyEvent;
+    use winit::event_loop::RecordedEvent;
+    use winit::window::WindowId;
+
+    let event = KeyEvent::synthetic(
+        PhysicalKey::Code(KeyCode::KeyA),
+        Key::Character("a".into()),
+        Some("a".into()),
+        KeyLocation::Standard,
+        ElementState::Pressed,
+        false,
+    );
+    round_trip(vec![
+        RecordedEvent::Window {
+            time: Duration::ZERO,
+            window_id: WindowId::from_raw(1),
+            event: WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: false },
+        },
+        RecordedEvent::Device {
+            time: Duration::from_millis(8),
+            device_id: Some(DeviceId::from_raw(2)),
+            event: DeviceEvent::Button { button: 1, state: ElementState::Pressed },
+        },
+    ]);
+}